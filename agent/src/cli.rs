@@ -2,9 +2,17 @@
 //!
 //! Handles argument parsing, validation, and help text generation.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use crate::config::{OutputFormat, ScanConfig};
+use contract_kit::collectors::PathExpansion;
+use contract_kit::contracts::FieldOverride;
+use contract_kit::execution_api::ExitCodePolicy;
+
+use crate::config::{parse_criticality, MetadataSelector, OutputFormat, ScanConfig};
+use crate::output::{ColorMode, Redaction, DEFAULT_MAX_EVIDENCE_BYTES};
+use crate::registry::{check_field_override, CtnFilter, BUILTIN_STRATEGIES};
 
 /// CLI parsing result
 pub enum CliResult {
@@ -12,6 +20,22 @@ pub enum CliResult {
     Run(ScanConfig),
     /// Show help and exit
     Help,
+    /// Print the JSON Schema of an output format and exit
+    PrintSchema(OutputFormat),
+    /// Print every command collectors may run and exit, with this site
+    /// allowlist file (None falls back to ESP_COMMAND_ALLOWLIST), registry
+    /// config and CTN filter
+    PrintAllowlist {
+        command_allowlist: Option<PathBuf>,
+        registry_config: Option<PathBuf>,
+        ctn_filter: CtnFilter,
+    },
+    /// Verify the chain of a scan journal and exit
+    VerifyJournal(PathBuf),
+    /// Verify the envelope signature of a saved result
+    Verify(PathBuf),
+    /// Print a saved result, JSON or MessagePack, as JSON
+    Decode(PathBuf),
     /// Error with message
     Error(String),
 }
@@ -20,10 +44,38 @@ pub enum CliResult {
 pub fn parse_args(args: &[String]) -> CliResult {
     let program_name = args.first().map(|s| s.as_str()).unwrap_or("esp-agent");
 
-    let mut input_path: Option<&str> = None;
+    let mut input_paths: Vec<PathBuf> = Vec::new();
     let mut output_file: Option<PathBuf> = None;
     let mut quiet = false;
     let mut output_format = OutputFormat::Full;
+    let mut baseline: Option<PathBuf> = None;
+    let mut update_baseline = false;
+    let mut explain = false;
+    let mut explain_criterion: Option<String> = None;
+    let mut check = false;
+    let mut watch = false;
+    let mut command_allowlist: Option<PathBuf> = None;
+    let mut registry_config: Option<PathBuf> = None;
+    let mut ctn_filter = CtnFilter::default();
+    let mut field_map: Vec<FieldOverride> = Vec::new();
+    let mut elevate: Option<String> = None;
+    let mut print_allowlist = false;
+    let mut color = ColorMode::Auto;
+    let mut width: Option<usize> = None;
+    let mut ascii = false;
+    let mut attestation_inventory = true;
+    let mut metrics_file: Option<PathBuf> = None;
+    let mut path_expansion: Option<PathExpansion> = None;
+    let mut exit_codes = ExitCodePolicy::default();
+    let mut max_evidence_bytes = Some(DEFAULT_MAX_EVIDENCE_BYTES);
+    let mut redaction: Option<Redaction> = None;
+    let mut vars: HashMap<String, String> = HashMap::new();
+    let mut select: Vec<MetadataSelector> = Vec::new();
+    let mut journal: Option<PathBuf> = None;
+    let mut checkpoint: Option<PathBuf> = None;
+    let mut jobs: Option<usize> = None;
+    let mut timeout: Option<Duration> = None;
+    let mut fail_on = None;
 
     let mut i = 1;
     while i < args.len() {
@@ -41,24 +93,359 @@ pub fn parse_args(args: &[String]) -> CliResult {
                     None => return CliResult::Error("--output requires a filename".to_string()),
                 }
             }
-            Some("--format" | "-f") => {
+            Some("--baseline") => {
                 i += 1;
-                match args.get(i).map(|s| s.as_str()) {
-                    Some("full") => output_format = OutputFormat::Full,
-                    Some("summary") => output_format = OutputFormat::Summary,
-                    Some("attestation") => output_format = OutputFormat::Attestation,
-                    Some("assessor") => output_format = OutputFormat::Assessor,
-                    Some(other) => {
+                match args.get(i) {
+                    Some(val) => baseline = Some(PathBuf::from(val)),
+                    None => return CliResult::Error("--baseline requires a filename".to_string()),
+                }
+            }
+            Some("--update-baseline") => {
+                update_baseline = true;
+            }
+            Some("--explain") => {
+                explain = true;
+            }
+            Some("--explain-criterion") => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) => {
+                        explain = true;
+                        explain_criterion = Some(val.clone());
+                    }
+                    None => {
+                        return CliResult::Error(
+                            "--explain-criterion requires a criterion ID".to_string(),
+                        )
+                    }
+                }
+            }
+            Some("--check") => {
+                check = true;
+            }
+            Some("--watch") => {
+                watch = true;
+            }
+            Some("--command-allowlist") => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) => command_allowlist = Some(PathBuf::from(val)),
+                    None => {
+                        return CliResult::Error(
+                            "--command-allowlist requires a filename".to_string(),
+                        )
+                    }
+                }
+            }
+            Some("--registry-config") => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) => registry_config = Some(PathBuf::from(val)),
+                    None => {
+                        return CliResult::Error(
+                            "--registry-config requires a filename".to_string(),
+                        )
+                    }
+                }
+            }
+            Some(flag @ ("--allow-ctn" | "--deny-ctn")) => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) if BUILTIN_STRATEGIES.contains(&val.as_str()) => {
+                        if flag == "--allow-ctn" {
+                            ctn_filter.allow.push(val.clone());
+                        } else {
+                            ctn_filter.deny.push(val.clone());
+                        }
+                    }
+                    Some(val) => {
                         return CliResult::Error(format!(
-                            "Unknown format '{}'. Use: full, summary, attestation, assessor",
-                            other
+                            "Unknown CTN type '{}' for {} (built-in: {})",
+                            val,
+                            flag,
+                            BUILTIN_STRATEGIES.join(", ")
                         ));
                     }
+                    None => return CliResult::Error(format!("{} requires a CTN type", flag)),
+                }
+            }
+            Some("--field-map") => {
+                i += 1;
+                let Some(val) = args.get(i) else {
+                    return CliResult::Error(
+                        "--field-map requires ctn_type:state_field=data_field".to_string(),
+                    );
+                };
+                let field_override = match FieldOverride::parse(val) {
+                    Ok(field_override) => field_override,
+                    Err(e) => return CliResult::Error(format!("--field-map: {}", e)),
+                };
+                if let Err(e) = check_field_override(&field_override) {
+                    return CliResult::Error(format!("--field-map {}: {}", val, e));
+                }
+                field_map.push(field_override);
+            }
+            Some("--elevate") => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) => elevate = Some(val.clone()),
+                    None => return CliResult::Error("--elevate requires a program".to_string()),
+                }
+            }
+            Some("--print-allowlist") => {
+                print_allowlist = true;
+            }
+            Some("--color") => {
+                i += 1;
+                match args.get(i).map(|s| s.as_str()) {
+                    Some(val) => match ColorMode::parse(val) {
+                        Some(mode) => color = mode,
+                        None => {
+                            return CliResult::Error(format!(
+                                "Unknown color mode '{}'. Use: auto, always, never",
+                                val
+                            ));
+                        }
+                    },
+                    None => return CliResult::Error("--color requires a value".to_string()),
+                }
+            }
+            Some("--no-color") => {
+                color = ColorMode::Never;
+            }
+            Some("--ascii") => {
+                ascii = true;
+            }
+            Some("--width") => {
+                i += 1;
+                match args.get(i).map(|s| s.parse::<usize>()) {
+                    Some(Ok(val)) if val > 0 => width = Some(val),
+                    Some(_) => {
+                        return CliResult::Error(
+                            "--width requires a positive number of columns".to_string(),
+                        );
+                    }
+                    None => return CliResult::Error("--width requires a value".to_string()),
+                }
+            }
+            Some("--jobs" | "-j") => {
+                i += 1;
+                match args.get(i).map(|s| s.parse::<usize>()) {
+                    Some(Ok(val)) if val > 0 => jobs = Some(val),
+                    Some(_) => {
+                        return CliResult::Error(
+                            "--jobs requires a positive number of policies".to_string(),
+                        );
+                    }
+                    None => return CliResult::Error("--jobs requires a value".to_string()),
+                }
+            }
+            Some("--timeout") => {
+                i += 1;
+                match args.get(i).map(|s| s.parse::<u64>()) {
+                    Some(Ok(val)) if val > 0 => timeout = Some(Duration::from_secs(val)),
+                    Some(_) => {
+                        return CliResult::Error(
+                            "--timeout requires a positive number of seconds".to_string(),
+                        );
+                    }
+                    None => return CliResult::Error("--timeout requires a value".to_string()),
+                }
+            }
+            Some("--fail-on") => {
+                i += 1;
+                match args.get(i).map(|s| s.as_str()) {
+                    Some(val) => match parse_criticality(val) {
+                        Some(criticality) => fail_on = Some(criticality),
+                        None => {
+                            return CliResult::Error(format!(
+                                "Unknown severity '{}'. Use: critical, high, medium, low, info",
+                                val
+                            ));
+                        }
+                    },
+                    None => return CliResult::Error("--fail-on requires a severity".to_string()),
+                }
+            }
+            Some("--no-attestation-inventory") => {
+                attestation_inventory = false;
+            }
+            Some("--expand-path-env") => {
+                path_expansion.get_or_insert_with(PathExpansion::new);
+            }
+            Some("--path-env-allow") => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) => {
+                        let expansion = path_expansion.take().unwrap_or_default();
+                        path_expansion = Some(expansion.allow(val.as_str()));
+                    }
+                    None => {
+                        return CliResult::Error(
+                            "--path-env-allow requires a variable name".to_string(),
+                        )
+                    }
+                }
+            }
+            Some("--exit-code-map") => {
+                i += 1;
+                match args.get(i).map(|s| s.parse::<ExitCodePolicy>()) {
+                    Some(Ok(policy)) => exit_codes = policy,
+                    Some(Err(e)) => return CliResult::Error(format!("--exit-code-map: {}", e)),
+                    None => return CliResult::Error("--exit-code-map requires a map".to_string()),
+                }
+            }
+            Some("--max-evidence-bytes") => {
+                i += 1;
+                match args.get(i).map(|s| s.parse::<usize>()) {
+                    Some(Ok(0)) => max_evidence_bytes = None,
+                    Some(Ok(val)) => max_evidence_bytes = Some(val),
+                    Some(Err(_)) => {
+                        return CliResult::Error(
+                            "--max-evidence-bytes requires a number of bytes".to_string(),
+                        );
+                    }
+                    None => {
+                        return CliResult::Error(
+                            "--max-evidence-bytes requires a value".to_string(),
+                        )
+                    }
+                }
+            }
+            Some("--redact") => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) => match redaction
+                        .take()
+                        .unwrap_or_else(Redaction::new)
+                        .with_pattern(val)
+                    {
+                        Ok(updated) => redaction = Some(updated),
+                        Err(e) => {
+                            return CliResult::Error(format!(
+                                "--redact: invalid pattern '{}': {}",
+                                val, e
+                            ));
+                        }
+                    },
+                    None => return CliResult::Error("--redact requires a pattern".to_string()),
+                }
+            }
+            Some("--redact-defaults") => {
+                redaction = Some(
+                    redaction
+                        .take()
+                        .unwrap_or_else(Redaction::new)
+                        .with_defaults(),
+                );
+            }
+            Some("--var") => {
+                i += 1;
+                match args.get(i).map(|s| s.split_once('=')) {
+                    Some(Some((name, value))) if !name.trim().is_empty() => {
+                        vars.insert(name.trim().to_string(), value.to_string());
+                    }
+                    Some(_) => {
+                        return CliResult::Error(
+                            "--var requires name=value, e.g. --var ntp_server=time.example.com"
+                                .to_string(),
+                        );
+                    }
+                    None => return CliResult::Error("--var requires name=value".to_string()),
+                }
+            }
+            Some("--select") => {
+                i += 1;
+                match args.get(i).map(|s| MetadataSelector::parse(s)) {
+                    Some(Some(selector)) => select.push(selector),
+                    Some(None) => {
+                        return CliResult::Error(
+                            "--select requires key=value, e.g. --select platform=linux".to_string(),
+                        );
+                    }
+                    None => return CliResult::Error("--select requires key=value".to_string()),
+                }
+            }
+            Some("--metrics") => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) => metrics_file = Some(PathBuf::from(val)),
+                    None => return CliResult::Error("--metrics requires a filename".to_string()),
+                }
+            }
+            Some("--journal") => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) => journal = Some(PathBuf::from(val)),
+                    None => return CliResult::Error("--journal requires a filename".to_string()),
+                }
+            }
+            Some("--checkpoint") => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) => checkpoint = Some(PathBuf::from(val)),
+                    None => {
+                        return CliResult::Error("--checkpoint requires a filename".to_string())
+                    }
+                }
+            }
+            Some("--verify-journal") => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) => return CliResult::VerifyJournal(PathBuf::from(val)),
+                    None => {
+                        return CliResult::Error("--verify-journal requires a filename".to_string())
+                    }
+                }
+            }
+            Some("--verify") => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) => return CliResult::Verify(PathBuf::from(val)),
+                    None => return CliResult::Error("--verify requires a filename".to_string()),
+                }
+            }
+            Some("--decode") => {
+                i += 1;
+                match args.get(i) {
+                    Some(val) => return CliResult::Decode(PathBuf::from(val)),
+                    None => return CliResult::Error("--decode requires a filename".to_string()),
+                }
+            }
+            Some("--print-schema") => {
+                i += 1;
+                match args.get(i).map(|s| s.as_str()) {
+                    Some(val) => match OutputFormat::parse(val) {
+                        Some(format) => return CliResult::PrintSchema(format),
+                        None => {
+                            return CliResult::Error(format!(
+                                "Unknown format '{}'. Use: full, summary, ndjson, attestation, assessor, oscal, csv, msgpack",
+                                val
+                            ));
+                        }
+                    },
+                    None => {
+                        return CliResult::Error("--print-schema requires a format".to_string())
+                    }
+                }
+            }
+            Some("--format" | "-f") => {
+                i += 1;
+                match args.get(i).map(|s| s.as_str()) {
+                    Some(val) => match OutputFormat::parse(val) {
+                        Some(format) => output_format = format,
+                        None => {
+                            return CliResult::Error(format!(
+                                "Unknown format '{}'. Use: full, summary, ndjson, attestation, assessor, oscal, csv, msgpack",
+                                val
+                            ));
+                        }
+                    },
                     None => return CliResult::Error("--format requires a value".to_string()),
                 }
             }
             Some(arg) if !arg.starts_with('-') => {
-                input_path = Some(arg);
+                input_paths.push(PathBuf::from(arg));
             }
             Some(arg) => {
                 return CliResult::Error(format!("Unknown option: {}", arg));
@@ -68,33 +455,205 @@ pub fn parse_args(args: &[String]) -> CliResult {
         i += 1;
     }
 
-    // Validate input path
-    let input_path = match input_path {
-        Some(p) => PathBuf::from(p),
-        None => {
+    // The allowlist needs no policies, only --command-allowlist,
+    // --registry-config and the CTN filter
+    if print_allowlist {
+        return CliResult::PrintAllowlist {
+            command_allowlist,
+            registry_config,
+            ctn_filter,
+        };
+    }
+
+    // Validate input paths
+    if input_paths.is_empty() {
+        return CliResult::Error(format!(
+            "Missing input path\nUsage: {} [OPTIONS] <file.esp|directory>...",
+            program_name
+        ));
+    }
+
+    if let Some(missing) = input_paths.iter().find(|p| !p.exists()) {
+        return CliResult::Error(format!("Path not found: {}", missing.display()));
+    }
+
+    // Validate baseline options
+    match &baseline {
+        None if update_baseline => {
+            return CliResult::Error("--update-baseline requires --baseline <file>".to_string());
+        }
+        Some(path) if !update_baseline && !path.exists() => {
             return CliResult::Error(format!(
-                "Missing input path\nUsage: {} [OPTIONS] <file.esp|directory>",
-                program_name
+                "Baseline not found: {} (use --update-baseline to create it)",
+                path.display()
             ));
         }
+        _ => {}
+    }
+
+    let explain_flag = if explain_criterion.is_some() {
+        "--explain-criterion"
+    } else {
+        "--explain"
     };
 
-    if !input_path.exists() {
-        return CliResult::Error(format!("Path not found: {}", input_path.display()));
+    // Watch prints fresh console results on every change and writes no files
+    if watch {
+        let conflict = if output_file.is_some() {
+            Some("--output")
+        } else if baseline.is_some() {
+            Some("--baseline")
+        } else if metrics_file.is_some() {
+            Some("--metrics")
+        } else if journal.is_some() {
+            Some("--journal")
+        } else if explain {
+            Some(explain_flag)
+        } else if check {
+            Some("--check")
+        } else if quiet {
+            Some("--quiet")
+        } else if output_format == OutputFormat::Ndjson {
+            Some("--format ndjson")
+        } else if timeout.is_some() {
+            Some("--timeout")
+        } else {
+            None
+        };
+        if let Some(flag) = conflict {
+            return CliResult::Error(format!("--watch cannot be combined with {}", flag));
+        }
+    }
+
+    // A checkpoint resumes the NDJSON file it was written alongside
+    if checkpoint.is_some() {
+        if output_format != OutputFormat::Ndjson || output_file.is_none() {
+            return CliResult::Error(
+                "--checkpoint requires --format ndjson and --output <file>".to_string(),
+            );
+        }
+        let conflict = if baseline.is_some() {
+            Some("--baseline")
+        } else if metrics_file.is_some() {
+            Some("--metrics")
+        } else if journal.is_some() {
+            Some("--journal")
+        } else if explain {
+            Some(explain_flag)
+        } else if check {
+            Some("--check")
+        } else {
+            None
+        };
+        if let Some(flag) = conflict {
+            return CliResult::Error(format!("--checkpoint cannot be combined with {}", flag));
+        }
+    }
+
+    // NDJSON without --output goes to stdout, so keep the console off it
+    if output_format == OutputFormat::Ndjson && output_file.is_none() {
+        if baseline.is_some() {
+            return CliResult::Error(
+                "--format ndjson without --output cannot be combined with --baseline".to_string(),
+            );
+        }
+        quiet = true;
+    }
+
+    // Only full results are redacted; refuse rather than leak evidence
+    if redaction.is_some() && matches!(output_format, OutputFormat::Assessor | OutputFormat::Oscal)
+    {
+        return CliResult::Error(format!(
+            "--redact applies to the full format only; {} output embeds evidence unredacted",
+            output_format
+        ));
+    }
+
+    // Explain collects nothing, so there is no result to compare
+    if explain && baseline.is_some() {
+        return CliResult::Error(format!(
+            "{} cannot be combined with --baseline",
+            explain_flag
+        ));
+    }
+    if explain && metrics_file.is_some() {
+        return CliResult::Error(format!(
+            "{} cannot be combined with --metrics",
+            explain_flag
+        ));
+    }
+    if explain && journal.is_some() {
+        return CliResult::Error(format!(
+            "{} cannot be combined with --journal",
+            explain_flag
+        ));
+    }
+    // A criterion trace is printed to the console only
+    if explain_criterion.is_some() && output_file.is_some() {
+        return CliResult::Error(
+            "--explain-criterion cannot be combined with --output".to_string(),
+        );
+    }
+
+    // Check only reads policies, so it produces no result to save or compare
+    if check {
+        let conflict = if explain {
+            Some(explain_flag)
+        } else if baseline.is_some() {
+            Some("--baseline")
+        } else if metrics_file.is_some() {
+            Some("--metrics")
+        } else if output_file.is_some() {
+            Some("--output")
+        } else if journal.is_some() {
+            Some("--journal")
+        } else {
+            None
+        };
+        if let Some(flag) = conflict {
+            return CliResult::Error(format!("--check cannot be combined with {}", flag));
+        }
     }
 
     CliResult::Run(ScanConfig {
-        input_path,
+        input_paths,
         output_file,
         output_format,
         quiet,
+        baseline,
+        update_baseline,
+        explain,
+        explain_criterion,
+        check,
+        watch,
+        command_allowlist,
+        registry_config,
+        ctn_filter,
+        field_map,
+        elevate,
+        color,
+        width,
+        ascii,
+        attestation_inventory,
+        metrics_file,
+        path_expansion,
+        exit_codes,
+        max_evidence_bytes,
+        redaction,
+        vars,
+        select,
+        journal,
+        checkpoint,
+        jobs,
+        timeout,
+        fail_on,
     })
 }
 
 /// Print usage information
 #[allow(dead_code)]
 pub fn print_usage(program_name: &str) {
-    eprintln!("Usage: {} [OPTIONS] <file.esp|directory>", program_name);
+    eprintln!("Usage: {} [OPTIONS] <file.esp|directory>...", program_name);
     eprintln!("       {} --help", program_name);
 }
 
@@ -112,6 +671,10 @@ pub fn print_help(program_name: &str) {
         "    {} [OPTIONS] <directory>      Scan all ESP files in directory",
         program_name
     );
+    println!(
+        "    {} [OPTIONS] <path>...        Scan files and directories as one set",
+        program_name
+    );
     println!(
         "    {} --help                     Show this help message\n",
         program_name
@@ -121,26 +684,182 @@ pub fn print_help(program_name: &str) {
     println!("    -h, --help                  Show this help message");
     println!("    -q, --quiet                 Suppress console output");
     println!("    -o, --output <file>         Write results to JSON file (optional)");
-    println!("    -f, --format <format>       Output format: full (default), summary, attestation, assessor");
+    println!("    -f, --format <format>       Output format: full (default), summary, ndjson, attestation, assessor, oscal, csv, msgpack");
+    println!(
+        "        --baseline <file>       Compare failing criteria against a saved full result"
+    );
+    println!(
+        "        --update-baseline       Write the current full result to the --baseline file"
+    );
+    println!(
+        "        --explain               List files and commands each policy would collect, without collecting"
+    );
+    println!(
+        "        --explain-criterion <id>  Scan and print only the decision trace of criterion <id> (e.g. file_metadata[shadow])"
+    );
+    println!(
+        "        --watch                 Scan again whenever an input policy changes, until Ctrl-C"
+    );
+    println!(
+        "        --command-allowlist <file>  Allow extra site commands (default: $ESP_COMMAND_ALLOWLIST)"
+    );
+    println!(
+        "        --registry-config <file>  Register only the built-in strategies a JSON file names"
+    );
+    println!("        --allow-ctn <type>      Register only these CTN types (repeatable)");
+    println!("        --deny-ctn <type>       Never register this CTN type (repeatable)");
+    println!(
+        "        --field-map <ctn:state=data>  Check a state field against another collected field (repeatable)"
+    );
+    println!(
+        "        --elevate <prog>        Run collector commands through an allowlisted wrapper (default: $ESP_ELEVATE_CMD)"
+    );
+    println!(
+        "        --color <when>          Colorize console output: auto (default), always, never"
+    );
+    println!("        --no-color              Same as --color never");
+    println!(
+        "        --width <columns>       Console width (default: terminal width, $COLUMNS, or 80)"
+    );
+    println!("        --ascii                 Draw boxes and symbols with ASCII characters");
+    println!("        --no-attestation-inventory  Leave host inventory out of attestations");
+    println!(
+        "        --metrics <file>        Write Prometheus metrics (textfile collector format)"
+    );
+    println!(
+        "        --expand-path-env       Expand ${{HOME}}, %ProgramData%, %SystemRoot% in file paths"
+    );
+    println!(
+        "        --path-env-allow <var>  Also expand <var> in file paths (repeatable; implies --expand-path-env)"
+    );
+    println!(
+        "        --exit-code-map <map>   Exit codes for outcomes, e.g. non-pass=1 (see EXIT CODES)"
+    );
+    println!(
+        "        --fail-on <severity>    Only fail on policies at or above <severity>: critical, high, medium, low, info"
+    );
+    println!(
+        "        --max-evidence-bytes <n>  Replace larger evidence values with a hashed placeholder (default: 1048576; 0 = no limit)"
+    );
+    println!(
+        "        --redact <pattern>      Replace regex matches in full result evidence with [REDACTED] (repeatable)"
+    );
+    println!(
+        "        --redact-defaults       Also redact PEM blocks, password= values and AWS keys"
+    );
+    println!("        --var <name=value>      Set a policy VAR for this scan (repeatable)");
+    println!(
+        "        --select <key=value>    Only scan policies whose META field matches (repeatable)"
+    );
+    println!(
+        "    -j, --jobs <n>              Scan <n> policies at once (default: number of logical CPUs)"
+    );
+    println!(
+        "        --timeout <seconds>     Stop the scan after <seconds>, reporting unfinished policies as errors"
+    );
+    println!(
+        "        --journal <file>        Append a signed, hash-chained record of the run to <file>"
+    );
+    println!(
+        "        --checkpoint <file>     Record completed policies in <file>; rerun to resume (ndjson only)"
+    );
+    println!("        --verify-journal <file> Verify a scan journal's chain and exit");
+    println!("        --verify <file>         Verify the signature of a saved result and exit");
+    println!("        --decode <file>         Print a saved result (JSON or MessagePack) as JSON and exit");
+    println!("        --print-schema <format> Print the JSON Schema of an output format and exit");
+    println!("        --print-allowlist       Print every command collectors may run and exit");
     println!();
 
     println!("OUTPUT FORMATS:");
     println!("    full          Complete results with findings and evidence (default)");
     println!("    summary       Minimal output with pass/fail counts only");
+    println!("    ndjson        One summary line per policy as it finishes, then the totals");
     println!("    attestation   CUI-free format safe for network transport");
     println!("    assessor      Full package with reproducibility info for assessors");
+    println!("    oscal         NIST OSCAL assessment results for GRC platforms");
+    println!("    csv           One row per policy with counts and controls, for spreadsheets");
+    println!("    msgpack       Full results encoded as MessagePack for constrained links");
     println!();
 
     println!("BEHAVIOR:");
     println!("    Results are always printed to the console (unless --quiet is set).");
-    println!("    Use --output to additionally save results to a JSON file.");
-    println!("    All formats produce a single envelope containing all scanned policies.");
+    println!("    Use --output to additionally save results to a file (CSV with --format csv).");
+    println!("    Other formats produce a single envelope containing all scanned policies;");
+    println!("    ndjson streams a line per policy to --output, or to stdout without it.");
+    println!("    With several paths, a file reached through more than one is scanned once.");
+    println!("    With --baseline, a JSON drift report is printed after the scan.");
+    println!("    With --explain, nothing is read or executed; --output saves the plan as JSON.");
+    println!("    With --explain-criterion, the policies are scanned and only the trace of the");
+    println!("    criterion is printed: objects collected, expected vs actual per field, and");
+    println!("    how the existence, state and item checks combined. The ID is a CTN type, or");
+    println!("    ctn_type[object, ...] as listed in the timings.");
+    println!("    With --check, policies are only checked against the collector contracts;");
+    println!("    unknown fields, disallowed operations and missing object fields are warnings.");
+    println!("    With --watch, results are printed to the console after every change; no file");
+    println!("    is written, so --output, --baseline, --metrics and --journal are refused.");
+    println!("    With --metrics, posture gauges are written alongside any --output file.");
+    println!("    With --journal, each run's content and evidence hashes are chained to the");
+    println!("    previous entry; --verify-journal reports the first broken link.");
+    println!("    With --checkpoint, an interrupted ndjson scan rerun with the same arguments");
+    println!("    appends only the policies not yet scanned, or changed since, to --output;");
+    println!("    the checkpoint is removed once the scan completes.");
+    println!("    With --print-allowlist, the site allowlist is loaded as for a scan and every");
+    println!("    command collectors may run is printed, one per line.");
+    println!("    With --registry-config, CTN types the file leaves out are not registered; a");
+    println!("    policy using one is reported as an error naming the denied type.");
+    println!("    k8s_resource is only registered when a registry config names it.");
+    println!("    With --allow-ctn or --deny-ctn, the strategies otherwise registered are");
+    println!("    narrowed (a denied type wins over an allowed one); a policy using a type left");
+    println!("    out is reported with phase denied and nothing is collected for it.");
+    println!("    With --field-map, only the named CTN type's contract changes; the state field");
+    println!("    must be one its contract defines. Each override is logged at startup.");
+    println!("    With --elevate, the wrapper must be on the site allowlist; the audit, firewall");
+    println!("    and kernel module commands run through it and are recorded with it.");
+    println!("    With --verify, the signature is checked against the envelope's content and");
+    println!("    evidence hashes; the hashes are not recomputed from the results. MessagePack");
+    println!("    results are verified the same way; --decode prints one as JSON.");
+    println!(
+        "    With --jobs, results are reported in input order whatever order policies finish;"
+    );
+    println!("    --jobs 1 scans one policy at a time.");
+    println!("    With --timeout, policies still scanning at the deadline are reported as timed");
+    println!("    out and the rest as not scanned; results so far are still written. On Linux,");
+    println!("    collector commands still running (kubectl, auditctl, ...) are killed.");
+    println!("    With --fail-on, failing policies below the severity are still reported but");
+    println!("    do not change the exit code.");
+    println!("    With --redact, full results and baselines no longer match their evidence_hash;");
+    println!("    the signature still verifies. Findings are not redacted.");
+    println!("    With --var, every scanned policy must declare the variable; a policy that");
+    println!("    does not is reported as an execution error.");
+    println!("    With --select, every condition must match; list fields such as tags match any");
+    println!("    item. Policies that do not compile are scanned anyway and reported as errors.");
+    println!("    With --expand-path-env, a file path naming a variable that is not allowed or");
+    println!("    not set is a collection error; paths are otherwise used as written.");
+    println!("    Signed formats include a host inventory (OS, kernel, hostname, IP, boot time).");
+    println!("    With --color auto, color is disabled when NO_COLOR is set or stdout is not a terminal.");
     println!();
 
     println!("EXIT CODES:");
-    println!("    0    All policies passed");
-    println!("    1    One or more policies failed");
-    println!("    2    Execution error");
+    println!("    0    All policies passed, or no policies found (an empty directory included)");
+    println!("    1    One or more policies failed (at or above --fail-on, if given)");
+    println!("    2    Usage error: invalid arguments, missing input path, invalid allowlist");
+    println!("         or elevation wrapper");
+    println!("    3    Scan error: no policy could be scanned (compilation or resolution failed)");
+    println!("    4    Partial scan: some policies could not be scanned, others were");
+    println!("    5    Failing criteria differ from baseline (--baseline only)");
+    println!("    A scan stopped by --timeout exits 3, whatever --exit-code-map says.");
+    println!("    Scan and partial errors take precedence over policy failures.");
+    println!("    With --explain-criterion: 0 if every traced criterion passed, 1 if one did");
+    println!("    not, 2 if no criterion matches.");
+    println!("    With --verify-journal: 0 if the chain is intact, 1 if broken, 2 if unreadable.");
+    println!("    With --verify: 0 if the signature is valid, 1 if invalid or unsigned,");
+    println!("    2 if the file is unreadable or its signature malformed.");
+    println!("    With --decode: 0 if the result was printed, 2 if unreadable or not a result.");
+    println!();
+    println!("    --exit-code-map takes comma-separated key=code pairs (codes 0-255):");
+    println!("    fail=<n>, error=<n>, partial=<n>, non-pass=<n> (all three),");
+    println!("    empty=<n> (no policies found). The default is fail=1,error=3,partial=4,empty=0.");
+    println!("    Usage errors always exit 2.");
     println!();
 
     println!("EXAMPLES:");
@@ -160,4 +879,100 @@ pub fn print_help(program_name: &str) {
         "    {} --quiet -o results.json /path/to/policies/  # File only, no console",
         program_name
     );
+    println!(
+        "    {} --baseline approved.json /path/to/policies/ # Fail on drift",
+        program_name
+    );
+    println!(
+        "    {} --explain policy.esp                        # Review before running",
+        program_name
+    );
+    println!(
+        "    {} --explain-criterion process policy.esp      # Why a check failed",
+        program_name
+    );
+    println!(
+        "    {} --check /path/to/policies/                  # Lint policies in CI",
+        program_name
+    );
+    println!(
+        "    {} -q --metrics /var/lib/node_exporter/esp.prom /path/to/policies/",
+        program_name
+    );
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> CliResult {
+        let args: Vec<String> = ["esp_agent"]
+            .iter()
+            .chain(args)
+            .map(|arg| arg.to_string())
+            .collect();
+        parse_args(&args)
+    }
+
+    fn scan_config(args: &[&str]) -> ScanConfig {
+        match parse(args) {
+            CliResult::Run(config) => config,
+            _ => panic!("expected a scan for {:?}", args),
+        }
+    }
+
+    #[test]
+    fn test_explain_takes_no_criterion() {
+        // A policy directory named like a CTN type
+        let dir = std::env::temp_dir()
+            .join(format!("esp_cli_{}", std::process::id()))
+            .join("process");
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.to_str().unwrap();
+
+        let config = scan_config(&["--explain", input]);
+        let traced = scan_config(&["--explain-criterion", "process", input]);
+        let custom = scan_config(&["--explain-criterion", "site_check[motd]", input]);
+        let _ = std::fs::remove_dir_all(dir.parent().unwrap());
+
+        assert!(config.explain);
+        assert_eq!(config.explain_criterion, None);
+        assert_eq!(config.input_paths, vec![dir.clone()]);
+
+        assert!(traced.explain);
+        assert_eq!(traced.explain_criterion.as_deref(), Some("process"));
+        assert_eq!(traced.input_paths, vec![dir]);
+
+        // Criteria of strategies outside the built-in set can be traced too
+        assert_eq!(
+            custom.explain_criterion.as_deref(),
+            Some("site_check[motd]")
+        );
+
+        // The argument after --explain is always an input path
+        match parse(&["--explain", "mount"]) {
+            CliResult::Error(message) => assert_eq!(message, "Path not found: mount"),
+            _ => panic!("expected mount to be read as an input path"),
+        }
+    }
+
+    #[test]
+    fn test_explain_criterion_requires_an_id() {
+        match parse(&["--explain-criterion"]) {
+            CliResult::Error(message) => {
+                assert_eq!(message, "--explain-criterion requires a criterion ID")
+            }
+            _ => panic!("expected a usage error"),
+        }
+    }
 }
@@ -2,50 +2,166 @@
 //!
 //! Defines the configuration structures used throughout the agent.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
+
+use common::results::Criticality;
+use contract_kit::collectors::PathExpansion;
+use contract_kit::contracts::FieldOverride;
+use contract_kit::execution_api::{ExitCodePolicy, MetaDataBlock, ScanCounts};
+use contract_kit::executors::EvidenceLevel;
+
+use crate::output::{criticality_index, ColorMode, EvidenceOptions, Redaction};
+use crate::registry::CtnFilter;
 
 /// Output format for scan results
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutputFormat {
     /// Summary only (minimal JSON)
     Summary,
+    /// One summary line per policy, streamed as the scan runs
+    Ndjson,
     /// Full results with findings and evidence
     Full,
     /// Attestation format (CUI-free)
     Attestation,
     /// Assessor package with full reproducibility info
     Assessor,
+    /// NIST OSCAL assessment results
+    Oscal,
+    /// One CSV row per policy, for spreadsheets
+    Csv,
+    /// Full results encoded as MessagePack
+    MsgPack,
 }
 
 impl OutputFormat {
+    /// Parse a `--format` value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "full" => Some(OutputFormat::Full),
+            "summary" => Some(OutputFormat::Summary),
+            "ndjson" => Some(OutputFormat::Ndjson),
+            "attestation" => Some(OutputFormat::Attestation),
+            "assessor" => Some(OutputFormat::Assessor),
+            "oscal" => Some(OutputFormat::Oscal),
+            "csv" => Some(OutputFormat::Csv),
+            "msgpack" => Some(OutputFormat::MsgPack),
+            _ => None,
+        }
+    }
+
     /// Get the default output filename for this format
     #[allow(dead_code)]
     pub fn default_filename(&self) -> &'static str {
         match self {
             OutputFormat::Summary => "summary.json",
+            OutputFormat::Ndjson => "results.ndjson",
             OutputFormat::Full => "results.json",
             OutputFormat::Attestation => "attestation.json",
             OutputFormat::Assessor => "assessor_package.json",
+            OutputFormat::Oscal => "assessment_results.json",
+            OutputFormat::Csv => "summary.csv",
+            OutputFormat::MsgPack => "results.mp",
         }
     }
+
+    /// Whether this format carries a signed result envelope
+    pub fn is_signed(&self) -> bool {
+        !matches!(
+            self,
+            OutputFormat::Summary | OutputFormat::Ndjson | OutputFormat::Csv
+        )
+    }
 }
 
 impl std::fmt::Display for OutputFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             OutputFormat::Summary => write!(f, "summary"),
+            OutputFormat::Ndjson => write!(f, "ndjson"),
             OutputFormat::Full => write!(f, "full"),
             OutputFormat::Attestation => write!(f, "attestation"),
             OutputFormat::Assessor => write!(f, "assessor"),
+            OutputFormat::Oscal => write!(f, "oscal"),
+            OutputFormat::Csv => write!(f, "csv"),
+            OutputFormat::MsgPack => write!(f, "msgpack"),
         }
     }
 }
 
+/// Parse a `--fail-on` severity
+pub fn parse_criticality(value: &str) -> Option<Criticality> {
+    match value {
+        "critical" => Some(Criticality::Critical),
+        "high" => Some(Criticality::High),
+        "medium" => Some(Criticality::Medium),
+        "low" => Some(Criticality::Low),
+        "info" => Some(Criticality::Info),
+        _ => None,
+    }
+}
+
+/// A `--select key=value` condition on policy metadata
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataSelector {
+    /// META field name, e.g. `platform`
+    pub key: String,
+    /// Value the field must have, or contain as a comma-separated item
+    pub value: String,
+}
+
+impl MetadataSelector {
+    /// Parse a `--select` value
+    pub fn parse(value: &str) -> Option<Self> {
+        let (key, value) = value.split_once('=')?;
+        let (key, value) = (key.trim(), value.trim());
+        if key.is_empty() || value.is_empty() {
+            return None;
+        }
+        Some(Self {
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    /// Whether a policy's metadata satisfies this condition
+    ///
+    /// List fields such as `tags` and `control_mapping` match on any item,
+    /// and a `framework:control` item also matches on its framework, so
+    /// `control_mapping=CIS` selects every policy mapped to a CIS control.
+    pub fn matches(&self, metadata: &MetaDataBlock) -> bool {
+        let Some(field) = metadata.fields.get(&self.key) else {
+            return false;
+        };
+        field.trim() == self.value
+            || field.split(',').map(str::trim).any(|item| {
+                item == self.value
+                    || item
+                        .split_once(':')
+                        .is_some_and(|(framework, _)| framework == self.value)
+            })
+    }
+}
+
+impl std::fmt::Display for MetadataSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+
+/// Whether a failing policy of `criticality` counts toward the failure exit
+/// code under a `--fail-on` threshold (every policy counts without one)
+pub fn meets_fail_threshold(criticality: Criticality, threshold: Option<Criticality>) -> bool {
+    threshold.is_none_or(|threshold| criticality_index(criticality) <= criticality_index(threshold))
+}
+
 /// Configuration for a scan run
 #[derive(Debug, Clone)]
 pub struct ScanConfig {
-    /// Input path (file or directory)
-    pub input_path: PathBuf,
+    /// Input paths (files or directories), scanned as one set
+    pub input_paths: Vec<PathBuf>,
 
     /// Output file path (None means console-only output)
     pub output_file: Option<PathBuf>,
@@ -55,6 +171,126 @@ pub struct ScanConfig {
 
     /// Suppress progress output
     pub quiet: bool,
+
+    /// Baseline full result to compare against (None means no comparison)
+    pub baseline: Option<PathBuf>,
+
+    /// Write the current result as the new baseline instead of comparing
+    pub update_baseline: bool,
+
+    /// Print what would be collected instead of scanning
+    pub explain: bool,
+
+    /// With `explain`, scan and print the decision trace of this criterion
+    /// (`ctn_type[object, ...]` or a CTN type) instead of the plan
+    pub explain_criterion: Option<String>,
+
+    /// Check policies against collector contracts instead of scanning
+    pub check: bool,
+
+    /// Scan again whenever an input policy changes, until Ctrl-C
+    pub watch: bool,
+
+    /// Site command allowlist file (None falls back to ESP_COMMAND_ALLOWLIST)
+    pub command_allowlist: Option<PathBuf>,
+
+    /// JSON file naming the built-in strategies to register (None registers
+    /// the defaults)
+    pub registry_config: Option<PathBuf>,
+
+    /// CTN types from `--allow-ctn` and `--deny-ctn`, narrowing the
+    /// strategies registered
+    pub ctn_filter: CtnFilter,
+
+    /// Field mapping overrides from `--field-map`, each applied to its CTN
+    /// type's contract
+    pub field_map: Vec<FieldOverride>,
+
+    /// Wrapper to run collector commands through (None falls back to
+    /// ESP_ELEVATE_CMD)
+    pub elevate: Option<String>,
+
+    /// When to color console output
+    pub color: ColorMode,
+
+    /// Console width in columns (None detects from the terminal)
+    pub width: Option<usize>,
+
+    /// Draw boxes and symbols with ASCII instead of Unicode
+    pub ascii: bool,
+
+    /// Include the host inventory in attestations (always included in
+    /// full results and assessor packages)
+    pub attestation_inventory: bool,
+
+    /// Prometheus metrics file (None means no metrics)
+    pub metrics_file: Option<PathBuf>,
+
+    /// Environment variables expandable in file paths (None disables expansion)
+    pub path_expansion: Option<PathExpansion>,
+
+    /// Exit codes for failed, errored and partial scans
+    pub exit_codes: ExitCodePolicy,
+
+    /// Largest evidence value embedded in output (None embeds everything)
+    pub max_evidence_bytes: Option<usize>,
+
+    /// Patterns redacted from evidence in full results (None redacts
+    /// nothing)
+    pub redaction: Option<Redaction>,
+
+    /// Values bound to policy `VAR`s at scan time (`--var name=value`)
+    pub vars: HashMap<String, String>,
+
+    /// Metadata conditions a policy must all meet to be scanned (empty
+    /// scans every policy)
+    pub select: Vec<MetadataSelector>,
+
+    /// Scan journal to append each run to (None means no journal)
+    pub journal: Option<PathBuf>,
+
+    /// Checkpoint of completed policies, to resume an interrupted NDJSON
+    /// scan from (None means no checkpoint)
+    pub checkpoint: Option<PathBuf>,
+
+    /// Policies scanned at once (None uses the number of logical CPUs)
+    pub jobs: Option<usize>,
+
+    /// Longest the scan may run before unfinished policies are abandoned
+    /// (None waits for every policy)
+    pub timeout: Option<Duration>,
+
+    /// Lowest criticality whose failures fail the scan (None means any)
+    pub fail_on: Option<Criticality>,
+}
+
+impl ScanConfig {
+    /// How evidence is embedded in this run's output
+    pub fn evidence_options(&self) -> EvidenceOptions<'_> {
+        EvidenceOptions {
+            max_bytes: self.max_evidence_bytes,
+            redaction: self.redaction.as_ref(),
+        }
+    }
+
+    /// How much per-object evidence this run's executors must produce
+    ///
+    /// Only outcomes are needed when nothing reports findings: a quiet scan
+    /// writing at most a summary or CSV, with no baseline or journal. Its
+    /// `all` item checks then stop at the first failing object.
+    pub fn evidence_level(&self) -> EvidenceLevel {
+        let output_has_findings = match self.output_format {
+            OutputFormat::Summary | OutputFormat::Csv => false,
+            // Streamed even without --output, with an evidence hash
+            OutputFormat::Ndjson => true,
+            _ => self.output_file.is_some(),
+        };
+        if self.quiet && !output_has_findings && self.baseline.is_none() && self.journal.is_none() {
+            EvidenceLevel::Outcome
+        } else {
+            EvidenceLevel::PerObject
+        }
+    }
 }
 
 /// Result of a scan run
@@ -69,9 +305,15 @@ pub struct ScanSummary {
     /// Policies that failed
     pub failed: usize,
 
+    /// Failed policies below the `--fail-on` threshold (included in `failed`)
+    pub failed_below_threshold: usize,
+
     /// Files that had errors
     pub errors: usize,
 
+    /// The scan reached `--timeout` before every policy finished
+    pub timed_out: bool,
+
     /// Total scan duration
     #[allow(dead_code)]
     pub duration: std::time::Duration,
@@ -84,19 +326,149 @@ impl ScanSummary {
             total_files,
             passed: 0,
             failed: 0,
+            failed_below_threshold: 0,
             errors: 0,
+            timed_out: false,
             duration: std::time::Duration::ZERO,
         }
     }
 
-    /// Get the exit code based on results
-    pub fn exit_code(&self) -> i32 {
-        if self.errors > 0 {
-            2
-        } else if self.failed > 0 {
-            1
-        } else {
-            0
+    /// Exit code for invalid arguments and inputs, before anything is scanned
+    pub const USAGE_EXIT_CODE: i32 = 2;
+
+    /// Exit code when the scan deviates from its baseline
+    pub const BASELINE_DRIFT_EXIT_CODE: i32 = 5;
+
+    /// Exit code when the scan reached `--timeout`
+    pub const TIMEOUT_EXIT_CODE: i32 = 3;
+
+    /// Get the exit code for the results under `policy`
+    ///
+    /// By default 0 when every policy passed or there were none, 1 when a
+    /// policy failed, 3 when no policy file could be scanned (compilation or
+    /// resolution failed) and 4 when only some could. Failures below the
+    /// `--fail-on` threshold count as passes. A scan that reached
+    /// `--timeout` always exits `TIMEOUT_EXIT_CODE`.
+    pub fn exit_code(&self, policy: &ExitCodePolicy) -> i32 {
+        if self.timed_out {
+            return Self::TIMEOUT_EXIT_CODE;
+        }
+        policy.exit_code(&ScanCounts {
+            passed: self.passed + self.failed_below_threshold,
+            failed: self.failed - self.failed_below_threshold,
+            errors: self.errors,
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{parse_args, CliResult};
+
+    /// Parse `args`, with the agent's own directory as the input path
+    fn scan_config(args: &[&str]) -> ScanConfig {
+        let args: Vec<String> = ["esp_agent"]
+            .iter()
+            .chain(args)
+            .chain(&[env!("CARGO_MANIFEST_DIR")])
+            .map(|arg| arg.to_string())
+            .collect();
+        match parse_args(&args) {
+            CliResult::Run(config) => config,
+            _ => panic!("expected a scan for {:?}", args),
+        }
+    }
+
+    fn metadata(fields: &[(&str, &str)]) -> MetaDataBlock {
+        MetaDataBlock {
+            fields: fields
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_metadata_selector_parse() {
+        let selector = MetadataSelector::parse("platform = linux").unwrap();
+        assert_eq!(selector.key, "platform");
+        assert_eq!(selector.value, "linux");
+        assert_eq!(selector.to_string(), "platform=linux");
+
+        assert!(MetadataSelector::parse("platform").is_none());
+        assert!(MetadataSelector::parse("=linux").is_none());
+        assert!(MetadataSelector::parse("platform=").is_none());
+    }
+
+    #[test]
+    fn test_metadata_selector_matches() {
+        let meta = metadata(&[
+            ("platform", "linux"),
+            ("tags", "file-permissions, hardening"),
+            ("control_mapping", "CIS:6.1.1,NIST-800-53:AC-6"),
+        ]);
+        let matches = |select: &str| MetadataSelector::parse(select).unwrap().matches(&meta);
+
+        assert!(matches("platform=linux"));
+        assert!(!matches("platform=windows"));
+        assert!(matches("tags=hardening"));
+        assert!(!matches("tags=harden"));
+        assert!(matches("control_mapping=CIS"));
+        assert!(matches("control_mapping=NIST-800-53:AC-6"));
+        assert!(!matches("control_mapping=STIG"));
+        assert!(!matches("criticality=high"));
+    }
+
+    #[test]
+    fn test_timeout_overrides_exit_code_map() {
+        let mut summary = ScanSummary::new(3);
+        summary.passed = 2;
+        summary.errors = 1;
+        let policy = ExitCodePolicy::default();
+        assert_eq!(summary.exit_code(&policy), 4);
+
+        summary.timed_out = true;
+        assert_eq!(summary.exit_code(&policy), ScanSummary::TIMEOUT_EXIT_CODE);
+        let lenient: ExitCodePolicy = "non-pass=0".parse().unwrap();
+        assert_eq!(summary.exit_code(&lenient), 3);
+    }
+
+    #[test]
+    fn test_evidence_level_follows_output() {
+        let level = |args: &[&str]| scan_config(args).evidence_level();
+
+        // Nothing lists findings
+        assert_eq!(level(&["-q"]), EvidenceLevel::Outcome);
+        for format in ["summary", "csv"] {
+            assert_eq!(
+                level(&["-q", "-f", format, "-o", "out"]),
+                EvidenceLevel::Outcome,
+                "{}",
+                format
+            );
+        }
+
+        // The console, a full result, the NDJSON stream, a baseline or a
+        // journal report findings
+        for args in [
+            &[][..],
+            &["-q", "-o", "results.json"],
+            &["-q", "-f", "ndjson"],
+            &["-q", "--baseline", "base.json", "--update-baseline"],
+            &["-q", "--journal", "scans.journal"],
+        ] {
+            assert_eq!(level(args), EvidenceLevel::PerObject, "{:?}", args);
         }
     }
 }
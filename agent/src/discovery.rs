@@ -2,8 +2,31 @@
 //!
 //! Functions for discovering ESP files in directories.
 
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+/// Discover ESP files from several input paths, each a file or directory
+///
+/// Files are returned in input order, with each directory's files sorted.
+/// A file reached through more than one input (e.g. listed directly and
+/// also inside a listed directory) is returned once, at its first
+/// position, comparing canonical paths.
+pub fn discover_esp_files_in(input_paths: &[PathBuf]) -> Result<Vec<PathBuf>, DiscoveryError> {
+    let mut seen = HashSet::new();
+    let mut esp_files = Vec::new();
+
+    for input_path in input_paths {
+        for path in discover_esp_files(input_path)? {
+            let key = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if seen.insert(key) {
+                esp_files.push(path);
+            }
+        }
+    }
+
+    Ok(esp_files)
+}
+
 /// Discover all ESP files from an input path
 ///
 /// If the path is a file, returns a vec containing just that file.
@@ -109,3 +132,72 @@ impl std::error::Error for DiscoveryError {
         }
     }
 }
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fresh directory of policies: `a.esp`, `b.esp` and a non-policy file
+    fn policy_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("esp_discovery_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for file in ["b.esp", "a.esp", "notes.txt"] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_mixed_directory_and_file() {
+        let dir = policy_dir("mixed");
+        let other = dir.join("extra");
+        std::fs::create_dir(&other).unwrap();
+        std::fs::write(other.join("c.esp"), "").unwrap();
+
+        let files = discover_esp_files_in(&[other.join("c.esp"), dir.clone()]).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            files,
+            vec![other.join("c.esp"), dir.join("a.esp"), dir.join("b.esp")]
+        );
+    }
+
+    #[test]
+    fn test_duplicates_are_scanned_once() {
+        let dir = policy_dir("duplicates");
+
+        // Listed directly, through another spelling, and inside the directory
+        let files = discover_esp_files_in(&[
+            dir.join("b.esp"),
+            dir.join(".").join("b.esp"),
+            dir.clone(),
+            dir.clone(),
+        ])
+        .unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(files, vec![dir.join("b.esp"), dir.join("a.esp")]);
+    }
+
+    #[test]
+    fn test_missing_input_is_an_error() {
+        let dir = policy_dir("missing");
+        let result = discover_esp_files_in(&[dir.clone(), dir.join("absent.esp")]);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(matches!(result, Err(DiscoveryError::InvalidPath(p)) if p.ends_with("absent.esp")));
+    }
+}
@@ -0,0 +1,405 @@
+//! Host inventory
+//!
+//! Gathers host details auditors ask for (OS release, kernel, hostname,
+//! primary IP, boot time) once per scan, separately from per-policy CTN
+//! collection. The inventory is attached to the `host` section of result
+//! envelopes.
+//!
+//! ## Sources
+//!
+//! | Field | Linux | Windows |
+//! |-------|-------|---------|
+//! | OS release | `/etc/os-release` | `HKLM\SOFTWARE\Microsoft\Windows NT\CurrentVersion` |
+//! | Kernel | `/proc/sys/kernel/osrelease` | Build number from the registry |
+//! | Hostname | `/proc/sys/kernel/hostname` | `COMPUTERNAME` |
+//! | Boot time | `/proc/uptime` | `GetTickCount64` |
+//!
+//! The primary IP is the local address the OS would route external traffic
+//! from. It is found by connecting an unbound UDP socket, which sends no
+//! packets. Fields that cannot be read are left empty.
+
+use std::net::{IpAddr, UdpSocket};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+
+/// Documentation address (TEST-NET-1) used only for route lookup
+const ROUTE_PROBE_ADDR: &str = "192.0.2.1:9";
+
+/// Host details captured once per scan
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HostInventory {
+    pub hostname: Option<String>,
+    pub os: OsRelease,
+    pub kernel: Option<String>,
+    pub architecture: String,
+    pub primary_ip: Option<IpAddr>,
+    /// Boot time as RFC 3339 UTC
+    pub boot_time: Option<String>,
+}
+
+/// OS identification, as found in `os-release`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OsRelease {
+    /// Lowercase OS identifier, e.g. `rhel`, `ubuntu`, `windows`
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub version_id: Option<String>,
+    pub pretty_name: Option<String>,
+}
+
+impl HostInventory {
+    /// Collect the inventory of the running host
+    pub fn collect() -> Self {
+        let boot_time = platform::uptime().and_then(|uptime| {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+            Some(format_utc(now.checked_sub(uptime)?.as_secs()))
+        });
+
+        Self {
+            hostname: platform::hostname(),
+            os: platform::os_release(),
+            kernel: platform::kernel(),
+            architecture: std::env::consts::ARCH.to_string(),
+            primary_ip: primary_ip(),
+            boot_time,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        serde_json::json!({
+            "hostname": self.hostname,
+            "os": {
+                "id": self.os.id,
+                "name": self.os.name,
+                "version_id": self.os.version_id,
+                "pretty_name": self.os.pretty_name,
+            },
+            "kernel": self.kernel,
+            "architecture": self.architecture,
+            "primary_ip": self.primary_ip.map(|ip| ip.to_string()),
+            "boot_time": self.boot_time,
+        })
+    }
+}
+
+/// Parse `os-release` contents
+///
+/// Values may be unquoted, or single- or double-quoted with backslash
+/// escapes. Unknown keys are ignored.
+#[cfg_attr(windows, allow(dead_code))]
+pub fn parse_os_release(content: &str) -> OsRelease {
+    let mut release = OsRelease::default();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, raw)) = line.split_once('=') else {
+            continue;
+        };
+
+        let value = unquote(raw.trim());
+        if value.is_empty() {
+            continue;
+        }
+        match key.trim() {
+            "ID" => release.id = Some(value),
+            "NAME" => release.name = Some(value),
+            "VERSION_ID" => release.version_id = Some(value),
+            "PRETTY_NAME" => release.pretty_name = Some(value),
+            _ => {}
+        }
+    }
+
+    release
+}
+
+/// Strip shell-style quotes and escapes from an `os-release` value
+fn unquote(raw: &str) -> String {
+    let quote = match raw.chars().next() {
+        Some(q @ ('"' | '\'')) if raw.len() >= 2 && raw.ends_with(q) => q,
+        _ => return raw.to_string(),
+    };
+    let inner = raw
+        .strip_prefix(quote)
+        .and_then(|r| r.strip_suffix(quote))
+        .unwrap_or(raw);
+    if quote == '\'' {
+        return inner.to_string();
+    }
+
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                value.push(escaped);
+            }
+        } else {
+            value.push(c);
+        }
+    }
+    value
+}
+
+/// Parse the seconds since boot from `/proc/uptime`
+#[cfg_attr(windows, allow(dead_code))]
+pub fn parse_uptime(content: &str) -> Option<Duration> {
+    let seconds: f64 = content.split_whitespace().next()?.parse().ok()?;
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Format seconds since the Unix epoch as RFC 3339 UTC
+pub fn format_utc(unix_seconds: u64) -> String {
+    let days = unix_seconds / 86_400;
+    let secs_of_day = unix_seconds % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Local address used to reach external networks
+fn primary_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect(ROUTE_PROBE_ADDR).ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_unspecified()).then_some(ip)
+}
+
+/// Attach an inventory to the `host` section of a serialized envelope
+///
+/// Returns false if the value has no `envelope.host` object.
+pub fn attach_to_envelope(result: &mut Value, inventory: &HostInventory) -> bool {
+    match result
+        .pointer_mut("/envelope/host")
+        .and_then(Value::as_object_mut)
+    {
+        Some(host) => {
+            host.insert("inventory".to_string(), inventory.to_json());
+            true
+        }
+        None => false,
+    }
+}
+
+// ============================================================================
+// Platform Sources
+// ============================================================================
+
+#[cfg(not(windows))]
+mod platform {
+    use super::{parse_os_release, parse_uptime, OsRelease};
+    use std::time::Duration;
+
+    fn read_trimmed(path: &str) -> Option<String> {
+        let value = std::fs::read_to_string(path).ok()?.trim().to_string();
+        (!value.is_empty()).then_some(value)
+    }
+
+    pub fn os_release() -> OsRelease {
+        ["/etc/os-release", "/usr/lib/os-release"]
+            .iter()
+            .find_map(|path| std::fs::read_to_string(path).ok())
+            .map(|content| parse_os_release(&content))
+            .unwrap_or_default()
+    }
+
+    pub fn kernel() -> Option<String> {
+        read_trimmed("/proc/sys/kernel/osrelease")
+    }
+
+    pub fn hostname() -> Option<String> {
+        read_trimmed("/proc/sys/kernel/hostname").or_else(|| read_trimmed("/etc/hostname"))
+    }
+
+    pub fn uptime() -> Option<Duration> {
+        parse_uptime(&std::fs::read_to_string("/proc/uptime").ok()?)
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::OsRelease;
+    use std::time::Duration;
+    use windows::core::HSTRING;
+    use windows::Win32::System::Registry::{RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_SZ};
+    use windows::Win32::System::SystemInformation::GetTickCount64;
+
+    const CURRENT_VERSION_KEY: &str = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion";
+
+    /// Read a string value from the `CurrentVersion` key
+    fn current_version(value: &str) -> Option<String> {
+        let subkey = HSTRING::from(CURRENT_VERSION_KEY);
+        let name = HSTRING::from(value);
+        let mut buffer = [0u16; 256];
+        let mut size = u32::try_from(std::mem::size_of_val(&buffer)).ok()?;
+
+        // SAFETY: buffer is owned and size is its length in bytes
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_LOCAL_MACHINE,
+                &subkey,
+                &name,
+                RRF_RT_REG_SZ,
+                None,
+                Some(buffer.as_mut_ptr().cast()),
+                Some(&mut size),
+            )
+        };
+        if status.is_err() {
+            return None;
+        }
+
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        let value = String::from_utf16(buffer.get(..len)?).ok()?;
+        (!value.is_empty()).then_some(value)
+    }
+
+    pub fn os_release() -> OsRelease {
+        let name = current_version("ProductName");
+        let version_id = current_version("DisplayVersion").or_else(|| current_version("ReleaseId"));
+        let pretty_name = match (&name, &version_id) {
+            (Some(name), Some(version)) => Some(format!("{} {}", name, version)),
+            (name, _) => name.clone(),
+        };
+
+        OsRelease {
+            id: Some("windows".to_string()),
+            name,
+            version_id,
+            pretty_name,
+        }
+    }
+
+    pub fn kernel() -> Option<String> {
+        current_version("CurrentBuildNumber").map(|build| format!("Windows NT build {}", build))
+    }
+
+    pub fn hostname() -> Option<String> {
+        std::env::var("COMPUTERNAME").ok().filter(|h| !h.is_empty())
+    }
+
+    pub fn uptime() -> Option<Duration> {
+        // SAFETY: GetTickCount64 has no preconditions
+        Some(Duration::from_millis(unsafe { GetTickCount64() }))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RHEL_OS_RELEASE: &str = r#"NAME="Red Hat Enterprise Linux"
+VERSION="9.4 (Plow)"
+ID="rhel"
+ID_LIKE="fedora"
+VERSION_ID="9.4"
+PLATFORM_ID="platform:el9"
+PRETTY_NAME="Red Hat Enterprise Linux 9.4 (Plow)"
+ANSI_COLOR="0;31"
+CPE_NAME="cpe:/o:redhat:enterprise_linux:9::baseos"
+HOME_URL="https://www.redhat.com/"
+"#;
+
+    const UBUNTU_OS_RELEASE: &str = "# Ubuntu release\nPRETTY_NAME=\"Ubuntu 22.04.4 LTS\"\nNAME=\"Ubuntu\"\nVERSION_ID=\"22.04\"\nID=ubuntu\nID_LIKE=debian\n";
+
+    #[test]
+    fn test_parse_rhel_os_release() {
+        let release = parse_os_release(RHEL_OS_RELEASE);
+        assert_eq!(
+            release,
+            OsRelease {
+                id: Some("rhel".to_string()),
+                name: Some("Red Hat Enterprise Linux".to_string()),
+                version_id: Some("9.4".to_string()),
+                pretty_name: Some("Red Hat Enterprise Linux 9.4 (Plow)".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_unquoted_and_comments() {
+        let release = parse_os_release(UBUNTU_OS_RELEASE);
+        assert_eq!(release.id.as_deref(), Some("ubuntu"));
+        assert_eq!(release.version_id.as_deref(), Some("22.04"));
+        assert_eq!(release.pretty_name.as_deref(), Some("Ubuntu 22.04.4 LTS"));
+    }
+
+    #[test]
+    fn test_unquote_escapes() {
+        assert_eq!(
+            unquote(r#""Linux \"Edge\" \$HOME""#),
+            r#"Linux "Edge" $HOME"#
+        );
+        assert_eq!(unquote(r"'single \n'"), r"single \n");
+        assert_eq!(unquote("\""), "\"");
+    }
+
+    #[test]
+    fn test_parse_uptime() {
+        assert_eq!(
+            parse_uptime("12345.67 98765.43\n"),
+            Some(Duration::from_secs_f64(12345.67))
+        );
+        assert_eq!(parse_uptime(""), None);
+    }
+
+    #[test]
+    fn test_format_utc() {
+        assert_eq!(format_utc(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_utc(1_735_689_599), "2024-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn test_attach_to_envelope() {
+        let inventory = HostInventory {
+            hostname: Some("web-01".to_string()),
+            os: parse_os_release(RHEL_OS_RELEASE),
+            architecture: "x86_64".to_string(),
+            ..Default::default()
+        };
+
+        let mut result = serde_json::json!({ "envelope": { "host": { "hostname": "web-01" } } });
+        assert!(attach_to_envelope(&mut result, &inventory));
+        let attached = &result["envelope"]["host"]["inventory"];
+        assert_eq!(attached["os"]["version_id"], "9.4");
+        assert_eq!(attached["kernel"], Value::Null);
+
+        assert!(!attach_to_envelope(
+            &mut serde_json::json!({ "summary": {} }),
+            &inventory
+        ));
+    }
+}
@@ -11,34 +11,93 @@
 //! # Scan a directory
 //! esp_agent /path/to/policies/
 //!
+//! # Scan directories and files together
+//! esp_agent /path/to/policies/ extra.esp
+//!
 //! # Specify output format
 //! esp_agent --format attestation -o attestation.json policy.esp
+//!
+//! # List what a policy would read and execute, without collecting
+//! esp_agent --explain policy.esp
+//!
+//! # Show why one criterion failed: collected fields, expected vs actual
+//! esp_agent --explain-criterion file_metadata[shadow_file] policy.esp
+//!
+//! # Check policies against the collector contracts, without collecting
+//! esp_agent --check /path/to/policies/
+//!
+//! # Re-scan a policy on every save, until Ctrl-C
+//! esp_agent --watch policy.esp
+//!
+//! # Append each run to a signed journal, then verify it
+//! esp_agent --journal scans.jsonl /path/to/policies/
+//! esp_agent --verify-journal scans.jsonl
+//!
+//! # Stream results to a file, resuming from the checkpoint if interrupted
+//! esp_agent --format ndjson -o results.ndjson --checkpoint scan.checkpoint /path/to/policies/
+//!
+//! # Verify the signature of a saved result
+//! esp_agent --verify results.json
+//!
+//! # Save compact binary results, then read them back as JSON
+//! esp_agent --format msgpack -o results.mp policy.esp
+//! esp_agent --decode results.mp
+//!
+//! # Scan only the Linux policies mapped to CIS controls
+//! esp_agent --select platform=linux --select control_mapping=CIS /path/to/policies/
+//!
+//! # List every command collectors may run, with the site allowlist
+//! esp_agent --command-allowlist site-commands.txt --print-allowlist
+//!
+//! # Register only the strategies a JSON registry config names
+//! esp_agent --registry-config registry.json /path/to/policies/
+//!
+//! # Scan without any collector that runs commands
+//! esp_agent --deny-ctn audit_rule --deny-ctn firewall_rule --deny-ctn kernel_module \
+//!     --deny-ctn dpkg_package policy.esp
+//!
+//! # Check file ownership against the `owner` field a custom collector emits
+//! esp_agent --field-map file_metadata:owner_id=owner policy.esp
+//!
+//! # Give up on the scan after 10 minutes, reporting what finished
+//! esp_agent --timeout 600 -o results.json /path/to/policies/
 //! ```
 //!
 //! ## Output Formats
 //!
 //! - **full** (default): Complete results with findings and evidence
 //! - **summary**: Minimal output with pass/fail counts only
+//! - **ndjson**: One summary line per policy, streamed as the scan runs
 //! - **attestation**: CUI-free format safe for network transport
+//! - **assessor**: Full package with reproducibility info for assessors
+//! - **oscal**: NIST OSCAL assessment results, reshaped from the assessor package
+//! - **csv**: One row per policy, for spreadsheets
+//! - **msgpack**: Full results encoded as MessagePack
 //!
-//! All formats produce a single envelope containing all scanned policies.
+//! Except for ndjson, all formats produce a single envelope containing all
+//! scanned policies.
 
 mod cli;
 mod config;
 mod discovery;
+mod inventory;
 mod output;
 mod registry;
 mod scanner;
 mod signing;
+mod subprocess;
 
 use cli::{parse_args, print_help, CliResult};
-use contract_kit::execution_api::logging;
+use config::ScanSummary;
+use contract_kit::execution_api::{log_info, logging, ExitCodePolicy};
+use discovery::DiscoveryError;
+use scanner::ScanError;
 
 fn main() {
     // Initialize logging
     if let Err(e) = logging::init_global_logging() {
         eprintln!("Failed to initialize logging: {}", e);
-        std::process::exit(2);
+        std::process::exit(ExitCodePolicy::default().error);
     }
 
     // Parse CLI arguments
@@ -50,17 +109,100 @@ fn main() {
             print_help(program_name);
             0
         }
-        CliResult::Error(msg) => {
-            eprintln!("Error: {}", msg);
-            2
-        }
-        CliResult::Run(config) => match run(config) {
-            Ok(code) => code,
+        CliResult::PrintSchema(format) => match output::output_schema(format) {
+            Ok(schema) => {
+                println!("{}", schema);
+                0
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                ExitCodePolicy::default().error
+            }
+        },
+        CliResult::PrintAllowlist {
+            command_allowlist,
+            registry_config,
+            ctn_filter,
+        } => match scanner::allowed_commands(
+            command_allowlist.as_deref(),
+            registry_config.as_deref(),
+            &ctn_filter,
+        ) {
+            Ok(commands) => {
+                for command in commands {
+                    println!("{}", command);
+                }
+                0
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                ScanSummary::USAGE_EXIT_CODE
+            }
+        },
+        CliResult::VerifyJournal(path) => match output::verify_journal(&path) {
+            Ok(report) => {
+                let style = output::ConsoleStyle::detect(output::ColorMode::Auto, None);
+                output::print_journal_report(&report, &path, &style);
+                if report.is_valid() {
+                    0
+                } else {
+                    1
+                }
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                2
+            }
+        },
+        CliResult::Verify(path) => match output::verify_result_file(&path) {
+            Ok(report) => {
+                let style = output::ConsoleStyle::detect(output::ColorMode::Auto, None);
+                output::print_verify_report(&report, &path, &style);
+                if report.is_valid() {
+                    0
+                } else {
+                    1
+                }
+            }
             Err(e) => {
                 eprintln!("Error: {}", e);
                 2
             }
         },
+        CliResult::Decode(path) => match output::read_result_file(&path) {
+            Ok(result) => match serde_json::to_string_pretty(&result) {
+                Ok(json) => {
+                    println!("{}", json);
+                    0
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    2
+                }
+            },
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                2
+            }
+        },
+        CliResult::Error(msg) => {
+            eprintln!("Error: {}", msg);
+            ScanSummary::USAGE_EXIT_CODE
+        }
+        CliResult::Run(config) => {
+            let exit_codes = config.exit_codes;
+            match run(config) {
+                Ok(code) => code,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    if is_usage_error(e.as_ref()) {
+                        ScanSummary::USAGE_EXIT_CODE
+                    } else {
+                        exit_codes.error
+                    }
+                }
+            }
+        }
     };
 
     // Print logging summary if not quiet
@@ -73,16 +215,84 @@ fn main() {
     std::process::exit(exit_code);
 }
 
+/// Whether `run` failed on an input the user named (a missing policy path,
+/// an invalid allowlist or registry config) rather than while scanning
+fn is_usage_error(error: &(dyn std::error::Error + 'static)) -> bool {
+    error.is::<DiscoveryError>()
+        || matches!(
+            error.downcast_ref::<ScanError>(),
+            Some(ScanError::Allowlist(_) | ScanError::RegistrySpec(_))
+        )
+}
+
 /// Run the scan with the given configuration
+///
+/// Returns the exit code: see `ScanSummary::exit_code`. A run with no ESP
+/// files exits with the `empty` code (0 by default).
 fn run(config: config::ScanConfig) -> Result<i32, Box<dyn std::error::Error>> {
     // Discover ESP files
-    let esp_files = discovery::discover_esp_files(&config.input_path)?;
+    let esp_files = discovery::discover_esp_files_in(&config.input_paths)?;
 
     if esp_files.is_empty() {
         if !config.quiet {
-            println!("No ESP files found in: {}", config.input_path.display());
+            let inputs: Vec<String> = config
+                .input_paths
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            println!("No ESP files found in: {}", inputs.join(", "));
+        }
+        return Ok(config.exit_codes.empty);
+    }
+
+    // Narrow to policies matching --select before anything runs
+    let discovered = esp_files.len();
+    let esp_files = scanner::select_policies(&config, &esp_files);
+    if !config.select.is_empty() {
+        let conditions: Vec<String> = config.select.iter().map(|s| s.to_string()).collect();
+        log_info!(
+            "Policies selected",
+            "selected" => esp_files.len(),
+            "discovered" => discovered,
+            "select" => conditions.join(",")
+        );
+        if esp_files.is_empty() {
+            if !config.quiet {
+                println!(
+                    "No ESP files match --select {} ({} discovered)",
+                    conditions.join(" --select "),
+                    discovered
+                );
+            }
+            return Ok(config.exit_codes.empty);
         }
-        return Ok(0);
+    }
+
+    if !config.field_map.is_empty() {
+        let overrides: Vec<String> = config.field_map.iter().map(|o| o.to_string()).collect();
+        log_info!(
+            "Field mapping overrides applied",
+            "overrides" => overrides.join(",")
+        );
+    }
+
+    // Explain only lists planned collections; it never scans. With a
+    // criterion ID it scans, printing only that criterion's trace.
+    if config.explain {
+        if let Some(criterion_id) = &config.explain_criterion {
+            return Ok(scanner::run_trace(&config, &esp_files, criterion_id)?);
+        }
+        return Ok(scanner::run_explain(&config, &esp_files)?);
+    }
+
+    // Check only validates policies against contracts; it never scans
+    if config.check {
+        return Ok(scanner::run_check(&config, &esp_files)?);
+    }
+
+    // Watch scans until Ctrl-C, printing to the console only
+    if config.watch {
+        return Ok(scanner::run_watch(&config, &esp_files)?);
     }
 
     // Run the scan
@@ -4,6 +4,10 @@
 //! This format includes exact commands and inputs used during collection,
 //! allowing assessors to verify and reproduce the scan.
 //!
+//! Evidence is carried as collected, so values a `computed_values` object
+//! reports (derived thresholds and the like) reach the assessor; the
+//! attestation format carries no evidence and omits them.
+//!
 //! ## Hash Architecture
 //!
 //! The `content_hash` and `evidence_hash` are pre-computed in the execution engine
@@ -14,7 +18,7 @@ use common::results::{
     builder::AssessorInput, AgentInfo, AssessorPackage, Criticality, Evidence, HostInfo,
     ResultBuilder,
 };
-use contract_kit::execution_api::ScanResult;
+use contract_kit::execution_api::CountedScanResult;
 
 use super::OutputError;
 use crate::output::combine_scan_hashes;
@@ -26,7 +30,9 @@ use crate::output::combine_scan_hashes;
 /// Uses pre-computed hashes from `ScanResult` rather than recomputing them.
 /// This ensures the assessor package's hashes match those in attestations and
 /// full results for the same scan.
-pub fn build_assessor_package(scan_results: &[ScanResult]) -> Result<AssessorPackage, OutputError> {
+pub fn build_assessor_package(
+    scan_results: &[CountedScanResult],
+) -> Result<AssessorPackage, OutputError> {
     if scan_results.is_empty() {
         return Err(OutputError::Build(
             "At least one scan result is required".to_string(),
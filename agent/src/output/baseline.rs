@@ -0,0 +1,357 @@
+//! Baseline comparison
+//!
+//! Compares the failing criteria of the current scan against an approved
+//! baseline (a previously saved full result). Any difference is drift:
+//!
+//! - **New failures**: criteria failing now that were passing in the baseline
+//! - **Resolved failures**: criteria failing in the baseline that now pass,
+//!   which usually means the policy set or host changed unexpectedly
+//!
+//! Criteria are keyed by `policy_id` + finding ID. A policy that fails
+//! without findings is keyed by `policy_id` alone.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use contract_kit::execution_api::CountedScanResult;
+use serde_json::Value;
+
+use super::terminal::{Color, ConsoleStyle};
+
+/// Criterion ID used for policies that fail without individual findings
+const POLICY_LEVEL_CRITERION: &str = "<policy>";
+
+/// Identity of a failing criterion
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CriterionKey {
+    pub policy_id: String,
+    pub criterion_id: String,
+}
+
+impl CriterionKey {
+    pub fn new(policy_id: impl Into<String>, criterion_id: impl Into<String>) -> Self {
+        Self {
+            policy_id: policy_id.into(),
+            criterion_id: criterion_id.into(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        serde_json::json!({
+            "policy_id": self.policy_id,
+            "criterion_id": self.criterion_id,
+        })
+    }
+}
+
+impl std::fmt::Display for CriterionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.policy_id, self.criterion_id)
+    }
+}
+
+/// Failing criteria recorded in a baseline file
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    pub failing: BTreeSet<CriterionKey>,
+}
+
+impl Baseline {
+    /// Load a baseline from a saved full result file
+    pub fn load(path: &Path) -> Result<Self, BaselineError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| BaselineError::Read(path.display().to_string(), e))?;
+        let json: Value = serde_json::from_str(&content)
+            .map_err(|e| BaselineError::Parse(path.display().to_string(), e.to_string()))?;
+        Self::from_json(&json).map_err(|msg| BaselineError::Parse(path.display().to_string(), msg))
+    }
+
+    /// Extract failing criteria from a full result JSON document
+    ///
+    /// Every object carrying a `policy_id` is treated as a policy result.
+    pub fn from_json(json: &Value) -> Result<Self, String> {
+        let mut baseline = Baseline::default();
+        let mut policies_found = 0;
+        collect_policy_failures(json, &mut baseline.failing, &mut policies_found);
+
+        if policies_found == 0 {
+            return Err("no policy results found (expected a full result)".to_string());
+        }
+
+        Ok(baseline)
+    }
+}
+
+/// Walk a JSON document collecting failing criteria from policy objects
+fn collect_policy_failures(
+    value: &Value,
+    failing: &mut BTreeSet<CriterionKey>,
+    policies_found: &mut usize,
+) {
+    match value {
+        Value::Object(map) => {
+            if let Some(policy_id) = map.get("policy_id").and_then(Value::as_str) {
+                *policies_found += 1;
+
+                let finding_ids: Vec<&str> = map
+                    .get("findings")
+                    .and_then(Value::as_array)
+                    .map(|findings| {
+                        findings
+                            .iter()
+                            .filter_map(|f| f.get("finding_id").and_then(Value::as_str))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                for finding_id in &finding_ids {
+                    failing.insert(CriterionKey::new(policy_id, *finding_id));
+                }
+
+                let failed = map
+                    .get("outcome")
+                    .and_then(Value::as_str)
+                    .is_some_and(|o| o.eq_ignore_ascii_case("fail"));
+                if failed && finding_ids.is_empty() {
+                    failing.insert(CriterionKey::new(policy_id, POLICY_LEVEL_CRITERION));
+                }
+                return;
+            }
+
+            for child in map.values() {
+                collect_policy_failures(child, failing, policies_found);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_policy_failures(item, failing, policies_found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collect failing criteria from the current scan
+pub fn failing_criteria(scan_results: &[CountedScanResult]) -> BTreeSet<CriterionKey> {
+    let mut failing = BTreeSet::new();
+
+    for result in scan_results {
+        let policy_id = &result.outcome.policy_id;
+        for finding in &result.findings {
+            failing.insert(CriterionKey::new(policy_id, &finding.finding_id));
+        }
+        if !result.tree_passed && result.findings.is_empty() {
+            failing.insert(CriterionKey::new(policy_id, POLICY_LEVEL_CRITERION));
+        }
+    }
+
+    failing
+}
+
+/// Difference between a baseline and the current scan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BaselineDiff {
+    /// Failing now, not failing in the baseline
+    pub new_failures: Vec<CriterionKey>,
+    /// Failing in the baseline, not failing now
+    pub resolved_failures: Vec<CriterionKey>,
+    /// Failing in both
+    pub unchanged_failures: usize,
+}
+
+impl BaselineDiff {
+    /// Whether the current scan matches the baseline
+    pub fn matches(&self) -> bool {
+        self.new_failures.is_empty() && self.resolved_failures.is_empty()
+    }
+
+    /// Build a structured report of the comparison
+    pub fn to_report(&self, baseline_path: &Path) -> Value {
+        serde_json::json!({
+            "baseline": baseline_path.display().to_string(),
+            "matches": self.matches(),
+            "new_failures": self.new_failures.iter().map(CriterionKey::to_json).collect::<Vec<_>>(),
+            "resolved_failures": self
+                .resolved_failures
+                .iter()
+                .map(CriterionKey::to_json)
+                .collect::<Vec<_>>(),
+            "unchanged_failures": self.unchanged_failures,
+        })
+    }
+}
+
+/// Compare current failing criteria against a baseline
+pub fn compare_to_baseline(baseline: &Baseline, current: &BTreeSet<CriterionKey>) -> BaselineDiff {
+    BaselineDiff {
+        new_failures: current.difference(&baseline.failing).cloned().collect(),
+        resolved_failures: baseline.failing.difference(current).cloned().collect(),
+        unchanged_failures: current.intersection(&baseline.failing).count(),
+    }
+}
+
+/// Print a human-readable baseline comparison
+pub fn print_baseline_diff(diff: &BaselineDiff, baseline_path: &Path, style: &ConsoleStyle) {
+    println!("BASELINE: {}", baseline_path.display());
+    if diff.matches() {
+        println!(
+            "  {} Matches baseline ({} known failure(s))",
+            style.paint(Color::Green, "✓"),
+            diff.unchanged_failures
+        );
+    } else {
+        println!("  {} Drift from baseline", style.paint(Color::Red, "✗"));
+        for key in &diff.new_failures {
+            println!("    + new failure:      {}", key);
+        }
+        for key in &diff.resolved_failures {
+            println!("    - resolved failure: {}", key);
+        }
+    }
+    println!();
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors that can occur loading a baseline
+#[derive(Debug)]
+pub enum BaselineError {
+    /// Failed to read baseline file
+    Read(String, std::io::Error),
+    /// Baseline file is not a usable full result
+    Parse(String, String),
+}
+
+impl std::fmt::Display for BaselineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BaselineError::Read(path, e) => write!(f, "Failed to read baseline {}: {}", path, e),
+            BaselineError::Parse(path, msg) => write!(f, "Invalid baseline {}: {}", path, msg),
+        }
+    }
+}
+
+impl std::error::Error for BaselineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BaselineError::Read(_, e) => Some(e),
+            BaselineError::Parse(_, _) => None,
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baseline_json() -> Value {
+        serde_json::json!({
+            "envelope": { "result_id": "r-1" },
+            "policies": [
+                {
+                    "policy_id": "ssh-hardening",
+                    "outcome": "fail",
+                    "findings": [
+                        { "finding_id": "ssh-root-login", "title": "Root login enabled" }
+                    ]
+                },
+                {
+                    "policy_id": "audit-config",
+                    "outcome": "pass",
+                    "findings": []
+                }
+            ]
+        })
+    }
+
+    fn current(keys: &[(&str, &str)]) -> BTreeSet<CriterionKey> {
+        keys.iter()
+            .map(|(p, c)| CriterionKey::new(*p, *c))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_json_extracts_failing_criteria() {
+        let baseline = Baseline::from_json(&baseline_json()).expect("valid baseline");
+        assert_eq!(
+            baseline.failing,
+            current(&[("ssh-hardening", "ssh-root-login")])
+        );
+    }
+
+    #[test]
+    fn test_from_json_policy_level_failure() {
+        let json = serde_json::json!({
+            "policies": [{ "policy_id": "fw", "outcome": "Fail", "findings": [] }]
+        });
+        let baseline = Baseline::from_json(&json).expect("valid baseline");
+        assert_eq!(baseline.failing, current(&[("fw", POLICY_LEVEL_CRITERION)]));
+    }
+
+    #[test]
+    fn test_from_json_rejects_non_result() {
+        assert!(Baseline::from_json(&serde_json::json!({ "summary": {} })).is_err());
+    }
+
+    #[test]
+    fn test_no_change() {
+        let baseline = Baseline::from_json(&baseline_json()).expect("valid baseline");
+        let diff = compare_to_baseline(&baseline, &current(&[("ssh-hardening", "ssh-root-login")]));
+        assert!(diff.matches());
+        assert_eq!(diff.unchanged_failures, 1);
+    }
+
+    #[test]
+    fn test_new_failure() {
+        let baseline = Baseline::from_json(&baseline_json()).expect("valid baseline");
+        let diff = compare_to_baseline(
+            &baseline,
+            &current(&[
+                ("ssh-hardening", "ssh-root-login"),
+                ("audit-config", "passwd-watch"),
+            ]),
+        );
+        assert!(!diff.matches());
+        assert_eq!(
+            diff.new_failures,
+            vec![CriterionKey::new("audit-config", "passwd-watch")]
+        );
+        assert!(diff.resolved_failures.is_empty());
+    }
+
+    #[test]
+    fn test_fixed_failure() {
+        let baseline = Baseline::from_json(&baseline_json()).expect("valid baseline");
+        let diff = compare_to_baseline(&baseline, &BTreeSet::new());
+        assert!(!diff.matches());
+        assert!(diff.new_failures.is_empty());
+        assert_eq!(
+            diff.resolved_failures,
+            vec![CriterionKey::new("ssh-hardening", "ssh-root-login")]
+        );
+    }
+
+    #[test]
+    fn test_report_structure() {
+        let baseline = Baseline::from_json(&baseline_json()).expect("valid baseline");
+        let diff = compare_to_baseline(&baseline, &BTreeSet::new());
+        let report = diff.to_report(Path::new("baseline.json"));
+        assert_eq!(report["matches"], false);
+        assert_eq!(
+            report["resolved_failures"][0]["criterion_id"],
+            "ssh-root-login"
+        );
+    }
+}
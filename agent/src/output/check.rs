@@ -0,0 +1,44 @@
+//! Check output
+//!
+//! Formats the result of `--check`: for each policy, the criteria that do
+//! not match their collector's contract (unknown state fields, disallowed
+//! operations, missing object fields). Nothing has been collected.
+
+use std::path::PathBuf;
+
+use contract_kit::execution_api::PolicyWarning;
+
+/// Contract check result for one policy file
+#[derive(Debug, Clone)]
+pub struct PolicyCheck {
+    pub esp_file: PathBuf,
+    pub warnings: Vec<PolicyWarning>,
+}
+
+/// Print the warnings of each checked policy
+pub fn print_check_report(checks: &[PolicyCheck]) {
+    println!();
+    println!("POLICY CHECK (nothing was collected)");
+    println!();
+
+    for check in checks {
+        if check.warnings.is_empty() {
+            println!("{}: ok", check.esp_file.display());
+            continue;
+        }
+        println!("{}", check.esp_file.display());
+        for warning in &check.warnings {
+            println!("  {}", warning);
+        }
+    }
+
+    let warnings: usize = checks.iter().map(|c| c.warnings.len()).sum();
+    let policies = checks.iter().filter(|c| !c.warnings.is_empty()).count();
+    println!();
+    println!(
+        "{} warning(s) in {} of {} policies",
+        warnings,
+        policies,
+        checks.len()
+    );
+}
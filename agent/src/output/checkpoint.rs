@@ -0,0 +1,390 @@
+//! Scan checkpoints
+//!
+//! `--checkpoint <file>` makes a long NDJSON scan resumable. Each scanned
+//! policy written to the output is also appended to the checkpoint, once
+//! its output line is flushed, as one tab-separated line:
+//!
+//! ```text
+//! failed  high  sha256:<policy file>  sha256:<content>  sha256:<evidence>  policies/ssh.esp
+//! ```
+//!
+//! The fields are the policy's status (`passed`, `failed` or
+//! `not_applicable`), its criticality, the SHA-256 of the policy file as
+//! scanned, the result's content and evidence hashes, and the path as
+//! discovered, last so it may contain spaces.
+//!
+//! Run again with the same inputs, output and checkpoint, a scan skips each
+//! policy whose file still has the checkpointed hash and appends the rest to
+//! the output; the final `scan` line counts and hashes both runs. A policy
+//! changed since it was checkpointed is scanned again, and its new line
+//! supersedes the earlier one. Policies that could not be scanned are not
+//! checkpointed, so a resumed scan retries them. The checkpoint is removed
+//! once a scan completes.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use contract_kit::execution_api::CountedScanResult;
+use sha2::{Digest, Sha256};
+
+use super::ndjson::NdjsonTotals;
+use super::summary::build_policy_summary;
+
+/// How a checkpointed policy counts in the totals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointStatus {
+    Passed,
+    Failed,
+    NotApplicable,
+}
+
+impl CheckpointStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Passed => "passed",
+            Self::Failed => "failed",
+            Self::NotApplicable => "not_applicable",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "passed" => Some(Self::Passed),
+            "failed" => Some(Self::Failed),
+            "not_applicable" => Some(Self::NotApplicable),
+            _ => None,
+        }
+    }
+}
+
+/// A policy completed by an earlier run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointEntry {
+    pub status: CheckpointStatus,
+    /// Lowercase criticality, as `--fail-on` takes it
+    pub criticality: String,
+    /// SHA-256 of the policy file when it was scanned
+    pub policy_hash: String,
+    pub content_hash: String,
+    pub evidence_hash: String,
+    /// Path of the policy file, as discovered
+    pub esp_file: String,
+}
+
+impl CheckpointEntry {
+    /// Parse a checkpoint line; `None` for a malformed one
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(6, '\t');
+        let entry = Self {
+            status: CheckpointStatus::parse(fields.next()?)?,
+            criticality: fields.next()?.to_string(),
+            policy_hash: fields.next()?.to_string(),
+            content_hash: fields.next()?.to_string(),
+            evidence_hash: fields.next()?.to_string(),
+            esp_file: fields.next()?.to_string(),
+        };
+        (!entry.esp_file.is_empty()).then_some(entry)
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\n",
+            self.status.as_str(),
+            self.criticality,
+            self.policy_hash,
+            self.content_hash,
+            self.evidence_hash,
+            self.esp_file
+        )
+    }
+}
+
+/// Policies completed so far by a resumable scan
+pub struct Checkpoint {
+    path: PathBuf,
+    /// Entries of earlier runs still valid for this one, in checkpoint order
+    prior: Vec<CheckpointEntry>,
+    completed: HashSet<String>,
+    out: File,
+}
+
+impl Checkpoint {
+    /// Open the checkpoint at `path` for a scan of `esp_files`, creating it
+    /// if missing
+    ///
+    /// Of several entries for one policy, the last is kept. Entries for
+    /// policies not in `esp_files`, or whose file no longer has the
+    /// checkpointed hash, are dropped, as are malformed lines (such as one
+    /// cut short by the interruption).
+    pub fn open(path: &Path, esp_files: &[PathBuf]) -> Result<Self, CheckpointError> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(CheckpointError::Read(path.to_path_buf(), e.to_string())),
+        };
+
+        let entries: Vec<CheckpointEntry> =
+            content.lines().filter_map(CheckpointEntry::parse).collect();
+        let last: HashMap<&str, usize> = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (entry.esp_file.as_str(), index))
+            .collect();
+        let inputs: HashMap<String, &PathBuf> = esp_files
+            .iter()
+            .map(|file| (file.display().to_string(), file))
+            .collect();
+
+        let prior: Vec<CheckpointEntry> = entries
+            .iter()
+            .enumerate()
+            .filter(|(index, entry)| last.get(entry.esp_file.as_str()) == Some(index))
+            .map(|(_, entry)| entry)
+            .filter(|entry| {
+                inputs.get(&entry.esp_file).and_then(|file| file_hash(file))
+                    == Some(entry.policy_hash.clone())
+            })
+            .cloned()
+            .collect();
+        let completed = prior.iter().map(|entry| entry.esp_file.clone()).collect();
+
+        let mut out = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| CheckpointError::Write(path.to_path_buf(), e.to_string()))?;
+        // Finish a line the interruption cut short, so the next one is whole
+        if !content.is_empty() && !content.ends_with('\n') {
+            out.write_all(b"\n")
+                .map_err(|e| CheckpointError::Write(path.to_path_buf(), e.to_string()))?;
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            prior,
+            completed,
+            out,
+        })
+    }
+
+    /// Path of the checkpoint file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Policies earlier runs completed, in the order they were written
+    pub fn prior(&self) -> &[CheckpointEntry] {
+        &self.prior
+    }
+
+    /// Whether an earlier run completed `esp_file`, unchanged since
+    pub fn is_completed(&self, esp_file: &Path) -> bool {
+        self.completed.contains(&esp_file.display().to_string())
+    }
+
+    /// NDJSON totals and result hashes of the earlier runs
+    pub(super) fn prior_totals(&self) -> (NdjsonTotals, Vec<(String, String)>) {
+        let mut totals = NdjsonTotals::default();
+        for entry in &self.prior {
+            totals.total_policies += 1;
+            match entry.status {
+                CheckpointStatus::Passed => totals.passed += 1,
+                CheckpointStatus::Failed => totals.failed += 1,
+                CheckpointStatus::NotApplicable => totals.not_applicable += 1,
+            }
+        }
+        let hashes = self
+            .prior
+            .iter()
+            .map(|entry| (entry.content_hash.clone(), entry.evidence_hash.clone()))
+            .collect();
+        (totals, hashes)
+    }
+
+    /// Append a scanned policy
+    ///
+    /// A policy file that can no longer be read is not recorded, so a
+    /// resumed scan tries it again.
+    pub(super) fn record(&mut self, esp_file: &Path, result: &CountedScanResult) -> io::Result<()> {
+        let Some(policy_hash) = file_hash(esp_file) else {
+            return Ok(());
+        };
+        let summary = build_policy_summary(result);
+        let status = if summary.not_applicable {
+            CheckpointStatus::NotApplicable
+        } else if summary.passed {
+            CheckpointStatus::Passed
+        } else {
+            CheckpointStatus::Failed
+        };
+        let entry = CheckpointEntry {
+            status,
+            criticality: summary.criticality.to_lowercase(),
+            policy_hash,
+            content_hash: result.content_hash.clone(),
+            evidence_hash: result.evidence_hash.clone(),
+            esp_file: esp_file.display().to_string(),
+        };
+        self.out.write_all(entry.to_line().as_bytes())?;
+        self.out.flush()
+    }
+
+    /// Delete the checkpoint of a completed scan
+    pub fn remove(self) -> Result<(), CheckpointError> {
+        drop(self.out);
+        std::fs::remove_file(&self.path)
+            .map_err(|e| CheckpointError::Write(self.path.clone(), e.to_string()))
+    }
+}
+
+/// SHA-256 of a policy file, `None` if it cannot be read
+fn file_hash(path: &Path) -> Option<String> {
+    let content = std::fs::read(path).ok()?;
+    Some(format!("sha256:{}", hex::encode(Sha256::digest(&content))))
+}
+
+/// Errors opening or updating a checkpoint
+#[derive(Debug)]
+pub enum CheckpointError {
+    /// Failed to read the checkpoint
+    Read(PathBuf, String),
+    /// Failed to update or remove the checkpoint
+    Write(PathBuf, String),
+    /// The checkpoint lists completed policies, but the output they were
+    /// written to is gone
+    MissingOutput {
+        checkpoint: PathBuf,
+        output: PathBuf,
+    },
+}
+
+impl std::fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckpointError::Read(path, e) => {
+                write!(f, "Failed to read checkpoint {}: {}", path.display(), e)
+            }
+            CheckpointError::Write(path, e) => {
+                write!(f, "Failed to update checkpoint {}: {}", path.display(), e)
+            }
+            CheckpointError::MissingOutput { checkpoint, output } => write!(
+                f,
+                "Checkpoint {} lists completed policies, but their output {} is missing \
+                 (delete the checkpoint to scan from the start)",
+                checkpoint.display(),
+                output.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("esp_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn entry(status: CheckpointStatus, file: &Path, content: &str) -> CheckpointEntry {
+        CheckpointEntry {
+            status,
+            criticality: "high".to_string(),
+            policy_hash: file_hash(file).unwrap_or_default(),
+            content_hash: format!("sha256:{}", content),
+            evidence_hash: "sha256:e".to_string(),
+            esp_file: file.display().to_string(),
+        }
+    }
+
+    #[test]
+    fn test_entry_line_round_trip() {
+        let entry = CheckpointEntry {
+            status: CheckpointStatus::NotApplicable,
+            criticality: "medium".to_string(),
+            policy_hash: "sha256:p".to_string(),
+            content_hash: "sha256:c".to_string(),
+            evidence_hash: "sha256:e".to_string(),
+            esp_file: "policies/with space.esp".to_string(),
+        };
+        let line = entry.to_line();
+        assert_eq!(line.matches('\t').count(), 5);
+        assert_eq!(CheckpointEntry::parse(line.trim_end()), Some(entry));
+
+        assert_eq!(CheckpointEntry::parse("passed\thigh\tsha256:p"), None);
+        assert_eq!(
+            CheckpointEntry::parse("skipped\thigh\ta\tb\tc\tx.esp"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_open_keeps_unchanged_policies_only() {
+        let dir = temp_dir("checkpoint_open");
+        let [a, b, c, gone] = ["a.esp", "b.esp", "c.esp", "gone.esp"].map(|n| dir.join(n));
+        for file in [&a, &b, &c, &gone] {
+            std::fs::write(file, format!("policy {}", file.display())).unwrap();
+        }
+
+        let path = dir.join("scan.checkpoint");
+        let lines = [
+            entry(CheckpointStatus::Failed, &a, "a1"),
+            entry(CheckpointStatus::Passed, &b, "b"),
+            entry(CheckpointStatus::Passed, &gone, "gone"),
+            entry(CheckpointStatus::Passed, &a, "a2"),
+        ]
+        .map(|entry| entry.to_line())
+        .concat();
+        // The last line was cut short by the interruption
+        std::fs::write(&path, format!("{}passed\thigh\tsha256:", lines)).unwrap();
+        // b changed after it was checkpointed
+        std::fs::write(&b, "policy b, edited").unwrap();
+
+        let checkpoint = Checkpoint::open(&path, &[a.clone(), b.clone(), c.clone()]).unwrap();
+        assert!(checkpoint.is_completed(&a));
+        assert!(!checkpoint.is_completed(&b));
+        assert!(!checkpoint.is_completed(&c));
+        assert_eq!(checkpoint.prior().len(), 1);
+
+        let (totals, hashes) = checkpoint.prior_totals();
+        assert_eq!(totals.total_policies, 1);
+        assert_eq!(totals.passed, 1);
+        assert_eq!(
+            hashes,
+            vec![("sha256:a2".to_string(), "sha256:e".to_string())]
+        );
+
+        checkpoint.remove().unwrap();
+        assert!(!path.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_open_creates_missing_checkpoint() {
+        let dir = temp_dir("checkpoint_new");
+        let path = dir.join("new.checkpoint");
+
+        let checkpoint = Checkpoint::open(&path, &[dir.join("a.esp")]).unwrap();
+        assert!(checkpoint.prior().is_empty());
+        assert_eq!(checkpoint.path(), path);
+        assert!(path.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,51 +1,84 @@
 //! Console output formatting
 //!
 //! Provides formatted console output for scan results.
+//!
+//! Each policy lists its failing findings, criteria that errored during
+//! collection, and criteria that were not applicable, each in its own
+//! subsection so a FAIL without findings is still explained.
+//!
+//! Criteria an executor marked not applicable (wrong platform, missing
+//! capability) are reported as N/A and left out of pass counts. A passing
+//! policy whose criteria were all marked is itself N/A and is left out of
+//! the posture score; a failing policy always counts as failed, as it does
+//! in the exit code.
+//!
+//! Colors and box widths come from a [`ConsoleStyle`], so output adapts to
+//! the terminal and stays escape-free when color is disabled.
+
+use common::results::Criticality;
+use contract_kit::collectors::error_reporting::COLLECTION_ERROR_CATEGORY;
+use contract_kit::execution_api::{CountedScanResult, CriteriaCounts};
+
+use super::terminal::{Color, ConsoleStyle};
+use super::Posture;
 
-use contract_kit::execution_api::ScanResult;
+/// Columns taken by the `│       ` prefix of detail lines
+const DETAIL_INDENT: usize = 8;
 
 /// Print scan results to console in a human-readable format
-pub fn print_results(scan_results: &[ScanResult]) {
+pub fn print_results(scan_results: &[CountedScanResult], style: &ConsoleStyle) {
     if scan_results.is_empty() {
         return;
     }
 
     println!();
-    println!("╔═══════════════════════════════════════════════════════════════════════════════╗");
-    println!("║                              SCAN RESULTS                                     ║");
-    println!("╚═══════════════════════════════════════════════════════════════════════════════╝");
+    println!("{}", style.rule('╔', '═', '╗'));
+    println!("{}", style.centered('║', "SCAN RESULTS"));
+    println!("{}", style.rule('╚', '═', '╝'));
     println!();
 
     for (index, result) in scan_results.iter().enumerate() {
-        print_policy_result(index + 1, scan_results.len(), result);
+        print_policy_result(index + 1, scan_results.len(), result, style);
     }
 
-    print_summary_table(scan_results);
+    print_summary_table(scan_results, style);
 }
 
 /// Print a single policy result
-fn print_policy_result(num: usize, total: usize, result: &ScanResult) {
-    let status_icon = if result.tree_passed { "✓" } else { "✗" };
-    let status_text = if result.tree_passed { "PASS" } else { "FAIL" };
-    let status_color = if result.tree_passed {
-        "\x1b[32m"
+fn print_policy_result(num: usize, total: usize, result: &CountedScanResult, style: &ConsoleStyle) {
+    let status = if result.tree_passed {
+        style.paint(Color::Green, "✓ PASS")
     } else {
-        "\x1b[31m"
-    }; // Green or Red
-    let reset = "\x1b[0m";
+        style.paint(Color::Red, "✗ FAIL")
+    };
 
-    println!("┌───────────────────────────────────────────────────────────────────────────────┐");
-    println!("│ Policy {}/{}: {}", num, total, result.outcome.policy_id);
-    println!("├───────────────────────────────────────────────────────────────────────────────┤");
+    println!("{}", style.rule('┌', '─', '┐'));
+    println!(
+        "{}",
+        style.line(
+            '│',
+            &format!("Policy {}/{}: {}", num, total, result.outcome.policy_id)
+        )
+    );
+    println!("{}", style.rule('├', '─', '┤'));
+    println!("{}", style.line('│', &format!("Status:      {}", status)));
+    println!(
+        "{}",
+        style.line('│', &format!("Platform:    {}", result.outcome.platform))
+    );
     println!(
-        "│ Status:      {}{} {}{}",
-        status_color, status_icon, status_text, reset
+        "{}",
+        style.line(
+            '│',
+            &format!("Criticality: {:?}", result.outcome.criticality)
+        )
     );
-    println!("│ Platform:    {}", result.outcome.platform);
-    println!("│ Criticality: {:?}", result.outcome.criticality);
     println!(
-        "│ Criteria:    {}/{} passed",
-        result.criteria_counts.passed, result.criteria_counts.total
+        "{}",
+        style.line(
+            '│',
+            &format!("Criteria:    {}", criteria_label(result.criteria_counts))
+        )
     );
 
     // Print control mappings
@@ -56,225 +89,609 @@ fn print_policy_result(num: usize, total: usize, result: &ScanResult) {
             .iter()
             .map(|m| format!("{}:{}", m.framework, m.control_id))
             .collect();
-        println!("│ Controls:    {}", mappings.join(", "));
-    }
-
-    // Print findings if any
-    if !result.findings.is_empty() {
         println!(
-            "├───────────────────────────────────────────────────────────────────────────────┤"
+            "{}",
+            style.line('│', &format!("Controls:    {}", mappings.join(", ")))
         );
-        println!("│ Findings ({}):", result.findings.len());
-        for finding in &result.findings {
-            println!(
-                "│   • [{}] {}",
-                finding.severity.to_string().to_uppercase(),
-                finding.title
-            );
-            // Print description lines with proper indentation
-            for line in finding.description.lines().take(3) {
-                let truncated = if line.len() > 70 {
-                    format!("{}...", &line[..67])
-                } else {
-                    line.to_string()
-                };
-                println!("│       {}", truncated);
-            }
-        }
     }
 
-    println!("└───────────────────────────────────────────────────────────────────────────────┘");
+    // Print findings, errors and skipped criteria
+    for line in breakdown_lines(&criteria_breakdown(result), style) {
+        println!("{}", line);
+    }
+
+    println!("{}", style.rule('└', '─', '┘'));
     println!();
 }
 
-/// Print summary table
-fn print_summary_table(scan_results: &[ScanResult]) {
-    let total = scan_results.len();
-    let passed = scan_results.iter().filter(|r| r.tree_passed).count();
-    let failed = total - passed;
-
-    // Calculate by criticality
-    let mut critical_pass = 0;
-    let mut critical_fail = 0;
-    let mut high_pass = 0;
-    let mut high_fail = 0;
-    let mut medium_pass = 0;
-    let mut medium_fail = 0;
-    let mut low_pass = 0;
-    let mut low_fail = 0;
-    let mut info_pass = 0;
-    let mut info_fail = 0;
-
-    for result in scan_results {
-        let passed = result.tree_passed;
-        match result.outcome.criticality {
-            common::results::Criticality::Critical => {
-                if passed {
-                    critical_pass += 1;
-                } else {
-                    critical_fail += 1;
-                }
-            }
-            common::results::Criticality::High => {
-                if passed {
-                    high_pass += 1;
-                } else {
-                    high_fail += 1;
-                }
-            }
-            common::results::Criticality::Medium => {
-                if passed {
-                    medium_pass += 1;
-                } else {
-                    medium_fail += 1;
-                }
-            }
-            common::results::Criticality::Low => {
-                if passed {
-                    low_pass += 1;
-                } else {
-                    low_fail += 1;
-                }
+/// A finding as shown on the console
+#[derive(Debug, Clone)]
+struct FindingView<'a> {
+    finding_id: &'a str,
+    severity: String,
+    title: &'a str,
+    description: &'a str,
+}
+
+/// A criterion that could not be evaluated because collection failed
+#[derive(Debug, Clone)]
+struct ErroredCriterion<'a> {
+    finding_id: &'a str,
+    title: &'a str,
+    reasons: Vec<&'a str>,
+}
+
+/// `passed/applicable passed`, with the N/A count when there is one
+fn criteria_label(counts: CriteriaCounts) -> String {
+    let label = format!("{}/{} passed", counts.passed, counts.applicable());
+    match counts.not_applicable {
+        0 => label,
+        n => format!("{}, {} N/A", label, n),
+    }
+}
+
+/// Criteria of one policy, grouped by how they are displayed
+#[derive(Debug, Default)]
+struct CriteriaBreakdown<'a> {
+    /// Findings for criteria that were checked and failed
+    failures: Vec<FindingView<'a>>,
+    /// Criteria that errored, with the reason from their finding
+    errors: Vec<ErroredCriterion<'a>>,
+    /// Errored criteria with no finding to explain them
+    unexplained_errors: usize,
+    /// Criteria marked not applicable
+    not_applicable: usize,
+}
+
+/// Group a policy's criteria for display
+fn criteria_breakdown(result: &CountedScanResult) -> CriteriaBreakdown<'_> {
+    let findings = result
+        .findings
+        .iter()
+        .map(|finding| FindingView {
+            finding_id: &finding.finding_id,
+            severity: finding.severity.to_string().to_uppercase(),
+            title: &finding.title,
+            description: &finding.description,
+        })
+        .collect();
+
+    breakdown_from(findings, result.criteria_counts)
+}
+
+/// Split findings into failures and collection errors, and count N/A criteria
+fn breakdown_from(findings: Vec<FindingView<'_>>, counts: CriteriaCounts) -> CriteriaBreakdown<'_> {
+    let marker = format!("[{}]", COLLECTION_ERROR_CATEGORY);
+    let mut breakdown = CriteriaBreakdown::default();
+
+    for finding in findings {
+        let reasons: Vec<&str> = finding
+            .description
+            .lines()
+            .filter(|line| line.contains(&marker))
+            .map(|line| line.trim().trim_start_matches("- ").trim())
+            .collect();
+
+        if reasons.is_empty() {
+            breakdown.failures.push(finding);
+        } else {
+            breakdown.errors.push(ErroredCriterion {
+                finding_id: finding.finding_id,
+                title: finding.title,
+                reasons,
+            });
+        }
+    }
+
+    breakdown.unexplained_errors = counts.error.saturating_sub(breakdown.errors.len());
+    breakdown.not_applicable = counts.not_applicable;
+    breakdown
+}
+
+/// Render the findings, errors and not applicable subsections of a policy box
+fn breakdown_lines(breakdown: &CriteriaBreakdown<'_>, style: &ConsoleStyle) -> Vec<String> {
+    let mut lines = Vec::new();
+    let detail = |text: &str| style.line('│', &format!("      {}", style.fit(text, DETAIL_INDENT)));
+
+    if !breakdown.failures.is_empty() {
+        lines.push(style.rule('├', '─', '┤'));
+        lines.push(style.line('│', &format!("Findings ({}):", breakdown.failures.len())));
+        for finding in &breakdown.failures {
+            lines.push(style.line(
+                '│',
+                &format!(
+                    "  {} [{}] {}",
+                    style.paint(Color::Red, "•"),
+                    finding.severity,
+                    finding.title
+                ),
+            ));
+            // Description lines with proper indentation
+            for line in finding.description.lines().take(3) {
+                lines.push(detail(line));
             }
-            common::results::Criticality::Info => {
-                if passed {
-                    info_pass += 1;
-                } else {
-                    info_fail += 1;
-                }
+        }
+    }
+
+    let error_count = breakdown.errors.len() + breakdown.unexplained_errors;
+    if error_count > 0 {
+        lines.push(style.rule('├', '─', '┤'));
+        lines.push(style.line('│', &format!("Errors ({}):", error_count)));
+        for error in &breakdown.errors {
+            lines.push(style.line(
+                '│',
+                &format!(
+                    "  {} {}: {}",
+                    style.paint(Color::Yellow, "⚠"),
+                    error.finding_id,
+                    error.title
+                ),
+            ));
+            for reason in error.reasons.iter().take(3) {
+                lines.push(detail(reason));
             }
         }
+        if breakdown.unexplained_errors > 0 {
+            lines.push(style.line(
+                '│',
+                &format!(
+                    "  {} {} criteria errored during collection (see log for details)",
+                    style.paint(Color::Yellow, "⚠"),
+                    breakdown.unexplained_errors
+                ),
+            ));
+        }
     }
 
-    // Calculate posture score
-    let total_weight: f32 = scan_results
-        .iter()
-        .map(|r| criticality_weight(r.outcome.criticality))
-        .sum();
-    let passed_weight: f32 = scan_results
-        .iter()
-        .filter(|r| r.tree_passed)
-        .map(|r| criticality_weight(r.outcome.criticality))
-        .sum();
-    let posture_score = if total_weight > 0.0 {
-        (passed_weight / total_weight) * 100.0
-    } else {
-        0.0
-    };
+    if breakdown.not_applicable > 0 {
+        lines.push(style.rule('├', '─', '┤'));
+        lines.push(style.line(
+            '│',
+            &format!("Not Applicable ({}):", breakdown.not_applicable),
+        ));
+        lines.push(style.line(
+            '│',
+            &format!(
+                "  {} {} criteria do not apply to this host",
+                style.paint(Color::Gray, "○"),
+                breakdown.not_applicable
+            ),
+        ));
+    }
 
-    println!("╔═══════════════════════════════════════════════════════════════════════════════╗");
-    println!("║                                 SUMMARY                                       ║");
-    println!("╠═══════════════════════════════════════════════════════════════════════════════╣");
-    println!("║                                                                               ║");
-    println!(
-        "║   Total Policies: {:3}                                                        ║",
-        total
-    );
-    println!("║   \x1b[32mPassed:\x1b[0m          {:3}                                                        ║", passed);
-    println!("║   \x1b[31mFailed:\x1b[0m          {:3}                                                        ║", failed);
-    println!("║                                                                               ║");
-    println!("╠═══════════════════════════════════════════════════════════════════════════════╣");
-    println!(
-        "║   Posture Score: {:5.1}%                                                      ║",
-        posture_score
-    );
-    println!("╠═══════════════════════════════════════════════════════════════════════════════╣");
-    println!("║                                                                               ║");
-    println!("║   By Criticality:        Pass    Fail    Total                                ║");
-    println!("║   ─────────────────────────────────────────                                   ║");
+    lines
+}
 
-    if critical_pass + critical_fail > 0 {
-        println!(
-            "║   Critical               {:3}     {:3}      {:3}                                  ║",
-            critical_pass,
-            critical_fail,
-            critical_pass + critical_fail
-        );
+/// What the summary table and posture score need from one policy
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PolicyPosture {
+    pub(crate) criticality: Criticality,
+    pub(crate) passed: bool,
+    /// False when the policy passed with every criterion marked not
+    /// applicable to this host
+    pub(crate) applicable: bool,
+}
+
+impl PolicyPosture {
+    pub(crate) fn from_result(result: &CountedScanResult) -> Self {
+        Self::new(
+            result.outcome.criticality,
+            result.tree_passed,
+            result.criteria_counts,
+        )
     }
-    if high_pass + high_fail > 0 {
-        println!(
-            "║   High                   {:3}     {:3}      {:3}                                  ║",
-            high_pass,
-            high_fail,
-            high_pass + high_fail
-        );
+
+    /// A failing policy is always applicable, so posture counts it as failed
+    /// just as the exit code does
+    fn new(criticality: Criticality, passed: bool, counts: CriteriaCounts) -> Self {
+        Self {
+            criticality,
+            passed,
+            applicable: !(passed && counts.none_applicable()),
+        }
     }
-    if medium_pass + medium_fail > 0 {
-        println!(
-            "║   Medium                 {:3}     {:3}      {:3}                                  ║",
-            medium_pass,
-            medium_fail,
-            medium_pass + medium_fail
-        );
+}
+
+/// Print summary table
+fn print_summary_table(scan_results: &[CountedScanResult], style: &ConsoleStyle) {
+    let policies: Vec<PolicyPosture> = scan_results
+        .iter()
+        .map(PolicyPosture::from_result)
+        .collect();
+    for line in summary_table_lines(&policies, style) {
+        println!("{}", line);
     }
-    if low_pass + low_fail > 0 {
-        println!(
-            "║   Low                    {:3}     {:3}      {:3}                                  ║",
-            low_pass,
-            low_fail,
-            low_pass + low_fail
-        );
+    println!();
+}
+
+/// Render the summary table
+fn summary_table_lines(policies: &[PolicyPosture], style: &ConsoleStyle) -> Vec<String> {
+    let total = policies.len();
+    let not_applicable = policies.iter().filter(|p| !p.applicable).count();
+    let passed = policies.iter().filter(|p| p.applicable && p.passed).count();
+    let failed = total - not_applicable - passed;
+    let posture = Posture::of(policies);
+
+    let blank = style.framed('║', "");
+    let divider = style.rule('╠', '═', '╣');
+
+    let mut lines = vec![
+        style.rule('╔', '═', '╗'),
+        style.centered('║', "SUMMARY"),
+        divider.clone(),
+        blank.clone(),
+        style.framed('║', &format!("  Total Policies: {:3}", total)),
+        style.framed(
+            '║',
+            &format!(
+                "  {}          {:3}",
+                style.paint(Color::Green, "Passed:"),
+                passed
+            ),
+        ),
+        style.framed(
+            '║',
+            &format!(
+                "  {}          {:3}",
+                style.paint(Color::Red, "Failed:"),
+                failed
+            ),
+        ),
+    ];
+    if not_applicable > 0 {
+        lines.push(style.framed(
+            '║',
+            &format!(
+                "  {}  {:3}",
+                style.paint(Color::Gray, "Not Applicable:"),
+                not_applicable
+            ),
+        ));
     }
-    if info_pass + info_fail > 0 {
-        println!(
-            "║   Info                   {:3}     {:3}      {:3}                                  ║",
-            info_pass,
-            info_fail,
-            info_pass + info_fail
-        );
+    lines.extend([
+        blank.clone(),
+        divider.clone(),
+        style.framed(
+            '║',
+            &format!("  Posture Score: {:5.1}%", posture.posture_score),
+        ),
+        divider,
+        blank.clone(),
+        style.framed('║', "  By Criticality:        Pass    Fail    N/A    Total"),
+        style.framed('║', "  ───────────────────────────────────────────────────"),
+    ]);
+
+    for (label, counts) in posture.by_criticality.rows() {
+        if counts.total() > 0 {
+            lines.push(style.framed(
+                '║',
+                &format!(
+                    "  {:<22} {:3}     {:3}     {:3}      {:3}",
+                    label,
+                    counts.passed,
+                    counts.failed,
+                    counts.not_applicable,
+                    counts.total()
+                ),
+            ));
+        }
     }
 
-    println!("║                                                                               ║");
-    println!("╚═══════════════════════════════════════════════════════════════════════════════╝");
-    println!();
+    lines.push(blank);
+    lines.push(style.rule('╚', '═', '╝'));
+    lines
 }
 
-/// Get weight for criticality level
-fn criticality_weight(criticality: common::results::Criticality) -> f32 {
+/// Position of a criticality, from Critical (0) to Info (4)
+pub(crate) fn criticality_index(criticality: Criticality) -> usize {
     match criticality {
-        common::results::Criticality::Critical => 1.0,
-        common::results::Criticality::High => 0.8,
-        common::results::Criticality::Medium => 0.5,
-        common::results::Criticality::Low => 0.3,
-        common::results::Criticality::Info => 0.1,
+        Criticality::Critical => 0,
+        Criticality::High => 1,
+        Criticality::Medium => 2,
+        Criticality::Low => 3,
+        Criticality::Info => 4,
     }
 }
 
 /// Print a compact single-line result for progress output
-pub fn print_progress_result(num: usize, total: usize, result: &ScanResult) {
-    let status_icon = if result.tree_passed { "✓" } else { "✗" };
-    let status_color = if result.tree_passed {
-        "\x1b[32m"
-    } else {
-        "\x1b[31m"
-    };
-    let reset = "\x1b[0m";
-
+pub fn print_progress_result(
+    num: usize,
+    total: usize,
+    result: &CountedScanResult,
+    style: &ConsoleStyle,
+) {
     if result.tree_passed {
+        let counts = result.criteria_counts;
+        let not_applicable = match counts.not_applicable {
+            0 => String::new(),
+            n => format!(", {} N/A", n),
+        };
         println!(
-            "[{}/{}] {}{}{} {} ({}/{} criteria)",
+            "[{}/{}] {} {} ({}/{} criteria{})",
             num,
             total,
-            status_color,
-            status_icon,
-            reset,
+            style.paint(Color::Green, "✓"),
             result.outcome.policy_id,
-            result.criteria_counts.passed,
-            result.criteria_counts.total
+            counts.passed,
+            counts.applicable(),
+            not_applicable
         );
     } else {
+        let breakdown = criteria_breakdown(result);
+        let errors = breakdown.errors.len() + breakdown.unexplained_errors;
+        let counts = if errors > 0 {
+            format!("{} findings, {} errors", breakdown.failures.len(), errors)
+        } else {
+            format!("{} findings", breakdown.failures.len())
+        };
         println!(
-            "[{}/{}] {}{}{} {} ({} findings)",
+            "[{}/{}] {} {} ({})",
             num,
             total,
-            status_color,
-            status_icon,
-            reset,
+            style.paint(Color::Red, "✗"),
             result.outcome.policy_id,
-            result.findings.len()
+            counts
+        );
+        for finding in &breakdown.failures {
+            let line = format!("{}: {}", finding.finding_id, finding.title);
+            println!("{}{}", style.text("       └─ "), style.fit(&line, 10));
+        }
+        for error in &breakdown.errors {
+            let line = format!("{}: {}", error.finding_id, error.title);
+            println!(
+                "{}{} {}",
+                style.text("       └─ "),
+                style.paint(Color::Yellow, "⚠"),
+                style.fit(&line, 12)
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contract_kit::execution_api::{ExitCodePolicy, ScanCounts};
+
+    const ERROR_DESCRIPTION: &str = "File metadata validation could not be completed:\n  - Object 'shadow': [collection_error] collector 'filesystem_collector' could not collect object (access_denied): Permission denied";
+
+    fn finding<'a>(finding_id: &'a str, title: &'a str, description: &'a str) -> FindingView<'a> {
+        FindingView {
+            finding_id,
+            severity: "HIGH".to_string(),
+            title,
+            description,
+        }
+    }
+
+    /// Two passed, one failed, one errored, one not applicable
+    fn mixed_breakdown() -> CriteriaBreakdown<'static> {
+        breakdown_from(
+            vec![
+                finding(
+                    "ssh-root-login",
+                    "Root login enabled",
+                    "PermitRootLogin is yes",
+                ),
+                finding("shadow-perms", "Shadow permissions", ERROR_DESCRIPTION),
+            ],
+            CriteriaCounts {
+                total: 5,
+                passed: 2,
+                failed: 1,
+                error: 1,
+                not_applicable: 1,
+            },
+        )
+    }
+
+    #[test]
+    fn test_breakdown_separates_errors_from_failures() {
+        let breakdown = mixed_breakdown();
+
+        assert_eq!(breakdown.failures.len(), 1);
+        assert_eq!(breakdown.failures[0].finding_id, "ssh-root-login");
+        assert_eq!(breakdown.errors.len(), 1);
+        assert_eq!(breakdown.errors[0].finding_id, "shadow-perms");
+        assert!(breakdown.errors[0].reasons[0].starts_with("Object 'shadow'"));
+        assert_eq!(breakdown.unexplained_errors, 0);
+        assert_eq!(breakdown.not_applicable, 1);
+    }
+
+    #[test]
+    fn test_breakdown_lines_sections() {
+        let lines = breakdown_lines(&mixed_breakdown(), &ConsoleStyle::plain(80));
+        let text = lines.join("\n");
+
+        assert!(text.contains("│ Findings (1):"));
+        assert!(text.contains("[HIGH] Root login enabled"));
+        assert!(text.contains("│ Errors (1):"));
+        assert!(text.contains("  ⚠ shadow-perms: Shadow permissions"));
+        assert!(text.contains("│       Object 'shadow': [collection_error]"));
+        assert!(text.contains("│ Not Applicable (1):"));
+
+        // Sections appear in a fixed order
+        let findings = text.find("Findings (").unwrap();
+        let errors = text.find("Errors (").unwrap();
+        let not_applicable = text.find("Not Applicable (").unwrap();
+        assert!(findings < errors && errors < not_applicable);
+    }
+
+    #[test]
+    fn test_fail_without_findings_is_explained() {
+        let breakdown = breakdown_from(
+            Vec::new(),
+            CriteriaCounts {
+                total: 3,
+                passed: 1,
+                failed: 0,
+                error: 2,
+                not_applicable: 0,
+            },
+        );
+        let text = breakdown_lines(&breakdown, &ConsoleStyle::plain(80)).join("\n");
+
+        assert!(!text.contains("Findings ("));
+        assert!(text.contains("│ Errors (2):"));
+        assert!(text.contains("2 criteria errored during collection"));
+        assert!(!text.contains("Not Applicable ("));
+    }
+
+    #[test]
+    fn test_all_passed_has_no_sections() {
+        let breakdown = breakdown_from(
+            Vec::new(),
+            CriteriaCounts {
+                total: 4,
+                passed: 4,
+                failed: 0,
+                error: 0,
+                not_applicable: 0,
+            },
         );
-        for finding in &result.findings {
-            println!("       └─ {}: {}", finding.finding_id, finding.title);
+        assert!(breakdown_lines(&breakdown, &ConsoleStyle::plain(80)).is_empty());
+    }
+
+    #[test]
+    fn test_lines_fit_width_without_color() {
+        let style = ConsoleStyle::new(false, 50);
+        let lines = breakdown_lines(&mixed_breakdown(), &style);
+
+        assert!(lines.iter().all(|l| !l.contains('\x1b')));
+        assert!(lines.iter().all(|l| l.chars().count() <= 50));
+    }
+
+    #[test]
+    fn test_colored_lines_keep_escapes() {
+        let style = ConsoleStyle::new(true, 80);
+        let text = breakdown_lines(&mixed_breakdown(), &style).join("\n");
+
+        assert!(text.contains("\x1b[33m⚠\x1b[0m shadow-perms"));
+    }
+
+    fn posture(criticality: Criticality, passed: bool, applicable: bool) -> PolicyPosture {
+        PolicyPosture {
+            criticality,
+            passed,
+            applicable,
         }
     }
+
+    #[test]
+    fn test_not_applicable_criteria_leave_pass_counts() {
+        let counts = CriteriaCounts {
+            total: 6,
+            passed: 3,
+            failed: 0,
+            error: 0,
+            not_applicable: 3,
+        };
+        assert_eq!(counts.applicable(), 3);
+        assert_eq!(criteria_label(counts), "3/3 passed, 3 N/A");
+
+        let counts = CriteriaCounts {
+            total: 3,
+            passed: 2,
+            failed: 1,
+            error: 0,
+            not_applicable: 0,
+        };
+        assert_eq!(criteria_label(counts), "2/3 passed");
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_scanned_not_applicable_policy() {
+        let result = crate::output::tests::not_applicable_scan();
+        assert_eq!(criteria_label(result.criteria_counts), "0/0 passed, 1 N/A");
+
+        let breakdown = criteria_breakdown(&result);
+        assert!(breakdown.failures.is_empty() && breakdown.errors.is_empty());
+        assert_eq!(breakdown.not_applicable, 1);
+        let text = breakdown_lines(&breakdown, &ConsoleStyle::plain(80)).join("\n");
+        assert!(text.contains("│ Not Applicable (1):"));
+
+        let posture = PolicyPosture::from_result(&result);
+        assert!(posture.passed && !posture.applicable);
+        let text = summary_table_lines(&[posture], &ConsoleStyle::plain(80)).join("\n");
+        assert!(text.contains("Passed:            0"));
+        assert!(text.contains("Not Applicable:    1"));
+    }
+
+    #[test]
+    fn test_summary_table_reports_not_applicable() {
+        let policies = [
+            posture(Criticality::High, true, true),
+            posture(Criticality::High, false, true),
+            posture(Criticality::High, false, false),
+            posture(Criticality::Low, true, false),
+        ];
+        let text = summary_table_lines(&policies, &ConsoleStyle::plain(80)).join("\n");
+
+        assert!(text.contains("Total Policies:   4"));
+        assert!(text.contains("Passed:            1"));
+        assert!(text.contains("Failed:            1"));
+        assert!(text.contains("Not Applicable:    2"));
+        assert!(text.contains("Posture Score:  50.0%"));
+        assert!(text.contains(&format!(
+            "{:<22} {:3}     {:3}     {:3}      {:3}",
+            "High", 1, 1, 1, 3
+        )));
+        assert!(text.contains(&format!(
+            "{:<22} {:3}     {:3}     {:3}      {:3}",
+            "Low", 0, 0, 1, 1
+        )));
+
+        let all_applicable =
+            summary_table_lines(&policies[..2], &ConsoleStyle::plain(80)).join("\n");
+        assert!(!all_applicable.contains("Not Applicable:"));
+    }
+
+    #[test]
+    fn test_posture_and_exit_code_agree() {
+        let counts = |total, passed| CriteriaCounts {
+            total,
+            passed,
+            failed: 0,
+            error: 0,
+            not_applicable: total - passed,
+        };
+        // (tree passed, criteria counts after counting marked criteria)
+        let run = [
+            // Failed with no criterion evaluated
+            (false, counts(0, 0)),
+            // Failed with every criterion marked, e.g. under a negated block
+            (false, counts(2, 0)),
+            // Passed with every criterion marked
+            (true, counts(2, 0)),
+            (true, counts(3, 3)),
+        ];
+        let policies: Vec<PolicyPosture> = run
+            .iter()
+            .map(|&(passed, counts)| PolicyPosture::new(Criticality::High, passed, counts))
+            .collect();
+        let passed = run.iter().filter(|(passed, _)| *passed).count();
+        let scan = ScanCounts {
+            passed,
+            failed: run.len() - passed,
+            errors: 0,
+        };
+
+        let text = summary_table_lines(&policies, &ConsoleStyle::plain(80)).join("\n");
+        assert!(text.contains("Passed:            1"));
+        assert!(text.contains("Failed:            2"));
+        assert!(text.contains("Not Applicable:    1"));
+        assert_eq!(scan.failed, 2);
+        assert_eq!(ExitCodePolicy::default().exit_code(&scan), 1);
+    }
 }
@@ -0,0 +1,140 @@
+//! CSV summary builder
+//!
+//! Flattens the summary into one row per policy for spreadsheets, with the
+//! policy's control mappings joined by `;` in a single column. The header
+//! row is always written, so a scan with no results still produces a valid
+//! file. Policies that could not be scanned get a row with their file path
+//! in place of the policy ID and status `Error`.
+//!
+//! Like the summary, CSV output has no envelope and is not signed.
+
+use contract_kit::execution_api::CountedScanResult;
+
+use super::scan_errors::PolicyError;
+use super::summary::build_policy_summary;
+
+/// Column names, in order
+pub const CSV_COLUMNS: [&str; 9] = [
+    "policy_id",
+    "platform",
+    "criticality",
+    "status",
+    "criteria_total",
+    "criteria_passed",
+    "criteria_failed",
+    "findings_count",
+    "controls",
+];
+
+/// Build the CSV summary of a scan, rows in scan order
+pub fn build_csv(scan_results: &[CountedScanResult], errors: &[PolicyError]) -> String {
+    let mut csv = csv_row(CSV_COLUMNS.iter().map(|column| column.to_string()));
+
+    for result in scan_results {
+        let summary = build_policy_summary(result);
+        let status = if summary.not_applicable {
+            "NotApplicable".to_string()
+        } else {
+            summary.outcome
+        };
+        let controls: Vec<String> = result
+            .outcome
+            .control_mappings
+            .iter()
+            .map(|m| format!("{}:{}", m.framework, m.control_id))
+            .collect();
+        csv.push_str(&csv_row([
+            summary.policy_id,
+            summary.platform,
+            summary.criticality,
+            status,
+            summary.criteria_counts.total.to_string(),
+            summary.criteria_counts.passed.to_string(),
+            summary.criteria_counts.failed.to_string(),
+            summary.findings_count.to_string(),
+            controls.join(";"),
+        ]));
+    }
+
+    for error in errors {
+        csv.push_str(&csv_row([
+            error.path.clone(),
+            String::new(),
+            String::new(),
+            "Error".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+            String::new(),
+        ]));
+    }
+
+    csv
+}
+
+/// One CSV line, CRLF-terminated as RFC 4180 specifies
+fn csv_row(fields: impl IntoIterator<Item = String>) -> String {
+    let fields: Vec<String> = fields.into_iter().map(|f| escape_field(&f)).collect();
+    format!("{}\r\n", fields.join(","))
+}
+
+/// Quote a field containing a comma, quote or line break, doubling quotes
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_without_results() {
+        assert_eq!(
+            build_csv(&[], &[]),
+            "policy_id,platform,criticality,status,criteria_total,criteria_passed,\
+             criteria_failed,findings_count,controls\r\n"
+        );
+    }
+
+    #[test]
+    fn test_fields_are_escaped() {
+        assert_eq!(escape_field("CIS:5.2.1"), "CIS:5.2.1");
+        assert_eq!(escape_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(
+            csv_row(["x".to_string(), "y,z".to_string(), String::new()]),
+            "x,\"y,z\",\r\n"
+        );
+    }
+
+    #[test]
+    fn test_unscanned_policies_are_error_rows() {
+        use contract_kit::execution_api::ScanError;
+
+        let errors = vec![PolicyError::new(
+            std::path::Path::new("policies/broken, old.esp"),
+            &ScanError::compilation_failed("broken.esp:2:1: error: expected META"),
+        )];
+        let csv = build_csv(&[], &errors);
+        let rows: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1], "\"policies/broken, old.esp\",,,Error,0,0,0,0,");
+    }
+}
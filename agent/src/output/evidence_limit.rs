@@ -0,0 +1,203 @@
+//! Evidence size limit
+//!
+//! Collected evidence is embedded in full results and assessor packages as
+//! collected, so a `file_content` check against a large file can push the
+//! output past downstream ingestion limits. Any evidence value larger than
+//! the limit is replaced in the serialized output with a placeholder:
+//!
+//! ```json
+//! { "truncated": true, "sha256": "…", "original_len": 5242880, "preview": "…" }
+//! ```
+//!
+//! `sha256` and `original_len` describe the original value: the UTF-8 bytes
+//! of a string, or the bytes of a binary value. `preview` is the start of a
+//! string, or the first bytes of a binary value in hex. The envelope's
+//! `evidence_hash` is computed by the engine before serialization, so it
+//! still covers the full original evidence.
+//!
+//! Only values under an `evidence` key are limited; findings are unchanged.
+
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+/// Default limit for a single evidence value (1 MiB)
+pub const DEFAULT_MAX_EVIDENCE_BYTES: usize = 1024 * 1024;
+
+/// Bytes of a string kept in the placeholder preview
+const PREVIEW_BYTES: usize = 256;
+
+/// Bytes of a binary value kept in the placeholder preview, as hex
+const BINARY_PREVIEW_BYTES: usize = 32;
+
+/// Replace evidence values larger than `max_bytes` with placeholders
+///
+/// Returns the number of values replaced.
+pub fn limit_evidence(result: &mut Value, max_bytes: usize) -> usize {
+    match result {
+        Value::Object(map) => map
+            .iter_mut()
+            .map(|(key, value)| {
+                if key == "evidence" {
+                    limit_values(value, max_bytes)
+                } else {
+                    limit_evidence(value, max_bytes)
+                }
+            })
+            .sum(),
+        Value::Array(items) => items
+            .iter_mut()
+            .map(|item| limit_evidence(item, max_bytes))
+            .sum(),
+        _ => 0,
+    }
+}
+
+/// Replace every oversized string or byte array within an evidence value
+fn limit_values(value: &mut Value, max_bytes: usize) -> usize {
+    if let Some(placeholder) = oversized_placeholder(value, max_bytes) {
+        *value = placeholder;
+        return 1;
+    }
+    match value {
+        Value::Object(map) => map.values_mut().map(|v| limit_values(v, max_bytes)).sum(),
+        Value::Array(items) => items.iter_mut().map(|v| limit_values(v, max_bytes)).sum(),
+        _ => 0,
+    }
+}
+
+/// Placeholder for a string or binary value over the limit
+fn oversized_placeholder(value: &Value, max_bytes: usize) -> Option<Value> {
+    match value {
+        Value::String(s) if s.len() > max_bytes => {
+            Some(placeholder(s.as_bytes(), preview_str(s).to_string()))
+        }
+        Value::Array(items) if items.len() > max_bytes => {
+            let bytes = as_bytes(items)?;
+            let preview = hex::encode(bytes.get(..BINARY_PREVIEW_BYTES).unwrap_or(&bytes));
+            Some(placeholder(&bytes, preview))
+        }
+        _ => None,
+    }
+}
+
+/// Serialized binary data: an array of integers from 0 to 255
+fn as_bytes(items: &[Value]) -> Option<Vec<u8>> {
+    items
+        .iter()
+        .map(|item| item.as_u64().and_then(|n| u8::try_from(n).ok()))
+        .collect()
+}
+
+/// Start of a string, cut at a character boundary
+fn preview_str(s: &str) -> &str {
+    let mut end = PREVIEW_BYTES.min(s.len());
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    s.get(..end).unwrap_or_default()
+}
+
+fn placeholder(original: &[u8], preview: String) -> Value {
+    let mut map = Map::new();
+    map.insert("truncated".to_string(), Value::Bool(true));
+    map.insert(
+        "sha256".to_string(),
+        Value::String(hex::encode(Sha256::digest(original))),
+    );
+    map.insert("original_len".to_string(), Value::from(original.len()));
+    map.insert("preview".to_string(), Value::String(preview));
+    Value::Object(map)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_small_values_pass_through() {
+        let mut result = json!({
+            "policies": [{
+                "evidence": {
+                    "data": { "file_content": "PermitRootLogin no", "file_size": 18 },
+                    "raw": [1, 2, 3]
+                }
+            }]
+        });
+        let original = result.clone();
+
+        assert_eq!(limit_evidence(&mut result, 64), 0);
+        assert_eq!(result, original);
+    }
+
+    #[test]
+    fn test_large_string_is_placeholdered() {
+        let content = "a".repeat(100);
+        let mut result = json!({
+            "envelope": { "evidence_hash": "sha256:abc" },
+            "policies": [{
+                "findings": [{ "actual": content }],
+                "evidence": { "data": { "file_content": content, "exists": true } }
+            }]
+        });
+
+        assert_eq!(limit_evidence(&mut result, 64), 1);
+
+        let data = &result["policies"][0]["evidence"]["data"];
+        assert_eq!(data["exists"], json!(true));
+        let placeholder = &data["file_content"];
+        assert_eq!(placeholder["truncated"], json!(true));
+        assert_eq!(
+            placeholder["sha256"],
+            json!("2816597888e4a0d3a36b82b83316ab32680eb8f00f8cd3b904d681246d285a0e")
+        );
+        assert_eq!(placeholder["original_len"], json!(100));
+        assert_eq!(placeholder["preview"], json!("a".repeat(100)));
+
+        // Only evidence is limited
+        assert_eq!(
+            result["policies"][0]["findings"][0]["actual"],
+            json!(content)
+        );
+        assert_eq!(result["envelope"]["evidence_hash"], json!("sha256:abc"));
+    }
+
+    #[test]
+    fn test_large_binary_is_placeholdered() {
+        let bytes: Vec<u8> = (0..40).collect();
+        let mut result = json!({ "evidence": { "Binary": bytes } });
+
+        assert_eq!(limit_evidence(&mut result, 16), 1);
+
+        let placeholder = &result["evidence"]["Binary"];
+        assert_eq!(
+            placeholder["sha256"],
+            json!("5faa4eec3611556812c2d74b437c8c49add3f910f10063d801441f7d75cd5e3b")
+        );
+        assert_eq!(placeholder["original_len"], json!(40));
+        assert_eq!(
+            placeholder["preview"],
+            json!(hex::encode(
+                (0..BINARY_PREVIEW_BYTES as u8).collect::<Vec<u8>>()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_preview_respects_char_boundaries() {
+        let s = "é".repeat(PREVIEW_BYTES);
+        let preview = preview_str(&s);
+        assert_eq!(preview.len(), PREVIEW_BYTES);
+        assert_eq!(preview_str(&format!("x{}", s)).len(), PREVIEW_BYTES - 1);
+    }
+}
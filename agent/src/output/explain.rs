@@ -0,0 +1,109 @@
+//! Explain output
+//!
+//! Formats the collection plan produced by `--explain`: for each policy,
+//! which collector would handle each object and exactly what it would read
+//! or execute. Nothing in the plan has been collected.
+
+use std::path::{Path, PathBuf};
+
+use contract_kit::execution_api::PlannedCollection;
+use serde_json::Value;
+
+/// Collection plan for one policy file
+#[derive(Debug, Clone)]
+pub struct PolicyPlan {
+    pub esp_file: PathBuf,
+    pub collections: Vec<PlannedCollection>,
+}
+
+/// Print a human-readable collection plan for each policy
+pub fn print_explain_plan(plans: &[PolicyPlan]) {
+    println!();
+    println!("COLLECTION PLAN (nothing was collected)");
+    println!();
+
+    for plan in plans {
+        print_policy_plan(&plan.esp_file, &plan.collections);
+    }
+}
+
+/// Print the plan for a single policy
+fn print_policy_plan(esp_file: &Path, collections: &[PlannedCollection]) {
+    println!("{}", esp_file.display());
+    if collections.is_empty() {
+        println!("  (no system collection)");
+        println!();
+        return;
+    }
+
+    for planned in collections {
+        let plan = &planned.plan;
+        println!(
+            "  {} [{}] via {}",
+            planned.object_id, planned.ctn_type, planned.collector_id
+        );
+        println!("    {:<9} {}", plan.kind.as_str(), plan.description);
+        println!("    target:   {}", plan.target);
+        if let Some(command) = &plan.command {
+            println!("    command:  {}", command);
+        }
+        for (name, value) in &plan.inputs {
+            println!("    {}: {}", name, value);
+        }
+    }
+    println!();
+}
+
+/// Build a structured report of the collection plan
+pub fn explain_report(plans: &[PolicyPlan]) -> Value {
+    let policies: Vec<Value> = plans
+        .iter()
+        .map(|plan| {
+            serde_json::json!({
+                "file": plan.esp_file.display().to_string(),
+                "collections": plan.collections,
+            })
+        })
+        .collect();
+
+    serde_json::json!({ "explain": true, "policies": policies })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contract_kit::collectors::{CollectionKind, CollectionPlan};
+
+    #[test]
+    fn test_report_structure() {
+        let plans = vec![PolicyPlan {
+            esp_file: PathBuf::from("audit.esp"),
+            collections: vec![PlannedCollection {
+                collector_id: "audit_rule_collector".to_string(),
+                ctn_type: "audit_rule".to_string(),
+                object_id: "identity_watch".to_string(),
+                plan: CollectionPlan::new(CollectionKind::Command, "List rules", "audit_rules")
+                    .with_command("/sbin/auditctl -l")
+                    .with_input("key", "identity"),
+            }],
+        }];
+
+        let report = explain_report(&plans);
+        let collection = &report["policies"][0]["collections"][0];
+        assert_eq!(report["policies"][0]["file"], "audit.esp");
+        assert_eq!(collection["object_id"], "identity_watch");
+        assert_eq!(collection["plan"]["kind"], "command");
+        assert_eq!(collection["plan"]["command"], "/sbin/auditctl -l");
+        assert_eq!(collection["plan"]["inputs"]["key"], "identity");
+    }
+}
@@ -9,7 +9,7 @@
 //! output formats.
 
 use common::results::{Evidence, FullResult, PolicyInput, ResultBuilder};
-use contract_kit::execution_api::ScanResult;
+use contract_kit::execution_api::CountedScanResult;
 
 use super::OutputError;
 use crate::output::combine_scan_hashes;
@@ -21,7 +21,7 @@ use crate::output::combine_scan_hashes;
 /// Uses pre-computed hashes from `ScanResult` rather than recomputing them.
 /// This ensures the full result's hashes match those in attestations and
 /// assessor packages for the same scan.
-pub fn build_full_result(scan_results: &[ScanResult]) -> Result<FullResult, OutputError> {
+pub fn build_full_result(scan_results: &[CountedScanResult]) -> Result<FullResult, OutputError> {
     if scan_results.is_empty() {
         return Err(OutputError::Build(
             "At least one scan result is required".to_string(),
@@ -0,0 +1,443 @@
+//! Scan journal
+//!
+//! An append-only, hash-chained record of scan runs. Each run appends one
+//! JSON line:
+//!
+//! ```text
+//! { seq, timestamp, host, content_hash, evidence_hash,
+//!   prev_entry_hash, entry_hash, signature }
+//! ```
+//!
+//! `entry_hash` is the SHA-256 of the entry's other fields, including
+//! `prev_entry_hash`, the `entry_hash` of the entry before it (all zeros for
+//! the first entry). Modifying, removing or reordering a past entry breaks
+//! the chain at that entry. Every new head is signed with the run's signing
+//! backend over `entry_hash` and `prev_entry_hash`.
+//!
+//! Signing keys are per run, so a signature shows that an entry was signed
+//! by the key it names, not by a particular host. Cutting entries off the
+//! end leaves a valid, shorter chain: record the head `seq` and
+//! `entry_hash` that `--verify-journal` prints somewhere the host cannot
+//! rewrite.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use super::terminal::{Color, ConsoleStyle};
+use crate::signing::{verify_signature, SigningBackend, SigningResult};
+
+/// `prev_entry_hash` of the first entry
+pub const GENESIS_HASH: &str =
+    "sha256:0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Fields a journal signature covers
+const SIGNATURE_COVERS: [&str; 2] = ["entry_hash", "prev_entry_hash"];
+
+/// One scan run in the journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Position in the journal, from 0
+    pub seq: u64,
+    /// When the entry was appended (RFC 3339 UTC)
+    pub timestamp: String,
+    pub host: String,
+    pub content_hash: String,
+    pub evidence_hash: String,
+    pub prev_entry_hash: String,
+    pub entry_hash: String,
+    /// Signature block over `entry_hash` and `prev_entry_hash`
+    #[serde(default)]
+    pub signature: Option<Value>,
+}
+
+impl JournalEntry {
+    /// An unsigned entry following `prev` (`None` for the first entry)
+    pub fn next(
+        prev: Option<&JournalEntry>,
+        timestamp: impl Into<String>,
+        host: impl Into<String>,
+        content_hash: impl Into<String>,
+        evidence_hash: impl Into<String>,
+    ) -> Self {
+        let mut entry = Self {
+            seq: prev.map_or(0, |p| p.seq + 1),
+            timestamp: timestamp.into(),
+            host: host.into(),
+            content_hash: content_hash.into(),
+            evidence_hash: evidence_hash.into(),
+            prev_entry_hash: prev
+                .map_or_else(|| GENESIS_HASH.to_string(), |p| p.entry_hash.clone()),
+            entry_hash: String::new(),
+            signature: None,
+        };
+        entry.entry_hash = entry.compute_hash();
+        entry
+    }
+
+    /// SHA-256 of every field except `entry_hash` and `signature`
+    pub fn compute_hash(&self) -> String {
+        let fields = json!([
+            self.seq,
+            self.timestamp,
+            self.host,
+            self.content_hash,
+            self.evidence_hash,
+            self.prev_entry_hash,
+        ]);
+        let digest = Sha256::digest(fields.to_string().as_bytes());
+        format!("sha256:{}", hex::encode(digest))
+    }
+
+    /// Sign the entry as the new head of the journal
+    pub fn sign(&mut self, backend: &dyn SigningBackend) -> SigningResult<()> {
+        let mut block = backend.sign_envelope_hashes(&self.entry_hash, &self.prev_entry_hash)?;
+        block.covers = SIGNATURE_COVERS.iter().map(|f| f.to_string()).collect();
+        self.signature = serde_json::to_value(&block).ok();
+        Ok(())
+    }
+
+    /// Check the signature against `entry_hash` and `prev_entry_hash`
+    fn verify_signature(&self) -> Result<(), BreakReason> {
+        let block = self.signature.as_ref().ok_or(BreakReason::Unsigned)?;
+        let field = |name: &str| block.get(name).and_then(Value::as_str).unwrap_or_default();
+        verify_signature(
+            field("algorithm"),
+            field("public_key"),
+            field("signature"),
+            &self.entry_hash,
+            &self.prev_entry_hash,
+        )
+        .map_err(|e| BreakReason::BadSignature(e.to_string()))
+    }
+}
+
+/// Why the chain breaks at an entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreakReason {
+    /// The line is not a journal entry
+    Unparseable(String),
+    /// `seq` does not follow the previous entry
+    Sequence { expected: u64, found: u64 },
+    /// `prev_entry_hash` is not the previous entry's hash
+    PrevHash { expected: String, found: String },
+    /// The entry's fields do not hash to its `entry_hash`
+    EntryHash { computed: String, recorded: String },
+    /// The entry has no signature
+    Unsigned,
+    /// The signature does not verify
+    BadSignature(String),
+}
+
+impl std::fmt::Display for BreakReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unparseable(e) => write!(f, "not a journal entry: {}", e),
+            Self::Sequence { expected, found } => {
+                write!(f, "seq is {}, expected {}", found, expected)
+            }
+            Self::PrevHash { expected, found } => write!(
+                f,
+                "prev_entry_hash {} does not match the previous entry ({})",
+                found, expected
+            ),
+            Self::EntryHash { computed, recorded } => write!(
+                f,
+                "entry was modified: fields hash to {}, entry_hash is {}",
+                computed, recorded
+            ),
+            Self::Unsigned => write!(f, "entry is not signed"),
+            Self::BadSignature(e) => write!(f, "signature does not verify: {}", e),
+        }
+    }
+}
+
+/// First broken link in a journal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+    /// Line number in the journal file, from 1
+    pub line: usize,
+    /// `seq` of the entry, if it could be read
+    pub seq: Option<u64>,
+    pub reason: BreakReason,
+}
+
+/// Result of walking a journal's chain
+#[derive(Debug, Clone, Default)]
+pub struct JournalReport {
+    /// Entries verified before the first break
+    pub verified: usize,
+    /// Last verified entry
+    pub head: Option<JournalEntry>,
+    pub broken: Option<BrokenLink>,
+}
+
+impl JournalReport {
+    pub fn is_valid(&self) -> bool {
+        self.broken.is_none()
+    }
+}
+
+/// Walk the chain of a journal's contents, stopping at the first break
+pub fn verify_entries(content: &str) -> JournalReport {
+    let mut report = JournalReport::default();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let broken = |seq, reason| BrokenLink {
+            line: index + 1,
+            seq,
+            reason,
+        };
+        let entry: JournalEntry = match serde_json::from_str(line) {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.broken = Some(broken(None, BreakReason::Unparseable(e.to_string())));
+                break;
+            }
+        };
+        if let Err(reason) = check_link(report.head.as_ref(), &entry) {
+            report.broken = Some(broken(Some(entry.seq), reason));
+            break;
+        }
+        report.verified += 1;
+        report.head = Some(entry);
+    }
+    report
+}
+
+/// Check that `entry` correctly follows `prev`
+fn check_link(prev: Option<&JournalEntry>, entry: &JournalEntry) -> Result<(), BreakReason> {
+    let expected_seq = prev.map_or(0, |p| p.seq + 1);
+    if entry.seq != expected_seq {
+        return Err(BreakReason::Sequence {
+            expected: expected_seq,
+            found: entry.seq,
+        });
+    }
+    let expected_prev = prev.map_or(GENESIS_HASH, |p| p.entry_hash.as_str());
+    if entry.prev_entry_hash != expected_prev {
+        return Err(BreakReason::PrevHash {
+            expected: expected_prev.to_string(),
+            found: entry.prev_entry_hash.clone(),
+        });
+    }
+    let computed = entry.compute_hash();
+    if computed != entry.entry_hash {
+        return Err(BreakReason::EntryHash {
+            computed,
+            recorded: entry.entry_hash.clone(),
+        });
+    }
+    entry.verify_signature()
+}
+
+/// Verify the journal at `path`
+pub fn verify_journal(path: &Path) -> Result<JournalReport, JournalError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| JournalError::Read(path.to_path_buf(), e.to_string()))?;
+    Ok(verify_entries(&content))
+}
+
+/// Append a signed entry for a scan to the journal at `path`
+///
+/// The file is created if it does not exist. Without a backend the entry is
+/// appended unsigned, and verification reports it as a break.
+pub fn append_entry(
+    path: &Path,
+    timestamp: &str,
+    host: &str,
+    content_hash: &str,
+    evidence_hash: &str,
+    backend: Option<&dyn SigningBackend>,
+) -> Result<JournalEntry, JournalError> {
+    let prev = last_entry(path)?;
+    let mut entry = JournalEntry::next(prev.as_ref(), timestamp, host, content_hash, evidence_hash);
+    if let Some(backend) = backend {
+        if let Err(e) = entry.sign(backend) {
+            log::warn!(
+                "Failed to sign journal entry: {}. Entry will be unsigned.",
+                e
+            );
+        }
+    }
+
+    let line = serde_json::to_string(&entry)
+        .map_err(|e| JournalError::Write(path.to_path_buf(), e.to_string()))?;
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{}", line))
+        .map_err(|e| JournalError::Write(path.to_path_buf(), e.to_string()))?;
+    Ok(entry)
+}
+
+/// Last entry of the journal at `path`, or `None` if it is missing or empty
+fn last_entry(path: &Path) -> Result<Option<JournalEntry>, JournalError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(JournalError::Read(path.to_path_buf(), e.to_string())),
+    };
+    match content.lines().rev().find(|line| !line.trim().is_empty()) {
+        Some(line) => serde_json::from_str(line)
+            .map(Some)
+            .map_err(|e| JournalError::Corrupt(path.to_path_buf(), e.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Print the result of `--verify-journal`
+pub fn print_journal_report(report: &JournalReport, path: &Path, style: &ConsoleStyle) {
+    println!("Journal: {}", path.display());
+    println!("  Verified entries: {}", report.verified);
+    if let Some(head) = &report.head {
+        println!("  Head: seq {} {}", head.seq, head.entry_hash);
+    }
+    match &report.broken {
+        None => println!("  {}", style.paint(Color::Green, "Chain intact")),
+        Some(broken) => {
+            let at = match broken.seq {
+                Some(seq) => format!("line {} (seq {})", broken.line, seq),
+                None => format!("line {}", broken.line),
+            };
+            println!(
+                "  {} at {}: {}",
+                style.paint(Color::Red, "Chain broken"),
+                at,
+                broken.reason
+            );
+        }
+    }
+}
+
+/// Errors reading or appending to a journal
+#[derive(Debug)]
+pub enum JournalError {
+    /// Failed to read the journal
+    Read(PathBuf, String),
+    /// Failed to append to the journal
+    Write(PathBuf, String),
+    /// The last entry cannot be read, so nothing can be chained to it
+    Corrupt(PathBuf, String),
+}
+
+impl std::fmt::Display for JournalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JournalError::Read(path, e) => {
+                write!(f, "Failed to read journal {}: {}", path.display(), e)
+            }
+            JournalError::Write(path, e) => {
+                write!(f, "Failed to append to journal {}: {}", path.display(), e)
+            }
+            JournalError::Corrupt(path, e) => write!(
+                f,
+                "Last entry of journal {} is unreadable: {} (run --verify-journal)",
+                path.display(),
+                e
+            ),
+        }
+    }
+}
+
+impl std::error::Error for JournalError {}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::SoftwareBackend;
+
+    fn write_chain(name: &str, runs: usize) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("esp_journal_{}_{}.jsonl", name, std::process::id()));
+        std::fs::remove_file(&path).ok();
+        for run in 0..runs {
+            // A fresh key per run, as in real scans
+            let backend = SoftwareBackend::new().unwrap();
+            append_entry(
+                &path,
+                &format!("2026-10-18T12:00:0{}Z", run),
+                "web-01",
+                &format!("sha256:content{}", run),
+                &format!("sha256:evidence{}", run),
+                Some(&backend),
+            )
+            .unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_valid_three_entry_chain() {
+        let path = write_chain("valid", 3);
+        let report = verify_journal(&path).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert!(report.is_valid(), "{:?}", report.broken);
+        assert_eq!(report.verified, 3);
+        let head = report.head.unwrap();
+        assert_eq!(head.seq, 2);
+
+        let entries: Vec<JournalEntry> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(entries[0].prev_entry_hash, GENESIS_HASH);
+        assert_eq!(entries[1].prev_entry_hash, entries[0].entry_hash);
+        assert_eq!(entries[2].prev_entry_hash, entries[1].entry_hash);
+        assert_eq!(
+            entries[2].signature.as_ref().unwrap()["covers"],
+            json!(["entry_hash", "prev_entry_hash"])
+        );
+    }
+
+    #[test]
+    fn test_tampered_middle_entry_is_detected() {
+        let path = write_chain("tampered", 3);
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        let lines: Vec<&str> = content.lines().collect();
+
+        // Rewrite the middle entry's result
+        let tampered = lines[1].replace("sha256:content1", "sha256:forged");
+        let report = verify_entries(&[lines[0], &tampered, lines[2]].join("\n"));
+        assert_eq!(report.verified, 1);
+        let broken = report.broken.unwrap();
+        assert_eq!((broken.line, broken.seq), (2, Some(1)));
+        assert!(matches!(broken.reason, BreakReason::EntryHash { .. }));
+
+        // Recomputing its hash too leaves a signature that does not verify...
+        let mut forged: JournalEntry = serde_json::from_str(&tampered).unwrap();
+        forged.entry_hash = forged.compute_hash();
+        let forged_line = serde_json::to_string(&forged).unwrap();
+        let report = verify_entries(&[lines[0], forged_line.as_str(), lines[2]].join("\n"));
+        assert!(matches!(
+            report.broken.unwrap().reason,
+            BreakReason::BadSignature(_)
+        ));
+
+        // ...and deleting it breaks the chain at the entry after it
+        let report = verify_entries(&[lines[0], lines[2]].join("\n"));
+        let broken = report.broken.unwrap();
+        assert_eq!((broken.line, broken.seq), (2, Some(2)));
+        assert!(matches!(broken.reason, BreakReason::Sequence { .. }));
+    }
+}
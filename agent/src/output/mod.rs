@@ -4,8 +4,38 @@
 //! - Full results with evidence (signed)
 //! - Attestations (CUI-free, signed)
 //! - Summary (minimal, unsigned)
+//! - NDJSON (one summary line per policy as it finishes, unsigned)
+//! - CSV (one summary row per policy for spreadsheets, unsigned)
+//! - MessagePack (the full result, binary-encoded, signed)
 //! - Assessor package (full reproducibility, signed)
+//! - OSCAL assessment results (assessor package reshaped for GRC platforms,
+//!   signed)
 //! - Console (human-readable)
+//! - Posture (criticality-weighted score and counts, shared by the console
+//!   summary table, summaries, full results and metrics)
+//! - Baseline comparison (drift against a saved full result)
+//! - Explain (collection plan without collecting)
+//! - Check (policy criteria against collector contracts)
+//! - Prometheus metrics (posture gauges for a textfile collector)
+//! - Timings (slowest policies and criteria, `timings` block of full output
+//!   and assessor package)
+//! - Scan errors (`errors` array of full results, summaries and
+//!   attestations, one entry per policy that could not be scanned)
+//! - Scan journal (hash-chained, signed record of runs)
+//! - Scan checkpoints (policies an interrupted NDJSON scan completed)
+//! - Envelope signature verification of saved results
+//! - JSON Schemas of the output formats
+//!
+//! Signed envelopes can carry a [`HostInventory`] in their `host` section;
+//! it is not covered by the signature, which spans only the two hashes.
+//! Evidence values over the size limit are replaced with hashed
+//! placeholders (see `evidence_limit`); `evidence_hash` still covers the
+//! original evidence. Full results can also have secrets redacted from
+//! their evidence (see `redaction`), after which the evidence no longer
+//! matches `evidence_hash`.
+//!
+//! Console printers take a [`ConsoleStyle`] resolved once per run from
+//! `--color`, `--width`, `NO_COLOR` and the terminal.
 //!
 //! ## Hash Architecture
 //!
@@ -24,65 +54,278 @@
 
 mod assessor;
 mod attestation;
+mod baseline;
+mod check;
+mod checkpoint;
 mod console;
+mod csv;
+mod evidence_limit;
+mod explain;
 mod full;
+mod journal;
+mod msgpack;
+mod ndjson;
+mod oscal;
+mod prometheus;
+mod redaction;
+mod scan_errors;
+mod schema;
 mod summary;
+mod terminal;
+mod timings;
+mod verify;
 
 pub use assessor::build_assessor_package;
 pub use attestation::build_attestation;
+pub use baseline::{
+    compare_to_baseline, failing_criteria, print_baseline_diff, Baseline, BaselineError,
+};
+pub use check::{print_check_report, PolicyCheck};
+pub use checkpoint::{Checkpoint, CheckpointEntry, CheckpointError, CheckpointStatus};
+pub(crate) use console::criticality_index;
 pub use console::{print_progress_result, print_results};
+pub use csv::build_csv;
+pub use evidence_limit::DEFAULT_MAX_EVIDENCE_BYTES;
+pub use explain::{explain_report, print_explain_plan, PolicyPlan};
 pub use full::build_full_result;
+pub use journal::{append_entry, print_journal_report, verify_journal, JournalError};
+pub use ndjson::NdjsonWriter;
+pub use oscal::build_oscal;
+pub use prometheus::build_metrics;
+pub use redaction::Redaction;
+pub use scan_errors::{PolicyError, ScanPhase};
+pub use schema::output_schema;
 pub use summary::build_summary;
+pub use terminal::{Color, ColorMode, ConsoleStyle};
+pub use timings::{print_timings, ScanTimer, ScanTimings};
+pub use verify::{
+    print_verify_report, read_result_file, verify_result_file, EnvelopeReport, EnvelopeStatus,
+    VerifyError,
+};
 
 use crate::config::OutputFormat;
+use crate::inventory::{self, HostInventory};
 use crate::signing::{self, SigningBackend};
-use contract_kit::execution_api::ScanResult;
+use common::results::Criticality;
+use console::PolicyPosture;
+use contract_kit::execution_api::CountedScanResult;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// How collected evidence is embedded in output
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EvidenceOptions<'a> {
+    /// Largest evidence value embedded (None embeds everything)
+    pub max_bytes: Option<usize>,
+    /// Patterns redacted from evidence in full results
+    pub redaction: Option<&'a Redaction>,
+}
 
-/// Build output in the specified format
+/// Build output in the specified format, as the bytes of the file
 ///
-/// Results with envelopes (Full, Attestation, Assessor) are automatically signed.
-/// If signing fails, the result is returned unsigned with a warning logged.
+/// Every format is UTF-8 text except MessagePack, the full result in binary.
+/// Results with envelopes (Full, Attestation, Assessor, Oscal, MsgPack) are
+/// signed with the provided backend. Pass the same backend for every call in a run so all
+/// envelopes share one signer identity. If no backend is given or signing
+/// fails, the result is returned unsigned with a warning logged.
+///
+/// An `inventory` is attached to the envelope's `host` section; the summary
+/// and CSV formats have no envelope and ignore it. CSV output is returned
+/// as CSV text rather than JSON. Evidence values longer than
+/// `evidence.max_bytes` are replaced with placeholders; `None` embeds all
+/// evidence as collected. Full results have `evidence.redaction` applied
+/// first, and record it in a top-level `redaction` block. The full,
+/// MessagePack and assessor formats carry `timings`, if given, in a
+/// top-level `timings` block outside the hashed and signed content;
+/// attestations never do, as they leave the host.
+///
+/// Policies that could not be scanned are listed from `errors` in an
+/// `errors` array of full results and summaries, also outside the signed
+/// content, and as `Error` rows of CSV output. Attestations list only the
+/// phase of each error.
 pub fn build_output(
-    scan_results: &[ScanResult],
+    scan_results: &[CountedScanResult],
+    errors: &[PolicyError],
     format: OutputFormat,
-) -> Result<String, OutputError> {
-    // Create signing backend once (reused for all signatures)
-    let backend = create_signing_backend();
-
+    backend: Option<&dyn SigningBackend>,
+    inventory: Option<&HostInventory>,
+    evidence: EvidenceOptions<'_>,
+    timings: Option<&ScanTimings>,
+) -> Result<Vec<u8>, OutputError> {
     let json = match format {
         OutputFormat::Full => {
             let mut result = build_full_result(scan_results)?;
-            sign_if_available(&mut result.envelope, backend.as_deref());
-            serde_json::to_string_pretty(&result)
-                .map_err(|e| OutputError::Serialization(e.to_string()))?
+            sign_if_available(&mut result.envelope, backend);
+            serialize_envelope(
+                &result,
+                inventory,
+                evidence,
+                timings,
+                Some(&Posture::from_results(scan_results)),
+                Some(scan_errors::errors_json(errors)),
+            )?
         }
         OutputFormat::Attestation => {
+            // Attestations carry no evidence
             let mut result = build_attestation(scan_results)?;
-            sign_if_available(&mut result.envelope, backend.as_deref());
-            serde_json::to_string_pretty(&result)
-                .map_err(|e| OutputError::Serialization(e.to_string()))?
+            sign_if_available(&mut result.envelope, backend);
+            serialize_envelope(
+                &result,
+                inventory,
+                EvidenceOptions::default(),
+                None,
+                None,
+                Some(scan_errors::attestation_errors_json(errors)),
+            )?
         }
         OutputFormat::Summary => {
             // Summary format has no envelope - not signed
-            let result = build_summary(scan_results);
+            let result = build_summary(scan_results).with_errors(errors.to_vec());
             serde_json::to_string_pretty(&result)
                 .map_err(|e| OutputError::Serialization(e.to_string()))?
         }
+        OutputFormat::MsgPack => {
+            // The full result, binary-encoded; signed over the same hashes
+            let mut result = build_full_result(scan_results)?;
+            sign_if_available(&mut result.envelope, backend);
+            let value = envelope_value(
+                &result,
+                inventory,
+                evidence,
+                timings,
+                Some(&Posture::from_results(scan_results)),
+                Some(scan_errors::errors_json(errors)),
+            )?;
+            return msgpack::encode(&value);
+        }
+        OutputFormat::Csv => {
+            // Flat rows for spreadsheets - not signed
+            build_csv(scan_results, errors)
+        }
+        OutputFormat::Ndjson => {
+            // Written line by line while scanning, by `NdjsonWriter`
+            return Err(OutputError::Build(
+                "ndjson output is streamed during the scan".to_string(),
+            ));
+        }
         OutputFormat::Assessor => {
             let mut result = build_assessor_package(scan_results)?;
-            sign_if_available(&mut result.envelope, backend.as_deref());
-            serde_json::to_string_pretty(&result)
+            sign_if_available(&mut result.envelope, backend);
+            let evidence = EvidenceOptions {
+                redaction: None,
+                ..evidence
+            };
+            serialize_envelope(&result, inventory, evidence, timings, None, None)?
+        }
+        OutputFormat::Oscal => {
+            // The assessor package reshaped; its signed envelope rides along
+            let mut package = build_assessor_package(scan_results)?;
+            sign_if_available(&mut package.envelope, backend);
+            let evidence = EvidenceOptions {
+                redaction: None,
+                ..evidence
+            };
+            let package = envelope_value(&package, inventory, evidence, None, None, None)?;
+            serde_json::to_string_pretty(&build_oscal(scan_results, &package)?)
                 .map_err(|e| OutputError::Serialization(e.to_string()))?
         }
     };
-    Ok(json)
+    Ok(json.into_bytes())
 }
 
-/// Create the signing backend, logging any errors
+/// Serialize a result with an envelope, attaching the host inventory,
+/// timings, `posture` block and `errors` array if given, redacting evidence
+/// and limiting it to `evidence.max_bytes` per value
+fn serialize_envelope<T: serde::Serialize>(
+    result: &T,
+    inventory: Option<&HostInventory>,
+    evidence: EvidenceOptions<'_>,
+    timings: Option<&ScanTimings>,
+    posture: Option<&Posture>,
+    errors: Option<Value>,
+) -> Result<String, OutputError> {
+    if inventory.is_none()
+        && evidence.max_bytes.is_none()
+        && evidence.redaction.is_none()
+        && timings.is_none()
+        && posture.is_none()
+        && errors.is_none()
+    {
+        return serde_json::to_string_pretty(result)
+            .map_err(|e| OutputError::Serialization(e.to_string()));
+    }
+
+    let value = envelope_value(result, inventory, evidence, timings, posture, errors)?;
+    serde_json::to_string_pretty(&value).map_err(|e| OutputError::Serialization(e.to_string()))
+}
+
+/// A result with an envelope as JSON, with the additions of
+/// `serialize_envelope`
+fn envelope_value<T: serde::Serialize>(
+    result: &T,
+    inventory: Option<&HostInventory>,
+    evidence: EvidenceOptions<'_>,
+    timings: Option<&ScanTimings>,
+    posture: Option<&Posture>,
+    errors: Option<Value>,
+) -> Result<Value, OutputError> {
+    let mut value =
+        serde_json::to_value(result).map_err(|e| OutputError::Serialization(e.to_string()))?;
+    if let Some(inventory) = inventory {
+        if !inventory::attach_to_envelope(&mut value, inventory) {
+            log::warn!("Result envelope has no host section; host inventory omitted");
+        }
+    }
+    // Redact before limiting, so placeholder previews are redacted too
+    let redacted = evidence
+        .redaction
+        .map(|redaction| redaction.redact_evidence(&mut value));
+    if let Some(count) = redacted.filter(|count| *count > 0) {
+        log::warn!(
+            "Redacted {} evidence value(s); the full result no longer matches its evidence_hash",
+            count
+        );
+    }
+    if let Some(max_bytes) = evidence.max_bytes {
+        let truncated = evidence_limit::limit_evidence(&mut value, max_bytes);
+        if truncated > 0 {
+            log::info!(
+                "Replaced {} evidence value(s) over {} bytes with placeholders",
+                truncated,
+                max_bytes
+            );
+        }
+    }
+    if let (Some(timings), Value::Object(fields)) = (timings, &mut value) {
+        fields.insert("timings".to_string(), timings.to_json());
+    }
+    if let (Some(redaction), Some(count), Value::Object(fields)) =
+        (evidence.redaction, redacted, &mut value)
+    {
+        fields.insert(
+            "redaction".to_string(),
+            serde_json::json!({ "patterns": redaction.pattern_count(), "redacted_values": count }),
+        );
+    }
+    if let (Some(posture), Value::Object(fields)) = (posture, &mut value) {
+        let posture =
+            serde_json::to_value(posture).map_err(|e| OutputError::Serialization(e.to_string()))?;
+        fields.insert("posture".to_string(), posture);
+    }
+    if let (Some(errors), Value::Object(fields)) = (errors, &mut value) {
+        fields.insert("errors".to_string(), errors);
+    }
+    Ok(value)
+}
+
+/// Create the shared signing backend for a run, logging any errors
 ///
+/// Call once per run and pass the result to every `build_output()` call.
 /// Returns `None` if backend creation fails (graceful degradation).
-fn create_signing_backend() -> Option<Box<dyn SigningBackend>> {
-    match signing::create_backend() {
+pub fn create_signing_backend() -> Option<Arc<dyn SigningBackend>> {
+    match signing::create_shared_backend() {
         Ok(backend) => Some(backend),
         Err(e) => {
             log::warn!(
@@ -108,6 +351,132 @@ fn sign_if_available(
     }
 }
 
+// ============================================================================
+// Posture
+// ============================================================================
+
+/// Criticality-weighted posture of a scan
+///
+/// The one computation behind the console summary table, the `summary`
+/// totals, the `posture` block of full results and the metrics, so they
+/// never disagree. Passing policies with no applicable criteria are left out
+/// of the score and counted as not applicable; failing policies always count
+/// as failed, matching the exit code.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct Posture {
+    /// Criticality-weighted percentage of passing applicable policies,
+    /// 0-100 to one decimal place (0 with no applicable policies)
+    pub posture_score: f32,
+    /// Policy counts by criticality
+    pub by_criticality: CriticalityCounts,
+}
+
+/// Policy counts for each criticality
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct CriticalityCounts {
+    pub critical: PolicyCounts,
+    pub high: PolicyCounts,
+    pub medium: PolicyCounts,
+    pub low: PolicyCounts,
+    pub info: PolicyCounts,
+}
+
+/// Passing, failing and not applicable policies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PolicyCounts {
+    pub passed: usize,
+    pub failed: usize,
+    pub not_applicable: usize,
+}
+
+impl Posture {
+    /// Posture of these scan results
+    pub fn from_results(scan_results: &[CountedScanResult]) -> Self {
+        let policies: Vec<PolicyPosture> = scan_results
+            .iter()
+            .map(PolicyPosture::from_result)
+            .collect();
+        Self::of(&policies)
+    }
+
+    /// Posture of these policies
+    pub(crate) fn of(policies: &[PolicyPosture]) -> Self {
+        let mut by_criticality = CriticalityCounts::default();
+        let mut total_weight = 0.0;
+        let mut passed_weight = 0.0;
+        for policy in policies {
+            let counts = by_criticality.get_mut(policy.criticality);
+            if !policy.applicable {
+                counts.not_applicable += 1;
+                continue;
+            }
+            let weight = criticality_weight(policy.criticality);
+            total_weight += weight;
+            if policy.passed {
+                counts.passed += 1;
+                passed_weight += weight;
+            } else {
+                counts.failed += 1;
+            }
+        }
+
+        let posture_score = if total_weight > 0.0 {
+            (passed_weight / total_weight * 1000.0).round() / 10.0
+        } else {
+            0.0
+        };
+        Self {
+            posture_score,
+            by_criticality,
+        }
+    }
+}
+
+impl CriticalityCounts {
+    /// Counts of one criticality
+    fn get_mut(&mut self, criticality: Criticality) -> &mut PolicyCounts {
+        match criticality {
+            Criticality::Critical => &mut self.critical,
+            Criticality::High => &mut self.high,
+            Criticality::Medium => &mut self.medium,
+            Criticality::Low => &mut self.low,
+            Criticality::Info => &mut self.info,
+        }
+    }
+
+    /// Counts with their criticality label, from Critical to Info
+    pub(crate) fn rows(&self) -> [(&'static str, PolicyCounts); 5] {
+        [
+            ("Critical", self.critical),
+            ("High", self.high),
+            ("Medium", self.medium),
+            ("Low", self.low),
+            ("Info", self.info),
+        ]
+    }
+}
+
+impl PolicyCounts {
+    /// Every policy counted
+    pub(crate) fn total(&self) -> usize {
+        self.passed + self.failed + self.not_applicable
+    }
+}
+
+/// Weight of a criticality in the posture score
+fn criticality_weight(criticality: Criticality) -> f32 {
+    match criticality {
+        Criticality::Critical => 1.0,
+        Criticality::High => 0.8,
+        Criticality::Medium => 0.5,
+        Criticality::Low => 0.3,
+        Criticality::Info => 0.1,
+    }
+}
+
 // ============================================================================
 // Hash Helpers
 // ============================================================================
@@ -121,28 +490,36 @@ fn sign_if_available(
 ///
 /// A tuple of (content_hash, evidence_hash) to pass to result builders.
 pub(crate) fn combine_scan_hashes(
-    scan_results: &[ScanResult],
+    scan_results: &[CountedScanResult],
 ) -> Result<(String, String), OutputError> {
-    if scan_results.is_empty() {
-        return Err(OutputError::Build(
-            "At least one scan result is required".to_string(),
-        ));
-    }
+    let pairs: Vec<(&String, &String)> = scan_results
+        .iter()
+        .map(|r| (&r.content_hash, &r.evidence_hash))
+        .collect();
+    combine_hash_pairs(&pairs)
+}
 
-    // Single result: use hashes directly
-    if scan_results.len() == 1 {
-        let result = scan_results
-            .first()
-            .ok_or_else(|| OutputError::Build("Empty scan results".to_string()))?;
-        return Ok((result.content_hash.clone(), result.evidence_hash.clone()));
+/// Combine per-policy (content_hash, evidence_hash) pairs
+///
+/// The hashes of `combine_scan_hashes`, for callers that keep only the
+/// hashes of each result.
+pub(crate) fn combine_hash_pairs(
+    pairs: &[(&String, &String)],
+) -> Result<(String, String), OutputError> {
+    match pairs {
+        [] => Err(OutputError::Build(
+            "At least one scan result is required".to_string(),
+        )),
+        // Single result: use hashes directly
+        [(content_hash, evidence_hash)] => {
+            Ok((content_hash.to_string(), evidence_hash.to_string()))
+        }
+        // Multiple results: combine hashes deterministically
+        _ => Ok((
+            combine_hashes_sorted(pairs.iter().map(|(content, _)| *content))?,
+            combine_hashes_sorted(pairs.iter().map(|(_, evidence)| *evidence))?,
+        )),
     }
-
-    // Multiple results: combine hashes deterministically
-    let content_hash = combine_hashes_sorted(scan_results.iter().map(|r| &r.content_hash))?;
-
-    let evidence_hash = combine_hashes_sorted(scan_results.iter().map(|r| &r.evidence_hash))?;
-
-    Ok((content_hash, evidence_hash))
 }
 
 /// Combine multiple hashes into one (sorted for determinism)
@@ -202,3 +579,143 @@ impl From<common::results::ResultError> for OutputError {
         OutputError::Build(e.to_string())
     }
 }
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Windows-only policy, whose one criterion the registry executor
+    /// marks not applicable on any other host
+    #[cfg(not(windows))]
+    const REGISTRY_POLICY: &str = "\
+META
+    esp_id `lm-compatibility-level`
+    version `1.0.0`
+    dsl_schema_version `1.0.0`
+    platform `windows`
+    criticality `medium`
+    control_mapping `CIS:18.3.1`
+    title `LM authentication level is set`
+META_END
+
+DEF
+    OBJECT lsa
+        hive `HKLM`
+        key_path `SYSTEM\\CurrentControlSet\\Control\\Lsa`
+        value_name `LmCompatibilityLevel`
+    OBJECT_END
+
+    STATE present
+        exists boolean = true
+    STATE_END
+
+    CRI AND
+        CTN registry_value
+            TEST all all
+            STATE_REF present
+            OBJECT_REF lsa
+        CTN_END
+    CRI_END
+DEF_END
+";
+
+    /// Scan `REGISTRY_POLICY` with the agent's registry, counting the
+    /// criteria marked not applicable as the scanner does
+    #[cfg(not(windows))]
+    pub(crate) fn not_applicable_scan() -> CountedScanResult {
+        use crate::registry::{create_scanner_registry, CtnFilter};
+        use contract_kit::commands::CommandAllowlist;
+        use contract_kit::execution_api::scan_file;
+        use contract_kit::executors::CriterionTimer;
+        use std::sync::Arc;
+
+        let path =
+            std::env::temp_dir().join(format!("esp_not_applicable_{}.esp", std::process::id()));
+        std::fs::write(&path, REGISTRY_POLICY).unwrap();
+
+        let timer = CriterionTimer::new();
+        let filter = CtnFilter {
+            allow: vec!["registry_value".to_string()],
+            deny: Vec::new(),
+        };
+        let registry = create_scanner_registry(
+            &CommandAllowlist::new(),
+            None,
+            None,
+            None,
+            &filter,
+            &[],
+            &timer,
+        )
+        .unwrap();
+
+        let result = scan_file(&path, Arc::new(registry));
+        let _ = std::fs::remove_file(&path);
+        let marked = timer.take().iter().filter(|c| c.not_applicable).count();
+        CountedScanResult::new(result.unwrap(), marked)
+    }
+
+    fn policy(criticality: Criticality, passed: bool, applicable: bool) -> PolicyPosture {
+        PolicyPosture {
+            criticality,
+            passed,
+            applicable,
+        }
+    }
+
+    #[test]
+    fn test_posture_counts_applicable_policies_only() {
+        let applicable = [
+            policy(Criticality::Critical, true, true),
+            policy(Criticality::High, false, true),
+        ];
+        let with_na = [
+            applicable[0],
+            applicable[1],
+            policy(Criticality::Critical, false, false),
+            policy(Criticality::Info, true, false),
+        ];
+
+        // 1.0 of 1.8 weight passed
+        assert_eq!(Posture::of(&applicable).posture_score, 55.6);
+        assert_eq!(Posture::of(&with_na).posture_score, 55.6);
+
+        // Nothing applicable scores 0, like an empty scan
+        assert_eq!(Posture::of(&with_na[2..]).posture_score, 0.0);
+        assert_eq!(Posture::of(&[]), Posture::default());
+    }
+
+    #[test]
+    fn test_posture_counts_policies_by_criticality() {
+        let posture = Posture::of(&[
+            policy(Criticality::Critical, true, true),
+            policy(Criticality::Critical, false, true),
+            policy(Criticality::Critical, true, false),
+            policy(Criticality::Low, false, true),
+        ]);
+
+        let critical = posture.by_criticality.critical;
+        assert_eq!(
+            (critical.passed, critical.failed, critical.not_applicable),
+            (1, 1, 1)
+        );
+        assert_eq!(critical.total(), 3);
+        assert_eq!(posture.by_criticality.low.failed, 1);
+        assert_eq!(posture.by_criticality.info.total(), 0);
+
+        let json = serde_json::to_value(&posture).unwrap();
+        assert_eq!(json["by_criticality"]["critical"]["not_applicable"], 1);
+        assert_eq!(json["by_criticality"]["high"]["passed"], 0);
+        assert!(json["posture_score"].as_f64().unwrap() > 0.0);
+    }
+}
@@ -0,0 +1,80 @@
+//! MessagePack output
+//!
+//! `msgpack` output is the full result, with the same `timings`, `posture`,
+//! `errors` and host inventory, encoded as MessagePack instead of pretty
+//! JSON for metered or satellite links. Maps keep their field names, so a
+//! decoded file is the full result field for field. The envelope is signed
+//! over the same hashes, and `--verify` reads either encoding.
+//!
+//! `--decode` re-emits a saved result as pretty JSON.
+
+use serde_json::Value;
+
+use super::OutputError;
+
+/// Encode a result as MessagePack
+pub fn encode(result: &Value) -> Result<Vec<u8>, OutputError> {
+    rmp_serde::to_vec_named(result).map_err(|e| OutputError::Serialization(e.to_string()))
+}
+
+/// Parse a saved result, MessagePack or JSON
+///
+/// JSON results start with `{` or `[`, possibly after whitespace; anything
+/// else is decoded as MessagePack.
+pub fn parse_result(bytes: &[u8]) -> Result<Value, String> {
+    let first = bytes.iter().find(|b| !b.is_ascii_whitespace());
+    if matches!(first, Some(b'{' | b'[') | None) {
+        serde_json::from_slice(bytes).map_err(|e| e.to_string())
+    } else {
+        rmp_serde::from_slice(bytes).map_err(|e| format!("invalid MessagePack: {}", e))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn full_result() -> Value {
+        json!({
+            "envelope": {
+                "content_hash": "sha256:abc",
+                "evidence_hash": "sha256:def",
+                "signature": { "algorithm": "ecdsa-p256", "signer_id": "esp-agent" }
+            },
+            "policies": [{ "policy_id": "ssh-hardening", "passed": false, "weight": 0.75 }],
+            "errors": []
+        })
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let result = full_result();
+        let bytes = encode(&result).unwrap();
+
+        assert!(bytes.len() < serde_json::to_vec_pretty(&result).unwrap().len());
+        assert_eq!(parse_result(&bytes).unwrap(), result);
+    }
+
+    #[test]
+    fn test_parse_json_result() {
+        let result = full_result();
+        let text = format!("\n{}", serde_json::to_string_pretty(&result).unwrap());
+
+        assert_eq!(parse_result(text.as_bytes()).unwrap(), result);
+        assert!(parse_result(b"").is_err());
+        assert!(parse_result(b"\xc1")
+            .unwrap_err()
+            .starts_with("invalid MessagePack"));
+    }
+}
@@ -0,0 +1,278 @@
+//! NDJSON streaming output
+//!
+//! Writes one JSON line per policy as each scan finishes, then a final
+//! `scan` line with the totals and the run's combined hashes. Nothing is
+//! aggregated in memory beyond the per-policy hashes, so large directory
+//! scans produce output from the first policy on and can be piped into
+//! `jq`. Lines are flushed as written.
+//!
+//! Lines are tagged by `type`:
+//! - `policy`: the policy's summary, as in the `summary` format
+//! - `error`: a policy that could not be scanned
+//! - `scan`: totals and combined hashes, always last
+//!
+//! The format has no envelope and is never signed. With a [`Checkpoint`],
+//! the writer continues the output of an interrupted scan: it appends to
+//! the file, counts and hashes the checkpointed policies with its own, and
+//! checkpoints each policy once its line is written.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use contract_kit::execution_api::CountedScanResult;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::checkpoint::Checkpoint;
+use super::combine_hash_pairs;
+use super::summary::{build_policy_summary, PolicySummary, SummaryAgent};
+
+/// One line of NDJSON output
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NdjsonLine {
+    /// A scanned policy
+    Policy {
+        esp_file: String,
+        policy: PolicySummary,
+    },
+    /// A policy that could not be scanned
+    Error { esp_file: String, error: String },
+    /// Totals and combined hashes of the run
+    Scan {
+        agent: SummaryAgent,
+        summary: NdjsonTotals,
+        /// Combined content hash; null when no policy was scanned
+        content_hash: Option<String>,
+        /// Combined evidence hash; null when no policy was scanned
+        evidence_hash: Option<String>,
+    },
+}
+
+/// Policy totals of the final `scan` line
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct NdjsonTotals {
+    pub total_policies: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub not_applicable: usize,
+    pub errors: usize,
+}
+
+/// Writes scan results as NDJSON while the scan runs
+pub struct NdjsonWriter<W: Write> {
+    out: W,
+    totals: NdjsonTotals,
+    hashes: Vec<(String, String)>,
+    checkpoint: Option<Checkpoint>,
+}
+
+impl NdjsonWriter<Box<dyn Write + Send>> {
+    /// Write to `path`, or to stdout without one
+    pub fn create(path: Option<&Path>) -> io::Result<Self> {
+        let out: Box<dyn Write + Send> = match path {
+            Some(path) => Box::new(io::BufWriter::new(std::fs::File::create(path)?)),
+            None => Box::new(io::stdout()),
+        };
+        Ok(Self::new(out))
+    }
+
+    /// Append to `path`, after the lines of an earlier run
+    pub fn append(path: &Path) -> io::Result<Self> {
+        let file = std::fs::OpenOptions::new().append(true).open(path)?;
+        Ok(Self::new(Box::new(io::BufWriter::new(file))))
+    }
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    /// Write lines to `out`
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            totals: NdjsonTotals::default(),
+            hashes: Vec::new(),
+            checkpoint: None,
+        }
+    }
+
+    /// Continue from the policies of `checkpoint`, recording each policy
+    /// scanned from now on
+    pub fn with_checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        (self.totals, self.hashes) = checkpoint.prior_totals();
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Write the line of one scanned policy, or of its scan error
+    pub fn write_policy<E: std::fmt::Display>(
+        &mut self,
+        esp_file: &Path,
+        result: &Result<CountedScanResult, E>,
+    ) -> io::Result<()> {
+        match result {
+            Ok(result) => {
+                self.hashes
+                    .push((result.content_hash.clone(), result.evidence_hash.clone()));
+                self.write_summary(esp_file, build_policy_summary(result))?;
+                match &mut self.checkpoint {
+                    Some(checkpoint) => checkpoint.record(esp_file, result),
+                    None => Ok(()),
+                }
+            }
+            Err(e) => self.write_error(esp_file, &e.to_string()),
+        }
+    }
+
+    /// Write the line of a scanned policy and count it
+    fn write_summary(&mut self, esp_file: &Path, policy: PolicySummary) -> io::Result<()> {
+        self.totals.total_policies += 1;
+        if policy.not_applicable {
+            self.totals.not_applicable += 1;
+        } else if policy.passed {
+            self.totals.passed += 1;
+        } else {
+            self.totals.failed += 1;
+        }
+        self.write_line(&NdjsonLine::Policy {
+            esp_file: esp_file.display().to_string(),
+            policy,
+        })
+    }
+
+    /// Write the line of a policy that could not be scanned and count it
+    fn write_error(&mut self, esp_file: &Path, error: &str) -> io::Result<()> {
+        self.totals.errors += 1;
+        self.write_line(&NdjsonLine::Error {
+            esp_file: esp_file.display().to_string(),
+            error: error.to_string(),
+        })
+    }
+
+    /// Write the final `scan` line, returning the checkpoint, if any, for
+    /// the caller to remove once the scan is complete
+    pub fn finish(mut self) -> io::Result<Option<Checkpoint>> {
+        let pairs: Vec<(&String, &String)> = self.hashes.iter().map(|(c, e)| (c, e)).collect();
+        let (content_hash, evidence_hash) = match combine_hash_pairs(&pairs) {
+            Ok((content, evidence)) => (Some(content), Some(evidence)),
+            Err(_) => (None, None),
+        };
+        let line = NdjsonLine::Scan {
+            agent: SummaryAgent::current(),
+            summary: std::mem::take(&mut self.totals),
+            content_hash,
+            evidence_hash,
+        };
+        self.write_line(&line)?;
+        Ok(self.checkpoint.take())
+    }
+
+    fn write_line(&mut self, line: &NdjsonLine) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, line)?;
+        self.out.write_all(b"\n")?;
+        self.out.flush()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::summary::CriteriaCountsSummary;
+
+    fn policy(passed: bool, counts: CriteriaCountsSummary) -> PolicySummary {
+        PolicySummary {
+            policy_id: "p".to_string(),
+            platform: "linux".to_string(),
+            passed,
+            not_applicable: passed && counts.not_applicable == counts.total,
+            outcome: if passed { "Pass" } else { "Fail" }.to_string(),
+            criticality: "High".to_string(),
+            criteria_counts: counts,
+            findings_count: 0,
+        }
+    }
+
+    fn lines(out: &[u8]) -> Vec<serde_json::Value> {
+        std::str::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_one_line_per_policy_then_totals() {
+        let mut out = Vec::new();
+        let mut writer = NdjsonWriter::new(&mut out);
+        writer
+            .write_summary(
+                Path::new("a.esp"),
+                policy(
+                    true,
+                    CriteriaCountsSummary {
+                        total: 2,
+                        passed: 2,
+                        failed: 0,
+                        error: 0,
+                        not_applicable: 0,
+                    },
+                ),
+            )
+            .unwrap();
+        writer
+            .write_error(Path::new("b.esp"), "Compilation failed")
+            .unwrap();
+        writer
+            .write_summary(
+                Path::new("c.esp"),
+                policy(
+                    true,
+                    CriteriaCountsSummary {
+                        total: 2,
+                        passed: 0,
+                        failed: 0,
+                        error: 0,
+                        not_applicable: 2,
+                    },
+                ),
+            )
+            .unwrap();
+        writer.finish().unwrap();
+
+        let lines = lines(&out);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0]["type"], "policy");
+        assert_eq!(lines[0]["esp_file"], "a.esp");
+        assert_eq!(lines[0]["policy"]["passed"], true);
+        assert_eq!(lines[1]["type"], "error");
+        assert_eq!(lines[1]["error"], "Compilation failed");
+
+        let scan = &lines[3];
+        assert_eq!(scan["type"], "scan");
+        assert_eq!(scan["summary"]["total_policies"], 2);
+        assert_eq!(scan["summary"]["passed"], 1);
+        assert_eq!(scan["summary"]["not_applicable"], 1);
+        assert_eq!(scan["summary"]["errors"], 1);
+        assert!(scan["content_hash"].is_null());
+    }
+
+    #[test]
+    fn test_empty_scan_writes_only_totals() {
+        let mut out = Vec::new();
+        NdjsonWriter::new(&mut out).finish().unwrap();
+
+        let lines = lines(&out);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["summary"]["total_policies"], 0);
+    }
+}
@@ -0,0 +1,656 @@
+//! OSCAL assessment results
+//!
+//! Reshapes the assessor package into a NIST OSCAL 1.1.2
+//! `assessment-results` document for GRC platforms:
+//! - each policy is an `activity` whose `related-controls` lists its
+//!   control mappings, and an `observation` whose evidence is a back-matter
+//!   resource (the policy's assessor-package evidence as base64 JSON)
+//! - each `framework:control_id` mapping of an applicable policy is a
+//!   `finding` targeting the control: `satisfied` when the policy passed,
+//!   `not-satisfied` otherwise
+//! - `import-ap` points at a back-matter resource for the scanned policy
+//!   content, whose props carry the envelope's `content_hash`,
+//!   `evidence_hash` and signature
+//!
+//! Control IDs are lowercased into OSCAL tokens (`AC-2(1)` becomes
+//! `ac-2.1`); the mapping as written is kept in a `control-mapping` prop.
+//! OSCAL arrays may not be empty, so empty ones are left out: a scan with
+//! no control mappings is a valid document without findings.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use contract_kit::execution_api::CountedScanResult;
+use serde_json::{json, Map, Value};
+
+use super::console::PolicyPosture;
+use super::OutputError;
+
+/// OSCAL version the document conforms to
+pub const OSCAL_VERSION: &str = "1.1.2";
+
+/// Namespace of the agent's own props
+const ESP_NS: &str = "urn:esp-agent";
+
+/// Prefix of the props carrying the envelope signature block
+const SIGNATURE_PROP_PREFIX: &str = "signature-";
+
+/// One policy as exported to OSCAL
+#[derive(Debug, Clone)]
+pub(crate) struct OscalPolicy {
+    pub(crate) policy_id: String,
+    /// Policy outcome (`Pass`, `Fail`, `Error`)
+    pub(crate) outcome: String,
+    pub(crate) passed: bool,
+    /// False when none of the policy's criteria applied to this host
+    pub(crate) applicable: bool,
+    /// `(framework, control_id)` mappings, as written
+    pub(crate) controls: Vec<(String, String)>,
+    /// The policy's evidence from the assessor package
+    pub(crate) evidence: Option<Value>,
+}
+
+/// Provenance of the scan, from the assessor package envelope
+#[derive(Debug, Clone)]
+pub(crate) struct Provenance {
+    pub(crate) content_hash: String,
+    pub(crate) evidence_hash: String,
+    /// The envelope's signature block, if signed
+    pub(crate) signature: Option<Value>,
+    pub(crate) hostname: Option<String>,
+}
+
+impl Provenance {
+    /// Provenance from a serialized assessor package
+    fn from_package(package: &Value) -> Result<Self, OutputError> {
+        let envelope_field = |name: &str| {
+            package
+                .pointer(&format!("/envelope/{}", name))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| OutputError::Build(format!("assessor envelope has no '{}'", name)))
+        };
+        Ok(Self {
+            content_hash: envelope_field("content_hash")?,
+            evidence_hash: envelope_field("evidence_hash")?,
+            signature: package
+                .pointer("/envelope/signature")
+                .filter(|block| block.is_object())
+                .cloned(),
+            hostname: package
+                .pointer("/envelope/host/hostname")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        })
+    }
+}
+
+/// Build an OSCAL `assessment-results` document
+///
+/// `package` is the serialized assessor package of the same results, signed
+/// if signing is available; its envelope provides the hashes and signature
+/// and its policies the evidence.
+pub fn build_oscal(
+    scan_results: &[CountedScanResult],
+    package: &Value,
+) -> Result<Value, OutputError> {
+    let provenance = Provenance::from_package(package)?;
+    let package_policies = package
+        .get("policies")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let policies: Vec<OscalPolicy> = scan_results
+        .iter()
+        .enumerate()
+        .map(|(index, result)| {
+            let posture = PolicyPosture::from_result(result);
+            let policy_id = &result.outcome.policy_id;
+            // Package policies are in scan order; the id check guards against drift
+            let evidence = package_policies
+                .get(index)
+                .filter(|p| p.get("policy_id").and_then(Value::as_str) == Some(policy_id))
+                .and_then(|p| p.get("evidence"))
+                .filter(|evidence| !evidence.is_null())
+                .cloned();
+            OscalPolicy {
+                policy_id: policy_id.clone(),
+                outcome: format!("{:?}", result.outcome.outcome),
+                passed: posture.passed,
+                applicable: posture.applicable,
+                controls: result
+                    .outcome
+                    .control_mappings
+                    .iter()
+                    .map(|m| (m.framework.to_string(), m.control_id.to_string()))
+                    .collect(),
+                evidence,
+            }
+        })
+        .collect();
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    Ok(render_oscal(
+        &policies,
+        &provenance,
+        &crate::inventory::format_utc(now),
+    ))
+}
+
+/// Render the document for these policies, timestamped `timestamp`
+pub(crate) fn render_oscal(
+    policies: &[OscalPolicy],
+    provenance: &Provenance,
+    timestamp: &str,
+) -> Value {
+    let plan_uuid = new_uuid();
+    let mut activities = Vec::new();
+    let mut observations = Vec::new();
+    let mut findings = Vec::new();
+    let mut resources = vec![plan_resource(&plan_uuid, provenance)];
+
+    for policy in policies {
+        let policy_prop = prop("policy-id", &policy.policy_id);
+
+        let mut activity = object(json!({
+            "uuid": new_uuid(),
+            "title": policy.policy_id,
+            "description": format!("Evaluate ESP policy {}", policy.policy_id),
+            "props": [policy_prop],
+        }));
+        if !policy.controls.is_empty() {
+            activity.insert(
+                "related-controls".to_string(),
+                reviewed_controls(policy.controls.iter()),
+            );
+        }
+        activities.push(Value::Object(activity));
+
+        let observation_uuid = new_uuid();
+        let mut observation = object(json!({
+            "uuid": observation_uuid,
+            "title": policy.policy_id,
+            "description": observation_description(policy),
+            "props": [policy_prop, prop("outcome", &policy.outcome)],
+            "methods": ["TEST"],
+            "collected": timestamp,
+        }));
+        if let Some(evidence) = &policy.evidence {
+            let resource_uuid = new_uuid();
+            resources.push(json!({
+                "uuid": resource_uuid,
+                "title": format!("Evidence of {}", policy.policy_id),
+                "base64": {
+                    "media-type": "application/json",
+                    "value": BASE64.encode(evidence.to_string()),
+                },
+            }));
+            observation.insert(
+                "relevant-evidence".to_string(),
+                json!([{
+                    "href": format!("#{}", resource_uuid),
+                    "description": "Evidence collected by the ESP agent",
+                }]),
+            );
+        }
+        observations.push(Value::Object(observation));
+
+        if !policy.applicable {
+            continue;
+        }
+        let state = if policy.passed {
+            "satisfied"
+        } else {
+            "not-satisfied"
+        };
+        for (framework, control_id) in &policy.controls {
+            findings.push(json!({
+                "uuid": new_uuid(),
+                "title": format!("{}:{}", framework, control_id),
+                "description": format!(
+                    "ESP policy {} {} ({})",
+                    policy.policy_id,
+                    if policy.passed { "passed" } else { "did not pass" },
+                    policy.outcome
+                ),
+                "props": [
+                    policy_prop,
+                    prop("framework", framework),
+                    prop("control-mapping", &format!("{}:{}", framework, control_id)),
+                ],
+                "target": {
+                    "type": "objective-id",
+                    "target-id": control_token(control_id),
+                    "status": { "state": state },
+                },
+                "related-observations": [{ "observation-uuid": observation_uuid }],
+            }));
+        }
+    }
+
+    let mut result = object(json!({
+        "uuid": new_uuid(),
+        "title": "ESP scan",
+        "description": format!("Results of {} ESP policies", policies.len()),
+        "start": timestamp,
+        "end": timestamp,
+        "reviewed-controls": reviewed_controls(policies.iter().flat_map(|p| &p.controls)),
+    }));
+    if let Some(hostname) = provenance.hostname.as_deref().filter(|h| !h.is_empty()) {
+        result.insert("props".to_string(), json!([prop("hostname", hostname)]));
+    }
+    for (key, items) in [("observations", observations), ("findings", findings)] {
+        if !items.is_empty() {
+            result.insert(key.to_string(), Value::Array(items));
+        }
+    }
+    if !activities.is_empty() {
+        result.insert(
+            "local-definitions".to_string(),
+            json!({ "activities": activities }),
+        );
+    }
+
+    json!({
+        "assessment-results": {
+            "uuid": new_uuid(),
+            "metadata": {
+                "title": "ESP agent assessment results",
+                "last-modified": timestamp,
+                "version": env!("CARGO_PKG_VERSION"),
+                "oscal-version": OSCAL_VERSION,
+            },
+            "import-ap": {
+                "href": format!("#{}", plan_uuid),
+                "remarks": format!(
+                    "ESP policy content {} with evidence {}",
+                    provenance.content_hash, provenance.evidence_hash
+                ),
+            },
+            "results": [Value::Object(result)],
+            "back-matter": { "resources": resources },
+        }
+    })
+}
+
+/// Back-matter resource standing in for the assessment plan
+///
+/// Its props carry the envelope hashes and every field of the signature
+/// block, so the signature can be checked from the document alone.
+fn plan_resource(uuid: &str, provenance: &Provenance) -> Value {
+    let mut props = vec![
+        prop("content-hash", &provenance.content_hash),
+        prop("evidence-hash", &provenance.evidence_hash),
+    ];
+    if let Some(Value::Object(block)) = &provenance.signature {
+        for (key, value) in block {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Array(items) => items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                _ => continue,
+            };
+            if !value.trim().is_empty() {
+                props.push(prop(&format!("{}{}", SIGNATURE_PROP_PREFIX, key), &value));
+            }
+        }
+    }
+    json!({
+        "uuid": uuid,
+        "title": "ESP policy content",
+        "description": "ESP policies evaluated by this scan, identified by their content hash",
+        "props": props,
+    })
+}
+
+/// Rebuild the result envelope carried by an OSCAL document
+///
+/// The inverse of `plan_resource`, for `--verify`. Returns `None` for a
+/// document without the plan resource or its hashes.
+pub(crate) fn embedded_envelope(document: &Value) -> Option<Value> {
+    let plan_uuid = document
+        .pointer("/assessment-results/import-ap/href")?
+        .as_str()?
+        .strip_prefix('#')?;
+    let resource = document
+        .pointer("/assessment-results/back-matter/resources")?
+        .as_array()?
+        .iter()
+        .find(|r| r.get("uuid").and_then(Value::as_str) == Some(plan_uuid))?;
+
+    let mut envelope = Map::new();
+    let mut signature = Map::new();
+    for prop in resource.get("props")?.as_array()? {
+        if prop.get("ns").and_then(Value::as_str) != Some(ESP_NS) {
+            continue;
+        }
+        let (Some(name), Some(value)) = (
+            prop.get("name").and_then(Value::as_str),
+            prop.get("value").and_then(Value::as_str),
+        ) else {
+            continue;
+        };
+        match name {
+            "content-hash" => envelope.insert("content_hash".to_string(), json!(value)),
+            "evidence-hash" => envelope.insert("evidence_hash".to_string(), json!(value)),
+            "signature-covers" => signature.insert(
+                "covers".to_string(),
+                json!(value.split_whitespace().collect::<Vec<_>>()),
+            ),
+            _ => match name.strip_prefix(SIGNATURE_PROP_PREFIX) {
+                Some(key) => signature.insert(key.to_string(), json!(value)),
+                None => None,
+            },
+        };
+    }
+    envelope.get("content_hash")?;
+    envelope.get("evidence_hash")?;
+    if !signature.is_empty() {
+        envelope.insert("signature".to_string(), Value::Object(signature));
+    }
+    Some(Value::Object(envelope))
+}
+
+/// `reviewed-controls` listing these mappings, one selection per framework
+///
+/// Without mappings the single selection only says so, as OSCAL requires
+/// at least one.
+fn reviewed_controls<'a>(mappings: impl Iterator<Item = &'a (String, String)>) -> Value {
+    let mut by_framework: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    for (framework, control_id) in mappings {
+        by_framework
+            .entry(framework)
+            .or_default()
+            .insert(control_token(control_id));
+    }
+    if by_framework.is_empty() {
+        return json!({
+            "control-selections": [{ "description": "No control mappings" }]
+        });
+    }
+
+    let selections: Vec<Value> = by_framework
+        .into_iter()
+        .map(|(framework, controls)| {
+            json!({
+                "props": [prop("framework", framework)],
+                "include-controls": controls
+                    .into_iter()
+                    .map(|control_id| json!({ "control-id": control_id }))
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    json!({ "control-selections": selections })
+}
+
+/// Observation text for a policy
+fn observation_description(policy: &OscalPolicy) -> String {
+    let result = if !policy.applicable {
+        "had no criteria applicable to this host"
+    } else if policy.passed {
+        "passed"
+    } else {
+        "did not pass"
+    };
+    format!(
+        "ESP policy {} {} ({})",
+        policy.policy_id, result, policy.outcome
+    )
+}
+
+/// Control ID as an OSCAL token: lowercase, `(n)` enhancements as `.n`,
+/// other characters as `_`, and a leading `_` when it would not start
+/// with a letter
+fn control_token(control_id: &str) -> String {
+    let mut token = String::with_capacity(control_id.len() + 1);
+    for c in control_id.trim().chars() {
+        match c {
+            '(' => token.push('.'),
+            ')' => {}
+            c if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') => {
+                token.extend(c.to_lowercase())
+            }
+            _ => token.push('_'),
+        }
+    }
+    if !token.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+        token.insert(0, '_');
+    }
+    token
+}
+
+/// An agent prop
+///
+/// Values are trimmed, as OSCAL props may not start or end with whitespace.
+fn prop(name: &str, value: &str) -> Value {
+    json!({ "name": name, "ns": ESP_NS, "value": value.trim() })
+}
+
+fn new_uuid() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// The fields of a `json!` object literal
+fn object(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(fields) => fields,
+        _ => Map::new(),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(policy_id: &str, passed: bool, controls: &[(&str, &str)]) -> OscalPolicy {
+        OscalPolicy {
+            policy_id: policy_id.to_string(),
+            outcome: if passed { "Pass" } else { "Fail" }.to_string(),
+            passed,
+            applicable: true,
+            controls: controls
+                .iter()
+                .map(|(f, c)| (f.to_string(), c.to_string()))
+                .collect(),
+            evidence: None,
+        }
+    }
+
+    fn provenance(signature: Option<Value>) -> Provenance {
+        Provenance {
+            content_hash: "sha256:content".to_string(),
+            evidence_hash: "sha256:evidence".to_string(),
+            signature,
+            hostname: Some("web-01".to_string()),
+        }
+    }
+
+    /// Paths of arrays OSCAL would reject for being empty
+    fn empty_arrays(value: &Value, path: &str, found: &mut Vec<String>) {
+        match value {
+            Value::Array(items) if items.is_empty() => found.push(path.to_string()),
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    empty_arrays(item, &format!("{}/{}", path, i), found);
+                }
+            }
+            Value::Object(fields) => {
+                for (key, item) in fields {
+                    empty_arrays(item, &format!("{}/{}", path, key), found);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_policies_without_mappings_have_no_findings() {
+        let doc = render_oscal(
+            &[policy("ssh", true, &[])],
+            &provenance(None),
+            "2026-01-01T00:00:00Z",
+        );
+
+        let mut found = Vec::new();
+        empty_arrays(&doc, "", &mut found);
+        assert!(found.is_empty(), "{:?}", found);
+
+        let result = &doc["assessment-results"]["results"][0];
+        assert!(result.get("findings").is_none());
+        assert_eq!(result["observations"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            result["reviewed-controls"]["control-selections"][0]["description"],
+            "No control mappings"
+        );
+        assert!(result["local-definitions"]["activities"][0]
+            .get("related-controls")
+            .is_none());
+        assert_eq!(
+            doc["assessment-results"]["metadata"]["oscal-version"],
+            "1.1.2"
+        );
+    }
+
+    #[test]
+    fn test_one_finding_per_control_mapping() {
+        let mut not_applicable = policy("fips", true, &[("NIST-800-53", "SC-13")]);
+        not_applicable.applicable = false;
+        let doc = render_oscal(
+            &[
+                policy("ssh", true, &[("NIST-800-53", "AC-17"), ("CIS", "5.2.1")]),
+                policy("audit", false, &[("NIST-800-53", "AU-2(1)")]),
+                not_applicable,
+            ],
+            &provenance(None),
+            "2026-01-01T00:00:00Z",
+        );
+
+        let result = &doc["assessment-results"]["results"][0];
+        let findings = result["findings"].as_array().unwrap();
+        assert_eq!(findings.len(), 3);
+        assert_eq!(findings[0]["target"]["target-id"], "ac-17");
+        assert_eq!(findings[0]["target"]["status"]["state"], "satisfied");
+        assert_eq!(findings[1]["target"]["target-id"], "_5.2.1");
+        assert_eq!(findings[2]["target"]["target-id"], "au-2.1");
+        assert_eq!(findings[2]["target"]["status"]["state"], "not-satisfied");
+        assert_eq!(findings[2]["props"][2]["value"], "NIST-800-53:AU-2(1)");
+
+        // Findings point at their policy's observation
+        assert_eq!(
+            findings[2]["related-observations"][0]["observation-uuid"],
+            result["observations"][1]["uuid"]
+        );
+
+        // Every mapping is reviewed, grouped by framework
+        let selections = result["reviewed-controls"]["control-selections"]
+            .as_array()
+            .unwrap();
+        assert_eq!(selections.len(), 2);
+        assert_eq!(selections[1]["props"][0]["value"], "NIST-800-53");
+        assert_eq!(
+            selections[1]["include-controls"].as_array().unwrap().len(),
+            3
+        );
+        assert_eq!(
+            result["local-definitions"]["activities"][1]["related-controls"]["control-selections"]
+                [0]["include-controls"][0]["control-id"],
+            "au-2.1"
+        );
+    }
+
+    #[test]
+    fn test_evidence_is_a_back_matter_resource() {
+        let mut with_evidence = policy("ssh", true, &[]);
+        with_evidence.evidence = Some(json!({ "data": { "exists": true } }));
+        let doc = render_oscal(&[with_evidence], &provenance(None), "2026-01-01T00:00:00Z");
+
+        let href = doc["assessment-results"]["results"][0]["observations"][0]["relevant-evidence"]
+            [0]["href"]
+            .as_str()
+            .unwrap();
+        let resource = doc["assessment-results"]["back-matter"]["resources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|r| format!("#{}", r["uuid"].as_str().unwrap()) == href)
+            .unwrap();
+        let decoded = BASE64
+            .decode(resource["base64"]["value"].as_str().unwrap())
+            .unwrap();
+        let evidence: Value = serde_json::from_slice(&decoded).unwrap();
+        assert_eq!(evidence["data"]["exists"], true);
+    }
+
+    #[test]
+    fn test_envelope_round_trips_through_plan_resource() {
+        let signature = json!({
+            "algorithm": "ecdsa-p256",
+            "signer_id": "software:ephemeral:1",
+            "public_key": "BASE64KEY",
+            "signature": "BASE64SIG",
+            "covers": ["content_hash", "evidence_hash"],
+        });
+        let doc = render_oscal(
+            &[policy("ssh", true, &[])],
+            &provenance(Some(signature.clone())),
+            "2026-01-01T00:00:00Z",
+        );
+
+        let envelope = embedded_envelope(&doc).unwrap();
+        assert_eq!(envelope["content_hash"], "sha256:content");
+        assert_eq!(envelope["evidence_hash"], "sha256:evidence");
+        assert_eq!(envelope["signature"], signature);
+
+        let unsigned = render_oscal(&[], &provenance(None), "2026-01-01T00:00:00Z");
+        assert!(embedded_envelope(&unsigned)
+            .unwrap()
+            .get("signature")
+            .is_none());
+        assert!(embedded_envelope(&json!({ "assessment-results": {} })).is_none());
+    }
+
+    #[test]
+    fn test_provenance_from_package() {
+        let package = json!({
+            "envelope": {
+                "content_hash": "sha256:c",
+                "evidence_hash": "sha256:e",
+                "signature": null,
+                "host": { "hostname": "web-01" }
+            },
+            "policies": []
+        });
+        let provenance = Provenance::from_package(&package).unwrap();
+        assert_eq!(provenance.content_hash, "sha256:c");
+        assert!(provenance.signature.is_none());
+        assert_eq!(provenance.hostname.as_deref(), Some("web-01"));
+
+        assert!(Provenance::from_package(&json!({ "policies": [] })).is_err());
+    }
+
+    #[test]
+    fn test_control_token() {
+        assert_eq!(control_token("AC-2"), "ac-2");
+        assert_eq!(control_token("AC-2(1)"), "ac-2.1");
+        assert_eq!(control_token("3.1.1"), "_3.1.1");
+        assert_eq!(control_token("CM 6"), "cm_6");
+    }
+}
@@ -0,0 +1,462 @@
+//! Prometheus metrics
+//!
+//! Renders scan posture in the Prometheus text exposition format, for a
+//! node-exporter textfile collector to pick up. Every metric is a gauge
+//! describing the latest scan:
+//!
+//! ```text
+//! esp_policies_total                                    policies evaluated
+//! esp_policies_passed                                   policies that passed
+//! esp_policies_failed                                   policies that failed
+//! esp_policies_not_applicable                           policies with no applicable criteria
+//! esp_policy_errors                                     policy files that could not be scanned
+//! esp_posture_score                                     criticality-weighted pass percentage
+//! esp_criteria_total                                    criteria evaluated
+//! esp_criteria_passed_total                             criteria that passed
+//! esp_policies_by_criticality{criticality,result}       per criticality level
+//! esp_policies_by_framework{framework,result}           per mapped framework
+//! esp_policy_passed{policy_id,criticality}              1 if the policy passed, 0 if not
+//! ```
+//!
+//! Passing policies with no applicable criteria are counted only in
+//! `esp_policies_not_applicable`, not as passed or failed, and have no
+//! `esp_policy_passed` sample. A policy ID scanned more than once (from
+//! several files) has one sample, which is 1 only if every scan passed.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use contract_kit::execution_api::CountedScanResult;
+
+use super::console::{criticality_index, PolicyPosture};
+use super::Posture;
+
+/// Criticality labels, in the order they are emitted
+const CRITICALITY_LABELS: [&str; 5] = ["critical", "high", "medium", "low", "info"];
+
+/// Passed and failed policy counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PassFail {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+impl PassFail {
+    fn add(&mut self, passed: bool) {
+        if passed {
+            self.passed += 1;
+        } else {
+            self.failed += 1;
+        }
+    }
+}
+
+/// Metric values derived from a scan
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanMetrics {
+    pub passed: usize,
+    pub failed: usize,
+    pub not_applicable: usize,
+    pub errors: usize,
+    pub posture_score: f32,
+    /// Indexed like `CRITICALITY_LABELS`
+    pub by_criticality: [PassFail; 5],
+    /// Framework name to policies mapping to it
+    pub by_framework: BTreeMap<String, PassFail>,
+    /// Criteria evaluated, over all policies
+    pub criteria_total: usize,
+    /// Criteria that passed, over all policies
+    pub criteria_passed: usize,
+    /// Policy ID to its result, for applicable policies
+    pub by_policy: BTreeMap<String, PolicyMetric>,
+}
+
+/// Result of one applicable policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolicyMetric {
+    /// Index into `CRITICALITY_LABELS`
+    pub criticality: usize,
+    pub passed: bool,
+}
+
+impl ScanMetrics {
+    /// Derive metrics from scan results
+    ///
+    /// `errors` counts policy files that produced no result.
+    pub fn from_results(scan_results: &[CountedScanResult], errors: usize) -> Self {
+        let mut metrics = Self {
+            errors,
+            posture_score: Posture::from_results(scan_results).posture_score,
+            ..Self::default()
+        };
+
+        for result in scan_results {
+            metrics.criteria_total += result.criteria_counts.total;
+            metrics.criteria_passed += result.criteria_counts.passed;
+
+            let posture = PolicyPosture::from_result(result);
+            if !posture.applicable {
+                metrics.not_applicable += 1;
+                continue;
+            }
+
+            let passed = posture.passed;
+            if passed {
+                metrics.passed += 1;
+            } else {
+                metrics.failed += 1;
+            }
+
+            let criticality = criticality_index(posture.criticality);
+            if let Some(counts) = metrics.by_criticality.get_mut(criticality) {
+                counts.add(passed);
+            }
+            metrics
+                .by_policy
+                .entry(result.outcome.policy_id.clone())
+                .and_modify(|policy| policy.passed &= passed)
+                .or_insert(PolicyMetric {
+                    criticality,
+                    passed,
+                });
+
+            // A policy counts once per framework, however many controls it maps
+            let mut frameworks: Vec<String> = result
+                .outcome
+                .control_mappings
+                .iter()
+                .map(|m| m.framework.to_string())
+                .collect();
+            frameworks.sort_unstable();
+            frameworks.dedup();
+            for framework in frameworks {
+                metrics
+                    .by_framework
+                    .entry(framework)
+                    .or_default()
+                    .add(passed);
+            }
+        }
+
+        metrics
+    }
+
+    /// Render in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        gauge(
+            &mut out,
+            "esp_policies_total",
+            "Number of policies evaluated in the last scan",
+            &[(String::new(), (self.passed + self.failed).to_string())],
+        );
+        gauge(
+            &mut out,
+            "esp_policies_passed",
+            "Number of policies that passed in the last scan",
+            &[(String::new(), self.passed.to_string())],
+        );
+        gauge(
+            &mut out,
+            "esp_policies_failed",
+            "Number of policies that failed in the last scan",
+            &[(String::new(), self.failed.to_string())],
+        );
+        gauge(
+            &mut out,
+            "esp_policies_not_applicable",
+            "Number of policies with no applicable criteria in the last scan",
+            &[(String::new(), self.not_applicable.to_string())],
+        );
+        gauge(
+            &mut out,
+            "esp_policy_errors",
+            "Number of policy files that could not be scanned in the last scan",
+            &[(String::new(), self.errors.to_string())],
+        );
+        gauge(
+            &mut out,
+            "esp_posture_score",
+            "Criticality-weighted percentage of passing policies",
+            &[(String::new(), format!("{:.1}", self.posture_score))],
+        );
+        gauge(
+            &mut out,
+            "esp_criteria_total",
+            "Number of criteria evaluated in the last scan",
+            &[(String::new(), self.criteria_total.to_string())],
+        );
+        gauge(
+            &mut out,
+            "esp_criteria_passed_total",
+            "Number of criteria that passed in the last scan",
+            &[(String::new(), self.criteria_passed.to_string())],
+        );
+
+        let criticality_samples: Vec<(String, String)> = CRITICALITY_LABELS
+            .iter()
+            .zip(&self.by_criticality)
+            .flat_map(|(label, counts)| pass_fail_samples("criticality", label, counts))
+            .collect();
+        gauge(
+            &mut out,
+            "esp_policies_by_criticality",
+            "Number of policies by criticality and result",
+            &criticality_samples,
+        );
+
+        if !self.by_framework.is_empty() {
+            let framework_samples: Vec<(String, String)> = self
+                .by_framework
+                .iter()
+                .flat_map(|(framework, counts)| pass_fail_samples("framework", framework, counts))
+                .collect();
+            gauge(
+                &mut out,
+                "esp_policies_by_framework",
+                "Number of policies mapped to each compliance framework by result",
+                &framework_samples,
+            );
+        }
+
+        if !self.by_policy.is_empty() {
+            let policy_samples: Vec<(String, String)> = self
+                .by_policy
+                .iter()
+                .map(|(policy_id, policy)| {
+                    let criticality = CRITICALITY_LABELS
+                        .get(policy.criticality)
+                        .copied()
+                        .unwrap_or_default();
+                    (
+                        format!(
+                            "{{policy_id=\"{}\",criticality=\"{}\"}}",
+                            escape_label_value(policy_id),
+                            criticality
+                        ),
+                        u8::from(policy.passed).to_string(),
+                    )
+                })
+                .collect();
+            gauge(
+                &mut out,
+                "esp_policy_passed",
+                "Whether each applicable policy passed in the last scan (1) or failed (0)",
+                &policy_samples,
+            );
+        }
+
+        out
+    }
+}
+
+/// Render metrics for a scan
+pub fn build_metrics(scan_results: &[CountedScanResult], errors: usize) -> String {
+    ScanMetrics::from_results(scan_results, errors).render()
+}
+
+/// Write one gauge with its `# HELP` and `# TYPE` header
+///
+/// Samples are `(labels, value)`, where `labels` is an already rendered
+/// `{...}` set or empty.
+fn gauge(out: &mut String, name: &str, help: &str, samples: &[(String, String)]) {
+    let _ = writeln!(out, "# HELP {} {}", name, escape_help(help));
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+    for (labels, value) in samples {
+        let _ = writeln!(out, "{}{} {}", name, labels, value);
+    }
+}
+
+/// Samples for the passed and failed counts of one label value
+fn pass_fail_samples(label: &str, value: &str, counts: &PassFail) -> [(String, String); 2] {
+    let value = escape_label_value(value);
+    [
+        (
+            format!("{{{}=\"{}\",result=\"passed\"}}", label, value),
+            counts.passed.to_string(),
+        ),
+        (
+            format!("{{{}=\"{}\",result=\"failed\"}}", label, value),
+            counts.failed.to_string(),
+        ),
+    ]
+}
+
+/// Escape a label value: backslash, double quote and line feed
+fn escape_label_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape `# HELP` text: backslash and line feed
+fn escape_help(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_metrics() -> ScanMetrics {
+        let mut metrics = ScanMetrics {
+            passed: 3,
+            failed: 1,
+            not_applicable: 2,
+            errors: 1,
+            posture_score: 72.5,
+            ..ScanMetrics::default()
+        };
+        metrics.by_criticality[0] = PassFail {
+            passed: 1,
+            failed: 1,
+        };
+        metrics.by_criticality[2] = PassFail {
+            passed: 2,
+            failed: 0,
+        };
+        metrics.by_framework.insert(
+            "NIST-800-53".to_string(),
+            PassFail {
+                passed: 2,
+                failed: 1,
+            },
+        );
+        metrics.criteria_total = 12;
+        metrics.criteria_passed = 9;
+        metrics.by_policy.insert(
+            "ssh-root-login".to_string(),
+            PolicyMetric {
+                criticality: 0,
+                passed: false,
+            },
+        );
+        metrics.by_policy.insert(
+            "shadow-perms".to_string(),
+            PolicyMetric {
+                criticality: 2,
+                passed: true,
+            },
+        );
+        metrics
+    }
+
+    /// Split a sample line into name, labels and value
+    fn parse_sample(line: &str) -> (&str, &str, &str) {
+        let (series, value) = line.rsplit_once(' ').unwrap();
+        match series.find('{') {
+            Some(open) => (&series[..open], &series[open..], value),
+            None => (series, "", value),
+        }
+    }
+
+    #[test]
+    fn test_render_is_well_formed() {
+        let text = sample_metrics().render();
+        assert!(text.ends_with('\n'));
+
+        let mut described: Option<&str> = None;
+        let mut typed: Option<&str> = None;
+        let mut seen = std::collections::HashSet::new();
+
+        for line in text.lines() {
+            if let Some(rest) = line.strip_prefix("# HELP ") {
+                let name = rest.split(' ').next().unwrap();
+                assert!(seen.insert(name.to_string()), "{} described twice", name);
+                described = Some(name);
+                typed = None;
+            } else if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let (name, kind) = rest.split_once(' ').unwrap();
+                assert_eq!(Some(name), described, "TYPE without HELP: {}", line);
+                assert_eq!(kind, "gauge");
+                typed = Some(name);
+            } else {
+                let (name, labels, value) = parse_sample(line);
+                assert_eq!(Some(name), typed, "sample outside its family: {}", line);
+                assert!(name.starts_with("esp_"));
+                assert!(labels.is_empty() || (labels.starts_with('{') && labels.ends_with('}')));
+                assert!(value.parse::<f64>().is_ok(), "bad value: {}", line);
+            }
+        }
+
+        assert!(text.contains("esp_policies_total 4\n"));
+        assert!(text.contains("esp_policies_passed 3\n"));
+        assert!(text.contains("esp_policies_not_applicable 2\n"));
+        assert!(text.contains("esp_policy_errors 1\n"));
+        assert!(text.contains("esp_posture_score 72.5\n"));
+        assert!(text.contains(
+            "esp_policies_by_criticality{criticality=\"critical\",result=\"failed\"} 1\n"
+        ));
+        assert!(text.contains(
+            "esp_policies_by_framework{framework=\"NIST-800-53\",result=\"passed\"} 2\n"
+        ));
+        assert!(text.contains("esp_criteria_total 12\n"));
+        assert!(text.contains("esp_criteria_passed_total 9\n"));
+        assert!(text.contains(
+            "esp_policy_passed{policy_id=\"ssh-root-login\",criticality=\"critical\"} 0\n"
+        ));
+        assert!(text
+            .contains("esp_policy_passed{policy_id=\"shadow-perms\",criticality=\"medium\"} 1\n"));
+    }
+
+    #[test]
+    fn test_every_criticality_is_emitted() {
+        let text = ScanMetrics::default().render();
+        for label in CRITICALITY_LABELS {
+            assert!(text.contains(&format!(
+                "esp_policies_by_criticality{{criticality=\"{}\",result=\"passed\"}} 0\n",
+                label
+            )));
+        }
+        assert!(!text.contains("esp_policies_by_framework"));
+        assert!(!text.contains("esp_policy_passed"));
+    }
+
+    #[test]
+    fn test_label_values_are_escaped() {
+        assert_eq!(escape_label_value(r#"a"b\c"#), r#"a\"b\\c"#);
+        assert_eq!(escape_label_value("line\nbreak"), "line\\nbreak");
+
+        let mut metrics = ScanMetrics::default();
+        metrics
+            .by_framework
+            .insert("CIS \"L1\"\\v8\n".to_string(), PassFail::default());
+        metrics.by_policy.insert(
+            "id\"with\\quote\n".to_string(),
+            PolicyMetric {
+                criticality: 1,
+                passed: true,
+            },
+        );
+        let text = metrics.render();
+
+        assert!(text.contains(
+            "esp_policies_by_framework{framework=\"CIS \\\"L1\\\"\\\\v8\\n\",result=\"passed\"} 0\n"
+        ));
+        assert!(text.contains(
+            "esp_policy_passed{policy_id=\"id\\\"with\\\\quote\\n\",criticality=\"high\"} 1\n"
+        ));
+        // The raw line feed never reaches the output
+        assert!(text
+            .lines()
+            .all(|line| line.starts_with('#') || line.starts_with("esp_")));
+    }
+}
@@ -0,0 +1,145 @@
+//! Policies that could not be scanned
+//!
+//! Full results and summaries carry an `errors` array alongside the scanned
+//! policies, one `{ path, phase, message }` entry per policy that failed to
+//! compile, resolve or execute, or that was denied, so one document shows
+//! both. The array is
+//! outside the hashed and signed content of an envelope, like `timings`.
+//!
+//! Attestations leave the host, and policy paths and compiler messages can
+//! name sensitive files or quote policy content, so attestation errors keep
+//! only the phase.
+
+use std::path::Path;
+
+use contract_kit::execution_api::ScanError;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Phase of a scan that failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanPhase {
+    /// Reading the policy, binding `--var` values or compiling it
+    Compilation,
+    /// Converting the AST and resolving references
+    Resolution,
+    /// Collecting and evaluating criteria
+    Execution,
+    /// Refused before collection: the policy uses a CTN type the agent
+    /// configuration denies
+    Denied,
+}
+
+impl ScanPhase {
+    /// Phase in which `error` occurred
+    pub fn of(error: &ScanError) -> Self {
+        match error {
+            ScanError::IoError(_)
+            | ScanError::CompilationFailed(_)
+            | ScanError::InvalidAst(_)
+            | ScanError::VarBinding(_) => Self::Compilation,
+            ScanError::ConversionFailed(_) | ScanError::ResolutionFailed(_) => Self::Resolution,
+            ScanError::ExecutionFailed(_)
+            | ScanError::RegistryError(_)
+            | ScanError::GroupEvaluation(_)
+            | ScanError::RecordFile(_) => Self::Execution,
+            ScanError::CtnTypeDenied { .. } => Self::Denied,
+        }
+    }
+}
+
+/// A policy that could not be scanned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PolicyError {
+    /// Path of the policy file, as discovered
+    pub path: String,
+    pub phase: ScanPhase,
+    /// Error message, as printed on the console
+    pub message: String,
+}
+
+impl PolicyError {
+    /// Record the failed scan of `esp_file`
+    pub fn new(esp_file: &Path, error: &ScanError) -> Self {
+        Self {
+            path: esp_file.display().to_string(),
+            phase: ScanPhase::of(error),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// `errors` array of a full result
+pub fn errors_json(errors: &[PolicyError]) -> Value {
+    serde_json::to_value(errors).unwrap_or_else(|_| Value::Array(Vec::new()))
+}
+
+/// `errors` array of an attestation: the phase of each error only
+pub fn attestation_errors_json(errors: &[PolicyError]) -> Value {
+    Value::Array(
+        errors
+            .iter()
+            .map(|error| serde_json::json!({ "phase": error.phase }))
+            .collect(),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_follows_error_variant() {
+        let compile = ScanError::compilation_failed("policy.esp:3:5: error: unexpected token");
+        assert_eq!(ScanPhase::of(&compile), ScanPhase::Compilation);
+        assert_eq!(
+            ScanPhase::of(&ScanError::ResolutionFailed("unknown VAR".to_string())),
+            ScanPhase::Resolution
+        );
+        assert_eq!(
+            ScanPhase::of(&ScanError::ExecutionFailed("no strategy".to_string())),
+            ScanPhase::Execution
+        );
+        let denied = ScanError::CtnTypeDenied {
+            ctn_type: "k8s_resource".to_string(),
+            line: 12,
+        };
+        assert_eq!(ScanPhase::of(&denied), ScanPhase::Denied);
+        assert_eq!(
+            serde_json::to_value(ScanPhase::of(&denied)).unwrap(),
+            "denied"
+        );
+    }
+
+    #[test]
+    fn test_attestation_errors_omit_path_and_message() {
+        let errors = vec![PolicyError::new(
+            Path::new("/srv/policies/secret-app.esp"),
+            &ScanError::ResolutionFailed("unknown VAR in /srv/secret".to_string()),
+        )];
+
+        let full = errors_json(&errors);
+        assert_eq!(full[0]["path"], "/srv/policies/secret-app.esp");
+        assert_eq!(full[0]["phase"], "resolution");
+        assert!(full[0]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Resolution failed"));
+
+        let attestation = attestation_errors_json(&errors);
+        assert_eq!(attestation, serde_json::json!([{ "phase": "resolution" }]));
+    }
+}
@@ -0,0 +1,566 @@
+//! Output JSON Schemas
+//!
+//! Emits JSON Schema (draft 2020-12) documents generated from the serde
+//! types of each output format, so the schema cannot drift from the code.
+//!
+//! The `full`, `attestation` and `assessor` envelopes are built from
+//! `common::results` types, which do not derive `JsonSchema`. Their schemas
+//! come from mirror types below that describe what the agent produces and
+//! relies on: the envelope hashes and signature block read by `--verify`,
+//! the policy IDs, findings and evidence read by baselines and OSCAL, and
+//! the blocks the agent adds outside the signed content (`posture`,
+//! `errors`, `timings`, `redaction`, `host.inventory`). Those agent-owned
+//! blocks are closed; the rest allow the additional properties
+//! `common::results` serializes. `msgpack` output is the full result, so it
+//! shares its schema.
+//!
+//! The `ndjson` schema describes a single line. `oscal` documents follow the
+//! NIST OSCAL assessment-results schema, which the agent does not bundle.
+
+use schemars::{schema_for, JsonSchema};
+
+use super::ndjson::NdjsonLine;
+use super::oscal::OSCAL_VERSION;
+use super::scan_errors::{PolicyError, ScanPhase};
+use super::summary::SummaryOutput;
+use super::Posture;
+use crate::config::OutputFormat;
+
+/// JSON Schema for an output format, pretty-printed
+pub fn output_schema(format: OutputFormat) -> Result<String, SchemaError> {
+    let schema = match format {
+        OutputFormat::Full | OutputFormat::MsgPack => schema_for!(FullResultSchema),
+        OutputFormat::Attestation => schema_for!(AttestationSchema),
+        OutputFormat::Assessor => schema_for!(AssessorPackageSchema),
+        OutputFormat::Summary => schema_for!(SummaryOutput),
+        OutputFormat::Ndjson => schema_for!(NdjsonLine),
+        OutputFormat::Oscal | OutputFormat::Csv => {
+            return Err(SchemaError::Unavailable(format));
+        }
+    };
+    serde_json::to_string_pretty(&schema).map_err(|e| SchemaError::Serialization(e.to_string()))
+}
+
+// ============================================================================
+// Envelope Schemas
+// ============================================================================
+
+// Schema-only mirrors of the serialized envelopes; never constructed.
+
+/// Full result (also the content of `msgpack` output)
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "FullResult")]
+struct FullResultSchema {
+    envelope: EnvelopeSchema,
+    policies: Vec<PolicyResultSchema>,
+    posture: Posture,
+    /// Policies that could not be scanned
+    errors: Vec<PolicyError>,
+    /// Present when timings were recorded
+    timings: Option<TimingsSchema>,
+    /// Present when evidence was redacted
+    redaction: Option<RedactionSchema>,
+}
+
+/// Attestation: pass/fail per check, no evidence, timings or posture
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "Attestation")]
+struct AttestationSchema {
+    envelope: EnvelopeSchema,
+    /// Phase of each policy that could not be scanned
+    errors: Vec<AttestationErrorSchema>,
+}
+
+/// Assessor package
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "AssessorPackage")]
+struct AssessorPackageSchema {
+    envelope: EnvelopeSchema,
+    policies: Vec<PolicyResultSchema>,
+    /// Present when timings were recorded
+    timings: Option<TimingsSchema>,
+}
+
+/// Result envelope
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "ResultEnvelope")]
+struct EnvelopeSchema {
+    /// Hash of the policy results, `sha256:<hex>`
+    content_hash: String,
+    /// Hash of the collected evidence, `sha256:<hex>`
+    evidence_hash: String,
+    /// Absent or null when the result is unsigned
+    signature: Option<SignatureSchema>,
+    host: Option<HostSchema>,
+}
+
+/// Signature over the envelope hashes
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "SignatureBlock")]
+struct SignatureSchema {
+    algorithm: String,
+    public_key: String,
+    signature: String,
+    signer_id: String,
+    /// Envelope fields the signature covers
+    covers: Option<Vec<String>>,
+}
+
+/// Host section of the envelope
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "Host")]
+struct HostSchema {
+    /// Present when the host inventory was collected
+    inventory: Option<InventorySchema>,
+}
+
+/// `host.inventory`, see `inventory::HostInventory`
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "HostInventory", deny_unknown_fields)]
+struct InventorySchema {
+    hostname: Option<String>,
+    os: OsReleaseSchema,
+    kernel: Option<String>,
+    architecture: String,
+    primary_ip: Option<String>,
+    /// Boot time as RFC 3339 UTC
+    boot_time: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "OsRelease", deny_unknown_fields)]
+struct OsReleaseSchema {
+    id: Option<String>,
+    name: Option<String>,
+    version_id: Option<String>,
+    pretty_name: Option<String>,
+}
+
+/// One policy of a full result or assessor package
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "PolicyResult")]
+struct PolicyResultSchema {
+    policy_id: String,
+    findings: Option<Vec<serde_json::Value>>,
+    /// Collected evidence, possibly redacted or replaced by placeholders
+    evidence: Option<serde_json::Value>,
+}
+
+/// Entry of an attestation's `errors` array
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "AttestationError", deny_unknown_fields)]
+struct AttestationErrorSchema {
+    phase: ScanPhase,
+}
+
+/// `timings` block, see `ScanTimings::to_json`
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "ScanTimings", deny_unknown_fields)]
+struct TimingsSchema {
+    total_ms: f64,
+    /// Policies in scan order
+    policies: Vec<PolicyTimingSchema>,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "PolicyTiming", deny_unknown_fields)]
+struct PolicyTimingSchema {
+    esp_file: String,
+    /// Null for a policy that did not compile
+    policy_id: Option<String>,
+    started_ms: f64,
+    duration_ms: f64,
+    /// Criteria in evaluation order
+    criteria: Vec<CriterionTimingSchema>,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "CriterionTiming", deny_unknown_fields)]
+struct CriterionTimingSchema {
+    ctn_type: String,
+    objects: Vec<String>,
+    collection_ms: f64,
+    evaluation_ms: f64,
+    duration_ms: f64,
+}
+
+/// `redaction` block
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "Redaction", deny_unknown_fields)]
+struct RedactionSchema {
+    /// Patterns applied
+    patterns: usize,
+    /// Evidence values redacted
+    redacted_values: usize,
+}
+
+/// Errors producing an output schema
+#[derive(Debug)]
+pub enum SchemaError {
+    /// The format has no JSON Schema of its own
+    Unavailable(OutputFormat),
+    /// Failed to serialize the schema
+    Serialization(String),
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaError::Unavailable(OutputFormat::Oscal) => write!(
+                f,
+                "No schema for 'oscal' output: use the NIST OSCAL {} assessment-results schema",
+                OSCAL_VERSION
+            ),
+            SchemaError::Unavailable(OutputFormat::Csv) => write!(
+                f,
+                "No schema for 'csv' output: it is CSV, not JSON (columns: {})",
+                super::csv::CSV_COLUMNS.join(", ")
+            ),
+            SchemaError::Unavailable(format) => write!(f, "No schema for '{}' output", format),
+            SchemaError::Serialization(msg) => write!(f, "Failed to serialize schema: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::HostInventory;
+    use crate::output::redaction::Redaction;
+    use crate::output::scan_errors::{attestation_errors_json, errors_json};
+    use crate::output::summary::{CriteriaCountsSummary, PolicySummary};
+    use crate::output::timings::{PolicyTiming, ScanTimings};
+    use crate::output::{envelope_value, sign_if_available, EvidenceOptions};
+    use crate::signing::SoftwareBackend;
+    use common::results::builder::AssessorInput;
+    use common::results::{
+        AgentInfo, CheckInput, Criticality, Evidence, HostInfo, Outcome, PolicyInput, ResultBuilder,
+    };
+    use contract_kit::executors::CriterionTiming;
+    use serde_json::{json, Value};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn policy(policy_id: &str, passed: bool) -> PolicySummary {
+        PolicySummary {
+            policy_id: policy_id.to_string(),
+            platform: "linux".to_string(),
+            passed,
+            not_applicable: false,
+            outcome: if passed { "Pass" } else { "Fail" }.to_string(),
+            criticality: "High".to_string(),
+            criteria_counts: CriteriaCountsSummary {
+                total: 3,
+                passed: if passed { 3 } else { 2 },
+                failed: if passed { 0 } else { 1 },
+                error: 0,
+                not_applicable: 0,
+            },
+            findings_count: usize::from(!passed),
+        }
+    }
+
+    fn validator(format: OutputFormat) -> jsonschema::Validator {
+        let schema: Value = serde_json::from_str(&output_schema(format).unwrap()).unwrap();
+        jsonschema::validator_for(&schema).unwrap()
+    }
+
+    fn assert_valid(validator: &jsonschema::Validator, output: &Value) {
+        let errors: Vec<String> = validator
+            .iter_errors(output)
+            .map(|e| e.to_string())
+            .collect();
+        assert!(errors.is_empty(), "{}: {:?}", output, errors);
+    }
+
+    #[test]
+    fn test_summary_output_validates_against_schema() {
+        let validator = validator(OutputFormat::Summary);
+
+        for policies in [
+            vec![],
+            vec![policy("ssh-hardening", true)],
+            vec![policy("ssh-hardening", true), policy("audit-rules", false)],
+        ] {
+            let output =
+                serde_json::to_value(SummaryOutput::new(policies, Posture::default())).unwrap();
+            assert_valid(&validator, &output);
+        }
+    }
+
+    #[test]
+    fn test_summary_schema_rejects_unknown_shapes() {
+        let validator = validator(OutputFormat::Summary);
+
+        let mut output = serde_json::to_value(SummaryOutput::new(
+            vec![policy("p", true)],
+            Posture::default(),
+        ))
+        .unwrap();
+        output["policies"][0]["unexpected"] = json!(1);
+        assert!(!validator.is_valid(&output));
+
+        let mut output =
+            serde_json::to_value(SummaryOutput::new(vec![], Posture::default())).unwrap();
+        output["summary"]["passed"] = json!("none");
+        assert!(!validator.is_valid(&output));
+    }
+
+    #[test]
+    fn test_ndjson_lines_validate_against_schema() {
+        let validator = validator(OutputFormat::Ndjson);
+
+        let policy_line = NdjsonLine::Policy {
+            esp_file: "ssh.esp".to_string(),
+            policy: policy("ssh-hardening", true),
+        };
+        let mut lines = vec![serde_json::to_value(policy_line).unwrap()];
+
+        let mut out = Vec::new();
+        let mut writer = crate::output::NdjsonWriter::new(&mut out);
+        writer
+            .write_policy::<&str>(std::path::Path::new("bad.esp"), &Err("Compilation failed"))
+            .unwrap();
+        writer.finish().unwrap();
+        for line in std::str::from_utf8(&out).unwrap().lines() {
+            lines.push(serde_json::from_str(line).unwrap());
+        }
+
+        for line in &lines {
+            assert_valid(&validator, line);
+        }
+        assert!(!validator.is_valid(&json!({ "type": "policy", "esp_file": "a.esp" })));
+    }
+
+    fn hashes() -> (String, String) {
+        (
+            format!("sha256:{}", "c".repeat(64)),
+            format!("sha256:{}", "e".repeat(64)),
+        )
+    }
+
+    fn errors() -> Vec<PolicyError> {
+        vec![PolicyError {
+            path: "bad.esp".to_string(),
+            phase: ScanPhase::Compilation,
+            message: "Compilation failed".to_string(),
+        }]
+    }
+
+    fn timings() -> ScanTimings {
+        ScanTimings {
+            total: Duration::from_millis(40),
+            policies: vec![
+                PolicyTiming {
+                    esp_file: PathBuf::from("ssh.esp"),
+                    policy_id: Some("ssh-hardening".to_string()),
+                    started: Duration::ZERO,
+                    duration: Duration::from_millis(30),
+                    criteria: vec![CriterionTiming {
+                        ctn_type: "file_content".to_string(),
+                        objects: vec!["sshd_config".to_string()],
+                        collection: Duration::from_millis(20),
+                        evaluation: Duration::from_millis(5),
+                        not_applicable: false,
+                    }],
+                },
+                PolicyTiming {
+                    esp_file: PathBuf::from("bad.esp"),
+                    policy_id: None,
+                    started: Duration::from_millis(30),
+                    duration: Duration::from_millis(1),
+                    criteria: Vec::new(),
+                },
+            ],
+        }
+    }
+
+    fn full_result(signed: bool) -> Value {
+        let (content_hash, evidence_hash) = hashes();
+        let policies = vec![
+            PolicyInput::new(
+                "ssh-hardening",
+                "linux",
+                Criticality::High,
+                Vec::new(),
+                Outcome::Pass,
+            )
+            .with_evidence(Evidence::default()),
+            PolicyInput::new(
+                "audit-rules",
+                "linux",
+                Criticality::Medium,
+                Vec::new(),
+                Outcome::Fail,
+            ),
+        ];
+        let mut result = ResultBuilder::from_system("esp-agent")
+            .build_full_result(policies, content_hash, evidence_hash)
+            .unwrap();
+        if signed {
+            let backend = SoftwareBackend::new().unwrap();
+            sign_if_available(&mut result.envelope, Some(&backend));
+        }
+        let redaction = Redaction::new().with_defaults();
+        let evidence = EvidenceOptions {
+            max_bytes: Some(64),
+            redaction: Some(&redaction),
+        };
+        envelope_value(
+            &result,
+            Some(&HostInventory::collect()),
+            evidence,
+            Some(&timings()),
+            Some(&Posture::default()),
+            Some(errors_json(&errors())),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_full_result_validates_against_schema() {
+        let validator = validator(OutputFormat::Full);
+
+        let signed = full_result(true);
+        assert!(signed["envelope"]["signature"].is_object());
+        assert!(signed["envelope"]["host"]["inventory"].is_object());
+        assert!(signed["redaction"].is_object());
+        assert_valid(&validator, &signed);
+        assert_valid(&validator, &full_result(false));
+
+        // MessagePack output is the full result, encoded
+        assert_eq!(
+            output_schema(OutputFormat::MsgPack).unwrap(),
+            output_schema(OutputFormat::Full).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_attestation_validates_against_schema() {
+        let validator = validator(OutputFormat::Attestation);
+
+        let (content_hash, evidence_hash) = hashes();
+        let checks = vec![CheckInput::new(
+            "ssh-hardening",
+            "linux",
+            Criticality::High,
+            Vec::new(),
+            Outcome::Pass,
+        )];
+        let mut result = ResultBuilder::from_system("esp-agent")
+            .build_attestation(checks, content_hash, evidence_hash)
+            .unwrap();
+        let backend = SoftwareBackend::new().unwrap();
+        sign_if_available(&mut result.envelope, Some(&backend));
+        let attestation = envelope_value(
+            &result,
+            Some(&HostInventory::collect()),
+            EvidenceOptions::default(),
+            None,
+            None,
+            Some(attestation_errors_json(&errors())),
+        )
+        .unwrap();
+        assert_valid(&validator, &attestation);
+
+        // Attestation errors carry the phase only
+        let mut leaky = attestation.clone();
+        leaky["errors"] = errors_json(&errors());
+        assert!(!validator.is_valid(&leaky));
+    }
+
+    #[test]
+    fn test_assessor_package_validates_against_schema() {
+        let validator = validator(OutputFormat::Assessor);
+
+        let (content_hash, evidence_hash) = hashes();
+        let policies = vec![AssessorInput::new(
+            "ssh-hardening",
+            "linux",
+            Criticality::High,
+            Vec::new(),
+            Outcome::Fail,
+        )
+        .with_weight(0.8)
+        .with_evidence(Evidence::default())];
+        let mut package = ResultBuilder::new(
+            AgentInfo::with_defaults("esp-agent"),
+            HostInfo::from_system(),
+        )
+        .build_assessor_package(policies, content_hash, evidence_hash)
+        .unwrap();
+        let backend = SoftwareBackend::new().unwrap();
+        sign_if_available(&mut package.envelope, Some(&backend));
+        let package = envelope_value(
+            &package,
+            Some(&HostInventory::collect()),
+            EvidenceOptions::default(),
+            Some(&timings()),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_valid(&validator, &package);
+    }
+
+    #[test]
+    fn test_envelope_schemas_reject_malformed_blocks() {
+        let validator = validator(OutputFormat::Full);
+        let result = full_result(true);
+
+        let mut output = result.clone();
+        output["envelope"]
+            .as_object_mut()
+            .unwrap()
+            .remove("evidence_hash");
+        assert!(!validator.is_valid(&output));
+
+        let mut output = result.clone();
+        output["envelope"]["signature"] = json!({ "algorithm": "ES256" });
+        assert!(!validator.is_valid(&output));
+
+        let mut output = result.clone();
+        output["timings"]["policies"][0]["unexpected"] = json!(1);
+        assert!(!validator.is_valid(&output));
+
+        let mut output = result;
+        output["posture"]["posture_score"] = json!("high");
+        assert!(!validator.is_valid(&output));
+    }
+
+    #[test]
+    fn test_oscal_and_csv_schemas_are_unavailable() {
+        for format in [OutputFormat::Oscal, OutputFormat::Csv] {
+            assert!(matches!(
+                output_schema(format),
+                Err(SchemaError::Unavailable(f)) if f == format
+            ));
+        }
+    }
+}
@@ -1,54 +1,261 @@
 //! Summary builder
 //!
 //! Builds minimal summary output with pass/fail counts.
+//!
+//! Criteria an executor marked not applicable are reported as
+//! `not_applicable`. A passing policy whose criteria were all marked is
+//! counted in `summary.not_applicable` instead of `passed`; a failing policy
+//! is always counted as `failed`. Policies that
+//! could not be scanned are listed in `errors` and counted in
+//! `summary.errors`. The totals carry the posture score and counts by
+//! criticality of the console summary table (see `Posture`).
+
+use contract_kit::execution_api::{CountedScanResult, CriteriaCounts};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::console::PolicyPosture;
+use super::scan_errors::PolicyError;
+use super::{CriticalityCounts, Posture};
+
+/// Summary output document
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SummaryOutput {
+    pub agent: SummaryAgent,
+    pub summary: SummaryTotals,
+    pub policies: Vec<PolicySummary>,
+    /// Policies that could not be scanned
+    pub errors: Vec<PolicyError>,
+}
+
+/// Agent that produced the summary
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SummaryAgent {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+}
+
+/// Policy totals across the scan
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SummaryTotals {
+    pub total_policies: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub not_applicable: usize,
+    /// Policies that could not be scanned (not in `total_policies`)
+    pub errors: usize,
+    /// Criticality-weighted percentage of passing applicable policies, 0-100
+    pub posture_score: f32,
+    pub by_criticality: CriticalityCounts,
+}
+
+/// Result of one policy
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PolicySummary {
+    pub policy_id: String,
+    pub platform: String,
+    pub passed: bool,
+    /// The policy passed with every criterion marked not applicable
+    pub not_applicable: bool,
+    /// Policy outcome (`Pass`, `Fail`, `Error`)
+    pub outcome: String,
+    /// Policy criticality (`Critical` to `Info`)
+    pub criticality: String,
+    pub criteria_counts: CriteriaCountsSummary,
+    pub findings_count: usize,
+}
 
-use contract_kit::execution_api::ScanResult;
+/// Criteria counts of one policy
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct CriteriaCountsSummary {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub error: usize,
+    /// Criteria marked not applicable
+    pub not_applicable: usize,
+}
 
-/// Build a unified summary JSON from all scan results
-pub fn build_summary(scan_results: &[ScanResult]) -> serde_json::Value {
-    let mut total_passed = 0;
-    let mut total_failed = 0;
-    let mut policies = Vec::new();
+impl SummaryAgent {
+    /// This agent
+    pub fn current() -> Self {
+        Self {
+            id: "esp-agent".to_string(),
+            name: "esp-agent".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
 
-    for result in scan_results {
-        if result.tree_passed {
-            total_passed += 1;
-        } else {
-            total_failed += 1;
+impl From<CriteriaCounts> for CriteriaCountsSummary {
+    fn from(counts: CriteriaCounts) -> Self {
+        Self {
+            total: counts.total,
+            passed: counts.passed,
+            failed: counts.failed,
+            error: counts.error,
+            not_applicable: counts.not_applicable,
         }
+    }
+}
 
-        policies.push(build_policy_summary(result));
+impl SummaryOutput {
+    /// Summary of these policies, with totals and their `posture`
+    pub fn new(policies: Vec<PolicySummary>, posture: Posture) -> Self {
+        let not_applicable = policies.iter().filter(|p| p.not_applicable).count();
+        let passed = policies
+            .iter()
+            .filter(|p| p.passed && !p.not_applicable)
+            .count();
+        Self {
+            agent: SummaryAgent::current(),
+            summary: SummaryTotals {
+                total_policies: policies.len(),
+                passed,
+                failed: policies.len() - passed - not_applicable,
+                not_applicable,
+                errors: 0,
+                posture_score: posture.posture_score,
+                by_criticality: posture.by_criticality,
+            },
+            policies,
+            errors: Vec::new(),
+        }
     }
 
-    serde_json::json!({
-        "agent": {
-            "id": "esp-agent",
-            "name": "esp-agent",
-            "version": env!("CARGO_PKG_VERSION")
-        },
-        "summary": {
-            "total_policies": scan_results.len(),
-            "passed": total_passed,
-            "failed": total_failed
-        },
-        "policies": policies
-    })
+    /// The same summary, listing policies that could not be scanned
+    pub fn with_errors(mut self, errors: Vec<PolicyError>) -> Self {
+        self.summary.errors = errors.len();
+        self.errors = errors;
+        self
+    }
+}
+
+/// Build a unified summary from all scan results
+pub fn build_summary(scan_results: &[CountedScanResult]) -> SummaryOutput {
+    SummaryOutput::new(
+        scan_results.iter().map(build_policy_summary).collect(),
+        Posture::from_results(scan_results),
+    )
 }
 
 /// Build summary for a single policy
-fn build_policy_summary(result: &ScanResult) -> serde_json::Value {
-    serde_json::json!({
-        "policy_id": result.outcome.policy_id,
-        "platform": result.outcome.platform,
-        "passed": result.tree_passed,
-        "outcome": format!("{:?}", result.outcome.outcome),
-        "criticality": format!("{:?}", result.outcome.criticality),
-        "criteria_counts": {
-            "total": result.criteria_counts.total,
-            "passed": result.criteria_counts.passed,
-            "failed": result.criteria_counts.failed,
-            "error": result.criteria_counts.error
-        },
-        "findings_count": result.findings.len()
-    })
+pub(super) fn build_policy_summary(result: &CountedScanResult) -> PolicySummary {
+    PolicySummary {
+        policy_id: result.outcome.policy_id.clone(),
+        platform: result.outcome.platform.clone(),
+        passed: result.tree_passed,
+        not_applicable: !PolicyPosture::from_result(result).applicable,
+        outcome: format!("{:?}", result.outcome.outcome),
+        criticality: format!("{:?}", result.outcome.criticality),
+        criteria_counts: result.criteria_counts.into(),
+        findings_count: result.findings.len(),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(passed: bool, counts: CriteriaCountsSummary) -> PolicySummary {
+        PolicySummary {
+            policy_id: "p".to_string(),
+            platform: "linux".to_string(),
+            passed,
+            not_applicable: passed && counts.not_applicable == counts.total,
+            outcome: if passed { "Pass" } else { "Fail" }.to_string(),
+            criticality: "High".to_string(),
+            criteria_counts: counts,
+            findings_count: 0,
+        }
+    }
+
+    /// Criteria counts, with the total of all four
+    fn counts(
+        passed: usize,
+        failed: usize,
+        error: usize,
+        not_applicable: usize,
+    ) -> CriteriaCountsSummary {
+        CriteriaCountsSummary {
+            total: passed + failed + error + not_applicable,
+            passed,
+            failed,
+            error,
+            not_applicable,
+        }
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_scanned_not_applicable_policy() {
+        let result = crate::output::tests::not_applicable_scan();
+        let output = build_summary(std::slice::from_ref(&result));
+
+        assert_eq!(output.summary.total_policies, 1);
+        assert_eq!(output.summary.passed, 0);
+        assert_eq!(output.summary.not_applicable, 1);
+
+        let json = serde_json::to_value(&output).unwrap();
+        let policy = &json["policies"][0];
+        assert_eq!(policy["not_applicable"], true);
+        assert_eq!(policy["criteria_counts"]["total"], 1);
+        assert_eq!(policy["criteria_counts"]["passed"], 0);
+        assert_eq!(policy["criteria_counts"]["not_applicable"], 1);
+    }
+
+    #[test]
+    fn test_not_applicable_policies_are_counted_separately() {
+        let output = SummaryOutput::new(
+            vec![
+                policy(true, counts(3, 0, 0, 0)),
+                policy(false, counts(1, 1, 0, 1)),
+                policy(true, counts(0, 0, 0, 2)),
+            ],
+            Posture::default(),
+        );
+
+        assert_eq!(output.summary.total_policies, 3);
+        assert_eq!(output.summary.passed, 1);
+        assert_eq!(output.summary.failed, 1);
+        assert_eq!(output.summary.not_applicable, 1);
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["summary"]["not_applicable"], 1);
+        assert_eq!(json["policies"][1]["criteria_counts"]["not_applicable"], 1);
+        assert_eq!(json["policies"][2]["not_applicable"], true);
+    }
+
+    #[test]
+    fn test_errors_are_listed_beside_policies() {
+        use contract_kit::execution_api::ScanError;
+
+        let output = SummaryOutput::new(vec![policy(true, counts(1, 0, 0, 0))], Posture::default())
+            .with_errors(vec![PolicyError::new(
+                std::path::Path::new("broken.esp"),
+                &ScanError::compilation_failed("broken.esp:2:1: error: expected META"),
+            )]);
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["summary"]["total_policies"], 1);
+        assert_eq!(json["summary"]["errors"], 1);
+        assert_eq!(json["errors"][0]["path"], "broken.esp");
+        assert_eq!(json["errors"][0]["phase"], "compilation");
+    }
 }
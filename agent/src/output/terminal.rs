@@ -0,0 +1,409 @@
+//! Terminal styling decisions
+//!
+//! Decides once per run whether to emit ANSI colors and how wide boxes and
+//! tables should be, so every console printer renders consistently:
+//!
+//! - **Color**: `--color always|never` wins; `auto` disables color when
+//!   `NO_COLOR` is set (any non-empty value) or stdout is not a terminal
+//! - **Width**: `--width` wins; otherwise the terminal width, then
+//!   `COLUMNS`, then 80. Clamped to `MIN_WIDTH..=MAX_WIDTH`.
+//! - **Glyphs**: with `--ascii`, box-drawing characters and status symbols
+//!   degrade to ASCII (`+--+`, `|`, `+ PASS`, `x FAIL`) for terminals
+//!   whose fonts lack them. Each glyph maps to one character, so widths
+//!   are unchanged.
+
+use std::io::IsTerminal;
+
+/// Width used when the terminal width cannot be detected
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Narrowest layout that still fits the summary table
+pub const MIN_WIDTH: usize = 40;
+
+/// Widest layout; wider terminals get this width
+pub const MAX_WIDTH: usize = 200;
+
+/// When to emit ANSI color codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// Color only on a terminal, and only when `NO_COLOR` is unset
+    #[default]
+    Auto,
+    /// Always emit color codes
+    Always,
+    /// Never emit color codes
+    Never,
+}
+
+impl ColorMode {
+    /// Parse a `--color` value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::Auto),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+}
+
+/// Console colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Green,
+    Red,
+    Yellow,
+    Gray,
+}
+
+impl Color {
+    fn code(self) -> &'static str {
+        match self {
+            Color::Green => "\x1b[32m",
+            Color::Red => "\x1b[31m",
+            Color::Yellow => "\x1b[33m",
+            Color::Gray => "\x1b[90m",
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+/// Resolved color, width and glyphs for console output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsoleStyle {
+    color: bool,
+    width: usize,
+    ascii: bool,
+}
+
+impl Default for ConsoleStyle {
+    fn default() -> Self {
+        Self::plain(DEFAULT_WIDTH)
+    }
+}
+
+impl ConsoleStyle {
+    /// Detect style from CLI settings and the environment
+    pub fn detect(mode: ColorMode, width: Option<usize>) -> Self {
+        let stdout_is_tty = std::io::stdout().is_terminal();
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        let detected_width = width.or_else(terminal_width).or_else(|| {
+            std::env::var("COLUMNS")
+                .ok()
+                .and_then(|c| c.trim().parse().ok())
+        });
+
+        Self::new(
+            resolve_color(mode, no_color, stdout_is_tty),
+            detected_width.unwrap_or(DEFAULT_WIDTH),
+        )
+    }
+
+    /// Style with explicit settings (width is clamped)
+    pub fn new(color: bool, width: usize) -> Self {
+        Self {
+            color,
+            width: width.clamp(MIN_WIDTH, MAX_WIDTH),
+            ascii: false,
+        }
+    }
+
+    /// Style without color
+    pub fn plain(width: usize) -> Self {
+        Self::new(false, width)
+    }
+
+    /// Degrade box-drawing characters and symbols to ASCII
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Text with its glyphs in this style's character set
+    ///
+    /// For box pieces and symbols printed outside the other helpers, which
+    /// all apply it.
+    pub fn text(&self, text: &str) -> String {
+        if self.ascii {
+            text.chars().map(ascii_glyph).collect()
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// A single glyph in this style's character set
+    fn glyph(&self, c: char) -> char {
+        if self.ascii {
+            ascii_glyph(c)
+        } else {
+            c
+        }
+    }
+
+    /// Wrap text in a color, or return it unchanged when color is off
+    pub fn paint(&self, color: Color, text: &str) -> String {
+        let text = self.text(text);
+        if self.color {
+            format!("{}{}{}", color.code(), text, RESET)
+        } else {
+            text
+        }
+    }
+
+    /// Horizontal rule spanning the width, e.g. `┌───┐`
+    pub fn rule(&self, left: char, fill: char, right: char) -> String {
+        let mut line = String::with_capacity(self.width * 3);
+        line.push(self.glyph(left));
+        line.extend(std::iter::repeat_n(
+            self.glyph(fill),
+            self.width.saturating_sub(2),
+        ));
+        line.push(self.glyph(right));
+        line
+    }
+
+    /// Open-sided box line, `│ content`, truncated to the width
+    pub fn line(&self, border: char, content: &str) -> String {
+        let content = truncate_visible(&self.text(content), self.width.saturating_sub(2));
+        format!("{} {}", self.glyph(border), content)
+    }
+
+    /// Closed box line, `║ content   ║`, padded or truncated to the width
+    pub fn framed(&self, border: char, content: &str) -> String {
+        let border = self.glyph(border);
+        let inner = self.width.saturating_sub(3);
+        let content = truncate_visible(&self.text(content), inner);
+        let padding = inner.saturating_sub(visible_len(&content));
+        format!("{} {}{}{}", border, content, " ".repeat(padding), border)
+    }
+
+    /// Closed box line with content centered
+    pub fn centered(&self, border: char, content: &str) -> String {
+        let border = self.glyph(border);
+        let inner = self.width.saturating_sub(2);
+        let len = visible_len(content).min(inner);
+        let left = (inner - len) / 2;
+        let right = inner - len - left;
+        format!(
+            "{}{}{}{}{}",
+            border,
+            " ".repeat(left),
+            truncate_visible(&self.text(content), inner),
+            " ".repeat(right),
+            border
+        )
+    }
+
+    /// Truncate plain text so a line prefixed by `indent` columns fits
+    pub fn fit(&self, text: &str, indent: usize) -> String {
+        truncate_visible(text, self.width.saturating_sub(indent))
+    }
+}
+
+/// ASCII stand-in for a box-drawing character or status symbol
+///
+/// Other characters are returned unchanged.
+fn ascii_glyph(c: char) -> char {
+    match c {
+        '─' | '━' => '-',
+        '═' => '=',
+        '│' | '║' => '|',
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' => '+',
+        '╔' | '╗' | '╚' | '╝' | '╠' | '╣' => '+',
+        '✓' => '+',
+        '✗' => 'x',
+        '⚠' => '!',
+        '•' => '*',
+        '○' => 'o',
+        _ => c,
+    }
+}
+
+/// Decide whether to color output
+fn resolve_color(mode: ColorMode, no_color: bool, stdout_is_tty: bool) -> bool {
+    match mode {
+        ColorMode::Always => true,
+        ColorMode::Never => false,
+        ColorMode::Auto => stdout_is_tty && !no_color,
+    }
+}
+
+/// Number of terminal columns, ignoring ANSI escape sequences
+pub fn visible_len(text: &str) -> usize {
+    let mut len = 0;
+    let mut in_escape = false;
+    for c in text.chars() {
+        if in_escape {
+            in_escape = c != 'm';
+        } else if c == '\x1b' {
+            in_escape = true;
+        } else {
+            len += 1;
+        }
+    }
+    len
+}
+
+/// Truncate to `max` visible columns, marking the cut with `...`
+///
+/// Escape sequences are kept, and a reset is appended if the cut could
+/// leave a color open.
+pub fn truncate_visible(text: &str, max: usize) -> String {
+    if visible_len(text) <= max {
+        return text.to_string();
+    }
+
+    let keep = max.saturating_sub(3);
+    let mut out = String::new();
+    let mut len = 0;
+    let mut in_escape = false;
+    let mut saw_escape = false;
+
+    for c in text.chars() {
+        if in_escape {
+            out.push(c);
+            in_escape = c != 'm';
+            continue;
+        }
+        if c == '\x1b' {
+            out.push(c);
+            in_escape = true;
+            saw_escape = true;
+            continue;
+        }
+        if len == keep {
+            break;
+        }
+        out.push(c);
+        len += 1;
+    }
+
+    if saw_escape {
+        out.push_str(RESET);
+    }
+    out.push_str(&".".repeat(max.min(3)));
+    out
+}
+
+/// Width of the terminal attached to stdout, if any
+#[cfg(unix)]
+fn terminal_width() -> Option<usize> {
+    let mut size = libc::winsize {
+        ws_row: 0,
+        ws_col: 0,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: TIOCGWINSZ only writes into the winsize struct we own
+    let rc = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+    (rc == 0 && size.ws_col > 0).then_some(usize::from(size.ws_col))
+}
+
+/// Width of the console window attached to stdout, if any
+#[cfg(windows)]
+fn terminal_width() -> Option<usize> {
+    use windows::Win32::System::Console::{
+        GetConsoleScreenBufferInfo, GetStdHandle, CONSOLE_SCREEN_BUFFER_INFO, STD_OUTPUT_HANDLE,
+    };
+
+    // SAFETY: the handle comes from GetStdHandle and info is owned
+    unsafe {
+        let handle = GetStdHandle(STD_OUTPUT_HANDLE).ok()?;
+        let mut info = CONSOLE_SCREEN_BUFFER_INFO::default();
+        GetConsoleScreenBufferInfo(handle, &mut info).ok()?;
+        let columns = i32::from(info.srWindow.Right) - i32::from(info.srWindow.Left) + 1;
+        usize::try_from(columns).ok().filter(|c| *c > 0)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn terminal_width() -> Option<usize> {
+    None
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_color() {
+        assert!(resolve_color(ColorMode::Always, true, false));
+        assert!(!resolve_color(ColorMode::Never, false, true));
+        assert!(resolve_color(ColorMode::Auto, false, true));
+        assert!(!resolve_color(ColorMode::Auto, true, true));
+        assert!(!resolve_color(ColorMode::Auto, false, false));
+    }
+
+    #[test]
+    fn test_never_paints_plain_text() {
+        let style = ConsoleStyle::detect(ColorMode::Never, Some(80));
+        assert_eq!(style.paint(Color::Red, "FAIL"), "FAIL");
+        assert!(!style.framed('║', "Passed: 3").contains('\x1b'));
+    }
+
+    #[test]
+    fn test_widths() {
+        let style = ConsoleStyle::plain(60);
+        assert_eq!(style.rule('┌', '─', '┐').chars().count(), 60);
+        assert_eq!(style.framed('║', "Total").chars().count(), 60);
+        assert_eq!(style.centered('║', "SUMMARY").chars().count(), 60);
+
+        let long = "x".repeat(100);
+        assert_eq!(style.line('│', &long).chars().count(), 60);
+        assert_eq!(style.framed('║', &long).chars().count(), 60);
+    }
+
+    #[test]
+    fn test_width_clamped() {
+        assert_eq!(ConsoleStyle::plain(10).width, MIN_WIDTH);
+        assert_eq!(ConsoleStyle::plain(1000).width, MAX_WIDTH);
+    }
+
+    #[test]
+    fn test_colored_padding_ignores_escapes() {
+        let style = ConsoleStyle::new(true, 40);
+        let line = style.framed('║', &style.paint(Color::Green, "Passed: 3"));
+        assert_eq!(visible_len(&line), 40);
+        assert!(line.contains("\x1b[32m"));
+    }
+
+    #[test]
+    fn test_ascii_glyphs_keep_widths() {
+        let style = ConsoleStyle::new(true, 40).with_ascii(true);
+        let rule = style.rule('╔', '═', '╗');
+        assert_eq!(rule, format!("+{}+", "=".repeat(38)));
+        assert_eq!(style.line('│', "└─ detail"), "| +- detail");
+
+        let framed = style.framed('║', &style.paint(Color::Green, "✓ PASS"));
+        assert!(framed.starts_with("| \x1b[32m+ PASS\x1b[0m"));
+        assert!(framed.ends_with('|'));
+        assert_eq!(visible_len(&framed), 40);
+        assert!(framed.is_ascii());
+
+        let unicode = ConsoleStyle::plain(40);
+        assert_eq!(unicode.text("✗ FAIL"), "✗ FAIL");
+        assert_eq!(style.text("✗ FAIL ⚠ • ○"), "x FAIL ! * o");
+    }
+
+    #[test]
+    fn test_truncate_visible() {
+        assert_eq!(truncate_visible("short", 10), "short");
+        assert_eq!(truncate_visible("ääääääääää", 6), "äää...");
+
+        let colored = "\x1b[31mabcdefghij\x1b[0m";
+        let cut = truncate_visible(colored, 6);
+        assert_eq!(visible_len(&cut), 6);
+        assert!(cut.ends_with("\x1b[0m..."));
+    }
+}
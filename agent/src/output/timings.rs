@@ -0,0 +1,319 @@
+//! Scan timings
+//!
+//! Wall-clock time of each policy in a scan, and of each of its criteria
+//! (collection plus evaluation, see `contract_kit::executors::timing`), so
+//! a slow scan can be attributed to specific policies and checks.
+//!
+//! The console summary lists the slowest policies and criteria; the full
+//! output and assessor package carry every timing in a top-level `timings`
+//! block, and attestations carry none. Timings sit
+//! outside the envelope, so they are covered by neither `content_hash`,
+//! `evidence_hash` nor the signature, and two scans of an unchanged host
+//! still hash identically.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use contract_kit::executors::CriterionTiming;
+use serde_json::{json, Value};
+
+use super::terminal::ConsoleStyle;
+
+/// Entries in each slowest list of the console summary
+pub const SLOWEST_COUNT: usize = 5;
+
+/// Time spent on one policy
+#[derive(Debug, Clone)]
+pub struct PolicyTiming {
+    pub esp_file: PathBuf,
+    /// Policy ID, if the policy compiled
+    pub policy_id: Option<String>,
+    /// When the policy started, relative to the start of the scan
+    pub started: Duration,
+    pub duration: Duration,
+    /// Criteria in evaluation order
+    pub criteria: Vec<CriterionTiming>,
+}
+
+impl PolicyTiming {
+    /// Policy ID, or the file name of a policy that did not compile
+    pub fn label(&self) -> String {
+        match &self.policy_id {
+            Some(policy_id) => policy_id.clone(),
+            None => self.esp_file.display().to_string(),
+        }
+    }
+}
+
+/// Timings of every policy in a scan
+#[derive(Debug, Clone, Default)]
+pub struct ScanTimings {
+    pub total: Duration,
+    /// Policies in scan order
+    pub policies: Vec<PolicyTiming>,
+}
+
+impl ScanTimings {
+    /// Up to `count` policies, slowest first
+    pub fn slowest_policies(&self, count: usize) -> Vec<&PolicyTiming> {
+        let mut policies: Vec<&PolicyTiming> = self.policies.iter().collect();
+        policies.sort_by_key(|policy| std::cmp::Reverse(policy.duration));
+        policies.truncate(count);
+        policies
+    }
+
+    /// Up to `count` criteria across all policies, slowest first
+    pub fn slowest_criteria(&self, count: usize) -> Vec<(&PolicyTiming, &CriterionTiming)> {
+        let mut criteria: Vec<(&PolicyTiming, &CriterionTiming)> = self
+            .policies
+            .iter()
+            .flat_map(|policy| policy.criteria.iter().map(move |c| (policy, c)))
+            .collect();
+        criteria.sort_by_key(|(_, criterion)| std::cmp::Reverse(criterion.total()));
+        criteria.truncate(count);
+        criteria
+    }
+
+    /// The `timings` block of the full output
+    pub fn to_json(&self) -> Value {
+        let policies: Vec<Value> = self
+            .policies
+            .iter()
+            .map(|policy| {
+                let criteria: Vec<Value> = policy
+                    .criteria
+                    .iter()
+                    .map(|criterion| {
+                        json!({
+                            "ctn_type": criterion.ctn_type,
+                            "objects": criterion.objects,
+                            "collection_ms": millis(criterion.collection),
+                            "evaluation_ms": millis(criterion.evaluation),
+                            "duration_ms": millis(criterion.total()),
+                        })
+                    })
+                    .collect();
+                json!({
+                    "esp_file": policy.esp_file.display().to_string(),
+                    "policy_id": policy.policy_id,
+                    "started_ms": millis(policy.started),
+                    "duration_ms": millis(policy.duration),
+                    "criteria": criteria,
+                })
+            })
+            .collect();
+
+        json!({
+            "total_ms": millis(self.total),
+            "policies": policies,
+        })
+    }
+}
+
+/// Records policy timings as a scan runs
+pub struct ScanTimer {
+    start: Instant,
+    policies: Vec<PolicyTiming>,
+}
+
+impl ScanTimer {
+    /// Start timing a scan
+    pub fn start() -> Self {
+        Self {
+            start: Instant::now(),
+            policies: Vec::new(),
+        }
+    }
+
+    /// Record a policy that started at `started` and has just finished
+    pub fn record(
+        &mut self,
+        esp_file: &Path,
+        policy_id: Option<&str>,
+        started: Instant,
+        criteria: Vec<CriterionTiming>,
+    ) {
+        self.record_duration(esp_file, policy_id, started, started.elapsed(), criteria);
+    }
+
+    /// Record a policy that started at `started` and took `duration`
+    ///
+    /// For policies scanned on worker threads and recorded afterwards.
+    pub fn record_duration(
+        &mut self,
+        esp_file: &Path,
+        policy_id: Option<&str>,
+        started: Instant,
+        duration: Duration,
+        criteria: Vec<CriterionTiming>,
+    ) {
+        self.policies.push(PolicyTiming {
+            esp_file: esp_file.to_path_buf(),
+            policy_id: policy_id.map(str::to_string),
+            started: started.saturating_duration_since(self.start),
+            duration,
+            criteria,
+        });
+    }
+
+    /// Stop timing and return the scan's timings
+    pub fn finish(self) -> ScanTimings {
+        ScanTimings {
+            total: self.start.elapsed(),
+            policies: self.policies,
+        }
+    }
+}
+
+/// Print the slowest policies and criteria
+pub fn print_timings(timings: &ScanTimings, style: &ConsoleStyle) {
+    let policies = timings.slowest_policies(SLOWEST_COUNT);
+    if policies.is_empty() {
+        return;
+    }
+
+    println!("{}", style.rule('┌', '─', '┐'));
+    println!("{}", style.line('│', "Slowest policies"));
+    for policy in policies {
+        println!(
+            "{}",
+            style.line(
+                '│',
+                &format!("{:>9}  {}", seconds(policy.duration), policy.label())
+            )
+        );
+    }
+
+    let criteria = timings.slowest_criteria(SLOWEST_COUNT);
+    if !criteria.is_empty() {
+        println!("{}", style.rule('├', '─', '┤'));
+        println!("{}", style.line('│', "Slowest criteria"));
+        for (policy, criterion) in criteria {
+            println!(
+                "{}",
+                style.line(
+                    '│',
+                    &format!(
+                        "{:>9}  {} [{}] {}",
+                        seconds(criterion.total()),
+                        policy.label(),
+                        criterion.ctn_type,
+                        criterion.objects.join(", ")
+                    )
+                )
+            );
+        }
+    }
+    println!("{}", style.rule('└', '─', '┘'));
+    println!();
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.3}s", duration.as_secs_f64())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn criterion(ctn_type: &str, object: &str, millis: u64) -> CriterionTiming {
+        CriterionTiming {
+            ctn_type: ctn_type.to_string(),
+            objects: vec![object.to_string()],
+            collection: Duration::from_millis(millis),
+            evaluation: Duration::ZERO,
+            not_applicable: false,
+        }
+    }
+
+    fn policy(policy_id: &str, millis: u64, criteria: Vec<CriterionTiming>) -> PolicyTiming {
+        PolicyTiming {
+            esp_file: PathBuf::from(format!("{}.esp", policy_id)),
+            policy_id: Some(policy_id.to_string()),
+            started: Duration::ZERO,
+            duration: Duration::from_millis(millis),
+            criteria,
+        }
+    }
+
+    #[test]
+    fn test_timings_are_recorded_in_order_and_monotonic() {
+        let mut timer = ScanTimer::start();
+        for name in ["ssh", "audit", "firewall"] {
+            let started = Instant::now();
+            std::thread::sleep(Duration::from_millis(2));
+            timer.record(Path::new(name), Some(name), started, Vec::new());
+        }
+        timer.record(Path::new("broken.esp"), None, Instant::now(), Vec::new());
+        let timings = timer.finish();
+
+        assert_eq!(timings.policies.len(), 4);
+        assert_eq!(timings.policies[3].label(), "broken.esp");
+        for pair in timings.policies.windows(2) {
+            let (previous, next) = (&pair[0], &pair[1]);
+            assert!(previous.duration >= Duration::from_millis(2));
+            assert!(next.started >= previous.started + previous.duration);
+        }
+        let last = timings.policies.last().unwrap();
+        assert!(timings.total >= last.started + last.duration);
+
+        let json = timings.to_json();
+        assert_eq!(json["policies"].as_array().unwrap().len(), 4);
+        assert!(json["policies"][0]["duration_ms"].as_f64().unwrap() >= 2.0);
+    }
+
+    #[test]
+    fn test_slowest_lists_are_ordered() {
+        let timings = ScanTimings {
+            total: Duration::from_millis(600),
+            policies: vec![
+                policy(
+                    "ssh",
+                    100,
+                    vec![criterion("file_content", "sshd_config", 60)],
+                ),
+                policy(
+                    "k8s",
+                    400,
+                    vec![
+                        criterion("k8s_resource", "pods", 150),
+                        criterion("k8s_resource", "roles", 240),
+                    ],
+                ),
+                policy("audit", 100, vec![criterion("audit_rule", "rules", 90)]),
+            ],
+        };
+
+        let policies: Vec<String> = timings
+            .slowest_policies(2)
+            .iter()
+            .map(|p| p.label())
+            .collect();
+        // Ties keep scan order
+        assert_eq!(policies, ["k8s", "ssh"]);
+
+        let criteria: Vec<(String, &str)> = timings
+            .slowest_criteria(SLOWEST_COUNT)
+            .iter()
+            .map(|(p, c)| (p.label(), c.objects[0].as_str()))
+            .collect();
+        assert_eq!(
+            criteria,
+            [
+                ("k8s".to_string(), "roles"),
+                ("k8s".to_string(), "pods"),
+                ("audit".to_string(), "rules"),
+                ("ssh".to_string(), "sshd_config"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Envelope signature verification
+//!
+//! Checks the signature of a saved `full`, `attestation`, `assessor`,
+//! `oscal` or `msgpack` result (`--verify`). The signature covers the envelope's
+//! `content_hash` and `evidence_hash`, so a valid signature shows that those
+//! hashes were signed by the key embedded in the envelope. OSCAL documents
+//! carry the envelope in the props of the back-matter resource their
+//! `import-ap` points at. It does not recompute the
+//! hashes from the results, and keys are per run unless `ESP_SIGNING_KEY`
+//! is set, so on its own it does not identify a particular host.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::msgpack;
+use super::oscal;
+use super::terminal::{Color, ConsoleStyle};
+use crate::signing::verify_envelope_hashes;
+
+/// Outcome of checking an envelope signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvelopeStatus {
+    /// Signature matches the envelope hashes
+    Valid,
+    /// Signature does not match the envelope hashes
+    Invalid,
+    /// Envelope carries no signature
+    Unsigned,
+}
+
+/// Result of verifying one result file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvelopeReport {
+    pub status: EnvelopeStatus,
+    /// `signer_id` of the signature block (empty when unsigned)
+    pub signer_id: String,
+    /// `algorithm` of the signature block (empty when unsigned)
+    pub algorithm: String,
+}
+
+impl EnvelopeReport {
+    /// Whether the signature verified
+    pub fn is_valid(&self) -> bool {
+        self.status == EnvelopeStatus::Valid
+    }
+}
+
+/// Verify the envelope signature of a saved result file
+pub fn verify_result_file(path: &Path) -> Result<EnvelopeReport, VerifyError> {
+    let result = read_result_file(path)?;
+    verify_result(&result).map_err(|e| VerifyError::Malformed(path.to_path_buf(), e))
+}
+
+/// Read a saved result, JSON or MessagePack
+pub fn read_result_file(path: &Path) -> Result<Value, VerifyError> {
+    let content =
+        std::fs::read(path).map_err(|e| VerifyError::Read(path.to_path_buf(), e.to_string()))?;
+    msgpack::parse_result(&content).map_err(|e| VerifyError::Parse(path.to_path_buf(), e))
+}
+
+/// Verify the envelope signature of a parsed result
+///
+/// Errors are descriptions of what is missing or malformed.
+pub fn verify_result(result: &Value) -> Result<EnvelopeReport, String> {
+    let embedded;
+    let envelope = match result.get("envelope") {
+        Some(envelope) => envelope,
+        None => {
+            embedded = oscal::embedded_envelope(result)
+                .ok_or_else(|| "no 'envelope' object".to_string())?;
+            &embedded
+        }
+    };
+    let envelope_field = |name: &str| {
+        envelope
+            .get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("envelope has no '{}'", name))
+    };
+    let content_hash = envelope_field("content_hash")?;
+    let evidence_hash = envelope_field("evidence_hash")?;
+
+    let block = match envelope.get("signature") {
+        None | Some(Value::Null) => {
+            return Ok(EnvelopeReport {
+                status: EnvelopeStatus::Unsigned,
+                signer_id: String::new(),
+                algorithm: String::new(),
+            })
+        }
+        Some(block) => block,
+    };
+    let block_field = |name: &str| {
+        block
+            .get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("signature has no '{}'", name))
+    };
+    let covers: Vec<String> = block
+        .get("covers")
+        .and_then(Value::as_array)
+        .map(|covers| {
+            covers
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let algorithm = block_field("algorithm")?;
+    let verified = verify_envelope_hashes(
+        algorithm,
+        block_field("public_key")?,
+        block_field("signature")?,
+        &covers,
+        content_hash,
+        evidence_hash,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(EnvelopeReport {
+        status: if verified {
+            EnvelopeStatus::Valid
+        } else {
+            EnvelopeStatus::Invalid
+        },
+        signer_id: block_field("signer_id")?.to_string(),
+        algorithm: algorithm.to_string(),
+    })
+}
+
+/// Print a verification report
+pub fn print_verify_report(report: &EnvelopeReport, path: &Path, style: &ConsoleStyle) {
+    println!("Result: {}", path.display());
+    match report.status {
+        EnvelopeStatus::Unsigned => {
+            println!("  {}", style.paint(Color::Red, "Not signed"));
+        }
+        EnvelopeStatus::Valid | EnvelopeStatus::Invalid => {
+            println!("  Signer: {}", report.signer_id);
+            println!("  Algorithm: {}", report.algorithm);
+            if report.is_valid() {
+                println!("  {}", style.paint(Color::Green, "Signature valid"));
+            } else {
+                println!("  {}", style.paint(Color::Red, "Signature invalid"));
+            }
+        }
+    }
+}
+
+/// Errors reading a result file for verification
+#[derive(Debug)]
+pub enum VerifyError {
+    /// Failed to read the file
+    Read(PathBuf, String),
+    /// The file is neither JSON nor MessagePack
+    Parse(PathBuf, String),
+    /// The envelope or its signature block is missing fields or cannot be decoded
+    Malformed(PathBuf, String),
+}
+
+impl std::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerifyError::Read(path, e) => {
+                write!(f, "Failed to read result {}: {}", path.display(), e)
+            }
+            VerifyError::Parse(path, e) => {
+                write!(f, "Failed to parse result {}: {}", path.display(), e)
+            }
+            VerifyError::Malformed(path, e) => {
+                write!(f, "Cannot verify result {}: {}", path.display(), e)
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::{SigningBackend, SoftwareBackend};
+    use serde_json::json;
+
+    fn signed_result(content_hash: &str, evidence_hash: &str) -> Value {
+        let backend = SoftwareBackend::new().expect("Failed to create backend");
+        let block = backend
+            .sign_envelope_hashes(content_hash, evidence_hash)
+            .expect("Signing failed");
+        json!({
+            "envelope": {
+                "content_hash": content_hash,
+                "evidence_hash": evidence_hash,
+                "signature": serde_json::to_value(&block).unwrap(),
+            }
+        })
+    }
+
+    #[test]
+    fn test_verify_result() {
+        let mut result = signed_result("sha256:aaa", "sha256:bbb");
+        let report = verify_result(&result).unwrap();
+        assert!(report.is_valid());
+        assert_eq!(
+            report.signer_id,
+            result["envelope"]["signature"]["signer_id"]
+        );
+
+        result["envelope"]["evidence_hash"] = json!("sha256:ccc");
+        let report = verify_result(&result).unwrap();
+        assert_eq!(report.status, EnvelopeStatus::Invalid);
+
+        result["envelope"]["signature"]["signature"] = json!("AAAA");
+        assert!(verify_result(&result).is_err());
+    }
+
+    #[test]
+    fn test_verify_oscal_document() {
+        let signed = signed_result("sha256:aaa", "sha256:bbb");
+        let mut provenance = oscal::Provenance {
+            content_hash: "sha256:aaa".to_string(),
+            evidence_hash: "sha256:bbb".to_string(),
+            signature: Some(signed["envelope"]["signature"].clone()),
+            hostname: None,
+        };
+        let document = oscal::render_oscal(&[], &provenance, "2026-01-01T00:00:00Z");
+        assert!(verify_result(&document).unwrap().is_valid());
+
+        provenance.evidence_hash = "sha256:ccc".to_string();
+        let document = oscal::render_oscal(&[], &provenance, "2026-01-01T00:00:00Z");
+        assert_eq!(
+            verify_result(&document).unwrap().status,
+            EnvelopeStatus::Invalid
+        );
+    }
+
+    #[test]
+    fn test_verify_unsigned_and_malformed_results() {
+        let unsigned = json!({
+            "envelope": { "content_hash": "sha256:aaa", "evidence_hash": "sha256:bbb" }
+        });
+        assert_eq!(
+            verify_result(&unsigned).unwrap().status,
+            EnvelopeStatus::Unsigned
+        );
+
+        assert!(verify_result(&json!({ "results": [] })).is_err());
+        let mut no_hash = signed_result("sha256:aaa", "sha256:bbb");
+        no_hash["envelope"]
+            .as_object_mut()
+            .unwrap()
+            .remove("content_hash");
+        assert!(verify_result(&no_hash).is_err());
+    }
+}
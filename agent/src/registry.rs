@@ -2,56 +2,1087 @@
 //!
 //! Creates and configures the CTN strategy registry with all available
 //! collectors and executors for the agent.
+//!
+//! ## Registry Config
+//!
+//! By default every built-in strategy except `k8s_resource` is registered.
+//! A [`RegistrySpec`], loaded from the JSON file given with
+//! `--registry-config`, names the strategies to register instead, with
+//! optional parameters for those that run commands:
+//!
+//! ```json
+//! {
+//!   "strategies": [
+//!     { "name": "file_metadata" },
+//!     { "name": "file_content" },
+//!     { "name": "firewall_rule", "timeout_secs": 20 },
+//!     { "name": "k8s_resource", "allow_commands": ["/opt/kube/bin/kubectl"] }
+//!   ]
+//! }
+//! ```
+//!
+//! Only the built-in strategies in [`BUILTIN_STRATEGIES`] can be named; an
+//! unknown name, a duplicate or a parameter the strategy does not take is
+//! an error. CTN types left out have no strategy, so policies using them
+//! are reported as unsupported rather than scanned.
+//!
+//! ## Allowed and Denied CTN Types
+//!
+//! A [`CtnFilter`], from `--allow-ctn` and `--deny-ctn`, narrows the
+//! strategies the defaults or the registry config would register: with any
+//! allowed type, only those are kept, and a denied type is never
+//! registered. The filter cannot add a strategy, so `--allow-ctn
+//! k8s_resource` still needs a registry config naming it. A policy using a
+//! filtered-out type is refused before anything is collected.
+//!
+//! ## Field Mapping Overrides
+//!
+//! A [`FieldOverride`], from `--field-map ctn:state=data`, points a state
+//! field of one CTN type's contract at another collected data field, e.g.
+//! `file_metadata:owner_id=owner` for a collector that emits `owner`.
+//! Overrides change only the contract of the CTN type they name, and are
+//! checked against it with [`check_field_override`] when parsed.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+use contract_kit::collectors::{
+    CollectorScheduler, DescribeCollection, PathExpansion, PlanRecorder,
+};
+use contract_kit::commands::{CommandAllowlist, Elevation};
+use contract_kit::contracts::{apply_field_overrides, remap_field, FieldOverride};
+use contract_kit::execution_api::strategies::{
+    CtnContract, CtnDataCollector, CtnExecutor, CtnStrategyRegistry, StrategyError,
+    SystemCommandExecutor,
+};
+use contract_kit::executors::{CriterionTimer, EvidenceLevel, TraceRecorder};
+use contract_kit::{collectors, commands, contracts, executors};
+use serde::Deserialize;
+
+/// CTN types of the built-in strategies, in registration order
+pub const BUILTIN_STRATEGIES: [&str; 18] = [
+    "file_metadata",
+    "file_content",
+    "file_hash",
+    "computed_values",
+    "json_record",
+    "structured_record",
+    "tcp_listener",
+    "registry_value",
+    "process",
+    "env_var",
+    "mount_point",
+    "cron_job",
+    "user_account",
+    "audit_rule",
+    "firewall_rule",
+    "kernel_module",
+    "dpkg_package",
+    "k8s_resource",
+];
+
+/// Built-in strategies registered only when a registry config names them
+const OPT_IN_STRATEGIES: [&str; 1] = ["k8s_resource"];
+
+/// Built-in command sets of the strategies that run commands
+const COMMAND_STRATEGIES: [(&str, &[&str]); 5] = [
+    ("audit_rule", commands::AUDIT_COMMANDS),
+    ("firewall_rule", commands::FIREWALL_COMMANDS),
+    ("kernel_module", commands::KERNEL_MODULE_COMMANDS),
+    ("dpkg_package", commands::DPKG_COMMANDS),
+    ("k8s_resource", commands::K8S_COMMANDS),
+];
+
+/// Contract of each built-in strategy, in registration order
+const BUILTIN_CONTRACTS: [(&str, fn() -> CtnContract); 18] = [
+    ("file_metadata", contracts::create_file_metadata_contract),
+    ("file_content", contracts::create_file_content_contract),
+    ("file_hash", contracts::create_file_hash_contract),
+    (
+        "computed_values",
+        contracts::create_computed_values_contract,
+    ),
+    ("json_record", contracts::create_json_record_contract),
+    (
+        "structured_record",
+        contracts::create_structured_record_contract,
+    ),
+    ("tcp_listener", contracts::create_tcp_listener_contract),
+    ("registry_value", contracts::create_registry_value_contract),
+    ("process", contracts::create_process_contract),
+    ("env_var", contracts::create_env_var_contract),
+    ("mount_point", contracts::create_mount_contract),
+    ("cron_job", contracts::create_cron_contract),
+    ("user_account", contracts::create_user_account_contract),
+    ("audit_rule", contracts::create_audit_rule_contract),
+    ("firewall_rule", contracts::create_firewall_rule_contract),
+    ("kernel_module", contracts::create_kernel_module_contract),
+    ("dpkg_package", contracts::create_dpkg_package_contract),
+    ("k8s_resource", contracts::create_k8s_resource_contract),
+];
+
+/// Built-in strategies to register, from `--registry-config`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistrySpec {
+    /// Strategies to register; every other CTN type is unsupported
+    pub strategies: Vec<StrategySpec>,
+}
+
+/// One built-in strategy and its parameters
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StrategySpec {
+    /// CTN type of a built-in strategy, e.g. `file_metadata`
+    pub name: String,
+    /// Command timeout in seconds (default: the executor's own)
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Absolute paths of commands this strategy's executor allows in
+    /// addition to its built-in set and the site allowlist
+    #[serde(default)]
+    pub allow_commands: Vec<String>,
+}
+
+impl RegistrySpec {
+    /// Parse and validate a registry config
+    pub fn parse(json: &str) -> Result<Self, RegistrySpecError> {
+        let spec: Self = serde_json::from_str(json).map_err(RegistrySpecError::Parse)?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// Load a registry config file
+    pub fn load(path: &Path) -> Result<Self, RegistrySpecError> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| RegistrySpecError::Read(path.display().to_string(), e))?;
+        Self::parse(&json)
+    }
+
+    /// The named strategy, if the config registers it
+    fn strategy(&self, name: &str) -> Option<&StrategySpec> {
+        self.strategies
+            .iter()
+            .find(|strategy| strategy.name == name)
+    }
+
+    /// Check every name is a built-in strategy, named once, with parameters
+    /// it takes
+    fn validate(&self) -> Result<(), RegistrySpecError> {
+        let mut seen = HashSet::new();
+        for strategy in &self.strategies {
+            let name = strategy.name.as_str();
+            if !BUILTIN_STRATEGIES.contains(&name) {
+                return Err(RegistrySpecError::UnknownStrategy(strategy.name.clone()));
+            }
+            if !seen.insert(name) {
+                return Err(RegistrySpecError::DuplicateStrategy(strategy.name.clone()));
+            }
+            strategy
+                .check_parameters()
+                .map_err(|reason| RegistrySpecError::InvalidParameter {
+                    strategy: strategy.name.clone(),
+                    reason,
+                })?;
+        }
+        Ok(())
+    }
+}
+
+impl StrategySpec {
+    /// Check the parameters suit the strategy
+    fn check_parameters(&self) -> Result<(), String> {
+        let runs_commands = COMMAND_STRATEGIES
+            .iter()
+            .any(|(name, _)| *name == self.name);
+        if !runs_commands && (self.timeout_secs.is_some() || !self.allow_commands.is_empty()) {
+            return Err("runs no commands, so takes no timeout_secs or allow_commands".to_string());
+        }
+        if self.timeout_secs == Some(0) {
+            return Err("timeout_secs must be at least 1".to_string());
+        }
+        let mut allowlist = CommandAllowlist::new();
+        for command in &self.allow_commands {
+            allowlist
+                .allow_absolute(command)
+                .map_err(|reason| format!("allow_commands entry '{}': {}", command, reason))?;
+        }
+        Ok(())
+    }
+
+    /// The strategy's extra commands
+    fn allowlist(&self) -> CommandAllowlist {
+        let mut allowlist = CommandAllowlist::new();
+        for command in &self.allow_commands {
+            // Entries were checked when the config was parsed
+            allowlist.allow_absolute(command).ok();
+        }
+        allowlist
+    }
+}
+
+/// CTN types narrowed by `--allow-ctn` and `--deny-ctn`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CtnFilter {
+    /// Types to keep; empty keeps every type
+    pub allow: Vec<String>,
+    /// Types never registered, even when allowed
+    pub deny: Vec<String>,
+}
 
-use contract_kit::execution_api::strategies::{CtnStrategyRegistry, StrategyError};
-use contract_kit::{collectors, contracts, executors};
+impl CtnFilter {
+    /// Whether the filter narrows nothing
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Whether a strategy for `name` may be registered
+    fn permits(&self, name: &str) -> bool {
+        let allowed = self.allow.is_empty() || self.allow.iter().any(|a| a == name);
+        allowed && !self.deny.iter().any(|d| d == name)
+    }
+}
+
+/// Errors loading a registry config
+#[derive(Debug)]
+pub enum RegistrySpecError {
+    /// Failed to read the config file
+    Read(String, std::io::Error),
+    /// Not valid JSON, or not the expected shape
+    Parse(serde_json::Error),
+    /// A name that is not a built-in strategy
+    UnknownStrategy(String),
+    /// A strategy named twice
+    DuplicateStrategy(String),
+    /// A parameter the strategy does not take, or an invalid value
+    InvalidParameter { strategy: String, reason: String },
+}
+
+impl std::fmt::Display for RegistrySpecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(path, e) => write!(f, "Failed to read registry config {}: {}", path, e),
+            Self::Parse(e) => write!(f, "Invalid registry config: {}", e),
+            Self::UnknownStrategy(name) => write!(
+                f,
+                "Unknown strategy '{}' in registry config (built-in: {})",
+                name,
+                BUILTIN_STRATEGIES.join(", ")
+            ),
+            Self::DuplicateStrategy(name) => {
+                write!(f, "Strategy '{}' is named twice in registry config", name)
+            }
+            Self::InvalidParameter { strategy, reason } => {
+                write!(f, "Strategy '{}' in registry config: {}", strategy, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistrySpecError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Read(_, e) => Some(e),
+            Self::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a built-in strategy is registered under `spec` and `filter`
+fn is_enabled(spec: Option<&RegistrySpec>, filter: &CtnFilter, name: &str) -> bool {
+    let enabled = match spec {
+        Some(spec) => spec.strategy(name).is_some(),
+        None => !OPT_IN_STRATEGIES.contains(&name),
+    };
+    enabled && filter.permits(name)
+}
+
+/// Check a field mapping override against its CTN type's contract
+///
+/// The CTN type must be a built-in strategy and the state field one its
+/// contract defines.
+pub fn check_field_override(field_override: &FieldOverride) -> Result<(), String> {
+    let Some((_, create)) = BUILTIN_CONTRACTS
+        .iter()
+        .find(|(name, _)| *name == field_override.ctn_type)
+    else {
+        return Err(format!(
+            "Unknown CTN type '{}' (built-in: {})",
+            field_override.ctn_type,
+            BUILTIN_STRATEGIES.join(", ")
+        ));
+    };
+    remap_field(
+        &mut create(),
+        &field_override.state_field,
+        &field_override.data_field,
+    )
+    .map(|_| ())
+    .map_err(|e| format!("{} for {}", e, field_override.ctn_type))
+}
+
+/// How collectors are wrapped when registered
+enum CollectorMode<'a> {
+    /// Collect, reporting collection errors as `collection_error` results
+    /// and recording collection and criterion times into the timer
+    Scan(&'a CriterionTimer),
+    /// Record collection plans without collecting
+    Explain(&'a PlanRecorder),
+    /// Collect as a scan, recording each criterion's decision trace
+    Trace(&'a TraceRecorder),
+}
+
+impl CollectorMode<'_> {
+    /// Wrap a system collector for this mode
+    ///
+    /// Scanning collectors are bounded by their concurrency hint, shared
+    /// through `scheduler` across every instance of the same collector.
+    /// They also cache successful collections, so objects of one registry
+    /// describing the same target are collected once; registries are
+    /// created per scan run, which is as long as a cache may live.
+    fn wrap<C: DescribeCollection + 'static>(
+        &self,
+        collector: C,
+        scheduler: &mut CollectorScheduler,
+    ) -> Box<dyn CtnDataCollector> {
+        match self {
+            CollectorMode::Scan(timer) => Box::new(collectors::TimedCollector::new(
+                collectors::CachingCollector::new(collectors::ErrorReportingCollector::new(
+                    scheduler.bound(collector),
+                )),
+                (*timer).clone(),
+            )),
+            CollectorMode::Explain(recorder) => Box::new(collectors::PlanningCollector::new(
+                collector,
+                (*recorder).clone(),
+            )),
+            CollectorMode::Trace(_) => Box::new(collectors::ErrorReportingCollector::new(
+                scheduler.bound(collector),
+            )),
+        }
+    }
+
+    /// Wrap a collector that performs no system access
+    fn wrap_computed<C: DescribeCollection + 'static>(
+        &self,
+        collector: C,
+        scheduler: &mut CollectorScheduler,
+    ) -> Box<dyn CtnDataCollector> {
+        match self {
+            CollectorMode::Scan(timer) => Box::new(collectors::TimedCollector::new(
+                scheduler.bound(collector),
+                (*timer).clone(),
+            )),
+            CollectorMode::Explain(_) => self.wrap(collector, scheduler),
+            CollectorMode::Trace(_) => Box::new(scheduler.bound(collector)),
+        }
+    }
+
+    /// Wrap an executor for this mode
+    fn executor(&self, executor: Box<dyn CtnExecutor>) -> Box<dyn CtnExecutor> {
+        match self {
+            CollectorMode::Scan(timer) => {
+                Box::new(executors::TimingExecutor::new(executor, (*timer).clone()))
+            }
+            CollectorMode::Explain(_) => executor,
+            CollectorMode::Trace(recorder) => Box::new(executors::TracingExecutor::new(
+                executor,
+                (*recorder).clone(),
+            )),
+        }
+    }
+}
 
 /// Create a registry with all available strategies
 ///
 /// Includes:
 /// - File metadata validation (fast stat-based checks)
 /// - File content validation (string operations)
+/// - File hash validation (SHA-256 against a known-good digest)
 /// - JSON record validation (structured data)
+/// - Structured record validation (JSON, TOML, INI or YAML files)
 /// - TCP listener validation (port listening state)
-/// - Kubernetes resource validation (K8s API objects)
+/// - Registry value validation (Windows registry values)
+/// - Process validation (running processes by name or command line)
+/// - Environment variable validation (agent or process environment)
+/// - Mount point validation (filesystem type, device, mount options)
+/// - Cron job validation (crontabs, or Task Scheduler tasks on Windows)
+/// - User account validation (/etc/passwd, or local accounts on Windows)
 /// - Computed values validation (derived/calculated values)
-pub fn create_scanner_registry() -> Result<CtnStrategyRegistry, StrategyError> {
+/// - Audit rule validation (auditd rules and audit enablement)
+/// - Firewall rule validation (nftables/iptables policy and exposure)
+/// - Kernel module validation (loaded, blacklisted, loadable via modprobe)
+/// - Debian package validation (installed and version, via dpkg-query)
+/// - Kubernetes resource validation (K8s API objects), only when `spec`
+///   names it
+///
+/// With `spec`, only the strategies it names are registered; `filter`
+/// narrows them further. Each contract gets the `field_map` overrides for
+/// its CTN type.
+///
+/// System collectors are wrapped in `ErrorReportingCollector` so access-denied and
+/// similar collection errors surface as `collection_error` results, and in
+/// `CachingCollector` so a target is collected once per scan run. Every
+/// collector is bounded by its concurrency hint, so a parallel engine never
+/// runs more auditctl or firewall collections at once than the collector allows.
+///
+/// Commands in the site `allowlist` are added to every command executor.
+/// With `elevation`, the audit, firewall and kernel module collectors run
+/// their built-in commands through its wrapper. With `path_expansion`, file
+/// paths may reference its allowlisted environment variables. Each
+/// criterion's collection and evaluation time is recorded into `timer`.
+pub fn create_scanner_registry(
+    allowlist: &CommandAllowlist,
+    elevation: Option<&Elevation>,
+    path_expansion: Option<&PathExpansion>,
+    spec: Option<&RegistrySpec>,
+    filter: &CtnFilter,
+    field_map: &[FieldOverride],
+    timer: &CriterionTimer,
+) -> Result<CtnStrategyRegistry, StrategyError> {
+    build_registry(
+        CollectorMode::Scan(timer),
+        &RegistryOptions {
+            allowlist,
+            elevation,
+            path_expansion,
+            spec,
+            filter,
+            field_map,
+            evidence_level: EvidenceLevel::PerObject,
+        },
+        &mut CollectorScheduler::new(),
+    )
+}
+
+/// Create one scanner registry per timer, for scanning policies in parallel
+///
+/// Each registry records into its own timer, so criterion timings stay with
+/// the policy that produced them. All registries share one scheduler: a
+/// collector's concurrency limit holds across every registry. Executors
+/// that can short-circuit `all` item checks produce `evidence_level`.
+#[allow(clippy::too_many_arguments)]
+pub fn create_scanner_registries(
+    allowlist: &CommandAllowlist,
+    elevation: Option<&Elevation>,
+    path_expansion: Option<&PathExpansion>,
+    spec: Option<&RegistrySpec>,
+    filter: &CtnFilter,
+    field_map: &[FieldOverride],
+    evidence_level: EvidenceLevel,
+    timers: &[CriterionTimer],
+) -> Result<Vec<CtnStrategyRegistry>, StrategyError> {
+    let options = RegistryOptions {
+        allowlist,
+        elevation,
+        path_expansion,
+        spec,
+        filter,
+        field_map,
+        evidence_level,
+    };
+    let mut scheduler = CollectorScheduler::new();
+    timers
+        .iter()
+        .map(|timer| build_registry(CollectorMode::Scan(timer), &options, &mut scheduler))
+        .collect()
+}
+
+/// Create a registry whose collectors record plans into `recorder` instead
+/// of collecting (for `--explain`)
+///
+/// Executors are the same as a scan, so policies resolve identically; every
+/// object is reported as not collected. Plans show elevated commands with
+/// their wrapper.
+pub fn create_explain_registry(
+    allowlist: &CommandAllowlist,
+    elevation: Option<&Elevation>,
+    path_expansion: Option<&PathExpansion>,
+    spec: Option<&RegistrySpec>,
+    filter: &CtnFilter,
+    field_map: &[FieldOverride],
+    recorder: &PlanRecorder,
+) -> Result<CtnStrategyRegistry, StrategyError> {
+    build_registry(
+        CollectorMode::Explain(recorder),
+        &RegistryOptions {
+            allowlist,
+            elevation,
+            path_expansion,
+            spec,
+            filter,
+            field_map,
+            evidence_level: EvidenceLevel::PerObject,
+        },
+        &mut CollectorScheduler::new(),
+    )
+}
+
+/// Create a scanner registry whose executors record each criterion's
+/// decision trace into `recorder` (for `--explain-criterion`)
+///
+/// Collectors are wrapped as for a scan, without timings.
+pub fn create_trace_registry(
+    allowlist: &CommandAllowlist,
+    elevation: Option<&Elevation>,
+    path_expansion: Option<&PathExpansion>,
+    spec: Option<&RegistrySpec>,
+    filter: &CtnFilter,
+    field_map: &[FieldOverride],
+    recorder: &TraceRecorder,
+) -> Result<CtnStrategyRegistry, StrategyError> {
+    build_registry(
+        CollectorMode::Trace(recorder),
+        &RegistryOptions {
+            allowlist,
+            elevation,
+            path_expansion,
+            spec,
+            filter,
+            field_map,
+            evidence_level: EvidenceLevel::PerObject,
+        },
+        &mut CollectorScheduler::new(),
+    )
+}
+
+/// Every command the scanner registry's collectors may run
+///
+/// The built-in sets of the command executors `build_registry` creates for
+/// the strategies `spec` and `filter` enable, their extra commands, and the
+/// site `allowlist` (which holds any elevation wrapper). Keep in step with
+/// the executors registered there.
+pub fn allowed_commands(
+    allowlist: &CommandAllowlist,
+    spec: Option<&RegistrySpec>,
+    filter: &CtnFilter,
+) -> Vec<String> {
+    let command_sets: Vec<&[&str]> = COMMAND_STRATEGIES
+        .iter()
+        .filter(|(name, _)| is_enabled(spec, filter, name))
+        .map(|(_, commands)| *commands)
+        .collect();
+
+    let mut allowed = allowlist.clone();
+    let strategies = spec.iter().flat_map(|spec| &spec.strategies);
+    for strategy in strategies.filter(|strategy| filter.permits(&strategy.name)) {
+        for command in &strategy.allow_commands {
+            allowed.allow_absolute(command).ok();
+        }
+    }
+
+    commands::allowed_commands(&command_sets, &allowed)
+}
+
+/// What every registry of a run is built from
+struct RegistryOptions<'a> {
+    allowlist: &'a CommandAllowlist,
+    elevation: Option<&'a Elevation>,
+    path_expansion: Option<&'a PathExpansion>,
+    spec: Option<&'a RegistrySpec>,
+    filter: &'a CtnFilter,
+    field_map: &'a [FieldOverride],
+    evidence_level: EvidenceLevel,
+}
+
+impl RegistryOptions<'_> {
+    /// Whether a built-in strategy is registered
+    fn enables(&self, name: &str) -> bool {
+        is_enabled(self.spec, self.filter, name)
+    }
+
+    /// Contract of a built-in strategy with its field mapping overrides
+    ///
+    /// Overrides were checked with `check_field_override` when parsed.
+    fn contract(&self, name: &str, create: fn() -> CtnContract) -> CtnContract {
+        let mut contract = create();
+        if let Err(e) = apply_field_overrides(&mut contract, name, self.field_map) {
+            log::warn!("Field mapping override for {} not applied: {}", name, e);
+        }
+        contract
+    }
+
+    /// Command executor for a strategy that runs commands
+    ///
+    /// `create` builds the executor with its built-in command set and
+    /// default timeout. The spec's timeout replaces the default, and the
+    /// site allowlist and the spec's extra commands are added.
+    fn command_executor(
+        &self,
+        name: &str,
+        create: fn() -> SystemCommandExecutor,
+        commands: &[&str],
+    ) -> SystemCommandExecutor {
+        let strategy = self.spec.and_then(|spec| spec.strategy(name));
+        let executor = match strategy.and_then(|strategy| strategy.timeout_secs) {
+            Some(secs) => {
+                let mut executor = SystemCommandExecutor::with_timeout(Duration::from_secs(secs));
+                executor.allow_commands(commands);
+                executor
+            }
+            None => create(),
+        };
+        let mut executor = with_allowlist(executor, self.allowlist);
+        if let Some(strategy) = strategy {
+            strategy.allowlist().apply(&mut executor);
+        }
+        executor
+    }
+}
+
+/// Register the strategies `options` enables, wrapping collectors for `mode`
+///
+/// Collectors are bounded through `scheduler`.
+fn build_registry(
+    mode: CollectorMode<'_>,
+    options: &RegistryOptions<'_>,
+    scheduler: &mut CollectorScheduler,
+) -> Result<CtnStrategyRegistry, StrategyError> {
     let mut registry = CtnStrategyRegistry::new();
+    let filesystem = || filesystem_collector(options.path_expansion);
+    // Each collector may elevate only its own built-in commands
+    let elevate = |commands: &[&str]| options.elevation.map(|e| e.for_commands(commands));
 
     // Register file system strategies
-    let metadata_contract = contracts::create_file_metadata_contract();
-    let content_contract = contracts::create_file_content_contract();
-    let json_contract = contracts::create_json_record_contract();
-    let computed_values_contract = contracts::create_computed_values_contract();
-
-    registry.register_ctn_strategy(
-        Box::new(collectors::FileSystemCollector::new()),
-        Box::new(executors::FileMetadataExecutor::new(metadata_contract)),
-    )?;
-
-    registry.register_ctn_strategy(
-        Box::new(collectors::FileSystemCollector::new()),
-        Box::new(executors::FileContentExecutor::new(content_contract)),
-    )?;
-
-    registry.register_ctn_strategy(
-        Box::new(collectors::ComputedValuesCollector::new()),
-        Box::new(executors::ComputedValuesExecutor::new(
-            computed_values_contract,
-        )),
-    )?;
-
-    registry.register_ctn_strategy(
-        Box::new(collectors::FileSystemCollector::new()),
-        Box::new(executors::JsonRecordExecutor::new(json_contract)),
-    )?;
+    if options.enables("file_metadata") {
+        registry.register_ctn_strategy(
+            mode.wrap(filesystem(), scheduler),
+            mode.executor(Box::new(
+                executors::FileMetadataExecutor::new(
+                    options.contract("file_metadata", contracts::create_file_metadata_contract),
+                )
+                .with_evidence_level(options.evidence_level),
+            )),
+        )?;
+    }
+
+    if options.enables("file_content") {
+        registry.register_ctn_strategy(
+            mode.wrap(filesystem(), scheduler),
+            // Content checks on the same file share one pass across criteria
+            mode.executor(Box::new(
+                executors::FileContentExecutor::new(
+                    options.contract("file_content", contracts::create_file_content_contract),
+                )
+                .with_batch(executors::ContentBatch::new()),
+            )),
+        )?;
+    }
+
+    if options.enables("file_hash") {
+        registry.register_ctn_strategy(
+            mode.wrap(filesystem(), scheduler),
+            mode.executor(Box::new(
+                executors::FileHashExecutor::new(
+                    options.contract("file_hash", contracts::create_file_hash_contract),
+                )
+                .with_evidence_level(options.evidence_level),
+            )),
+        )?;
+    }
+
+    if options.enables("computed_values") {
+        registry.register_ctn_strategy(
+            mode.wrap_computed(collectors::ComputedValuesCollector::new(), scheduler),
+            mode.executor(Box::new(executors::ComputedValuesExecutor::new(
+                options.contract(
+                    "computed_values",
+                    contracts::create_computed_values_contract,
+                ),
+            ))),
+        )?;
+    }
+
+    if options.enables("json_record") {
+        registry.register_ctn_strategy(
+            mode.wrap(filesystem(), scheduler),
+            mode.executor(Box::new(executors::JsonRecordExecutor::new(
+                options.contract("json_record", contracts::create_json_record_contract),
+            ))),
+        )?;
+    }
+
+    // Register structured record strategy (record checks as for JSON)
+    if options.enables("structured_record") {
+        registry.register_ctn_strategy(
+            mode.wrap(
+                structured_record_collector(options.path_expansion),
+                scheduler,
+            ),
+            mode.executor(Box::new(executors::JsonRecordExecutor::new(
+                options.contract(
+                    "structured_record",
+                    contracts::create_structured_record_contract,
+                ),
+            ))),
+        )?;
+    }
 
     // Register TCP listener strategy
-    let tcp_listener_contract = contracts::create_tcp_listener_contract();
-    registry.register_ctn_strategy(
-        Box::new(collectors::TcpListenerCollector::new()),
-        Box::new(executors::TcpListenerExecutor::new(tcp_listener_contract)),
-    )?;
+    if options.enables("tcp_listener") {
+        registry.register_ctn_strategy(
+            mode.wrap(collectors::TcpListenerCollector::new(), scheduler),
+            mode.executor(Box::new(
+                executors::TcpListenerExecutor::new(
+                    options.contract("tcp_listener", contracts::create_tcp_listener_contract),
+                )
+                .with_evidence_level(options.evidence_level),
+            )),
+        )?;
+    }
+
+    // Register registry value strategy
+    if options.enables("registry_value") {
+        registry.register_ctn_strategy(
+            mode.wrap(collectors::RegistryCollector::new(), scheduler),
+            mode.executor(Box::new(
+                executors::RegistryExecutor::new(
+                    options.contract("registry_value", contracts::create_registry_value_contract),
+                )
+                .with_evidence_level(options.evidence_level),
+            )),
+        )?;
+    }
+
+    // Register process strategy
+    if options.enables("process") {
+        registry.register_ctn_strategy(
+            mode.wrap(collectors::ProcessCollector::new(), scheduler),
+            mode.executor(Box::new(
+                executors::ProcessExecutor::new(
+                    options.contract("process", contracts::create_process_contract),
+                )
+                .with_evidence_level(options.evidence_level),
+            )),
+        )?;
+    }
+
+    // Register environment variable strategy
+    if options.enables("env_var") {
+        registry.register_ctn_strategy(
+            mode.wrap(collectors::EnvVarCollector::new(), scheduler),
+            mode.executor(Box::new(
+                executors::EnvVarExecutor::new(
+                    options.contract("env_var", contracts::create_env_var_contract),
+                )
+                .with_evidence_level(options.evidence_level),
+            )),
+        )?;
+    }
+
+    // Register mount point strategy
+    if options.enables("mount_point") {
+        registry.register_ctn_strategy(
+            mode.wrap(collectors::MountCollector::new(), scheduler),
+            mode.executor(Box::new(
+                executors::MountExecutor::new(
+                    options.contract("mount_point", contracts::create_mount_contract),
+                )
+                .with_evidence_level(options.evidence_level),
+            )),
+        )?;
+    }
+
+    // Register cron job strategy
+    if options.enables("cron_job") {
+        registry.register_ctn_strategy(
+            mode.wrap(collectors::CronCollector::new(), scheduler),
+            mode.executor(Box::new(
+                executors::CronExecutor::new(
+                    options.contract("cron_job", contracts::create_cron_contract),
+                )
+                .with_evidence_level(options.evidence_level),
+            )),
+        )?;
+    }
+
+    // Register user account strategy
+    if options.enables("user_account") {
+        registry.register_ctn_strategy(
+            mode.wrap(collectors::PasswdCollector::new(), scheduler),
+            mode.executor(Box::new(
+                executors::UserAccountExecutor::new(
+                    options.contract("user_account", contracts::create_user_account_contract),
+                )
+                .with_evidence_level(options.evidence_level),
+            )),
+        )?;
+    }
+
+    // Register audit rule strategy
+    if options.enables("audit_rule") {
+        registry.register_ctn_strategy(
+            mode.wrap(
+                collectors::AuditRuleCollector::new(
+                    "audit_rule_collector",
+                    options.command_executor(
+                        "audit_rule",
+                        commands::create_audit_command_executor,
+                        commands::AUDIT_COMMANDS,
+                    ),
+                )
+                .with_elevation(elevate(commands::AUDIT_COMMANDS)),
+                scheduler,
+            ),
+            mode.executor(Box::new(
+                executors::AuditRuleExecutor::new(
+                    options.contract("audit_rule", contracts::create_audit_rule_contract),
+                )
+                .with_evidence_level(options.evidence_level),
+            )),
+        )?;
+    }
+
+    // Register firewall rule strategy
+    if options.enables("firewall_rule") {
+        registry.register_ctn_strategy(
+            mode.wrap(
+                collectors::FirewallRuleCollector::new(
+                    "firewall_rule_collector",
+                    options.command_executor(
+                        "firewall_rule",
+                        commands::create_firewall_command_executor,
+                        commands::FIREWALL_COMMANDS,
+                    ),
+                )
+                .with_elevation(elevate(commands::FIREWALL_COMMANDS)),
+                scheduler,
+            ),
+            mode.executor(Box::new(executors::FirewallRuleExecutor::new(
+                options.contract("firewall_rule", contracts::create_firewall_rule_contract),
+            ))),
+        )?;
+    }
+
+    // Register kernel module strategy
+    if options.enables("kernel_module") {
+        registry.register_ctn_strategy(
+            mode.wrap(
+                collectors::KernelModuleCollector::new(
+                    "kernel_module_collector",
+                    options.command_executor(
+                        "kernel_module",
+                        commands::create_kernel_module_command_executor,
+                        commands::KERNEL_MODULE_COMMANDS,
+                    ),
+                )
+                .with_elevation(elevate(commands::KERNEL_MODULE_COMMANDS)),
+                scheduler,
+            ),
+            mode.executor(Box::new(
+                executors::KernelModuleExecutor::new(
+                    options.contract("kernel_module", contracts::create_kernel_module_contract),
+                )
+                .with_evidence_level(options.evidence_level),
+            )),
+        )?;
+    }
+
+    // Register Debian package strategy
+    if options.enables("dpkg_package") {
+        registry.register_ctn_strategy(
+            mode.wrap(
+                collectors::DpkgPackageCollector::new(
+                    "dpkg_package_collector",
+                    options.command_executor(
+                        "dpkg_package",
+                        commands::create_dpkg_command_executor,
+                        commands::DPKG_COMMANDS,
+                    ),
+                ),
+                scheduler,
+            ),
+            mode.executor(Box::new(
+                executors::DpkgPackageExecutor::new(
+                    options.contract("dpkg_package", contracts::create_dpkg_package_contract),
+                )
+                .with_evidence_level(options.evidence_level),
+            )),
+        )?;
+    }
+
+    // Register Kubernetes resource strategy (registry config only)
+    if options.enables("k8s_resource") {
+        registry.register_ctn_strategy(
+            mode.wrap(
+                collectors::K8sResourceCollector::new(
+                    "k8s_resource_collector",
+                    options.command_executor(
+                        "k8s_resource",
+                        commands::create_k8s_command_executor,
+                        commands::K8S_COMMANDS,
+                    ),
+                ),
+                scheduler,
+            ),
+            mode.executor(Box::new(executors::K8sResourceExecutor::new(
+                options.contract("k8s_resource", contracts::create_k8s_resource_contract),
+            ))),
+        )?;
+    }
 
     Ok(registry)
 }
+
+/// File system collector, expanding environment variables if configured
+///
+/// Metadata batching stays off: batches carry no behavior hints, so
+/// `glob_expand` and `collect_acls` objects would be collected as if the
+/// behavior were not set.
+fn filesystem_collector(path_expansion: Option<&PathExpansion>) -> collectors::FileSystemCollector {
+    let collector = collectors::FileSystemCollector::new();
+    match path_expansion {
+        Some(expansion) => collector.with_path_expansion(expansion.clone()),
+        None => collector,
+    }
+}
+
+/// Structured record collector, expanding environment variables if configured
+fn structured_record_collector(
+    path_expansion: Option<&PathExpansion>,
+) -> collectors::StructuredRecordCollector {
+    let collector = collectors::StructuredRecordCollector::new();
+    match path_expansion {
+        Some(expansion) => collector.with_path_expansion(expansion.clone()),
+        None => collector,
+    }
+}
+
+/// Add the site allowlist to a built-in command executor
+///
+/// An elevation wrapper is always on the allowlist, so the executor allows
+/// it too.
+fn with_allowlist(
+    mut executor: SystemCommandExecutor,
+    allowlist: &CommandAllowlist,
+) -> SystemCommandExecutor {
+    allowlist.apply(&mut executor);
+    executor
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_spec_parses_strategies_and_parameters() {
+        let spec = RegistrySpec::parse(
+            r#"{"strategies": [
+                {"name": "file_metadata"},
+                {"name": "k8s_resource", "timeout_secs": 60,
+                 "allow_commands": ["/opt/kube/bin/kubectl"]}
+            ]}"#,
+        )
+        .unwrap();
+
+        let all = CtnFilter::default();
+        assert!(is_enabled(Some(&spec), &all, "file_metadata"));
+        assert!(is_enabled(Some(&spec), &all, "k8s_resource"));
+        assert!(!is_enabled(Some(&spec), &all, "file_content"));
+        let k8s = spec.strategy("k8s_resource").unwrap();
+        assert_eq!(k8s.timeout_secs, Some(60));
+        assert_eq!(k8s.allow_commands, vec!["/opt/kube/bin/kubectl"]);
+    }
+
+    #[test]
+    fn test_registry_spec_rejects_unknown_and_duplicate_strategies() {
+        let unknown = RegistrySpec::parse(r#"{"strategies": [{"name": "file_metadta"}]}"#);
+        assert!(matches!(
+            unknown,
+            Err(RegistrySpecError::UnknownStrategy(name)) if name == "file_metadta"
+        ));
+
+        let duplicate =
+            RegistrySpec::parse(r#"{"strategies": [{"name": "process"}, {"name": "process"}]}"#);
+        assert!(matches!(
+            duplicate,
+            Err(RegistrySpecError::DuplicateStrategy(name)) if name == "process"
+        ));
+
+        let unknown_field = RegistrySpec::parse(r#"{"strategies": [{"name": "process", "x": 1}]}"#);
+        assert!(matches!(unknown_field, Err(RegistrySpecError::Parse(_))));
+    }
+
+    #[test]
+    fn test_registry_spec_rejects_invalid_parameters() {
+        for json in [
+            r#"{"strategies": [{"name": "file_content", "timeout_secs": 5}]}"#,
+            r#"{"strategies": [{"name": "audit_rule", "timeout_secs": 0}]}"#,
+            r#"{"strategies": [{"name": "firewall_rule", "allow_commands": ["nft"]}]}"#,
+        ] {
+            assert!(
+                matches!(
+                    RegistrySpec::parse(json),
+                    Err(RegistrySpecError::InvalidParameter { .. })
+                ),
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_registry_leaves_out_opt_in_strategies() {
+        let all = CtnFilter::default();
+        assert!(is_enabled(None, &all, "file_metadata"));
+        assert!(is_enabled(None, &all, "kernel_module"));
+        assert!(!is_enabled(None, &all, "k8s_resource"));
+    }
+
+    #[test]
+    fn test_builtin_contracts_match_strategies() {
+        let names: Vec<&str> = BUILTIN_CONTRACTS.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, BUILTIN_STRATEGIES);
+    }
+
+    #[test]
+    fn test_check_field_override() {
+        let valid = FieldOverride::parse("file_metadata:owner_id=owner").unwrap();
+        assert!(check_field_override(&valid).is_ok());
+
+        let unknown_type = FieldOverride::parse("file_meta:owner_id=owner").unwrap();
+        assert!(check_field_override(&unknown_type)
+            .unwrap_err()
+            .starts_with("Unknown CTN type 'file_meta'"));
+        // The state field must belong to the named CTN type
+        let other_type = FieldOverride::parse("process:owner_id=owner").unwrap();
+        assert!(check_field_override(&other_type)
+            .unwrap_err()
+            .contains("Unknown state field 'owner_id'"));
+    }
+
+    #[test]
+    fn test_ctn_filter_narrows_registered_strategies() {
+        let deny = CtnFilter {
+            allow: Vec::new(),
+            deny: vec!["audit_rule".to_string()],
+        };
+        assert!(!is_enabled(None, &deny, "audit_rule"));
+        assert!(is_enabled(None, &deny, "file_metadata"));
+        let commands = allowed_commands(&CommandAllowlist::new(), None, &deny);
+        assert!(!commands.contains(&"auditctl".to_string()));
+        assert!(commands.contains(&"nft".to_string()));
+
+        let allow = CtnFilter {
+            allow: vec!["file_metadata".to_string(), "k8s_resource".to_string()],
+            deny: vec!["file_metadata".to_string()],
+        };
+        // Denied wins over allowed; allowing cannot add an opt-in strategy
+        assert!(!is_enabled(None, &allow, "file_metadata"));
+        assert!(!is_enabled(None, &allow, "k8s_resource"));
+        assert!(!is_enabled(None, &allow, "process"));
+        assert!(allowed_commands(&CommandAllowlist::new(), None, &allow).is_empty());
+    }
+}
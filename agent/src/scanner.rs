@@ -2,58 +2,181 @@
 //!
 //! Handles the execution of ESP scans and result collection.
 
-use std::path::PathBuf;
-use std::sync::Arc;
-use std::time::Instant;
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::OsString;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use common::results::{Criticality, Outcome};
 use contract_kit::execution_api::{
-    log_error, log_info, log_success, logging, scan_file_with_logging, CtnStrategyRegistry,
-    ScanResult, StrategyError,
+    check_ctn_types, check_file, compile_file_with_vars, explain, explain_criterion,
+    extract_metadata, format_trace, log_error, log_info, log_success, logging, scan_ast,
+    scan_file_with_logging, scan_file_with_vars, CountedScanResult, CtnStrategyRegistry,
+    FileContext, PlanRecorder, StrategyError, TraceRecorder,
 };
 
-use crate::config::{ScanConfig, ScanSummary};
+use contract_kit::commands::{AllowlistError, CommandAllowlist, Elevation};
+use contract_kit::executors::{CriterionTimer, CriterionTiming};
+use notify::event::ModifyKind;
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::config::{
+    meets_fail_threshold, parse_criticality, OutputFormat, ScanConfig, ScanSummary,
+};
+use crate::discovery;
+use crate::inventory::{self, HostInventory};
 use crate::output;
-use crate::registry;
+use crate::registry::{self, CtnFilter, RegistrySpec, RegistrySpecError};
+use crate::signing::SigningBackend;
+use crate::subprocess;
 
 /// Run a scan with the given configuration
 pub fn run_scan(config: &ScanConfig, esp_files: &[PathBuf]) -> Result<i32, ScanError> {
     let start = Instant::now();
+    let style = output::ConsoleStyle::detect(config.color, config.width).with_ascii(config.ascii);
+
+    // A checkpointed scan skips the policies an earlier run completed
+    let checkpoint = config
+        .checkpoint
+        .as_deref()
+        .map(|path| output::Checkpoint::open(path, esp_files))
+        .transpose()
+        .map_err(ScanError::Checkpoint)?;
+    let resumed = checkpoint
+        .as_ref()
+        .map_or_else(Vec::new, |checkpoint| checkpoint.prior().to_vec());
+    let esp_files: Vec<PathBuf> = match &checkpoint {
+        Some(checkpoint) => esp_files
+            .iter()
+            .filter(|file| !checkpoint.is_completed(file))
+            .cloned()
+            .collect(),
+        None => esp_files.to_vec(),
+    };
+    let esp_files = esp_files.as_slice();
 
     log_info!("Starting unified scan", "file_count" => esp_files.len());
     if !config.quiet {
         println!();
         println!("ESP Compliance Agent v{}", env!("CARGO_PKG_VERSION"));
+        if !resumed.is_empty() {
+            println!(
+                "Resuming from checkpoint: {} policy(ies) already scanned",
+                resumed.len()
+            );
+        }
         println!("Scanning {} ESP file(s)...", esp_files.len());
         println!();
     }
 
-    // Create registry once for all scans
-    let registry = Arc::new(create_registry()?);
+    // Load baseline before scanning so a bad file fails fast
+    let baseline = match &config.baseline {
+        Some(path) if !config.update_baseline => {
+            Some(output::Baseline::load(path).map_err(ScanError::Baseline)?)
+        }
+        _ => None,
+    };
+
+    // Capture host inventory once per scan
+    let inventory = HostInventory::collect();
+    log_info!(
+        "Host inventory collected",
+        "hostname" => inventory.hostname.as_deref().unwrap_or("unknown"),
+        "os" => inventory.os.pretty_name.as_deref().unwrap_or("unknown")
+    );
+
+    // Create registries once for all scans, one per parallel job
+    let allowlist = load_allowlist(config.command_allowlist.as_deref())?;
+    let elevation = load_elevation(config.elevate.as_deref(), &allowlist)?;
+    let jobs = scan_jobs(config.jobs, esp_files.len());
+    let workers = create_workers(&allowlist, elevation.as_ref(), config, jobs)?;
 
     if !config.quiet {
-        let stats = registry.get_statistics();
-        log_info!(
-            "Registry initialized",
-            "strategies" => stats.total_ctn_types,
-            "healthy" => stats.registry_health.is_healthy()
-        );
+        if let Some(worker) = workers.first() {
+            let stats = worker.registry.get_statistics();
+            log_info!(
+                "Registry initialized",
+                "strategies" => stats.total_ctn_types,
+                "healthy" => stats.registry_health.is_healthy(),
+                "jobs" => jobs
+            );
+        }
     }
 
-    // Execute scans and collect results
-    let (scan_results, summary) = execute_scans(esp_files, &registry, config.quiet)?;
+    // NDJSON is written while scanning rather than saved afterwards. Results
+    // are only kept when something other than the stream needs them.
+    let mut stream = match config.output_format {
+        OutputFormat::Ndjson => Some(open_stream(config, checkpoint)?),
+        _ => None,
+    };
+    let retain = stream.is_none()
+        || !config.quiet
+        || config.metrics_file.is_some()
+        || config.journal.is_some()
+        || config.baseline.is_some();
+
+    // Execute scans and collect results, until --timeout if given
+    let deadline = config.timeout.map(|timeout| start + timeout);
+    let (scan_results, errors, mut summary, timings) = execute_scans(
+        esp_files,
+        &workers,
+        config,
+        &style,
+        stream.as_mut(),
+        retain,
+        deadline,
+    )?;
+    add_checkpointed(&mut summary, &resumed, config.fail_on);
+    if let Some(stream) = stream {
+        let checkpoint = stream
+            .finish()
+            .map_err(|e| ScanError::WriteFile(ndjson_target(config), e))?;
+        // An abandoned scan keeps its checkpoint, to resume from
+        if let Some(checkpoint) = checkpoint.filter(|_| !summary.timed_out) {
+            checkpoint.remove().map_err(ScanError::Checkpoint)?;
+        }
+    }
 
     let duration = start.elapsed();
 
     // Print detailed results to console
     if !config.quiet {
-        output::print_results(&scan_results);
-        print_execution_info(duration, config);
+        output::print_results(&scan_results, &style);
+        output::print_timings(&timings, &style);
+        print_execution_info(duration, config, &style);
     }
 
-    // Build and save output file only if explicitly requested
+    // Create signing backend once so every envelope shares one signer
+    let needs_signing = (config.output_file.is_some() && config.output_format.is_signed())
+        || (config.update_baseline && config.baseline.is_some())
+        || config.journal.is_some();
+    let signing_backend = if needs_signing && !scan_results.is_empty() {
+        output::create_signing_backend()
+    } else {
+        None
+    };
+
+    // Build and save output file only if explicitly requested. A summary or
+    // CSV is written even when no policy could be scanned, to list the errors.
     if let Some(output_path) = &config.output_file {
-        if !scan_results.is_empty() {
-            save_output(&scan_results, config)?;
+        let writable = !scan_results.is_empty()
+            || matches!(
+                config.output_format,
+                OutputFormat::Summary | OutputFormat::Csv
+            );
+        if writable && config.output_format != OutputFormat::Ndjson {
+            save_output(
+                &scan_results,
+                &errors,
+                config,
+                signing_backend.as_deref(),
+                &inventory,
+                &timings,
+            )?;
         }
 
         if !config.quiet {
@@ -62,6 +185,33 @@ pub fn run_scan(config: &ScanConfig, esp_files: &[PathBuf]) -> Result<i32, ScanE
         }
     }
 
+    if let Some(metrics_path) = &config.metrics_file {
+        save_metrics(&scan_results, summary.errors, metrics_path)?;
+        if !config.quiet {
+            println!("Metrics saved to: {}", metrics_path.display());
+            println!();
+        }
+    }
+
+    if let Some(journal_path) = &config.journal {
+        if !scan_results.is_empty() {
+            let seq = append_journal(
+                &scan_results,
+                journal_path,
+                signing_backend.as_deref(),
+                &inventory,
+            )?;
+            if !config.quiet {
+                println!(
+                    "Journal entry {} appended to: {}",
+                    seq,
+                    journal_path.display()
+                );
+                println!();
+            }
+        }
+    }
+
     log_success!(
         logging::codes::success::FILE_PROCESSING_SUCCESS,
         "Scan completed",
@@ -71,94 +221,1258 @@ pub fn run_scan(config: &ScanConfig, esp_files: &[PathBuf]) -> Result<i32, ScanE
         "errors" => summary.errors
     );
 
-    Ok(summary.exit_code())
+    // Baseline mode replaces pass/fail with match/drift
+    if let Some(baseline_path) = &config.baseline {
+        if summary.errors > 0 {
+            // An incomplete scan can neither update nor be compared to a baseline
+            return Ok(summary.exit_code(&config.exit_codes));
+        }
+
+        if config.update_baseline {
+            update_baseline(
+                &scan_results,
+                baseline_path,
+                signing_backend.as_deref(),
+                &inventory,
+                config.evidence_options(),
+            )?;
+            if !config.quiet {
+                println!("Baseline updated: {}", baseline_path.display());
+                println!();
+            }
+            return Ok(0);
+        }
+
+        if let Some(baseline) = &baseline {
+            return check_baseline(&scan_results, baseline, baseline_path, config.quiet, &style);
+        }
+    }
+
+    Ok(summary.exit_code(&config.exit_codes))
+}
+
+/// Print what each policy would collect, without collecting anything
+///
+/// Policies are compiled and resolved as in a scan, but every collector
+/// only records its plan. With `--output`, the plan is saved as JSON.
+pub fn run_explain(config: &ScanConfig, esp_files: &[PathBuf]) -> Result<i32, ScanError> {
+    let style = output::ConsoleStyle::detect(config.color, config.width).with_ascii(config.ascii);
+    let allowlist = load_allowlist(config.command_allowlist.as_deref())?;
+    let elevation = load_elevation(config.elevate.as_deref(), &allowlist)?;
+    let spec = load_registry_spec(config.registry_config.as_deref())?;
+    let recorder = PlanRecorder::new();
+    let registry = Arc::new(
+        registry::create_explain_registry(
+            &allowlist,
+            elevation.as_ref(),
+            config.path_expansion.as_ref(),
+            spec.as_ref(),
+            &config.ctn_filter,
+            &config.field_map,
+            &recorder,
+        )
+        .map_err(|e| {
+            log_error!(
+                logging::codes::system::INTERNAL_ERROR,
+                "Failed to create explain registry",
+                "error" => e.to_string()
+            );
+            ScanError::Registry(e)
+        })?,
+    );
+
+    let mut plans = Vec::new();
+    let mut errors = 0;
+
+    for (index, esp_file) in esp_files.iter().enumerate() {
+        let planned = compile_file_with_vars(esp_file, &config.vars)
+            .and_then(|ast| explain(&ast, registry.clone(), &recorder));
+        match planned {
+            Ok(collections) => plans.push(output::PolicyPlan {
+                esp_file: esp_file.clone(),
+                collections,
+            }),
+            Err(e) => {
+                errors += 1;
+                if !config.quiet {
+                    print_scan_error(index + 1, esp_files.len(), esp_file, &e, &style);
+                }
+            }
+        }
+    }
+
+    if !config.quiet {
+        output::print_explain_plan(&plans);
+    }
+
+    if let Some(output_path) = &config.output_file {
+        let json = serde_json::to_string_pretty(&output::explain_report(&plans))
+            .map_err(|e| ScanError::Output(output::OutputError::Serialization(e.to_string())))?;
+        std::fs::write(output_path, &json)
+            .map_err(|e| ScanError::WriteFile(output_path.display().to_string(), e))?;
+    }
+
+    Ok(if errors > 0 {
+        config.exit_codes.error
+    } else {
+        0
+    })
 }
 
-/// Execute scans on all ESP files
-fn execute_scans(
+/// Scan each policy and print the decision trace of the criteria matching
+/// `criterion_id`, and nothing else
+///
+/// The ID is `ctn_type[object, ...]` as in the timings, or a bare CTN type
+/// for every criterion of that type. Exits with the failure code if a traced
+/// criterion did not pass, the error code if a policy could not be scanned,
+/// and the usage code if no criterion matches.
+pub fn run_trace(
+    config: &ScanConfig,
     esp_files: &[PathBuf],
-    registry: &Arc<CtnStrategyRegistry>,
-    quiet: bool,
-) -> Result<(Vec<ScanResult>, ScanSummary), ScanError> {
-    let mut scan_results: Vec<ScanResult> = Vec::new();
-    let mut summary = ScanSummary::new(esp_files.len());
+    criterion_id: &str,
+) -> Result<i32, ScanError> {
+    let style = output::ConsoleStyle::detect(config.color, config.width).with_ascii(config.ascii);
+    let allowlist = load_allowlist(config.command_allowlist.as_deref())?;
+    let elevation = load_elevation(config.elevate.as_deref(), &allowlist)?;
+    let spec = load_registry_spec(config.registry_config.as_deref())?;
+    let recorder = TraceRecorder::new();
+    let registry = Arc::new(
+        registry::create_trace_registry(
+            &allowlist,
+            elevation.as_ref(),
+            config.path_expansion.as_ref(),
+            spec.as_ref(),
+            &config.ctn_filter,
+            &config.field_map,
+            &recorder,
+        )
+        .map_err(|e| {
+            log_error!(
+                logging::codes::system::INTERNAL_ERROR,
+                "Failed to create trace registry",
+                "error" => e.to_string()
+            );
+            ScanError::Registry(e)
+        })?,
+    );
+
+    let mut matched = 0;
+    let mut failed = false;
+    let mut errors = 0;
 
     for (index, esp_file) in esp_files.iter().enumerate() {
-        let file_num = index + 1;
-        logging::set_file_context(esp_file.clone(), file_num);
+        // Discard traces left over from a policy that could not be scanned
+        recorder.take();
+        let scanned = compile_file_with_vars(esp_file, &config.vars)
+            .and_then(|ast| scan_ast(&ast, registry.clone()));
+        if let Err(e) = scanned {
+            errors += 1;
+            if !config.quiet {
+                print_scan_error(index + 1, esp_files.len(), esp_file, &e, &style);
+            }
+            continue;
+        }
+
+        let traces = recorder.take();
+        for trace in explain_criterion(&traces, criterion_id) {
+            matched += 1;
+            failed |= trace.outcome != Outcome::Pass;
+            println!("{}", esp_file.display());
+            println!("{}", format_trace(trace));
+            println!();
+        }
+    }
+
+    if matched == 0 {
+        eprintln!(
+            "No criterion matches '{}' in the scanned policies",
+            criterion_id
+        );
+        return Ok(ScanSummary::USAGE_EXIT_CODE);
+    }
+    Ok(if errors > 0 {
+        config.exit_codes.error
+    } else if failed {
+        config.exit_codes.fail
+    } else {
+        0
+    })
+}
+
+/// Check each policy against the collector contracts, without scanning
+///
+/// Policies are compiled, then each criterion's state fields, operations
+/// and object fields are checked against its CTN type's contract. Warnings
+/// exit with the failure code; policies that do not compile with the error
+/// code.
+pub fn run_check(config: &ScanConfig, esp_files: &[PathBuf]) -> Result<i32, ScanError> {
+    let style = output::ConsoleStyle::detect(config.color, config.width).with_ascii(config.ascii);
+    let allowlist = load_allowlist(config.command_allowlist.as_deref())?;
+    let elevation = load_elevation(config.elevate.as_deref(), &allowlist)?;
+    let registry = create_registry(
+        &allowlist,
+        elevation.as_ref(),
+        config,
+        &CriterionTimer::new(),
+    )?;
+
+    let mut checks = Vec::new();
+    let mut errors = 0;
 
-        match scan_file_with_logging(esp_file, registry.clone()) {
+    for (index, esp_file) in esp_files.iter().enumerate() {
+        match check_file(esp_file, &registry) {
+            Ok(warnings) => checks.push(output::PolicyCheck {
+                esp_file: esp_file.clone(),
+                warnings,
+            }),
+            Err(e) => {
+                errors += 1;
+                if !config.quiet {
+                    print_scan_error(index + 1, esp_files.len(), esp_file, &e, &style);
+                }
+            }
+        }
+    }
+
+    if !config.quiet {
+        output::print_check_report(&checks);
+    }
+
+    Ok(if errors > 0 {
+        config.exit_codes.error
+    } else if checks.iter().any(|c| !c.warnings.is_empty()) {
+        config.exit_codes.fail
+    } else {
+        0
+    })
+}
+
+/// Quiet period after a change before re-scanning, so the several writes
+/// of one editor save trigger one scan
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// What the watch loop waits for
+enum WatchEvent {
+    /// A file in a watched directory was created, written or removed
+    Changed(PathBuf),
+    /// Ctrl-C
+    Stop,
+}
+
+/// An input path being watched: a directory's policies, or one file
+///
+/// Both are watched through the directory, so a file an editor saves by
+/// writing a new file and renaming it over the old is still seen.
+struct WatchTarget {
+    dir: PathBuf,
+    file: Option<OsString>,
+}
+
+impl WatchTarget {
+    fn new(input_path: &Path) -> Result<Self, std::io::Error> {
+        let path = input_path.canonicalize()?;
+        if path.is_dir() {
+            return Ok(Self {
+                dir: path,
+                file: None,
+            });
+        }
+        Ok(Self {
+            dir: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+            file: path.file_name().map(|name| name.to_os_string()),
+        })
+    }
+
+    /// Whether a change to `path` can change the scan
+    fn covers(&self, path: &Path) -> bool {
+        path.parent() == Some(self.dir.as_path())
+            && match &self.file {
+                Some(name) => path.file_name() == Some(name.as_os_str()),
+                None => path.extension().is_some_and(|ext| ext == "esp"),
+            }
+    }
+}
+
+/// Scan, then scan again whenever a policy changes, until Ctrl-C
+///
+/// Registries are created once and reused by every scan. Each change
+/// re-discovers the input paths, so a policy added to an input directory is
+/// scanned from then on, and re-applies `--select`. Several changes within
+/// `WATCH_DEBOUNCE` of each other trigger one scan. Results go to the
+/// console only. Ctrl-C stops the watch once the scan in progress, if any,
+/// has finished, and exits 0.
+pub fn run_watch(config: &ScanConfig, esp_files: &[PathBuf]) -> Result<i32, ScanError> {
+    let style = output::ConsoleStyle::detect(config.color, config.width).with_ascii(config.ascii);
+    let allowlist = load_allowlist(config.command_allowlist.as_deref())?;
+    let elevation = load_elevation(config.elevate.as_deref(), &allowlist)?;
+    let jobs = scan_jobs(config.jobs, esp_files.len());
+
+    let targets = config
+        .input_paths
+        .iter()
+        .map(|path| {
+            WatchTarget::new(path)
+                .map_err(|e| ScanError::Watch(format!("{}: {}", path.display(), e).into()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (sender, events) = mpsc::channel();
+    let stop = sender.clone();
+    ctrlc::set_handler(move || {
+        let _ = stop.send(WatchEvent::Stop);
+    })
+    .map_err(|e| ScanError::Watch(e.into()))?;
+
+    let mut watcher =
+        notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+            Ok(event) if is_content_change(&event.kind) => {
+                for path in event.paths {
+                    let _ = sender.send(WatchEvent::Changed(path));
+                }
+            }
+            Ok(_) => {}
+            Err(e) => log_error!(
+                logging::codes::system::INTERNAL_ERROR,
+                "File watch failed",
+                "error" => e.to_string()
+            ),
+        })
+        .map_err(|e| ScanError::Watch(e.into()))?;
+    let dirs: BTreeSet<&Path> = targets.iter().map(|target| target.dir.as_path()).collect();
+    for dir in dirs {
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| ScanError::Watch(e.into()))?;
+    }
+
+    println!();
+    println!("ESP Compliance Agent v{}", env!("CARGO_PKG_VERSION"));
+    let mut esp_files = Some(esp_files.to_vec());
+    loop {
+        if let Some(esp_files) = &esp_files {
+            // Fresh workers per scan, so no collection is served from an
+            // earlier scan's cache
+            let workers = create_workers(&allowlist, elevation.as_ref(), config, jobs)?;
+            watch_scan(esp_files, &workers, config, &style);
+        }
+
+        println!("Watching for changes (Ctrl-C to stop)...");
+        let Some(changed) = next_change(&events, &targets) else {
+            println!("Watch stopped");
+            return Ok(0);
+        };
+
+        let changed: Vec<String> = changed.iter().map(|p| p.display().to_string()).collect();
+        println!();
+        println!("Changed: {}", changed.join(", "));
+        log_info!("Policies changed, re-scanning", "files" => changed.join(","));
+
+        esp_files = match discovery::discover_esp_files_in(&config.input_paths) {
+            Ok(found) => Some(select_policies(config, &found)).filter(|files| !files.is_empty()),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                None
+            }
+        };
+        if esp_files.is_none() {
+            println!("No ESP files to scan");
+        }
+    }
+}
+
+/// Scan once and print the results, as a scan without output files would
+fn watch_scan(
+    esp_files: &[PathBuf],
+    workers: &[Arc<ScanWorker>],
+    config: &ScanConfig,
+    style: &output::ConsoleStyle,
+) {
+    let start = Instant::now();
+    println!("Scanning {} ESP file(s)...", esp_files.len());
+    println!();
+
+    match execute_scans(esp_files, workers, config, style, None, true, None) {
+        Ok((scan_results, _, _, timings)) => {
+            output::print_results(&scan_results, style);
+            output::print_timings(&timings, style);
+            print_execution_info(start.elapsed(), config, style);
+        }
+        Err(e) => eprintln!("Error: {}", e),
+    }
+}
+
+/// Whether a file system event can have changed a file's content
+fn is_content_change(kind: &EventKind) -> bool {
+    match kind {
+        EventKind::Create(_) | EventKind::Remove(_) => true,
+        EventKind::Modify(modify) => !matches!(modify, ModifyKind::Metadata(_)),
+        _ => false,
+    }
+}
+
+/// Wait for a change covered by `targets`, then until `WATCH_DEBOUNCE`
+/// passes without another, returning every changed path
+///
+/// Returns `None` on Ctrl-C, or if the watcher stopped.
+fn next_change(events: &Receiver<WatchEvent>, targets: &[WatchTarget]) -> Option<Vec<PathBuf>> {
+    let mut changed: Vec<PathBuf> = Vec::new();
+    loop {
+        let event = if changed.is_empty() {
+            events.recv().ok()?
+        } else {
+            match events.recv_timeout(WATCH_DEBOUNCE) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => return Some(changed),
+                Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        };
+
+        match event {
+            WatchEvent::Stop => return None,
+            WatchEvent::Changed(path) => {
+                if targets.iter().any(|target| target.covers(&path)) && !changed.contains(&path) {
+                    changed.push(path);
+                }
+            }
+        }
+    }
+}
+
+/// Compare the scan against a baseline and print the drift report
+///
+/// The structured JSON report is always printed, even in quiet mode, since
+/// it is the output of a baseline run.
+fn check_baseline(
+    scan_results: &[CountedScanResult],
+    baseline: &output::Baseline,
+    baseline_path: &Path,
+    quiet: bool,
+    style: &output::ConsoleStyle,
+) -> Result<i32, ScanError> {
+    let current = output::failing_criteria(scan_results);
+    let diff = output::compare_to_baseline(baseline, &current);
+
+    if !quiet {
+        output::print_baseline_diff(&diff, baseline_path, style);
+    }
+
+    let report = serde_json::to_string_pretty(&diff.to_report(baseline_path))
+        .map_err(|e| ScanError::Output(output::OutputError::Serialization(e.to_string())))?;
+    println!("{}", report);
+
+    if diff.matches() {
+        Ok(0)
+    } else {
+        log_info!(
+            "Scan deviates from baseline",
+            "new_failures" => diff.new_failures.len(),
+            "resolved_failures" => diff.resolved_failures.len()
+        );
+        Ok(ScanSummary::BASELINE_DRIFT_EXIT_CODE)
+    }
+}
+
+/// Write the current scan as a full result to the baseline path
+fn update_baseline(
+    scan_results: &[CountedScanResult],
+    baseline_path: &Path,
+    backend: Option<&dyn SigningBackend>,
+    inventory: &HostInventory,
+    evidence: output::EvidenceOptions<'_>,
+) -> Result<(), ScanError> {
+    if scan_results.is_empty() {
+        return Ok(());
+    }
+
+    let json = output::build_output(
+        scan_results,
+        &[],
+        OutputFormat::Full,
+        backend,
+        Some(inventory),
+        evidence,
+        None,
+    )
+    .map_err(ScanError::Output)?;
+
+    std::fs::write(baseline_path, &json)
+        .map_err(|e| ScanError::WriteFile(baseline_path.display().to_string(), e))?;
+
+    Ok(())
+}
+
+/// Append the scan's hashes to the journal, returning the entry's `seq`
+fn append_journal(
+    scan_results: &[CountedScanResult],
+    journal_path: &Path,
+    backend: Option<&dyn SigningBackend>,
+    inventory: &HostInventory,
+) -> Result<u64, ScanError> {
+    let (content_hash, evidence_hash) =
+        output::combine_scan_hashes(scan_results).map_err(ScanError::Output)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    let entry = output::append_entry(
+        journal_path,
+        &inventory::format_utc(now),
+        inventory.hostname.as_deref().unwrap_or("unknown"),
+        &content_hash,
+        &evidence_hash,
+        backend,
+    )
+    .map_err(ScanError::Journal)?;
+    Ok(entry.seq)
+}
+
+/// Number of policies to scan at once
+///
+/// Defaults to the number of logical CPUs, and is never more than the
+/// number of files.
+fn scan_jobs(requested: Option<usize>, file_count: usize) -> usize {
+    requested
+        .unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(NonZeroUsize::get)
+                .unwrap_or(1)
+        })
+        .min(file_count)
+        .max(1)
+}
+
+/// A scanner registry and the timer its criteria record into
+struct ScanWorker {
+    registry: Arc<CtnStrategyRegistry>,
+    timer: CriterionTimer,
+    /// The registry config, `--allow-ctn` or `--deny-ctn` may leave CTN
+    /// types out, so policies are checked for them before scanning
+    check_ctn_types: bool,
+}
+
+impl ScanWorker {
+    /// Refuse a policy using a CTN type that has no strategy, when
+    /// checking CTN types
+    fn check_ctn_types(
+        &self,
+        esp_file: &Path,
+        vars: &HashMap<String, String>,
+    ) -> Result<(), contract_kit::execution_api::ScanError> {
+        if !self.check_ctn_types {
+            return Ok(());
+        }
+        check_ctn_types(esp_file, vars, &self.registry)
+    }
+}
+
+/// Create `count` workers whose collectors share concurrency limits
+///
+/// Workers are shared so a scan with a deadline can leave one behind on a
+/// thread of its own.
+fn create_workers(
+    allowlist: &CommandAllowlist,
+    elevation: Option<&Elevation>,
+    config: &ScanConfig,
+    count: usize,
+) -> Result<Vec<Arc<ScanWorker>>, ScanError> {
+    let spec = load_registry_spec(config.registry_config.as_deref())?;
+    let timers: Vec<CriterionTimer> = (0..count).map(|_| CriterionTimer::new()).collect();
+    let registries = registry::create_scanner_registries(
+        allowlist,
+        elevation,
+        config.path_expansion.as_ref(),
+        spec.as_ref(),
+        &config.ctn_filter,
+        &config.field_map,
+        config.evidence_level(),
+        &timers,
+    )
+    .map_err(registry_error)?;
+
+    Ok(registries
+        .into_iter()
+        .zip(timers)
+        .map(|(registry, timer)| {
+            Arc::new(ScanWorker {
+                registry: Arc::new(registry),
+                timer,
+                check_ctn_types: spec.is_some() || !config.ctn_filter.is_empty(),
+            })
+        })
+        .collect())
+}
+
+/// One scanned policy, with its timings
+struct PolicyScan {
+    esp_file: PathBuf,
+    started: Instant,
+    duration: Duration,
+    criteria: Vec<CriterionTiming>,
+    result: Result<CountedScanResult, contract_kit::execution_api::ScanError>,
+}
+
+/// Scan one policy with `worker`, timing it and the criteria it ran
+///
+/// A policy using a CTN type the agent configuration leaves out is not
+/// scanned; it is reported as denied, naming the type. Criteria an executor
+/// marked not applicable are counted as such in the result.
+fn scan_policy(esp_file: &Path, worker: &ScanWorker, vars: &HashMap<String, String>) -> PolicyScan {
+    let started = Instant::now();
+    let result = match worker.check_ctn_types(esp_file, vars) {
+        Err(error) => Err(error),
+        Ok(()) if vars.is_empty() => scan_file_with_logging(esp_file, worker.registry.clone()),
+        Ok(()) => scan_file_with_vars(esp_file, vars.clone(), worker.registry.clone()),
+    };
+    let duration = started.elapsed();
+    let criteria = worker.timer.take();
+    let marked = criteria.iter().filter(|c| c.not_applicable).count();
+
+    PolicyScan {
+        esp_file: esp_file.to_path_buf(),
+        started,
+        duration,
+        criteria,
+        result: result.map(|result| CountedScanResult::new(result, marked)),
+    }
+}
+
+impl PolicyScan {
+    /// A policy that produced no result, failing with `reason`
+    fn unfinished(esp_file: &Path, reason: &str) -> Self {
+        Self {
+            esp_file: esp_file.to_path_buf(),
+            started: Instant::now(),
+            duration: Duration::ZERO,
+            criteria: Vec::new(),
+            result: Err(contract_kit::execution_api::ScanError::ExecutionFailed(
+                reason.to_string(),
+            )),
+        }
+    }
+}
+
+/// Print a scanned policy's progress line and log a failed scan
+fn report_scan(
+    file_num: usize,
+    total: usize,
+    scan: &PolicyScan,
+    quiet: bool,
+    style: &output::ConsoleStyle,
+) {
+    match &scan.result {
+        Ok(scan_result) => {
+            if !quiet {
+                output::print_progress_result(file_num, total, scan_result, style);
+            }
+        }
+        Err(e) => {
+            if !quiet {
+                print_scan_error(file_num, total, &scan.esp_file, e, style);
+            }
+            log_error!(
+                logging::codes::system::INTERNAL_ERROR,
+                "Scan failed",
+                "file" => scan.esp_file.display().to_string(),
+                "error" => e.to_string()
+            );
+        }
+    }
+}
+
+/// NDJSON output written while scanning
+type NdjsonStream = output::NdjsonWriter<Box<dyn std::io::Write + Send>>;
+
+/// Open the NDJSON stream, continuing the output of an earlier run when
+/// resuming from `checkpoint`
+fn open_stream(
+    config: &ScanConfig,
+    checkpoint: Option<output::Checkpoint>,
+) -> Result<NdjsonStream, ScanError> {
+    let target = config.output_file.as_deref();
+    let writer = match (&checkpoint, target) {
+        (Some(checkpoint), Some(path)) if !checkpoint.prior().is_empty() => {
+            if !path.exists() {
+                return Err(ScanError::Checkpoint(
+                    output::CheckpointError::MissingOutput {
+                        checkpoint: checkpoint.path().to_path_buf(),
+                        output: path.to_path_buf(),
+                    },
+                ));
+            }
+            output::NdjsonWriter::append(path)
+        }
+        _ => output::NdjsonWriter::create(target),
+    }
+    .map_err(|e| ScanError::WriteFile(ndjson_target(config), e))?;
+
+    Ok(match checkpoint {
+        Some(checkpoint) => writer.with_checkpoint(checkpoint),
+        None => writer,
+    })
+}
+
+/// Count the policies an earlier run checkpointed in the scan's summary
+///
+/// Not applicable policies count as passed, as they do when scanned.
+fn add_checkpointed(
+    summary: &mut ScanSummary,
+    resumed: &[output::CheckpointEntry],
+    fail_on: Option<Criticality>,
+) {
+    for entry in resumed {
+        summary.total_files += 1;
+        if entry.status == output::CheckpointStatus::Failed {
+            summary.failed += 1;
+            let fails_scan = parse_criticality(&entry.criticality)
+                .is_none_or(|criticality| meets_fail_threshold(criticality, fail_on));
+            if !fails_scan {
+                summary.failed_below_threshold += 1;
+            }
+        } else {
+            summary.passed += 1;
+        }
+    }
+}
+
+/// Where NDJSON output goes, for error messages
+fn ndjson_target(config: &ScanConfig) -> String {
+    config
+        .output_file
+        .as_ref()
+        .map_or_else(|| "stdout".to_string(), |path| path.display().to_string())
+}
+
+/// Totals of a scan, updated as each policy finishes
+struct ScanTally<'a> {
+    config: &'a ScanConfig,
+    style: &'a output::ConsoleStyle,
+    total: usize,
+    finished: usize,
+    summary: ScanSummary,
+    timer: output::ScanTimer,
+    stream: Option<&'a mut NdjsonStream>,
+    stream_error: Option<std::io::Error>,
+    retain: bool,
+    results: Vec<(usize, CountedScanResult)>,
+    errors: Vec<(usize, output::PolicyError)>,
+}
+
+impl<'a> ScanTally<'a> {
+    fn new(
+        total: usize,
+        config: &'a ScanConfig,
+        style: &'a output::ConsoleStyle,
+        stream: Option<&'a mut NdjsonStream>,
+        retain: bool,
+    ) -> Self {
+        Self {
+            config,
+            style,
+            total,
+            finished: 0,
+            summary: ScanSummary::new(total),
+            timer: output::ScanTimer::start(),
+            stream,
+            stream_error: None,
+            retain,
+            results: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Report, stream and count the scan of the `index`th policy
+    fn record(&mut self, index: usize, scan: PolicyScan) {
+        self.finished += 1;
+        report_scan(
+            self.finished,
+            self.total,
+            &scan,
+            self.config.quiet,
+            self.style,
+        );
+
+        if let Some(stream) = self.stream.as_deref_mut() {
+            if self.stream_error.is_none() {
+                self.stream_error = stream.write_policy(&scan.esp_file, &scan.result).err();
+            }
+        }
+
+        self.timer.record_duration(
+            &scan.esp_file,
+            scan.result
+                .as_ref()
+                .ok()
+                .map(|result| result.outcome.policy_id.as_str()),
+            scan.started,
+            scan.duration,
+            scan.criteria,
+        );
+
+        match scan.result {
             Ok(scan_result) => {
                 if scan_result.tree_passed {
-                    summary.passed += 1;
+                    self.summary.passed += 1;
                 } else {
-                    summary.failed += 1;
+                    self.summary.failed += 1;
+                    if !meets_fail_threshold(scan_result.outcome.criticality, self.config.fail_on) {
+                        self.summary.failed_below_threshold += 1;
+                    }
                 }
-
-                // Print progress indicator
-                if !quiet {
-                    output::print_progress_result(file_num, esp_files.len(), &scan_result);
+                if self.retain {
+                    self.results.push((index, scan_result));
                 }
-
-                scan_results.push(scan_result);
             }
             Err(e) => {
-                summary.errors += 1;
-                if !quiet {
-                    println!(
-                        "[{}/{}] \x1b[31m✗\x1b[0m {} (ERROR: {})",
-                        file_num,
-                        esp_files.len(),
-                        esp_file.display(),
-                        e
-                    );
+                self.summary.errors += 1;
+                self.errors
+                    .push((index, output::PolicyError::new(&scan.esp_file, &e)));
+            }
+        }
+    }
+
+    /// Results and errors in input order, with the summary and timings
+    fn finish(self) -> Result<ScanOutcome, ScanError> {
+        if let Some(e) = self.stream_error {
+            return Err(ScanError::WriteFile(ndjson_target(self.config), e));
+        }
+
+        let mut results = self.results;
+        results.sort_by_key(|(index, _)| *index);
+        let mut errors = self.errors;
+        errors.sort_by_key(|(index, _)| *index);
+        Ok((
+            results.into_iter().map(|(_, result)| result).collect(),
+            errors.into_iter().map(|(_, error)| error).collect(),
+            self.summary,
+            self.timer.finish(),
+        ))
+    }
+}
+
+/// Results, unscannable policies, summary and timings of a scan
+type ScanOutcome = (
+    Vec<CountedScanResult>,
+    Vec<output::PolicyError>,
+    ScanSummary,
+    output::ScanTimings,
+);
+
+/// Execute scans on all ESP files
+///
+/// With one worker, policies are scanned one at a time. With more, each
+/// worker thread takes the next unscanned policy. Either way, progress lines
+/// and `stream` lines are written as policies finish, numbered in completion
+/// order, and results are returned in `esp_files` order, so output and its
+/// hashes do not depend on the number of jobs. Without `retain`, results are
+/// only counted and streamed, and none are returned; policies that could not
+/// be scanned are always returned. Failed policies below
+/// the `--fail-on` threshold are counted separately so they do not fail the
+/// scan.
+///
+/// With a `deadline`, policies are scanned as in `scan_until`, and the
+/// summary records whether the deadline passed.
+fn execute_scans(
+    esp_files: &[PathBuf],
+    workers: &[Arc<ScanWorker>],
+    config: &ScanConfig,
+    style: &output::ConsoleStyle,
+    stream: Option<&mut NdjsonStream>,
+    retain: bool,
+    deadline: Option<Instant>,
+) -> Result<ScanOutcome, ScanError> {
+    let mut tally = ScanTally::new(esp_files.len(), config, style, stream, retain);
+
+    match (workers, deadline) {
+        (_, Some(deadline)) => {
+            tally.summary.timed_out =
+                scan_until(esp_files, workers, &config.vars, &mut tally, deadline);
+        }
+        ([worker], None) => {
+            for (index, esp_file) in esp_files.iter().enumerate() {
+                let _context = FileContext::enter(esp_file, index + 1);
+                let scan = scan_policy(esp_file, worker, &config.vars);
+                tally.record(index, scan);
+            }
+        }
+        _ => {
+            let shared = Mutex::new(tally);
+            scan_parallel(esp_files, workers, &config.vars, &shared);
+            tally = shared.into_inner().unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    tally.finish()
+}
+
+/// Scan policies on one thread per worker, recording each in `tally`
+///
+/// Recording is serialized so progress and stream lines do not interleave.
+fn scan_parallel(
+    esp_files: &[PathBuf],
+    workers: &[Arc<ScanWorker>],
+    vars: &HashMap<String, String>,
+    tally: &Mutex<ScanTally<'_>>,
+) {
+    let next = AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        for worker in workers {
+            let next = &next;
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(esp_file) = esp_files.get(index) else {
+                    break;
+                };
+
+                let _context = FileContext::enter(esp_file, index + 1);
+                let scan = scan_policy(esp_file, worker, vars);
+                tally
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .record(index, scan);
+            });
+        }
+    });
+}
+
+/// Scan policies on one detached thread per worker until all are recorded
+/// in `tally` or `deadline` passes
+///
+/// Results are recorded on the calling thread as they arrive. Threads are
+/// not joined, so a collector that never returns cannot hold the scan past
+/// the deadline. When it passes, no further policy is started, the agent's
+/// child processes are killed, and each policy without a result is recorded
+/// as an error: timed out if a worker had started it, not scanned otherwise.
+/// The abandoned threads end with the process. Returns whether the deadline
+/// passed.
+fn scan_until(
+    esp_files: &[PathBuf],
+    workers: &[Arc<ScanWorker>],
+    vars: &HashMap<String, String>,
+    tally: &mut ScanTally<'_>,
+    deadline: Instant,
+) -> bool {
+    let files: Arc<[PathBuf]> = esp_files.into();
+    let vars = Arc::new(vars.clone());
+    let next = Arc::new(AtomicUsize::new(0));
+    let (sender, results) = mpsc::channel();
+
+    for worker in workers {
+        let (worker, files, vars, next, sender) = (
+            worker.clone(),
+            files.clone(),
+            vars.clone(),
+            next.clone(),
+            sender.clone(),
+        );
+        std::thread::spawn(move || loop {
+            let index = next.fetch_add(1, Ordering::SeqCst);
+            let Some(esp_file) = files.get(index) else {
+                break;
+            };
+
+            let scan = {
+                let _context = FileContext::enter(esp_file, index + 1);
+                scan_policy(esp_file, &worker, &vars)
+            };
+            if sender.send((index, scan)).is_err() {
+                break;
+            }
+        });
+    }
+    drop(sender);
+
+    let mut unrecorded: BTreeSet<usize> = (0..esp_files.len()).collect();
+    while !unrecorded.is_empty() {
+        match results.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok((index, scan)) => {
+                unrecorded.remove(&index);
+                tally.record(index, scan);
+            }
+            Err(RecvTimeoutError::Timeout) => break,
+            // Every worker stopped early, which only a panic does
+            Err(RecvTimeoutError::Disconnected) => {
+                for index in std::mem::take(&mut unrecorded) {
+                    if let Some(esp_file) = esp_files.get(index) {
+                        let scan = PolicyScan::unfinished(esp_file, "scan thread panicked");
+                        tally.record(index, scan);
+                    }
                 }
-                log_error!(
-                    logging::codes::system::INTERNAL_ERROR,
-                    "Scan failed",
-                    "file" => esp_file.display().to_string(),
-                    "error" => e.to_string()
-                );
             }
         }
+    }
+    if unrecorded.is_empty() {
+        return false;
+    }
 
-        logging::clear_file_context();
+    // Claim every remaining policy so no worker starts another
+    let started = next.swap(esp_files.len(), Ordering::SeqCst);
+    let killed = subprocess::kill_descendants();
+    log_error!(
+        logging::codes::system::INTERNAL_ERROR,
+        "Scan deadline reached",
+        "unfinished" => unrecorded.len(),
+        "killed_processes" => killed
+    );
+    for index in unrecorded {
+        let Some(esp_file) = esp_files.get(index) else {
+            continue;
+        };
+        let reason = if index < started {
+            "--timeout reached while scanning; in-flight collector commands were killed"
+        } else {
+            "not scanned: --timeout reached"
+        };
+        tally.record(index, PolicyScan::unfinished(esp_file, reason));
+    }
+    true
+}
+
+/// Print a failed scan, with one `file:line:column` line per compiler diagnostic
+fn print_scan_error(
+    file_num: usize,
+    total: usize,
+    esp_file: &Path,
+    error: &contract_kit::execution_api::ScanError,
+    style: &output::ConsoleStyle,
+) {
+    let failed = style.paint(output::Color::Red, "✗");
+    let diagnostics = error.diagnostics();
+    if diagnostics.is_empty() {
+        println!(
+            "[{}/{}] {} {} (ERROR: {})",
+            file_num,
+            total,
+            failed,
+            esp_file.display(),
+            error
+        );
+        return;
     }
 
-    Ok((scan_results, summary))
+    println!(
+        "[{}/{}] {} {} (ERROR: compilation failed)",
+        file_num,
+        total,
+        failed,
+        esp_file.display()
+    );
+    let file = esp_file.display().to_string();
+    for diagnostic in diagnostics {
+        println!("    {}", diagnostic.to_annotation(&file));
+    }
 }
 
-/// Create the strategy registry
-fn create_registry() -> Result<CtnStrategyRegistry, ScanError> {
-    registry::create_scanner_registry().map_err(|e| {
-        log_error!(
-            logging::codes::system::INTERNAL_ERROR,
-            "Failed to create scanner registry",
-            "error" => e.to_string()
+/// Policies whose metadata meets every `--select` condition, in input order
+///
+/// Metadata is only known once a policy compiles, so each policy is compiled
+/// here (with its `--var` values) and compiled again when scanned. Policies
+/// that do not compile are kept, so the scan reports them as errors rather
+/// than a selector hiding them. Without conditions every policy is kept and
+/// none is compiled.
+pub fn select_policies(config: &ScanConfig, esp_files: &[PathBuf]) -> Vec<PathBuf> {
+    if config.select.is_empty() {
+        return esp_files.to_vec();
+    }
+
+    esp_files
+        .iter()
+        .filter(
+            |esp_file| match compile_file_with_vars(esp_file, &config.vars) {
+                Ok(ast) => {
+                    let metadata = extract_metadata(&ast);
+                    config
+                        .select
+                        .iter()
+                        .all(|selector| selector.matches(&metadata))
+                }
+                Err(_) => true,
+            },
+        )
+        .cloned()
+        .collect()
+}
+
+/// Every command the scanner's collectors may run, sorted
+///
+/// The site allowlist and registry config are loaded as for a scan, and
+/// `ctn_filter` applied, so `--print-allowlist` shows exactly what a scan
+/// with the same options could invoke.
+pub fn allowed_commands(
+    command_allowlist: Option<&Path>,
+    registry_config: Option<&Path>,
+    ctn_filter: &CtnFilter,
+) -> Result<Vec<String>, ScanError> {
+    let allowlist = load_allowlist(command_allowlist)?;
+    let spec = load_registry_spec(registry_config)?;
+    Ok(registry::allowed_commands(
+        &allowlist,
+        spec.as_ref(),
+        ctn_filter,
+    ))
+}
+
+/// Load the site command allowlist
+///
+/// `--command-allowlist` takes precedence over `ESP_COMMAND_ALLOWLIST`.
+/// Without either, only the built-in commands are allowed.
+fn load_allowlist(path: Option<&Path>) -> Result<CommandAllowlist, ScanError> {
+    let allowlist = match path {
+        Some(path) => Some(CommandAllowlist::load(path)),
+        None => CommandAllowlist::from_env().transpose(),
+    };
+
+    match allowlist {
+        Some(Ok(allowlist)) => {
+            log_info!(
+                "Site command allowlist loaded",
+                "commands" => allowlist.entries().join(", ")
+            );
+            Ok(allowlist)
+        }
+        Some(Err(e)) => Err(ScanError::Allowlist(e)),
+        None => Ok(CommandAllowlist::new()),
+    }
+}
+
+/// Load the registry config naming the strategies to register
+///
+/// Without `--registry-config`, the default strategies are registered.
+fn load_registry_spec(path: Option<&Path>) -> Result<Option<RegistrySpec>, ScanError> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    let spec = RegistrySpec::load(path).map_err(ScanError::RegistrySpec)?;
+    let names: Vec<&str> = spec.strategies.iter().map(|s| s.name.as_str()).collect();
+    log_info!(
+        "Registry config loaded",
+        "strategies" => names.join(", ")
+    );
+    Ok(Some(spec))
+}
+
+/// Load the elevation wrapper for collector commands
+///
+/// `--elevate` takes precedence over `ESP_ELEVATE_CMD`. The wrapper must be
+/// on the site `allowlist`. Without either, commands run unelevated.
+fn load_elevation(
+    program: Option<&str>,
+    allowlist: &CommandAllowlist,
+) -> Result<Option<Elevation>, ScanError> {
+    let elevation = match program {
+        Some(program) => Elevation::new(program, allowlist).map(Some),
+        None => Elevation::from_env(allowlist),
+    }
+    .map_err(ScanError::Allowlist)?;
+
+    if let Some(elevation) = &elevation {
+        log_info!(
+            "Collector commands elevated",
+            "wrapper" => elevation.program()
         );
-        ScanError::Registry(e)
-    })
+    }
+    Ok(elevation)
+}
+
+/// Create the strategy registry
+fn create_registry(
+    allowlist: &CommandAllowlist,
+    elevation: Option<&Elevation>,
+    config: &ScanConfig,
+    timer: &CriterionTimer,
+) -> Result<CtnStrategyRegistry, ScanError> {
+    let spec = load_registry_spec(config.registry_config.as_deref())?;
+    registry::create_scanner_registry(
+        allowlist,
+        elevation,
+        config.path_expansion.as_ref(),
+        spec.as_ref(),
+        &config.ctn_filter,
+        &config.field_map,
+        timer,
+    )
+    .map_err(registry_error)
+}
+
+/// Log and wrap a registry creation error
+fn registry_error(error: StrategyError) -> ScanError {
+    log_error!(
+        logging::codes::system::INTERNAL_ERROR,
+        "Failed to create scanner registry",
+        "error" => error.to_string()
+    );
+    ScanError::Registry(error)
 }
 
 /// Save output to file
-fn save_output(scan_results: &[ScanResult], config: &ScanConfig) -> Result<(), ScanError> {
+///
+/// Signs with the run's shared `backend` (if any) so that all envelopes
+/// written during a run carry the same signer identity.
+fn save_output(
+    scan_results: &[CountedScanResult],
+    errors: &[output::PolicyError],
+    config: &ScanConfig,
+    backend: Option<&dyn SigningBackend>,
+    inventory: &HostInventory,
+    timings: &output::ScanTimings,
+) -> Result<(), ScanError> {
     let output_path = match &config.output_file {
         Some(path) => path,
         None => return Ok(()), // No output file specified, nothing to do
     };
 
-    let json =
-        output::build_output(scan_results, config.output_format).map_err(ScanError::Output)?;
+    // Attestations leave the host unless configured otherwise
+    let inventory = match config.output_format {
+        OutputFormat::Attestation if !config.attestation_inventory => None,
+        _ => Some(inventory),
+    };
+
+    let output = output::build_output(
+        scan_results,
+        errors,
+        config.output_format,
+        backend,
+        inventory,
+        config.evidence_options(),
+        Some(timings),
+    )
+    .map_err(ScanError::Output)?;
 
-    std::fs::write(output_path, &json)
+    std::fs::write(output_path, &output)
         .map_err(|e| ScanError::WriteFile(output_path.display().to_string(), e))?;
 
     Ok(())
 }
 
+/// Write Prometheus metrics
+///
+/// Writes to a temporary file and renames it into place, so a textfile
+/// collector never reads a partial file.
+fn save_metrics(
+    scan_results: &[CountedScanResult],
+    errors: usize,
+    metrics_path: &Path,
+) -> Result<(), ScanError> {
+    let metrics = output::build_metrics(scan_results, errors);
+
+    let mut tmp_name = metrics_path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    std::fs::write(&tmp_path, metrics)
+        .and_then(|()| std::fs::rename(&tmp_path, metrics_path))
+        .map_err(|e| ScanError::WriteFile(metrics_path.display().to_string(), e))
+}
+
 /// Print execution information
-fn print_execution_info(duration: std::time::Duration, config: &ScanConfig) {
-    println!("────────────────────────────────────────────────────────────────────────────────");
+fn print_execution_info(
+    duration: std::time::Duration,
+    config: &ScanConfig,
+    style: &output::ConsoleStyle,
+) {
+    let rule = style.text(&"─".repeat(80));
+    println!("{}", rule);
     println!("  Duration:     {:.2}s", duration.as_secs_f64());
     if let Some(output_path) = &config.output_file {
         println!(
@@ -167,7 +1481,7 @@ fn print_execution_info(duration: std::time::Duration, config: &ScanConfig) {
             config.output_format
         );
     }
-    println!("────────────────────────────────────────────────────────────────────────────────");
+    println!("{}", rule);
     println!();
 }
 
@@ -180,6 +1494,18 @@ pub enum ScanError {
     Output(output::OutputError),
     /// Failed to write output file
     WriteFile(String, std::io::Error),
+    /// Failed to load baseline
+    Baseline(output::BaselineError),
+    /// Failed to load command allowlist
+    Allowlist(AllowlistError),
+    /// Failed to load the registry config
+    RegistrySpec(RegistrySpecError),
+    /// Failed to append to the scan journal
+    Journal(output::JournalError),
+    /// Failed to open or update the scan checkpoint
+    Checkpoint(output::CheckpointError),
+    /// Failed to watch the input paths
+    Watch(Box<dyn std::error::Error + Send + Sync>),
 }
 
 impl std::fmt::Display for ScanError {
@@ -188,6 +1514,12 @@ impl std::fmt::Display for ScanError {
             ScanError::Registry(e) => write!(f, "Registry creation failed: {}", e),
             ScanError::Output(e) => write!(f, "Output generation failed: {}", e),
             ScanError::WriteFile(path, e) => write!(f, "Failed to write {}: {}", path, e),
+            ScanError::Baseline(e) => write!(f, "Baseline comparison failed: {}", e),
+            ScanError::Allowlist(e) => write!(f, "Command allowlist rejected: {}", e),
+            ScanError::RegistrySpec(e) => write!(f, "Registry config rejected: {}", e),
+            ScanError::Journal(e) => write!(f, "Journal update failed: {}", e),
+            ScanError::Checkpoint(e) => write!(f, "Checkpoint failed: {}", e),
+            ScanError::Watch(e) => write!(f, "Cannot watch input paths: {}", e),
         }
     }
 }
@@ -198,6 +1530,12 @@ impl std::error::Error for ScanError {
             ScanError::Registry(e) => Some(e),
             ScanError::Output(e) => Some(e),
             ScanError::WriteFile(_, e) => Some(e),
+            ScanError::Baseline(e) => Some(e),
+            ScanError::Allowlist(e) => Some(e),
+            ScanError::RegistrySpec(e) => Some(e),
+            ScanError::Journal(e) => Some(e),
+            ScanError::Checkpoint(e) => Some(e),
+            ScanError::Watch(e) => Some(e.as_ref()),
         }
     }
 }
@@ -67,7 +67,8 @@ pub trait SigningBackend: Send + Sync {
     /// Get the key ID
     ///
     /// Returns the key identifier for external lookup/audit.
-    /// Format: `"{backend}:ephemeral:{key_name}"`
+    /// Format: `"{backend}:ephemeral:{key_name}"`, or
+    /// `"software:persistent:{fingerprint}"` for a persisted software key
     #[allow(dead_code)]
     fn key_id(&self) -> &str;
 
@@ -19,6 +19,10 @@
 //! - **TPM (Windows)**: Hardware-backed ECDSA P-256 keys
 //! - **Software**: Cross-platform ECDSA P-256 (FIPS 140-3 compliant)
 //!
+//! Keys are ephemeral by default, so each run has its own `signer_id`. Set
+//! `ESP_SIGNING_KEY` to a PKCS#8 PEM file holding a P-256 key to sign every
+//! run with that key instead.
+//!
 //! ## Usage
 //!
 //! ```ignore
@@ -30,24 +34,47 @@
 //! // Sign an envelope in place
 //! sign_envelope(&mut result.envelope, backend.as_ref())?;
 //! ```
+//!
+//! ## Shared Backends
+//!
+//! A run should sign every envelope with the same key so that all results
+//! carry one stable `signer_id`. Use `create_shared_backend()` once per run
+//! and hand out clones of the `Arc` to every thread that builds output.
+//!
+//! ## Verification
+//!
+//! `verify_signature()` checks a signature block from either backend
+//! against the hashes it covers, using the public key embedded in the block.
+//! `verify_envelope()` does the same for a signed `ResultEnvelope`, and
+//! returns `Ok(false)` (rather than an error) when the signature does not
+//! match. It proves the hashes were signed by the embedded key; it does not
+//! recompute the hashes from the results.
 
 mod backend;
 mod backends;
 mod types;
+mod verify;
 
 pub use backend::SigningBackend;
 pub use backends::SoftwareBackend;
-pub use types::SigningResult;
+pub use types::{SigningError, SigningResult};
+pub use verify::{check_signature, verify_signature};
 
 #[cfg(windows)]
 pub use backends::TpmBackend;
 
-use common::results::ResultEnvelope;
+use common::results::{ResultEnvelope, SignatureBlock};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Environment variable naming a persisted software signing key (PKCS#8 PEM)
+pub const SIGNING_KEY_ENV: &str = "ESP_SIGNING_KEY";
 
 /// Create the best available signing backend for the current platform
 ///
-/// On Windows, attempts to use TPM first, falling back to software.
-/// On other platforms, uses software backend.
+/// If `ESP_SIGNING_KEY` is set, uses a software backend with the key in
+/// that file. Otherwise, on Windows, attempts to use TPM first, falling
+/// back to software; on other platforms, uses an ephemeral software key.
 ///
 /// # Returns
 ///
@@ -55,8 +82,19 @@ use common::results::ResultEnvelope;
 ///
 /// # Errors
 ///
-/// Returns `SigningError::BackendUnavailable` if no backend can be created.
+/// Returns `SigningError::BackendUnavailable` if no backend can be created,
+/// or `SigningError::KeyError` if `ESP_SIGNING_KEY` names an unusable key.
 pub fn create_backend() -> SigningResult<Box<dyn SigningBackend>> {
+    if let Some(path) = std::env::var_os(SIGNING_KEY_ENV).filter(|path| !path.is_empty()) {
+        let path = Path::new(&path);
+        let backend = SoftwareBackend::from_pem_file(path)?;
+        log::info!(
+            "Using software signing backend with key from {}",
+            path.display()
+        );
+        return Ok(Box::new(backend));
+    }
+
     #[cfg(windows)]
     {
         if TpmBackend::is_tpm_available() {
@@ -82,6 +120,19 @@ pub fn create_backend() -> SigningResult<Box<dyn SigningBackend>> {
     Ok(Box::new(SoftwareBackend::new()?))
 }
 
+/// Create the best available signing backend as a shareable handle
+///
+/// Same selection logic as `create_backend()`, but returns an `Arc` so a
+/// single key can sign every envelope produced during a run, including
+/// envelopes built on different threads.
+///
+/// # Errors
+///
+/// Returns `SigningError::BackendUnavailable` if no backend can be created.
+pub fn create_shared_backend() -> SigningResult<Arc<dyn SigningBackend>> {
+    create_backend().map(Arc::from)
+}
+
 /// Sign an envelope in place
 ///
 /// Computes a signature over the envelope's `content_hash` and `evidence_hash`,
@@ -135,6 +186,63 @@ pub fn try_sign_envelope(envelope: &mut ResultEnvelope, backend: &dyn SigningBac
     }
 }
 
+/// Verify the signature of a signed envelope
+///
+/// Recomputes the signed data from the envelope's `content_hash` and
+/// `evidence_hash` and checks it against the signature and public key in
+/// `envelope.signature`. Works for software and TPM signatures.
+///
+/// # Returns
+///
+/// `Ok(true)` if the signature matches, `Ok(false)` if it does not.
+///
+/// # Errors
+///
+/// Returns an error if the envelope is unsigned, the signature covers
+/// other fields, or the key or signature cannot be decoded.
+pub fn verify_envelope(envelope: &ResultEnvelope) -> SigningResult<bool> {
+    let block = envelope
+        .signature
+        .as_ref()
+        .ok_or_else(|| SigningError::SigningFailed("Envelope is not signed".to_string()))?;
+    verify_envelope_hashes(
+        &block.algorithm,
+        &block.public_key,
+        &block.signature,
+        &block.covers,
+        &envelope.content_hash,
+        &envelope.evidence_hash,
+    )
+}
+
+/// Verify envelope signature fields read from a saved result
+///
+/// Same checks as `verify_envelope()`, for callers that only have the
+/// envelope as JSON.
+pub fn verify_envelope_hashes(
+    algorithm: &str,
+    public_key: &str,
+    signature: &str,
+    covers: &[String],
+    content_hash: &str,
+    evidence_hash: &str,
+) -> SigningResult<bool> {
+    let expected = SignatureBlock::standard_covers();
+    if covers != expected.as_slice() {
+        return Err(SigningError::SigningFailed(format!(
+            "Signature covers {:?}, expected {:?}",
+            covers, expected
+        )));
+    }
+    check_signature(
+        algorithm,
+        public_key,
+        signature,
+        content_hash,
+        evidence_hash,
+    )
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -206,4 +314,61 @@ mod tests {
         assert!(sig.covers.contains(&"content_hash".to_string()));
         assert!(sig.covers.contains(&"evidence_hash".to_string()));
     }
+
+    #[test]
+    fn test_verify_envelope() {
+        let backend = create_backend().expect("Failed to create backend");
+        let mut envelope = create_test_envelope();
+        sign_envelope(&mut envelope, backend.as_ref()).expect("Signing failed");
+
+        assert!(verify_envelope(&envelope).unwrap());
+
+        // Changed hashes no longer match the signature
+        let content_hash = std::mem::replace(&mut envelope.content_hash, "sha256:0000".into());
+        assert!(!verify_envelope(&envelope).unwrap());
+        envelope.content_hash = content_hash;
+
+        // Malformed or missing signatures are errors
+        let block = envelope.signature.as_mut().unwrap();
+        block.covers = vec!["content_hash".to_string()];
+        assert!(verify_envelope(&envelope).is_err());
+        let block = envelope.signature.as_mut().unwrap();
+        block.covers = vec!["content_hash".to_string(), "evidence_hash".to_string()];
+        block.signature = "AAAA".to_string();
+        assert!(verify_envelope(&envelope).is_err());
+
+        assert!(verify_envelope(&create_test_envelope()).is_err());
+    }
+
+    #[test]
+    fn test_signing_backend_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync + ?Sized>() {}
+        assert_send_sync::<dyn SigningBackend>();
+        assert_send_sync::<Arc<dyn SigningBackend>>();
+    }
+
+    #[test]
+    fn test_shared_backend_uses_same_signer_for_all_envelopes() {
+        let backend = create_shared_backend().expect("Failed to create backend");
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let backend = Arc::clone(&backend);
+                std::thread::spawn(move || {
+                    let mut envelope = create_test_envelope();
+                    sign_envelope(&mut envelope, backend.as_ref()).expect("Signing failed");
+                    envelope.signature.unwrap().signer_id
+                })
+            })
+            .collect();
+
+        let signer_ids: Vec<String> = handles
+            .into_iter()
+            .map(|h| h.join().expect("Signing thread panicked"))
+            .collect();
+
+        let expected = backend.signer_id().unwrap();
+        assert_eq!(signer_ids.len(), 4);
+        assert!(signer_ids.iter().all(|id| *id == expected));
+    }
 }
@@ -0,0 +1,160 @@
+//! Signature verification
+//!
+//! Checks a `SignatureBlock` produced by either backend against the two
+//! hashes it covers. Each block carries the signer's public key, so no key
+//! store is needed: verification proves the block was signed by the key it
+//! names, and the caller decides whether that key is trusted.
+//!
+//! | Algorithm | Public key | Signature | Signed message |
+//! |-----------|------------|-----------|----------------|
+//! | `ecdsa-p256` | SEC1 point | DER | `signed_data`, hashed again by ECDSA |
+//! | `tpm-ecdsa-p256` | `ECCPUBLICBLOB` | raw `r \|\| s` | `signed_data` as the prehash |
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use p256::ecdsa::signature::hazmat::PrehashVerifier;
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+
+use super::backend::compute_signed_data;
+use super::types::{SigningError, SigningResult};
+
+/// Size of the `BCRYPT_ECCKEY_BLOB` header (magic, key length)
+const ECC_BLOB_HEADER_LEN: usize = 8;
+
+/// Verify a signature over `SHA256(first_hash || second_hash)`
+///
+/// `algorithm`, `public_key` and `signature` are the fields of the
+/// `SignatureBlock`, with the key and signature Base64-encoded. A signature
+/// that does not match is an error, like a malformed one; use
+/// `check_signature` to tell them apart.
+pub fn verify_signature(
+    algorithm: &str,
+    public_key: &str,
+    signature: &str,
+    first_hash: &str,
+    second_hash: &str,
+) -> SigningResult<()> {
+    if check_signature(algorithm, public_key, signature, first_hash, second_hash)? {
+        Ok(())
+    } else {
+        Err(SigningError::SigningFailed(
+            "Signature does not match".to_string(),
+        ))
+    }
+}
+
+/// Check a signature over `SHA256(first_hash || second_hash)`
+///
+/// Returns `Ok(false)` for a well-formed signature that does not match, and
+/// an error for an unsupported algorithm or a key or signature that cannot
+/// be decoded.
+pub fn check_signature(
+    algorithm: &str,
+    public_key: &str,
+    signature: &str,
+    first_hash: &str,
+    second_hash: &str,
+) -> SigningResult<bool> {
+    let key_bytes = BASE64
+        .decode(public_key)
+        .map_err(|e| SigningError::KeyError(format!("Invalid public key encoding: {}", e)))?;
+    let signature_bytes = BASE64
+        .decode(signature)
+        .map_err(|e| SigningError::SigningFailed(format!("Invalid signature encoding: {}", e)))?;
+    let signed_data = compute_signed_data(first_hash, second_hash);
+
+    let verified = match algorithm {
+        "ecdsa-p256" => {
+            let key = VerifyingKey::from_sec1_bytes(&key_bytes)
+                .map_err(|e| SigningError::KeyError(format!("Invalid public key: {}", e)))?;
+            let signature = Signature::from_der(&signature_bytes)
+                .map_err(|e| SigningError::SigningFailed(format!("Invalid signature: {}", e)))?;
+            key.verify(&signed_data, &signature)
+        }
+        "tpm-ecdsa-p256" => {
+            let key = verifying_key_from_ecc_blob(&key_bytes)?;
+            let signature = Signature::from_slice(&signature_bytes)
+                .map_err(|e| SigningError::SigningFailed(format!("Invalid signature: {}", e)))?;
+            key.verify_prehash(&signed_data, &signature)
+        }
+        other => {
+            return Err(SigningError::SigningFailed(format!(
+                "Unsupported signature algorithm '{}'",
+                other
+            )))
+        }
+    };
+
+    Ok(verified.is_ok())
+}
+
+/// Public key from a Windows `ECCPUBLICBLOB` (header, then X and Y)
+fn verifying_key_from_ecc_blob(blob: &[u8]) -> SigningResult<VerifyingKey> {
+    let point = blob
+        .get(ECC_BLOB_HEADER_LEN..)
+        .ok_or_else(|| SigningError::KeyError("Truncated ECCPUBLICBLOB".to_string()))?;
+    let mut sec1 = Vec::with_capacity(point.len() + 1);
+    sec1.push(0x04);
+    sec1.extend_from_slice(point);
+    VerifyingKey::from_sec1_bytes(&sec1)
+        .map_err(|e| SigningError::KeyError(format!("Invalid public key: {}", e)))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::{SigningBackend, SoftwareBackend};
+
+    #[test]
+    fn test_verify_software_signature() {
+        let backend = SoftwareBackend::new().expect("Failed to create backend");
+        let block = backend
+            .sign_envelope_hashes("sha256:aaa", "sha256:bbb")
+            .expect("Signing failed");
+
+        let verify = |first: &str, second: &str| {
+            verify_signature(
+                &block.algorithm,
+                &block.public_key,
+                &block.signature,
+                first,
+                second,
+            )
+        };
+        assert!(verify("sha256:aaa", "sha256:bbb").is_ok());
+        assert!(verify("sha256:aaa", "sha256:ccc").is_err());
+        assert!(verify_signature("rsa", &block.public_key, &block.signature, "a", "b").is_err());
+    }
+
+    #[test]
+    fn test_check_signature_separates_mismatch_from_malformed() {
+        let backend = SoftwareBackend::new().expect("Failed to create backend");
+        let block = backend
+            .sign_envelope_hashes("sha256:aaa", "sha256:bbb")
+            .expect("Signing failed");
+
+        let check = |signature: &str, second: &str| {
+            check_signature(
+                &block.algorithm,
+                &block.public_key,
+                signature,
+                "sha256:aaa",
+                second,
+            )
+        };
+        assert!(check(&block.signature, "sha256:bbb").unwrap());
+        assert!(!check(&block.signature, "sha256:ccc").unwrap());
+        assert!(check("not base64!", "sha256:bbb").is_err());
+        assert!(check("AAAA", "sha256:bbb").is_err());
+    }
+}
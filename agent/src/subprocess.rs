@@ -0,0 +1,108 @@
+//! Child processes of the agent
+//!
+//! Collectors run commands (`kubectl`, `auditctl`, `nft`, ...) through the
+//! engine's command executor, which does not expose the processes it
+//! spawns. When `--timeout` abandons a scan, the commands still running are
+//! found by walking the process table from the agent's PID and killed, so a
+//! hung command does not outlive the agent.
+//!
+//! Only Linux is supported, through `/proc`; elsewhere nothing is killed.
+
+/// Kill every process descended from the agent with `SIGKILL`
+///
+/// Returns the number of processes signalled.
+#[cfg(target_os = "linux")]
+pub fn kill_descendants() -> usize {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return 0;
+    };
+    let parents: Vec<(u32, u32)> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_str()?.parse().ok()?;
+            let stat = std::fs::read_to_string(entry.path().join("stat")).ok()?;
+            Some((pid, parent_pid(&stat)?))
+        })
+        .collect();
+
+    descendants(std::process::id(), &parents)
+        .into_iter()
+        .filter_map(|pid| libc::pid_t::try_from(pid).ok())
+        // SAFETY: kill takes no pointers; a PID that has exited is an error
+        .filter(|&pid| unsafe { libc::kill(pid, libc::SIGKILL) } == 0)
+        .count()
+}
+
+/// Kill every process descended from the agent (unsupported here)
+#[cfg(not(target_os = "linux"))]
+pub fn kill_descendants() -> usize {
+    0
+}
+
+/// Parent PID from the contents of `/proc/<pid>/stat`
+///
+/// The command name in parentheses may itself contain spaces and
+/// parentheses, so fields are counted from the last `)`.
+#[cfg(target_os = "linux")]
+fn parent_pid(stat: &str) -> Option<u32> {
+    let (_, fields) = stat.rsplit_once(')')?;
+    // State, then parent PID
+    fields.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// PIDs below `root` in a process tree of `(pid, parent)` pairs, parents
+/// before their children
+#[cfg(target_os = "linux")]
+fn descendants(root: u32, parents: &[(u32, u32)]) -> Vec<u32> {
+    let mut found = Vec::new();
+    let mut queue = std::collections::VecDeque::from([root]);
+    while let Some(parent) = queue.pop_front() {
+        for &(pid, _) in parents.iter().filter(|(_, ppid)| *ppid == parent) {
+            if pid != root && !found.contains(&pid) {
+                found.push(pid);
+                queue.push_back(pid);
+            }
+        }
+    }
+    found
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    #[cfg(target_os = "linux")]
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parent_pid() {
+        assert_eq!(
+            parent_pid("4242 (kubectl) S 4100 4242 4100 0 -1 4194560"),
+            Some(4100)
+        );
+        // Command names may contain spaces and parentheses
+        assert_eq!(
+            parent_pid("77 (my (odd) cmd) R 1 77 77 0 -1 4194304"),
+            Some(1)
+        );
+        assert_eq!(parent_pid("garbage"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_descendants() {
+        // 10 -> 11 -> 13, 10 -> 12; 20 is unrelated
+        let parents = [(11, 10), (12, 10), (13, 11), (20, 1), (10, 1)];
+        assert_eq!(descendants(10, &parents), vec![11, 12, 13]);
+        assert!(descendants(20, &parents).is_empty());
+    }
+}
@@ -0,0 +1,371 @@
+//! Audit Rule Collector
+//!
+//! Collects the effective Linux audit (auditd) rules.
+//! - Primary source: `auditctl -l` (loaded kernel rules)
+//! - Fallback: concatenated `/etc/audit/rules.d/*.rules` (persistent rules)
+//!
+//! Rules are normalized so that flag ordering does not affect matching.
+//! auditctl calls that time out can be retried through the `retry` or
+//! `retries` behaviors; see [`RetryPolicy`].
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{
+    CollectedData, CollectionError, CtnContract, CtnDataCollector, SystemCommandExecutor,
+};
+use execution_engine::types::common::ResolvedValue;
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+use std::path::Path;
+
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::audit_rule::{
+    find_auditctl, parse_audit_enabled, parse_audit_rules, read_rules_dir, rule_key,
+    rule_matches_pattern, AUDIT_RULES_DIR,
+};
+use crate::commands::command_output::{run_command_with_retry, CommandError};
+use crate::commands::elevation::Elevation;
+use crate::commands::retry::RetryPolicy;
+
+/// Where the collected rules were read from
+enum RuleSource {
+    Auditctl(String),
+    /// Persistent rules, with the auditctl error that forced the fallback
+    RulesDir(String, CommandError),
+}
+
+/// auditctl arguments that list the loaded rules
+const AUDITCTL_LIST_ARGS: [&str; 1] = ["-l"];
+
+/// Plan for listing loaded rules via auditctl
+fn auditctl_plan() -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::Command,
+        "List loaded audit rules via auditctl",
+        "audit_rules",
+    )
+    .with_command(format!(
+        "{} {}",
+        find_auditctl(),
+        AUDITCTL_LIST_ARGS.join(" ")
+    ))
+    .with_input("source", "auditctl")
+}
+
+/// Plan for reading persistent rules when auditctl is unavailable
+fn rules_dir_plan() -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::FileRead,
+        "Read persistent audit rules (auditctl unavailable)",
+        AUDIT_RULES_DIR,
+    )
+    .with_input("source", "rules.d")
+}
+
+/// Add the object selectors to a plan
+fn with_selectors(
+    mut plan: CollectionPlan,
+    rule_pattern: Option<&str>,
+    key: Option<&str>,
+) -> CollectionPlan {
+    if let Some(pattern) = rule_pattern {
+        plan = plan.with_input("rule_pattern", pattern);
+    }
+    if let Some(k) = key {
+        plan = plan.with_input("key", k);
+    }
+    plan
+}
+
+/// Collector for audit rule information
+#[derive(Clone)]
+pub struct AuditRuleCollector {
+    id: String,
+    executor: SystemCommandExecutor,
+    retry: RetryPolicy,
+    elevation: Option<Elevation>,
+}
+
+impl AuditRuleCollector {
+    /// Create new collector with the given executor
+    pub fn new(id: impl Into<String>, executor: SystemCommandExecutor) -> Self {
+        Self {
+            id: id.into(),
+            executor,
+            retry: RetryPolicy::none(),
+            elevation: None,
+        }
+    }
+
+    /// Retry policy for objects without retry behaviors
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Run commands through `elevation`'s wrapper (None runs them directly)
+    pub fn with_elevation(mut self, elevation: Option<Elevation>) -> Self {
+        self.elevation = elevation;
+        self
+    }
+
+    /// The plan as run, wrapper included
+    fn elevated(&self, plan: CollectionPlan) -> CollectionPlan {
+        match &self.elevation {
+            Some(elevation) => elevation.record(plan),
+            None => plan,
+        }
+    }
+
+    /// Retry policy for an object, from its behaviors or the collector default
+    fn retry_policy(
+        &self,
+        hints: &BehaviorHints,
+        object_id: &str,
+    ) -> Result<RetryPolicy, CollectionError> {
+        RetryPolicy::from_hints(hints, self.retry).map_err(|reason| {
+            CollectionError::InvalidObjectConfiguration {
+                object_id: object_id.to_string(),
+                reason,
+            }
+        })
+    }
+
+    /// Extract optional string field from object
+    fn extract_string_field(
+        &self,
+        object: &ExecutableObject,
+        field_name: &str,
+    ) -> Result<Option<String>, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == field_name {
+                    match value {
+                        ResolvedValue::String(s) => return Ok(Some(s.clone())),
+                        _ => {
+                            return Err(CollectionError::InvalidObjectConfiguration {
+                                object_id: object.identifier.clone(),
+                                reason: format!("Field '{}' must be a string", field_name),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Run auditctl with the given arguments, retrying transient failures
+    ///
+    /// Returns stdout on success and the number of retries made.
+    fn run_auditctl(
+        &self,
+        args: &[&str],
+        retry: &RetryPolicy,
+    ) -> (Result<String, CommandError>, u32) {
+        run_command_with_retry(
+            &self.executor,
+            self.elevation.as_ref(),
+            find_auditctl(),
+            args,
+            None,
+            retry,
+        )
+    }
+
+    /// Load rules from auditctl, falling back to the rules.d directory
+    ///
+    /// Also returns the number of auditctl retries made.
+    fn load_rules(
+        &self,
+        object_id: &str,
+        retry: &RetryPolicy,
+    ) -> Result<(RuleSource, u32), CollectionError> {
+        let (result, retries) = self.run_auditctl(&AUDITCTL_LIST_ARGS, retry);
+        let auditctl_error = match result {
+            Ok(stdout) => return Ok((RuleSource::Auditctl(stdout), retries)),
+            Err(e) => e,
+        };
+
+        read_rules_dir(Path::new(AUDIT_RULES_DIR))
+            .map(|contents| {
+                (
+                    RuleSource::RulesDir(contents, auditctl_error.clone()),
+                    retries,
+                )
+            })
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::PermissionDenied {
+                    CollectionError::AccessDenied {
+                        object_id: object_id.to_string(),
+                        reason: format!(
+                            "{}; cannot read {}: {}",
+                            auditctl_error, AUDIT_RULES_DIR, e
+                        ),
+                    }
+                } else {
+                    CollectionError::CollectionFailed {
+                        object_id: object_id.to_string(),
+                        reason: format!(
+                            "{}; cannot read {}: {}",
+                            auditctl_error, AUDIT_RULES_DIR, e
+                        ),
+                    }
+                }
+            })
+    }
+}
+
+impl CtnDataCollector for AuditRuleCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        // Validate contract compatibility
+        self.validate_ctn_compatibility(contract)?;
+
+        // Extract object fields (both optional)
+        let rule_pattern = self.extract_string_field(object, "rule_pattern")?;
+        let key = self.extract_string_field(object, "key")?;
+
+        let retry = self.retry_policy(hints, &object.identifier)?;
+        let (source, list_retries) = self.load_rules(&object.identifier, &retry)?;
+
+        // Enabled state is only available from the running audit system
+        let (status, status_retries) = self.run_auditctl(&["-s"], &retry);
+        let enabled = status
+            .map(|stdout| parse_audit_enabled(&stdout))
+            .unwrap_or(false);
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "audit_rule".to_string(),
+            self.id.clone(),
+        );
+
+        // Set collection method for traceability
+        let (raw_rules, plan) = match source {
+            RuleSource::Auditctl(stdout) => (stdout, self.elevated(auditctl_plan())),
+            RuleSource::RulesDir(contents, CommandError::Exit(failure)) => {
+                (contents, failure.record(rules_dir_plan()))
+            }
+            RuleSource::RulesDir(contents, CommandError::Execute { .. }) => {
+                (contents, rules_dir_plan())
+            }
+        };
+        let plan = retry.record(plan, list_retries + status_retries);
+        data.set_method(
+            with_selectors(plan, rule_pattern.as_deref(), key.as_deref()).into_method(),
+        );
+
+        let rules = parse_audit_rules(&raw_rules);
+
+        // A rule is present when it satisfies every selector given on the object
+        let present = rules.iter().any(|rule| {
+            let pattern_ok = rule_pattern
+                .as_deref()
+                .map(|pattern| rule_matches_pattern(rule, pattern))
+                .unwrap_or(true);
+            let key_ok = key
+                .as_deref()
+                .map(|k| rule_key(rule).as_deref() == Some(k))
+                .unwrap_or(true);
+            pattern_ok && key_ok
+        });
+
+        data.add_field("present".to_string(), ResolvedValue::Boolean(present));
+        data.add_field("enabled".to_string(), ResolvedValue::Boolean(enabled));
+        data.add_field(
+            "rule_count".to_string(),
+            ResolvedValue::Integer(rules.len() as i64),
+        );
+        data.add_field(
+            "rules".to_string(),
+            ResolvedValue::Collection(rules.into_iter().map(ResolvedValue::String).collect()),
+        );
+
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["audit_rule".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != "audit_rule" {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!(
+                    "Incompatible CTN type: expected 'audit_rule', got '{}'",
+                    contract.ctn_type
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
+
+impl DescribeCollection for AuditRuleCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+
+        let rule_pattern = self.extract_string_field(object, "rule_pattern")?;
+        let key = self.extract_string_field(object, "key")?;
+        let retry = self.retry_policy(hints, &object.identifier)?;
+
+        // auditctl is tried first; rules.d is read only if it fails
+        let plan = self
+            .elevated(auditctl_plan())
+            .with_input("fallback", AUDIT_RULES_DIR);
+        let plan = retry.describe(plan);
+        Ok(with_selectors(
+            plan,
+            rule_pattern.as_deref(),
+            key.as_deref(),
+        ))
+    }
+
+    /// `auditctl` talks to the kernel audit subsystem one caller at a time
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::serial()
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auditctl_plan_matches_invocation() {
+        let plan = with_selectors(auditctl_plan(), Some("-w /etc/passwd"), Some("identity"));
+
+        // The planned command is exactly what load_rules executes
+        let expected = format!("{} {}", find_auditctl(), AUDITCTL_LIST_ARGS.join(" "));
+        assert_eq!(plan.command.as_deref(), Some(expected.as_str()));
+        assert_eq!(plan.kind, CollectionKind::Command);
+        assert_eq!(plan.inputs["rule_pattern"], "-w /etc/passwd");
+        assert_eq!(plan.inputs["key"], "identity");
+    }
+
+    #[test]
+    fn test_rules_dir_plan_reads_rules_dir() {
+        let plan = rules_dir_plan();
+        assert_eq!(plan.kind, CollectionKind::FileRead);
+        assert_eq!(plan.target, AUDIT_RULES_DIR);
+        assert!(plan.command.is_none());
+    }
+}
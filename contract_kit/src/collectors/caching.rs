@@ -0,0 +1,150 @@
+//! Collection Caching
+//!
+//! `CachingCollector` wraps a collector and memoizes successful
+//! collections, so objects that describe the same target (two objects
+//! reading `/etc/ssh/sshd_config` with the same behaviors) are collected
+//! once. Entries are keyed by CTN type, the object's fields and the
+//! behavior hints; the object identifier is not part of the key.
+//!
+//! Only successes are cached. A `CollectionError`, or an error record
+//! returned as data by `ErrorReportingCollector`, is collected again on the
+//! next request.
+//!
+//! The cache is only correct within a single scan run: the host may change
+//! between runs, so create a new wrapper (or call `clear`) per run.
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use super::error_reporting::CollectionErrorRecord;
+
+/// What a collection depends on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    ctn_type: String,
+    /// Object fields as `(name, value)`, sorted
+    fields: Vec<(String, String)>,
+    /// Other object elements, sorted
+    elements: Vec<String>,
+    hints: String,
+}
+
+impl CacheKey {
+    fn new(object: &ExecutableObject, contract: &CtnContract, hints: &BehaviorHints) -> Self {
+        let mut fields = Vec::new();
+        let mut elements = Vec::new();
+        for element in &object.elements {
+            match element {
+                ExecutableObjectElement::Field { name, value, .. } => {
+                    fields.push((name.clone(), format!("{:?}", value)));
+                }
+                other => elements.push(format!("{:?}", other)),
+            }
+        }
+        fields.sort();
+        elements.sort();
+
+        Self {
+            ctn_type: contract.ctn_type.clone(),
+            fields,
+            elements,
+            hints: format!("{:?}", hints),
+        }
+    }
+}
+
+/// Collector wrapper that memoizes successful collections
+///
+/// Safe to share across threads: lookups take a read lock, and the lock is
+/// not held while the inner collector runs, so two threads missing on the
+/// same key may both collect it.
+pub struct CachingCollector<C> {
+    inner: C,
+    cache: RwLock<HashMap<CacheKey, CollectedData>>,
+}
+
+impl<C: CtnDataCollector> CachingCollector<C> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Drop every cached collection
+    pub fn clear(&self) {
+        if let Ok(mut cache) = self.cache.write() {
+            cache.clear();
+        }
+    }
+
+    /// Number of cached collections
+    pub fn len(&self) -> usize {
+        self.cache.read().map(|cache| cache.len()).unwrap_or(0)
+    }
+
+    /// Whether nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<C: CtnDataCollector> CtnDataCollector for CachingCollector<C> {
+    /// Collected data served from the cache keeps the identifier of the
+    /// object that was collected first; executors key results by the
+    /// object being evaluated.
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        let key = CacheKey::new(object, contract, hints);
+        if let Some(data) = self
+            .cache
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&key).cloned())
+        {
+            return Ok(data);
+        }
+
+        let data = self
+            .inner
+            .collect_for_ctn_with_hints(object, contract, hints)?;
+        if CollectionErrorRecord::from_collected_data(&data).is_none() {
+            if let Ok(mut cache) = self.cache.write() {
+                cache.insert(key, data.clone());
+            }
+        }
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        self.inner.supported_ctn_types()
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        self.inner.validate_ctn_compatibility(contract)
+    }
+
+    fn collector_id(&self) -> &str {
+        self.inner.collector_id()
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        self.inner.supports_batch_collection()
+    }
+
+    /// Batches bypass the cache
+    fn collect_batch(
+        &self,
+        objects: Vec<&ExecutableObject>,
+        contract: &CtnContract,
+    ) -> Result<HashMap<String, CollectedData>, CollectionError> {
+        self.inner.collect_batch(objects, contract)
+    }
+}
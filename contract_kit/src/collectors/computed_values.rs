@@ -14,11 +14,52 @@
 //! use common::results::CollectionMethod;
 //! let method = CollectionMethod::computed().with_description("Computed value - no actual system collection performed");
 //! ```
+//!
+//! # Reported Values
+//!
+//! Object fields other than `type` and `description` are computed values the
+//! policy wants on record, e.g. `max_age VAR max_age` after a RUN operation.
+//! The engine resolves them before collection; they are recorded as
+//! collected data, so they appear in the evidence of full results and
+//! assessor packages (never in attestations, which carry no evidence).
 
-use common::results::CollectionMethod;
 use execution_engine::execution::BehaviorHints;
 use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
-use execution_engine::types::execution_context::ExecutableObject;
+use execution_engine::types::common::ResolvedValue;
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+
+/// Object fields that describe the check rather than carry a computed value
+const INFORMATIONAL_FIELDS: &[&str] = &["type", "description"];
+
+/// Computed values an object reports: its resolved fields other than `type`
+/// and `description`
+pub(crate) fn computed_fields(
+    object: &ExecutableObject,
+) -> impl Iterator<Item = (&str, &ResolvedValue)> {
+    object.elements.iter().filter_map(|element| match element {
+        ExecutableObjectElement::Field { name, value, .. }
+            if !INFORMATIONAL_FIELDS.contains(&name.as_str()) =>
+        {
+            Some((name.as_str(), value))
+        }
+        _ => None,
+    })
+}
+
+/// The object's `description` field, if it is a string
+pub(crate) fn object_description(object: &ExecutableObject) -> Option<&str> {
+    object.elements.iter().find_map(|element| match element {
+        ExecutableObjectElement::Field {
+            name,
+            value: ResolvedValue::String(description),
+            ..
+        } if name == "description" => Some(description.as_str()),
+        _ => None,
+    })
+}
 
 pub struct ComputedValuesCollector {
     id: String,
@@ -54,11 +95,13 @@ impl CtnDataCollector for ComputedValuesCollector {
         );
 
         // Set collection method for traceability - marks this as computed/derived
-        let method = CollectionMethod::computed()
-            .with_description("Computed value - no actual system collection performed");
-        data.set_method(method);
+        data.set_method(computed_plan().into_method());
 
-        // No fields to add - validation happens against variables, not collected data
+        // Computed values the object reports; validation still happens
+        // against variables, not collected data
+        for (name, value) in computed_fields(object) {
+            data.add_field(name.to_string(), value.clone());
+        }
         Ok(data)
     }
 
@@ -86,3 +129,28 @@ impl CtnDataCollector for ComputedValuesCollector {
         false // No actual collection to batch
     }
 }
+
+impl DescribeCollection for ComputedValuesCollector {
+    fn describe(
+        &self,
+        _object: &ExecutableObject,
+        _contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        Ok(computed_plan())
+    }
+
+    /// No system access
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::high()
+    }
+}
+
+/// Plan for computed values (no system access)
+fn computed_plan() -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::Computed,
+        "Computed value - no actual system collection performed",
+        "resolved_variables",
+    )
+}
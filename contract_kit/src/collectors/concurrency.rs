@@ -0,0 +1,421 @@
+//! Collector Concurrency
+//!
+//! Collectors differ in how much parallel work they tolerate. Stat calls
+//! against the local filesystem scale well, while every kubectl call hits
+//! the API server and every `auditctl` or `iptables` call contends for a
+//! kernel or xtables lock. Each collector advertises a `ConcurrencyHint`
+//! through `DescribeCollection::concurrency`.
+//!
+//! `CollectorScheduler` honors the hints:
+//!
+//! - Jobs passed to `run` are grouped by collector ID. Each group runs on at
+//!   most `max_concurrent` worker threads, and groups run side by side, so a
+//!   slow kubectl group never holds up filesystem checks.
+//! - Collectors wrapped with `bound` share one limit per collector ID, even
+//!   when several strategies register their own instance of the same
+//!   collector. A collection beyond the limit waits for a slot.
+//!
+//! A collector that is not parallel-safe always runs one collection at a
+//! time. Collectors that do not override `concurrency` are treated as not
+//! parallel-safe.
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use execution_engine::types::execution_context::ExecutableObject;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+
+use super::planning::{CollectionPlan, DescribeCollection};
+
+/// Suggested concurrency for collectors with no shared bottleneck
+pub const HIGH_CONCURRENCY: usize = 16;
+
+/// Suggested concurrency for collectors backed by a rate-limited service
+pub const LOW_CONCURRENCY: usize = 2;
+
+/// How many collections a collector can usefully run at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcurrencyHint {
+    /// Whether collections may overlap at all
+    pub parallel_safe: bool,
+    /// Most collections to run at once (1 when not parallel-safe)
+    pub max_concurrent: usize,
+}
+
+impl Default for ConcurrencyHint {
+    fn default() -> Self {
+        Self::serial()
+    }
+}
+
+impl ConcurrencyHint {
+    /// One collection at a time
+    pub fn serial() -> Self {
+        Self {
+            parallel_safe: false,
+            max_concurrent: 1,
+        }
+    }
+
+    /// Up to `max_concurrent` collections at once (at least 1)
+    pub fn limited(max_concurrent: usize) -> Self {
+        Self {
+            parallel_safe: true,
+            max_concurrent: max_concurrent.max(1),
+        }
+    }
+
+    /// Suggested hint for local, lock-free collection
+    pub fn high() -> Self {
+        Self::limited(HIGH_CONCURRENCY)
+    }
+
+    /// Suggested hint for collection against a remote API
+    pub fn low() -> Self {
+        Self::limited(LOW_CONCURRENCY)
+    }
+
+    /// Number of collections allowed at once
+    pub fn limit(&self) -> usize {
+        if self.parallel_safe {
+            self.max_concurrent.max(1)
+        } else {
+            1
+        }
+    }
+}
+
+/// Counting semaphore bounding one collector's simultaneous collections
+#[derive(Debug)]
+struct ConcurrencyLimit {
+    limit: usize,
+    running: Mutex<usize>,
+    freed: Condvar,
+}
+
+impl ConcurrencyLimit {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit: limit.max(1),
+            running: Mutex::new(0),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// Wait for a free slot and hold it until the permit is dropped
+    fn acquire(&self) -> Permit<'_> {
+        let mut running = self.running.lock().unwrap_or_else(PoisonError::into_inner);
+        while *running >= self.limit {
+            running = self
+                .freed
+                .wait(running)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        *running += 1;
+        Permit { limit: self }
+    }
+}
+
+/// A held slot of a `ConcurrencyLimit`
+struct Permit<'a> {
+    limit: &'a ConcurrencyLimit,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let mut running = self
+            .limit
+            .running
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        *running = running.saturating_sub(1);
+        self.limit.freed.notify_one();
+    }
+}
+
+/// A unit of work attributed to a collector
+pub struct ScheduledJob<'a, T> {
+    pub collector_id: String,
+    pub run: Box<dyn FnOnce() -> T + Send + 'a>,
+}
+
+impl<'a, T> ScheduledJob<'a, T> {
+    pub fn new(collector_id: impl Into<String>, run: impl FnOnce() -> T + Send + 'a) -> Self {
+        Self {
+            collector_id: collector_id.into(),
+            run: Box::new(run),
+        }
+    }
+}
+
+/// Concurrency hints of registered collectors, and the limits enforcing them
+#[derive(Debug, Default)]
+pub struct CollectorScheduler {
+    hints: BTreeMap<String, ConcurrencyHint>,
+    limits: HashMap<String, Arc<ConcurrencyLimit>>,
+}
+
+impl CollectorScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the hint of a collector
+    ///
+    /// If the collector ID is already registered, the stricter hint is kept.
+    pub fn register(&mut self, collector_id: &str, hint: ConcurrencyHint) {
+        if let Some(existing) = self.hints.get(collector_id) {
+            if existing.limit() <= hint.limit() {
+                return;
+            }
+        }
+        self.hints.insert(collector_id.to_string(), hint);
+        self.limits.insert(
+            collector_id.to_string(),
+            Arc::new(ConcurrencyLimit::new(hint.limit())),
+        );
+    }
+
+    /// Hint of a collector (serial if it was never registered)
+    pub fn hint(&self, collector_id: &str) -> ConcurrencyHint {
+        self.hints.get(collector_id).copied().unwrap_or_default()
+    }
+
+    /// Hints of all registered collectors, by collector ID
+    pub fn hints(&self) -> &BTreeMap<String, ConcurrencyHint> {
+        &self.hints
+    }
+
+    /// Register `collector` and wrap it so its collections respect its limit
+    ///
+    /// All wrappers for the same collector ID share one limit. Register every
+    /// instance before scanning: a later registration with a stricter hint
+    /// replaces the limit for wrappers created afterwards only.
+    pub fn bound<C: DescribeCollection>(&mut self, collector: C) -> BoundedCollector<C> {
+        let collector_id = collector.collector_id().to_string();
+        self.register(&collector_id, collector.concurrency());
+        let limit = self
+            .limits
+            .get(&collector_id)
+            .cloned()
+            .unwrap_or_else(|| Arc::new(ConcurrencyLimit::new(1)));
+        BoundedCollector {
+            inner: collector,
+            limit,
+        }
+    }
+
+    /// Run `jobs` in parallel, bounding each collector's group by its hint
+    ///
+    /// Results are returned in the order of `jobs`.
+    pub fn run<T: Send>(&self, jobs: Vec<ScheduledJob<'_, T>>) -> Vec<T> {
+        let total = jobs.len();
+        let mut groups: BTreeMap<String, VecDeque<(usize, ScheduledJob<'_, T>)>> = BTreeMap::new();
+        for (index, job) in jobs.into_iter().enumerate() {
+            groups
+                .entry(job.collector_id.clone())
+                .or_default()
+                .push_back((index, job));
+        }
+
+        let results: Mutex<Vec<Option<T>>> = Mutex::new((0..total).map(|_| None).collect());
+
+        std::thread::scope(|scope| {
+            for (collector_id, queue) in groups {
+                let workers = self.hint(&collector_id).limit().min(queue.len());
+                let queue = Arc::new(Mutex::new(queue));
+                for _ in 0..workers {
+                    let queue = Arc::clone(&queue);
+                    let results = &results;
+                    scope.spawn(move || loop {
+                        let next = queue
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .pop_front();
+                        let Some((index, job)) = next else {
+                            break;
+                        };
+                        let value = (job.run)();
+                        let mut results = results.lock().unwrap_or_else(PoisonError::into_inner);
+                        if let Some(slot) = results.get_mut(index) {
+                            *slot = Some(value);
+                        }
+                    });
+                }
+            }
+        });
+
+        results
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+/// Collector wrapper that waits for a slot of its collector's limit
+pub struct BoundedCollector<C> {
+    inner: C,
+    limit: Arc<ConcurrencyLimit>,
+}
+
+impl<C: DescribeCollection> CtnDataCollector for BoundedCollector<C> {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        let _permit = self.limit.acquire();
+        self.inner
+            .collect_for_ctn_with_hints(object, contract, hints)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        self.inner.supported_ctn_types()
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        self.inner.validate_ctn_compatibility(contract)
+    }
+
+    fn collector_id(&self) -> &str {
+        self.inner.collector_id()
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        self.inner.supports_batch_collection()
+    }
+
+    /// A batch takes one slot
+    fn collect_batch(
+        &self,
+        objects: Vec<&ExecutableObject>,
+        contract: &CtnContract,
+    ) -> Result<HashMap<String, CollectedData>, CollectionError> {
+        let _permit = self.limit.acquire();
+        self.inner.collect_batch(objects, contract)
+    }
+}
+
+impl<C: DescribeCollection> DescribeCollection for BoundedCollector<C> {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.inner.describe(object, contract, hints)
+    }
+
+    fn concurrency(&self) -> ConcurrencyHint {
+        self.inner.concurrency()
+    }
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Tracks how many jobs of one collector run at once
+    #[derive(Default)]
+    struct Gauge {
+        running: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl Gauge {
+        fn work(&self) {
+            let now = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(5));
+            self.running.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_low_concurrency_group_never_exceeds_limit() {
+        let mut scheduler = CollectorScheduler::new();
+        scheduler.register("k8s_resource_collector", ConcurrencyHint::low());
+        scheduler.register("filesystem_collector", ConcurrencyHint::high());
+
+        let kubectl = Gauge::default();
+        let files = Gauge::default();
+        let mut jobs = Vec::new();
+        for i in 0..40 {
+            let (kubectl, files) = (&kubectl, &files);
+            if i % 2 == 0 {
+                jobs.push(ScheduledJob::new("k8s_resource_collector", move || {
+                    kubectl.work();
+                    i
+                }));
+            } else {
+                jobs.push(ScheduledJob::new("filesystem_collector", move || {
+                    files.work();
+                    i
+                }));
+            }
+        }
+
+        let results = scheduler.run(jobs);
+
+        assert_eq!(results, (0..40).collect::<Vec<_>>());
+        let kubectl_peak = kubectl.peak.load(Ordering::SeqCst);
+        assert!((1..=LOW_CONCURRENCY).contains(&kubectl_peak));
+        assert!(files.peak.load(Ordering::SeqCst) <= HIGH_CONCURRENCY);
+    }
+
+    #[test]
+    fn test_unregistered_and_serial_collectors_run_one_at_a_time() {
+        let mut scheduler = CollectorScheduler::new();
+        scheduler.register("audit_rule_collector", ConcurrencyHint::serial());
+
+        let audit = Gauge::default();
+        let custom = Gauge::default();
+        let mut jobs = Vec::new();
+        for _ in 0..6 {
+            jobs.push(ScheduledJob::new("audit_rule_collector", || audit.work()));
+            jobs.push(ScheduledJob::new("custom_collector", || custom.work()));
+        }
+        scheduler.run(jobs);
+
+        assert_eq!(audit.peak.load(Ordering::SeqCst), 1);
+        assert_eq!(custom.peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_shared_limit_bounds_concurrent_acquires() {
+        let limit = ConcurrencyLimit::new(LOW_CONCURRENCY);
+        let gauge = Gauge::default();
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    let _permit = limit.acquire();
+                    gauge.work();
+                });
+            }
+        });
+
+        assert!(gauge.peak.load(Ordering::SeqCst) <= LOW_CONCURRENCY);
+        assert_eq!(*limit.running.lock().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_register_keeps_stricter_hint() {
+        let mut scheduler = CollectorScheduler::new();
+        scheduler.register("filesystem_collector", ConcurrencyHint::high());
+        scheduler.register("filesystem_collector", ConcurrencyHint::limited(4));
+        scheduler.register("filesystem_collector", ConcurrencyHint::high());
+
+        assert_eq!(scheduler.hint("filesystem_collector").limit(), 4);
+        assert_eq!(scheduler.hint("unknown").limit(), 1);
+        assert_eq!(ConcurrencyHint::limited(0).limit(), 1);
+    }
+}
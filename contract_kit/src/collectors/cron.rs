@@ -0,0 +1,217 @@
+//! Cron Job Collector
+//!
+//! Lists scheduled jobs for the `cron_job` CTN type.
+//! - Linux: Parses `/etc/crontab`, `/etc/cron.d/*` and per-user crontabs
+//! - Windows: Enumerates Task Scheduler exec actions via COM
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use execution_engine::types::common::ResolvedValue;
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::cron::{find_cron_jobs, CronError, CronFilter};
+
+/// Collector for scheduled jobs
+pub struct CronCollector {
+    id: String,
+}
+
+impl CronCollector {
+    pub fn new() -> Self {
+        Self {
+            id: "cron_collector".to_string(),
+        }
+    }
+
+    /// Extract an optional string field from object
+    fn extract_string(
+        &self,
+        object: &ExecutableObject,
+        field: &str,
+    ) -> Result<Option<String>, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == field {
+                    return match value {
+                        ResolvedValue::String(s) if !s.is_empty() => Ok(Some(s.clone())),
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!(
+                                "Field '{}' must be a non-empty string, got {:?}",
+                                field, value
+                            ),
+                        }),
+                    };
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Extract the job filter from object
+    fn extract_filter(&self, object: &ExecutableObject) -> Result<CronFilter, CollectionError> {
+        let filter = CronFilter {
+            user: self.extract_string(object, "user")?,
+            command_contains: self.extract_string(object, "command_contains")?,
+        };
+
+        if filter.user.is_none() && filter.command_contains.is_none() {
+            return Err(CollectionError::InvalidObjectConfiguration {
+                object_id: object.identifier.clone(),
+                reason: "Object needs 'user' or 'command_contains'".to_string(),
+            });
+        }
+        Ok(filter)
+    }
+}
+
+impl Default for CronCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Plan for listing the jobs matching one filter
+///
+/// The Task Scheduler has no collection kind of its own; its tasks are
+/// registered in the `TaskCache` registry tree, so it is a registry query.
+fn cron_plan(filter: &CronFilter) -> CollectionPlan {
+    let (kind, description, target) = if cfg!(windows) {
+        (
+            CollectionKind::RegistryQuery,
+            "Enumerate scheduled tasks via the Task Scheduler COM API",
+            "task_scheduler",
+        )
+    } else {
+        (
+            CollectionKind::FileRead,
+            "Parse system and per-user crontabs",
+            "/etc/crontab",
+        )
+    };
+
+    let mut plan = CollectionPlan::new(kind, description, target);
+    if let Some(user) = &filter.user {
+        plan = plan.with_input("user", user.as_str());
+    }
+    if let Some(needle) = &filter.command_contains {
+        plan = plan.with_input("command_contains", needle.as_str());
+    }
+    plan
+}
+
+impl DescribeCollection for CronCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let filter = self.extract_filter(object)?;
+        Ok(cron_plan(&filter))
+    }
+
+    /// Each object reads every crontab or walks every task folder
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::low()
+    }
+}
+
+impl CtnDataCollector for CronCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let filter = self.extract_filter(object)?;
+
+        // No matching job is data (exists = false), not an error
+        let jobs = find_cron_jobs(&filter).map_err(|e| match e {
+            CronError::AccessDenied(_) => CollectionError::AccessDenied {
+                object_id: object.identifier.clone(),
+                reason: e.to_string(),
+            },
+            CronError::ReadFailed(..) => CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: e.to_string(),
+            },
+        })?;
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "cron_job".to_string(),
+            self.id.clone(),
+        );
+        data.set_method(cron_plan(&filter).into_method());
+
+        data.add_field(
+            "exists".to_string(),
+            ResolvedValue::Boolean(!jobs.is_empty()),
+        );
+        data.add_field(
+            "count".to_string(),
+            ResolvedValue::Integer(jobs.len() as i64),
+        );
+        if let Some(job) = jobs.into_iter().next() {
+            data.add_field("schedule".to_string(), ResolvedValue::String(job.schedule));
+        }
+
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["cron_job".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != "cron_job" {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!(
+                    "Incompatible CTN type: expected 'cron_job', got '{}'",
+                    contract.ctn_type
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_ctn_types() {
+        let collector = CronCollector::new();
+        assert_eq!(collector.collector_id(), "cron_collector");
+        assert_eq!(collector.supported_ctn_types(), vec!["cron_job"]);
+    }
+
+    #[test]
+    fn test_cron_plan() {
+        let filter = CronFilter {
+            user: None,
+            command_contains: Some("aide".to_string()),
+        };
+        let plan = cron_plan(&filter);
+        assert_eq!(
+            plan.inputs.get("command_contains").map(String::as_str),
+            Some("aide")
+        );
+        assert!(!plan.inputs.contains_key("user"));
+        assert!(plan.command.is_none());
+    }
+}
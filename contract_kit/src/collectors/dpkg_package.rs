@@ -0,0 +1,300 @@
+//! Debian Package Collector
+//!
+//! Collects whether a Debian package is installed, and its version, for
+//! the `dpkg_package` CTN type.
+//! - One object: `dpkg-query -W -f=<format> <name>`
+//! - A batch: one `dpkg-query -W -f=<format>` listing every package, looked
+//!   up for each object
+//!
+//! A package dpkg does not know, or one that was removed (even if its
+//! configuration files remain), is data (`installed` false, `version`
+//! empty), not an error. See [`crate::commands::dpkg`] for which statuses
+//! count as installed.
+//!
+//! A host without dpkg-query (not a Debian system) cannot have Debian
+//! packages, so each object is marked not applicable.
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{
+    CollectedData, CollectionError, CtnContract, CtnDataCollector, SystemCommandExecutor,
+};
+use execution_engine::types::common::ResolvedValue;
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+use std::collections::HashMap;
+
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::command_output::{run_command, CommandError, CommandFailure};
+use crate::commands::dpkg::{
+    dpkg_query_args, dpkg_query_installed, find_dpkg_query, find_installed, is_valid_package_name,
+    parse_dpkg_query, PackageStatus,
+};
+use crate::executors::applicability::not_applicable_data;
+
+/// Exit code of `dpkg-query -W` when no package matches
+const NO_PACKAGES_FOUND: i32 = 1;
+
+/// Why a package object does not apply to a host without dpkg
+const NO_DPKG: &str = "dpkg is not installed";
+
+/// Packages listed by one dpkg-query run
+type Listing = (Vec<PackageStatus>, Option<CommandFailure>);
+
+/// Plan for querying one package, or with None every package
+fn dpkg_package_plan(name: &str, listed: Option<&str>) -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::Command,
+        "Query the dpkg database for the package status and version",
+        "dpkg_status",
+    )
+    .with_command(format!(
+        "{} {}",
+        find_dpkg_query(),
+        dpkg_query_args(listed).join(" ")
+    ))
+    .with_input("name", name)
+}
+
+/// Collector for Debian package status
+#[derive(Clone)]
+pub struct DpkgPackageCollector {
+    id: String,
+    executor: SystemCommandExecutor,
+}
+
+impl DpkgPackageCollector {
+    /// Create new collector with the given executor
+    pub fn new(id: impl Into<String>, executor: SystemCommandExecutor) -> Self {
+        Self {
+            id: id.into(),
+            executor,
+        }
+    }
+
+    /// Extract the package name from object
+    fn extract_name(&self, object: &ExecutableObject) -> Result<String, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == "name" {
+                    return match value {
+                        ResolvedValue::String(s) if is_valid_package_name(s.trim()) => {
+                            Ok(s.trim().to_string())
+                        }
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!(
+                                "Field 'name' must be a package name (lowercase letters, \
+                                 digits, '+', '-' and '.', optionally ':arch'), got {:?}",
+                                value
+                            ),
+                        }),
+                    };
+                }
+            }
+        }
+
+        Err(CollectionError::InvalidObjectConfiguration {
+            object_id: object.identifier.clone(),
+            reason: "Missing required field 'name'".to_string(),
+        })
+    }
+
+    /// Run dpkg-query for `package`, or every package with None
+    ///
+    /// A package missing from the database lists nothing, with the failure
+    /// returned for the collection method. None when dpkg is not installed.
+    fn query(
+        &self,
+        object_id: &str,
+        package: Option<&str>,
+    ) -> Result<Option<Listing>, CollectionError> {
+        let args = dpkg_query_args(package);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        match run_command(&self.executor, None, find_dpkg_query(), &args, None) {
+            Ok(stdout) => Ok(Some((parse_dpkg_query(&stdout), None))),
+            Err(CommandError::Exit(failure))
+                if package.is_some() && failure.exit_code == NO_PACKAGES_FOUND =>
+            {
+                Ok(Some((Vec::new(), Some(failure))))
+            }
+            Err(_) if !dpkg_query_installed() => Ok(None),
+            Err(e) => Err(CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    /// Collected data for `name`, looked up in `packages`
+    fn build_data(
+        &self,
+        object: &ExecutableObject,
+        plan: CollectionPlan,
+        packages: &[PackageStatus],
+        name: &str,
+    ) -> CollectedData {
+        let installed = find_installed(packages, name);
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "dpkg_package".to_string(),
+            self.id.clone(),
+        );
+        data.set_method(plan.into_method());
+
+        data.add_field(
+            "installed".to_string(),
+            ResolvedValue::Boolean(installed.is_some()),
+        );
+        data.add_field(
+            "version".to_string(),
+            ResolvedValue::String(
+                installed
+                    .map(|package| package.version.clone())
+                    .unwrap_or_default(),
+            ),
+        );
+
+        data
+    }
+}
+
+impl DescribeCollection for DpkgPackageCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let name = self.extract_name(object)?;
+        Ok(dpkg_package_plan(&name, Some(&name)))
+    }
+
+    /// Read-only queries of the dpkg database, which take no lock
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::high()
+    }
+}
+
+impl CtnDataCollector for DpkgPackageCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let name = self.extract_name(object)?;
+
+        let Some((packages, failure)) = self.query(&object.identifier, Some(&name))? else {
+            return Ok(not_applicable_data(
+                &object.identifier,
+                &contract.ctn_type,
+                &self.id,
+                NO_DPKG,
+            ));
+        };
+        let mut plan = dpkg_package_plan(&name, Some(&name));
+        if let Some(failure) = failure {
+            plan = failure.record(plan);
+        }
+
+        Ok(self.build_data(object, plan, &packages, &name))
+    }
+
+    /// Collect many packages with one `dpkg-query -W` listing every package
+    ///
+    /// Fails if any object is misconfigured or the listing fails.
+    fn collect_batch(
+        &self,
+        objects: Vec<&ExecutableObject>,
+        contract: &CtnContract,
+    ) -> Result<HashMap<String, CollectedData>, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+
+        let members = objects
+            .into_iter()
+            .map(|object| Ok((self.extract_name(object)?, object)))
+            .collect::<Result<Vec<_>, CollectionError>>()?;
+        let Some((_, first)) = members.first() else {
+            return Ok(HashMap::new());
+        };
+
+        let Some((packages, _)) = self.query(&first.identifier, None)? else {
+            return Ok(members
+                .iter()
+                .map(|(_, object)| {
+                    let data = not_applicable_data(
+                        &object.identifier,
+                        &contract.ctn_type,
+                        &self.id,
+                        NO_DPKG,
+                    );
+                    (object.identifier.clone(), data)
+                })
+                .collect());
+        };
+
+        let batch_size = members.len().to_string();
+        Ok(members
+            .iter()
+            .map(|(name, object)| {
+                let plan = dpkg_package_plan(name, None).with_input("batch_size", &batch_size);
+                (
+                    object.identifier.clone(),
+                    self.build_data(object, plan, &packages, name),
+                )
+            })
+            .collect())
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["dpkg_package".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != "dpkg_package" {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!(
+                    "Incompatible CTN type: expected 'dpkg_package', got '{}'",
+                    contract.ctn_type
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        true
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dpkg_package_plan_matches_invocation() {
+        let plan = dpkg_package_plan("telnetd", Some("telnetd"));
+
+        // The planned command is exactly what collection executes
+        let expected = format!(
+            "{} {}",
+            find_dpkg_query(),
+            dpkg_query_args(Some("telnetd")).join(" ")
+        );
+        assert_eq!(plan.command.as_deref(), Some(expected.as_str()));
+        assert_eq!(plan.kind, CollectionKind::Command);
+        assert_eq!(plan.inputs["name"], "telnetd");
+
+        // A batch lists every package
+        let listing = dpkg_package_plan("telnetd", None);
+        assert!(!listing.command.unwrap().ends_with("telnetd"));
+    }
+}
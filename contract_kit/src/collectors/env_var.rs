@@ -0,0 +1,220 @@
+//! Environment Variable Collector
+//!
+//! Reads one environment variable for the `env_var` CTN type.
+//! - Without `pid`: the agent's own environment
+//! - With `pid` (Linux only): `/proc/<pid>/environ`
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use execution_engine::types::common::ResolvedValue;
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::env_var::{read_env_var, EnvVarError};
+
+/// Collector for environment variables
+pub struct EnvVarCollector {
+    id: String,
+}
+
+impl EnvVarCollector {
+    pub fn new() -> Self {
+        Self {
+            id: "env_var_collector".to_string(),
+        }
+    }
+
+    /// Extract the variable name from object
+    fn extract_name(&self, object: &ExecutableObject) -> Result<String, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == "name" {
+                    return match value {
+                        ResolvedValue::String(s) if !s.is_empty() && !s.contains(['=', '\0']) => {
+                            Ok(s.clone())
+                        }
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!(
+                                "Field 'name' must be a non-empty string without '=', got {:?}",
+                                value
+                            ),
+                        }),
+                    };
+                }
+            }
+        }
+
+        Err(CollectionError::InvalidObjectConfiguration {
+            object_id: object.identifier.clone(),
+            reason: "Missing required field 'name'".to_string(),
+        })
+    }
+
+    /// Extract optional process ID from object
+    fn extract_pid(&self, object: &ExecutableObject) -> Result<Option<u32>, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == "pid" {
+                    let pid = match value {
+                        ResolvedValue::Integer(i) => u32::try_from(*i).ok(),
+                        ResolvedValue::String(s) => s.parse::<u32>().ok(),
+                        _ => None,
+                    };
+                    return match pid {
+                        Some(pid) if pid > 0 => Ok(Some(pid)),
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!("Invalid pid: {:?}", value),
+                        }),
+                    };
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Default for EnvVarCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Plan for reading one variable
+fn env_var_plan(name: &str, pid: Option<u32>) -> CollectionPlan {
+    let plan = match pid {
+        None => CollectionPlan::new(
+            CollectionKind::ProcessInspection,
+            "Read environment variable of the agent process",
+            format!("env:{}", name),
+        ),
+        Some(pid) => CollectionPlan::new(
+            CollectionKind::FileRead,
+            "Read environment variable from /proc/<pid>/environ",
+            format!("/proc/{}/environ", pid),
+        )
+        .with_input("pid", pid.to_string()),
+    };
+    plan.with_input("name", name)
+}
+
+impl DescribeCollection for EnvVarCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let name = self.extract_name(object)?;
+        let pid = self.extract_pid(object)?;
+        Ok(env_var_plan(&name, pid))
+    }
+
+    /// Lookups are in-memory or a single small procfs read
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::high()
+    }
+}
+
+impl CtnDataCollector for EnvVarCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let name = self.extract_name(object)?;
+        let pid = self.extract_pid(object)?;
+
+        // An unset variable is data (exists = false), not an error
+        let value = read_env_var(&name, pid).map_err(|e| match e {
+            EnvVarError::ProcessNotFound(_) => CollectionError::ObjectNotFound {
+                object_id: object.identifier.clone(),
+            },
+            EnvVarError::AccessDenied(_) => CollectionError::AccessDenied {
+                object_id: object.identifier.clone(),
+                reason: e.to_string(),
+            },
+            EnvVarError::Unsupported(reason) => CollectionError::InvalidObjectConfiguration {
+                object_id: object.identifier.clone(),
+                reason,
+            },
+            EnvVarError::ReadFailed(..) => CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: e.to_string(),
+            },
+        })?;
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "env_var".to_string(),
+            self.id.clone(),
+        );
+        data.set_method(env_var_plan(&name, pid).into_method());
+
+        data.add_field(
+            "exists".to_string(),
+            ResolvedValue::Boolean(value.is_some()),
+        );
+        // Present-but-empty variables keep an empty value
+        if let Some(value) = value {
+            data.add_field("value".to_string(), ResolvedValue::String(value));
+        }
+
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["env_var".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != "env_var" {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!(
+                    "Incompatible CTN type: expected 'env_var', got '{}'",
+                    contract.ctn_type
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_ctn_types() {
+        let collector = EnvVarCollector::new();
+        assert_eq!(collector.collector_id(), "env_var_collector");
+        assert_eq!(collector.supported_ctn_types(), vec!["env_var"]);
+    }
+
+    #[test]
+    fn test_env_var_plan() {
+        let plan = env_var_plan("LANG", None);
+        assert_eq!(plan.kind, CollectionKind::ProcessInspection);
+        assert_eq!(plan.target, "env:LANG");
+        assert!(!plan.inputs.contains_key("pid"));
+
+        let plan = env_var_plan("LANG", Some(42));
+        assert_eq!(plan.kind, CollectionKind::FileRead);
+        assert_eq!(plan.target, "/proc/42/environ");
+        assert_eq!(plan.inputs.get("name").map(String::as_str), Some("LANG"));
+        assert_eq!(plan.inputs.get("pid").map(String::as_str), Some("42"));
+    }
+}
@@ -0,0 +1,401 @@
+//! Collection Error Reporting
+//!
+//! Wraps a collector so runtime collection errors are returned as data
+//! instead of being absorbed by the engine as a generic failure. Executors
+//! recognise the marker fields and report a `collection_error` result naming
+//! the collector, object, and reason, so "couldn't check" is distinct from
+//! "checked and non-compliant".
+//!
+//! Only runtime errors are converted (`AccessDenied`, `ObjectNotFound`,
+//! `CollectionFailed`). Configuration errors still propagate unchanged.
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{
+    CollectedData, CollectionError, CtnContract, CtnDataCollector, FieldValidationResult,
+    StateValidationResult,
+};
+use execution_engine::types::common::{Operation, ResolvedValue};
+use execution_engine::types::execution_context::ExecutableObject;
+use std::collections::HashMap;
+
+/// Finding category for errors that prevented a check
+pub const COLLECTION_ERROR_CATEGORY: &str = "collection_error";
+
+/// Marker field holding the error kind
+pub const COLLECTION_ERROR_FIELD: &str = "collection_error";
+
+const COLLECTOR_FIELD: &str = "collection_error_collector";
+const OBJECT_FIELD: &str = "collection_error_object";
+const REASON_FIELD: &str = "collection_error_reason";
+
+/// Every field a record sets on collected data
+pub(crate) const COLLECTION_ERROR_FIELDS: [&str; 4] = [
+    COLLECTION_ERROR_FIELD,
+    COLLECTOR_FIELD,
+    OBJECT_FIELD,
+    REASON_FIELD,
+];
+
+/// Kind of runtime collection error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionErrorKind {
+    /// Object exists but could not be read
+    AccessDenied,
+    /// Object does not exist
+    ObjectNotFound,
+    /// Collection failed for another reason (command error, parse error)
+    CollectionFailed,
+}
+
+impl CollectionErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AccessDenied => "access_denied",
+            Self::ObjectNotFound => "object_not_found",
+            Self::CollectionFailed => "collection_failed",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "access_denied" => Some(Self::AccessDenied),
+            "object_not_found" => Some(Self::ObjectNotFound),
+            "collection_failed" => Some(Self::CollectionFailed),
+            _ => None,
+        }
+    }
+}
+
+/// A collection error carried through to the executor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionErrorRecord {
+    pub kind: CollectionErrorKind,
+    pub collector_id: String,
+    pub object_id: String,
+    pub reason: String,
+}
+
+impl CollectionErrorRecord {
+    /// Build a record from a runtime collection error
+    ///
+    /// Returns `None` for configuration errors, which should not be masked.
+    pub fn from_error(error: &CollectionError, collector_id: &str) -> Option<Self> {
+        let (kind, object_id, reason) = match error {
+            CollectionError::AccessDenied { object_id, reason } => {
+                (CollectionErrorKind::AccessDenied, object_id, reason.clone())
+            }
+            CollectionError::ObjectNotFound { object_id } => (
+                CollectionErrorKind::ObjectNotFound,
+                object_id,
+                "Object not found".to_string(),
+            ),
+            CollectionError::CollectionFailed { object_id, reason } => (
+                CollectionErrorKind::CollectionFailed,
+                object_id,
+                reason.clone(),
+            ),
+            _ => return None,
+        };
+
+        Some(Self {
+            kind,
+            collector_id: collector_id.to_string(),
+            object_id: object_id.clone(),
+            reason,
+        })
+    }
+
+    /// Read a record back from collected data, if the data carries one
+    pub fn from_collected_data(data: &CollectedData) -> Option<Self> {
+        let string_field = |name: &str| match data.get_field(name) {
+            Some(ResolvedValue::String(s)) => Some(s.clone()),
+            _ => None,
+        };
+
+        Some(Self {
+            kind: CollectionErrorKind::parse(&string_field(COLLECTION_ERROR_FIELD)?)?,
+            collector_id: string_field(COLLECTOR_FIELD).unwrap_or_default(),
+            object_id: string_field(OBJECT_FIELD).unwrap_or_default(),
+            reason: string_field(REASON_FIELD).unwrap_or_default(),
+        })
+    }
+
+    /// All records in a criterion's collected data, ordered by object ID
+    pub fn from_collected(collected_data: &HashMap<String, CollectedData>) -> Vec<Self> {
+        let mut records: Vec<Self> = collected_data
+            .values()
+            .filter_map(Self::from_collected_data)
+            .collect();
+        records.sort_by(|a, b| a.object_id.cmp(&b.object_id));
+        records
+    }
+
+    /// Encode the record as collected data for the executor
+    pub fn to_collected_data(&self, ctn_type: &str) -> CollectedData {
+        let mut data = CollectedData::new(
+            self.object_id.clone(),
+            ctn_type.to_string(),
+            self.collector_id.clone(),
+        );
+        data.add_field(
+            COLLECTION_ERROR_FIELD.to_string(),
+            ResolvedValue::String(self.kind.as_str().to_string()),
+        );
+        data.add_field(
+            COLLECTOR_FIELD.to_string(),
+            ResolvedValue::String(self.collector_id.clone()),
+        );
+        data.add_field(
+            OBJECT_FIELD.to_string(),
+            ResolvedValue::String(self.object_id.clone()),
+        );
+        data.add_field(
+            REASON_FIELD.to_string(),
+            ResolvedValue::String(self.reason.clone()),
+        );
+        data
+    }
+
+    /// Whether the object exists for existence checks
+    ///
+    /// Objects that could not be read still exist; only `ObjectNotFound`
+    /// counts as missing.
+    pub fn counts_as_found(&self) -> bool {
+        self.kind != CollectionErrorKind::ObjectNotFound
+    }
+
+    /// Human-readable description
+    pub fn message(&self) -> String {
+        format!(
+            "Object '{}': [{}] collector '{}' could not collect object ({}): {}",
+            self.object_id,
+            COLLECTION_ERROR_CATEGORY,
+            self.collector_id,
+            self.kind.as_str(),
+            self.reason
+        )
+    }
+
+    /// Field result recorded in place of the state fields that were not checked
+    pub fn field_result(&self) -> FieldValidationResult {
+        FieldValidationResult {
+            field_name: COLLECTION_ERROR_FIELD.to_string(),
+            expected_value: ResolvedValue::String(String::new()),
+            actual_value: ResolvedValue::String(self.reason.clone()),
+            operation: Operation::Equals,
+            passed: false,
+            message: self.message(),
+        }
+    }
+
+    /// Structured form for result details
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "category": COLLECTION_ERROR_CATEGORY,
+            "kind": self.kind.as_str(),
+            "collector_id": self.collector_id,
+            "object_id": self.object_id,
+            "reason": self.reason,
+        })
+    }
+}
+
+/// Count objects that exist, excluding ones reported as not found
+pub fn count_found_objects(
+    collected_data: &HashMap<String, CollectedData>,
+    collection_errors: &[CollectionErrorRecord],
+) -> usize {
+    let not_found = collection_errors
+        .iter()
+        .filter(|e| !e.counts_as_found())
+        .count();
+    collected_data.len().saturating_sub(not_found)
+}
+
+/// Describe objects reported as not found, for existence check messages
+pub fn not_found_details(collection_errors: &[CollectionErrorRecord]) -> String {
+    collection_errors
+        .iter()
+        .filter(|e| !e.counts_as_found())
+        .map(|e| format!("\n  - {}", e.message()))
+        .collect()
+}
+
+/// Whether a state result stands in for an object that could not be collected
+pub fn is_collection_error_result(result: &StateValidationResult) -> bool {
+    result
+        .state_results
+        .iter()
+        .any(|r| r.field_name == COLLECTION_ERROR_FIELD)
+}
+
+/// Failing objects whose failure is a collection error rather than a policy result
+///
+/// Returns `(failing, failing_from_errors)`.
+pub fn count_failing_objects(state_results: &[StateValidationResult]) -> (usize, usize) {
+    let failing: Vec<&StateValidationResult> = state_results
+        .iter()
+        .filter(|r| !r.combined_result)
+        .collect();
+    let from_errors = failing
+        .iter()
+        .filter(|r| is_collection_error_result(r))
+        .count();
+    (failing.len(), from_errors)
+}
+
+/// Collector wrapper that returns runtime errors as collected data
+#[derive(Clone)]
+pub struct ErrorReportingCollector<C> {
+    inner: C,
+}
+
+impl<C: CtnDataCollector> ErrorReportingCollector<C> {
+    pub fn new(inner: C) -> Self {
+        Self { inner }
+    }
+}
+
+impl<C: CtnDataCollector> CtnDataCollector for ErrorReportingCollector<C> {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        match self
+            .inner
+            .collect_for_ctn_with_hints(object, contract, hints)
+        {
+            Ok(data) => Ok(data),
+            Err(e) => match CollectionErrorRecord::from_error(&e, self.inner.collector_id()) {
+                Some(record) => Ok(record.to_collected_data(&contract.ctn_type)),
+                None => Err(e),
+            },
+        }
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        self.inner.supported_ctn_types()
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        self.inner.validate_ctn_compatibility(contract)
+    }
+
+    fn collector_id(&self) -> &str {
+        self.inner.collector_id()
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        self.inner.supports_batch_collection()
+    }
+
+    /// Batching collectors report per-object errors as data themselves
+    fn collect_batch(
+        &self,
+        objects: Vec<&ExecutableObject>,
+        contract: &CtnContract,
+    ) -> Result<HashMap<String, CollectedData>, CollectionError> {
+        self.inner.collect_batch(objects, contract)
+    }
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_denied_round_trip() {
+        let error = CollectionError::AccessDenied {
+            object_id: "shadow".to_string(),
+            reason: "Access denied: /etc/shadow".to_string(),
+        };
+        let record = CollectionErrorRecord::from_error(&error, "filesystem_collector").unwrap();
+        assert_eq!(record.kind, CollectionErrorKind::AccessDenied);
+        assert!(record.counts_as_found());
+
+        let data = record.to_collected_data("file_content");
+        let decoded = CollectionErrorRecord::from_collected_data(&data).unwrap();
+        assert_eq!(decoded, record);
+        assert_eq!(decoded.collector_id, "filesystem_collector");
+        assert_eq!(decoded.object_id, "shadow");
+        assert_eq!(decoded.reason, "Access denied: /etc/shadow");
+
+        let json = decoded.to_json();
+        assert_eq!(json["category"], "collection_error");
+        assert_eq!(json["kind"], "access_denied");
+    }
+
+    #[test]
+    fn test_access_denied_counts_as_found() {
+        let record = CollectionErrorRecord::from_error(
+            &CollectionError::AccessDenied {
+                object_id: "shadow".to_string(),
+                reason: "denied".to_string(),
+            },
+            "filesystem_collector",
+        )
+        .unwrap();
+
+        let mut collected = HashMap::new();
+        collected.insert(
+            "shadow".to_string(),
+            record.to_collected_data("file_content"),
+        );
+        let errors = CollectionErrorRecord::from_collected(&collected);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(count_found_objects(&collected, &errors), 1);
+
+        let field = errors[0].field_result();
+        assert!(!field.passed);
+        assert!(field.message.contains("[collection_error]"));
+        assert!(field.message.contains("filesystem_collector"));
+    }
+
+    #[test]
+    fn test_object_not_found_is_missing() {
+        let record = CollectionErrorRecord::from_error(
+            &CollectionError::ObjectNotFound {
+                object_id: "missing_conf".to_string(),
+            },
+            "filesystem_collector",
+        )
+        .unwrap();
+        assert_eq!(record.kind, CollectionErrorKind::ObjectNotFound);
+        assert!(!record.counts_as_found());
+
+        let mut collected = HashMap::new();
+        collected.insert(
+            "missing_conf".to_string(),
+            record.to_collected_data("file_metadata"),
+        );
+        let errors = CollectionErrorRecord::from_collected(&collected);
+        assert_eq!(errors[0].kind, CollectionErrorKind::ObjectNotFound);
+        assert_eq!(count_found_objects(&collected, &errors), 0);
+        assert!(not_found_details(&errors).contains("object_not_found"));
+    }
+
+    #[test]
+    fn test_configuration_errors_not_converted() {
+        let error = CollectionError::InvalidObjectConfiguration {
+            object_id: "obj".to_string(),
+            reason: "missing path".to_string(),
+        };
+        assert!(CollectionErrorRecord::from_error(&error, "filesystem_collector").is_none());
+    }
+
+    #[test]
+    fn test_regular_data_has_no_record() {
+        let mut data = CollectedData::new(
+            "obj".to_string(),
+            "file_metadata".to_string(),
+            "filesystem_collector".to_string(),
+        );
+        data.add_field("exists".to_string(), ResolvedValue::Boolean(true));
+        assert!(CollectionErrorRecord::from_collected_data(&data).is_none());
+    }
+}
@@ -1,6 +1,6 @@
 //! # File System Data Collector
 //!
-//! Collects file metadata and content.
+//! Collects file metadata, content and content hashes.
 //! On Windows, uses native Win32 APIs for metadata collection.
 //! On other platforms, uses standard Rust APIs.
 //!
@@ -8,40 +8,133 @@
 //!
 //! | Category | Fields |
 //! |----------|--------|
-//! | Portable | `exists`, `readable`, `writable`, `file_size`, `is_directory`, `file_owner`, `file_group` |
+//! | Portable | `exists`, `readable`, `writable`, `file_size`, `is_directory`, `file_owner`, `file_group`, `file_owner_name`, `file_group_name`, `is_symlink`, `symlink_target`, `acl_entries`, `acl_record`, `modified_unix`, `accessed_unix`, `created_unix`, `age_seconds` |
 //! | Linux/macOS | `file_mode` (octal permissions) |
+//! | Linux | `selinux_context` (with `BEHAVIOR collect_acls`; empty without it or without SELinux labels) |
 //! | Windows | `is_readonly`, `is_hidden`, `is_system` |
+//!
+//! Times are epoch seconds. `created_unix` is 0 where the platform or
+//! filesystem records no creation time. `age_seconds` is the time since the
+//! last modification, measured when the file is collected during the scan.
+//!
+//! ## Partial Reads
+//!
+//! `file_content` reads the whole file unless one of `tail_lines`,
+//! `head_bytes` or `byte_range` is set, in which case only that part is
+//! read (see `read_file_range`). Partial reads apply to single files and
+//! cannot be combined with each other or with `recursive_scan`.
+//!
+//! ## Size Limit
+//!
+//! `file_content` collects at most `max_bytes` (default 10 MiB) per
+//! object: from one file, from a partial read, or across every file of a
+//! recursive scan. Larger content fails collection with `TooLarge` instead
+//! of being read into memory.
+//!
+//! ## Strict Scans
+//!
+//! A recursive scan skips directories and files it cannot read. With
+//! `BEHAVIOR strict_scan`, the ones skipped because permission was denied
+//! are collected as `skipped_paths`, which the `file_content` executor
+//! reports as a collection error instead of evaluating the content, so an
+//! unreadable file cannot make a check pass.
+//!
+//! ## Hashing
+//!
+//! `file_hash` streams the file through SHA-256 in chunks (see
+//! `hash_file`), so it has no size limit and never holds the content.
+//!
+//! ## Path Expansion
+//!
+//! With `with_path_expansion`, allowlisted environment variables in `path`
+//! (`${HOME}`, `%ProgramData%`) are expanded before use; see
+//! `path_expansion`. Without it, paths are used as written.
+//!
+//! ## Glob Paths
+//!
+//! With `BEHAVIOR glob_expand`, a `file_metadata` path containing `*`, `?`
+//! or `[` is expanded (see `commands::glob`) and each match is collected
+//! as its own object, keyed by its path (see `glob_objects`).
+//!
+//! ## SELinux Contexts
+//!
+//! Reading `selinux_context` costs an extended attribute read per file, so
+//! it is read only for objects with `BEHAVIOR collect_acls` and is empty
+//! otherwise. ACL entries are read for every object.
+//!
+//! ## Batch Collection
+//!
+//! With `with_metadata_batching`, `file_metadata` objects are collected in
+//! one `collect_batch` call: each distinct path is stat'ed once, and
+//! runtime errors are recorded per object instead of failing the batch.
+//! Batches carry no behavior hints, so `glob_expand` objects are not
+//! expanded and `collect_acls` reads no SELinux context in a batch; leave
+//! batching off where policies use them. The agent leaves it off for this
+//! reason, so it is for embedders whose policies use neither behavior. Content,
+//! JSON and hash collection are never batched.
 
-use common::results::{CollectionMethod, CollectionMethodType};
 use execution_engine::execution::BehaviorHints;
 use execution_engine::strategies::{
     CollectedData, CollectionError, CollectionMode, CtnContract, CtnDataCollector,
 };
 use execution_engine::types::common::{RecordData, ResolvedValue};
 use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+use std::collections::HashMap;
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::commands::filesystem::{get_file_metadata, read_file_content, FileSystemError};
+use super::concurrency::ConcurrencyHint;
+use super::error_reporting::{CollectionErrorKind, CollectionErrorRecord};
+use super::glob_objects::{glob_plan, pack_glob_matches};
+use super::path_expansion::PathExpansion;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::directory_walk::{walk_directory_checked, WalkOptions, DEFAULT_WALK_THREADS};
+use crate::commands::file_acl::{get_selinux_context, AclEntry};
+use crate::commands::filesystem::{
+    get_file_metadata, hash_file, read_file_content, read_file_content_bounded,
+    read_file_range_bounded, ContentRange, FileMetadata, FileSystemError,
+    DEFAULT_MAX_CONTENT_BYTES,
+};
+use crate::commands::glob::{expand_glob, has_glob_metacharacters, GlobError};
 
 /// Collector for file system data
 pub struct FileSystemCollector {
     id: String,
+    path_expansion: Option<PathExpansion>,
+    batch_metadata: bool,
 }
 
 impl FileSystemCollector {
     pub fn new() -> Self {
         Self {
             id: "filesystem_collector".to_string(),
+            path_expansion: None,
+            batch_metadata: false,
         }
     }
 
-    /// Extract path from object, handling VAR resolution
+    /// Collect `file_metadata` objects in batches
+    ///
+    /// Only for a collector registered for `file_metadata`; content, JSON
+    /// and hash collection are never batched.
+    pub fn with_metadata_batching(mut self) -> Self {
+        self.batch_metadata = true;
+        self
+    }
+
+    /// Expand allowlisted environment variables in object paths
+    pub fn with_path_expansion(mut self, expansion: PathExpansion) -> Self {
+        self.path_expansion = Some(expansion);
+        self
+    }
+
+    /// Extract path from object, handling VAR resolution and path expansion
     fn extract_path(&self, object: &ExecutableObject) -> Result<String, CollectionError> {
         for element in &object.elements {
             if let ExecutableObjectElement::Field { name, value, .. } = element {
                 if name == "path" {
                     match value {
-                        ResolvedValue::String(s) => return Ok(s.clone()),
+                        ResolvedValue::String(s) => return self.expand_path(s, object),
                         _ => {
                             return Err(CollectionError::InvalidObjectConfiguration {
                                 object_id: object.identifier.clone(),
@@ -59,12 +152,82 @@ impl FileSystemCollector {
         })
     }
 
+    /// Apply environment expansion, if enabled
+    fn expand_path(
+        &self,
+        path: &str,
+        object: &ExecutableObject,
+    ) -> Result<String, CollectionError> {
+        match &self.path_expansion {
+            Some(expansion) => {
+                expansion
+                    .expand(path)
+                    .map_err(|e| CollectionError::InvalidObjectConfiguration {
+                        object_id: object.identifier.clone(),
+                        reason: format!("Cannot expand path '{}': {}", path, e),
+                    })
+            }
+            None => Ok(path.to_string()),
+        }
+    }
+
     /// Collect metadata using platform-native API
+    ///
+    /// The SELinux context is read only with `selinux`.
     fn collect_metadata(
         &self,
         path: &str,
         object_id: &str,
+        selinux: bool,
     ) -> Result<CollectedData, CollectionError> {
+        let mut metadata = lookup_metadata(path, object_id)?;
+        if selinux && metadata.exists {
+            metadata.selinux_context = get_selinux_context(path);
+        }
+        Ok(self.metadata_data(path, object_id, metadata))
+    }
+
+    /// Collect metadata for several objects, stat'ing each distinct path once
+    ///
+    /// `objects` pairs object identifiers with their paths. Runtime errors
+    /// are recorded as collection errors of the objects they affect.
+    fn collect_metadata_batch(
+        &self,
+        objects: &[(String, String)],
+        ctn_type: &str,
+    ) -> HashMap<String, CollectedData> {
+        let mut lookups: HashMap<&str, Result<FileMetadata, CollectionErrorRecord>> =
+            HashMap::new();
+        let mut collected = HashMap::with_capacity(objects.len());
+
+        for (object_id, path) in objects {
+            let lookup = lookups.entry(path.as_str()).or_insert_with(|| {
+                lookup_metadata(path, object_id).map_err(|e| {
+                    CollectionErrorRecord::from_error(&e, &self.id).unwrap_or_else(|| {
+                        CollectionErrorRecord {
+                            kind: CollectionErrorKind::CollectionFailed,
+                            collector_id: self.id.clone(),
+                            object_id: object_id.clone(),
+                            reason: e.to_string(),
+                        }
+                    })
+                })
+            });
+            let data = match lookup {
+                Ok(metadata) => self.metadata_data(path, object_id, metadata.clone()),
+                Err(record) => CollectionErrorRecord {
+                    object_id: object_id.clone(),
+                    ..record.clone()
+                }
+                .to_collected_data(ctn_type),
+            };
+            collected.insert(object_id.clone(), data);
+        }
+        collected
+    }
+
+    /// Collected data for metadata read from `path`
+    fn metadata_data(&self, path: &str, object_id: &str, metadata: FileMetadata) -> CollectedData {
         let mut data = CollectedData::new(
             object_id.to_string(),
             "file_metadata".to_string(),
@@ -72,32 +235,7 @@ impl FileSystemCollector {
         );
 
         // Set collection method for traceability
-        #[cfg(windows)]
-        let description = "Query file metadata via Windows API";
-        #[cfg(not(windows))]
-        let description = "Query file metadata via stat()";
-
-        let method = CollectionMethod::builder()
-            .method_type(CollectionMethodType::FileStat)
-            .description(description)
-            .target(path)
-            .build();
-        data.set_method(method);
-
-        // Get metadata using platform-native API
-        let metadata = get_file_metadata(path).map_err(|e| match e {
-            FileSystemError::AccessDenied(p) => CollectionError::AccessDenied {
-                object_id: object_id.to_string(),
-                reason: format!("Permission denied: {}", p),
-            },
-            FileSystemError::NotFound(_) => CollectionError::ObjectNotFound {
-                object_id: object_id.to_string(),
-            },
-            _ => CollectionError::CollectionFailed {
-                object_id: object_id.to_string(),
-                reason: e.to_string(),
-            },
-        })?;
+        data.set_method(metadata_plan(path).into_method());
 
         // ====================================================================
         // Portable Fields (All Platforms)
@@ -107,6 +245,17 @@ impl FileSystemCollector {
             "exists".to_string(),
             ResolvedValue::Boolean(metadata.exists),
         );
+        add_time_fields(&mut data, &metadata);
+
+        // Describe the link itself, which may exist when its target does not
+        data.add_field(
+            "is_symlink".to_string(),
+            ResolvedValue::Boolean(metadata.is_symlink),
+        );
+        data.add_field(
+            "symlink_target".to_string(),
+            ResolvedValue::String(metadata.symlink_target),
+        );
 
         if !metadata.exists {
             // Early return for non-existent files with default values
@@ -122,15 +271,28 @@ impl FileSystemCollector {
                 "file_group".to_string(),
                 ResolvedValue::String(String::new()),
             );
+            data.add_field(
+                "file_owner_name".to_string(),
+                ResolvedValue::String(String::new()),
+            );
+            data.add_field(
+                "file_group_name".to_string(),
+                ResolvedValue::String(String::new()),
+            );
+            add_acl_fields(&mut data, &[]);
             // Platform-specific fields
             data.add_field(
                 "file_mode".to_string(),
                 ResolvedValue::String(String::new()),
             );
+            data.add_field(
+                "selinux_context".to_string(),
+                ResolvedValue::String(String::new()),
+            );
             data.add_field("is_readonly".to_string(), ResolvedValue::Boolean(false));
             data.add_field("is_hidden".to_string(), ResolvedValue::Boolean(false));
             data.add_field("is_system".to_string(), ResolvedValue::Boolean(false));
-            return Ok(data);
+            return data;
         }
 
         data.add_field(
@@ -157,15 +319,28 @@ impl FileSystemCollector {
             "file_group".to_string(),
             ResolvedValue::String(metadata.file_group),
         );
+        data.add_field(
+            "file_owner_name".to_string(),
+            ResolvedValue::String(metadata.file_owner_name),
+        );
+        data.add_field(
+            "file_group_name".to_string(),
+            ResolvedValue::String(metadata.file_group_name),
+        );
+        add_acl_fields(&mut data, &metadata.acl_entries);
 
         // ====================================================================
-        // Linux/macOS Only (empty string on Windows)
+        // Linux/macOS Only (empty string on Windows; SELinux on Linux only)
         // ====================================================================
 
         data.add_field(
             "file_mode".to_string(),
             ResolvedValue::String(metadata.file_mode),
         );
+        data.add_field(
+            "selinux_context".to_string(),
+            ResolvedValue::String(metadata.selinux_context),
+        );
 
         // ====================================================================
         // Windows Only (false on Linux/macOS)
@@ -184,14 +359,59 @@ impl FileSystemCollector {
             ResolvedValue::Boolean(metadata.is_system),
         );
 
-        Ok(data)
+        data
+    }
+
+    /// Collect metadata of every path matching `pattern`
+    ///
+    /// Files that vanish between expansion and collection are dropped;
+    /// files that cannot be read are kept as collection errors.
+    fn collect_glob_metadata(
+        &self,
+        pattern: &str,
+        object_id: &str,
+        contract: &CtnContract,
+        selinux: bool,
+    ) -> Result<CollectedData, CollectionError> {
+        let paths = expand_glob(pattern).map_err(|e| match e {
+            GlobError::AccessDenied(_) => CollectionError::AccessDenied {
+                object_id: object_id.to_string(),
+                reason: e.to_string(),
+            },
+            _ => CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: e.to_string(),
+            },
+        })?;
+
+        let mut matches = Vec::with_capacity(paths.len());
+        for path in paths {
+            let path = path.display().to_string();
+            let data = match self.collect_metadata(&path, &path, selinux) {
+                Ok(data) => data,
+                Err(e) => match CollectionErrorRecord::from_error(&e, &self.id) {
+                    Some(record) if record.counts_as_found() => {
+                        record.to_collected_data(&contract.ctn_type)
+                    }
+                    Some(_) => continue,
+                    None => return Err(e),
+                },
+            };
+            matches.push((path, data));
+        }
+
+        Ok(pack_glob_matches(
+            object_id, pattern, &self.id, contract, matches,
+        ))
     }
 
-    /// Collect file content
+    /// Collect file content, or only `range` of it, up to `max_bytes`
     fn collect_content(
         &self,
         path: &str,
         object_id: &str,
+        range: Option<ContentRange>,
+        max_bytes: u64,
     ) -> Result<CollectedData, CollectionError> {
         let mut data = CollectedData::new(
             object_id.to_string(),
@@ -200,11 +420,14 @@ impl FileSystemCollector {
         );
 
         // Set collection method for traceability
-        let method = CollectionMethod::file_read(path).with_description("Read file contents");
-        data.set_method(method);
+        data.set_method(content_plan(path, range, max_bytes).into_method());
 
         // Read file content
-        let content = read_file_content(path).map_err(|e| match e {
+        let content = match range {
+            Some(range) => read_file_range_bounded(path, range, max_bytes),
+            None => read_file_content_bounded(path, max_bytes),
+        };
+        let content = content.map_err(|e| match e {
             FileSystemError::AccessDenied(p) => CollectionError::AccessDenied {
                 object_id: object_id.to_string(),
                 reason: format!("Cannot read file: {}", p),
@@ -223,6 +446,40 @@ impl FileSystemCollector {
         Ok(data)
     }
 
+    /// Collect the SHA-256 and size of a file
+    fn collect_hash(&self, path: &str, object_id: &str) -> Result<CollectedData, CollectionError> {
+        let mut data = CollectedData::new(
+            object_id.to_string(),
+            "file_hash".to_string(),
+            self.id.clone(),
+        );
+
+        // Set collection method for traceability
+        data.set_method(hash_plan(path).into_method());
+
+        let hash = hash_file(path).map_err(|e| match e {
+            FileSystemError::AccessDenied(p) => CollectionError::AccessDenied {
+                object_id: object_id.to_string(),
+                reason: format!("Cannot read file: {}", p),
+            },
+            FileSystemError::NotFound(_) => CollectionError::ObjectNotFound {
+                object_id: object_id.to_string(),
+            },
+            _ => CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: e.to_string(),
+            },
+        })?;
+
+        data.add_field("sha256".to_string(), ResolvedValue::String(hash.sha256));
+        data.add_field(
+            "file_size".to_string(),
+            ResolvedValue::Integer(i64::try_from(hash.file_size).unwrap_or(i64::MAX)),
+        );
+
+        Ok(data)
+    }
+
     /// Collect JSON file as RecordData
     fn collect_json_record(
         &self,
@@ -236,8 +493,7 @@ impl FileSystemCollector {
         );
 
         // Set collection method for traceability
-        let method = CollectionMethod::file_read(path).with_description("Read and parse JSON file");
-        data.set_method(method);
+        data.set_method(json_plan(path).into_method());
 
         // Read and parse JSON
         let content = read_file_content(path).map_err(|e| CollectionError::CollectionFailed {
@@ -262,13 +518,17 @@ impl FileSystemCollector {
     }
 
     /// Collect files recursively from a directory
+    ///
+    /// `max_bytes` is a budget for the content of all files together. With
+    /// `strict`, paths skipped because permission was denied are collected
+    /// as `skipped_paths`.
     fn collect_recursive(
         &self,
         base_path: &str,
         object_id: &str,
-        max_depth: i64,
-        include_hidden: bool,
-        follow_symlinks: bool,
+        options: &WalkOptions,
+        max_bytes: u64,
+        strict: bool,
     ) -> Result<CollectedData, CollectionError> {
         let mut data = CollectedData::new(
             object_id.to_string(),
@@ -277,15 +537,7 @@ impl FileSystemCollector {
         );
 
         // Set collection method for traceability
-        let method = CollectionMethod::builder()
-            .method_type(CollectionMethodType::FileRead)
-            .description("Recursive directory scan")
-            .target(base_path)
-            .input("max_depth", max_depth.to_string())
-            .input("include_hidden", include_hidden.to_string())
-            .input("follow_symlinks", follow_symlinks.to_string())
-            .build();
-        data.set_method(method);
+        data.set_method(recursive_plan(base_path, options, max_bytes, strict).into_method());
 
         let base = Path::new(base_path);
 
@@ -296,31 +548,39 @@ impl FileSystemCollector {
             });
         }
 
-        // Collect files recursively
-        let mut files = Vec::new();
-        scan_directory_recursive(
-            base,
-            &mut files,
-            0,
-            max_depth,
-            include_hidden,
-            follow_symlinks,
-        )?;
+        // Collect files recursively (sorted by path)
+        let walk = walk_directory_checked(base, options);
+        let mut skipped = walk.denied;
 
         // Collect content from all found files
         let mut all_content = String::new();
         let mut file_count = 0;
+        let mut remaining = max_bytes;
 
-        for file_path in files {
-            match std::fs::read_to_string(&file_path) {
+        for file_path in walk.files {
+            let path_str = file_path.to_string_lossy();
+            match read_file_content_bounded(&path_str, remaining) {
                 Ok(content) => {
+                    remaining -= content.len() as u64;
                     all_content.push_str(&format!("=== {} ===\n", file_path.display()));
                     all_content.push_str(&content);
                     all_content.push_str("\n\n");
                     file_count += 1;
                 }
+                Err(FileSystemError::TooLarge(..)) => {
+                    return Err(CollectionError::CollectionFailed {
+                        object_id: object_id.to_string(),
+                        reason: format!(
+                            "Recursive scan of {} exceeds max_bytes ({} bytes) at {}",
+                            base_path,
+                            max_bytes,
+                            file_path.display()
+                        ),
+                    });
+                }
+                Err(FileSystemError::AccessDenied(_)) => skipped.push(file_path),
                 Err(_) => {
-                    // Skip files we can't read (binary, permissions, etc.)
+                    // Skip files we can't read (binary, etc.)
                     continue;
                 }
             }
@@ -331,92 +591,281 @@ impl FileSystemCollector {
             ResolvedValue::String(all_content),
         );
         data.add_field("file_count".to_string(), ResolvedValue::Integer(file_count));
+        if strict {
+            skipped.sort();
+            data.add_field(
+                "skipped_paths".to_string(),
+                ResolvedValue::Collection(
+                    skipped
+                        .iter()
+                        .map(|path| ResolvedValue::String(path.display().to_string()))
+                        .collect(),
+                ),
+            );
+        }
 
         Ok(data)
     }
 }
 
-/// Recursively scan directory tree
-fn scan_directory_recursive(
-    dir: &Path,
-    files: &mut Vec<std::path::PathBuf>,
-    current_depth: i64,
-    max_depth: i64,
-    include_hidden: bool,
-    follow_symlinks: bool,
-) -> Result<(), CollectionError> {
-    // Check depth limit
-    if current_depth >= max_depth {
-        return Ok(());
-    }
-
-    // Try to read directory
-    let entries = match std::fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => {
-            // Skip directories we can't read
-            return Ok(());
-        }
+/// Add the ACL as a set of entry texts and as a record
+///
+/// The record is `{"entries": [{"tag", "principal", "permissions"}]}`.
+fn add_acl_fields(data: &mut CollectedData, entries: &[AclEntry]) {
+    data.add_field(
+        "acl_entries".to_string(),
+        ResolvedValue::Collection(
+            entries
+                .iter()
+                .map(|entry| ResolvedValue::String(entry.text()))
+                .collect(),
+        ),
+    );
+    let record = serde_json::json!({
+        "entries": entries.iter().map(AclEntry::to_json).collect::<Vec<_>>(),
+    });
+    data.add_field(
+        "acl_record".to_string(),
+        ResolvedValue::RecordData(Box::new(RecordData::from_json_value(record))),
+    );
+}
+
+/// Build walk options from behavior hints
+fn walk_options(hints: &BehaviorHints) -> WalkOptions {
+    let threads = hints
+        .get_parameter_as_int("walk_threads")
+        .and_then(|t| usize::try_from(t).ok())
+        .unwrap_or(DEFAULT_WALK_THREADS);
+    WalkOptions {
+        max_depth: hints.get_parameter_as_int("max_depth").unwrap_or(3),
+        include_hidden: hints.has_flag("include_hidden"),
+        follow_symlinks: hints.has_flag("follow_symlinks"),
+        threads,
+    }
+}
+
+/// Add the file times and the age of the last modification
+///
+/// A missing file has all times and its age 0.
+fn add_time_fields(data: &mut CollectedData, metadata: &FileMetadata) {
+    let age = if metadata.exists {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX))
+            .unwrap_or_default();
+        now.saturating_sub(metadata.modified_unix)
+    } else {
+        0
     };
+    for (name, value) in [
+        ("modified_unix", metadata.modified_unix),
+        ("accessed_unix", metadata.accessed_unix),
+        ("created_unix", metadata.created_unix),
+        ("age_seconds", age),
+    ] {
+        data.add_field(name.to_string(), ResolvedValue::Integer(value));
+    }
+}
 
-    for entry_result in entries {
-        let entry = match entry_result {
-            Ok(e) => e,
-            Err(_) => continue, // Skip bad entries
-        };
+/// Read metadata with the platform-native API
+fn lookup_metadata(path: &str, object_id: &str) -> Result<FileMetadata, CollectionError> {
+    get_file_metadata(path).map_err(|e| match e {
+        FileSystemError::AccessDenied(p) => CollectionError::AccessDenied {
+            object_id: object_id.to_string(),
+            reason: format!("Permission denied: {}", p),
+        },
+        FileSystemError::NotFound(_) => CollectionError::ObjectNotFound {
+            object_id: object_id.to_string(),
+        },
+        _ => CollectionError::CollectionFailed {
+            object_id: object_id.to_string(),
+            reason: e.to_string(),
+        },
+    })
+}
 
-        let path = entry.path();
+/// Whether `path` is a pattern to expand (`glob_expand` behavior)
+fn expands_glob(path: &str, hints: &BehaviorHints) -> bool {
+    hints.has_flag("glob_expand") && has_glob_metacharacters(path)
+}
 
-        // Get filename for hidden check
-        let file_name = match path.file_name().and_then(|n| n.to_str()) {
-            Some(n) => n,
-            None => continue,
-        };
+/// Plan for metadata collection
+fn metadata_plan(path: &str) -> CollectionPlan {
+    #[cfg(windows)]
+    let description = "Query file metadata via Windows API";
+    #[cfg(not(windows))]
+    let description = "Query file metadata via stat()";
+
+    CollectionPlan::new(CollectionKind::FileStat, description, path)
+}
+
+/// Part of the file to read, from the partial read behaviors
+fn content_range(
+    hints: &BehaviorHints,
+    object_id: &str,
+) -> Result<Option<ContentRange>, CollectionError> {
+    let invalid = |reason: String| CollectionError::InvalidObjectConfiguration {
+        object_id: object_id.to_string(),
+        reason,
+    };
+    let positive = |name: &str, value: i64| {
+        u64::try_from(value)
+            .ok()
+            .filter(|v| *v > 0)
+            .ok_or_else(|| invalid(format!("{} must be positive, got {}", name, value)))
+    };
 
-        // Skip hidden files unless include_hidden is set
-        if !include_hidden && file_name.starts_with('.') {
-            continue;
+    let tail_lines = hints.get_parameter_as_int("tail_lines");
+    let head_bytes = hints.get_parameter_as_int("head_bytes");
+    let byte_range = hints.has_flag("byte_range");
+
+    let requested = [tail_lines.is_some(), head_bytes.is_some(), byte_range];
+    match requested.iter().filter(|set| **set).count() {
+        0 => return Ok(None),
+        1 => {}
+        _ => {
+            return Err(invalid(
+                "only one of tail_lines, head_bytes and byte_range may be set".to_string(),
+            ))
         }
+    }
+    if hints.has_flag("recursive_scan") {
+        return Err(invalid(
+            "tail_lines, head_bytes and byte_range cannot be combined with recursive_scan"
+                .to_string(),
+        ));
+    }
 
-        // On Windows, also check hidden attribute
-        #[cfg(windows)]
-        if !include_hidden {
-            if let Ok(metadata) = get_file_metadata(path.to_str().unwrap_or("")) {
-                if metadata.is_hidden {
-                    continue;
-                }
+    if let Some(lines) = tail_lines {
+        let lines = positive("tail_lines", lines)?;
+        return Ok(Some(ContentRange::TailLines(
+            usize::try_from(lines).unwrap_or(usize::MAX),
+        )));
+    }
+    if let Some(bytes) = head_bytes {
+        return Ok(Some(ContentRange::Head(positive("head_bytes", bytes)?)));
+    }
+
+    let offset = hints.get_parameter_as_int("offset").unwrap_or(0);
+    let offset = u64::try_from(offset)
+        .map_err(|_| invalid(format!("offset must not be negative, got {}", offset)))?;
+    let length = hints
+        .get_parameter_as_int("length")
+        .ok_or_else(|| invalid("byte_range requires length".to_string()))?;
+    Ok(Some(ContentRange::Bytes {
+        offset,
+        length: positive("length", length)?,
+    }))
+}
+
+/// Content size limit from the `max_bytes` behavior
+fn max_content_bytes(hints: &BehaviorHints, object_id: &str) -> Result<u64, CollectionError> {
+    match hints.get_parameter_as_int("max_bytes") {
+        None => Ok(DEFAULT_MAX_CONTENT_BYTES),
+        Some(bytes) => u64::try_from(bytes).ok().filter(|b| *b > 0).ok_or_else(|| {
+            CollectionError::InvalidObjectConfiguration {
+                object_id: object_id.to_string(),
+                reason: format!("max_bytes must be positive, got {}", bytes),
             }
+        }),
+    }
+}
+
+/// Plan for reading a single file, or part of it
+fn content_plan(path: &str, range: Option<ContentRange>, max_bytes: u64) -> CollectionPlan {
+    let plan = match range {
+        None => CollectionPlan::new(CollectionKind::FileRead, "Read file contents", path),
+        Some(ContentRange::TailLines(lines)) => {
+            CollectionPlan::new(CollectionKind::FileRead, "Read end of file", path)
+                .with_input("tail_lines", lines.to_string())
+        }
+        Some(ContentRange::Head(bytes)) => {
+            CollectionPlan::new(CollectionKind::FileRead, "Read start of file", path)
+                .with_input("head_bytes", bytes.to_string())
+        }
+        Some(ContentRange::Bytes { offset, length }) => {
+            CollectionPlan::new(CollectionKind::FileRead, "Read byte range of file", path)
+                .with_input("offset", offset.to_string())
+                .with_input("length", length.to_string())
         }
+    };
+    plan.with_input("max_bytes", max_bytes.to_string())
+}
 
-        // Get metadata (respecting symlinks setting)
-        let metadata = if follow_symlinks {
-            match std::fs::metadata(&path) {
-                Ok(m) => m,
-                Err(_) => continue,
-            }
-        } else {
-            match std::fs::symlink_metadata(&path) {
-                Ok(m) => m,
-                Err(_) => continue,
+/// Plan for hashing a file
+fn hash_plan(path: &str) -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::FileRead,
+        "Hash file contents (SHA-256)",
+        path,
+    )
+}
+
+/// Plan for reading and parsing a JSON file
+fn json_plan(path: &str) -> CollectionPlan {
+    CollectionPlan::new(CollectionKind::FileRead, "Read and parse JSON file", path)
+}
+
+/// Plan for a recursive directory scan
+fn recursive_plan(
+    path: &str,
+    options: &WalkOptions,
+    max_bytes: u64,
+    strict: bool,
+) -> CollectionPlan {
+    CollectionPlan::new(CollectionKind::FileRead, "Recursive directory scan", path)
+        .with_input("max_depth", options.max_depth.to_string())
+        .with_input("include_hidden", options.include_hidden.to_string())
+        .with_input("follow_symlinks", options.follow_symlinks.to_string())
+        .with_input("walk_threads", options.threads.to_string())
+        .with_input("max_bytes", max_bytes.to_string())
+        .with_input("strict_scan", strict.to_string())
+}
+
+impl DescribeCollection for FileSystemCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        contract.validate_behavior_hints(hints).map_err(|e| {
+            CollectionError::CtnContractValidation {
+                reason: e.to_string(),
             }
-        };
+        })?;
+        let path = self.extract_path(object)?;
 
-        if metadata.is_file() {
-            files.push(path);
-        } else if metadata.is_dir() {
-            // Recurse into subdirectory
-            let _ = scan_directory_recursive(
-                &path,
-                files,
-                current_depth + 1,
-                max_depth,
-                include_hidden,
-                follow_symlinks,
-            );
+        match contract.collection_strategy.collection_mode {
+            CollectionMode::Metadata if expands_glob(&path, hints) => Ok(glob_plan(&path)),
+            CollectionMode::Metadata => Ok(metadata_plan(&path)),
+            CollectionMode::Content if contract.ctn_type == "json_record" => Ok(json_plan(&path)),
+            CollectionMode::Content if contract.ctn_type == "file_hash" => Ok(hash_plan(&path)),
+            CollectionMode::Content => {
+                let range = content_range(hints, &object.identifier)?;
+                let max_bytes = max_content_bytes(hints, &object.identifier)?;
+                if hints.has_flag("recursive_scan") {
+                    Ok(recursive_plan(
+                        &path,
+                        &walk_options(hints),
+                        max_bytes,
+                        hints.has_flag("strict_scan"),
+                    ))
+                } else {
+                    Ok(content_plan(&path, range, max_bytes))
+                }
+            }
+            _ => Err(CollectionError::UnsupportedCollectionMode {
+                collector_id: self.id.clone(),
+                mode: format!("{:?}", contract.collection_strategy.collection_mode),
+            }),
         }
     }
 
-    Ok(())
+    /// Local stat and read calls scale well
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::high()
+    }
 }
 
 impl CtnDataCollector for FileSystemCollector {
@@ -435,29 +884,40 @@ impl CtnDataCollector for FileSystemCollector {
         let path = self.extract_path(object)?;
 
         match contract.collection_strategy.collection_mode {
-            CollectionMode::Metadata => self.collect_metadata(&path, &object.identifier),
+            CollectionMode::Metadata if expands_glob(&path, hints) => self.collect_glob_metadata(
+                &path,
+                &object.identifier,
+                contract,
+                hints.has_flag("collect_acls"),
+            ),
+            CollectionMode::Metadata => {
+                self.collect_metadata(&path, &object.identifier, hints.has_flag("collect_acls"))
+            }
             CollectionMode::Content => {
                 // Check if this is a JSON record request
                 if contract.ctn_type == "json_record" {
                     return self.collect_json_record(&path, &object.identifier);
                 }
+                if contract.ctn_type == "file_hash" {
+                    return self.collect_hash(&path, &object.identifier);
+                }
 
-                if hints.has_flag("recursive_scan") {
-                    let max_depth = hints.get_parameter_as_int("max_depth").unwrap_or(3);
-                    let include_hidden = hints.has_flag("include_hidden");
-                    let follow_symlinks = hints.has_flag("follow_symlinks");
+                let range = content_range(hints, &object.identifier)?;
+                let max_bytes = max_content_bytes(hints, &object.identifier)?;
 
+                if hints.has_flag("recursive_scan") {
+                    let options = walk_options(hints);
                     return self.collect_recursive(
                         &path,
                         &object.identifier,
-                        max_depth,
-                        include_hidden,
-                        follow_symlinks,
+                        &options,
+                        max_bytes,
+                        hints.has_flag("strict_scan"),
                     );
                 }
 
                 // Default content collection
-                self.collect_content(&path, &object.identifier)
+                self.collect_content(&path, &object.identifier, range, max_bytes)
             }
             _ => Err(CollectionError::UnsupportedCollectionMode {
                 collector_id: self.id.clone(),
@@ -471,6 +931,7 @@ impl CtnDataCollector for FileSystemCollector {
             "file_metadata".to_string(),
             "file_content".to_string(),
             "json_record".to_string(),
+            "file_hash".to_string(),
         ]
     }
 
@@ -487,8 +948,32 @@ impl CtnDataCollector for FileSystemCollector {
         &self.id
     }
 
+    /// Only when built `with_metadata_batching`
     fn supports_batch_collection(&self) -> bool {
-        false
+        self.batch_metadata
+    }
+
+    fn collect_batch(
+        &self,
+        objects: Vec<&ExecutableObject>,
+        contract: &CtnContract,
+    ) -> Result<HashMap<String, CollectedData>, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        if !matches!(
+            contract.collection_strategy.collection_mode,
+            CollectionMode::Metadata
+        ) {
+            return Err(CollectionError::UnsupportedCollectionMode {
+                collector_id: self.id.clone(),
+                mode: format!("{:?}", contract.collection_strategy.collection_mode),
+            });
+        }
+
+        let objects = objects
+            .into_iter()
+            .map(|object| Ok((object.identifier.clone(), self.extract_path(object)?)))
+            .collect::<Result<Vec<_>, CollectionError>>()?;
+        Ok(self.collect_metadata_batch(&objects, &contract.ctn_type))
     }
 }
 
@@ -497,3 +982,83 @@ impl Default for FileSystemCollector {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Debug form of every field of `data` named in the contract
+    fn field_values(data: &CollectedData, fields: &[String]) -> Vec<String> {
+        fields
+            .iter()
+            .map(|field| format!("{}={:?}", field, data.get_field(field)))
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_matches_per_object_collection() {
+        let contract = crate::contracts::create_file_metadata_contract();
+        let fields = &contract
+            .field_mappings
+            .collection_mappings
+            .required_data_fields;
+        let collector = FileSystemCollector::new().with_metadata_batching();
+        assert!(collector.supports_batch_collection());
+
+        let file = std::env::temp_dir().join(format!("esp_batch_{}", std::process::id()));
+        std::fs::write(&file, "batch").expect("write test file");
+        let file = file.display().to_string();
+        let missing = format!("{}_missing", file);
+
+        let objects = vec![
+            ("first".to_string(), file.clone()),
+            ("same_file".to_string(), file.clone()),
+            ("missing".to_string(), missing.clone()),
+        ];
+        let batch = collector.collect_metadata_batch(&objects, "file_metadata");
+        assert_eq!(batch.len(), 3);
+
+        for (object_id, path) in &objects {
+            let single = collector
+                .collect_metadata(path, object_id, false)
+                .expect("per-object collection");
+            let batched = batch.get(object_id).expect("object in batch");
+            assert_eq!(
+                field_values(batched, fields),
+                field_values(&single, fields),
+                "fields differ for {}",
+                object_id
+            );
+        }
+
+        std::fs::remove_file(&file).expect("remove test file");
+    }
+
+    #[test]
+    fn test_selinux_context_needs_collect_acls() {
+        let collector = FileSystemCollector::new();
+        let file = std::env::temp_dir().join(format!("esp_selinux_{}", std::process::id()));
+        std::fs::write(&file, "label").expect("write test file");
+        let path = file.display().to_string();
+
+        let context = |selinux| {
+            collector
+                .collect_metadata(&path, "file", selinux)
+                .expect("collect metadata")
+                .get_field("selinux_context")
+                .cloned()
+        };
+        assert_eq!(context(false), Some(ResolvedValue::String(String::new())));
+        assert_eq!(
+            context(true),
+            Some(ResolvedValue::String(get_selinux_context(&path)))
+        );
+
+        std::fs::remove_file(&file).expect("remove test file");
+    }
+
+    #[test]
+    fn test_batching_is_opt_in() {
+        assert!(!FileSystemCollector::new().supports_batch_collection());
+    }
+}
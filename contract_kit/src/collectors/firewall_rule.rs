@@ -0,0 +1,446 @@
+//! Firewall Rule Collector
+//!
+//! Collects the host firewall ruleset from nftables or legacy iptables.
+//! - Primary source: `nft -j list ruleset`
+//! - Fallback: `iptables-save` (nft missing, failing, or iptables-nft rules),
+//!   or `iptables -S` where only `iptables` is installed
+//!
+//! Returns the parsed ruleset as RecordData plus derived fields for common
+//! controls (default policy, port exposure, required rules). With a `chain`
+//! object field, `rule_count`, `rules`, `default_policy` and `has_rule`
+//! cover only that chain. When no firewall tool is installed, collection
+//! fails rather than reporting an empty ruleset, which accepts everything.
+//! Commands that time out can be retried through the `retry` or `retries`
+//! behaviors; see [`RetryPolicy`]. With an [`Elevation`], both commands run
+//! through its wrapper.
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{
+    CollectedData, CollectionError, CtnContract, CtnDataCollector, SystemCommandExecutor,
+};
+use execution_engine::types::common::{RecordData, ResolvedValue};
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::command_output::{run_command_with_retry, CommandError};
+use crate::commands::elevation::Elevation;
+use crate::commands::firewall_rule::{
+    find_iptables_source, find_nft, firewall_tools_installed, nft_has_xt_expressions,
+    parse_iptables_save, parse_nft_ruleset, FirewallRuleset,
+};
+use crate::commands::retry::RetryPolicy;
+
+/// nft arguments that list the ruleset as JSON
+const NFT_LIST_ARGS: [&str; 3] = ["-j", "list", "ruleset"];
+
+/// Plan for listing the nftables ruleset
+fn nft_plan() -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::Command,
+        "List nftables ruleset as JSON",
+        "firewall_ruleset",
+    )
+    .with_command(format!("{} {}", find_nft(), NFT_LIST_ARGS.join(" ")))
+    .with_input("backend", "nftables")
+}
+
+/// Plan for dumping the legacy iptables ruleset
+fn iptables_plan() -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::Command,
+        "Dump iptables ruleset (nftables unavailable)",
+        "firewall_ruleset",
+    )
+    .with_command(iptables_command_line())
+    .with_input("backend", "iptables")
+}
+
+/// The legacy iptables command as run, arguments included
+fn iptables_command_line() -> String {
+    let (command, args) = find_iptables_source();
+    std::iter::once(command)
+        .chain(args.iter().copied())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Collector for firewall ruleset information
+#[derive(Clone)]
+pub struct FirewallRuleCollector {
+    id: String,
+    executor: SystemCommandExecutor,
+    retry: RetryPolicy,
+    elevation: Option<Elevation>,
+}
+
+impl FirewallRuleCollector {
+    /// Create new collector with the given executor
+    pub fn new(id: impl Into<String>, executor: SystemCommandExecutor) -> Self {
+        Self {
+            id: id.into(),
+            executor,
+            retry: RetryPolicy::none(),
+            elevation: None,
+        }
+    }
+
+    /// Retry policy for objects without retry behaviors
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Run commands through `elevation`'s wrapper (None runs them directly)
+    pub fn with_elevation(mut self, elevation: Option<Elevation>) -> Self {
+        self.elevation = elevation;
+        self
+    }
+
+    /// The plan as run, wrapper included
+    fn elevated(&self, plan: CollectionPlan) -> CollectionPlan {
+        match &self.elevation {
+            Some(elevation) => elevation.record(plan),
+            None => plan,
+        }
+    }
+
+    /// Retry policy for an object, from its behaviors or the collector default
+    fn retry_policy(
+        &self,
+        hints: &BehaviorHints,
+        object_id: &str,
+    ) -> Result<RetryPolicy, CollectionError> {
+        RetryPolicy::from_hints(hints, self.retry).map_err(|reason| {
+            CollectionError::InvalidObjectConfiguration {
+                object_id: object_id.to_string(),
+                reason,
+            }
+        })
+    }
+
+    /// Extract optional port field from object
+    fn extract_port(&self, object: &ExecutableObject) -> Result<Option<u16>, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == "port" {
+                    let port = match value {
+                        ResolvedValue::Integer(i) => u16::try_from(*i).ok(),
+                        ResolvedValue::String(s) => s.parse::<u16>().ok(),
+                        _ => None,
+                    };
+                    return match port {
+                        Some(p) if p > 0 => Ok(Some(p)),
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!("Invalid port: {:?} (must be 1-65535)", value),
+                        }),
+                    };
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Extract optional protocol field from object (default: tcp)
+    fn extract_protocol(&self, object: &ExecutableObject) -> Result<String, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == "protocol" {
+                    return match value {
+                        ResolvedValue::String(s)
+                            if s.eq_ignore_ascii_case("tcp") || s.eq_ignore_ascii_case("udp") =>
+                        {
+                            Ok(s.to_lowercase())
+                        }
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!("Invalid protocol: {:?} (must be tcp or udp)", value),
+                        }),
+                    };
+                }
+            }
+        }
+        Ok("tcp".to_string())
+    }
+
+    /// Extract an optional non-empty string field from object
+    fn extract_string(
+        &self,
+        object: &ExecutableObject,
+        field: &str,
+    ) -> Result<Option<String>, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == field {
+                    return match value {
+                        ResolvedValue::String(s) if !s.trim().is_empty() => Ok(Some(s.clone())),
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!(
+                                "Invalid {}: {:?} (must be a non-empty string)",
+                                field, value
+                            ),
+                        }),
+                    };
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Run a command, retrying transient failures
+    ///
+    /// Returns stdout on success and the number of retries made.
+    fn run(
+        &self,
+        command: &str,
+        args: &[&str],
+        retry: &RetryPolicy,
+    ) -> (Result<String, CommandError>, u32) {
+        run_command_with_retry(
+            &self.executor,
+            self.elevation.as_ref(),
+            command,
+            args,
+            None,
+            retry,
+        )
+    }
+
+    /// Load the ruleset from nftables, falling back to iptables-save
+    ///
+    /// The plan records the retries made across both commands.
+    fn load_ruleset(
+        &self,
+        object_id: &str,
+        retry: &RetryPolicy,
+    ) -> Result<(FirewallRuleset, CollectionPlan), CollectionError> {
+        let nft = find_nft();
+        let mut nft_failure = None;
+        let (nft_result, nft_retries) = self.run(nft, &NFT_LIST_ARGS, retry);
+        let nft_error = match nft_result {
+            Ok(stdout) if !nft_has_xt_expressions(&stdout) => match parse_nft_ruleset(&stdout) {
+                Ok(ruleset) => {
+                    let plan = retry.record(self.elevated(nft_plan()), nft_retries);
+                    return Ok((ruleset, plan));
+                }
+                Err(e) => e.to_string(),
+            },
+            Ok(_) => "nft ruleset contains iptables-nft rules".to_string(),
+            Err(e) => {
+                if let CommandError::Exit(failure) = &e {
+                    nft_failure = Some(failure.clone());
+                }
+                e.to_string()
+            }
+        };
+
+        let (iptables, iptables_args) = find_iptables_source();
+        let (iptables_result, iptables_retries) = self.run(iptables, iptables_args, retry);
+        match iptables_result {
+            Ok(stdout) => {
+                let plan = self.elevated(iptables_plan());
+                let plan = match &nft_failure {
+                    Some(failure) => failure.record(plan),
+                    None => plan,
+                };
+                let plan = retry.record(plan, nft_retries + iptables_retries);
+                Ok((parse_iptables_save(&stdout), plan))
+            }
+            Err(_) if !firewall_tools_installed() => Err(CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: "Neither nft nor iptables is installed; cannot read the firewall ruleset"
+                    .to_string(),
+            }),
+            Err(e) => {
+                let reason = format!("{}; {}", nft_error, e);
+                if reason.contains("Permission denied")
+                    || reason.contains("Operation not permitted")
+                {
+                    Err(CollectionError::AccessDenied {
+                        object_id: object_id.to_string(),
+                        reason,
+                    })
+                } else {
+                    Err(CollectionError::CollectionFailed {
+                        object_id: object_id.to_string(),
+                        reason,
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl CtnDataCollector for FirewallRuleCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        // Validate contract compatibility
+        self.validate_ctn_compatibility(contract)?;
+
+        // Extract object fields (all optional)
+        let port = self.extract_port(object)?;
+        let protocol = self.extract_protocol(object)?;
+        let chain = self.extract_string(object, "chain")?;
+        let match_contains = self.extract_string(object, "match_contains")?;
+        let retry = self.retry_policy(hints, &object.identifier)?;
+
+        let (ruleset, plan) = self.load_ruleset(&object.identifier, &retry)?;
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "firewall_rule".to_string(),
+            self.id.clone(),
+        );
+        data.set_method(plan.into_method());
+
+        data.add_field(
+            "backend".to_string(),
+            ResolvedValue::String(ruleset.backend.as_str().to_string()),
+        );
+        data.add_field(
+            "default_input_policy".to_string(),
+            ResolvedValue::String(ruleset.default_policy("input")),
+        );
+        data.add_field(
+            "default_forward_policy".to_string(),
+            ResolvedValue::String(ruleset.default_policy("forward")),
+        );
+        if let Some(policy) = ruleset.chain_policy(chain.as_deref().unwrap_or("input")) {
+            data.add_field("default_policy".to_string(), ResolvedValue::String(policy));
+        }
+
+        let rule_texts = match &chain {
+            Some(chain) => ruleset
+                .chain_rules(chain)
+                .into_iter()
+                .map(|rule| rule.to_text())
+                .collect(),
+            None => ruleset.rule_texts(),
+        };
+        data.add_field(
+            "rule_count".to_string(),
+            ResolvedValue::Integer(rule_texts.len() as i64),
+        );
+        if let Some(needle) = &match_contains {
+            let needle = needle.to_lowercase();
+            data.add_field(
+                "has_rule".to_string(),
+                ResolvedValue::Boolean(
+                    rule_texts
+                        .iter()
+                        .any(|text| text.to_lowercase().contains(&needle)),
+                ),
+            );
+        }
+        data.add_field(
+            "rules".to_string(),
+            ResolvedValue::Collection(rule_texts.into_iter().map(ResolvedValue::String).collect()),
+        );
+
+        // Port exposure only makes sense when the object names a port
+        if let Some(p) = port {
+            data.add_field(
+                "allows_from_anywhere".to_string(),
+                ResolvedValue::Boolean(ruleset.allows_from_anywhere(p, &protocol)),
+            );
+        }
+
+        let json =
+            serde_json::to_value(&ruleset).map_err(|e| CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: format!("Failed to serialize ruleset: {}", e),
+            })?;
+        data.add_field(
+            "ruleset".to_string(),
+            ResolvedValue::RecordData(Box::new(RecordData::from_json_value(json))),
+        );
+
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["firewall_rule".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != "firewall_rule" {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!(
+                    "Incompatible CTN type: expected 'firewall_rule', got '{}'",
+                    contract.ctn_type
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
+
+impl DescribeCollection for FirewallRuleCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+
+        let retry = self.retry_policy(hints, &object.identifier)?;
+        let plan = self
+            .elevated(nft_plan())
+            .with_input("fallback", iptables_command_line());
+        let mut plan = retry.describe(plan);
+        if let Some(chain) = self.extract_string(object, "chain")? {
+            plan = plan.with_input("chain", chain);
+        }
+        if let Some(needle) = self.extract_string(object, "match_contains")? {
+            plan = plan.with_input("match_contains", needle);
+        }
+        if let Some(port) = self.extract_port(object)? {
+            plan = plan
+                .with_input("port", port.to_string())
+                .with_input("protocol", self.extract_protocol(object)?);
+        }
+        Ok(plan)
+    }
+
+    /// `iptables` and `nft` contend for the same ruleset lock
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::serial()
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plans_match_invocations() {
+        // The planned commands are exactly what load_ruleset executes
+        let nft = nft_plan();
+        let expected = format!("{} {}", find_nft(), NFT_LIST_ARGS.join(" "));
+        assert_eq!(nft.command.as_deref(), Some(expected.as_str()));
+        assert_eq!(nft.inputs["backend"], "nftables");
+
+        let iptables = iptables_plan();
+        assert_eq!(
+            iptables.command.as_deref(),
+            Some(iptables_command_line().as_str())
+        );
+        assert_eq!(iptables.inputs["backend"], "iptables");
+    }
+}
@@ -0,0 +1,274 @@
+//! Glob Objects
+//!
+//! Carries the matches of a glob file path from the collector to the
+//! executor. The engine collects one `CollectedData` per object, so the
+//! collector packs the data of every matched file into the object's data
+//! (`pack_glob_matches`), and the executor unpacks it into one entry per
+//! matched path (`expand_glob_objects`) before its existence check. A glob
+//! object then counts as one object per match, like a multi-object
+//! criterion; a glob that matches nothing contributes no objects.
+
+use execution_engine::strategies::{CollectedData, CtnContract};
+use execution_engine::types::common::ResolvedValue;
+use std::collections::HashMap;
+
+use super::error_reporting::COLLECTION_ERROR_FIELDS;
+use super::planning::{CollectionKind, CollectionPlan};
+
+/// Pattern the object path was expanded from
+pub const GLOB_PATTERN_FIELD: &str = "glob_pattern";
+
+/// Matched paths, in order
+pub const GLOB_MATCHES_FIELD: &str = "glob_matches";
+
+const GLOB_COLLECTOR_FIELD: &str = "glob_collector";
+
+/// Plan for expanding a glob object path
+pub fn glob_plan(pattern: &str) -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::FileStat,
+        "Expand glob and query metadata of each match",
+        pattern,
+    )
+    .with_input("glob", "true")
+}
+
+/// Plan recorded on the data of one match
+fn match_plan(pattern: &str, path: &str) -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::FileStat,
+        "Query metadata of a glob match",
+        path,
+    )
+    .with_input("glob", pattern)
+}
+
+/// Key of `field` of the match at `index` in packed data
+fn match_field(index: usize, field: &str) -> String {
+    format!("glob_match.{}.{}", index, field)
+}
+
+/// Fields copied for each match: the contract's data fields and error markers
+fn packed_fields(contract: &CtnContract) -> Vec<String> {
+    let mappings = &contract.field_mappings.collection_mappings;
+    mappings
+        .required_data_fields
+        .iter()
+        .chain(&mappings.optional_data_fields)
+        .cloned()
+        .chain(COLLECTION_ERROR_FIELDS.iter().map(|f| f.to_string()))
+        .collect()
+}
+
+/// Pack the data of every match into the data of the glob object
+///
+/// `matches` holds each matched path with its collected data, which may
+/// be a `CollectionErrorRecord` for a file that could not be read.
+pub fn pack_glob_matches(
+    object_id: &str,
+    pattern: &str,
+    collector_id: &str,
+    contract: &CtnContract,
+    matches: Vec<(String, CollectedData)>,
+) -> CollectedData {
+    let mut data = CollectedData::new(
+        object_id.to_string(),
+        contract.ctn_type.clone(),
+        collector_id.to_string(),
+    );
+    data.set_method(glob_plan(pattern).into_method());
+    data.add_field(
+        GLOB_PATTERN_FIELD.to_string(),
+        ResolvedValue::String(pattern.to_string()),
+    );
+    data.add_field(
+        GLOB_COLLECTOR_FIELD.to_string(),
+        ResolvedValue::String(collector_id.to_string()),
+    );
+
+    let fields = packed_fields(contract);
+    let mut paths = Vec::with_capacity(matches.len());
+    for (index, (path, match_data)) in matches.into_iter().enumerate() {
+        for field in &fields {
+            if let Some(value) = match_data.get_field(field) {
+                data.add_field(match_field(index, field), value.clone());
+            }
+        }
+        paths.push(ResolvedValue::String(path));
+    }
+    data.add_field(
+        GLOB_MATCHES_FIELD.to_string(),
+        ResolvedValue::Collection(paths),
+    );
+    data
+}
+
+/// Whether collected data is a packed glob object
+pub fn is_glob_object(data: &CollectedData) -> bool {
+    data.has_field(GLOB_MATCHES_FIELD)
+}
+
+/// Collected data with glob objects replaced by their matches
+pub struct ExpandedObjects {
+    /// One entry per object, keyed by the matched path for glob matches
+    pub collected_data: HashMap<String, CollectedData>,
+    /// Objects beyond one per glob object
+    extra_objects: usize,
+}
+
+impl ExpandedObjects {
+    /// Objects the existence check expects, given the criterion's count
+    ///
+    /// A glob object with matches expects each of them; one without still
+    /// expects one object, so `all` and `at_least_one` existence checks fail.
+    pub fn expected_objects(&self, declared: usize) -> usize {
+        declared + self.extra_objects
+    }
+}
+
+/// Replace each glob object with one entry per matched path
+pub fn expand_glob_objects(
+    collected_data: HashMap<String, CollectedData>,
+    contract: &CtnContract,
+) -> ExpandedObjects {
+    let fields = packed_fields(contract);
+    let mut expanded = HashMap::with_capacity(collected_data.len());
+    let mut extra_objects = 0;
+
+    for (object_id, data) in collected_data {
+        let paths = match data.get_field(GLOB_MATCHES_FIELD) {
+            Some(ResolvedValue::Collection(paths)) => paths.clone(),
+            _ => {
+                expanded.insert(object_id, data);
+                continue;
+            }
+        };
+        let string_field = |name: &str| match data.get_field(name) {
+            Some(ResolvedValue::String(s)) => s.clone(),
+            _ => String::new(),
+        };
+        let pattern = string_field(GLOB_PATTERN_FIELD);
+        let collector_id = string_field(GLOB_COLLECTOR_FIELD);
+
+        extra_objects += paths.len().saturating_sub(1);
+        for (index, path) in paths.iter().enumerate() {
+            let ResolvedValue::String(path) = path else {
+                continue;
+            };
+            let mut match_data = CollectedData::new(
+                path.clone(),
+                contract.ctn_type.clone(),
+                collector_id.clone(),
+            );
+            match_data.set_method(match_plan(&pattern, path).into_method());
+            for field in &fields {
+                if let Some(value) = data.get_field(&match_field(index, field)) {
+                    match_data.add_field(field.clone(), value.clone());
+                }
+            }
+            expanded.insert(path.clone(), match_data);
+        }
+    }
+
+    ExpandedObjects {
+        collected_data: expanded,
+        extra_objects,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::error_reporting::CollectionErrorRecord;
+    use crate::contracts::file_contracts::create_file_metadata_contract;
+    use execution_engine::strategies::CollectionError;
+
+    fn file_data(path: &str, mode: &str) -> CollectedData {
+        let mut data = CollectedData::new(
+            path.to_string(),
+            "file_metadata".to_string(),
+            "filesystem_collector".to_string(),
+        );
+        data.add_field("exists".to_string(), ResolvedValue::Boolean(true));
+        data.add_field(
+            "file_mode".to_string(),
+            ResolvedValue::String(mode.to_string()),
+        );
+        data
+    }
+
+    #[test]
+    fn test_pack_and_expand_glob_matches() {
+        let contract = create_file_metadata_contract();
+        let denied = CollectionErrorRecord::from_error(
+            &CollectionError::AccessDenied {
+                object_id: "/etc/cron.d/secret".to_string(),
+                reason: "Permission denied".to_string(),
+            },
+            "filesystem_collector",
+        )
+        .map(|record| record.to_collected_data("file_metadata"))
+        .expect("access denied is a runtime error");
+
+        let packed = pack_glob_matches(
+            "cron_files",
+            "/etc/cron.d/*",
+            "filesystem_collector",
+            &contract,
+            vec![
+                (
+                    "/etc/cron.d/a".to_string(),
+                    file_data("/etc/cron.d/a", "0644"),
+                ),
+                (
+                    "/etc/cron.d/b".to_string(),
+                    file_data("/etc/cron.d/b", "0600"),
+                ),
+                ("/etc/cron.d/secret".to_string(), denied),
+            ],
+        );
+        assert!(is_glob_object(&packed));
+
+        let mut collected = HashMap::new();
+        collected.insert("cron_files".to_string(), packed);
+        collected.insert("passwd".to_string(), file_data("/etc/passwd", "0644"));
+
+        let expanded = expand_glob_objects(collected, &contract);
+        assert_eq!(expanded.expected_objects(2), 4);
+        assert_eq!(expanded.collected_data.len(), 4);
+        assert!(expanded.collected_data.contains_key("passwd"));
+
+        let b = expanded.collected_data.get("/etc/cron.d/b").expect("match");
+        assert!(!is_glob_object(b));
+        assert!(matches!(
+            b.get_field("file_mode"),
+            Some(ResolvedValue::String(mode)) if mode == "0600"
+        ));
+
+        let secret = expanded
+            .collected_data
+            .get("/etc/cron.d/secret")
+            .expect("match");
+        let record = CollectionErrorRecord::from_collected_data(secret).expect("error record");
+        assert_eq!(record.object_id, "/etc/cron.d/secret");
+    }
+
+    #[test]
+    fn test_glob_without_matches_has_no_objects() {
+        let contract = create_file_metadata_contract();
+        let packed = pack_glob_matches(
+            "none",
+            "/nonexistent/*",
+            "filesystem_collector",
+            &contract,
+            Vec::new(),
+        );
+
+        let mut collected = HashMap::new();
+        collected.insert("none".to_string(), packed);
+
+        let expanded = expand_glob_objects(collected, &contract);
+        assert!(expanded.collected_data.is_empty());
+        assert_eq!(expanded.expected_objects(1), 1);
+    }
+}
@@ -1,21 +1,109 @@
 //! Kubernetes Resource Collector
 //!
 //! Collects Kubernetes resources via kubectl and returns as RecordData.
+//!
+//! Transient kubectl failures (API server unavailable, timeouts, DNS) can be
+//! retried with backoff through the `retry` or `retries` behaviors or a
+//! collector-wide default; see [`RetryPolicy`].
+//!
+//! ## Batch Collection
+//!
+//! `collect_batch` lists each `(kind, namespace, label_selector)` group once
+//! and selects every object's resources from the list in memory, by `name`
+//! or `name_prefix`, instead of running kubectl per object. Objects with a
+//! label selector are never grouped with objects without one (or with a
+//! different one), and a namespace stays in the query, so a namespaced Role
+//! still suffices. Batched calls use the collector-wide retry policy and the
+//! executor's timeout.
 
-use common::results::{CollectionMethod, CollectionMethodType};
 use execution_engine::execution::BehaviorHints;
 use execution_engine::strategies::{
     CollectedData, CollectionError, CtnContract, CtnDataCollector, SystemCommandExecutor,
 };
 use execution_engine::types::common::{RecordData, ResolvedValue};
 use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+use std::collections::HashMap;
 use std::time::Duration;
 
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::command_output::{run_command, CommandError, CommandFailure};
+use crate::commands::retry::{is_transient_failure, RetryPolicy};
+
+/// Why a single kubectl attempt failed
+#[derive(Debug)]
+enum KubectlFailure {
+    /// kubectl could not be run (not allowed, timed out)
+    Execute(String),
+    /// kubectl exited with a non-zero status
+    Exit(CommandFailure),
+    /// kubectl printed something other than JSON
+    Parse(String),
+}
+
+impl KubectlFailure {
+    /// Whether retrying may succeed
+    fn is_transient(&self) -> bool {
+        match self {
+            KubectlFailure::Execute(reason) => is_transient_failure(reason),
+            KubectlFailure::Exit(failure) => is_transient_failure(&failure.stderr),
+            KubectlFailure::Parse(_) => false,
+        }
+    }
+
+    fn into_collection_error(self, retries: u32) -> CollectionError {
+        let reason = match self {
+            KubectlFailure::Execute(reason) => format!("Failed to execute kubectl: {}", reason),
+            KubectlFailure::Exit(failure) => failure.to_string(),
+            KubectlFailure::Parse(reason) => {
+                format!("Failed to parse kubectl JSON output: {}", reason)
+            }
+        };
+        let reason = match retries {
+            0 => reason,
+            n => format!("{} (after {} retries)", reason, n),
+        };
+        CollectionError::CollectionFailed {
+            object_id: "kubectl".to_string(),
+            reason,
+        }
+    }
+}
+
+/// Object fields that select Kubernetes resources
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ResourceQuery {
+    kind: String,
+    namespace: Option<String>,
+    name: Option<String>,
+    name_prefix: Option<String>,
+    label_selector: Option<String>,
+}
+
+impl ResourceQuery {
+    /// Queries with the same key are answered by one list call
+    fn batch_key(&self) -> (String, Option<String>, Option<String>) {
+        (
+            self.kind.to_lowercase(),
+            self.namespace.clone(),
+            self.label_selector.clone(),
+        )
+    }
+}
+
+/// One kubectl list call and the queries it answers
+#[derive(Debug)]
+struct KubectlBatch<T> {
+    args: Vec<String>,
+    members: Vec<(ResourceQuery, T)>,
+}
+
 /// Collector for Kubernetes resources via kubectl
 #[derive(Clone)]
 pub struct K8sResourceCollector {
     id: String,
     executor: SystemCommandExecutor,
+    retry: RetryPolicy,
 }
 
 impl K8sResourceCollector {
@@ -24,9 +112,30 @@ impl K8sResourceCollector {
         Self {
             id: id.into(),
             executor,
+            retry: RetryPolicy::none(),
         }
     }
 
+    /// Retry policy for objects without retry behaviors
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Retry policy for an object, from its behaviors or the collector default
+    fn retry_policy(
+        &self,
+        hints: &BehaviorHints,
+        object_id: &str,
+    ) -> Result<RetryPolicy, CollectionError> {
+        RetryPolicy::from_hints(hints, self.retry).map_err(|reason| {
+            CollectionError::InvalidObjectConfiguration {
+                object_id: object_id.to_string(),
+                reason,
+            }
+        })
+    }
+
     /// Extract required 'kind' field from object
     fn extract_kind(&self, object: &ExecutableObject) -> Result<String, CollectionError> {
         self.extract_string_field(object, "kind")?.ok_or_else(|| {
@@ -161,43 +270,146 @@ impl K8sResourceCollector {
         format!("{} {}", kubectl_path, args.join(" "))
     }
 
-    /// Execute kubectl and parse response
+    /// Extract the resource selection fields from an object
+    fn extract_query(&self, object: &ExecutableObject) -> Result<ResourceQuery, CollectionError> {
+        Ok(ResourceQuery {
+            kind: self.extract_kind(object)?,
+            namespace: self.extract_string_field(object, "namespace")?,
+            name: self.extract_string_field(object, "name")?,
+            name_prefix: self.extract_string_field(object, "name_prefix")?,
+            label_selector: self.extract_string_field(object, "label_selector")?,
+        })
+    }
+
+    /// Build the kubectl arguments and matching plan for an object
+    fn plan_query(
+        &self,
+        object: &ExecutableObject,
+    ) -> Result<(Vec<String>, CollectionPlan), CollectionError> {
+        let query = self.extract_query(object)?;
+        let args = self.build_kubectl_args(
+            &query.kind,
+            query.namespace.as_deref(),
+            query.name.as_deref(),
+            query.label_selector.as_deref(),
+        );
+        let plan = self.query_plan(&query, &args);
+        Ok((args, plan))
+    }
+
+    /// Plan for a query answered by running kubectl with `args`
+    fn query_plan(&self, query: &ResourceQuery, args: &[String]) -> CollectionPlan {
+        // Build target string for traceability
+        let target = format!(
+            "{}{}{}",
+            query.kind,
+            query
+                .namespace
+                .as_ref()
+                .map(|n| format!(":{}", n))
+                .unwrap_or_default(),
+            query
+                .label_selector
+                .as_ref()
+                .map(|l| format!(":{}", l))
+                .unwrap_or_default()
+        );
+
+        let mut plan = CollectionPlan::new(
+            CollectionKind::Command,
+            "Query Kubernetes API for resources",
+            target,
+        )
+        .with_command(self.build_command_string(args))
+        .with_input("kind", &query.kind);
+
+        if let Some(ns) = &query.namespace {
+            plan = plan.with_input("namespace", ns);
+        }
+        if let Some(n) = &query.name {
+            plan = plan.with_input("name", n);
+        }
+        if let Some(prefix) = &query.name_prefix {
+            plan = plan.with_input("name_prefix", prefix);
+        }
+        if let Some(selector) = &query.label_selector {
+            plan = plan.with_input("label_selector", selector);
+        }
+
+        plan
+    }
+
+    /// Group queries into one kubectl list call per batch key
+    ///
+    /// Batches keep the order in which their first query appears.
+    fn plan_batches<T>(&self, members: Vec<(ResourceQuery, T)>) -> Vec<KubectlBatch<T>> {
+        let mut batches: Vec<KubectlBatch<T>> = Vec::new();
+        let mut index = HashMap::new();
+        for (query, member) in members {
+            let i = *index.entry(query.batch_key()).or_insert_with(|| {
+                batches.push(KubectlBatch {
+                    args: self.build_kubectl_args(
+                        &query.kind,
+                        query.namespace.as_deref(),
+                        None,
+                        query.label_selector.as_deref(),
+                    ),
+                    members: Vec::new(),
+                });
+                batches.len() - 1
+            });
+            if let Some(batch) = batches.get_mut(i) {
+                batch.members.push((query, member));
+            }
+        }
+        batches
+    }
+
+    /// Execute kubectl, retrying transient failures, and parse response
+    ///
+    /// Returns the response and the number of retries made.
     fn execute_kubectl(
         &self,
         args: &[String],
         timeout: Option<Duration>,
-    ) -> Result<serde_json::Value, CollectionError> {
+        retry: &RetryPolicy,
+    ) -> Result<(serde_json::Value, u32), CollectionError> {
+        let (result, retries) = retry.run(
+            || self.run_kubectl(args, timeout),
+            KubectlFailure::is_transient,
+        );
+        result
+            .map(|json| (json, retries))
+            .map_err(|failure| failure.into_collection_error(retries))
+    }
+
+    /// Run kubectl once and parse its response
+    fn run_kubectl(
+        &self,
+        args: &[String],
+        timeout: Option<Duration>,
+    ) -> Result<serde_json::Value, KubectlFailure> {
         // Convert args to &str slice
         let args_str: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
 
         let kubectl_path = self.find_kubectl();
-        let output = self
-            .executor
-            .execute(kubectl_path, &args_str, timeout)
-            .map_err(|e| CollectionError::CollectionFailed {
-                object_id: "kubectl".to_string(),
-                reason: format!("Failed to execute kubectl: {}", e),
-            })?;
-
-        if output.exit_code != 0 {
-            // Check for "not found" which is not an error, just empty result
-            if output.stderr.contains("not found") || output.stderr.contains("No resources found") {
-                return Ok(serde_json::json!({"items": []}));
+        let stdout = match run_command(&self.executor, None, kubectl_path, &args_str, timeout) {
+            Ok(stdout) => stdout,
+            Err(CommandError::Execute { reason, .. }) => {
+                return Err(KubectlFailure::Execute(reason))
             }
+            Err(CommandError::Exit(failure)) => {
+                // "not found" is not an error, just an empty result
+                if failure.stderr.contains("not found")
+                    || failure.stderr.contains("No resources found")
+                {
+                    return Ok(serde_json::json!({"items": []}));
+                }
+                return Err(KubectlFailure::Exit(failure));
+            }
+        };
 
-            return Err(CollectionError::CollectionFailed {
-                object_id: "kubectl".to_string(),
-                reason: format!(
-                    "kubectl failed (exit {}): {}",
-                    output.exit_code, output.stderr
-                ),
-            });
-        }
-
-        serde_json::from_str(&output.stdout).map_err(|e| CollectionError::CollectionFailed {
-            object_id: "kubectl".to_string(),
-            reason: format!("Failed to parse kubectl JSON output: {}", e),
-        })
+        serde_json::from_str(&stdout).map_err(|e| KubectlFailure::Parse(e.to_string()))
     }
 
     /// Filter results by name_prefix
@@ -261,6 +473,81 @@ impl K8sResourceCollector {
             0
         }
     }
+
+    /// Select a query's count and resource from its batch's list response
+    ///
+    /// Reports what the query's own kubectl call would: with `name`, the
+    /// resources of that name; otherwise the whole list, with the resource
+    /// chosen by `name_prefix` or the first item.
+    fn select_from_list(
+        &self,
+        query: &ResourceQuery,
+        list: &serde_json::Value,
+    ) -> (i64, Option<serde_json::Value>) {
+        let Some(name) = &query.name else {
+            let resource = match &query.name_prefix {
+                Some(prefix) => self.filter_by_name_prefix(list, prefix),
+                None => self.get_first_resource(list),
+            };
+            return (self.count_resources(list), resource);
+        };
+
+        let named: Vec<&serde_json::Value> = list
+            .get("items")
+            .and_then(|i| i.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter(|item| {
+                        item.get("metadata")
+                            .and_then(|m| m.get("name"))
+                            .and_then(|n| n.as_str())
+                            == Some(name.as_str())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        (named.len() as i64, named.first().map(|r| (*r).clone()))
+    }
+
+    /// Collected data for an object from its count and selected resource
+    fn build_data(
+        &self,
+        object: &ExecutableObject,
+        plan: CollectionPlan,
+        count: i64,
+        resource: Option<serde_json::Value>,
+    ) -> CollectedData {
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "k8s_resource".to_string(),
+            self.id.clone(),
+        );
+
+        // Set collection method for traceability
+        data.set_method(plan.into_method());
+
+        let found = resource.is_some();
+        data.add_field("found".to_string(), ResolvedValue::Boolean(found));
+        data.add_field("count".to_string(), ResolvedValue::Integer(count));
+
+        if let Some(res) = resource {
+            let record_data = RecordData::from_json_value(res);
+            data.add_field(
+                "resource".to_string(),
+                ResolvedValue::RecordData(Box::new(record_data)),
+            );
+        } else {
+            // Return empty record if not found
+            let empty_record = RecordData::from_json_value(serde_json::json!({}));
+            data.add_field(
+                "resource".to_string(),
+                ResolvedValue::RecordData(Box::new(empty_record)),
+            );
+        }
+
+        data
+    }
 }
 
 /// Check if resource kind is cluster-scoped (no namespace)
@@ -281,30 +568,17 @@ impl CtnDataCollector for K8sResourceCollector {
         // Validate contract compatibility
         self.validate_ctn_compatibility(contract)?;
 
-        // Extract object fields
-        let kind = self.extract_kind(object)?;
-        let namespace = self.extract_string_field(object, "namespace")?;
-        let name = self.extract_string_field(object, "name")?;
+        let (args, mut plan) = self.plan_query(object)?;
         let name_prefix = self.extract_string_field(object, "name_prefix")?;
-        let label_selector = self.extract_string_field(object, "label_selector")?;
 
         // Check for timeout hint
         let timeout = hints
             .get_parameter_as_int("timeout")
             .map(|t| Duration::from_secs(t as u64));
 
-        // Build and execute kubectl command
-        let args = self.build_kubectl_args(
-            &kind,
-            namespace.as_deref(),
-            name.as_deref(),
-            label_selector.as_deref(),
-        );
-
-        // Build command string for traceability
-        let command_str = self.build_command_string(&args);
-
-        let json_response = self.execute_kubectl(&args, timeout)?;
+        let retry = self.retry_policy(hints, &object.identifier)?;
+        let (json_response, retries) = self.execute_kubectl(&args, timeout, &retry)?;
+        plan = retry.record(plan, retries);
 
         // Count total resources
         let count = self.count_resources(&json_response);
@@ -316,70 +590,42 @@ impl CtnDataCollector for K8sResourceCollector {
             self.get_first_resource(&json_response)
         };
 
-        // Build collected data
-        let mut data = CollectedData::new(
-            object.identifier.clone(),
-            "k8s_resource".to_string(),
-            self.id.clone(),
-        );
-
-        // Build target string for traceability
-        let target = format!(
-            "{}{}{}",
-            kind,
-            namespace
-                .as_ref()
-                .map(|n| format!(":{}", n))
-                .unwrap_or_default(),
-            label_selector
-                .as_ref()
-                .map(|l| format!(":{}", l))
-                .unwrap_or_default()
-        );
-
-        // Set collection method for traceability
-        let mut method_builder = CollectionMethod::builder()
-            .method_type(CollectionMethodType::Command)
-            .description("Query Kubernetes API for resources")
-            .target(&target)
-            .command(&command_str)
-            .input("kind", &kind);
-
-        if let Some(ref ns) = namespace {
-            method_builder = method_builder.input("namespace", ns);
-        }
-        if let Some(ref n) = name {
-            method_builder = method_builder.input("name", n);
-        }
-        if let Some(ref prefix) = name_prefix {
-            method_builder = method_builder.input("name_prefix", prefix);
-        }
-        if let Some(ref selector) = label_selector {
-            method_builder = method_builder.input("label_selector", selector);
-        }
-
-        data.set_method(method_builder.build());
+        Ok(self.build_data(object, plan, count, resource))
+    }
 
-        let found = resource.is_some();
-        data.add_field("found".to_string(), ResolvedValue::Boolean(found));
-        data.add_field("count".to_string(), ResolvedValue::Integer(count));
+    /// Collect many objects with one kubectl call per batch key
+    ///
+    /// Fails if any object is misconfigured or any list call fails.
+    fn collect_batch(
+        &self,
+        objects: Vec<&ExecutableObject>,
+        contract: &CtnContract,
+    ) -> Result<HashMap<String, CollectedData>, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
 
-        if let Some(res) = resource {
-            let record_data = RecordData::from_json_value(res);
-            data.add_field(
-                "resource".to_string(),
-                ResolvedValue::RecordData(Box::new(record_data)),
-            );
-        } else {
-            // Return empty record if not found
-            let empty_record = RecordData::from_json_value(serde_json::json!({}));
-            data.add_field(
-                "resource".to_string(),
-                ResolvedValue::RecordData(Box::new(empty_record)),
-            );
+        let members = objects
+            .into_iter()
+            .map(|object| Ok((self.extract_query(object)?, object)))
+            .collect::<Result<Vec<_>, CollectionError>>()?;
+
+        let mut results = HashMap::new();
+        for batch in self.plan_batches(members) {
+            let (list, retries) = self.execute_kubectl(&batch.args, None, &self.retry)?;
+            let batch_size = batch.members.len().to_string();
+            for (query, object) in &batch.members {
+                let plan = self
+                    .query_plan(query, &batch.args)
+                    .with_input("batch_size", &batch_size);
+                let plan = self.retry.record(plan, retries);
+                let (count, resource) = self.select_from_list(query, &list);
+                results.insert(
+                    object.identifier.clone(),
+                    self.build_data(object, plan, count, resource),
+                );
+            }
         }
 
-        Ok(data)
+        Ok(results)
     }
 
     fn supported_ctn_types(&self) -> Vec<String> {
@@ -403,10 +649,30 @@ impl CtnDataCollector for K8sResourceCollector {
     }
 
     fn supports_batch_collection(&self) -> bool {
-        false
+        true
+    }
+}
+
+impl DescribeCollection for K8sResourceCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let (_, plan) = self.plan_query(object)?;
+        let retry = self.retry_policy(hints, &object.identifier)?;
+        Ok(retry.describe(plan))
+    }
+
+    /// Every collection is a kubectl call against the API server
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::low()
     }
 }
 
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -457,4 +723,100 @@ mod tests {
         // Should NOT contain --all-namespaces for cluster-scoped
         assert!(!args.contains(&"--all-namespaces".to_string()));
     }
+
+    fn test_collector() -> K8sResourceCollector {
+        let mut executor = SystemCommandExecutor::with_timeout(Duration::from_secs(30));
+        executor.allow_commands(&["kubectl", "/usr/local/bin/kubectl"]);
+        K8sResourceCollector::new("test", executor)
+    }
+
+    fn query(kind: &str, namespace: Option<&str>, label_selector: Option<&str>) -> ResourceQuery {
+        ResourceQuery {
+            kind: kind.to_string(),
+            namespace: namespace.map(str::to_string),
+            name: None,
+            name_prefix: None,
+            label_selector: label_selector.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_same_kind_batches_into_one_invocation() {
+        let collector = test_collector();
+        let named = ResourceQuery {
+            name: Some("kube-apiserver-cp1".to_string()),
+            ..query("Pod", None, None)
+        };
+        let prefixed = ResourceQuery {
+            name_prefix: Some("etcd-".to_string()),
+            ..query("pod", None, None)
+        };
+
+        let batches = collector.plan_batches(vec![(named, "apiserver"), (prefixed, "etcd")]);
+
+        assert_eq!(batches.len(), 1);
+        let ids: Vec<&str> = batches[0].members.iter().map(|(_, id)| *id).collect();
+        assert_eq!(ids, ["apiserver", "etcd"]);
+        // The list call names no resource; names are matched in memory
+        let args = &batches[0].args;
+        assert!(args.contains(&"--all-namespaces".to_string()));
+        assert!(!args.contains(&"kube-apiserver-cp1".to_string()));
+    }
+
+    #[test]
+    fn test_label_selector_groups_are_not_merged() {
+        let collector = test_collector();
+        let batches = collector.plan_batches(vec![
+            (query("Pod", Some("kube-system"), None), "all"),
+            (
+                query("Pod", Some("kube-system"), Some("tier=control-plane")),
+                "selected",
+            ),
+            (query("Pod", Some("default"), None), "default"),
+            (query("Pod", Some("kube-system"), None), "all_again"),
+        ]);
+
+        let groups: Vec<Vec<&str>> = batches
+            .iter()
+            .map(|b| b.members.iter().map(|(_, id)| *id).collect())
+            .collect();
+        assert_eq!(
+            groups,
+            [vec!["all", "all_again"], vec!["selected"], vec!["default"]]
+        );
+        assert!(!batches[0].args.contains(&"-l".to_string()));
+        assert!(batches[1].args.contains(&"tier=control-plane".to_string()));
+    }
+
+    #[test]
+    fn test_select_from_list_matches_single_queries() {
+        let collector = test_collector();
+        let list = serde_json::json!({"items": [
+            {"metadata": {"name": "coredns-abc", "namespace": "kube-system"}},
+            {"metadata": {"name": "etcd-cp1", "namespace": "kube-system"}},
+        ]});
+
+        let named = ResourceQuery {
+            name: Some("etcd-cp1".to_string()),
+            ..query("Pod", None, None)
+        };
+        let (count, resource) = collector.select_from_list(&named, &list);
+        assert_eq!(count, 1);
+        assert_eq!(resource.unwrap()["metadata"]["name"], "etcd-cp1");
+
+        let missing = ResourceQuery {
+            name: Some("etcd".to_string()),
+            ..query("Pod", None, None)
+        };
+        assert_eq!(collector.select_from_list(&missing, &list), (0, None));
+
+        // name_prefix counts the whole list, as its own kubectl call would
+        let prefixed = ResourceQuery {
+            name_prefix: Some("etcd-".to_string()),
+            ..query("Pod", None, None)
+        };
+        let (count, resource) = collector.select_from_list(&prefixed, &list);
+        assert_eq!(count, 2);
+        assert_eq!(resource.unwrap()["metadata"]["name"], "etcd-cp1");
+    }
 }
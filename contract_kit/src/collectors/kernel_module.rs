@@ -0,0 +1,244 @@
+//! Kernel Module Collector
+//!
+//! Collects whether a Linux kernel module is loaded, blacklisted and
+//! loadable for the `kernel_module` CTN type.
+//! - Loaded: `/proc/modules`
+//! - Blacklisted: `blacklist` lines in the modprobe.d configuration
+//! - Loadable: `modprobe -n -v <name>` (a dry run; nothing is loaded)
+//!
+//! A module that does not exist is data (`loaded`, `blacklisted` and
+//! `loadable` all false), not an error. Names are normalized so
+//! `usb-storage` and `usb_storage` collect the same module. With an
+//! [`Elevation`], modprobe runs through its wrapper.
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{
+    CollectedData, CollectionError, CtnContract, CtnDataCollector, SystemCommandExecutor,
+};
+use execution_engine::types::common::ResolvedValue;
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::command_output::{run_command, CommandError};
+use crate::commands::elevation::Elevation;
+use crate::commands::kernel_module::{
+    find_modprobe, is_valid_module_name, normalize_module_name, parse_modprobe_dry_run,
+    parse_modprobe_settings, read_loaded_modules, read_modprobe_config, MODPROBE_CONFIG_DIRS,
+    PROC_MODULES,
+};
+
+/// Plan for checking one module
+fn kernel_module_plan(name: &str) -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::Command,
+        "Dry-run modprobe for the module and read loaded modules and modprobe.d",
+        "kernel_modules",
+    )
+    .with_command(format!("{} -n -v {}", find_modprobe(), name))
+    .with_input("name", name)
+    .with_input("loaded_from", PROC_MODULES)
+    .with_input("config_dirs", MODPROBE_CONFIG_DIRS.join(","))
+}
+
+/// Collector for kernel module status
+#[derive(Clone)]
+pub struct KernelModuleCollector {
+    id: String,
+    executor: SystemCommandExecutor,
+    elevation: Option<Elevation>,
+}
+
+impl KernelModuleCollector {
+    /// Create new collector with the given executor
+    pub fn new(id: impl Into<String>, executor: SystemCommandExecutor) -> Self {
+        Self {
+            id: id.into(),
+            executor,
+            elevation: None,
+        }
+    }
+
+    /// Run commands through `elevation`'s wrapper (None runs them directly)
+    pub fn with_elevation(mut self, elevation: Option<Elevation>) -> Self {
+        self.elevation = elevation;
+        self
+    }
+
+    /// The plan as run, wrapper included
+    fn elevated(&self, plan: CollectionPlan) -> CollectionPlan {
+        match &self.elevation {
+            Some(elevation) => elevation.record(plan),
+            None => plan,
+        }
+    }
+
+    /// Extract the normalized module name from object
+    fn extract_name(&self, object: &ExecutableObject) -> Result<String, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == "name" {
+                    return match value {
+                        ResolvedValue::String(s) if is_valid_module_name(s.trim()) => {
+                            Ok(normalize_module_name(s))
+                        }
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!(
+                                "Field 'name' must be a module name (letters, digits, '_' \
+                                 and '-'), got {:?}",
+                                value
+                            ),
+                        }),
+                    };
+                }
+            }
+        }
+
+        Err(CollectionError::InvalidObjectConfiguration {
+            object_id: object.identifier.clone(),
+            reason: "Missing required field 'name'".to_string(),
+        })
+    }
+
+    /// Map a file read error to a collection error
+    fn read_error(object_id: &str, path: &str, e: std::io::Error) -> CollectionError {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            CollectionError::AccessDenied {
+                object_id: object_id.to_string(),
+                reason: format!("Permission denied reading {}", path),
+            }
+        } else {
+            CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!("Cannot read {}: {}", path, e),
+            }
+        }
+    }
+}
+
+impl DescribeCollection for KernelModuleCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let name = self.extract_name(object)?;
+        Ok(self.elevated(kernel_module_plan(&name)))
+    }
+
+    /// Each check is a small procfs read and one modprobe dry run
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::high()
+    }
+}
+
+impl CtnDataCollector for KernelModuleCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let name = self.extract_name(object)?;
+
+        if !cfg!(target_os = "linux") {
+            return Err(CollectionError::InvalidObjectConfiguration {
+                object_id: object.identifier.clone(),
+                reason: "Kernel module inspection is only supported on Linux".to_string(),
+            });
+        }
+
+        let loaded = read_loaded_modules()
+            .map_err(|e| Self::read_error(&object.identifier, PROC_MODULES, e))?
+            .contains(&name);
+
+        let config = read_modprobe_config(MODPROBE_CONFIG_DIRS)
+            .map_err(|e| Self::read_error(&object.identifier, "modprobe.d", e))?;
+        let settings = parse_modprobe_settings(&config, &name);
+
+        // modprobe exits non-zero for a module it cannot find: not loadable
+        let mut plan = self.elevated(kernel_module_plan(&name));
+        let args = ["-n", "-v", name.as_str()];
+        let loadable = match run_command(
+            &self.executor,
+            self.elevation.as_ref(),
+            find_modprobe(),
+            &args,
+            None,
+        ) {
+            Ok(stdout) => parse_modprobe_dry_run(&stdout),
+            Err(CommandError::Exit(failure)) => {
+                plan = failure.record(plan);
+                false
+            }
+            Err(e @ CommandError::Execute { .. }) => {
+                return Err(CollectionError::CollectionFailed {
+                    object_id: object.identifier.clone(),
+                    reason: e.to_string(),
+                });
+            }
+        };
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "kernel_module".to_string(),
+            self.id.clone(),
+        );
+        data.set_method(plan.into_method());
+
+        data.add_field("loaded".to_string(), ResolvedValue::Boolean(loaded));
+        data.add_field(
+            "blacklisted".to_string(),
+            ResolvedValue::Boolean(settings.blacklisted),
+        );
+        data.add_field("loadable".to_string(), ResolvedValue::Boolean(loadable));
+
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["kernel_module".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != "kernel_module" {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!(
+                    "Incompatible CTN type: expected 'kernel_module', got '{}'",
+                    contract.ctn_type
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kernel_module_plan_matches_invocation() {
+        let plan = kernel_module_plan("usb_storage");
+
+        // The planned command is exactly what collection executes
+        let expected = format!("{} -n -v usb_storage", find_modprobe());
+        assert_eq!(plan.command.as_deref(), Some(expected.as_str()));
+        assert_eq!(plan.kind, CollectionKind::Command);
+        assert_eq!(plan.inputs["name"], "usb_storage");
+        assert_eq!(plan.inputs["loaded_from"], PROC_MODULES);
+    }
+}
@@ -1,11 +1,88 @@
 //! # Data Collectors Module
+//!
+//! Collectors that read the host are only built with the `native` feature.
+//! `computed_values`, `glob_objects` and the wrappers (`caching`,
+//! `concurrency`, `error_reporting`, `planning`) need no OS access and are
+//! always available. `timing` reads the system clock and is native-only.
 
+#[cfg(feature = "native")]
+pub mod audit_rule;
+pub mod caching;
 pub mod computed_values;
+pub mod concurrency;
+#[cfg(feature = "native")]
+pub mod cron;
+#[cfg(feature = "native")]
+pub mod dpkg_package;
+#[cfg(feature = "native")]
+pub mod env_var;
+pub mod error_reporting;
+#[cfg(feature = "native")]
 pub mod filesystem;
+#[cfg(feature = "native")]
+pub mod firewall_rule;
+pub mod glob_objects;
+#[cfg(feature = "native")]
 pub mod k8s_resource;
+#[cfg(feature = "native")]
+pub mod kernel_module;
+#[cfg(feature = "native")]
+pub mod mount;
+#[cfg(feature = "native")]
+pub mod passwd;
+pub mod path_expansion;
+pub mod planning;
+#[cfg(feature = "native")]
+pub mod process;
+#[cfg(feature = "native")]
+pub mod registry;
+#[cfg(feature = "native")]
+pub mod structured_record;
+#[cfg(feature = "native")]
 pub mod tcp_listener;
+#[cfg(feature = "native")]
+pub mod timing;
 
+#[cfg(feature = "native")]
+pub use audit_rule::AuditRuleCollector;
+pub use caching::CachingCollector;
 pub use computed_values::ComputedValuesCollector;
+pub use concurrency::{
+    BoundedCollector, CollectorScheduler, ConcurrencyHint, ScheduledJob, HIGH_CONCURRENCY,
+    LOW_CONCURRENCY,
+};
+#[cfg(feature = "native")]
+pub use cron::CronCollector;
+#[cfg(feature = "native")]
+pub use dpkg_package::DpkgPackageCollector;
+#[cfg(feature = "native")]
+pub use env_var::EnvVarCollector;
+pub use error_reporting::{CollectionErrorRecord, ErrorReportingCollector};
+#[cfg(feature = "native")]
 pub use filesystem::FileSystemCollector;
+#[cfg(feature = "native")]
+pub use firewall_rule::FirewallRuleCollector;
+pub use glob_objects::{expand_glob_objects, is_glob_object, ExpandedObjects};
+#[cfg(feature = "native")]
 pub use k8s_resource::K8sResourceCollector;
+#[cfg(feature = "native")]
+pub use kernel_module::KernelModuleCollector;
+#[cfg(feature = "native")]
+pub use mount::MountCollector;
+#[cfg(feature = "native")]
+pub use passwd::PasswdCollector;
+pub use path_expansion::{PathExpansion, PathExpansionError, DEFAULT_EXPANDABLE_VARS};
+pub use planning::{
+    CollectionKind, CollectionPlan, DescribeCollection, PlanRecorder, PlannedCollection,
+    PlanningCollector,
+};
+#[cfg(feature = "native")]
+pub use process::ProcessCollector;
+#[cfg(feature = "native")]
+pub use registry::RegistryCollector;
+#[cfg(feature = "native")]
+pub use structured_record::StructuredRecordCollector;
+#[cfg(feature = "native")]
 pub use tcp_listener::TcpListenerCollector;
+#[cfg(feature = "native")]
+pub use timing::TimedCollector;
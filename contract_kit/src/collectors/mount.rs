@@ -0,0 +1,186 @@
+//! Mount Point Collector
+//!
+//! Looks up the filesystem mounted at a path in `/proc/mounts` for the
+//! `mount_point` CTN type. A path that is not itself a mount point
+//! (including a directory inside a mount) is collected as `mounted = false`.
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use execution_engine::types::common::ResolvedValue;
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::mount::{find_mount, MountError, PROC_MOUNTS};
+
+/// Collector for mount points
+pub struct MountCollector {
+    id: String,
+}
+
+impl MountCollector {
+    pub fn new() -> Self {
+        Self {
+            id: "mount_collector".to_string(),
+        }
+    }
+
+    /// Extract the mount point path from object
+    fn extract_path(&self, object: &ExecutableObject) -> Result<String, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == "path" {
+                    return match value {
+                        ResolvedValue::String(s) if s.starts_with('/') => Ok(s.clone()),
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!(
+                                "Field 'path' must be an absolute path, got {:?}",
+                                value
+                            ),
+                        }),
+                    };
+                }
+            }
+        }
+
+        Err(CollectionError::InvalidObjectConfiguration {
+            object_id: object.identifier.clone(),
+            reason: "Missing required field 'path'".to_string(),
+        })
+    }
+}
+
+impl Default for MountCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Plan for looking up one mount point
+fn mount_plan(path: &str) -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::FileRead,
+        "Look up mount point in the mount table",
+        PROC_MOUNTS,
+    )
+    .with_input("path", path)
+}
+
+impl DescribeCollection for MountCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let path = self.extract_path(object)?;
+        Ok(mount_plan(&path))
+    }
+
+    /// Each lookup is a single small procfs read
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::high()
+    }
+}
+
+impl CtnDataCollector for MountCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let path = self.extract_path(object)?;
+
+        // A path that is not a mount point is data (mounted = false), not an error
+        let entry = find_mount(&path).map_err(|e| match e {
+            MountError::AccessDenied(_) => CollectionError::AccessDenied {
+                object_id: object.identifier.clone(),
+                reason: e.to_string(),
+            },
+            MountError::Unsupported(reason) => CollectionError::InvalidObjectConfiguration {
+                object_id: object.identifier.clone(),
+                reason,
+            },
+            MountError::ReadFailed(..) => CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: e.to_string(),
+            },
+        })?;
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "mount_point".to_string(),
+            self.id.clone(),
+        );
+        data.set_method(mount_plan(&path).into_method());
+
+        data.add_field(
+            "mounted".to_string(),
+            ResolvedValue::Boolean(entry.is_some()),
+        );
+        if let Some(entry) = entry {
+            data.add_field("device".to_string(), ResolvedValue::String(entry.device));
+            data.add_field("fstype".to_string(), ResolvedValue::String(entry.fstype));
+            data.add_field(
+                "options".to_string(),
+                ResolvedValue::Collection(
+                    entry
+                        .options
+                        .into_iter()
+                        .map(ResolvedValue::String)
+                        .collect(),
+                ),
+            );
+        }
+
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["mount_point".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != "mount_point" {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!(
+                    "Incompatible CTN type: expected 'mount_point', got '{}'",
+                    contract.ctn_type
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_ctn_types() {
+        let collector = MountCollector::new();
+        assert_eq!(collector.collector_id(), "mount_collector");
+        assert_eq!(collector.supported_ctn_types(), vec!["mount_point"]);
+    }
+
+    #[test]
+    fn test_mount_plan() {
+        let plan = mount_plan("/tmp");
+        assert_eq!(plan.kind, CollectionKind::FileRead);
+        assert_eq!(plan.target, "/proc/mounts");
+        assert_eq!(plan.inputs.get("path").map(String::as_str), Some("/tmp"));
+    }
+}
@@ -0,0 +1,225 @@
+//! User Account Collector
+//!
+//! Looks up local accounts for the `user_account` CTN type.
+//! - Linux: Parses `/etc/passwd` and `/etc/group`
+//! - Windows: Local accounts via `NetUserGetInfo` and `NetUserEnum`
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use execution_engine::types::common::ResolvedValue;
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::passwd::{find_user_accounts, PasswdError, UserSelector, PASSWD_FILE};
+
+/// Collector for local user accounts
+pub struct PasswdCollector {
+    id: String,
+}
+
+impl PasswdCollector {
+    pub fn new() -> Self {
+        Self {
+            id: "passwd_collector".to_string(),
+        }
+    }
+
+    /// Extract the account selector (`username` or `uid`) from object
+    fn extract_selector(&self, object: &ExecutableObject) -> Result<UserSelector, CollectionError> {
+        let mut selector = None;
+        for element in &object.elements {
+            let ExecutableObjectElement::Field { name, value, .. } = element else {
+                continue;
+            };
+            let parsed = match (name.as_str(), value) {
+                ("username", ResolvedValue::String(s)) => {
+                    (!s.is_empty() && !s.contains(':')).then(|| UserSelector::Name(s.clone()))
+                }
+                ("uid", ResolvedValue::Integer(i)) => u32::try_from(*i).ok().map(UserSelector::Uid),
+                ("uid", ResolvedValue::String(s)) => s.parse().ok().map(UserSelector::Uid),
+                ("username" | "uid", _) => None,
+                _ => continue,
+            };
+            let Some(parsed) = parsed else {
+                return Err(CollectionError::InvalidObjectConfiguration {
+                    object_id: object.identifier.clone(),
+                    reason: format!("Invalid {}: {:?}", name, value),
+                });
+            };
+            if selector.replace(parsed).is_some() {
+                return Err(CollectionError::InvalidObjectConfiguration {
+                    object_id: object.identifier.clone(),
+                    reason: "Object needs exactly one of 'username' or 'uid'".to_string(),
+                });
+            }
+        }
+
+        selector.ok_or_else(|| CollectionError::InvalidObjectConfiguration {
+            object_id: object.identifier.clone(),
+            reason: "Object needs 'username' or 'uid'".to_string(),
+        })
+    }
+}
+
+impl Default for PasswdCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Plan for looking up the accounts one selector matches
+///
+/// Windows local accounts are stored in the SAM registry hive, so the
+/// NetAPI lookup is a registry query.
+fn passwd_plan(selector: &UserSelector) -> CollectionPlan {
+    let plan = if cfg!(windows) {
+        CollectionPlan::new(
+            CollectionKind::RegistryQuery,
+            "Look up local accounts via NetUserGetInfo / NetUserEnum",
+            "local_accounts",
+        )
+    } else {
+        CollectionPlan::new(
+            CollectionKind::FileRead,
+            "Parse /etc/passwd and /etc/group",
+            PASSWD_FILE,
+        )
+    };
+
+    match selector {
+        UserSelector::Name(name) => plan.with_input("username", name.as_str()),
+        UserSelector::Uid(uid) => plan.with_input("uid", uid.to_string()),
+    }
+}
+
+impl DescribeCollection for PasswdCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let selector = self.extract_selector(object)?;
+        Ok(passwd_plan(&selector))
+    }
+
+    /// Each lookup reads two small files or makes one NetAPI call
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::high()
+    }
+}
+
+impl CtnDataCollector for PasswdCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let selector = self.extract_selector(object)?;
+
+        // A missing account is data (exists = false), not an error
+        let accounts = find_user_accounts(&selector).map_err(|e| match e {
+            PasswdError::AccessDenied(_) => CollectionError::AccessDenied {
+                object_id: object.identifier.clone(),
+                reason: e.to_string(),
+            },
+            PasswdError::ReadFailed(..) => CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: e.to_string(),
+            },
+        })?;
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "user_account".to_string(),
+            self.id.clone(),
+        );
+        data.set_method(passwd_plan(&selector).into_method());
+
+        data.add_field(
+            "exists".to_string(),
+            ResolvedValue::Boolean(!accounts.is_empty()),
+        );
+        data.add_field(
+            "count".to_string(),
+            ResolvedValue::Integer(accounts.len() as i64),
+        );
+
+        // Duplicates resolve to the first account, as getpwnam does
+        if let Some(account) = accounts.into_iter().next() {
+            data.add_field(
+                "is_system".to_string(),
+                ResolvedValue::Boolean(account.is_system()),
+            );
+            data.add_field(
+                "username".to_string(),
+                ResolvedValue::String(account.username),
+            );
+            data.add_field(
+                "uid".to_string(),
+                ResolvedValue::Integer(i64::from(account.uid)),
+            );
+            data.add_field(
+                "gid".to_string(),
+                ResolvedValue::Integer(i64::from(account.gid)),
+            );
+            data.add_field("home".to_string(), ResolvedValue::String(account.home));
+            if let Some(shell) = account.shell {
+                data.add_field("shell".to_string(), ResolvedValue::String(shell));
+            }
+            if let Some(group) = account.group {
+                data.add_field("group".to_string(), ResolvedValue::String(group));
+            }
+        }
+
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["user_account".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != "user_account" {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!(
+                    "Incompatible CTN type: expected 'user_account', got '{}'",
+                    contract.ctn_type
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_ctn_types() {
+        let collector = PasswdCollector::new();
+        assert_eq!(collector.collector_id(), "passwd_collector");
+        assert_eq!(collector.supported_ctn_types(), vec!["user_account"]);
+    }
+
+    #[test]
+    fn test_passwd_plan() {
+        let plan = passwd_plan(&UserSelector::Uid(0));
+        assert_eq!(plan.inputs.get("uid").map(String::as_str), Some("0"));
+        assert!(!plan.inputs.contains_key("username"));
+        assert!(plan.command.is_none());
+    }
+}
@@ -0,0 +1,277 @@
+//! Path Environment Expansion
+//!
+//! Expands host environment variables in object paths, so one policy can
+//! name `%ProgramData%\MyApp\config.ini` or `${HOME}/.ssh/config` on every
+//! host. This is separate from ESP `VAR` resolution, which is internal to
+//! the policy and happens before collection.
+//!
+//! Only allowlisted variables expand. A reference to any other variable, or
+//! to an allowlisted variable that is unset or empty, is an error rather
+//! than an empty string.
+//!
+//! ## Syntax
+//!
+//! | Form | Meaning |
+//! |------|---------|
+//! | `${NAME}` | Variable reference |
+//! | `%NAME%` | Variable reference (Windows style) |
+//! | `%%` | Literal `%` |
+//! | `%` not forming `%NAME%` | Literal `%` (e.g. `C:\Reports\100%\`) |
+//! | `$` not followed by `{` | Literal `$` |
+//!
+//! `NAME` is ASCII letters, digits and `_`. Names are case-insensitive on
+//! Windows, matching the platform's environment.
+
+/// Variables expandable by default
+pub const DEFAULT_EXPANDABLE_VARS: [&str; 3] = ["HOME", "ProgramData", "SystemRoot"];
+
+/// Expands allowlisted environment variables in paths
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathExpansion {
+    allowed: Vec<String>,
+}
+
+impl Default for PathExpansion {
+    fn default() -> Self {
+        Self::with_allowed(DEFAULT_EXPANDABLE_VARS)
+    }
+}
+
+impl PathExpansion {
+    /// Expansion with the default allowlist (`HOME`, `ProgramData`, `SystemRoot`)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expansion with exactly these variables allowed
+    pub fn with_allowed<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            allowed: names.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Allow one more variable
+    pub fn allow(mut self, name: impl Into<String>) -> Self {
+        self.allowed.push(name.into());
+        self
+    }
+
+    /// Allowed variable names
+    pub fn allowed(&self) -> &[String] {
+        &self.allowed
+    }
+
+    /// Expand variables in `path` from the process environment
+    pub fn expand(&self, path: &str) -> Result<String, PathExpansionError> {
+        self.expand_with(path, |name| std::env::var(name).ok())
+    }
+
+    /// Expand variables in `path`, looking values up with `lookup`
+    fn expand_with(
+        &self,
+        path: &str,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<String, PathExpansionError> {
+        let mut out = String::with_capacity(path.len());
+        let mut rest = path;
+
+        while let Some(pos) = rest.find(['$', '%']) {
+            let (literal, tail) = rest.split_at(pos);
+            out.push_str(literal);
+
+            if let Some(after) = tail.strip_prefix("${") {
+                let end = after
+                    .find('}')
+                    .ok_or_else(|| PathExpansionError::Unterminated(path.to_string()))?;
+                let (name, remainder) = after.split_at(end);
+                if !is_var_name(name) {
+                    return Err(PathExpansionError::InvalidName(name.to_string()));
+                }
+                out.push_str(&self.value(name, &lookup)?);
+                rest = remainder.get(1..).unwrap_or_default();
+            } else if let Some(after) = tail.strip_prefix("%%") {
+                out.push('%');
+                rest = after;
+            } else if let Some(after) = tail.strip_prefix('%') {
+                match after.find('%').map(|end| after.split_at(end)) {
+                    Some((name, remainder)) if is_var_name(name) => {
+                        out.push_str(&self.value(name, &lookup)?);
+                        rest = remainder.get(1..).unwrap_or_default();
+                    }
+                    // Not a reference: keep the percent sign
+                    _ => {
+                        out.push('%');
+                        rest = after;
+                    }
+                }
+            } else {
+                // A `$` that does not start `${`
+                out.push('$');
+                rest = tail.get(1..).unwrap_or_default();
+            }
+        }
+
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    /// Value of an allowlisted, non-empty variable
+    fn value(
+        &self,
+        name: &str,
+        lookup: &impl Fn(&str) -> Option<String>,
+    ) -> Result<String, PathExpansionError> {
+        if !self
+            .allowed
+            .iter()
+            .any(|allowed| names_match(allowed, name))
+        {
+            return Err(PathExpansionError::NotAllowed(name.to_string()));
+        }
+        match lookup(name) {
+            Some(value) if !value.is_empty() => Ok(value),
+            _ => Err(PathExpansionError::Unset(name.to_string())),
+        }
+    }
+}
+
+fn is_var_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn names_match(allowed: &str, name: &str) -> bool {
+    if cfg!(windows) {
+        allowed.eq_ignore_ascii_case(name)
+    } else {
+        allowed == name
+    }
+}
+
+/// Errors from path expansion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathExpansionError {
+    /// Variable is not in the allowlist
+    NotAllowed(String),
+    /// Allowlisted variable is unset or empty
+    Unset(String),
+    /// `${...}` contains an invalid variable name
+    InvalidName(String),
+    /// `${` without a closing `}`
+    Unterminated(String),
+}
+
+impl std::fmt::Display for PathExpansionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAllowed(name) => {
+                write!(f, "environment variable '{}' is not allowed in paths", name)
+            }
+            Self::Unset(name) => write!(f, "environment variable '{}' is not set", name),
+            Self::InvalidName(name) => write!(f, "invalid environment variable name '{}'", name),
+            Self::Unterminated(path) => write!(f, "unterminated '${{' in path '{}'", path),
+        }
+    }
+}
+
+impl std::error::Error for PathExpansionError {}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some("/home/alice".to_string()),
+            "ProgramData" => Some(r"C:\ProgramData".to_string()),
+            "EMPTY" => Some(String::new()),
+            "PATH" => Some("/usr/bin".to_string()),
+            _ => None,
+        }
+    }
+
+    fn expand(expansion: &PathExpansion, path: &str) -> Result<String, PathExpansionError> {
+        expansion.expand_with(path, lookup)
+    }
+
+    #[test]
+    fn test_allowed_variables_expand() {
+        let expansion = PathExpansion::new();
+        assert_eq!(
+            expand(&expansion, "${HOME}/.ssh/config").unwrap(),
+            "/home/alice/.ssh/config"
+        );
+        assert_eq!(
+            expand(&expansion, r"%ProgramData%\MyApp\config.ini").unwrap(),
+            r"C:\ProgramData\MyApp\config.ini"
+        );
+        assert_eq!(expand(&expansion, "/etc/passwd").unwrap(), "/etc/passwd");
+    }
+
+    #[test]
+    fn test_disallowed_and_unset_variables_error() {
+        let expansion = PathExpansion::new();
+        assert_eq!(
+            expand(&expansion, "${PATH}/sh"),
+            Err(PathExpansionError::NotAllowed("PATH".to_string()))
+        );
+        assert_eq!(
+            expand(&expansion, "%USERPROFILE%\\file"),
+            Err(PathExpansionError::NotAllowed("USERPROFILE".to_string()))
+        );
+        assert_eq!(
+            expand(&expansion, r"%SystemRoot%\System32"),
+            Err(PathExpansionError::Unset("SystemRoot".to_string()))
+        );
+
+        let expansion = PathExpansion::with_allowed(["EMPTY"]);
+        assert_eq!(
+            expand(&expansion, "${EMPTY}/etc"),
+            Err(PathExpansionError::Unset("EMPTY".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_literal_percent_in_windows_paths() {
+        let expansion = PathExpansion::new();
+        assert_eq!(
+            expand(&expansion, r"C:\Reports\100%\summary.txt").unwrap(),
+            r"C:\Reports\100%\summary.txt"
+        );
+        assert_eq!(
+            expand(&expansion, r"C:\50% off\%ProgramData%").unwrap(),
+            r"C:\50% off\C:\ProgramData"
+        );
+        assert_eq!(
+            expand(&expansion, r"C:\Reports\100%%done%%").unwrap(),
+            r"C:\Reports\100%done%"
+        );
+    }
+
+    #[test]
+    fn test_dollar_forms() {
+        let expansion = PathExpansion::new();
+        assert_eq!(expand(&expansion, "/srv/$HOME").unwrap(), "/srv/$HOME");
+        assert_eq!(
+            expand(&expansion, "${HOME/x"),
+            Err(PathExpansionError::Unterminated("${HOME/x".to_string()))
+        );
+        assert_eq!(
+            expand(&expansion, "${HO ME}"),
+            Err(PathExpansionError::InvalidName("HO ME".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_allow_extends_defaults() {
+        let expansion = PathExpansion::new().allow("PATH");
+        assert_eq!(expand(&expansion, "${PATH}/sh").unwrap(), "/usr/bin/sh");
+        assert_eq!(expansion.allowed().len(), 4);
+    }
+}
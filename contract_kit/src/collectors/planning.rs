@@ -0,0 +1,270 @@
+//! Collection Planning
+//!
+//! Describes what a collector would read or execute for an object without
+//! touching the system. Collectors build their `CollectionMethod` from the
+//! same `CollectionPlan` they report here, so a plan always matches what
+//! collection would actually do.
+//!
+//! `PlanningCollector` wraps a collector for explain mode: it records the
+//! plan for each object and returns a `collection_error` record instead of
+//! collecting.
+
+use common::results::{CollectionMethod, CollectionMethodType};
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use execution_engine::types::execution_context::ExecutableObject;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use super::concurrency::ConcurrencyHint;
+use super::error_reporting::{CollectionErrorKind, CollectionErrorRecord};
+
+/// Kind of access a collection performs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionKind {
+    /// Run an external command
+    Command,
+    /// Read file contents
+    FileRead,
+    /// Query file metadata
+    FileStat,
+    /// Inspect socket tables
+    SocketInspection,
+    /// Read a Windows registry value
+    RegistryQuery,
+    /// Enumerate the process table
+    ProcessInspection,
+    /// No system access (derived values)
+    Computed,
+}
+
+impl CollectionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Command => "command",
+            Self::FileRead => "file_read",
+            Self::FileStat => "file_stat",
+            Self::SocketInspection => "socket_inspection",
+            Self::RegistryQuery => "registry_query",
+            Self::ProcessInspection => "process_inspection",
+            Self::Computed => "computed",
+        }
+    }
+}
+
+/// How a collector would collect one object
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CollectionPlan {
+    pub kind: CollectionKind,
+    pub description: String,
+    /// File path, directory, or logical target
+    pub target: String,
+    /// Full command line, for command collections
+    pub command: Option<String>,
+    /// Resolved object fields and options
+    pub inputs: BTreeMap<String, String>,
+}
+
+impl CollectionPlan {
+    pub fn new(
+        kind: CollectionKind,
+        description: impl Into<String>,
+        target: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            description: description.into(),
+            target: target.into(),
+            command: None,
+            inputs: BTreeMap::new(),
+        }
+    }
+
+    /// Set the command line that would run
+    pub fn with_command(mut self, command: impl Into<String>) -> Self {
+        self.command = Some(command.into());
+        self
+    }
+
+    /// Add a resolved input
+    pub fn with_input(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.inputs.insert(name.into(), value.into());
+        self
+    }
+
+    /// Build the `CollectionMethod` recorded on collected data
+    pub fn into_method(self) -> CollectionMethod {
+        let method_type = match self.kind {
+            CollectionKind::Command => CollectionMethodType::Command,
+            CollectionKind::FileRead => CollectionMethodType::FileRead,
+            CollectionKind::FileStat => CollectionMethodType::FileStat,
+            CollectionKind::SocketInspection => CollectionMethodType::SocketInspection,
+            CollectionKind::RegistryQuery => CollectionMethodType::RegistryQuery,
+            CollectionKind::ProcessInspection => CollectionMethodType::ProcessInspection,
+            CollectionKind::Computed => {
+                return CollectionMethod::computed().with_description(self.description);
+            }
+        };
+
+        let mut builder = CollectionMethod::builder()
+            .method_type(method_type)
+            .description(self.description)
+            .target(self.target);
+        if let Some(command) = self.command {
+            builder = builder.command(command);
+        }
+        for (name, value) in self.inputs {
+            builder = builder.input(name, value);
+        }
+        builder.build()
+    }
+}
+
+/// A planned collection for one object
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedCollection {
+    pub collector_id: String,
+    pub ctn_type: String,
+    pub object_id: String,
+    pub plan: CollectionPlan,
+}
+
+/// Collectors that can describe a collection without performing it
+pub trait DescribeCollection: CtnDataCollector {
+    /// Describe how `object` would be collected, without system access
+    ///
+    /// Returns the same configuration errors collection would.
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError>;
+
+    /// How many collections may run at once (one at a time by default)
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::serial()
+    }
+}
+
+/// Shared sink for planned collections
+#[derive(Clone, Default)]
+pub struct PlanRecorder {
+    plans: Arc<Mutex<Vec<PlannedCollection>>>,
+}
+
+impl PlanRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&self, planned: PlannedCollection) {
+        if let Ok(mut plans) = self.plans.lock() {
+            plans.push(planned);
+        }
+    }
+
+    /// Take all recorded plans, leaving the recorder empty
+    pub fn take(&self) -> Vec<PlannedCollection> {
+        self.plans
+            .lock()
+            .map(|mut plans| std::mem::take(&mut *plans))
+            .unwrap_or_default()
+    }
+}
+
+/// Collector wrapper that records plans instead of collecting
+pub struct PlanningCollector<C> {
+    inner: C,
+    recorder: PlanRecorder,
+}
+
+impl<C: DescribeCollection> PlanningCollector<C> {
+    pub fn new(inner: C, recorder: PlanRecorder) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+impl<C: DescribeCollection> CtnDataCollector for PlanningCollector<C> {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        let plan = self.inner.describe(object, contract, hints)?;
+
+        self.recorder.record(PlannedCollection {
+            collector_id: self.inner.collector_id().to_string(),
+            ctn_type: contract.ctn_type.clone(),
+            object_id: object.identifier.clone(),
+            plan: plan.clone(),
+        });
+
+        // Report as not collected so executors skip validation
+        let record = CollectionErrorRecord {
+            kind: CollectionErrorKind::CollectionFailed,
+            collector_id: self.inner.collector_id().to_string(),
+            object_id: object.identifier.clone(),
+            reason: "Not collected (explain mode)".to_string(),
+        };
+        let mut data = record.to_collected_data(&contract.ctn_type);
+        data.set_method(plan.into_method());
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        self.inner.supported_ctn_types()
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        self.inner.validate_ctn_compatibility(contract)
+    }
+
+    fn collector_id(&self) -> &str {
+        self.inner.collector_id()
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_builder() {
+        let plan = CollectionPlan::new(CollectionKind::Command, "List rules", "audit_rules")
+            .with_command("/sbin/auditctl -l")
+            .with_input("key", "identity");
+
+        assert_eq!(plan.command.as_deref(), Some("/sbin/auditctl -l"));
+        assert_eq!(plan.inputs["key"], "identity");
+
+        let json = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["kind"], "command");
+        assert_eq!(json["target"], "audit_rules");
+    }
+
+    #[test]
+    fn test_recorder_take_empties() {
+        let recorder = PlanRecorder::new();
+        recorder.record(PlannedCollection {
+            collector_id: "filesystem_collector".to_string(),
+            ctn_type: "file_metadata".to_string(),
+            object_id: "shadow".to_string(),
+            plan: CollectionPlan::new(CollectionKind::FileStat, "stat", "/etc/shadow"),
+        });
+
+        let clone = recorder.clone();
+        assert_eq!(clone.take().len(), 1);
+        assert!(recorder.take().is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+//! Process Collector
+//!
+//! Lists running processes for the `process` CTN type.
+//! - Linux: Reads `/proc/<pid>/stat` and `/proc/<pid>/cmdline`
+//! - Windows: Uses CreateToolhelp32Snapshot (name matching only)
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use execution_engine::types::common::ResolvedValue;
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::process::{find_processes, ProcessError, ProcessFilter};
+
+/// Collector for running processes
+pub struct ProcessCollector {
+    id: String,
+}
+
+impl ProcessCollector {
+    pub fn new() -> Self {
+        Self {
+            id: "process_collector".to_string(),
+        }
+    }
+
+    /// Extract an optional string field from object
+    fn extract_string(
+        &self,
+        object: &ExecutableObject,
+        field: &str,
+    ) -> Result<Option<String>, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == field {
+                    return match value {
+                        ResolvedValue::String(s) if !s.is_empty() => Ok(Some(s.clone())),
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!(
+                                "Field '{}' must be a non-empty string, got {:?}",
+                                field, value
+                            ),
+                        }),
+                    };
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Extract the process filter from object
+    fn extract_filter(&self, object: &ExecutableObject) -> Result<ProcessFilter, CollectionError> {
+        let filter = ProcessFilter {
+            name: self.extract_string(object, "name")?,
+            cmdline_contains: self.extract_string(object, "cmdline_contains")?,
+        };
+
+        if filter.name.is_none() && filter.cmdline_contains.is_none() {
+            return Err(CollectionError::InvalidObjectConfiguration {
+                object_id: object.identifier.clone(),
+                reason: "Object needs 'name' or 'cmdline_contains'".to_string(),
+            });
+        }
+        Ok(filter)
+    }
+}
+
+impl Default for ProcessCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Plan for listing the processes matching one filter
+fn process_plan(filter: &ProcessFilter) -> CollectionPlan {
+    let (description, target) = if cfg!(windows) {
+        (
+            "Enumerate processes via CreateToolhelp32Snapshot",
+            "toolhelp",
+        )
+    } else {
+        ("Read /proc/<pid>/stat and cmdline", "/proc")
+    };
+
+    let mut plan = CollectionPlan::new(CollectionKind::ProcessInspection, description, target);
+    if let Some(name) = &filter.name {
+        plan = plan.with_input("name", name.as_str());
+    }
+    if let Some(needle) = &filter.cmdline_contains {
+        plan = plan.with_input("cmdline_contains", needle.as_str());
+    }
+    plan
+}
+
+impl DescribeCollection for ProcessCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let filter = self.extract_filter(object)?;
+        Ok(process_plan(&filter))
+    }
+
+    /// Each object walks the whole process table
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::low()
+    }
+}
+
+impl CtnDataCollector for ProcessCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let filter = self.extract_filter(object)?;
+
+        // No matching process is data (running = false), not an error
+        let processes = find_processes(&filter).map_err(|e| match e {
+            ProcessError::Unsupported(reason) => CollectionError::InvalidObjectConfiguration {
+                object_id: object.identifier.clone(),
+                reason,
+            },
+            ProcessError::ListFailed(_) => CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: e.to_string(),
+            },
+        })?;
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "process".to_string(),
+            self.id.clone(),
+        );
+        data.set_method(process_plan(&filter).into_method());
+
+        data.add_field(
+            "running".to_string(),
+            ResolvedValue::Boolean(!processes.is_empty()),
+        );
+        data.add_field(
+            "count".to_string(),
+            ResolvedValue::Integer(processes.len() as i64),
+        );
+        data.add_field(
+            "pids".to_string(),
+            ResolvedValue::Collection(
+                processes
+                    .iter()
+                    .map(|p| ResolvedValue::Integer(i64::from(p.pid)))
+                    .collect(),
+            ),
+        );
+
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["process".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != "process" {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!(
+                    "Incompatible CTN type: expected 'process', got '{}'",
+                    contract.ctn_type
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_ctn_types() {
+        let collector = ProcessCollector::new();
+        assert_eq!(collector.collector_id(), "process_collector");
+        assert_eq!(collector.supported_ctn_types(), vec!["process"]);
+    }
+
+    #[test]
+    fn test_process_plan() {
+        let filter = ProcessFilter {
+            name: Some("sshd".to_string()),
+            cmdline_contains: None,
+        };
+        let plan = process_plan(&filter);
+        assert_eq!(plan.kind, CollectionKind::ProcessInspection);
+        assert_eq!(plan.inputs.get("name").map(String::as_str), Some("sshd"));
+        assert!(!plan.inputs.contains_key("cmdline_contains"));
+        assert!(plan.command.is_none());
+    }
+}
@@ -0,0 +1,242 @@
+//! Registry Collector
+//!
+//! Reads Windows registry values for the `registry_value` CTN type.
+//! - Windows: Uses RegGetValueW
+//! - Other platforms: Marks each value not applicable
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use execution_engine::types::common::ResolvedValue;
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::registry::{
+    normalize_key_path, read_registry_value, RegistryError, RegistryHive,
+};
+use crate::executors::applicability::not_applicable_data;
+
+/// Collector for Windows registry values
+pub struct RegistryCollector {
+    id: String,
+}
+
+/// Location of one registry value
+struct RegistryTarget {
+    hive: RegistryHive,
+    key_path: String,
+    value_name: String,
+}
+
+impl RegistryCollector {
+    pub fn new() -> Self {
+        Self {
+            id: "registry_collector".to_string(),
+        }
+    }
+
+    /// Extract a required string field from object
+    fn extract_string(
+        &self,
+        object: &ExecutableObject,
+        field: &str,
+    ) -> Result<String, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == field {
+                    return match value {
+                        ResolvedValue::String(s) => Ok(s.clone()),
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!("Field '{}' must be a string, got {:?}", field, value),
+                        }),
+                    };
+                }
+            }
+        }
+
+        Err(CollectionError::InvalidObjectConfiguration {
+            object_id: object.identifier.clone(),
+            reason: format!("Missing required field '{}'", field),
+        })
+    }
+
+    /// Extract hive, key path and value name from object
+    fn extract_target(&self, object: &ExecutableObject) -> Result<RegistryTarget, CollectionError> {
+        let hive = self.extract_string(object, "hive")?;
+        let hive = RegistryHive::parse(&hive).map_err(|e| {
+            CollectionError::InvalidObjectConfiguration {
+                object_id: object.identifier.clone(),
+                reason: e.to_string(),
+            }
+        })?;
+
+        Ok(RegistryTarget {
+            hive,
+            key_path: normalize_key_path(&self.extract_string(object, "key_path")?),
+            value_name: self.extract_string(object, "value_name")?,
+        })
+    }
+}
+
+impl Default for RegistryCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Plan for reading one registry value
+fn registry_plan(target: &RegistryTarget) -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::RegistryQuery,
+        "Read registry value via RegGetValueW",
+        format!(
+            "{}\\{}\\{}",
+            target.hive.as_str(),
+            target.key_path,
+            target.value_name
+        ),
+    )
+    .with_input("hive", target.hive.as_str())
+    .with_input("key_path", target.key_path.as_str())
+    .with_input("value_name", target.value_name.as_str())
+}
+
+impl DescribeCollection for RegistryCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let target = self.extract_target(object)?;
+        Ok(registry_plan(&target))
+    }
+
+    /// Registry reads are local and lock-free
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::high()
+    }
+}
+
+impl CtnDataCollector for RegistryCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let target = self.extract_target(object)?;
+
+        // Missing keys and values are data (exists = false), not errors
+        let value = match read_registry_value(target.hive, &target.key_path, &target.value_name) {
+            Ok(value) => value,
+            Err(RegistryError::Unsupported) => {
+                return Ok(not_applicable_data(
+                    &object.identifier,
+                    &contract.ctn_type,
+                    &self.id,
+                    "registry values exist only on Windows",
+                ));
+            }
+            Err(RegistryError::AccessDenied(key)) => {
+                return Err(CollectionError::AccessDenied {
+                    object_id: object.identifier.clone(),
+                    reason: format!("Cannot read {}", key),
+                });
+            }
+            Err(e) => {
+                return Err(CollectionError::CollectionFailed {
+                    object_id: object.identifier.clone(),
+                    reason: e.to_string(),
+                });
+            }
+        };
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "registry_value".to_string(),
+            self.id.clone(),
+        );
+        data.set_method(registry_plan(&target).into_method());
+
+        data.add_field(
+            "exists".to_string(),
+            ResolvedValue::Boolean(value.is_some()),
+        );
+
+        if let Some(value) = value {
+            data.add_field(
+                "value_type".to_string(),
+                ResolvedValue::String(value.value_type),
+            );
+            data.add_field(
+                "value_data".to_string(),
+                ResolvedValue::String(value.value_data),
+            );
+            if let Some(int) = value.value_int {
+                data.add_field("value_int".to_string(), ResolvedValue::Integer(int));
+            }
+        }
+
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["registry_value".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != "registry_value" {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!(
+                    "Incompatible CTN type: expected 'registry_value', got '{}'",
+                    contract.ctn_type
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_ctn_types() {
+        let collector = RegistryCollector::new();
+        assert_eq!(collector.collector_id(), "registry_collector");
+        assert_eq!(collector.supported_ctn_types(), vec!["registry_value"]);
+    }
+
+    #[test]
+    fn test_registry_plan() {
+        let target = RegistryTarget {
+            hive: RegistryHive::LocalMachine,
+            key_path: r"SYSTEM\CurrentControlSet\Control\Lsa".to_string(),
+            value_name: "LmCompatibilityLevel".to_string(),
+        };
+        let plan = registry_plan(&target);
+        assert_eq!(plan.kind, CollectionKind::RegistryQuery);
+        assert_eq!(
+            plan.target,
+            r"HKEY_LOCAL_MACHINE\SYSTEM\CurrentControlSet\Control\Lsa\LmCompatibilityLevel"
+        );
+        assert_eq!(
+            plan.inputs.get("hive").map(String::as_str),
+            Some("HKEY_LOCAL_MACHINE")
+        );
+        assert!(plan.command.is_none());
+    }
+}
@@ -0,0 +1,205 @@
+//! Structured Record Collector
+//!
+//! Reads a configuration file and parses it into `RecordData` for the
+//! `structured_record` CTN type, using the object's `format` field: `json`,
+//! `toml`, `ini` or `yaml` (see `commands::structured_record`). The record
+//! is validated with the same record checks as `json_record`.
+//!
+//! A file that does not parse fails collection with an error naming the
+//! format and the line where parsing stopped.
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use execution_engine::types::common::{RecordData, ResolvedValue};
+use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+
+use super::concurrency::ConcurrencyHint;
+use super::path_expansion::PathExpansion;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::filesystem::{read_file_content, FileSystemError};
+use crate::commands::structured_record::{parse_record, RecordFormat};
+
+/// Plan for reading and parsing one file
+fn structured_record_plan(path: &str, format: RecordFormat) -> CollectionPlan {
+    CollectionPlan::new(
+        CollectionKind::FileRead,
+        format!("Read and parse {} file", format.label()),
+        path,
+    )
+    .with_input("format", format.as_str())
+}
+
+/// Collector for structured configuration files
+pub struct StructuredRecordCollector {
+    id: String,
+    path_expansion: Option<PathExpansion>,
+}
+
+impl StructuredRecordCollector {
+    pub fn new() -> Self {
+        Self {
+            id: "structured_record_collector".to_string(),
+            path_expansion: None,
+        }
+    }
+
+    /// Expand allowlisted environment variables in object paths
+    pub fn with_path_expansion(mut self, expansion: PathExpansion) -> Self {
+        self.path_expansion = Some(expansion);
+        self
+    }
+
+    /// Extract a required string field from object
+    fn extract_string_field(
+        &self,
+        object: &ExecutableObject,
+        field_name: &str,
+    ) -> Result<String, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == field_name {
+                    return match value {
+                        ResolvedValue::String(s) => Ok(s.clone()),
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!(
+                                "'{}' field must be a string, got {:?}",
+                                field_name, value
+                            ),
+                        }),
+                    };
+                }
+            }
+        }
+
+        Err(CollectionError::InvalidObjectConfiguration {
+            object_id: object.identifier.clone(),
+            reason: format!("Missing required '{}' field", field_name),
+        })
+    }
+
+    /// Extract the path, applying environment expansion if enabled
+    fn extract_path(&self, object: &ExecutableObject) -> Result<String, CollectionError> {
+        let path = self.extract_string_field(object, "path")?;
+        match &self.path_expansion {
+            Some(expansion) => {
+                expansion
+                    .expand(&path)
+                    .map_err(|e| CollectionError::InvalidObjectConfiguration {
+                        object_id: object.identifier.clone(),
+                        reason: format!("Cannot expand path '{}': {}", path, e),
+                    })
+            }
+            None => Ok(path),
+        }
+    }
+
+    /// Extract the file format
+    fn extract_format(&self, object: &ExecutableObject) -> Result<RecordFormat, CollectionError> {
+        let name = self.extract_string_field(object, "format")?;
+        RecordFormat::parse(&name).ok_or_else(|| CollectionError::InvalidObjectConfiguration {
+            object_id: object.identifier.clone(),
+            reason: format!(
+                "Unsupported format '{}' (expected one of: {})",
+                name,
+                RecordFormat::ALL.map(|format| format.as_str()).join(", ")
+            ),
+        })
+    }
+}
+
+impl Default for StructuredRecordCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DescribeCollection for StructuredRecordCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let path = self.extract_path(object)?;
+        let format = self.extract_format(object)?;
+        Ok(structured_record_plan(&path, format))
+    }
+
+    /// Local file reads scale well
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::high()
+    }
+}
+
+impl CtnDataCollector for StructuredRecordCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let path = self.extract_path(object)?;
+        let format = self.extract_format(object)?;
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "structured_record".to_string(),
+            self.id.clone(),
+        );
+        data.set_method(structured_record_plan(&path, format).into_method());
+
+        let content = read_file_content(&path).map_err(|e| match e {
+            FileSystemError::AccessDenied(p) => CollectionError::AccessDenied {
+                object_id: object.identifier.clone(),
+                reason: format!("Cannot read file: {}", p),
+            },
+            FileSystemError::NotFound(_) => CollectionError::ObjectNotFound {
+                object_id: object.identifier.clone(),
+            },
+            _ => CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: e.to_string(),
+            },
+        })?;
+
+        let value =
+            parse_record(&content, format).map_err(|e| CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: format!("{}: {}", path, e),
+            })?;
+
+        data.add_field(
+            "record_data".to_string(),
+            ResolvedValue::RecordData(Box::new(RecordData::from_json_value(value))),
+        );
+
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["structured_record".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != "structured_record" {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!(
+                    "Incompatible CTN type: expected 'structured_record', got '{}'",
+                    contract.ctn_type
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
@@ -2,15 +2,20 @@
 //!
 //! Collects information about TCP ports in LISTEN state.
 //! - Windows: Uses IP Helper API (GetExtendedTcpTable)
-//! - Linux: Reads /proc/net/tcp
+//! - Linux: Reads /proc/net/tcp and /proc/net/tcp6
+//!
+//! A listening port also reports its owning process: `owner_process` is
+//! always set (empty when the owner could not be found, e.g. another user's
+//! process on Linux without privilege) and `owner_pid` only when found.
 
-use common::results::{CollectionMethod, CollectionMethodType};
 use execution_engine::execution::BehaviorHints;
 use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
 use execution_engine::types::common::ResolvedValue;
 use execution_engine::types::execution_context::{ExecutableObject, ExecutableObjectElement};
 
-use crate::commands::tcp_listener::check_port_listening;
+use super::concurrency::ConcurrencyHint;
+use super::planning::{CollectionKind, CollectionPlan, DescribeCollection};
+use crate::commands::tcp_listener::{check_port_listening, AddressFamily};
 
 /// Collector for TCP listener information
 pub struct TcpListenerCollector {
@@ -82,6 +87,29 @@ impl TcpListenerCollector {
         }
         None
     }
+
+    /// Extract optional address family from object (default: any)
+    fn extract_family(&self, object: &ExecutableObject) -> Result<AddressFamily, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == "family" {
+                    return match value {
+                        ResolvedValue::String(s) => AddressFamily::parse(s).ok_or_else(|| {
+                            CollectionError::InvalidObjectConfiguration {
+                                object_id: object.identifier.clone(),
+                                reason: format!("Invalid family '{}': expected v4, v6 or any", s),
+                            }
+                        }),
+                        _ => Err(CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!("Family must be a string, got {:?}", value),
+                        }),
+                    };
+                }
+            }
+        }
+        Ok(AddressFamily::default())
+    }
 }
 
 impl Default for TcpListenerCollector {
@@ -90,6 +118,51 @@ impl Default for TcpListenerCollector {
     }
 }
 
+/// Plan for checking a port's listener state
+fn listener_plan(port: u16, host_filter: Option<&str>, family: AddressFamily) -> CollectionPlan {
+    #[cfg(windows)]
+    let description = "Check TCP port listener state via Windows IP Helper API";
+    #[cfg(not(windows))]
+    let description = match family {
+        AddressFamily::V4 => "Check TCP port listener state via /proc/net/tcp",
+        AddressFamily::V6 => "Check TCP port listener state via /proc/net/tcp6",
+        AddressFamily::Any => "Check TCP port listener state via /proc/net/tcp and /proc/net/tcp6",
+    };
+
+    let plan = CollectionPlan::new(
+        CollectionKind::SocketInspection,
+        description,
+        format!("tcp:{}", port),
+    )
+    .with_input("port", port.to_string())
+    .with_input("family", family.as_str());
+
+    match host_filter {
+        Some(host) => plan.with_input("host_filter", host),
+        None => plan,
+    }
+}
+
+impl DescribeCollection for TcpListenerCollector {
+    fn describe(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectionPlan, CollectionError> {
+        self.validate_ctn_compatibility(contract)?;
+        let port = self.extract_port(object)?;
+        let host_filter = self.extract_host(object);
+        let family = self.extract_family(object)?;
+        Ok(listener_plan(port, host_filter.as_deref(), family))
+    }
+
+    /// Socket table reads are local and lock-free
+    fn concurrency(&self) -> ConcurrencyHint {
+        ConcurrencyHint::high()
+    }
+}
+
 impl CtnDataCollector for TcpListenerCollector {
     fn collect_for_ctn_with_hints(
         &self,
@@ -106,8 +179,11 @@ impl CtnDataCollector for TcpListenerCollector {
         // Extract host filter (optional)
         let host_filter = self.extract_host(object);
 
+        // Extract address family (optional, default any)
+        let family = self.extract_family(object)?;
+
         // Check if port is listening using platform-native API
-        let result = check_port_listening(port, host_filter.as_deref());
+        let result = check_port_listening(port, host_filter.as_deref(), family);
 
         // Handle collection errors
         if let Some(ref error) = result.error {
@@ -125,22 +201,7 @@ impl CtnDataCollector for TcpListenerCollector {
         );
 
         // Set collection method for traceability
-        #[cfg(windows)]
-        let description = "Check TCP port listener state via Windows IP Helper API";
-        #[cfg(not(windows))]
-        let description = "Check TCP port listener state via /proc/net/tcp";
-
-        let mut method_builder = CollectionMethod::builder()
-            .method_type(CollectionMethodType::SocketInspection)
-            .description(description)
-            .target(format!("tcp:{}", port))
-            .input("port", port.to_string());
-
-        if let Some(ref host) = host_filter {
-            method_builder = method_builder.input("host_filter", host);
-        }
-
-        data.set_method(method_builder.build());
+        data.set_method(listener_plan(port, host_filter.as_deref(), family).into_method());
 
         data.add_field(
             "listening".to_string(),
@@ -151,6 +212,19 @@ impl CtnDataCollector for TcpListenerCollector {
             data.add_field("local_address".to_string(), ResolvedValue::String(addr));
         }
 
+        if result.listening {
+            data.add_field(
+                "owner_process".to_string(),
+                ResolvedValue::String(result.owner_process.unwrap_or_default()),
+            );
+            if let Some(pid) = result.owner_pid {
+                data.add_field(
+                    "owner_pid".to_string(),
+                    ResolvedValue::Integer(i64::from(pid)),
+                );
+            }
+        }
+
         Ok(data)
     }
 
@@ -194,4 +268,18 @@ mod tests {
         let collector = TcpListenerCollector::new();
         assert_eq!(collector.supported_ctn_types(), vec!["tcp_listener"]);
     }
+
+    #[test]
+    fn test_listener_plan() {
+        let plan = listener_plan(22, Some("0.0.0.0"), AddressFamily::Any);
+        assert_eq!(plan.kind, CollectionKind::SocketInspection);
+        assert_eq!(plan.target, "tcp:22");
+        assert_eq!(plan.inputs.get("port").map(String::as_str), Some("22"));
+        assert_eq!(
+            plan.inputs.get("host_filter").map(String::as_str),
+            Some("0.0.0.0")
+        );
+        assert_eq!(plan.inputs.get("family").map(String::as_str), Some("any"));
+        assert!(plan.command.is_none());
+    }
 }
@@ -0,0 +1,75 @@
+//! Collection Timing
+//!
+//! `TimedCollector` wraps a collector and records how long each object
+//! took to collect into a `CriterionTimer`, where `TimingExecutor` adds it
+//! to the time of the criterion that evaluates the object (see
+//! `crate::executors::timing`).
+
+use execution_engine::execution::BehaviorHints;
+use execution_engine::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use execution_engine::types::execution_context::ExecutableObject;
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::executors::timing::CriterionTimer;
+
+/// Collector wrapper that records collection time per object
+pub struct TimedCollector<C> {
+    inner: C,
+    timer: CriterionTimer,
+}
+
+impl<C: CtnDataCollector> TimedCollector<C> {
+    pub fn new(inner: C, timer: CriterionTimer) -> Self {
+        Self { inner, timer }
+    }
+}
+
+impl<C: CtnDataCollector> CtnDataCollector for TimedCollector<C> {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        let start = Instant::now();
+        let collected = self
+            .inner
+            .collect_for_ctn_with_hints(object, contract, hints);
+        self.timer
+            .record_collection(&object.identifier, start.elapsed());
+        collected
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        self.inner.supported_ctn_types()
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        self.inner.validate_ctn_compatibility(contract)
+    }
+
+    fn collector_id(&self) -> &str {
+        self.inner.collector_id()
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        self.inner.supports_batch_collection()
+    }
+
+    /// The batch time is shared equally among its objects
+    fn collect_batch(
+        &self,
+        objects: Vec<&ExecutableObject>,
+        contract: &CtnContract,
+    ) -> Result<HashMap<String, CollectedData>, CollectionError> {
+        let object_ids: Vec<String> = objects.iter().map(|o| o.identifier.clone()).collect();
+        let start = Instant::now();
+        let collected = self.inner.collect_batch(objects, contract);
+        let share = start.elapsed() / u32::try_from(object_ids.len().max(1)).unwrap_or(u32::MAX);
+        for object_id in &object_ids {
+            self.timer.record_collection(object_id, share);
+        }
+        collected
+    }
+}
@@ -0,0 +1,354 @@
+//! Site command allowlist
+//!
+//! Lets a deployment allow extra commands (e.g. a vendor CLI) without
+//! recompiling. The allowlist augments the built-in command sets of each
+//! executor; it never removes built-in entries.
+//!
+//! ## File Format
+//!
+//! One entry per line. Blank lines and `#` comments are ignored.
+//!
+//! ```text
+//! # Vendor agent CLI, pinned to its install location
+//! /opt/vendor/bin/vendorctl
+//! /usr/bin/rpm
+//!
+//! # Explicitly permit a bare name resolved through the sandbox PATH
+//! allow-relative dpkg-query
+//! ```
+//!
+//! ## Pinning
+//!
+//! Entries are absolute paths, so `rpm` resolves only to the pinned
+//! `/usr/bin/rpm` and cannot be shadowed by a `./rpm` in the working
+//! directory. Bare names are rejected unless written as `allow-relative`,
+//! and names containing a path separator (`./rpm`, `bin/rpm`) or `..`
+//! components are always rejected.
+
+use execution_engine::strategies::SystemCommandExecutor;
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+/// Environment variable naming the allowlist file
+pub const COMMAND_ALLOWLIST_ENV: &str = "ESP_COMMAND_ALLOWLIST";
+
+/// Directive permitting a bare command name
+const ALLOW_RELATIVE: &str = "allow-relative";
+
+/// Site-specific commands allowed in addition to the built-in sets
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandAllowlist {
+    /// Pinned absolute command paths
+    absolute: BTreeSet<PathBuf>,
+    /// Bare names explicitly permitted for PATH lookup
+    relative: BTreeSet<String>,
+}
+
+impl CommandAllowlist {
+    /// Empty allowlist (built-in commands only)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse allowlist file contents
+    pub fn parse(content: &str) -> Result<Self, AllowlistError> {
+        let mut allowlist = Self::new();
+
+        for (index, raw) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let line = raw.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            match line.split_once(char::is_whitespace) {
+                Some((ALLOW_RELATIVE, name)) => {
+                    allowlist.allow_relative(name.trim()).map_err(|reason| {
+                        AllowlistError::InvalidEntry {
+                            line: line_number,
+                            entry: line.to_string(),
+                            reason,
+                        }
+                    })?;
+                }
+                _ => {
+                    allowlist.allow_absolute(line).map_err(|reason| {
+                        AllowlistError::InvalidEntry {
+                            line: line_number,
+                            entry: line.to_string(),
+                            reason,
+                        }
+                    })?;
+                }
+            }
+        }
+
+        Ok(allowlist)
+    }
+
+    /// Load an allowlist file
+    pub fn load(path: &Path) -> Result<Self, AllowlistError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| AllowlistError::Read(path.display().to_string(), e))?;
+        Self::parse(&content)
+    }
+
+    /// Load the allowlist named by `ESP_COMMAND_ALLOWLIST`, if set
+    pub fn from_env() -> Result<Option<Self>, AllowlistError> {
+        match std::env::var(COMMAND_ALLOWLIST_ENV) {
+            Ok(path) if !path.trim().is_empty() => Self::load(Path::new(path.trim())).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Pin an absolute command path
+    pub fn allow_absolute(&mut self, command: &str) -> Result<(), String> {
+        let path = Path::new(command);
+        if !path.is_absolute() {
+            return Err(if command.contains(['/', '\\']) {
+                "relative paths are not allowed".to_string()
+            } else {
+                format!(
+                    "bare command names must be absolute paths or use '{} {}'",
+                    ALLOW_RELATIVE, command
+                )
+            });
+        }
+        if path.components().any(|c| c == Component::ParentDir) {
+            return Err("paths must not contain '..'".to_string());
+        }
+        self.absolute.insert(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Explicitly permit a bare command name
+    pub fn allow_relative(&mut self, name: &str) -> Result<(), String> {
+        if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+            return Err("allow-relative takes a bare command name".to_string());
+        }
+        if name.contains(char::is_whitespace) {
+            return Err("command names must not contain whitespace".to_string());
+        }
+        self.relative.insert(name.to_string());
+        Ok(())
+    }
+
+    /// Whether `command` may be executed under this allowlist
+    pub fn is_allowed(&self, command: &str) -> bool {
+        let path = Path::new(command);
+        if path.is_absolute() {
+            self.absolute.contains(path)
+        } else {
+            self.relative.contains(command)
+        }
+    }
+
+    /// Check a command, explaining why it is refused
+    pub fn check(&self, command: &str) -> Result<(), AllowlistError> {
+        if self.is_allowed(command) {
+            return Ok(());
+        }
+        if Path::new(command).is_absolute() {
+            Err(AllowlistError::NotAllowed(command.to_string()))
+        } else {
+            Err(AllowlistError::RelativeCommand(command.to_string()))
+        }
+    }
+
+    /// All allowed entries, absolute paths first
+    pub fn entries(&self) -> Vec<String> {
+        self.absolute
+            .iter()
+            .map(|p| p.display().to_string())
+            .chain(self.relative.iter().cloned())
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.absolute.is_empty() && self.relative.is_empty()
+    }
+
+    /// Add the allowed entries to an executor's command set
+    pub fn apply(&self, executor: &mut SystemCommandExecutor) {
+        let entries = self.entries();
+        if entries.is_empty() {
+            return;
+        }
+        let entries: Vec<&str> = entries.iter().map(String::as_str).collect();
+        executor.allow_commands(&entries);
+    }
+
+    /// Create an executor allowing only this allowlist's commands
+    ///
+    /// For site-specific collectors that have no built-in command set.
+    pub fn create_executor(&self, timeout: Duration) -> SystemCommandExecutor {
+        let mut executor = SystemCommandExecutor::with_timeout(timeout);
+        self.apply(&mut executor);
+        executor
+    }
+}
+
+/// Every command executors with these built-in sets may run
+///
+/// The union of `command_sets` and the site `allowlist`, sorted and
+/// deduplicated, for reviewing what a registry can invoke before deployment.
+pub fn allowed_commands(command_sets: &[&[&str]], allowlist: &CommandAllowlist) -> Vec<String> {
+    command_sets
+        .iter()
+        .flat_map(|set| set.iter().map(|command| command.to_string()))
+        .chain(allowlist.entries())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors loading or checking the command allowlist
+#[derive(Debug)]
+pub enum AllowlistError {
+    /// Failed to read allowlist file
+    Read(String, std::io::Error),
+    /// Entry is not a valid pinned path or directive
+    InvalidEntry {
+        line: usize,
+        entry: String,
+        reason: String,
+    },
+    /// Absolute command path is not allowlisted
+    NotAllowed(String),
+    /// Bare command name was not explicitly permitted
+    RelativeCommand(String),
+}
+
+impl std::fmt::Display for AllowlistError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(path, e) => write!(f, "Failed to read command allowlist {}: {}", path, e),
+            Self::InvalidEntry {
+                line,
+                entry,
+                reason,
+            } => write!(
+                f,
+                "Invalid command allowlist entry '{}' on line {}: {}",
+                entry, line, reason
+            ),
+            Self::NotAllowed(command) => write!(f, "Command not allowlisted: {}", command),
+            Self::RelativeCommand(command) => write!(
+                f,
+                "Relative command '{}' refused (pin an absolute path or use '{} {}')",
+                command, ALLOW_RELATIVE, command
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AllowlistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Read(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entries() {
+        let allowlist = CommandAllowlist::parse(
+            "# vendor tools\n/opt/vendor/bin/vendorctl\n\n/usr/bin/rpm  # pinned\nallow-relative dpkg-query\n",
+        )
+        .unwrap();
+
+        assert!(allowlist.is_allowed("/opt/vendor/bin/vendorctl"));
+        assert!(allowlist.is_allowed("/usr/bin/rpm"));
+        assert!(allowlist.is_allowed("dpkg-query"));
+        assert_eq!(allowlist.entries().len(), 3);
+    }
+
+    #[test]
+    fn test_rejects_relative_entries() {
+        for entry in ["rpm", "./rpm", "bin/rpm", "/usr/bin/../../tmp/rpm"] {
+            let err = CommandAllowlist::parse(entry).unwrap_err();
+            assert!(
+                matches!(err, AllowlistError::InvalidEntry { line: 1, .. }),
+                "{} should be rejected",
+                entry
+            );
+        }
+        assert!(CommandAllowlist::parse("allow-relative ./rpm").is_err());
+    }
+
+    #[test]
+    fn test_pinned_path_not_shadowed() {
+        let allowlist = CommandAllowlist::parse("/usr/bin/rpm").unwrap();
+
+        assert!(allowlist.check("/usr/bin/rpm").is_ok());
+        assert!(matches!(
+            allowlist.check("rpm"),
+            Err(AllowlistError::RelativeCommand(_))
+        ));
+        assert!(matches!(
+            allowlist.check("./rpm"),
+            Err(AllowlistError::RelativeCommand(_))
+        ));
+        assert!(matches!(
+            allowlist.check("/tmp/rpm"),
+            Err(AllowlistError::NotAllowed(_))
+        ));
+    }
+
+    #[test]
+    fn test_allowed_commands_unions_sets_and_allowlist() {
+        let allowlist =
+            CommandAllowlist::parse("/opt/vendor/bin/vendorctl\nallow-relative rpm\n").unwrap();
+
+        let firewall: &[&str] = &["nft", "/usr/sbin/nft"];
+        let commands = allowed_commands(&[firewall, &["rpm"]], &allowlist);
+        assert_eq!(
+            commands,
+            vec!["/opt/vendor/bin/vendorctl", "/usr/sbin/nft", "nft", "rpm"]
+        );
+        assert!(allowed_commands(&[], &CommandAllowlist::new()).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_executor_runs_allowlisted_absolute_path() {
+        let Some(echo) = ["/bin/echo", "/usr/bin/echo"]
+            .into_iter()
+            .find(|p| Path::new(p).exists())
+        else {
+            return;
+        };
+
+        let allowlist = CommandAllowlist::parse(echo).unwrap();
+        let executor = allowlist.create_executor(Duration::from_secs(5));
+
+        let output = executor.execute(echo, &["allowlisted"], None).unwrap();
+        assert_eq!(output.exit_code, 0);
+        assert_eq!(output.stdout.trim(), "allowlisted");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_executor_refuses_unlisted_command() {
+        let allowlist = CommandAllowlist::parse("/opt/vendor/bin/vendorctl").unwrap();
+        let executor = allowlist.create_executor(Duration::from_secs(5));
+
+        assert!(executor.execute("/bin/sh", &["-c", "true"], None).is_err());
+        assert!(executor.execute("echo", &["refused"], None).is_err());
+    }
+}
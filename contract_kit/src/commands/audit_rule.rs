@@ -0,0 +1,374 @@
+//! Linux audit rule operations
+//!
+//! Reads the effective audit rules via `auditctl -l`, falling back to the
+//! persistent rule files in `/etc/audit/rules.d/` when `auditctl` cannot be run.
+//!
+//! ## Rule Normalization
+//!
+//! `auditctl` accepts flags in any order, so the same rule can be written
+//! several ways. Rules are normalized into a canonical form before comparison:
+//!
+//! - Flags are grouped with their values and sorted (`-w` / `-a` first)
+//! - `-F key=<k>` is rewritten as `-k <k>`
+//! - Permission sets (`-p wa`, `-F perm=aw`) are sorted
+//! - Syscall lists (`-S open,openat`) are split into one `-S` per syscall
+//! - Action/list pairs (`-a exit,always`) are written action first
+//!
+//! ```text
+//! -k identity -p aw -w /etc/passwd   →   -w /etc/passwd -k identity -p aw
+//! -w /etc/passwd -p wa -k identity   →   -w /etc/passwd -k identity -p aw
+//! ```
+//!
+//! Reading rules needs the `native` feature; parsing and normalization do
+//! not.
+
+#[cfg(feature = "native")]
+use execution_engine::strategies::SystemCommandExecutor;
+#[cfg(feature = "native")]
+use std::path::Path;
+#[cfg(feature = "native")]
+use std::time::Duration;
+
+/// Directory containing persistent audit rule files
+pub const AUDIT_RULES_DIR: &str = "/etc/audit/rules.d";
+
+/// Candidate locations for the auditctl binary
+#[cfg(feature = "native")]
+const AUDITCTL_PATHS: &[&str] = &["/sbin/auditctl", "/usr/sbin/auditctl"];
+
+/// Control flags that configure the audit system rather than define rules
+const CONTROL_FLAGS: &[&str] = &[
+    "-D",
+    "-b",
+    "-f",
+    "-e",
+    "-r",
+    "-i",
+    "-c",
+    "--backlog_wait_time",
+    "--loginuid-immutable",
+    "--reset-lost",
+];
+
+/// Commands the audit rule executor allows
+pub const AUDIT_COMMANDS: &[&str] = &[
+    "auditctl",           // Standard PATH lookup
+    "/sbin/auditctl",     // Traditional location
+    "/usr/sbin/auditctl", // Merged /usr location
+];
+
+/// Create command executor configured for audit rule collection
+///
+/// Whitelist includes:
+/// - auditctl: Audit control utility (PATH lookup and common sbin locations)
+#[cfg(feature = "native")]
+pub fn create_audit_command_executor() -> SystemCommandExecutor {
+    let mut executor = SystemCommandExecutor::with_timeout(Duration::from_secs(10));
+
+    executor.allow_commands(AUDIT_COMMANDS);
+
+    executor
+}
+
+/// Find the auditctl binary path
+#[cfg(feature = "native")]
+pub fn find_auditctl() -> &'static str {
+    for path in AUDITCTL_PATHS {
+        if Path::new(path).exists() {
+            return path;
+        }
+    }
+    "auditctl" // Fall back to PATH lookup
+}
+
+/// Parse rule listing output into normalized rules
+///
+/// Accepts both `auditctl -l` output and the contents of `.rules` files.
+/// Comments, blank lines, `No rules` and control lines (`-D`, `-b`, `-e`, ...)
+/// are skipped. Duplicate rules are removed while preserving order.
+pub fn parse_audit_rules(output: &str) -> Vec<String> {
+    let mut rules: Vec<String> = Vec::new();
+
+    for line in output.lines() {
+        if let Some(rule) = normalize_audit_rule(line) {
+            if !rules.contains(&rule) {
+                rules.push(rule);
+            }
+        }
+    }
+
+    rules
+}
+
+/// Parse `auditctl -s` output to determine whether auditing is enabled
+///
+/// `enabled 1` means enabled, `enabled 2` means enabled and locked
+/// (immutable). Any other value, or a missing line, is treated as disabled.
+pub fn parse_audit_enabled(status_output: &str) -> bool {
+    status_output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some("enabled"), Some(value)) => Some(value),
+                _ => None,
+            }
+        })
+        .any(|value| value == "1" || value == "2")
+}
+
+/// Read and concatenate all `*.rules` files in a directory
+///
+/// Files are read in lexical order, matching how `augenrules` merges them.
+#[cfg(feature = "native")]
+pub fn read_rules_dir(dir: &Path) -> std::io::Result<String> {
+    let mut files: Vec<std::path::PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "rules"))
+        .collect();
+    files.sort();
+
+    let mut combined = String::new();
+    for file in files {
+        combined.push_str(&std::fs::read_to_string(&file)?);
+        combined.push('\n');
+    }
+
+    Ok(combined)
+}
+
+/// Normalize a single audit rule into canonical form
+///
+/// Returns `None` for lines that are not rules (comments, blank lines,
+/// control settings).
+pub fn normalize_audit_rule(line: &str) -> Option<String> {
+    let pairs = rule_pairs(line)?;
+
+    let normalized: Vec<String> = pairs
+        .iter()
+        .map(|(flag, value)| {
+            if value.is_empty() {
+                flag.clone()
+            } else {
+                format!("{} {}", flag, value)
+            }
+        })
+        .collect();
+
+    Some(normalized.join(" "))
+}
+
+/// Check whether a rule matches a (possibly partial) rule pattern
+///
+/// Both sides are normalized first. The rule matches when every flag/value
+/// pair in the pattern is present in the rule, so `-w /etc/passwd -p wa`
+/// matches `-k identity -p aw -w /etc/passwd`.
+pub fn rule_matches_pattern(rule: &str, pattern: &str) -> bool {
+    let (Some(rule_pairs), Some(pattern_pairs)) = (rule_pairs(rule), rule_pairs(pattern)) else {
+        return false;
+    };
+
+    !pattern_pairs.is_empty() && pattern_pairs.iter().all(|pair| rule_pairs.contains(pair))
+}
+
+/// Extract the key (`-k` / `-F key=`) from a rule, if any
+pub fn rule_key(rule: &str) -> Option<String> {
+    rule_pairs(rule)?
+        .into_iter()
+        .find(|(flag, _)| flag == "-k")
+        .map(|(_, value)| value)
+}
+
+/// Split a rule into sorted, canonical (flag, value) pairs
+fn rule_pairs(line: &str) -> Option<Vec<(String, String)>> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') || !line.starts_with('-') {
+        return None;
+    }
+
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if let Some(first) = tokens.first() {
+        if CONTROL_FLAGS.contains(first) {
+            return None;
+        }
+    }
+
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    let mut i = 0;
+    while let Some(flag) = tokens.get(i) {
+        let value = match tokens.get(i + 1) {
+            Some(next) if !next.starts_with('-') => {
+                i += 1;
+                *next
+            }
+            _ => "",
+        };
+        canonicalize_pair(flag, value, &mut pairs);
+        i += 1;
+    }
+
+    pairs.sort_by(|a, b| {
+        flag_rank(&a.0)
+            .cmp(&flag_rank(&b.0))
+            .then_with(|| a.0.cmp(&b.0))
+            .then_with(|| a.1.cmp(&b.1))
+    });
+    pairs.dedup();
+
+    Some(pairs)
+}
+
+/// Rewrite one flag/value pair into canonical form
+fn canonicalize_pair(flag: &str, value: &str, pairs: &mut Vec<(String, String)>) {
+    match flag {
+        "-F" => {
+            if let Some(key) = value.strip_prefix("key=") {
+                pairs.push(("-k".to_string(), key.to_string()));
+            } else if let Some(perm) = value.strip_prefix("perm=") {
+                pairs.push(("-F".to_string(), format!("perm={}", sort_chars(perm))));
+            } else {
+                pairs.push(("-F".to_string(), value.to_string()));
+            }
+        }
+        "-p" => pairs.push(("-p".to_string(), sort_chars(value))),
+        "-S" => {
+            for syscall in value.split(',').filter(|s| !s.is_empty()) {
+                pairs.push(("-S".to_string(), syscall.to_string()));
+            }
+        }
+        "-a" | "-A" => pairs.push((flag.to_string(), normalize_action_list(value))),
+        _ => pairs.push((flag.to_string(), value.to_string())),
+    }
+}
+
+/// Write an `-a` value as `action,list` regardless of input order
+fn normalize_action_list(value: &str) -> String {
+    let mut parts: Vec<&str> = value.split(',').collect();
+    parts.sort_by_key(|p| !matches!(*p, "always" | "never"));
+    parts.join(",")
+}
+
+/// Sort the characters of a permission string (`wa` → `aw`)
+fn sort_chars(value: &str) -> String {
+    let mut chars: Vec<char> = value.chars().collect();
+    chars.sort_unstable();
+    chars.into_iter().collect()
+}
+
+/// Ordering rank so rule-type flags come first
+fn flag_rank(flag: &str) -> u8 {
+    match flag {
+        "-w" | "-W" | "-a" | "-A" | "-d" => 0,
+        _ => 1,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUDITCTL_LIST: &str = "\
+-w /etc/passwd -p wa -k identity
+-w /etc/shadow -p wa -k identity
+-w /etc/sudoers -p wa -k scope
+-a always,exit -F arch=b64 -S execve -F key=exec
+-a always,exit -F arch=b64 -S open,openat -F exit=-EACCES -F auid>=1000 -F auid!=-1 -F key=access
+";
+
+    const AUDITCTL_STATUS: &str = "\
+enabled 1
+failure 1
+pid 812
+rate_limit 0
+backlog_limit 8192
+lost 0
+backlog 0
+";
+
+    #[test]
+    fn test_parse_auditctl_list() {
+        let rules = parse_audit_rules(AUDITCTL_LIST);
+        assert_eq!(rules.len(), 5);
+        assert_eq!(rules[0], "-w /etc/passwd -k identity -p aw");
+        assert_eq!(rules[3], "-a always,exit -F arch=b64 -S execve -k exec");
+    }
+
+    #[test]
+    fn test_parse_no_rules() {
+        assert!(parse_audit_rules("No rules\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_rules_file_skips_control_lines() {
+        let file = "\
+## First rule - delete all
+-D
+-b 8192
+--backlog_wait_time 60000
+-f 1
+
+-w /etc/passwd -p wa -k identity
+-e 2
+";
+        let rules = parse_audit_rules(file);
+        assert_eq!(rules, vec!["-w /etc/passwd -k identity -p aw".to_string()]);
+    }
+
+    #[test]
+    fn test_normalize_ignores_flag_order() {
+        let a = normalize_audit_rule("-w /etc/passwd -p wa -k identity");
+        let b = normalize_audit_rule("-k identity -p aw -w /etc/passwd");
+        assert!(a.is_some());
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_normalize_key_field_forms() {
+        let a = normalize_audit_rule("-a always,exit -F arch=b64 -S execve -k exec");
+        let b = normalize_audit_rule("-a exit,always -S execve -F key=exec -F arch=b64");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_normalize_syscall_lists() {
+        let a = normalize_audit_rule("-a always,exit -S open,openat -k access");
+        let b = normalize_audit_rule("-a always,exit -S openat -S open -k access");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_rule_matches_partial_pattern() {
+        let rule = "-w /etc/passwd -p wa -k identity";
+        assert!(rule_matches_pattern(rule, "-w /etc/passwd"));
+        assert!(rule_matches_pattern(rule, "-p aw -w /etc/passwd"));
+        assert!(!rule_matches_pattern(rule, "-w /etc/shadow"));
+        assert!(!rule_matches_pattern(rule, "-w /etc/passwd -p r"));
+        assert!(!rule_matches_pattern(rule, ""));
+    }
+
+    #[test]
+    fn test_rule_key() {
+        assert_eq!(
+            rule_key("-w /etc/passwd -p wa -k identity"),
+            Some("identity".to_string())
+        );
+        assert_eq!(
+            rule_key("-a always,exit -S execve -F key=exec"),
+            Some("exec".to_string())
+        );
+        assert_eq!(rule_key("-w /etc/passwd -p wa"), None);
+    }
+
+    #[test]
+    fn test_parse_audit_enabled() {
+        assert!(parse_audit_enabled(AUDITCTL_STATUS));
+        assert!(parse_audit_enabled("enabled 2\n"));
+        assert!(!parse_audit_enabled("enabled 0\nfailure 1\n"));
+        assert!(!parse_audit_enabled(""));
+    }
+}
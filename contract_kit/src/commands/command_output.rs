@@ -0,0 +1,346 @@
+//! Command failure diagnostics
+//!
+//! `SystemCommandExecutor` returns a command's stderr alongside its exit
+//! code. [`run_command`] keeps both when a command exits non-zero, so a
+//! collector can explain the failure instead of reporting a generic error:
+//!
+//! - The collection error quotes the start of stderr, at most
+//!   [`MAX_STDERR_EXCERPT`] bytes; longer output is marked truncated with
+//!   its length and SHA-256.
+//! - When a fallback source succeeds, [`CommandFailure::record`] adds the
+//!   failed command's exit code, stderr excerpt and stderr SHA-256 to the
+//!   plan, so the `CollectionMethod` in evidence (and the assessor package)
+//!   shows why the primary source was not used.
+//!
+//! [`run_command_with_retry`] re-runs a command whose failure looks
+//! transient (a timeout, a refused connection) under a [`RetryPolicy`].
+//!
+//! Both run the command through an [`Elevation`] wrapper when one is given.
+//! Failures still name the wrapped command, so `record` inputs are the same
+//! with or without elevation.
+
+use std::path::Path;
+use std::time::Duration;
+
+use common::results::crypto::sha256_hash;
+use execution_engine::strategies::SystemCommandExecutor;
+
+use crate::collectors::CollectionPlan;
+use crate::commands::elevation::Elevation;
+use crate::commands::retry::{is_transient_failure, RetryPolicy};
+
+/// Most stderr bytes quoted in errors and collection methods
+pub const MAX_STDERR_EXCERPT: usize = 512;
+
+/// A command that ran and exited non-zero
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandFailure {
+    pub command: String,
+    pub exit_code: i32,
+    /// Complete stderr
+    pub stderr: String,
+}
+
+impl CommandFailure {
+    pub fn new(command: impl Into<String>, exit_code: i32, stderr: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            exit_code,
+            stderr: stderr.into(),
+        }
+    }
+
+    /// File name of the command, e.g. `nft` for `/usr/sbin/nft`
+    pub fn name(&self) -> &str {
+        Path::new(&self.command)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&self.command)
+    }
+
+    /// Trimmed stderr, cut to at most [`MAX_STDERR_EXCERPT`] bytes
+    pub fn stderr_excerpt(&self) -> &str {
+        let stderr = self.stderr.trim();
+        let mut end = stderr.len().min(MAX_STDERR_EXCERPT);
+        while !stderr.is_char_boundary(end) {
+            end -= 1;
+        }
+        stderr.get(..end).unwrap_or_default()
+    }
+
+    /// Whether the excerpt leaves part of stderr out
+    pub fn is_truncated(&self) -> bool {
+        self.stderr_excerpt().len() < self.stderr.trim().len()
+    }
+
+    /// Hex SHA-256 of the complete stderr
+    pub fn stderr_sha256(&self) -> String {
+        sha256_hash(self.stderr.as_bytes())
+            .map(|digest| digest.iter().map(|b| format!("{:02x}", b)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Record the failure on the plan of the source used instead
+    pub fn record(&self, plan: CollectionPlan) -> CollectionPlan {
+        let name = self.name().to_string();
+        plan.with_input(format!("{}_exit_code", name), self.exit_code.to_string())
+            .with_input(format!("{}_stderr", name), self.stderr_excerpt())
+            .with_input(format!("{}_stderr_sha256", name), self.stderr_sha256())
+    }
+}
+
+impl std::fmt::Display for CommandFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} failed (exit {})", self.name(), self.exit_code)?;
+        let excerpt = self.stderr_excerpt();
+        if !excerpt.is_empty() {
+            write!(f, ": {}", excerpt)?;
+        }
+        if self.is_truncated() {
+            write!(
+                f,
+                " [stderr truncated: {} bytes, sha256 {}]",
+                self.stderr.len(),
+                self.stderr_sha256()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Why a command produced no usable output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// The command could not be run (not allowed, not found, timed out)
+    Execute { command: String, reason: String },
+    /// The command exited non-zero
+    Exit(CommandFailure),
+}
+
+impl CommandError {
+    /// Complete stderr of a command that exited non-zero
+    pub fn stderr(&self) -> Option<&str> {
+        match self {
+            Self::Execute { .. } => None,
+            Self::Exit(failure) => Some(&failure.stderr),
+        }
+    }
+
+    /// Whether running the command again may succeed
+    ///
+    /// Timeouts and connection failures are transient. A command that is
+    /// not installed or not allowed, or that reports a missing object, is
+    /// a valid answer and is not.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Execute { reason, .. } => is_transient_failure(reason),
+            Self::Exit(failure) => is_transient_failure(&failure.stderr),
+        }
+    }
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Execute { command, reason } => {
+                write!(f, "Failed to execute {}: {}", command, reason)
+            }
+            Self::Exit(failure) => write!(f, "{}", failure),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// Run a command, returning stdout if it exits 0
+///
+/// With `elevation`, the command is run through its wrapper; the executor
+/// must allow the wrapper.
+pub fn run_command(
+    executor: &SystemCommandExecutor,
+    elevation: Option<&Elevation>,
+    command: &str,
+    args: &[&str],
+    timeout: Option<Duration>,
+) -> Result<String, CommandError> {
+    let result = match elevation {
+        Some(elevation) => executor.execute(
+            elevation.program(),
+            &elevation.wrap(command, args)?,
+            timeout,
+        ),
+        None => executor.execute(command, args, timeout),
+    };
+    let output = result.map_err(|e| CommandError::Execute {
+        command: match elevation {
+            Some(elevation) => elevation.command_line(command),
+            None => command.to_string(),
+        },
+        reason: e.to_string(),
+    })?;
+
+    if output.exit_code != 0 {
+        return Err(CommandError::Exit(CommandFailure::new(
+            command,
+            output.exit_code,
+            output.stderr,
+        )));
+    }
+
+    Ok(output.stdout)
+}
+
+/// Run a command, retrying transient failures under `retry`
+///
+/// Returns the last result and the number of retries made.
+pub fn run_command_with_retry(
+    executor: &SystemCommandExecutor,
+    elevation: Option<&Elevation>,
+    command: &str,
+    args: &[&str],
+    timeout: Option<Duration>,
+    retry: &RetryPolicy,
+) -> (Result<String, CommandError>, u32) {
+    retry.run(
+        || run_command(executor, elevation, command, args, timeout),
+        CommandError::is_transient,
+    )
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::CollectionKind;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_nonzero_exit_surfaces_stderr() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = std::env::temp_dir().join(format!("esp_failing_{}.sh", std::process::id()));
+        std::fs::write(
+            &script,
+            "#!/bin/sh\necho 'getenforce: SELinux policy store is corrupt' >&2\nexit 3\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let command = script.to_str().unwrap();
+
+        let mut executor = SystemCommandExecutor::with_timeout(Duration::from_secs(5));
+        executor.allow_commands(&[command]);
+        let result = run_command(&executor, None, command, &[], None);
+        std::fs::remove_file(&script).ok();
+
+        let Err(CommandError::Exit(failure)) = result else {
+            panic!("expected a non-zero exit, got {:?}", result);
+        };
+        assert_eq!(failure.exit_code, 3);
+        assert_eq!(
+            failure.stderr_excerpt(),
+            "getenforce: SELinux policy store is corrupt"
+        );
+        assert!(failure
+            .to_string()
+            .ends_with("failed (exit 3): getenforce: SELinux policy store is corrupt"));
+
+        let plan = failure.record(CollectionPlan::new(
+            CollectionKind::FileRead,
+            "Read fallback",
+            "/etc/selinux/config",
+        ));
+        let name = failure.name();
+        assert_eq!(plan.inputs[&format!("{}_exit_code", name)], "3");
+        assert_eq!(plan.inputs[&format!("{}_stderr_sha256", name)].len(), 64);
+    }
+
+    /// Script that fails with `stderr` on its first `failures` runs, then
+    /// prints `ok`; the run count is kept next to it
+    #[cfg(unix)]
+    fn flaky_script(name: &str, failures: u32, stderr: &str) -> std::path::PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = std::env::temp_dir().join(format!("esp_{}_{}.sh", name, std::process::id()));
+        let counter = script.with_extension("count");
+        std::fs::write(&counter, "0\n").unwrap();
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\nread n < {counter}\nn=$((n + 1))\necho $n > {counter}\n\
+                 if [ $n -le {failures} ]; then echo '{stderr}' >&2; exit 1; fi\necho ok\n",
+                counter = counter.display(),
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        script
+    }
+
+    #[cfg(unix)]
+    fn run_count(script: &std::path::Path) -> u32 {
+        let counter = script.with_extension("count");
+        let count = std::fs::read_to_string(&counter)
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        std::fs::remove_file(&counter).ok();
+        std::fs::remove_file(script).ok();
+        count
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_retry_fails_twice_then_succeeds() {
+        let script = flaky_script("flaky", 2, "connect: connection refused");
+        let command = script.to_str().unwrap();
+        let mut executor = SystemCommandExecutor::with_timeout(Duration::from_secs(5));
+        executor.allow_commands(&[command]);
+
+        let retry = RetryPolicy::fixed(3, Duration::from_millis(1));
+        let (result, retries) = run_command_with_retry(&executor, None, command, &[], None, &retry);
+        let runs = run_count(&script);
+
+        assert_eq!(result.unwrap().trim(), "ok");
+        assert_eq!(retries, 2);
+        assert_eq!(runs, 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_not_found_is_not_retried() {
+        let script = flaky_script("missing", 5, "Unit sshd.service could not be found.");
+        let command = script.to_str().unwrap();
+        let mut executor = SystemCommandExecutor::with_timeout(Duration::from_secs(5));
+        executor.allow_commands(&[command]);
+
+        let retry = RetryPolicy::fixed(3, Duration::from_millis(1));
+        let (result, retries) = run_command_with_retry(&executor, None, command, &[], None, &retry);
+        let runs = run_count(&script);
+
+        assert!(matches!(result, Err(CommandError::Exit(_))));
+        assert_eq!((retries, runs), (0, 1));
+    }
+
+    #[test]
+    fn test_long_stderr_is_truncated_with_hash() {
+        let failure = CommandFailure::new("/usr/sbin/nft", 1, "é".repeat(MAX_STDERR_EXCERPT));
+
+        assert_eq!(failure.name(), "nft");
+        assert!(failure.is_truncated());
+        assert_eq!(failure.stderr_excerpt().len(), MAX_STDERR_EXCERPT);
+        let message = failure.to_string();
+        assert!(message.starts_with("nft failed (exit 1): é"));
+        assert!(message.contains(&format!(
+            "[stderr truncated: {} bytes, sha256 {}]",
+            MAX_STDERR_EXCERPT * 2,
+            failure.stderr_sha256()
+        )));
+
+        let quiet = CommandFailure::new("auditctl", 4, "  \n");
+        assert_eq!(quiet.to_string(), "auditctl failed (exit 4)");
+        assert!(!quiet.is_truncated());
+    }
+}
@@ -0,0 +1,510 @@
+//! Scheduled job listing
+//!
+//! Lists cron jobs for the `cron_job` CTN type.
+//!
+//! ## Platform Support
+//!
+//! - **Linux**: `/etc/crontab`, `/etc/cron.d/*` and the per-user crontabs in
+//!   `/var/spool/cron/crontabs` (Debian) or `/var/spool/cron` (Red Hat)
+//! - **Windows**: Task Scheduler via the COM API; each exec action of a
+//!   registered task is one job, with the task path as its source
+//!
+//! Comments, blank lines and environment assignments (`MAILTO=root`) are
+//! not jobs. `@reboot` and the other `@` shortcuts are kept verbatim as
+//! the schedule.
+
+/// System crontab, with a user column
+pub const SYSTEM_CRONTAB: &str = "/etc/crontab";
+
+/// Drop-in system crontabs, with a user column
+pub const CRON_D_DIR: &str = "/etc/cron.d";
+
+/// Directories of per-user crontabs, named after their owner
+pub const USER_CRONTAB_DIRS: &[&str] = &["/var/spool/cron/crontabs", "/var/spool/cron"];
+
+/// Schedule shortcuts cron accepts in place of the five time fields
+const SPECIAL_SCHEDULES: &[&str] = &[
+    "@reboot",
+    "@yearly",
+    "@annually",
+    "@monthly",
+    "@weekly",
+    "@daily",
+    "@midnight",
+    "@hourly",
+];
+
+/// One scheduled job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronJob {
+    /// File the job was read from, or the task path on Windows
+    pub source: String,
+
+    /// User the job runs as
+    pub user: String,
+
+    /// Five time fields joined with single spaces (`0 5 * * 1`), or an
+    /// `@` shortcut (`@reboot`)
+    pub schedule: String,
+
+    /// Command line, as written
+    pub command: String,
+}
+
+/// Which jobs an object selects
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CronFilter {
+    /// Exact user the job runs as
+    pub user: Option<String>,
+
+    /// Substring of the command
+    pub command_contains: Option<String>,
+}
+
+impl CronFilter {
+    /// Whether `job` matches every criterion of the filter
+    pub fn matches(&self, job: &CronJob) -> bool {
+        let user_matches = self.user.as_deref().is_none_or(|user| {
+            job.user == user || (cfg!(windows) && job.user.eq_ignore_ascii_case(user))
+        });
+        let command_matches = self
+            .command_contains
+            .as_deref()
+            .is_none_or(|needle| job.command.contains(needle));
+        user_matches && command_matches
+    }
+}
+
+/// Error type for job listing
+#[derive(Debug)]
+pub enum CronError {
+    /// Reading a crontab or crontab directory was denied
+    AccessDenied(String),
+
+    /// A crontab or the task scheduler could not be read
+    ReadFailed(String, String),
+}
+
+impl std::fmt::Display for CronError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AccessDenied(path) => write!(f, "Permission denied reading {}", path),
+            Self::ReadFailed(source, msg) => write!(f, "Cannot read {}: {}", source, msg),
+        }
+    }
+}
+
+impl std::error::Error for CronError {}
+
+/// Result type for job listing
+pub type CronResult<T> = Result<T, CronError>;
+
+/// Jobs matching `filter`, in the order they were listed
+pub fn find_cron_jobs(filter: &CronFilter) -> CronResult<Vec<CronJob>> {
+    Ok(list_cron_jobs()?
+        .into_iter()
+        .filter(|job| filter.matches(job))
+        .collect())
+}
+
+/// Parse crontab content
+///
+/// `owner` is the user of a per-user crontab, which has no user column;
+/// system crontabs (`owner = None`) name the user after the schedule.
+/// Lines that are not jobs, including malformed ones, are skipped.
+pub fn parse_crontab(content: &str, source: &str, owner: Option<&str>) -> Vec<CronJob> {
+    content
+        .lines()
+        .filter_map(|line| parse_cron_line(line.trim(), source, owner))
+        .collect()
+}
+
+/// Parse one trimmed crontab line
+///
+/// Jobs start with `@`, `*` or a digit; anything else is a comment, a blank
+/// line or an environment assignment.
+fn parse_cron_line(line: &str, source: &str, owner: Option<&str>) -> Option<CronJob> {
+    let first = line.chars().next()?;
+    let time_fields = match first {
+        '@' => 1,
+        '*' | '0'..='9' => 5,
+        _ => return None,
+    };
+    let user_fields = usize::from(owner.is_none());
+
+    let (fields, command) = split_fields(line, time_fields + user_fields)?;
+    let (schedule, user) = fields.split_at(time_fields);
+    if first == '@' && !schedule.iter().all(|s| SPECIAL_SCHEDULES.contains(s)) {
+        return None;
+    }
+
+    let user = match (owner, user.first()) {
+        (Some(owner), _) => owner.to_string(),
+        (None, Some(user)) => (*user).to_string(),
+        (None, None) => return None,
+    };
+    Some(CronJob {
+        source: source.to_string(),
+        user,
+        schedule: schedule.join(" "),
+        command: command.to_string(),
+    })
+}
+
+/// Split off `count` whitespace-separated fields, returning them and the
+/// rest of the line, which must not be empty
+fn split_fields(line: &str, count: usize) -> Option<(Vec<&str>, &str)> {
+    let mut fields = Vec::with_capacity(count);
+    let mut rest = line;
+    for _ in 0..count {
+        let trimmed = rest.trim_start();
+        let end = trimmed.find(char::is_whitespace)?;
+        let (field, tail) = trimmed.split_at(end);
+        fields.push(field);
+        rest = tail;
+    }
+    let rest = rest.trim();
+    if rest.is_empty() {
+        None
+    } else {
+        Some((fields, rest))
+    }
+}
+
+// ============================================================================
+// Windows Implementation
+// ============================================================================
+
+/// List the exec actions of every registered task, hidden tasks included
+#[cfg(windows)]
+pub fn list_cron_jobs() -> CronResult<Vec<CronJob>> {
+    use windows::core::VARIANT;
+    use windows::Win32::Foundation::E_ACCESSDENIED;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER,
+        COINIT_MULTITHREADED,
+    };
+    use windows::Win32::System::TaskScheduler::{ITaskService, TaskScheduler};
+
+    // SAFETY: COM is uninitialized below only if this call initialized it
+    let initialized = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.is_ok();
+
+    // SAFETY: the service is a COM interface released when dropped
+    let result = unsafe {
+        CoCreateInstance::<_, ITaskService>(&TaskScheduler, None, CLSCTX_INPROC_SERVER).and_then(
+            |service| {
+                let local = VARIANT::default();
+                service.Connect(&local, &local, &local, &local)?;
+                let mut jobs = Vec::new();
+                collect_task_folder(&service.GetFolder(&"\\".into())?, &mut jobs)?;
+                Ok(jobs)
+            },
+        )
+    };
+
+    if initialized {
+        // SAFETY: balances the successful CoInitializeEx above
+        unsafe { CoUninitialize() };
+    }
+
+    result.map_err(|e| {
+        if e.code() == E_ACCESSDENIED {
+            CronError::AccessDenied("Task Scheduler".to_string())
+        } else {
+            CronError::ReadFailed("Task Scheduler".to_string(), e.message())
+        }
+    })
+}
+
+/// Add the jobs of `folder` and its subfolders to `jobs`
+///
+/// Subfolders the agent may not open are skipped, as `schtasks` does.
+#[cfg(windows)]
+unsafe fn collect_task_folder(
+    folder: &windows::Win32::System::TaskScheduler::ITaskFolder,
+    jobs: &mut Vec<CronJob>,
+) -> windows::core::Result<()> {
+    use windows::core::{Interface, BSTR, VARIANT};
+    use windows::Win32::System::TaskScheduler::{IExecAction, TASK_ACTION_EXEC, TASK_ENUM_HIDDEN};
+
+    // Collections are indexed from 1
+    let tasks = folder.GetTasks(TASK_ENUM_HIDDEN.0)?;
+    for index in 1..=tasks.Count()? {
+        let Ok(task) = tasks.get_Item(&VARIANT::from(index)) else {
+            continue;
+        };
+        let definition = task.Definition()?;
+        let source = task.Path()?.to_string();
+
+        let principal = definition.Principal()?;
+        let mut user = BSTR::new();
+        principal.UserId(&mut user)?;
+        if user.is_empty() {
+            principal.GroupId(&mut user)?;
+        }
+        let schedule = task_schedule(&definition)?;
+
+        let actions = definition.Actions()?;
+        let mut count = 0;
+        actions.Count(&mut count)?;
+        for action_index in 1..=count {
+            let action = actions.get_Item(action_index)?;
+            let mut action_type = Default::default();
+            action.Type(&mut action_type)?;
+            if action_type != TASK_ACTION_EXEC {
+                continue;
+            }
+            let exec = action.cast::<IExecAction>()?;
+            let mut path = BSTR::new();
+            let mut arguments = BSTR::new();
+            exec.Path(&mut path)?;
+            exec.Arguments(&mut arguments)?;
+            let command = if arguments.is_empty() {
+                path.to_string()
+            } else {
+                format!("{} {}", path, arguments)
+            };
+            jobs.push(CronJob {
+                source: source.clone(),
+                user: user.to_string(),
+                schedule: schedule.clone(),
+                command,
+            });
+        }
+    }
+
+    let folders = folder.GetFolders(0)?;
+    for index in 1..=folders.Count()? {
+        if let Ok(subfolder) = folders.get_Item(&VARIANT::from(index)) {
+            let _ = collect_task_folder(&subfolder, jobs);
+        }
+    }
+    Ok(())
+}
+
+/// Triggers of a task as cron-style shortcuts, joined with spaces
+///
+/// Boot triggers map to `@reboot` and calendar triggers to `@once`,
+/// `@daily`, `@weekly` and `@monthly`; event, idle, logon and session
+/// triggers, which cron has no equivalent for, keep a descriptive name
+/// (`@logon`). A task without triggers only runs on demand and has an
+/// empty schedule.
+#[cfg(windows)]
+unsafe fn task_schedule(
+    definition: &windows::Win32::System::TaskScheduler::ITaskDefinition,
+) -> windows::core::Result<String> {
+    use windows::Win32::System::TaskScheduler::{
+        TASK_TRIGGER_BOOT, TASK_TRIGGER_DAILY, TASK_TRIGGER_EVENT, TASK_TRIGGER_IDLE,
+        TASK_TRIGGER_LOGON, TASK_TRIGGER_MONTHLY, TASK_TRIGGER_MONTHLYDOW,
+        TASK_TRIGGER_REGISTRATION, TASK_TRIGGER_SESSION_STATE_CHANGE, TASK_TRIGGER_TIME,
+        TASK_TRIGGER_WEEKLY,
+    };
+
+    let triggers = definition.Triggers()?;
+    let mut count = 0;
+    triggers.Count(&mut count)?;
+    let mut schedule = Vec::new();
+    for index in 1..=count {
+        let mut trigger_type = Default::default();
+        triggers.get_Item(index)?.Type(&mut trigger_type)?;
+        let name = match trigger_type {
+            TASK_TRIGGER_BOOT => "@reboot",
+            TASK_TRIGGER_TIME => "@once",
+            TASK_TRIGGER_DAILY => "@daily",
+            TASK_TRIGGER_WEEKLY => "@weekly",
+            TASK_TRIGGER_MONTHLY | TASK_TRIGGER_MONTHLYDOW => "@monthly",
+            TASK_TRIGGER_LOGON => "@logon",
+            TASK_TRIGGER_IDLE => "@idle",
+            TASK_TRIGGER_EVENT => "@event",
+            TASK_TRIGGER_REGISTRATION => "@registration",
+            TASK_TRIGGER_SESSION_STATE_CHANGE => "@session",
+            _ => "@other",
+        };
+        if !schedule.contains(&name) {
+            schedule.push(name);
+        }
+    }
+    Ok(schedule.join(" "))
+}
+
+// ============================================================================
+// Crontab Implementation
+// ============================================================================
+
+/// List the jobs of the system and per-user crontabs
+///
+/// Sources are read in order: `/etc/crontab`, `/etc/cron.d/*`, then the
+/// per-user crontabs, each directory sorted by name. Missing files and
+/// directories have no jobs.
+#[cfg(not(windows))]
+pub fn list_cron_jobs() -> CronResult<Vec<CronJob>> {
+    let mut jobs = Vec::new();
+
+    if let Some(content) = read_crontab(SYSTEM_CRONTAB)? {
+        jobs.extend(parse_crontab(&content, SYSTEM_CRONTAB, None));
+    }
+
+    for (path, _) in crontab_files(CRON_D_DIR)? {
+        if let Some(content) = read_crontab(&path)? {
+            jobs.extend(parse_crontab(&content, &path, None));
+        }
+    }
+
+    for dir in USER_CRONTAB_DIRS {
+        for (path, owner) in crontab_files(dir)? {
+            if let Some(content) = read_crontab(&path)? {
+                jobs.extend(parse_crontab(&content, &path, Some(&owner)));
+            }
+        }
+    }
+
+    Ok(jobs)
+}
+
+/// Regular files in `dir` as (path, file name), sorted by name
+///
+/// Hidden files and editor backups (`~`) are skipped, as cron skips them.
+/// Subdirectories are skipped, so `/var/spool/cron` does not descend into
+/// `crontabs`.
+#[cfg(not(windows))]
+fn crontab_files(dir: &str) -> CronResult<Vec<(String, String)>> {
+    use std::io::ErrorKind;
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+            return Err(CronError::AccessDenied(dir.to_string()))
+        }
+        Err(e) => return Err(CronError::ReadFailed(dir.to_string(), e.to_string())),
+    };
+
+    let mut files: Vec<(String, String)> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            if name.starts_with('.') || name.ends_with('~') {
+                return None;
+            }
+            Some((entry.path().to_string_lossy().into_owned(), name))
+        })
+        .collect();
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(files)
+}
+
+/// Content of a crontab, or `None` if it does not exist
+#[cfg(not(windows))]
+fn read_crontab(path: &str) -> CronResult<Option<String>> {
+    use std::io::ErrorKind;
+
+    match std::fs::read(path) {
+        Ok(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).into_owned())),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+            Err(CronError::AccessDenied(path.to_string()))
+        }
+        Err(e) => Err(CronError::ReadFailed(path.to_string(), e.to_string())),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYSTEM: &str = "\
+# /etc/crontab: system-wide crontab
+SHELL=/bin/sh
+PATH = /usr/local/sbin:/usr/local/bin:/sbin:/bin
+
+17 *\t* * *\troot    cd / && run-parts --report /etc/cron.hourly
+# 25 6 * * * root test -x /usr/sbin/anacron
+   #@reboot root /usr/local/bin/disabled
+@reboot root /usr/local/bin/aide --check > /var/log/aide.log
+@fortnightly root /usr/local/bin/unknown
+*/5 * * * *
+0 4 * * 1 www-data /usr/bin/php /srv/cron.php 'a  b'
+";
+
+    #[test]
+    fn test_parse_system_crontab() {
+        let jobs = parse_crontab(SYSTEM, SYSTEM_CRONTAB, None);
+        assert_eq!(jobs.len(), 3);
+
+        let hourly = jobs.first().unwrap();
+        assert_eq!(hourly.source, "/etc/crontab");
+        assert_eq!(hourly.user, "root");
+        assert_eq!(hourly.schedule, "17 * * * *");
+        assert_eq!(
+            hourly.command,
+            "cd / && run-parts --report /etc/cron.hourly"
+        );
+
+        // @ shortcuts are one schedule field; the rest of the line is kept
+        let reboot = jobs.get(1).unwrap();
+        assert_eq!(reboot.schedule, "@reboot");
+        assert_eq!(reboot.user, "root");
+        assert_eq!(
+            reboot.command,
+            "/usr/local/bin/aide --check > /var/log/aide.log"
+        );
+
+        let php = jobs.get(2).unwrap();
+        assert_eq!(php.user, "www-data");
+        assert_eq!(php.command, "/usr/bin/php /srv/cron.php 'a  b'");
+    }
+
+    #[test]
+    fn test_parse_user_crontab() {
+        let content = "\
+MAILTO=\"\"
+@daily /home/alice/backup.sh
+30 2 * * 0 /usr/bin/find /tmp -mtime +7 -delete
+@reboot
+";
+        let jobs = parse_crontab(content, "/var/spool/cron/crontabs/alice", Some("alice"));
+        assert_eq!(jobs.len(), 2);
+        assert!(jobs.iter().all(|job| job.user == "alice"));
+        assert_eq!(jobs.first().unwrap().schedule, "@daily");
+        assert_eq!(jobs.first().unwrap().command, "/home/alice/backup.sh");
+        assert_eq!(jobs.get(1).unwrap().schedule, "30 2 * * 0");
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let jobs = parse_crontab(SYSTEM, SYSTEM_CRONTAB, None);
+
+        let aide = CronFilter {
+            user: None,
+            command_contains: Some("aide".to_string()),
+        };
+        let matched: Vec<_> = jobs.iter().filter(|job| aide.matches(job)).collect();
+        assert_eq!(matched.len(), 1);
+
+        // Commented-out jobs never match
+        let anacron = CronFilter {
+            user: Some("root".to_string()),
+            command_contains: Some("anacron".to_string()),
+        };
+        assert!(!jobs.iter().any(|job| anacron.matches(job)));
+
+        let www = CronFilter {
+            user: Some("www-data".to_string()),
+            command_contains: None,
+        };
+        assert_eq!(jobs.iter().filter(|job| www.matches(job)).count(), 1);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_crontab_files_missing_dir() {
+        assert!(crontab_files("/nonexistent/esp/cron.d").unwrap().is_empty());
+        assert!(read_crontab("/nonexistent/esp/crontab").unwrap().is_none());
+    }
+}
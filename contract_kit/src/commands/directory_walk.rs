@@ -0,0 +1,370 @@
+//! Directory traversal for recursive file collection
+//!
+//! Walks a directory tree level by level, reading the directories of each
+//! level across a bounded set of worker threads. Results are sorted by path
+//! so the output is identical regardless of thread count or scheduling.
+//!
+//! ## Behavior
+//!
+//! - `max_depth`: directories at depth `>= max_depth` are not read (base is depth 0)
+//! - `include_hidden`: include dot-files (and Windows hidden-attribute files)
+//! - `follow_symlinks`: descend through symlinked directories; each real
+//!   directory is visited at most once, so symlink cycles terminate
+//!
+//! Unreadable directories and entries are skipped. `walk_directory_checked`
+//! also reports the ones skipped because permission was denied.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Upper bound on walker threads, regardless of configuration
+pub const MAX_WALK_THREADS: usize = 16;
+
+/// Default walker threads when not configured
+pub const DEFAULT_WALK_THREADS: usize = 4;
+
+/// Options controlling a directory walk
+#[derive(Debug, Clone)]
+pub struct WalkOptions {
+    pub max_depth: i64,
+    pub include_hidden: bool,
+    pub follow_symlinks: bool,
+    /// Worker threads (clamped to 1..=MAX_WALK_THREADS)
+    pub threads: usize,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            include_hidden: false,
+            follow_symlinks: false,
+            threads: DEFAULT_WALK_THREADS,
+        }
+    }
+}
+
+/// Files found by a walk, and the paths it could not read
+#[derive(Debug, Default)]
+pub struct Walk {
+    /// Regular files, sorted by path
+    pub files: Vec<PathBuf>,
+    /// Directories and entries skipped because permission was denied,
+    /// sorted by path
+    pub denied: Vec<PathBuf>,
+}
+
+/// Entries found in one directory
+#[derive(Default)]
+struct DirEntries {
+    files: Vec<PathBuf>,
+    dirs: Vec<PathBuf>,
+    denied: Vec<PathBuf>,
+}
+
+/// Walk a directory tree, returning all regular files sorted by path
+///
+/// Unreadable directories and entries are skipped.
+pub fn walk_directory(base: &Path, options: &WalkOptions) -> Vec<PathBuf> {
+    walk_directory_checked(base, options).files
+}
+
+/// Walk a directory tree, also returning the paths skipped because
+/// permission was denied
+pub fn walk_directory_checked(base: &Path, options: &WalkOptions) -> Walk {
+    let threads = options.threads.clamp(1, MAX_WALK_THREADS);
+    let mut walk = Walk::default();
+    let mut visited = HashSet::new();
+
+    if options.max_depth <= 0 {
+        return walk;
+    }
+
+    mark_visited(base, &mut visited);
+    let mut frontier = vec![base.to_path_buf()];
+    let mut depth = 0;
+
+    while !frontier.is_empty() && depth < options.max_depth {
+        let level = read_level(&frontier, options, threads);
+
+        let mut next = Vec::new();
+        for entries in level {
+            walk.files.extend(entries.files);
+            walk.denied.extend(entries.denied);
+            for dir in entries.dirs {
+                if mark_visited(&dir, &mut visited) {
+                    next.push(dir);
+                }
+            }
+        }
+
+        frontier = next;
+        depth += 1;
+    }
+
+    walk.files.sort();
+    walk.denied.sort();
+    walk
+}
+
+/// Record a directory as visited, returning false if already seen
+fn mark_visited(dir: &Path, visited: &mut HashSet<PathBuf>) -> bool {
+    let key = std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    visited.insert(key)
+}
+
+/// Read every directory of one level, splitting the work across threads
+fn read_level(dirs: &[PathBuf], options: &WalkOptions, threads: usize) -> Vec<DirEntries> {
+    if threads <= 1 || dirs.len() <= 1 {
+        return dirs.iter().map(|d| read_entries(d, options)).collect();
+    }
+
+    let chunk_size = dirs.len().div_ceil(threads);
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = dirs
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|d| read_entries(d, options))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_default())
+            .collect()
+    })
+}
+
+/// Read a single directory, classifying entries into files and subdirectories
+fn read_entries(dir: &Path, options: &WalkOptions) -> DirEntries {
+    let mut result = DirEntries::default();
+
+    // Skip directories we can't read
+    let entries = match std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(e) => {
+            if e.kind() == std::io::ErrorKind::PermissionDenied {
+                result.denied.push(dir.to_path_buf());
+            }
+            return result;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        let file_name = match path.file_name().and_then(|n| n.to_str()) {
+            Some(n) => n,
+            None => continue,
+        };
+
+        // Skip hidden files unless include_hidden is set
+        if !options.include_hidden && file_name.starts_with('.') {
+            continue;
+        }
+
+        // On Windows, also check hidden attribute
+        #[cfg(windows)]
+        if !options.include_hidden {
+            if let Ok(metadata) = super::filesystem::get_file_metadata(path.to_str().unwrap_or(""))
+            {
+                if metadata.is_hidden {
+                    continue;
+                }
+            }
+        }
+
+        // Get metadata (respecting symlinks setting)
+        let metadata = if options.follow_symlinks {
+            std::fs::metadata(&path)
+        } else {
+            std::fs::symlink_metadata(&path)
+        };
+        let metadata = match metadata {
+            Ok(m) => m,
+            Err(e) => {
+                if e.kind() == std::io::ErrorKind::PermissionDenied {
+                    result.denied.push(path);
+                }
+                continue;
+            }
+        };
+
+        if metadata.is_file() {
+            result.files.push(path);
+        } else if metadata.is_dir() {
+            result.dirs.push(path);
+        }
+    }
+
+    result
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Instant;
+
+    fn create_test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("esp_walk_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Build a tree `depth` levels deep with `fan_out` subdirectories and
+    /// `files` files per directory. Returns the number of files created.
+    fn build_tree(dir: &Path, depth: usize, fan_out: usize, files: usize) -> usize {
+        let mut count = 0;
+        for i in 0..files {
+            fs::write(dir.join(format!("file_{}.conf", i)), "setting = 1\n").unwrap();
+            count += 1;
+        }
+        if depth > 0 {
+            for i in 0..fan_out {
+                let sub = dir.join(format!("dir_{}", i));
+                fs::create_dir(&sub).unwrap();
+                count += build_tree(&sub, depth - 1, fan_out, files);
+            }
+        }
+        count
+    }
+
+    fn options(max_depth: i64, threads: usize) -> WalkOptions {
+        WalkOptions {
+            max_depth,
+            threads,
+            ..WalkOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_parallel_walk_matches_sequential() {
+        let dir = create_test_dir("deep");
+        let expected = build_tree(&dir, 6, 3, 2);
+
+        let start = Instant::now();
+        let sequential = walk_directory(&dir, &options(10, 1));
+        let sequential_time = start.elapsed();
+
+        let start = Instant::now();
+        let parallel = walk_directory(&dir, &options(10, 8));
+        let parallel_time = start.elapsed();
+
+        eprintln!(
+            "walk of {} files: sequential {:?}, parallel {:?}",
+            expected, sequential_time, parallel_time
+        );
+
+        assert_eq!(sequential.len(), expected);
+        assert_eq!(parallel, sequential);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_results_sorted_by_path() {
+        let dir = create_test_dir("sorted");
+        build_tree(&dir, 2, 4, 3);
+
+        let files = walk_directory(&dir, &options(5, 4));
+        let mut sorted = files.clone();
+        sorted.sort();
+        assert_eq!(files, sorted);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let dir = create_test_dir("depth");
+        build_tree(&dir, 3, 2, 1);
+
+        // Depth 1 reads only the base directory
+        assert_eq!(walk_directory(&dir, &options(1, 4)).len(), 1);
+        // Depth 2 adds the two first-level subdirectories
+        assert_eq!(walk_directory(&dir, &options(2, 4)).len(), 3);
+        assert!(walk_directory(&dir, &options(0, 4)).is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_hidden_files() {
+        let dir = create_test_dir("hidden");
+        fs::write(dir.join("visible.conf"), "a").unwrap();
+        fs::write(dir.join(".hidden.conf"), "b").unwrap();
+        fs::create_dir(dir.join(".git")).unwrap();
+        fs::write(dir.join(".git").join("config"), "c").unwrap();
+
+        assert_eq!(walk_directory(&dir, &options(3, 2)).len(), 1);
+
+        let with_hidden = WalkOptions {
+            include_hidden: true,
+            ..options(3, 2)
+        };
+        assert_eq!(walk_directory(&dir, &with_hidden).len(), 3);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_cycle_terminates() {
+        let dir = create_test_dir("cycle");
+        let sub = dir.join("sub");
+        fs::create_dir(&sub).unwrap();
+        fs::write(sub.join("file.conf"), "a").unwrap();
+        std::os::unix::fs::symlink(&dir, sub.join("loop")).unwrap();
+
+        let follow = WalkOptions {
+            follow_symlinks: true,
+            ..options(50, 4)
+        };
+        assert_eq!(walk_directory(&dir, &follow).len(), 1);
+
+        // Without following, the link is neither file nor directory
+        assert_eq!(walk_directory(&dir, &options(50, 4)).len(), 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_denied_directories_are_reported() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = create_test_dir("denied");
+        fs::write(dir.join("open.conf"), "a").unwrap();
+        let locked = dir.join("locked");
+        fs::create_dir(&locked).unwrap();
+        fs::write(locked.join("secret.conf"), "b").unwrap();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+
+        // Root reads the directory anyway; nothing to check
+        if fs::read_dir(&locked).is_err() {
+            let walk = walk_directory_checked(&dir, &options(3, 2));
+            assert_eq!(walk.files, vec![dir.join("open.conf")]);
+            assert_eq!(walk.denied, vec![locked.clone()]);
+        }
+
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+        let walk = walk_directory_checked(&dir, &options(3, 2));
+        assert_eq!(walk.files.len(), 2);
+        assert!(walk.denied.is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
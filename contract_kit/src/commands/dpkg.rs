@@ -0,0 +1,271 @@
+//! Debian package queries
+//!
+//! Reads the dpkg status database with `dpkg-query -W`, either for one
+//! package or, for a batch, for every package at once. Each line of output
+//! is `package<TAB>architecture<TAB>status<TAB>version`.
+//!
+//! ## Installed
+//!
+//! `${Status}` is three words: the selected action, an error flag and the
+//! package state. The state decides, and the error flag must be `ok`:
+//!
+//! | Status | Installed |
+//! |--------|-----------|
+//! | `install ok installed` | yes |
+//! | `hold ok installed` | yes, held at its version |
+//! | `deinstall ok config-files` | no: removed, configuration files remain |
+//! | `purge ok not-installed` | no |
+//! | `install reinstreq half-installed` | no: installation failed part way |
+//!
+//! ## Package Names
+//!
+//! A name may carry an architecture qualifier, `libc6:amd64`. Without one,
+//! a name matches the package on any architecture.
+
+use execution_engine::strategies::SystemCommandExecutor;
+use std::path::Path;
+use std::time::Duration;
+
+/// Output format passed to `dpkg-query -f`
+pub const DPKG_QUERY_FORMAT: &str = "${Package}\\t${Architecture}\\t${Status}\\t${Version}\\n";
+
+/// Candidate locations for the dpkg-query binary
+const DPKG_QUERY_PATHS: &[&str] = &["/usr/bin/dpkg-query", "/bin/dpkg-query"];
+
+/// Commands the dpkg package executor allows
+pub const DPKG_COMMANDS: &[&str] = &[
+    "dpkg-query",          // Standard PATH lookup
+    "/usr/bin/dpkg-query", // Standard location
+    "/bin/dpkg-query",     // Merged /usr symlink target on older layouts
+];
+
+/// Create command executor configured for dpkg package collection
+///
+/// Whitelist includes:
+/// - dpkg-query: Read-only queries of the package database
+pub fn create_dpkg_command_executor() -> SystemCommandExecutor {
+    let mut executor = SystemCommandExecutor::with_timeout(Duration::from_secs(30));
+
+    executor.allow_commands(DPKG_COMMANDS);
+
+    executor
+}
+
+/// Find the dpkg-query binary path
+pub fn find_dpkg_query() -> &'static str {
+    for path in DPKG_QUERY_PATHS {
+        if Path::new(path).exists() {
+            return path;
+        }
+    }
+    "dpkg-query" // Fall back to PATH lookup
+}
+
+/// Whether dpkg-query is installed in a standard location
+pub fn dpkg_query_installed() -> bool {
+    DPKG_QUERY_PATHS.iter().any(|path| Path::new(path).exists())
+}
+
+/// Arguments for `dpkg-query`, listing `package` or, with None, every
+/// package in the database
+pub fn dpkg_query_args(package: Option<&str>) -> Vec<String> {
+    let mut args = vec!["-W".to_string(), format!("-f={}", DPKG_QUERY_FORMAT)];
+    args.extend(package.map(str::to_string));
+    args
+}
+
+/// Whether `name` is a Debian package name, optionally with `:arch`
+///
+/// Lowercase letters, digits, `+`, `-` and `.`, starting with a letter or
+/// digit, so a name can never be read as an option or a glob pattern.
+pub fn is_valid_package_name(name: &str) -> bool {
+    let (package, arch) = match name.split_once(':') {
+        Some((package, arch)) => (package, Some(arch)),
+        None => (name, None),
+    };
+    let starts_alphanumeric = |s: &str| {
+        s.chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    };
+
+    starts_alphanumeric(package)
+        && package
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "+-.".contains(c))
+        && arch.is_none_or(|arch| {
+            starts_alphanumeric(arch)
+                && arch
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        })
+}
+
+/// One package as recorded in the dpkg database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageStatus {
+    pub name: String,
+    pub architecture: String,
+    /// `want eflag state`, e.g. `install ok installed`
+    pub status: String,
+    /// Version as recorded; also kept for removed packages
+    pub version: String,
+}
+
+impl PackageStatus {
+    /// Whether the package is installed (see the module docs)
+    pub fn installed(&self) -> bool {
+        is_installed_status(&self.status)
+    }
+
+    /// Whether `name`, with or without `:arch`, names this package
+    pub fn matches(&self, name: &str) -> bool {
+        match name.split_once(':') {
+            Some((package, arch)) => self.name == package && self.architecture == arch,
+            None => self.name == name,
+        }
+    }
+}
+
+/// Whether a `${Status}` value describes an installed package
+pub fn is_installed_status(status: &str) -> bool {
+    let words: Vec<&str> = status.split_whitespace().collect();
+    matches!(words.as_slice(), [_, "ok", "installed"])
+}
+
+/// Packages listed in `dpkg-query` output in [`DPKG_QUERY_FORMAT`]
+///
+/// Lines without all four fields are skipped.
+pub fn parse_dpkg_query(output: &str) -> Vec<PackageStatus> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next().filter(|name| !name.is_empty())?;
+            let (architecture, status, version) = (fields.next()?, fields.next()?, fields.next()?);
+            Some(PackageStatus {
+                name: name.to_string(),
+                architecture: architecture.to_string(),
+                status: status.to_string(),
+                version: version.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The installed package `name` names, if any
+///
+/// Without an architecture qualifier, the first installed architecture in
+/// `packages` order is returned.
+pub fn find_installed<'a>(packages: &'a [PackageStatus], name: &str) -> Option<&'a PackageStatus> {
+    packages
+        .iter()
+        .find(|package| package.matches(name) && package.installed())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LISTING: &str = "\
+openssh-server\tamd64\tinstall ok installed\t1:9.2p1-2+deb12u3
+telnetd\tamd64\tdeinstall ok config-files\t0.17+2.4-2
+libc6\tamd64\tinstall ok installed\t2.36-9+deb12u7
+libc6\ti386\thold ok installed\t2.36-9+deb12u7
+rsh-server\tamd64\tpurge ok not-installed\t
+nis\tamd64\tinstall reinstreq half-installed\t4.5
+";
+
+    #[test]
+    fn test_is_valid_package_name() {
+        for name in [
+            "openssh-server",
+            "libstdc++6",
+            "python3.11",
+            "libc6:amd64",
+            "g++",
+        ] {
+            assert!(is_valid_package_name(name), "{}", name);
+        }
+        for name in [
+            "",
+            "-W",
+            "OpenSSH",
+            "ssh*",
+            "a b",
+            "libc6:",
+            ":amd64",
+            "libc6:AMD64",
+        ] {
+            assert!(!is_valid_package_name(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_dpkg_query_args() {
+        assert_eq!(
+            dpkg_query_args(Some("telnetd")),
+            vec!["-W", &format!("-f={}", DPKG_QUERY_FORMAT), "telnetd"]
+        );
+        assert_eq!(dpkg_query_args(None).len(), 2);
+    }
+
+    #[test]
+    fn test_installed_status() {
+        assert!(is_installed_status("install ok installed"));
+        assert!(is_installed_status("hold ok installed"));
+        assert!(!is_installed_status("deinstall ok config-files"));
+        assert!(!is_installed_status("purge ok not-installed"));
+        assert!(!is_installed_status("install reinstreq half-installed"));
+        assert!(!is_installed_status("install reinstreq installed"));
+        assert!(!is_installed_status(""));
+    }
+
+    #[test]
+    fn test_removed_package_with_config_files_is_not_installed() {
+        let packages = parse_dpkg_query(LISTING);
+        let telnetd = packages.iter().find(|p| p.name == "telnetd").unwrap();
+        assert_eq!(telnetd.status, "deinstall ok config-files");
+        assert_eq!(telnetd.version, "0.17+2.4-2");
+        assert!(!telnetd.installed());
+        assert_eq!(find_installed(&packages, "telnetd"), None);
+    }
+
+    #[test]
+    fn test_find_installed() {
+        let packages = parse_dpkg_query(LISTING);
+        assert_eq!(packages.len(), 6);
+
+        let ssh = find_installed(&packages, "openssh-server").unwrap();
+        assert_eq!(ssh.version, "1:9.2p1-2+deb12u3");
+
+        // Without a qualifier any architecture matches
+        assert_eq!(
+            find_installed(&packages, "libc6").unwrap().architecture,
+            "amd64"
+        );
+        assert_eq!(
+            find_installed(&packages, "libc6:i386")
+                .unwrap()
+                .architecture,
+            "i386"
+        );
+        assert_eq!(find_installed(&packages, "libc6:arm64"), None);
+
+        for name in ["rsh-server", "nis", "vim"] {
+            assert_eq!(find_installed(&packages, name), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_parse_dpkg_query_skips_malformed_lines() {
+        let packages = parse_dpkg_query("vim\tamd64\n\nbash\tamd64\tinstall ok installed\t5.2\n");
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "bash");
+    }
+}
@@ -0,0 +1,212 @@
+//! Command elevation
+//!
+//! In hardened deployments the agent runs unprivileged, but some commands
+//! (`auditctl -l`, `nft list ruleset`) need root. An [`Elevation`] runs a
+//! collector's commands through a wrapper program, typically `sudo` with a
+//! rule granting exactly those commands:
+//!
+//! ```text
+//! /usr/bin/sudo /sbin/auditctl -l
+//! ```
+//!
+//! ## Safety
+//!
+//! - The wrapper must be on the site [`CommandAllowlist`], so allowing it
+//!   is a deliberate deployment decision like any other extra command.
+//! - An elevation only wraps its targets: the built-in command set of the
+//!   collector it was given to. Any other command is refused rather than
+//!   handed to the wrapper, so policy input never chooses what is elevated.
+//! - The wrapper takes no arguments of its own. Options such as `sudo -n`
+//!   belong in a wrapper script pinned on the allowlist.
+//!
+//! Elevated commands are recorded as run, wrapper included, in the
+//! `CollectionMethod` command of collected data, so evidence and the
+//! content hashes over it show that a check ran elevated.
+
+use std::collections::BTreeSet;
+
+use crate::collectors::CollectionPlan;
+use crate::commands::allowlist::{AllowlistError, CommandAllowlist};
+use crate::commands::command_output::CommandError;
+
+/// Environment variable naming the elevation wrapper
+pub const ELEVATE_CMD_ENV: &str = "ESP_ELEVATE_CMD";
+
+/// Wrapper program that collector commands are run through
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elevation {
+    program: String,
+    /// Commands this elevation may wrap
+    targets: BTreeSet<String>,
+}
+
+impl Elevation {
+    /// Elevation through `program`, which must be on `allowlist`
+    ///
+    /// The elevation has no targets until given a command set with
+    /// [`for_commands`](Self::for_commands).
+    pub fn new(program: &str, allowlist: &CommandAllowlist) -> Result<Self, AllowlistError> {
+        let program = program.trim();
+        allowlist.check(program)?;
+        Ok(Self {
+            program: program.to_string(),
+            targets: BTreeSet::new(),
+        })
+    }
+
+    /// Elevation named by `ESP_ELEVATE_CMD`, if set
+    pub fn from_env(allowlist: &CommandAllowlist) -> Result<Option<Self>, AllowlistError> {
+        match std::env::var(ELEVATE_CMD_ENV) {
+            Ok(program) if !program.trim().is_empty() => Self::new(&program, allowlist).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// The wrapper program
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// The same wrapper, wrapping only `commands`
+    pub fn for_commands(&self, commands: &[&str]) -> Self {
+        Self {
+            program: self.program.clone(),
+            targets: commands.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    /// Whether `command` is run through the wrapper
+    pub fn wraps(&self, command: &str) -> bool {
+        self.targets.contains(command)
+    }
+
+    /// Arguments to run `command` with `args` through the wrapper
+    pub fn wrap<'a>(
+        &self,
+        command: &'a str,
+        args: &[&'a str],
+    ) -> Result<Vec<&'a str>, CommandError> {
+        if !self.wraps(command) {
+            return Err(CommandError::Execute {
+                command: self.command_line(command),
+                reason: format!("{} is not an elevation target", command),
+            });
+        }
+        Ok(std::iter::once(command)
+            .chain(args.iter().copied())
+            .collect())
+    }
+
+    /// `command_line` as run through the wrapper
+    pub fn command_line(&self, command_line: &str) -> String {
+        format!("{} {}", self.program, command_line)
+    }
+
+    /// Record the wrapper on the plan of a command collection
+    ///
+    /// Plans without a command (file reads) are returned unchanged.
+    pub fn record(&self, mut plan: CollectionPlan) -> CollectionPlan {
+        match plan.command.take() {
+            Some(command) => {
+                plan.command = Some(self.command_line(&command));
+                plan.with_input("elevation", self.program.as_str())
+            }
+            None => plan,
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrapper_must_be_allowlisted() {
+        let allowlist = CommandAllowlist::parse("/usr/bin/sudo").unwrap();
+
+        assert!(Elevation::new("/usr/bin/sudo", &allowlist).is_ok());
+        assert!(matches!(
+            Elevation::new("/usr/bin/doas", &allowlist),
+            Err(AllowlistError::NotAllowed(_))
+        ));
+        assert!(matches!(
+            Elevation::new("sudo", &allowlist),
+            Err(AllowlistError::RelativeCommand(_))
+        ));
+    }
+
+    #[test]
+    fn test_only_targets_are_wrapped() {
+        let allowlist = CommandAllowlist::parse("/usr/bin/sudo").unwrap();
+        let elevation = Elevation::new("/usr/bin/sudo", &allowlist)
+            .unwrap()
+            .for_commands(&["auditctl", "/sbin/auditctl"]);
+
+        assert_eq!(
+            elevation.wrap("/sbin/auditctl", &["-l"]).unwrap(),
+            vec!["/sbin/auditctl", "-l"]
+        );
+        assert_eq!(
+            elevation.command_line("/sbin/auditctl -l"),
+            "/usr/bin/sudo /sbin/auditctl -l"
+        );
+        assert!(matches!(
+            elevation.wrap("/bin/sh", &["-c", "id"]),
+            Err(CommandError::Execute { .. })
+        ));
+    }
+
+    #[test]
+    fn test_plan_records_wrapper() {
+        use crate::collectors::CollectionKind;
+
+        let allowlist = CommandAllowlist::parse("/usr/bin/sudo").unwrap();
+        let elevation = Elevation::new("/usr/bin/sudo", &allowlist).unwrap();
+
+        let plan = elevation.record(
+            CollectionPlan::new(CollectionKind::Command, "List rules", "audit_rules")
+                .with_command("auditctl -l"),
+        );
+        assert_eq!(plan.command.as_deref(), Some("/usr/bin/sudo auditctl -l"));
+        assert_eq!(plan.inputs["elevation"], "/usr/bin/sudo");
+
+        let read = elevation.record(CollectionPlan::new(
+            CollectionKind::FileRead,
+            "Read rules.d",
+            "/etc/audit/rules.d",
+        ));
+        assert!(read.command.is_none());
+        assert!(read.inputs.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_executor_only_needs_the_wrapper() {
+        use crate::commands::command_output::run_command;
+        use std::path::Path;
+        use std::time::Duration;
+
+        let Some(env) = ["/usr/bin/env", "/bin/env"]
+            .into_iter()
+            .find(|p| Path::new(p).exists())
+        else {
+            return;
+        };
+
+        // The executor allows env only; env runs echo
+        let allowlist = CommandAllowlist::parse(env).unwrap();
+        let elevation = Elevation::new(env, &allowlist)
+            .unwrap()
+            .for_commands(&["echo"]);
+        let executor = allowlist.create_executor(Duration::from_secs(5));
+
+        let stdout = run_command(&executor, Some(&elevation), "echo", &["elevated"], None).unwrap();
+        assert_eq!(stdout.trim(), "elevated");
+        assert!(run_command(&executor, None, "echo", &["direct"], None).is_err());
+    }
+}
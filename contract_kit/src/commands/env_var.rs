@@ -0,0 +1,124 @@
+//! Environment variable lookup
+//!
+//! Reads environment variables for the `env_var` CTN type.
+//!
+//! ## Platform Support
+//!
+//! - **All platforms**: The agent's own environment (`std::env::var_os`)
+//! - **Linux**: Another process's environment from `/proc/<pid>/environ`
+
+/// Error type for environment lookups
+#[derive(Debug)]
+pub enum EnvVarError {
+    /// Process does not exist
+    ProcessNotFound(u32),
+
+    /// Reading the process environment was denied
+    AccessDenied(u32),
+
+    /// Process environment could not be read
+    ReadFailed(u32, String),
+
+    /// Reading another process's environment is not supported on this platform
+    Unsupported(String),
+}
+
+impl std::fmt::Display for EnvVarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ProcessNotFound(pid) => write!(f, "Process not found: {}", pid),
+            Self::AccessDenied(pid) => {
+                write!(f, "Access denied reading environment of process {}", pid)
+            }
+            Self::ReadFailed(pid, msg) => {
+                write!(f, "Cannot read environment of process {}: {}", pid, msg)
+            }
+            Self::Unsupported(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for EnvVarError {}
+
+/// Result type for environment lookups
+pub type EnvVarResult<T> = Result<T, EnvVarError>;
+
+/// Value of `name`, from the agent's environment or process `pid`'s
+///
+/// `Ok(None)` means the variable is not set; a variable set to the empty
+/// string is `Ok(Some(""))`. Values that are not valid UTF-8 are converted
+/// lossily.
+pub fn read_env_var(name: &str, pid: Option<u32>) -> EnvVarResult<Option<String>> {
+    match pid {
+        None => Ok(std::env::var_os(name).map(|value| value.to_string_lossy().into_owned())),
+        Some(pid) => read_process_env_var(name, pid),
+    }
+}
+
+/// Look up `name` in `/proc/<pid>/environ`
+#[cfg(target_os = "linux")]
+fn read_process_env_var(name: &str, pid: u32) -> EnvVarResult<Option<String>> {
+    use std::io::ErrorKind;
+
+    let environ = std::fs::read(format!("/proc/{}/environ", pid)).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => EnvVarError::ProcessNotFound(pid),
+        ErrorKind::PermissionDenied => EnvVarError::AccessDenied(pid),
+        _ => EnvVarError::ReadFailed(pid, e.to_string()),
+    })?;
+    Ok(parse_environ(&environ, name))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_process_env_var(_name: &str, _pid: u32) -> EnvVarResult<Option<String>> {
+    Err(EnvVarError::Unsupported(
+        "Reading another process's environment (pid) is only supported on Linux".to_string(),
+    ))
+}
+
+/// Find `name` in a NUL-separated `NAME=value` block (`/proc/<pid>/environ`)
+///
+/// The first entry for a name wins, as with `getenv`.
+pub fn parse_environ(environ: &[u8], name: &str) -> Option<String> {
+    environ
+        .split(|&b| b == 0)
+        .find_map(|entry| entry.strip_prefix(name.as_bytes())?.strip_prefix(b"="))
+        .map(|value| String::from_utf8_lossy(value).into_owned())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_environ() {
+        let environ = b"PATH=/usr/bin\0LANG=C.UTF-8\0EMPTY=\0PATHEXT=x\0LANG=fr_FR\0";
+        assert_eq!(parse_environ(environ, "LANG"), Some("C.UTF-8".to_string()));
+        assert_eq!(parse_environ(environ, "PATH"), Some("/usr/bin".to_string()));
+        // Present but empty
+        assert_eq!(parse_environ(environ, "EMPTY"), Some(String::new()));
+        assert_eq!(parse_environ(environ, "MISSING"), None);
+        assert_eq!(parse_environ(b"", "PATH"), None);
+    }
+
+    #[test]
+    fn test_read_own_environment() {
+        // PATH is set for test runs on every supported platform
+        assert!(read_env_var("PATH", None).unwrap().is_some());
+        assert_eq!(
+            read_env_var("ESP_AGENT_TEST_UNSET_VARIABLE", None).unwrap(),
+            None
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_process_environment() {
+        let own = read_env_var("PATH", None).unwrap();
+        assert_eq!(read_env_var("PATH", Some(std::process::id())).unwrap(), own);
+    }
+}
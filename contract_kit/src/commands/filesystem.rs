@@ -10,6 +10,9 @@
 //! println!("Size: {}", metadata.file_size);
 //! ```
 //!
+//! On Windows, absolute paths (`C:\...`, `\\server\share\...`) are opened in
+//! extended-length form so paths past MAX_PATH and UNC shares can be collected.
+//!
 //! ## Collected Fields
 //!
 //! ### Portable Fields (All Platforms)
@@ -21,14 +24,29 @@
 //! | `writable` | Whether the file can be written by current process |
 //! | `file_size` | File size in bytes |
 //! | `is_directory` | Whether the path is a directory |
-//! | `file_owner` | File owner (UID on Unix, SID or DOMAIN\User on Windows) |
-//! | `file_group` | File group (GID on Unix, SID or DOMAIN\Group on Windows) |
+//! | `file_owner` | File owner (UID on Unix, SID on Windows) |
+//! | `file_group` | File group (GID on Unix, SID on Windows) |
+//! | `file_owner_name` | Owner name (`root`, `BUILTIN\Administrators`); empty if unresolvable |
+//! | `file_group_name` | Group name (`wheel`, `NT AUTHORITY\SYSTEM`); empty if unresolvable |
+//! | `is_symlink` | Whether the path itself is a symbolic link |
+//! | `symlink_target` | Link target as stored in the link; empty if not a link |
+//! | `acl_entries` | Access control list (see `file_acl`); empty if unreadable |
+//! | `modified_unix` | Last modification time (epoch seconds) |
+//! | `accessed_unix` | Last access time (epoch seconds) |
+//! | `created_unix` | Creation time (epoch seconds); 0 where the platform or filesystem has none |
+//!
+//! On Linux/macOS, `exists` and the remaining fields follow symbolic links:
+//! they describe the target. A broken link reports `exists = false` with
+//! `is_symlink = true`. On Windows, symbolic links and junctions are both
+//! reported as links (reparse points with a target), and the other fields
+//! describe the link itself.
 //!
 //! ### Linux/macOS Only
 //!
 //! | Field | Description |
 //! |-------|-------------|
 //! | `file_mode` | File permissions in 4-digit octal format (e.g., "0644") |
+//! | `selinux_context` | SELinux context (Linux); not read here, see `file_acl::get_selinux_context` |
 //!
 //! ### Windows Only
 //!
@@ -38,6 +56,8 @@
 //! | `is_hidden` | Whether the file has hidden attribute |
 //! | `is_system` | Whether the file has system attribute |
 
+use super::file_acl::{get_file_acl, AclEntry};
+
 #[cfg(windows)]
 use std::ffi::OsStr;
 #[cfg(windows)]
@@ -47,7 +67,7 @@ use std::os::windows::ffi::OsStrExt;
 use windows::core::{PCWSTR, PWSTR};
 #[cfg(windows)]
 use windows::Win32::Foundation::{
-    CloseHandle, GetLastError, LocalFree, HANDLE, HLOCAL, WIN32_ERROR,
+    CloseHandle, GetLastError, LocalFree, FILETIME, HANDLE, HLOCAL, WIN32_ERROR,
 };
 #[cfg(windows)]
 use windows::Win32::Security::Authorization::{GetSecurityInfo, SE_FILE_OBJECT};
@@ -59,10 +79,10 @@ use windows::Win32::Security::{
 #[cfg(windows)]
 use windows::Win32::Storage::FileSystem::{
     CreateFileW, GetFileAttributesExW, GetFileAttributesW, FILE_ATTRIBUTE_DIRECTORY,
-    FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_READONLY, FILE_ATTRIBUTE_SYSTEM,
-    FILE_FLAGS_AND_ATTRIBUTES, FILE_FLAG_BACKUP_SEMANTICS, FILE_GENERIC_READ, FILE_GENERIC_WRITE,
-    FILE_SHARE_READ, FILE_SHARE_WRITE, GET_FILEEX_INFO_LEVELS, INVALID_FILE_ATTRIBUTES,
-    OPEN_EXISTING, WIN32_FILE_ATTRIBUTE_DATA,
+    FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_READONLY, FILE_ATTRIBUTE_REPARSE_POINT,
+    FILE_ATTRIBUTE_SYSTEM, FILE_FLAGS_AND_ATTRIBUTES, FILE_FLAG_BACKUP_SEMANTICS,
+    FILE_GENERIC_READ, FILE_GENERIC_WRITE, FILE_SHARE_READ, FILE_SHARE_WRITE,
+    GET_FILEEX_INFO_LEVELS, INVALID_FILE_ATTRIBUTES, OPEN_EXISTING, WIN32_FILE_ATTRIBUTE_DATA,
 };
 
 /// File metadata collected from platform-native APIs
@@ -86,12 +106,41 @@ pub struct FileMetadata {
     /// Whether the path is a directory
     pub is_directory: bool,
 
-    /// File owner identifier (UID on Unix, SID or DOMAIN\User on Windows)
+    /// File owner identifier (UID on Unix, SID on Windows)
     pub file_owner: String,
 
-    /// File group identifier (GID on Unix, SID or DOMAIN\Group on Windows)
+    /// File group identifier (GID on Unix, SID on Windows)
     pub file_group: String,
 
+    /// File owner name (user name on Unix, DOMAIN\User on Windows)
+    /// Empty if the identifier cannot be resolved
+    pub file_owner_name: String,
+
+    /// File group name (group name on Unix, DOMAIN\Group on Windows)
+    /// Empty if the identifier cannot be resolved
+    pub file_group_name: String,
+
+    /// Whether the path itself is a symbolic link (not followed)
+    pub is_symlink: bool,
+
+    /// Link target as stored in the link, which may be relative or missing
+    /// Empty if the path is not a symbolic link
+    pub symlink_target: String,
+
+    /// Access control list entries (POSIX ACL on Unix, DACL on Windows)
+    /// Empty if the ACL cannot be read, which may need elevated privileges
+    pub acl_entries: Vec<AclEntry>,
+
+    /// Last modification time in seconds since the Unix epoch
+    pub modified_unix: i64,
+
+    /// Last access time in seconds since the Unix epoch
+    pub accessed_unix: i64,
+
+    /// Creation (birth) time in seconds since the Unix epoch
+    /// 0 if the platform or filesystem does not record it
+    pub created_unix: i64,
+
     // ========================================================================
     // Linux/macOS Only
     // ========================================================================
@@ -99,6 +148,11 @@ pub struct FileMetadata {
     /// Returns empty string on Windows
     pub file_mode: String,
 
+    /// SELinux context (e.g., "system_u:object_r:shadow_t:s0")
+    /// Not read by `get_file_metadata`, which leaves it empty; the
+    /// collector reads it with `BEHAVIOR collect_acls`
+    pub selinux_context: String,
+
     // ========================================================================
     // Windows Only
     // ========================================================================
@@ -124,9 +178,11 @@ pub enum FileSystemError {
     /// Other Windows error
     WindowsError(String, u32),
 
-    /// Invalid path
-    #[allow(dead_code)]
+    /// Invalid path (e.g. a directory where a file is needed)
     InvalidPath(String),
+
+    /// Content is larger than the read limit (path, bytes seen so far)
+    TooLarge(String, u64),
 }
 
 impl std::fmt::Display for FileSystemError {
@@ -136,6 +192,9 @@ impl std::fmt::Display for FileSystemError {
             Self::AccessDenied(path) => write!(f, "Access denied: {}", path),
             Self::WindowsError(msg, code) => write!(f, "{} (error {})", msg, code),
             Self::InvalidPath(path) => write!(f, "Invalid path: {}", path),
+            Self::TooLarge(path, size) => {
+                write!(f, "File too large: {} (at least {} bytes)", path, size)
+            }
         }
     }
 }
@@ -151,16 +210,83 @@ pub type FileSystemResult<T> = Result<T, FileSystemError>;
 
 /// Convert a Rust string to a null-terminated wide string
 #[cfg(windows)]
-fn to_wide_string(s: &str) -> Vec<u16> {
+pub(super) fn to_wide_string(s: &str) -> Vec<u16> {
     OsStr::new(s)
         .encode_wide()
         .chain(std::iter::once(0))
         .collect()
 }
 
-/// Convert a SID to a string representation (DOMAIN\User or S-1-5-...)
+/// Convert a path to a null-terminated wide string in extended-length form
+///
+/// See `extended_length_path`; pass the result to `CreateFileW` and
+/// `GetFileAttributesW` so deep paths and network shares can be opened.
+#[cfg(windows)]
+pub(super) fn to_extended_wide_path(path: &str) -> Vec<u16> {
+    to_wide_string(&extended_length_path(path))
+}
+
+/// Extended-length form of an absolute path
+///
+/// `C:\dir\file` becomes `\\?\C:\dir\file` and `\\server\share\file`
+/// becomes `\\?\UNC\server\share\file`, lifting the MAX_PATH limit. The
+/// prefix turns off Win32 path normalization, so forward slashes become
+/// backslashes and `.`, `..` and repeated separators are resolved first;
+/// `..` never climbs above the drive or share. Relative paths (including
+/// `C:file` and `\file`) and paths already starting with `\\?\` or
+/// `\\.\` are returned unchanged.
 #[cfg(windows)]
-fn sid_to_string(sid: PSID) -> String {
+fn extended_length_path(path: &str) -> String {
+    if path.starts_with(r"\\?\") || path.starts_with(r"\\.\") {
+        return path.to_string();
+    }
+    let normalized = path.replace('/', "\\");
+
+    let (prefix, root_len, rest) = if let Some(rest) = normalized.strip_prefix(r"\\") {
+        if rest.starts_with(r"?\") || rest.starts_with(r".\") {
+            return normalized;
+        }
+        // The server and share are the root of a UNC path
+        (r"\\?\UNC\", 2, rest)
+    } else {
+        match normalized.as_bytes() {
+            [drive, b':', b'\\', ..] if drive.is_ascii_alphabetic() => {
+                (r"\\?\", 1, normalized.as_str())
+            }
+            _ => return path.to_string(),
+        }
+    };
+
+    let mut components: Vec<&str> = Vec::new();
+    for component in rest.split('\\') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                if components.len() > root_len {
+                    components.pop();
+                }
+            }
+            _ => components.push(component),
+        }
+    }
+    if components.len() < root_len {
+        // A UNC path without a share is not a file path
+        return path.to_string();
+    }
+
+    let mut extended = format!("{}{}", prefix, components.join("\\"));
+    if components.len() == root_len && root_len == 1 {
+        // A drive root keeps its trailing separator
+        extended.push('\\');
+    }
+    extended
+}
+
+/// Resolve a SID to its account name (DOMAIN\User)
+///
+/// Returns an empty string if the SID cannot be resolved.
+#[cfg(windows)]
+pub(super) fn sid_to_account_name(sid: PSID) -> String {
     if sid.is_invalid() {
         return String::new();
     }
@@ -182,8 +308,8 @@ fn sid_to_string(sid: PSID) -> String {
         );
 
         if name_size == 0 {
-            // Lookup failed, convert SID to string format
-            return sid_to_string_format(sid);
+            // Lookup failed, no account name
+            return String::new();
         }
 
         // Allocate buffers
@@ -217,15 +343,14 @@ fn sid_to_string(sid: PSID) -> String {
                 format!("{}\\{}", domain, name)
             }
         } else {
-            // Fallback to SID string format
-            sid_to_string_format(sid)
+            String::new()
         }
     }
 }
 
 /// Convert SID to S-1-5-... string format
 #[cfg(windows)]
-fn sid_to_string_format(sid: PSID) -> String {
+pub(super) fn sid_to_string_format(sid: PSID) -> String {
     use windows::Win32::Security::Authorization::ConvertSidToStringSidW;
 
     unsafe {
@@ -254,7 +379,7 @@ fn sid_to_string_format(sid: PSID) -> String {
 /// If the file doesn't exist, returns metadata with `exists = false`.
 #[cfg(windows)]
 pub fn get_file_metadata(path: &str) -> FileSystemResult<FileMetadata> {
-    let wide_path = to_wide_string(path);
+    let wide_path = to_extended_wide_path(path);
     let mut metadata = FileMetadata::default();
 
     // Check if file exists and get attributes
@@ -274,6 +399,7 @@ pub fn get_file_metadata(path: &str) -> FileSystemResult<FileMetadata> {
     }
 
     metadata.exists = true;
+    (metadata.is_symlink, metadata.symlink_target) = reparse_link_info(path, attributes);
     metadata.file_mode = String::new(); // Not applicable on Windows
     metadata.is_directory = (attributes & FILE_ATTRIBUTE_DIRECTORY.0) != 0;
     metadata.is_readonly = (attributes & FILE_ATTRIBUTE_READONLY.0) != 0;
@@ -293,6 +419,9 @@ pub fn get_file_metadata(path: &str) -> FileSystemResult<FileMetadata> {
     if size_result.is_ok() {
         metadata.file_size =
             ((file_info.nFileSizeHigh as u64) << 32) | (file_info.nFileSizeLow as u64);
+        metadata.modified_unix = filetime_to_unix(file_info.ftLastWriteTime);
+        metadata.accessed_unix = filetime_to_unix(file_info.ftLastAccessTime);
+        metadata.created_unix = filetime_to_unix(file_info.ftCreationTime);
     }
 
     // Check if readable
@@ -302,18 +431,40 @@ pub fn get_file_metadata(path: &str) -> FileSystemResult<FileMetadata> {
     metadata.writable = check_writable(path);
 
     // Get owner and group
-    if let Ok((owner, group)) = get_file_security_info(path) {
-        metadata.file_owner = owner;
-        metadata.file_group = group;
+    if let Ok(ownership) = get_file_security_info(path) {
+        metadata.file_owner = ownership.owner_sid;
+        metadata.file_group = ownership.group_sid;
+        metadata.file_owner_name = ownership.owner_name;
+        metadata.file_group_name = ownership.group_name;
     }
 
+    metadata.acl_entries = get_file_acl(path);
+
     Ok(metadata)
 }
 
+/// 100-nanosecond intervals between 1601-01-01 and the Unix epoch
+#[cfg(windows)]
+const FILETIME_UNIX_EPOCH: u64 = 116_444_736_000_000_000;
+
+/// Convert a FILETIME (100 ns intervals since 1601) to epoch seconds
+///
+/// A zero FILETIME, which the filesystem reports when it has no such
+/// time, converts to 0.
+#[cfg(windows)]
+fn filetime_to_unix(time: FILETIME) -> i64 {
+    let ticks = ((time.dwHighDateTime as u64) << 32) | (time.dwLowDateTime as u64);
+    if ticks == 0 {
+        return 0;
+    }
+    let seconds = (ticks as i128 - FILETIME_UNIX_EPOCH as i128) / 10_000_000;
+    i64::try_from(seconds).unwrap_or_default()
+}
+
 /// Check if file is readable by current process
 #[cfg(windows)]
 fn check_readable(path: &str) -> bool {
-    let wide_path = to_wide_string(path);
+    let wide_path = to_extended_wide_path(path);
 
     unsafe {
         let handle = CreateFileW(
@@ -339,7 +490,7 @@ fn check_readable(path: &str) -> bool {
 /// Check if file is writable by current process
 #[cfg(windows)]
 fn check_writable(path: &str) -> bool {
-    let wide_path = to_wide_string(path);
+    let wide_path = to_extended_wide_path(path);
 
     unsafe {
         let handle = CreateFileW(
@@ -362,10 +513,19 @@ fn check_writable(path: &str) -> bool {
     }
 }
 
+/// File owner and group SIDs with their resolved account names
+#[cfg(windows)]
+struct FileOwnership {
+    owner_sid: String,
+    owner_name: String,
+    group_sid: String,
+    group_name: String,
+}
+
 /// Get file owner and group using GetSecurityInfo
 #[cfg(windows)]
-fn get_file_security_info(path: &str) -> FileSystemResult<(String, String)> {
-    let wide_path = to_wide_string(path);
+fn get_file_security_info(path: &str) -> FileSystemResult<FileOwnership> {
+    let wide_path = to_extended_wide_path(path);
 
     unsafe {
         // Open file handle for reading security info
@@ -406,30 +566,101 @@ fn get_file_security_info(path: &str) -> FileSystemResult<(String, String)> {
             ));
         }
 
-        let owner = sid_to_string(owner_sid);
-        let group = sid_to_string(group_sid);
+        let ownership = FileOwnership {
+            owner_sid: sid_to_string_format(owner_sid),
+            owner_name: sid_to_account_name(owner_sid),
+            group_sid: sid_to_string_format(group_sid),
+            group_name: sid_to_account_name(group_sid),
+        };
 
         // Free the security descriptor
         if !security_descriptor.0.is_null() {
             let _ = LocalFree(HLOCAL(security_descriptor.0));
         }
 
-        Ok((owner, group))
+        Ok(ownership)
     }
 }
 
 /// Check if a file exists
 #[cfg(windows)]
 pub fn file_exists(path: &str) -> bool {
-    let wide_path = to_wide_string(path);
+    let wide_path = to_extended_wide_path(path);
     let attributes = unsafe { GetFileAttributesW(PCWSTR(wide_path.as_ptr())) };
     attributes != INVALID_FILE_ATTRIBUTES
 }
 
-/// Read file content as UTF-8 string
+/// Whether `path` itself is a symbolic link or junction, and its target
+///
+/// `attributes` come from `GetFileAttributesW`, which does not follow links.
+/// Only reparse points are read with `read_link`; reparse points that are
+/// not links (cloud placeholders, deduplicated files) have no target, so
+/// `read_link` fails and they are not reported as links.
+#[cfg(windows)]
+fn reparse_link_info(path: &str, attributes: u32) -> (bool, String) {
+    if attributes & FILE_ATTRIBUTE_REPARSE_POINT.0 == 0 {
+        return (false, String::new());
+    }
+    match std::fs::read_link(extended_length_path(path)) {
+        Ok(target) => (true, strip_extended_prefix(&target.to_string_lossy())),
+        Err(_) => (false, String::new()),
+    }
+}
+
+/// Link target without the extended-length prefix Windows stores it with
+///
+/// `\\?\C:\dir` (or `\??\C:\dir`) becomes `C:\dir` and
+/// `\\?\UNC\server\share` becomes `\\server\share`, so targets compare
+/// like the paths policies are written with. Volume GUID paths and relative
+/// targets are returned unchanged.
+#[cfg(windows)]
+fn strip_extended_prefix(target: &str) -> String {
+    if let Some(rest) = target.strip_prefix(r"\\?\UNC\") {
+        return format!(r"\\{}", rest);
+    }
+    let rest = target
+        .strip_prefix(r"\\?\")
+        .or_else(|| target.strip_prefix(r"\??\"));
+    match rest {
+        Some(rest) if rest.as_bytes().get(1) == Some(&b':') => rest.to_string(),
+        _ => target.to_string(),
+    }
+}
+
+/// Whether `path` itself is a symbolic link, and the link's target
+///
+/// Uses `symlink_metadata`, which does not follow links. The target is read
+/// verbatim with `read_link`, so it may be relative or name a missing path.
+#[cfg(not(windows))]
+fn symlink_info(path: &str) -> (bool, String) {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_symlink() => {
+            let target = std::fs::read_link(path)
+                .map(|target| target.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            (true, target)
+        }
+        _ => (false, String::new()),
+    }
+}
+
+/// Default limit on the content read from one file or one recursive scan
+pub const DEFAULT_MAX_CONTENT_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Read file content as UTF-8 string, up to `DEFAULT_MAX_CONTENT_BYTES`
 ///
 /// Uses standard Rust file I/O (works on all platforms)
 pub fn read_file_content(path: &str) -> FileSystemResult<String> {
+    read_file_content_bounded(path, DEFAULT_MAX_CONTENT_BYTES)
+}
+
+/// Read file content as UTF-8 string, failing with `TooLarge` past `max_bytes`
+///
+/// Never reads more than `max_bytes + 1` bytes, so devices and files that
+/// grow while being read (`/dev/zero`, active logs) are cut off too.
+pub fn read_file_content_bounded(path: &str, max_bytes: u64) -> FileSystemResult<String> {
+    use std::io::Read;
+
     #[cfg(windows)]
     {
         if !file_exists(path) {
@@ -444,12 +675,209 @@ pub fn read_file_content(path: &str) -> FileSystemResult<String> {
         }
     }
 
-    std::fs::read_to_string(path).map_err(|e| {
+    let read_error = |e: std::io::Error| {
         if e.kind() == std::io::ErrorKind::PermissionDenied {
             FileSystemError::AccessDenied(path.to_string())
         } else {
             FileSystemError::WindowsError(format!("Failed to read {}: {}", path, e), 0)
         }
+    };
+
+    let file = std::fs::File::open(path).map_err(read_error)?;
+    let size = file.metadata().map_err(read_error)?.len();
+    if size > max_bytes {
+        return Err(FileSystemError::TooLarge(path.to_string(), size));
+    }
+
+    let mut bytes = Vec::new();
+    file.take(max_bytes.saturating_add(1))
+        .read_to_end(&mut bytes)
+        .map_err(read_error)?;
+    check_size(path, &bytes, max_bytes)?;
+
+    String::from_utf8(bytes).map_err(|_| {
+        FileSystemError::WindowsError(
+            format!(
+                "Failed to read {}: stream did not contain valid UTF-8",
+                path
+            ),
+            0,
+        )
+    })
+}
+
+/// `TooLarge` if more than `max_bytes` were read
+fn check_size(path: &str, bytes: &[u8], max_bytes: u64) -> FileSystemResult<()> {
+    let len = bytes.len() as u64;
+    if len > max_bytes {
+        return Err(FileSystemError::TooLarge(path.to_string(), len));
+    }
+    Ok(())
+}
+
+/// Part of a file to read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentRange {
+    /// First `n` bytes
+    Head(u64),
+    /// `length` bytes starting at `offset`
+    Bytes { offset: u64, length: u64 },
+    /// Last `n` lines
+    TailLines(usize),
+}
+
+/// Chunk size for reading backwards from the end of a file
+const TAIL_CHUNK_SIZE: u64 = 8 * 1024;
+
+/// Read part of a file as a UTF-8 string, up to `DEFAULT_MAX_CONTENT_BYTES`
+pub fn read_file_range(path: &str, range: ContentRange) -> FileSystemResult<String> {
+    read_file_range_bounded(path, range, DEFAULT_MAX_CONTENT_BYTES)
+}
+
+/// Read part of a file as a UTF-8 string, without reading the rest
+///
+/// Byte ranges seek to their offset; tails read backwards from the end of
+/// the file in chunks until enough line breaks are found. A range past the
+/// end of the file is empty. A multi-byte character cut by a byte range
+/// boundary is replaced with U+FFFD. A part longer than `max_bytes` fails
+/// with `TooLarge`.
+pub fn read_file_range_bounded(
+    path: &str,
+    range: ContentRange,
+    max_bytes: u64,
+) -> FileSystemResult<String> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let limit = max_bytes.saturating_add(1);
+
+    let read_error = |e: std::io::Error| {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            FileSystemError::AccessDenied(path.to_string())
+        } else {
+            FileSystemError::WindowsError(format!("Failed to read {}: {}", path, e), 0)
+        }
+    };
+
+    if !std::path::Path::new(path).exists() {
+        return Err(FileSystemError::NotFound(path.to_string()));
+    }
+    let mut file = std::fs::File::open(path).map_err(read_error)?;
+
+    let bytes = match range {
+        ContentRange::Head(length) => {
+            let mut bytes = Vec::new();
+            file.take(length.min(limit))
+                .read_to_end(&mut bytes)
+                .map_err(read_error)?;
+            bytes
+        }
+        ContentRange::Bytes { offset, length } => {
+            file.seek(SeekFrom::Start(offset)).map_err(read_error)?;
+            let mut bytes = Vec::new();
+            file.take(length.min(limit))
+                .read_to_end(&mut bytes)
+                .map_err(read_error)?;
+            bytes
+        }
+        ContentRange::TailLines(lines) => {
+            let len = file.metadata().map_err(read_error)?.len();
+            let mut tail: Vec<u8> = Vec::new();
+            let mut pos = len;
+            loop {
+                if let Some(start) = tail_start(&tail, lines) {
+                    tail.drain(..start);
+                    break;
+                }
+                // All of the tail so far is needed, and more may follow
+                check_size(path, &tail, max_bytes)?;
+                if pos == 0 {
+                    break;
+                }
+                let chunk_len = pos.min(TAIL_CHUNK_SIZE);
+                pos -= chunk_len;
+                file.seek(SeekFrom::Start(pos)).map_err(read_error)?;
+                let mut chunk = Vec::new();
+                (&mut file)
+                    .take(chunk_len)
+                    .read_to_end(&mut chunk)
+                    .map_err(read_error)?;
+                chunk.append(&mut tail);
+                tail = chunk;
+            }
+            tail
+        }
+    };
+    check_size(path, &bytes, max_bytes)?;
+
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Start of the last `lines` lines in `data`, which ends at end of file
+///
+/// `None` until `data` holds a line break before those lines.
+fn tail_start(data: &[u8], lines: usize) -> Option<usize> {
+    if lines == 0 {
+        return Some(data.len());
+    }
+    // A final line break ends the last line rather than starting another
+    let body = data.strip_suffix(b"\n").unwrap_or(data);
+    body.iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, byte)| **byte == b'\n')
+        .nth(lines - 1)
+        .map(|(i, _)| i + 1)
+}
+
+/// SHA-256 digest of a file's content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHash {
+    /// Lowercase hex digest
+    pub sha256: String,
+    /// Number of bytes hashed
+    pub file_size: u64,
+}
+
+/// Chunk size for hashing a file
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Hash a file with SHA-256, reading it in chunks
+///
+/// Memory use is one chunk whatever the file size, so there is no size
+/// limit. The digest matches `common::results::crypto::sha256_hash` of the
+/// whole content. A directory fails with `InvalidPath`.
+pub fn hash_file(path: &str) -> FileSystemResult<FileHash> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+
+    let read_error = |e: std::io::Error| match e.kind() {
+        std::io::ErrorKind::NotFound => FileSystemError::NotFound(path.to_string()),
+        std::io::ErrorKind::PermissionDenied => FileSystemError::AccessDenied(path.to_string()),
+        _ => FileSystemError::WindowsError(format!("Failed to read {}: {}", path, e), 0),
+    };
+
+    let mut file = std::fs::File::open(path).map_err(read_error)?;
+    if file.metadata().map_err(read_error)?.is_dir() {
+        return Err(FileSystemError::InvalidPath(path.to_string()));
+    }
+
+    let mut hasher = Sha256::new();
+    let mut chunk = vec![0u8; HASH_CHUNK_SIZE];
+    let mut file_size = 0u64;
+    loop {
+        let read = match file.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(read_error(e)),
+        };
+        hasher.update(chunk.get(..read).unwrap_or_default());
+        file_size += read as u64;
+    }
+
+    Ok(FileHash {
+        sha256: format!("{:x}", hasher.finalize()),
+        file_size,
     })
 }
 
@@ -467,7 +895,9 @@ pub fn get_file_metadata(path: &str) -> FileSystemResult<FileMetadata> {
 
     let path_obj = Path::new(path);
     let mut metadata = FileMetadata::default();
+    (metadata.is_symlink, metadata.symlink_target) = symlink_info(path);
 
+    // Follows symbolic links, so a broken link does not exist
     if !path_obj.exists() {
         metadata.exists = false;
         return Ok(metadata);
@@ -497,6 +927,11 @@ pub fn get_file_metadata(path: &str) -> FileSystemResult<FileMetadata> {
             metadata.file_mode = format!("{:04o}", fs_meta.permissions().mode() & 0o7777);
             metadata.file_owner = fs_meta.uid().to_string();
             metadata.file_group = fs_meta.gid().to_string();
+            metadata.file_owner_name = user_name(fs_meta.uid());
+            metadata.file_group_name = group_name(fs_meta.gid());
+            metadata.acl_entries = get_file_acl(path);
+            metadata.modified_unix = fs_meta.mtime();
+            metadata.accessed_unix = fs_meta.atime();
         }
 
         #[cfg(not(unix))]
@@ -504,18 +939,104 @@ pub fn get_file_metadata(path: &str) -> FileSystemResult<FileMetadata> {
             metadata.file_mode = String::new();
             metadata.file_owner = String::new();
             metadata.file_group = String::new();
+            metadata.file_owner_name = String::new();
+            metadata.file_group_name = String::new();
+            metadata.modified_unix = fs_meta.modified().map(unix_seconds).unwrap_or_default();
+            metadata.accessed_unix = fs_meta.accessed().map(unix_seconds).unwrap_or_default();
         }
+
+        // Birth time, from statx on Linux; not every filesystem records it
+        metadata.created_unix = fs_meta.created().map(unix_seconds).unwrap_or_default();
     }
 
     Ok(metadata)
 }
 
+/// Seconds since the Unix epoch, negative before it
+#[cfg(not(windows))]
+fn unix_seconds(time: std::time::SystemTime) -> i64 {
+    match time.duration_since(std::time::UNIX_EPOCH) {
+        Ok(since) => i64::try_from(since.as_secs()).unwrap_or(i64::MAX),
+        Err(before) => -i64::try_from(before.duration().as_secs()).unwrap_or(i64::MAX),
+    }
+}
+
 /// Check if a file exists (Unix)
 #[cfg(not(windows))]
 pub fn file_exists(path: &str) -> bool {
     std::path::Path::new(path).exists()
 }
 
+/// Initial buffer size for passwd/group lookups, grown on ERANGE
+#[cfg(unix)]
+const NSS_BUFFER_SIZE: usize = 1024;
+
+/// Upper bound for passwd/group lookup buffers
+#[cfg(unix)]
+const NSS_BUFFER_MAX: usize = 1024 * 1024;
+
+/// Resolve a UID to its user name via `getpwuid_r`
+///
+/// Returns an empty string if the UID has no passwd entry.
+#[cfg(unix)]
+pub fn user_name(uid: u32) -> String {
+    let mut buf_size = NSS_BUFFER_SIZE;
+
+    while buf_size <= NSS_BUFFER_MAX {
+        let mut buf: Vec<libc::c_char> = vec![0; buf_size];
+        let mut pwd: libc::passwd = unsafe { std::mem::zeroed() };
+        let mut result: *mut libc::passwd = std::ptr::null_mut();
+
+        let rc =
+            unsafe { libc::getpwuid_r(uid, &mut pwd, buf.as_mut_ptr(), buf.len(), &mut result) };
+
+        if rc == libc::ERANGE {
+            buf_size *= 2;
+            continue;
+        }
+        if rc != 0 || result.is_null() || pwd.pw_name.is_null() {
+            return String::new();
+        }
+
+        return unsafe { std::ffi::CStr::from_ptr(pwd.pw_name) }
+            .to_string_lossy()
+            .into_owned();
+    }
+
+    String::new()
+}
+
+/// Resolve a GID to its group name via `getgrgid_r`
+///
+/// Returns an empty string if the GID has no group entry.
+#[cfg(unix)]
+pub fn group_name(gid: u32) -> String {
+    let mut buf_size = NSS_BUFFER_SIZE;
+
+    while buf_size <= NSS_BUFFER_MAX {
+        let mut buf: Vec<libc::c_char> = vec![0; buf_size];
+        let mut grp: libc::group = unsafe { std::mem::zeroed() };
+        let mut result: *mut libc::group = std::ptr::null_mut();
+
+        let rc =
+            unsafe { libc::getgrgid_r(gid, &mut grp, buf.as_mut_ptr(), buf.len(), &mut result) };
+
+        if rc == libc::ERANGE {
+            buf_size *= 2;
+            continue;
+        }
+        if rc != 0 || result.is_null() || grp.gr_name.is_null() {
+            return String::new();
+        }
+
+        return unsafe { std::ffi::CStr::from_ptr(grp.gr_name) }
+            .to_string_lossy()
+            .into_owned();
+    }
+
+    String::new()
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -541,6 +1062,131 @@ mod tests {
         assert!(!file_exists("/definitely/nonexistent/path/12345.xyz"));
     }
 
+    /// Write `content` to a fresh file in the temp directory
+    fn write_temp_file(name: &str, content: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("esp_range_{}_{}", std::process::id(), name));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_hash_file_in_chunks() {
+        let path = write_temp_file("hash_abc", b"abc");
+        let hash = hash_file(path.to_str().unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            hash.sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(hash.file_size, 3);
+
+        // Several chunks, with a partial last one
+        let content: Vec<u8> = (0..200_000u32).map(|n| (n % 251) as u8).collect();
+        let path = write_temp_file("hash_large", &content);
+        let hash = hash_file(path.to_str().unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(hash.sha256, common::results::crypto::sha256_hash(&content));
+        assert_eq!(hash.file_size, 200_000);
+    }
+
+    #[test]
+    fn test_hash_file_errors() {
+        assert!(matches!(
+            hash_file("/nonexistent/path/file.txt"),
+            Err(FileSystemError::NotFound(_))
+        ));
+        let dir = std::env::temp_dir();
+        assert!(matches!(
+            hash_file(dir.to_str().unwrap()),
+            Err(FileSystemError::InvalidPath(_))
+        ));
+    }
+
+    #[test]
+    fn test_tail_lines() {
+        // Longer than one chunk, so the tail is found across reads
+        let content: String = (1..=3000).map(|n| format!("line {}\n", n)).collect();
+        let path = write_temp_file("tail", content.as_bytes());
+        let path_str = path.to_str().unwrap();
+
+        let tail = read_file_range(path_str, ContentRange::TailLines(3)).unwrap();
+        assert_eq!(tail, "line 2998\nline 2999\nline 3000\n");
+        let all = read_file_range(path_str, ContentRange::TailLines(5000)).unwrap();
+        assert_eq!(all, content);
+        let none = read_file_range(path_str, ContentRange::TailLines(0)).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(none, "");
+
+        // Without a final line break, the partial last line counts
+        assert_eq!(tail_start(b"a\nb\nc", 2), Some(2));
+        assert_eq!(tail_start(b"a\nb\nc\n", 2), Some(2));
+        assert_eq!(tail_start(b"b\nc\n", 2), None);
+    }
+
+    #[test]
+    fn test_head_bytes_and_byte_range() {
+        let mut content = b"#!/bin/sh\n".to_vec();
+        content.extend(std::iter::repeat_n(b'x', 100_000));
+        let path = write_temp_file("head", &content);
+        let path_str = path.to_str().unwrap();
+
+        let head = read_file_range(path_str, ContentRange::Head(10)).unwrap();
+        assert_eq!(head, "#!/bin/sh\n");
+        let range = read_file_range(
+            path_str,
+            ContentRange::Bytes {
+                offset: 2,
+                length: 7,
+            },
+        )
+        .unwrap();
+        assert_eq!(range, "/bin/sh");
+        let past_end = read_file_range(
+            path_str,
+            ContentRange::Bytes {
+                offset: 200_000,
+                length: 10,
+            },
+        )
+        .unwrap();
+        let whole = read_file_range(path_str, ContentRange::Head(1_000_000)).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(past_end, "");
+        assert_eq!(whole.len(), content.len());
+    }
+
+    #[test]
+    fn test_content_size_limit() {
+        let path = write_temp_file("limit", &[b'a'; 64]);
+        let path_str = path.to_str().unwrap();
+
+        let exact = read_file_content_bounded(path_str, 64).unwrap();
+        let over = read_file_content_bounded(path_str, 63);
+        let head = read_file_range_bounded(path_str, ContentRange::Head(1_000), 32);
+        let tail = read_file_range_bounded(path_str, ContentRange::TailLines(1), 32);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(exact.len(), 64);
+        assert!(matches!(over, Err(FileSystemError::TooLarge(_, 64))));
+        assert!(matches!(head, Err(FileSystemError::TooLarge(_, 33))));
+        assert!(matches!(tail, Err(FileSystemError::TooLarge(_, _))));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_unbounded_device_is_cut_off() {
+        // Reports a size of 0 but never ends
+        let result = read_file_content_bounded("/dev/zero", 1024);
+        assert!(matches!(result, Err(FileSystemError::TooLarge(_, 1025))));
+    }
+
+    #[test]
+    fn test_range_of_missing_file() {
+        let result = read_file_range("/nonexistent/path/file.log", ContentRange::TailLines(1));
+        assert!(matches!(result, Err(FileSystemError::NotFound(_))));
+    }
+
     #[cfg(unix)]
     mod unix_tests {
         use super::*;
@@ -553,6 +1199,14 @@ mod tests {
             dir
         }
 
+        /// Separate directory per test, so cleanup cannot race other tests
+        fn create_named_test_dir(name: &str) -> std::path::PathBuf {
+            let dir =
+                std::env::temp_dir().join(format!("esp_test_{}_{}", std::process::id(), name));
+            let _ = fs::create_dir_all(&dir);
+            dir
+        }
+
         fn cleanup_test_dir(dir: &std::path::Path) {
             let _ = fs::remove_dir_all(dir);
         }
@@ -579,6 +1233,32 @@ mod tests {
             cleanup_test_dir(&dir);
         }
 
+        #[test]
+        fn test_get_metadata_times() {
+            let dir = create_named_test_dir("times");
+            let file_path = dir.join("times.txt");
+            File::create(&file_path).unwrap();
+            let now = unix_seconds(std::time::SystemTime::now());
+
+            let metadata = get_file_metadata(file_path.to_str().unwrap()).unwrap();
+            assert!((metadata.modified_unix - now).abs() <= 5);
+            assert!((metadata.accessed_unix - now).abs() <= 5);
+            // Birth time is optional; when recorded it is the creation just now
+            assert!(metadata.created_unix == 0 || (metadata.created_unix - now).abs() <= 5);
+
+            let missing = get_file_metadata(dir.join("missing").to_str().unwrap()).unwrap();
+            assert_eq!(
+                (
+                    missing.modified_unix,
+                    missing.accessed_unix,
+                    missing.created_unix
+                ),
+                (0, 0, 0)
+            );
+
+            cleanup_test_dir(&dir);
+        }
+
         #[test]
         fn test_get_metadata_directory() {
             let dir = create_test_dir();
@@ -590,6 +1270,85 @@ mod tests {
             cleanup_test_dir(&dir);
         }
 
+        #[test]
+        fn test_root_uid_resolves_to_root() {
+            assert_eq!(user_name(0), "root");
+            assert!(!group_name(0).is_empty());
+        }
+
+        #[test]
+        fn test_unresolvable_ids_are_empty() {
+            // Reserved "nobody"-style IDs near u32::MAX are not assigned
+            assert_eq!(user_name(u32::MAX - 7), "");
+            assert_eq!(group_name(u32::MAX - 7), "");
+        }
+
+        #[test]
+        fn test_owner_name_matches_uid() {
+            let dir = create_test_dir();
+            let metadata = get_file_metadata(dir.to_str().unwrap()).unwrap();
+
+            let uid: u32 = metadata.file_owner.parse().unwrap();
+            assert_eq!(metadata.file_owner_name, user_name(uid));
+
+            // Root-owned paths report both forms
+            let root = get_file_metadata("/").unwrap();
+            if root.file_owner == "0" {
+                assert_eq!(root.file_owner_name, "root");
+            }
+
+            cleanup_test_dir(&dir);
+        }
+
+        #[test]
+        fn test_symlink_reports_target() {
+            let dir = create_named_test_dir("symlink");
+            let target = dir.join("target.txt");
+            fs::write(&target, "content").unwrap();
+            let link = dir.join("link");
+            std::os::unix::fs::symlink(&target, &link).unwrap();
+
+            let metadata = get_file_metadata(link.to_str().unwrap()).unwrap();
+
+            assert!(metadata.exists);
+            assert!(metadata.is_symlink);
+            assert_eq!(metadata.symlink_target, target.to_str().unwrap());
+            // Other fields describe the target
+            assert_eq!(metadata.file_size, 7);
+
+            cleanup_test_dir(&dir);
+        }
+
+        #[test]
+        fn test_broken_symlink() {
+            let dir = create_named_test_dir("broken_symlink");
+            let link = dir.join("link");
+            std::os::unix::fs::symlink("missing.txt", &link).unwrap();
+
+            let metadata = get_file_metadata(link.to_str().unwrap()).unwrap();
+
+            assert!(!metadata.exists);
+            assert!(metadata.is_symlink);
+            assert_eq!(metadata.symlink_target, "missing.txt");
+
+            cleanup_test_dir(&dir);
+        }
+
+        #[test]
+        fn test_regular_file_is_not_symlink() {
+            let dir = create_named_test_dir("regular");
+            let file_path = dir.join("file.txt");
+            fs::write(&file_path, "content").unwrap();
+
+            let metadata = get_file_metadata(file_path.to_str().unwrap()).unwrap();
+
+            assert!(metadata.exists);
+            assert!(!metadata.is_symlink);
+            assert!(metadata.symlink_target.is_empty());
+
+            cleanup_test_dir(&dir);
+        }
+
         #[test]
         fn test_windows_fields_false_on_unix() {
             let dir = create_test_dir();
@@ -649,5 +1408,136 @@ mod tests {
             let wide = to_wide_string("test");
             assert_eq!(wide, vec![116, 101, 115, 116, 0]); // "test" + null terminator
         }
+
+        #[test]
+        fn test_filetime_to_unix() {
+            let filetime = |ticks: u64| FILETIME {
+                dwLowDateTime: ticks as u32,
+                dwHighDateTime: (ticks >> 32) as u32,
+            };
+            assert_eq!(filetime_to_unix(filetime(FILETIME_UNIX_EPOCH)), 0);
+            // 2024-01-01T00:00:00Z
+            assert_eq!(
+                filetime_to_unix(filetime(FILETIME_UNIX_EPOCH + 1_704_067_200 * 10_000_000)),
+                1_704_067_200
+            );
+            assert_eq!(filetime_to_unix(filetime(0)), 0);
+        }
+
+        #[test]
+        fn test_extended_length_path() {
+            assert_eq!(
+                extended_length_path(r"C:\Windows\System32"),
+                r"\\?\C:\Windows\System32"
+            );
+            assert_eq!(
+                extended_length_path("c:/Program Files//App/./bin/../app.exe"),
+                r"\\?\c:\Program Files\App\app.exe"
+            );
+            assert_eq!(extended_length_path(r"C:\.."), r"\\?\C:\");
+            assert_eq!(
+                extended_length_path(r"\\fileserver\audit\logs\..\policy.txt"),
+                r"\\?\UNC\fileserver\audit\policy.txt"
+            );
+            assert_eq!(
+                extended_length_path("//fileserver/audit/../../x"),
+                r"\\?\UNC\fileserver\audit\x"
+            );
+
+            // Already extended, device, relative and drive-relative paths
+            for path in [
+                r"\\?\C:\Windows",
+                r"\\?\UNC\server\share",
+                r"\\.\PhysicalDrive0",
+                r"config\app.ini",
+                "config/app.ini",
+                r"C:app.ini",
+                r"\Windows",
+                r"\\server",
+            ] {
+                assert_eq!(extended_length_path(path), path);
+            }
+        }
+
+        #[test]
+        fn test_get_metadata_long_path() {
+            // Deeper than MAX_PATH (260 characters); std::fs handles the prefix itself
+            let root = std::env::temp_dir().join(format!("esp_long_{}", std::process::id()));
+            let mut dir = root.clone();
+            while dir.as_os_str().len() < 300 {
+                dir.push("a_deeply_nested_directory_name");
+            }
+            std::fs::create_dir_all(&dir).unwrap();
+            let file = dir.join("file.txt");
+            std::fs::write(&file, "content").unwrap();
+
+            let metadata = get_file_metadata(file.to_str().unwrap()).unwrap();
+            assert!(metadata.exists);
+            assert_eq!(metadata.file_size, 7);
+            assert!(metadata.readable);
+            assert!(!metadata.file_owner.is_empty());
+
+            let _ = std::fs::remove_dir_all(&root);
+        }
+
+        #[test]
+        fn test_strip_extended_prefix() {
+            assert_eq!(strip_extended_prefix(r"\\?\C:\Windows"), r"C:\Windows");
+            assert_eq!(
+                strip_extended_prefix(r"\??\C:\Users\Public"),
+                r"C:\Users\Public"
+            );
+            assert_eq!(
+                strip_extended_prefix(r"\\?\UNC\server\share\dir"),
+                r"\\server\share\dir"
+            );
+            for target in [
+                r"\\?\Volume{0b1c2d3e-0000-0000-0000-100000000000}\",
+                r"..\config\app.ini",
+                r"C:\Windows",
+            ] {
+                assert_eq!(strip_extended_prefix(target), target);
+            }
+        }
+
+        /// Removes a test directory however the test ends
+        struct TempRoot(std::path::PathBuf);
+
+        impl Drop for TempRoot {
+            fn drop(&mut self) {
+                let _ = std::fs::remove_dir_all(&self.0);
+            }
+        }
+
+        // A fixed test command: the allowlisted executor has no shell to
+        // reach `mklink`, which is a cmd builtin
+        #[allow(clippy::disallowed_methods)]
+        #[test]
+        fn test_junction_is_symlink() {
+            let root =
+                TempRoot(std::env::temp_dir().join(format!("esp_junction_{}", std::process::id())));
+            let target = root.0.join("target");
+            let junction = root.0.join("junction");
+            std::fs::create_dir_all(&target).unwrap();
+
+            // Junctions need no privilege, unlike symbolic links
+            let status = std::process::Command::new("cmd")
+                .args(["/C", "mklink", "/J"])
+                .arg(&junction)
+                .arg(&target)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success());
+
+            let metadata = get_file_metadata(junction.to_str().unwrap()).unwrap();
+            assert!(metadata.exists);
+            assert!(metadata.is_symlink);
+            assert_eq!(metadata.symlink_target, target.to_str().unwrap());
+
+            let metadata = get_file_metadata(target.to_str().unwrap()).unwrap();
+            assert!(!metadata.is_symlink);
+            assert!(metadata.symlink_target.is_empty());
+        }
     }
 }
@@ -0,0 +1,1029 @@
+//! Linux firewall ruleset operations
+//!
+//! Reads the host firewall from either backend found on Linux fleets:
+//! - **nftables**: `nft -j list ruleset` (preferred, JSON)
+//! - **iptables (legacy)**: `iptables-save` (fallback), or `iptables -S`
+//!   where only `iptables` is installed
+//!
+//! Both are parsed into a common [`FirewallRuleset`] so policies can be
+//! written once regardless of the backend in use.
+//!
+//! ## Chain Policies
+//!
+//! [`FirewallRuleset::chain_policy`] normalizes a chain's default verdict to
+//! `accept`, `drop` or `reject`. A chain whose policy accepts but whose rules
+//! include a catch-all `drop` or `reject` (as firewalld writes) denies by
+//! default, so the first rule that matches every packet decides before the
+//! policy does.
+//!
+//! ## Exposure Evaluation
+//!
+//! [`FirewallRuleset::allows_from_anywhere`] answers "would a new connection
+//! from an arbitrary address reach port N?" by walking each input chain in
+//! order, first match wins:
+//!
+//! - Rules restricted to a source address, to loopback, or to non-new
+//!   conntrack states do not apply to an arbitrary client
+//! - Rules with matches that cannot be modelled (negations, jumps, unknown
+//!   expressions) are skipped
+//! - If no rule decides, the chain policy applies
+//!
+//! Every input chain of an address family must accept for the port to be
+//! reachable over that family. The result is `true` if any family accepts.
+
+use execution_engine::strategies::SystemCommandExecutor;
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+
+/// Candidate locations for the nft binary
+const NFT_PATHS: &[&str] = &["/usr/sbin/nft", "/sbin/nft"];
+
+/// Candidate locations for the iptables-save binary
+const IPTABLES_SAVE_PATHS: &[&str] = &["/usr/sbin/iptables-save", "/sbin/iptables-save"];
+
+/// Candidate locations for the iptables binary
+const IPTABLES_PATHS: &[&str] = &["/usr/sbin/iptables", "/sbin/iptables"];
+
+/// iptables arguments that list the filter table's rules
+pub const IPTABLES_LIST_ARGS: [&str; 1] = ["-S"];
+
+/// Commands the firewall rule executor allows
+pub const FIREWALL_COMMANDS: &[&str] = &[
+    "nft",                     // Standard PATH lookup
+    "/usr/sbin/nft",           // Merged /usr location
+    "/sbin/nft",               // Traditional location
+    "iptables-save",           // Standard PATH lookup
+    "/usr/sbin/iptables-save", // Merged /usr location
+    "/sbin/iptables-save",     // Traditional location
+    "iptables",                // Standard PATH lookup
+    "/usr/sbin/iptables",      // Merged /usr location
+    "/sbin/iptables",          // Traditional location
+];
+
+/// Create command executor configured for firewall rule collection
+///
+/// Whitelist includes:
+/// - nft: nftables CLI
+/// - iptables-save: Legacy iptables dump
+/// - iptables: Legacy iptables rule listing (`-S`)
+pub fn create_firewall_command_executor() -> SystemCommandExecutor {
+    let mut executor = SystemCommandExecutor::with_timeout(Duration::from_secs(10));
+
+    executor.allow_commands(FIREWALL_COMMANDS);
+
+    executor
+}
+
+/// Find the nft binary path
+pub fn find_nft() -> &'static str {
+    find_binary(NFT_PATHS, "nft")
+}
+
+/// Find the iptables-save binary path
+pub fn find_iptables_save() -> &'static str {
+    find_binary(IPTABLES_SAVE_PATHS, "iptables-save")
+}
+
+/// Find the legacy iptables source: `iptables-save`, or `iptables -S` when
+/// only `iptables` is installed
+///
+/// Returns the command and its arguments.
+pub fn find_iptables_source() -> (&'static str, &'static [&'static str]) {
+    if !is_installed(IPTABLES_SAVE_PATHS) && is_installed(IPTABLES_PATHS) {
+        return (find_binary(IPTABLES_PATHS, "iptables"), &IPTABLES_LIST_ARGS);
+    }
+    (find_iptables_save(), &[])
+}
+
+/// Whether nft, iptables-save or iptables is installed in a standard location
+pub fn firewall_tools_installed() -> bool {
+    [NFT_PATHS, IPTABLES_SAVE_PATHS, IPTABLES_PATHS]
+        .into_iter()
+        .any(is_installed)
+}
+
+fn is_installed(paths: &[&str]) -> bool {
+    paths.iter().any(|path| std::path::Path::new(path).exists())
+}
+
+fn find_binary(paths: &[&'static str], fallback: &'static str) -> &'static str {
+    for path in paths {
+        if std::path::Path::new(path).exists() {
+            return path;
+        }
+    }
+    fallback // Fall back to PATH lookup
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Error type for firewall ruleset parsing
+#[derive(Debug)]
+pub enum FirewallRuleError {
+    /// Output was not valid JSON
+    InvalidJson(String),
+
+    /// JSON was valid but not an nftables ruleset
+    InvalidRuleset(String),
+}
+
+impl std::fmt::Display for FirewallRuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidJson(msg) => write!(f, "Invalid nft JSON: {}", msg),
+            Self::InvalidRuleset(msg) => write!(f, "Invalid nft ruleset: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for FirewallRuleError {}
+
+/// Result type for firewall ruleset parsing
+pub type FirewallRuleResult<T> = Result<T, FirewallRuleError>;
+
+/// Firewall backend the ruleset was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FirewallBackend {
+    Nftables,
+    Iptables,
+}
+
+impl FirewallBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Nftables => "nftables",
+            Self::Iptables => "iptables",
+        }
+    }
+}
+
+/// Inclusive destination port range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PortRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl PortRange {
+    pub fn contains(&self, port: u16) -> bool {
+        self.start <= port && port <= self.end
+    }
+}
+
+/// A base chain attached to a netfilter hook
+#[derive(Debug, Clone, Serialize)]
+pub struct FirewallChain {
+    pub family: String,
+    pub table: String,
+    pub name: String,
+    pub hook: String,
+    /// Lowercase policy (`accept` / `drop`)
+    pub policy: String,
+}
+
+impl FirewallChain {
+    /// Whether `chain` names this chain, by name or by hook, ignoring case
+    fn is_named(&self, chain: &str) -> bool {
+        self.name.eq_ignore_ascii_case(chain) || self.hook.eq_ignore_ascii_case(chain)
+    }
+
+    /// Whether `rule` belongs to this chain
+    fn holds(&self, rule: &FirewallRule) -> bool {
+        rule.family == self.family && rule.table == self.table && rule.chain == self.name
+    }
+}
+
+/// A single firewall rule in backend-neutral form
+#[derive(Debug, Clone, Serialize)]
+pub struct FirewallRule {
+    pub family: String,
+    pub table: String,
+    pub chain: String,
+    /// Lowercase verdict: `accept`, `drop`, `reject`, `jump`, `return`, or
+    /// `none` for rules without a terminating verdict (counters, logging)
+    pub action: String,
+    pub protocol: Option<String>,
+    pub source: Option<String>,
+    pub destination: Option<String>,
+    pub in_interface: Option<String>,
+    pub ct_state: Vec<String>,
+    pub dports: Vec<PortRange>,
+    /// Rule has matches that are not modelled (negations, unknown expressions)
+    pub conditional: bool,
+}
+
+impl FirewallRule {
+    fn new(family: &str, table: &str, chain: &str) -> Self {
+        Self {
+            family: family.to_string(),
+            table: table.to_string(),
+            chain: chain.to_string(),
+            action: "none".to_string(),
+            protocol: None,
+            source: None,
+            destination: None,
+            in_interface: None,
+            ct_state: Vec::new(),
+            dports: Vec::new(),
+            conditional: false,
+        }
+    }
+
+    /// Render the rule in a backend-neutral text form
+    ///
+    /// ```text
+    /// input tcp dport 22 accept
+    /// input saddr 10.0.0.0/8 tcp dport 5432 accept
+    /// input ct state established,related accept
+    /// ```
+    pub fn to_text(&self) -> String {
+        let mut parts = vec![self.chain.to_lowercase()];
+        if let Some(ref iface) = self.in_interface {
+            parts.push(format!("iif {}", iface));
+        }
+        if let Some(ref src) = self.source {
+            parts.push(format!("saddr {}", src));
+        }
+        if let Some(ref dst) = self.destination {
+            parts.push(format!("daddr {}", dst));
+        }
+        if !self.ct_state.is_empty() {
+            parts.push(format!("ct state {}", self.ct_state.join(",")));
+        }
+        if let Some(ref proto) = self.protocol {
+            parts.push(proto.clone());
+        }
+        if !self.dports.is_empty() {
+            let ports: Vec<String> = self
+                .dports
+                .iter()
+                .map(|r| {
+                    if r.start == r.end {
+                        r.start.to_string()
+                    } else {
+                        format!("{}-{}", r.start, r.end)
+                    }
+                })
+                .collect();
+            parts.push(format!("dport {}", ports.join(",")));
+        }
+        parts.push(self.action.clone());
+        parts.join(" ")
+    }
+
+    /// Whether the rule matches every packet
+    fn is_catch_all(&self) -> bool {
+        !self.conditional
+            && self.protocol.is_none()
+            && self.source.is_none()
+            && self.destination.is_none()
+            && self.in_interface.is_none()
+            && self.ct_state.is_empty()
+            && self.dports.is_empty()
+    }
+
+    /// Whether the rule applies to a new connection from any source address
+    /// on at least one external interface
+    fn matches_any_source(&self, port: u16, protocol: &str) -> bool {
+        !self.conditional
+            && self.source.as_deref().is_none_or(is_any_address)
+            && self.in_interface.as_deref() != Some("lo")
+            && (self.ct_state.is_empty() || self.ct_state.iter().any(|s| s == "new"))
+            && self.protocol.as_deref().is_none_or(|p| p == protocol)
+            && (self.dports.is_empty() || self.dports.iter().any(|r| r.contains(port)))
+    }
+}
+
+/// Parsed firewall ruleset
+#[derive(Debug, Clone, Serialize)]
+pub struct FirewallRuleset {
+    pub backend: FirewallBackend,
+    pub chains: Vec<FirewallChain>,
+    pub rules: Vec<FirewallRule>,
+}
+
+impl FirewallRuleset {
+    /// Input base chains
+    fn input_chains(&self) -> impl Iterator<Item = &FirewallChain> {
+        self.chains.iter().filter(|c| c.hook == "input")
+    }
+
+    /// Effective default policy for a hook
+    ///
+    /// Returns the most restrictive policy across all base chains on the
+    /// hook, or `accept` when no chain filters it.
+    pub fn default_policy(&self, hook: &str) -> String {
+        let restrictive = self
+            .chains
+            .iter()
+            .filter(|c| c.hook == hook)
+            .any(|c| c.policy != "accept");
+        if restrictive {
+            "drop".to_string()
+        } else {
+            "accept".to_string()
+        }
+    }
+
+    /// Rules in the chains named `chain`, by name or by base chain hook,
+    /// ignoring case (`input` finds nft `input` and iptables `INPUT`)
+    pub fn chain_rules(&self, chain: &str) -> Vec<&FirewallRule> {
+        let base_chains: Vec<&FirewallChain> =
+            self.chains.iter().filter(|c| c.is_named(chain)).collect();
+        self.rules
+            .iter()
+            .filter(|rule| {
+                rule.chain.eq_ignore_ascii_case(chain) || base_chains.iter().any(|c| c.holds(rule))
+            })
+            .collect()
+    }
+
+    /// Normalized default verdict of the base chains named `chain`: `accept`,
+    /// `drop` or `reject`
+    ///
+    /// Each chain's verdict is that of its first catch-all rule, if it ends
+    /// in `accept`, `drop` or `reject`, and otherwise its policy. Across
+    /// several chains (e.g. `ip` and `ip6` tables) the most restrictive wins.
+    /// A built-in hook no chain filters accepts; any other name that is not a
+    /// base chain has no default (`None`).
+    pub fn chain_policy(&self, chain: &str) -> Option<String> {
+        let verdicts: Vec<&str> = self
+            .chains
+            .iter()
+            .filter(|c| c.is_named(chain))
+            .map(|c| {
+                self.rules
+                    .iter()
+                    .filter(|rule| c.holds(rule) && rule.is_catch_all())
+                    .map(|rule| rule.action.as_str())
+                    .find(|action| matches!(*action, "accept" | "drop" | "reject"))
+                    .unwrap_or(c.policy.as_str())
+            })
+            .collect();
+
+        if verdicts.is_empty() {
+            let hook = ["input", "forward", "output"]
+                .into_iter()
+                .any(|hook| hook.eq_ignore_ascii_case(chain));
+            return hook.then(|| "accept".to_string());
+        }
+        let verdict = ["drop", "reject"]
+            .into_iter()
+            .find(|verdict| verdicts.contains(verdict))
+            .unwrap_or("accept");
+        Some(verdict.to_string())
+    }
+
+    /// Whether a new connection from any address can reach `port`
+    pub fn allows_from_anywhere(&self, port: u16, protocol: &str) -> bool {
+        let input_chains: Vec<&FirewallChain> = self.input_chains().collect();
+        if input_chains.is_empty() {
+            return true; // Nothing filters input
+        }
+
+        ["ipv4", "ipv6"].iter().any(|addr_family| {
+            let applicable: Vec<&&FirewallChain> = input_chains
+                .iter()
+                .filter(|c| family_applies(&c.family, addr_family))
+                .collect();
+            !applicable.is_empty()
+                && applicable
+                    .iter()
+                    .all(|chain| self.chain_accepts(chain, port, protocol))
+        })
+    }
+
+    /// Evaluate a single base chain for a new connection from anywhere
+    fn chain_accepts(&self, chain: &FirewallChain, port: u16, protocol: &str) -> bool {
+        let rules = self.rules.iter().filter(|r| {
+            r.family == chain.family && r.table == chain.table && r.chain == chain.name
+        });
+
+        for rule in rules {
+            if !rule.matches_any_source(port, protocol) {
+                continue;
+            }
+            match rule.action.as_str() {
+                "accept" => return true,
+                // A drop only covers every client when not tied to one interface
+                "drop" | "reject" if rule.in_interface.is_none() => return false,
+                _ => {}
+            }
+        }
+
+        chain.policy == "accept"
+    }
+
+    /// Rules rendered in backend-neutral text form
+    pub fn rule_texts(&self) -> Vec<String> {
+        self.rules.iter().map(FirewallRule::to_text).collect()
+    }
+}
+
+/// Whether a netfilter family handles traffic of the given address family
+fn family_applies(family: &str, addr_family: &str) -> bool {
+    match family {
+        "inet" => true,
+        "ip" => addr_family == "ipv4",
+        "ip6" => addr_family == "ipv6",
+        _ => false,
+    }
+}
+
+/// Whether an address matches every source
+fn is_any_address(addr: &str) -> bool {
+    matches!(addr, "0.0.0.0/0" | "::/0")
+}
+
+// ============================================================================
+// nftables JSON parsing
+// ============================================================================
+
+/// Parse `nft -j list ruleset` output
+pub fn parse_nft_ruleset(output: &str) -> FirewallRuleResult<FirewallRuleset> {
+    let json: Value =
+        serde_json::from_str(output).map_err(|e| FirewallRuleError::InvalidJson(e.to_string()))?;
+
+    let items = json
+        .get("nftables")
+        .and_then(Value::as_array)
+        .ok_or_else(|| {
+            FirewallRuleError::InvalidRuleset("missing top-level 'nftables' array".to_string())
+        })?;
+
+    let mut ruleset = FirewallRuleset {
+        backend: FirewallBackend::Nftables,
+        chains: Vec::new(),
+        rules: Vec::new(),
+    };
+
+    for item in items {
+        if let Some(chain) = item.get("chain") {
+            // Only base chains (with a hook) carry a policy
+            if let Some(hook) = chain.get("hook").and_then(Value::as_str) {
+                ruleset.chains.push(FirewallChain {
+                    family: json_str(chain, "family"),
+                    table: json_str(chain, "table"),
+                    name: json_str(chain, "name"),
+                    hook: hook.to_string(),
+                    policy: chain
+                        .get("policy")
+                        .and_then(Value::as_str)
+                        .unwrap_or("accept")
+                        .to_lowercase(),
+                });
+            }
+        } else if let Some(rule) = item.get("rule") {
+            ruleset.rules.push(parse_nft_rule(rule));
+        }
+    }
+
+    Ok(ruleset)
+}
+
+/// Whether an nft ruleset contains iptables-nft compatibility expressions
+///
+/// Rules created through `iptables-nft` appear as opaque `xt` expressions in
+/// nft JSON; `iptables-save` describes them faithfully.
+pub fn nft_has_xt_expressions(output: &str) -> bool {
+    output.contains("\"xt\"")
+}
+
+fn json_str(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn parse_nft_rule(rule: &Value) -> FirewallRule {
+    let mut parsed = FirewallRule::new(
+        &json_str(rule, "family"),
+        &json_str(rule, "table"),
+        &json_str(rule, "chain"),
+    );
+
+    let exprs = rule
+        .get("expr")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    for expr in &exprs {
+        let Some((kind, body)) = expr.as_object().and_then(|o| o.iter().next()) else {
+            continue;
+        };
+        match kind.as_str() {
+            "match" => apply_nft_match(body, &mut parsed),
+            "accept" | "drop" | "reject" | "return" => parsed.action = kind.clone(),
+            "jump" | "goto" => parsed.action = "jump".to_string(),
+            // Non-terminating statements
+            "counter" | "log" | "limit" | "comment" => {}
+            _ => parsed.conditional = true,
+        }
+    }
+
+    parsed
+}
+
+fn apply_nft_match(body: &Value, rule: &mut FirewallRule) {
+    let op = body.get("op").and_then(Value::as_str).unwrap_or("==");
+    let (Some(left), Some(right)) = (body.get("left"), body.get("right")) else {
+        rule.conditional = true;
+        return;
+    };
+
+    if op != "==" && op != "in" {
+        rule.conditional = true;
+        return;
+    }
+
+    if let Some(payload) = left.get("payload") {
+        let protocol = payload.get("protocol").and_then(Value::as_str);
+        let field = payload.get("field").and_then(Value::as_str);
+        match (protocol, field) {
+            (Some("ip" | "ip6"), Some("saddr")) => rule.source = nft_address(right),
+            (Some("ip" | "ip6"), Some("daddr")) => rule.destination = nft_address(right),
+            (Some(proto), Some("dport")) => {
+                if proto != "th" {
+                    rule.protocol = Some(proto.to_string());
+                }
+                rule.dports = nft_ports(right);
+                if rule.dports.is_empty() {
+                    rule.conditional = true;
+                }
+            }
+            _ => rule.conditional = true,
+        }
+    } else if let Some(meta) = left.get("meta") {
+        match meta.get("key").and_then(Value::as_str) {
+            Some("l4proto") => match right.as_str() {
+                Some(proto) => rule.protocol = Some(proto.to_string()),
+                None => rule.conditional = true,
+            },
+            Some("iifname" | "iif") => match right.as_str() {
+                Some(iface) => rule.in_interface = Some(iface.to_string()),
+                None => rule.conditional = true,
+            },
+            // Family selection within inet tables does not restrict sources
+            Some("nfproto") => {}
+            _ => rule.conditional = true,
+        }
+    } else if left
+        .get("ct")
+        .and_then(|ct| ct.get("key"))
+        .and_then(Value::as_str)
+        == Some("state")
+    {
+        rule.ct_state = nft_string_list(right);
+    } else {
+        rule.conditional = true;
+    }
+}
+
+/// Parse an nft address value (`"10.0.0.1"` or `{"prefix": {...}}`)
+fn nft_address(value: &Value) -> Option<String> {
+    if let Some(addr) = value.as_str() {
+        return Some(addr.to_string());
+    }
+    let prefix = value.get("prefix")?;
+    let addr = prefix.get("addr").and_then(Value::as_str)?;
+    let len = prefix.get("len").and_then(Value::as_u64)?;
+    Some(format!("{}/{}", addr, len))
+}
+
+/// Parse an nft port value (number, `{"range": [a, b]}`, or `{"set": [...]}`)
+fn nft_ports(value: &Value) -> Vec<PortRange> {
+    if let Some(port) = value.as_u64().and_then(|p| u16::try_from(p).ok()) {
+        return vec![PortRange {
+            start: port,
+            end: port,
+        }];
+    }
+    if let Some(range) = value.get("range").and_then(Value::as_array) {
+        let bounds: Vec<u16> = range
+            .iter()
+            .filter_map(|v| v.as_u64().and_then(|p| u16::try_from(p).ok()))
+            .collect();
+        return match bounds.as_slice() {
+            [start, end] => vec![PortRange {
+                start: *start,
+                end: *end,
+            }],
+            _ => Vec::new(),
+        };
+    }
+    if let Some(set) = value.get("set").and_then(Value::as_array) {
+        return set.iter().flat_map(nft_ports).collect();
+    }
+    Vec::new()
+}
+
+/// Parse an nft string or string set (`"new"` or `["established", "related"]`)
+fn nft_string_list(value: &Value) -> Vec<String> {
+    if let Some(s) = value.as_str() {
+        return vec![s.to_string()];
+    }
+    let items = value
+        .as_array()
+        .or_else(|| value.get("set").and_then(Value::as_array));
+    items
+        .map(|arr| {
+            arr.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// iptables-save parsing
+// ============================================================================
+
+/// Parse `iptables-save` or `iptables -S` output
+///
+/// Only the `filter` table is considered; `iptables -S` output, which has
+/// no table headers, lists the filter table. Built-in chains become base
+/// chains on the matching hook (`:INPUT DROP` or `-P INPUT DROP`);
+/// user-defined chains carry rules but no policy.
+pub fn parse_iptables_save(output: &str) -> FirewallRuleset {
+    let mut ruleset = FirewallRuleset {
+        backend: FirewallBackend::Iptables,
+        chains: Vec::new(),
+        rules: Vec::new(),
+    };
+
+    let mut table = "filter".to_string();
+
+    for line in output.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line == "COMMIT" {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('*') {
+            table = name.to_string();
+            continue;
+        }
+
+        if table != "filter" {
+            continue;
+        }
+
+        if let Some(decl) = line.strip_prefix(':').or_else(|| line.strip_prefix("-P ")) {
+            let mut parts = decl.split_whitespace();
+            if let (Some(name), Some(policy)) = (parts.next(), parts.next()) {
+                if let Some(hook) = builtin_hook(name) {
+                    ruleset.chains.push(FirewallChain {
+                        family: "ip".to_string(),
+                        table: table.clone(),
+                        name: name.to_string(),
+                        hook: hook.to_string(),
+                        policy: policy.to_lowercase(),
+                    });
+                }
+            }
+            continue;
+        }
+
+        if line.starts_with("-A ") {
+            ruleset.rules.push(parse_iptables_rule(line, &table));
+        }
+    }
+
+    ruleset
+}
+
+/// Hook for a built-in iptables filter chain
+fn builtin_hook(chain: &str) -> Option<&'static str> {
+    match chain {
+        "INPUT" => Some("input"),
+        "FORWARD" => Some("forward"),
+        "OUTPUT" => Some("output"),
+        _ => None,
+    }
+}
+
+fn parse_iptables_rule(line: &str, table: &str) -> FirewallRule {
+    let tokens = tokenize(line);
+    let chain = tokens.get(1).map(String::as_str).unwrap_or_default();
+    let mut rule = FirewallRule::new("ip", table, chain);
+
+    let mut i = 2;
+    while let Some(token) = tokens.get(i) {
+        let value = tokens.get(i + 1).cloned();
+        let mut consumed = 1;
+
+        match token.as_str() {
+            "!" => rule.conditional = true,
+            "-p" | "--protocol" => {
+                rule.protocol = value.map(|v| v.to_lowercase());
+                consumed = 2;
+            }
+            "-s" | "--source" => {
+                rule.source = value;
+                consumed = 2;
+            }
+            "-d" | "--destination" => {
+                rule.destination = value;
+                consumed = 2;
+            }
+            "-i" | "--in-interface" => {
+                rule.in_interface = value;
+                consumed = 2;
+            }
+            "--dport" | "--destination-port" | "--dports" | "--destination-ports" => {
+                rule.dports = value.as_deref().map(iptables_ports).unwrap_or_default();
+                if rule.dports.is_empty() {
+                    rule.conditional = true;
+                }
+                consumed = 2;
+            }
+            "--state" | "--ctstate" => {
+                rule.ct_state = value
+                    .map(|v| v.split(',').map(|s| s.to_lowercase()).collect())
+                    .unwrap_or_default();
+                consumed = 2;
+            }
+            "-j" | "--jump" | "-g" | "--goto" => {
+                rule.action = match value.as_deref() {
+                    Some("ACCEPT") => "accept".to_string(),
+                    Some("DROP") => "drop".to_string(),
+                    Some("REJECT") => "reject".to_string(),
+                    Some("RETURN") => "return".to_string(),
+                    Some("LOG") | None => "none".to_string(),
+                    Some(_) => "jump".to_string(),
+                };
+                consumed = 2;
+            }
+            // Module loads and comments do not change what the rule matches
+            "-m" | "--match" | "--comment" => consumed = 2,
+            // Target options (e.g. --reject-with) follow the verdict
+            "--reject-with" | "--log-prefix" | "--log-level" => consumed = 2,
+            other if other.starts_with('-') => {
+                rule.conditional = true;
+                // Skip the option's argument, if any
+                if value.as_deref().is_some_and(|v| !v.starts_with('-')) {
+                    consumed = 2;
+                }
+            }
+            _ => {}
+        }
+
+        i += consumed;
+    }
+
+    rule
+}
+
+/// Parse an iptables port spec (`22`, `1000:2000`, `80,443,8000:8080`)
+fn iptables_ports(spec: &str) -> Vec<PortRange> {
+    spec.split(',')
+        .filter_map(|part| {
+            let (start, end) = match part.split_once(':') {
+                Some((s, e)) => (s.parse().ok()?, e.parse().ok()?),
+                None => {
+                    let p = part.parse().ok()?;
+                    (p, p)
+                }
+            };
+            Some(PortRange { start, end })
+        })
+        .collect()
+}
+
+/// Split an iptables-save line into tokens, honouring double quotes
+fn tokenize(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in line.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NFT_RULESET: &str = r#"{"nftables": [
+        {"metainfo": {"version": "1.0.2", "release_name": "Lester Gooch", "json_schema_version": 1}},
+        {"table": {"family": "inet", "name": "filter", "handle": 1}},
+        {"chain": {"family": "inet", "table": "filter", "name": "input", "handle": 1,
+                   "type": "filter", "hook": "input", "prio": 0, "policy": "drop"}},
+        {"chain": {"family": "inet", "table": "filter", "name": "forward", "handle": 2,
+                   "type": "filter", "hook": "forward", "prio": 0, "policy": "drop"}},
+        {"chain": {"family": "inet", "table": "filter", "name": "output", "handle": 3,
+                   "type": "filter", "hook": "output", "prio": 0, "policy": "accept"}},
+        {"rule": {"family": "inet", "table": "filter", "chain": "input", "handle": 4, "expr": [
+            {"match": {"op": "in", "left": {"ct": {"key": "state"}}, "right": ["established", "related"]}},
+            {"accept": null}]}},
+        {"rule": {"family": "inet", "table": "filter", "chain": "input", "handle": 5, "expr": [
+            {"match": {"op": "==", "left": {"meta": {"key": "iifname"}}, "right": "lo"}},
+            {"accept": null}]}},
+        {"rule": {"family": "inet", "table": "filter", "chain": "input", "handle": 6, "expr": [
+            {"match": {"op": "==", "left": {"payload": {"protocol": "ip", "field": "saddr"}},
+                       "right": {"prefix": {"addr": "10.0.0.0", "len": 8}}}},
+            {"match": {"op": "==", "left": {"payload": {"protocol": "tcp", "field": "dport"}}, "right": 22}},
+            {"accept": null}]}},
+        {"rule": {"family": "inet", "table": "filter", "chain": "input", "handle": 7, "expr": [
+            {"match": {"op": "==", "left": {"payload": {"protocol": "tcp", "field": "dport"}},
+                       "right": {"set": [80, 443]}}},
+            {"counter": {"packets": 0, "bytes": 0}},
+            {"accept": null}]}}
+    ]}"#;
+
+    const IPTABLES_SAVE: &str = "\
+# Generated by iptables-save v1.8.7 on Mon Jan  1 00:00:00 2024
+*filter
+:INPUT DROP [0:0]
+:FORWARD DROP [0:0]
+:OUTPUT ACCEPT [0:0]
+-A INPUT -m state --state RELATED,ESTABLISHED -j ACCEPT
+-A INPUT -i lo -j ACCEPT
+-A INPUT -p tcp -m tcp --dport 22 -j ACCEPT
+-A INPUT -s 10.0.0.0/8 -p tcp -m multiport --dports 5432,6000:6010 -m comment --comment \"db access\" -j ACCEPT
+-A INPUT -p tcp -m tcp --dport 23 -j REJECT --reject-with icmp-port-unreachable
+COMMIT
+";
+
+    #[test]
+    fn test_parse_nft_ruleset() {
+        let ruleset = parse_nft_ruleset(NFT_RULESET).unwrap();
+        assert_eq!(ruleset.backend, FirewallBackend::Nftables);
+        assert_eq!(ruleset.chains.len(), 3);
+        assert_eq!(ruleset.rules.len(), 4);
+        assert_eq!(ruleset.default_policy("input"), "drop");
+        assert_eq!(ruleset.default_policy("output"), "accept");
+        assert_eq!(
+            ruleset.rules[2].to_text(),
+            "input saddr 10.0.0.0/8 tcp dport 22 accept"
+        );
+    }
+
+    #[test]
+    fn test_nft_allows_from_anywhere() {
+        let ruleset = parse_nft_ruleset(NFT_RULESET).unwrap();
+        assert!(ruleset.allows_from_anywhere(443, "tcp"));
+        assert!(!ruleset.allows_from_anywhere(22, "tcp")); // source restricted
+        assert!(!ruleset.allows_from_anywhere(443, "udp"));
+        assert!(!ruleset.allows_from_anywhere(3306, "tcp"));
+    }
+
+    #[test]
+    fn test_parse_nft_invalid() {
+        assert!(matches!(
+            parse_nft_ruleset("not json"),
+            Err(FirewallRuleError::InvalidJson(_))
+        ));
+        assert!(matches!(
+            parse_nft_ruleset("{}"),
+            Err(FirewallRuleError::InvalidRuleset(_))
+        ));
+    }
+
+    #[test]
+    fn test_empty_nft_ruleset_allows_everything() {
+        let ruleset = parse_nft_ruleset(r#"{"nftables": [{"metainfo": {}}]}"#).unwrap();
+        assert_eq!(ruleset.default_policy("input"), "accept");
+        assert!(ruleset.allows_from_anywhere(22, "tcp"));
+    }
+
+    #[test]
+    fn test_parse_iptables_save() {
+        let ruleset = parse_iptables_save(IPTABLES_SAVE);
+        assert_eq!(ruleset.backend, FirewallBackend::Iptables);
+        assert_eq!(ruleset.default_policy("input"), "drop");
+        assert_eq!(ruleset.rules.len(), 5);
+        assert_eq!(
+            ruleset.rules[0].to_text(),
+            "input ct state related,established accept"
+        );
+        assert_eq!(
+            ruleset.rules[3].to_text(),
+            "input saddr 10.0.0.0/8 tcp dport 5432,6000-6010 accept"
+        );
+    }
+
+    #[test]
+    fn test_iptables_allows_from_anywhere() {
+        let ruleset = parse_iptables_save(IPTABLES_SAVE);
+        assert!(ruleset.allows_from_anywhere(22, "tcp"));
+        assert!(!ruleset.allows_from_anywhere(23, "tcp"));
+        assert!(!ruleset.allows_from_anywhere(5432, "tcp")); // source restricted
+        assert!(!ruleset.allows_from_anywhere(80, "tcp"));
+    }
+
+    #[test]
+    fn test_first_match_wins() {
+        let save = "\
+*filter
+:INPUT ACCEPT [0:0]
+-A INPUT -p tcp --dport 22 -j DROP
+-A INPUT -p tcp --dport 22 -j ACCEPT
+COMMIT
+";
+        let ruleset = parse_iptables_save(save);
+        assert!(!ruleset.allows_from_anywhere(22, "tcp"));
+        assert!(ruleset.allows_from_anywhere(80, "tcp")); // policy accept
+    }
+
+    #[test]
+    fn test_negated_rules_are_conditional() {
+        let save = "\
+*filter
+:INPUT ACCEPT [0:0]
+-A INPUT ! -s 10.0.0.0/8 -p tcp --dport 22 -j DROP
+COMMIT
+";
+        let ruleset = parse_iptables_save(save);
+        assert!(ruleset.rules[0].conditional);
+        assert!(ruleset.allows_from_anywhere(22, "tcp"));
+    }
+
+    #[test]
+    fn test_non_filter_tables_ignored() {
+        let save = "\
+*nat
+:PREROUTING ACCEPT [0:0]
+-A PREROUTING -p tcp --dport 80 -j REDIRECT --to-ports 8080
+COMMIT
+";
+        let ruleset = parse_iptables_save(save);
+        assert!(ruleset.rules.is_empty());
+        assert!(ruleset.chains.is_empty());
+    }
+
+    #[test]
+    fn test_parse_iptables_list_rules() {
+        // `iptables -S` output has no table headers and declares policies
+        // with -P
+        let list = "\
+-P INPUT DROP
+-P FORWARD DROP
+-P OUTPUT ACCEPT
+-N LOGDROP
+-A INPUT -i lo -j ACCEPT
+-A INPUT -p tcp -m tcp --dport 22 -j ACCEPT
+-A LOGDROP -j DROP
+";
+        let ruleset = parse_iptables_save(list);
+        assert_eq!(ruleset.chains.len(), 3);
+        assert_eq!(ruleset.chain_policy("INPUT").as_deref(), Some("drop"));
+        assert_eq!(ruleset.chain_policy("output").as_deref(), Some("accept"));
+        assert_eq!(ruleset.chain_rules("input").len(), 2);
+        assert_eq!(ruleset.chain_rules("LOGDROP").len(), 1);
+        // User-defined chains have no default verdict
+        assert_eq!(ruleset.chain_policy("LOGDROP"), None);
+        assert!(ruleset.allows_from_anywhere(22, "tcp"));
+    }
+
+    #[test]
+    fn test_catch_all_rule_overrides_policy() {
+        // firewalld keeps policy accept and ends the chain with a reject
+        let save = "\
+*filter
+:INPUT ACCEPT [0:0]
+:FORWARD ACCEPT [0:0]
+-A INPUT -m conntrack --ctstate RELATED,ESTABLISHED -j ACCEPT
+-A INPUT -p tcp --dport 22 -j ACCEPT
+-A INPUT -j REJECT --reject-with icmp-host-prohibited
+COMMIT
+";
+        let ruleset = parse_iptables_save(save);
+        assert_eq!(ruleset.chain_policy("input").as_deref(), Some("reject"));
+        assert_eq!(ruleset.chain_policy("forward").as_deref(), Some("accept"));
+
+        // No chain on a built-in hook: nothing filters, so traffic is accepted
+        let ruleset = parse_nft_ruleset(NFT_RULESET).unwrap();
+        assert_eq!(ruleset.chain_policy("input").as_deref(), Some("drop"));
+        let empty = parse_nft_ruleset(r#"{"nftables": []}"#).unwrap();
+        assert_eq!(empty.chain_policy("input").as_deref(), Some("accept"));
+        assert_eq!(empty.chain_policy("docker"), None);
+    }
+}
@@ -0,0 +1,376 @@
+//! Glob expansion for file object paths
+//!
+//! Expands a path pattern into the existing paths it matches, sorted.
+//!
+//! ## Syntax
+//!
+//! Wildcards apply within one path component:
+//! - `*` matches any run of characters, `?` any one character
+//! - `[abc]`, `[a-z]` match one listed character; `[!abc]` negates
+//!
+//! A component that is exactly `**` matches any number of directories,
+//! including none. As in shells, wildcards do not match a leading `.`
+//! unless the pattern component starts with one, and `**` does not descend
+//! into dot-directories. Matching is case-insensitive on Windows.
+//!
+//! `**` follows symlinked directories but visits each real directory at
+//! most once, so symlink loops terminate. Other wildcards only read the
+//! directories named by the pattern, which bounds the walk by its depth.
+
+use std::collections::HashSet;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+/// Upper bound on the paths one pattern may match
+pub const MAX_GLOB_MATCHES: usize = 10_000;
+
+/// Error type for glob expansion
+#[derive(Debug)]
+pub enum GlobError {
+    /// A directory the pattern needs could not be read
+    AccessDenied(String),
+
+    /// A directory could not be read for another reason
+    ReadFailed(String, String),
+
+    /// The pattern matched more than `MAX_GLOB_MATCHES` paths
+    TooManyMatches(String),
+}
+
+impl std::fmt::Display for GlobError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AccessDenied(path) => write!(f, "Permission denied reading {}", path),
+            Self::ReadFailed(path, msg) => write!(f, "Cannot read directory {}: {}", path, msg),
+            Self::TooManyMatches(pattern) => write!(
+                f,
+                "Pattern '{}' matches more than {} paths",
+                pattern, MAX_GLOB_MATCHES
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GlobError {}
+
+/// Result type for glob expansion
+pub type GlobResult<T> = Result<T, GlobError>;
+
+/// Whether `path` contains wildcard characters (`*`, `?`, `[`)
+pub fn has_glob_metacharacters(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+/// Expand `pattern` into the paths it matches, sorted
+///
+/// Matches must exist; a pattern matching nothing gives an empty list.
+/// Directories that do not exist are skipped, but a directory that cannot
+/// be read is an error, since its matches would be silently missed.
+pub fn expand_glob(pattern: &str) -> GlobResult<Vec<PathBuf>> {
+    let components: Vec<Component> = Path::new(pattern).components().collect();
+    let mut candidates = vec![PathBuf::new()];
+
+    for (index, component) in components.iter().enumerate() {
+        let last = index + 1 == components.len();
+        let name = match component {
+            Component::Normal(name) => name.to_string_lossy(),
+            // Prefix, root, `.` and `..` are used as written
+            other => {
+                for candidate in &mut candidates {
+                    candidate.push(other.as_os_str());
+                }
+                continue;
+            }
+        };
+
+        let mut next = Vec::new();
+        for candidate in &candidates {
+            if name == "**" {
+                descendant_dirs(candidate, &mut next)?;
+            } else if has_glob_metacharacters(&name) {
+                matching_entries(candidate, &name, &mut next)?;
+            } else {
+                next.push(candidate.join(name.as_ref()));
+            }
+            if next.len() > MAX_GLOB_MATCHES {
+                return Err(GlobError::TooManyMatches(pattern.to_string()));
+            }
+        }
+        // Intermediate matches must be directories to have children
+        candidates = next
+            .into_iter()
+            .filter(|path| {
+                if last {
+                    path.symlink_metadata().is_ok()
+                } else {
+                    path.is_dir()
+                }
+            })
+            .collect();
+    }
+
+    candidates.sort();
+    candidates.dedup();
+    Ok(candidates)
+}
+
+/// Entries of `dir` whose names match `pattern`
+fn matching_entries(dir: &Path, pattern: &str, out: &mut Vec<PathBuf>) -> GlobResult<()> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    for name in read_dir_names(dir)? {
+        let name_chars: Vec<char> = name.chars().collect();
+        if wildcard_match(&pattern, &name_chars) {
+            out.push(dir.join(name));
+        }
+    }
+    Ok(())
+}
+
+/// `dir` and every directory below it, for a `**` component
+fn descendant_dirs(dir: &Path, out: &mut Vec<PathBuf>) -> GlobResult<()> {
+    let mut visited = HashSet::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let real = display_dir(&current)
+            .canonicalize()
+            .unwrap_or_else(|_| current.clone());
+        if !visited.insert(real) {
+            continue;
+        }
+        if out.len() > MAX_GLOB_MATCHES {
+            break;
+        }
+        for name in read_dir_names(&current)? {
+            let child = current.join(&name);
+            if !name.starts_with('.') && child.is_dir() {
+                pending.push(child);
+            }
+        }
+        out.push(current);
+    }
+    Ok(())
+}
+
+/// Names in `dir`; a missing directory has none
+fn read_dir_names(dir: &Path) -> GlobResult<Vec<String>> {
+    let path = display_dir(dir);
+    let entries = match std::fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            return match e.kind() {
+                ErrorKind::NotFound | ErrorKind::NotADirectory => Ok(Vec::new()),
+                ErrorKind::PermissionDenied => {
+                    Err(GlobError::AccessDenied(path.display().to_string()))
+                }
+                _ => Err(GlobError::ReadFailed(
+                    path.display().to_string(),
+                    e.to_string(),
+                )),
+            }
+        }
+    };
+    Ok(entries
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect())
+}
+
+/// Directory to read for a candidate (the current directory for relative patterns)
+fn display_dir(dir: &Path) -> &Path {
+    if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    }
+}
+
+/// Match one path component against a wildcard pattern
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    // Leading dots must be matched literally
+    if name.first() == Some(&'.') && pattern.first() != Some(&'.') {
+        return false;
+    }
+
+    let (mut p, mut n) = (0, 0);
+    // Position after the last `*` and the name position it is retried from
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        let c = name.get(n).copied().unwrap_or_default();
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, n));
+                p += 1;
+                continue;
+            }
+            Some('?') => {
+                p += 1;
+                n += 1;
+                continue;
+            }
+            Some('[') => {
+                if let Some((matched, len)) = match_class(pattern.get(p + 1..).unwrap_or(&[]), c) {
+                    if matched {
+                        p += len + 1;
+                        n += 1;
+                        continue;
+                    }
+                } else if chars_equal('[', c) {
+                    // Unterminated class: `[` is literal
+                    p += 1;
+                    n += 1;
+                    continue;
+                }
+            }
+            Some(&literal) if chars_equal(literal, c) => {
+                p += 1;
+                n += 1;
+                continue;
+            }
+            _ => {}
+        }
+        // Mismatch: let the last `*` absorb one more character
+        match backtrack {
+            Some((star_p, star_n)) => {
+                backtrack = Some((star_p, star_n + 1));
+                p = star_p;
+                n = star_n + 1;
+            }
+            None => return false,
+        }
+    }
+
+    pattern.get(p..).unwrap_or(&[]).iter().all(|&c| c == '*')
+}
+
+/// Match `c` against a character class following `[`
+///
+/// Returns whether it matched and the class length including `]`, or
+/// `None` if the class is not terminated.
+fn match_class(class: &[char], c: char) -> Option<(bool, usize)> {
+    let negated = matches!(class.first(), Some('!') | Some('^'));
+    let start = usize::from(negated);
+    // `]` right after the opening bracket is a member, not the end
+    let end = class
+        .iter()
+        .skip(start + 1)
+        .position(|&ch| ch == ']')
+        .map(|pos| pos + start + 1)?;
+    let members = class.get(start..end)?;
+
+    let mut matched = false;
+    let mut i = 0;
+    while let Some(&first) = members.get(i) {
+        match (members.get(i + 1), members.get(i + 2)) {
+            (Some('-'), Some(&last)) => {
+                if in_range(first, last, c) {
+                    matched = true;
+                }
+                i += 3;
+            }
+            _ => {
+                if chars_equal(first, c) {
+                    matched = true;
+                }
+                i += 1;
+            }
+        }
+    }
+    Some((matched != negated, end + 1))
+}
+
+fn in_range(first: char, last: char, c: char) -> bool {
+    if cfg!(windows) {
+        let c = c.to_ascii_lowercase();
+        (first.to_ascii_lowercase()..=last.to_ascii_lowercase()).contains(&c)
+    } else {
+        (first..=last).contains(&c)
+    }
+}
+
+fn chars_equal(a: char, b: char) -> bool {
+    if cfg!(windows) {
+        a.eq_ignore_ascii_case(&b)
+    } else {
+        a == b
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let name: Vec<char> = name.chars().collect();
+        wildcard_match(&pattern, &name)
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(matches("*", "crontab"));
+        assert!(matches("*.conf", "sshd.conf"));
+        assert!(!matches("*.conf", "sshd.conf.bak"));
+        assert!(matches("a*b*c", "axxbyyc"));
+        assert!(matches("?at", "cat"));
+        assert!(!matches("?at", "at"));
+        assert!(matches("[cb]at", "bat"));
+        assert!(matches("[a-c]at", "bat"));
+        assert!(!matches("[!a-c]at", "bat"));
+        assert!(matches("file[", "file["));
+        // Leading dots are not matched by wildcards
+        assert!(!matches("*", ".hidden"));
+        assert!(matches(".*", ".hidden"));
+    }
+
+    #[test]
+    fn test_has_glob_metacharacters() {
+        assert!(has_glob_metacharacters("/etc/cron.d/*"));
+        assert!(has_glob_metacharacters("/etc/rc?.d"));
+        assert!(!has_glob_metacharacters("/etc/passwd"));
+    }
+
+    #[test]
+    fn test_expand_glob() {
+        let dir = std::env::temp_dir().join(format!("esp_glob_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("a/b")).unwrap();
+        for file in [
+            "one.conf",
+            "two.conf",
+            "skip.txt",
+            "a/three.conf",
+            "a/b/four.conf",
+        ] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        let base = dir.display().to_string();
+
+        let found = expand_glob(&format!("{}/*.conf", base)).unwrap();
+        assert_eq!(found, vec![dir.join("one.conf"), dir.join("two.conf")]);
+
+        let found = expand_glob(&format!("{}/**/*.conf", base)).unwrap();
+        assert_eq!(found.len(), 4);
+
+        assert!(expand_glob(&format!("{}/*.none", base)).unwrap().is_empty());
+        assert!(expand_glob(&format!("{}/missing/*", base))
+            .unwrap()
+            .is_empty());
+
+        // A symlink loop terminates
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&dir, dir.join("a/loop")).unwrap();
+            let found = expand_glob(&format!("{}/**/*.conf", base)).unwrap();
+            assert_eq!(found.len(), 4);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
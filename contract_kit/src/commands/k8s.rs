@@ -5,6 +5,13 @@
 use execution_engine::strategies::SystemCommandExecutor;
 use std::time::Duration;
 
+/// Commands the Kubernetes executor allows
+pub const K8S_COMMANDS: &[&str] = &[
+    "kubectl",                // Standard PATH lookup
+    "/usr/local/bin/kubectl", // Common container location
+    "/usr/bin/kubectl",       // Alternative location
+];
+
 /// Create command executor configured for Kubernetes scanning
 ///
 /// Whitelist includes:
@@ -14,11 +21,7 @@ use std::time::Duration;
 pub fn create_k8s_command_executor() -> SystemCommandExecutor {
     let mut executor = SystemCommandExecutor::with_timeout(Duration::from_secs(30));
 
-    executor.allow_commands(&[
-        "kubectl",                // Standard PATH lookup
-        "/usr/local/bin/kubectl", // Common container location
-        "/usr/bin/kubectl",       // Alternative location
-    ]);
+    executor.allow_commands(K8S_COMMANDS);
 
     executor
 }
@@ -0,0 +1,340 @@
+//! Linux kernel module operations
+//!
+//! Answers the three questions hardening benchmarks ask of a module such as
+//! `cramfs` or `usb-storage`:
+//!
+//! - **loaded**: listed in `/proc/modules`
+//! - **blacklisted**: a `blacklist <module>` line in the modprobe
+//!   configuration (`/etc/modprobe.d`, `/run/modprobe.d`,
+//!   `/usr/lib/modprobe.d`, `/lib/modprobe.d`)
+//! - **loadable**: `modprobe -n -v <module>` would load it, i.e. it exits 0
+//!   and is not redirected to `/bin/true` or `/bin/false` by an `install`
+//!   line
+//!
+//! `blacklist` only stops a module being loaded by its aliases, so a
+//! blacklisted module can still be loadable; benchmarks usually require
+//! both `blacklisted = true` and `loadable = false`.
+//!
+//! ## Module Names
+//!
+//! The kernel treats `-` and `_` in module names as the same character and
+//! reports names with `_`. Names from policies, `/proc/modules` and the
+//! modprobe configuration are all normalized with [`normalize_module_name`],
+//! so `usb-storage` and `usb_storage` match each other.
+
+use execution_engine::strategies::SystemCommandExecutor;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Loaded module list on Linux
+pub const PROC_MODULES: &str = "/proc/modules";
+
+/// modprobe configuration directories, highest precedence first
+///
+/// A file in an earlier directory hides a file of the same name in a later
+/// one, as modprobe does.
+pub const MODPROBE_CONFIG_DIRS: &[&str] = &[
+    "/etc/modprobe.d",
+    "/run/modprobe.d",
+    "/usr/lib/modprobe.d",
+    "/lib/modprobe.d",
+];
+
+/// Candidate locations for the modprobe binary
+const MODPROBE_PATHS: &[&str] = &["/sbin/modprobe", "/usr/sbin/modprobe"];
+
+/// Commands that stand in for a module to stop it loading
+const DISABLING_INSTALL_COMMANDS: &[&str] =
+    &["/bin/true", "/bin/false", "/usr/bin/true", "/usr/bin/false"];
+
+/// Commands the kernel module executor allows
+pub const KERNEL_MODULE_COMMANDS: &[&str] = &[
+    "modprobe",           // Standard PATH lookup
+    "/sbin/modprobe",     // Traditional location
+    "/usr/sbin/modprobe", // Merged /usr location
+];
+
+/// Create command executor configured for kernel module collection
+///
+/// Whitelist includes:
+/// - modprobe: Only run as a dry run (`-n`), which loads nothing
+pub fn create_kernel_module_command_executor() -> SystemCommandExecutor {
+    let mut executor = SystemCommandExecutor::with_timeout(Duration::from_secs(10));
+
+    executor.allow_commands(KERNEL_MODULE_COMMANDS);
+
+    executor
+}
+
+/// Find the modprobe binary path
+pub fn find_modprobe() -> &'static str {
+    for path in MODPROBE_PATHS {
+        if Path::new(path).exists() {
+            return path;
+        }
+    }
+    "modprobe" // Fall back to PATH lookup
+}
+
+/// Module name in the form the kernel reports, with `-` written as `_`
+pub fn normalize_module_name(name: &str) -> String {
+    name.trim().replace('-', "_")
+}
+
+/// Whether `name` can be passed to modprobe as a module name
+///
+/// Letters, digits, `_` and `-`, not starting with `-`, so a name can never
+/// be read as an option.
+pub fn is_valid_module_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Normalized names of the modules currently loaded
+pub fn read_loaded_modules() -> std::io::Result<Vec<String>> {
+    std::fs::read_to_string(PROC_MODULES).map(|content| parse_loaded_modules(&content))
+}
+
+/// Normalized names of the modules listed in `/proc/modules` content
+///
+/// Each line starts with the module name; the remaining fields (size,
+/// use count, dependents, state, address) are ignored.
+pub fn parse_loaded_modules(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(normalize_module_name)
+        .collect()
+}
+
+/// What the modprobe configuration says about one module
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModprobeSettings {
+    /// A `blacklist` line names the module
+    pub blacklisted: bool,
+    /// Command of the last `install` line for the module, if any
+    pub install: Option<String>,
+}
+
+impl ModprobeSettings {
+    /// Whether the `install` command replaces loading with a no-op
+    pub fn install_disabled(&self) -> bool {
+        self.install
+            .as_deref()
+            .is_some_and(is_disabling_install_command)
+    }
+}
+
+/// Settings for `module` in modprobe configuration content
+///
+/// Comments (`#`) and blank lines are skipped, and lines ending in `\`
+/// continue on the next line. Module names on both sides are normalized.
+pub fn parse_modprobe_settings(content: &str, module: &str) -> ModprobeSettings {
+    let module = normalize_module_name(module);
+    let mut settings = ModprobeSettings::default();
+
+    for line in join_continuations(content) {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut words = line.split_whitespace();
+        let (Some(directive), Some(name)) = (words.next(), words.next()) else {
+            continue;
+        };
+        if normalize_module_name(name) != module {
+            continue;
+        }
+        match directive {
+            "blacklist" => settings.blacklisted = true,
+            "install" => settings.install = Some(words.collect::<Vec<_>>().join(" ")),
+            _ => {}
+        }
+    }
+
+    settings
+}
+
+/// Configuration lines with `\` continuations joined
+fn join_continuations(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for line in content.lines() {
+        match line.strip_suffix('\\') {
+            Some(head) => {
+                current.push_str(head);
+                current.push(' ');
+            }
+            None => {
+                current.push_str(line);
+                lines.push(std::mem::take(&mut current));
+            }
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Whether an `install` command only runs `true` or `false`
+fn is_disabling_install_command(command: &str) -> bool {
+    command
+        .split_whitespace()
+        .next()
+        .is_some_and(|program| DISABLING_INSTALL_COMMANDS.contains(&program))
+}
+
+/// Whether `modprobe -n -v` output, from a run that exited 0, would load
+/// the module
+///
+/// A module is loaded with `insmod` lines, or through an `install` line
+/// that runs something other than `/bin/true` or `/bin/false`. No output
+/// means the module is built in or already loaded, so it is loadable.
+pub fn parse_modprobe_dry_run(output: &str) -> bool {
+    !output.lines().any(|line| {
+        line.trim()
+            .strip_prefix("install ")
+            .is_some_and(is_disabling_install_command)
+    })
+}
+
+/// Read and concatenate the `*.conf` files of the modprobe configuration
+///
+/// Files are read in name order across `dirs`, and a file name already
+/// seen in an earlier directory is skipped. Missing directories are
+/// skipped; other errors are returned.
+pub fn read_modprobe_config(dirs: &[&str]) -> std::io::Result<String> {
+    let mut files: BTreeMap<std::ffi::OsString, PathBuf> = BTreeMap::new();
+    for dir in dirs {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "conf") {
+                if let Some(name) = path.file_name() {
+                    files.entry(name.to_os_string()).or_insert(path);
+                }
+            }
+        }
+    }
+
+    let mut combined = String::new();
+    for path in files.values() {
+        combined.push_str(&std::fs::read_to_string(path)?);
+        combined.push('\n');
+    }
+
+    Ok(combined)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_module_name() {
+        assert!(is_valid_module_name("usb-storage"));
+        assert!(is_valid_module_name("nf_tables"));
+        assert!(!is_valid_module_name(""));
+        assert!(!is_valid_module_name("-r"));
+        assert!(!is_valid_module_name("cramfs squashfs"));
+        assert!(!is_valid_module_name("../cramfs"));
+    }
+
+    #[test]
+    fn test_parse_loaded_modules() {
+        let content = "\
+usb_storage 77824 0 - Live 0x0000000000000000
+nf_tables 270336 0 - Live 0x0000000000000000
+";
+        let loaded = parse_loaded_modules(content);
+        assert_eq!(loaded, vec!["usb_storage", "nf_tables"]);
+        assert!(loaded.contains(&normalize_module_name("usb-storage")));
+    }
+
+    #[test]
+    fn test_parse_modprobe_settings() {
+        let content = "\
+# CIS 1.1.1.1
+install cramfs /bin/true
+blacklist cramfs
+blacklist usb-storage
+install squashfs /sbin/modprobe --ignore-install \\
+    squashfs
+options snd slots=1
+";
+        let cramfs = parse_modprobe_settings(content, "cramfs");
+        assert!(cramfs.blacklisted);
+        assert_eq!(cramfs.install.as_deref(), Some("/bin/true"));
+        assert!(cramfs.install_disabled());
+
+        // Names match with either separator
+        assert!(parse_modprobe_settings(content, "usb_storage").blacklisted);
+
+        let squashfs = parse_modprobe_settings(content, "squashfs");
+        assert!(!squashfs.blacklisted);
+        assert_eq!(
+            squashfs.install.as_deref(),
+            Some("/sbin/modprobe --ignore-install squashfs")
+        );
+        assert!(!squashfs.install_disabled());
+
+        assert_eq!(
+            parse_modprobe_settings(content, "snd"),
+            ModprobeSettings::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_modprobe_dry_run() {
+        assert!(parse_modprobe_dry_run(
+            "insmod /lib/modules/6.1.0/kernel/fs/cramfs/cramfs.ko\n"
+        ));
+        assert!(!parse_modprobe_dry_run("install /bin/true \n"));
+        assert!(!parse_modprobe_dry_run("install /usr/bin/false\n"));
+        assert!(parse_modprobe_dry_run(
+            "install /sbin/modprobe --ignore-install squashfs\n"
+        ));
+        // Built in or already loaded
+        assert!(parse_modprobe_dry_run(""));
+    }
+
+    #[test]
+    fn test_read_modprobe_config_precedence() {
+        let root = std::env::temp_dir().join(format!("esp_modprobe_{}", std::process::id()));
+        let etc = root.join("etc");
+        let lib = root.join("lib");
+        std::fs::create_dir_all(&etc).unwrap();
+        std::fs::create_dir_all(&lib).unwrap();
+        std::fs::write(etc.join("cis.conf"), "install cramfs /bin/true\n").unwrap();
+        std::fs::write(lib.join("cis.conf"), "install cramfs /bin/false\n").unwrap();
+        std::fs::write(lib.join("blacklist.conf"), "blacklist cramfs\n").unwrap();
+        std::fs::write(lib.join("README"), "blacklist squashfs\n").unwrap();
+
+        let missing = root.join("missing");
+        let dirs = [
+            etc.to_str().unwrap(),
+            missing.to_str().unwrap(),
+            lib.to_str().unwrap(),
+        ];
+        let config = read_modprobe_config(&dirs).unwrap();
+        std::fs::remove_dir_all(&root).ok();
+
+        let cramfs = parse_modprobe_settings(&config, "cramfs");
+        assert!(cramfs.blacklisted);
+        assert_eq!(cramfs.install.as_deref(), Some("/bin/true"));
+        assert!(!parse_modprobe_settings(&config, "squashfs").blacklisted);
+    }
+}
@@ -1,16 +1,109 @@
 //! Command execution configurations for different platforms
 //!
 //! Provides whitelisted command executors for secure system scanning.
+//!
+//! Everything here except audit rule normalization needs the `native`
+//! feature.
 
+#[cfg(feature = "native")]
+pub mod allowlist;
+pub mod audit_rule;
+#[cfg(feature = "native")]
+pub mod command_output;
+#[cfg(feature = "native")]
+pub mod cron;
+#[cfg(feature = "native")]
+pub mod directory_walk;
+#[cfg(feature = "native")]
+pub mod dpkg;
+#[cfg(feature = "native")]
+pub mod elevation;
+#[cfg(feature = "native")]
+pub mod env_var;
+#[cfg(feature = "native")]
+pub mod file_acl;
+#[cfg(feature = "native")]
 pub mod filesystem;
+#[cfg(feature = "native")]
+pub mod firewall_rule;
+#[cfg(feature = "native")]
+pub mod glob;
+#[cfg(feature = "native")]
 pub mod k8s;
+#[cfg(feature = "native")]
+pub mod kernel_module;
+#[cfg(feature = "native")]
+pub mod mount;
+#[cfg(feature = "native")]
+pub mod passwd;
+#[cfg(feature = "native")]
+pub mod process;
+#[cfg(feature = "native")]
+pub mod registry;
+#[cfg(feature = "native")]
+pub mod retry;
+#[cfg(feature = "native")]
+pub mod structured_record;
+#[cfg(feature = "native")]
 pub mod tcp_listener;
 
+#[cfg(feature = "native")]
+pub use allowlist::{allowed_commands, AllowlistError, CommandAllowlist, COMMAND_ALLOWLIST_ENV};
+#[cfg(feature = "native")]
+pub use audit_rule::{create_audit_command_executor, AUDIT_COMMANDS};
+#[cfg(feature = "native")]
+pub use command_output::{run_command, CommandError, CommandFailure, MAX_STDERR_EXCERPT};
+#[cfg(feature = "native")]
+pub use cron::{
+    find_cron_jobs, list_cron_jobs, parse_crontab, CronError, CronFilter, CronJob, CronResult,
+};
+#[cfg(feature = "native")]
+pub use directory_walk::{walk_directory, walk_directory_checked, Walk, WalkOptions};
+#[cfg(feature = "native")]
+pub use dpkg::{create_dpkg_command_executor, DPKG_COMMANDS};
+#[cfg(feature = "native")]
+pub use elevation::{Elevation, ELEVATE_CMD_ENV};
+#[cfg(feature = "native")]
+pub use env_var::{parse_environ, read_env_var, EnvVarError, EnvVarResult};
+#[cfg(feature = "native")]
+pub use file_acl::{get_file_acl, AclEntry};
+#[cfg(feature = "native")]
 pub use filesystem::{
-    file_exists, get_file_metadata, read_file_content, FileMetadata, FileSystemError,
-    FileSystemResult,
+    file_exists, get_file_metadata, hash_file, read_file_content, read_file_content_bounded,
+    read_file_range, read_file_range_bounded, ContentRange, FileHash, FileMetadata,
+    FileSystemError, FileSystemResult, DEFAULT_MAX_CONTENT_BYTES,
+};
+#[cfg(feature = "native")]
+pub use firewall_rule::{create_firewall_command_executor, FIREWALL_COMMANDS};
+#[cfg(feature = "native")]
+pub use glob::{expand_glob, has_glob_metacharacters, GlobError, GlobResult, MAX_GLOB_MATCHES};
+#[cfg(feature = "native")]
+pub use k8s::{create_k8s_command_executor, K8S_COMMANDS};
+#[cfg(feature = "native")]
+pub use kernel_module::{
+    create_kernel_module_command_executor, normalize_module_name, KERNEL_MODULE_COMMANDS,
+};
+#[cfg(feature = "native")]
+pub use mount::{find_mount, parse_mounts, MountEntry, MountError, MountResult};
+#[cfg(feature = "native")]
+pub use passwd::{
+    find_user_accounts, list_user_accounts, parse_group, parse_passwd, PasswdError, PasswdResult,
+    UserAccount, UserSelector,
+};
+#[cfg(feature = "native")]
+pub use process::{
+    find_processes, list_processes, ProcessError, ProcessFilter, ProcessInfo, ProcessResult,
+};
+#[cfg(feature = "native")]
+pub use registry::{
+    read_registry_value, RegistryError, RegistryHive, RegistryResult, RegistryValue,
 };
-pub use k8s::create_k8s_command_executor;
+#[cfg(feature = "native")]
+pub use retry::{is_transient_failure, RetryPolicy};
+#[cfg(feature = "native")]
+pub use structured_record::{parse_ini, parse_record, RecordFormat, RecordParseError};
+#[cfg(feature = "native")]
 pub use tcp_listener::{
-    check_port_listening, get_all_listening_ports, TcpListenerError, TcpListenerResult,
+    check_port_listening, get_all_listening_ports, AddressFamily, TcpListenerError,
+    TcpListenerResult,
 };
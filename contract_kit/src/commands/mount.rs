@@ -0,0 +1,233 @@
+//! Mount table lookup
+//!
+//! Finds the filesystem mounted at a path for the `mount_point` CTN type.
+//!
+//! ## Platform Support
+//!
+//! - **Linux**: `/proc/mounts`
+//!
+//! Bind mounts appear in `/proc/mounts` with the device of the filesystem
+//! they expose, so a bind mount reports the real backing device rather
+//! than the directory it was bound from.
+
+use std::path::Path;
+
+/// Mount table read on Linux
+pub const PROC_MOUNTS: &str = "/proc/mounts";
+
+/// One line of the mount table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountEntry {
+    /// Mounted device or source (`/dev/sda1`, `tmpfs`)
+    pub device: String,
+    /// Directory the filesystem is mounted on
+    pub mount_point: String,
+    /// Filesystem type (`ext4`, `tmpfs`)
+    pub fstype: String,
+    /// Mount options, one per entry (`nosuid`, `size=1024k`)
+    pub options: Vec<String>,
+}
+
+/// Error type for mount table lookups
+#[derive(Debug)]
+pub enum MountError {
+    /// Reading the mount table was denied
+    AccessDenied(String),
+
+    /// Mount table could not be read
+    ReadFailed(String, String),
+
+    /// Mount tables are not supported on this platform
+    Unsupported(String),
+}
+
+impl std::fmt::Display for MountError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AccessDenied(path) => write!(f, "Permission denied reading {}", path),
+            Self::ReadFailed(path, msg) => write!(f, "Cannot read {}: {}", path, msg),
+            Self::Unsupported(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for MountError {}
+
+/// Result type for mount table lookups
+pub type MountResult<T> = Result<T, MountError>;
+
+/// The filesystem mounted exactly at `path`, if any
+///
+/// Symlinks in `path` are resolved first, so `/var/run` finds a mount on
+/// `/run`. A directory inside a mount that is not itself a mount point, or
+/// a path that does not exist, gives `Ok(None)`.
+pub fn find_mount(path: &str) -> MountResult<Option<MountEntry>> {
+    let content = read_mount_table()?;
+    Ok(find_mount_in(&parse_mounts(&content), &resolve_path(path)).cloned())
+}
+
+#[cfg(target_os = "linux")]
+fn read_mount_table() -> MountResult<String> {
+    use std::io::ErrorKind;
+
+    std::fs::read_to_string(PROC_MOUNTS).map_err(|e| match e.kind() {
+        ErrorKind::PermissionDenied => MountError::AccessDenied(PROC_MOUNTS.to_string()),
+        _ => MountError::ReadFailed(PROC_MOUNTS.to_string(), e.to_string()),
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_mount_table() -> MountResult<String> {
+    Err(MountError::Unsupported(
+        "Mount point inspection is only supported on Linux".to_string(),
+    ))
+}
+
+/// Canonical form of `path`, or `path` without trailing slashes if it
+/// cannot be resolved
+fn resolve_path(path: &str) -> String {
+    if let Ok(real) = Path::new(path).canonicalize() {
+        return real.to_string_lossy().into_owned();
+    }
+    let trimmed = path.trim_end_matches('/');
+    if trimmed.is_empty() {
+        "/".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Entry mounted at `mount_point`
+///
+/// When several filesystems are stacked on one directory, the last one
+/// mounted is the one visible, so the last matching entry wins.
+pub fn find_mount_in<'a>(entries: &'a [MountEntry], mount_point: &str) -> Option<&'a MountEntry> {
+    entries
+        .iter()
+        .rev()
+        .find(|entry| entry.mount_point == mount_point)
+}
+
+/// Parse `/proc/mounts` content
+///
+/// Each line is `device mount_point fstype options dump pass`, with spaces,
+/// tabs, newlines and backslashes in the first two fields written as octal
+/// escapes (`\040`). Malformed lines are skipped.
+pub fn parse_mounts(content: &str) -> Vec<MountEntry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = unescape(fields.next()?);
+            let mount_point = unescape(fields.next()?);
+            let fstype = fields.next()?.to_string();
+            let options = fields
+                .next()?
+                .split(',')
+                .filter(|option| !option.is_empty())
+                .map(str::to_string)
+                .collect();
+            Some(MountEntry {
+                device,
+                mount_point,
+                fstype,
+                options,
+            })
+        })
+        .collect()
+}
+
+/// Decode the octal escapes the kernel writes in mount table fields
+fn unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while let Some(&b) = bytes.get(i) {
+        let octal = bytes
+            .get(i + 1..i + 4)
+            .filter(|digits| b == b'\\' && digits.iter().all(|d| (b'0'..=b'7').contains(d)))
+            .and_then(|digits| {
+                let digits = std::str::from_utf8(digits).ok()?;
+                u8::from_str_radix(digits, 8).ok()
+            });
+        match octal {
+            Some(decoded) => {
+                out.push(decoded);
+                i += 4;
+            }
+            None => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOUNTS: &str = "\
+/dev/sda1 / ext4 rw,relatime 0 0
+tmpfs /tmp tmpfs rw,nosuid,nodev,size=1024k 0 0
+/dev/sdb1 /srv/my\\040data xfs rw,noexec 0 0
+/dev/sda1 /mnt/bound ext4 rw,relatime 0 0
+tmpfs /tmp tmpfs rw,nosuid,nodev,noexec 0 0
+malformed
+";
+
+    #[test]
+    fn test_parse_mounts() {
+        let entries = parse_mounts(MOUNTS);
+        assert_eq!(entries.len(), 5);
+
+        let tmp = entries.get(1).unwrap();
+        assert_eq!(tmp.device, "tmpfs");
+        assert_eq!(tmp.fstype, "tmpfs");
+        assert_eq!(tmp.options, vec!["rw", "nosuid", "nodev", "size=1024k"]);
+
+        // Octal escapes are decoded
+        assert_eq!(entries.get(2).unwrap().mount_point, "/srv/my data");
+        assert_eq!(unescape("a\\134b"), "a\\b");
+        assert_eq!(unescape("trailing\\04"), "trailing\\04");
+    }
+
+    #[test]
+    fn test_find_mount_in() {
+        let entries = parse_mounts(MOUNTS);
+
+        // The last of stacked mounts is the visible one
+        let tmp = find_mount_in(&entries, "/tmp").unwrap();
+        assert!(tmp.options.contains(&"noexec".to_string()));
+
+        // A bind mount reports its backing device
+        let bound = find_mount_in(&entries, "/mnt/bound").unwrap();
+        assert_eq!(bound.device, "/dev/sda1");
+
+        // A directory inside a mount is not a mount point
+        assert!(find_mount_in(&entries, "/tmp/cache").is_none());
+        assert!(find_mount_in(&entries, "/srv").is_none());
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        assert_eq!(resolve_path("/"), "/");
+        assert_eq!(
+            resolve_path("/nonexistent/esp/mount/"),
+            "/nonexistent/esp/mount"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_find_root_mount() {
+        let root = find_mount("/").unwrap().expect("/ is always mounted");
+        assert!(!root.fstype.is_empty());
+    }
+}
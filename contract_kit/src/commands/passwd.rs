@@ -0,0 +1,418 @@
+//! Local account lookup
+//!
+//! Reads user accounts for the `user_account` CTN type.
+//!
+//! ## Platform Support
+//!
+//! - **Linux**: `/etc/passwd`, with primary group names from `/etc/group`
+//! - **Windows**: Local accounts via `NetUserGetInfo` (by name) and
+//!   `NetUserEnum` (by uid); the uid is the account's RID and the gid its
+//!   primary group RID. Windows accounts have no login shell.
+//!
+//! NIS compat lines (`+`, `-`, `+@netgroup`) are not accounts and are
+//! skipped, as are comments and malformed lines. Duplicate usernames are
+//! all listed, in file order; the first is the one `getpwnam` returns.
+
+use std::collections::HashMap;
+
+/// Account database
+pub const PASSWD_FILE: &str = "/etc/passwd";
+
+/// Group database, for primary group names
+pub const GROUP_FILE: &str = "/etc/group";
+
+/// Accounts with a uid below this are system accounts
+pub const SYSTEM_UID_LIMIT: u32 = 1000;
+
+/// One local user account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserAccount {
+    /// Login name
+    pub username: String,
+
+    /// User ID (RID on Windows)
+    pub uid: u32,
+
+    /// Primary group ID (primary group RID on Windows)
+    pub gid: u32,
+
+    /// Home directory, as written
+    pub home: String,
+
+    /// Login shell, as written; `None` on Windows
+    pub shell: Option<String>,
+
+    /// Name of the primary group, if the group database has it
+    pub group: Option<String>,
+}
+
+impl UserAccount {
+    /// Whether this is a system account (uid below `SYSTEM_UID_LIMIT`)
+    pub fn is_system(&self) -> bool {
+        self.uid < SYSTEM_UID_LIMIT
+    }
+}
+
+/// Which accounts an object selects
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserSelector {
+    /// Exact login name (case-insensitive on Windows)
+    Name(String),
+
+    /// User ID
+    Uid(u32),
+}
+
+impl UserSelector {
+    /// Whether `account` is selected
+    pub fn matches(&self, account: &UserAccount) -> bool {
+        match self {
+            Self::Name(name) => {
+                account.username == *name
+                    || (cfg!(windows) && account.username.eq_ignore_ascii_case(name))
+            }
+            Self::Uid(uid) => account.uid == *uid,
+        }
+    }
+}
+
+/// Error type for account lookup
+#[derive(Debug)]
+pub enum PasswdError {
+    /// Reading the account database was denied
+    AccessDenied(String),
+
+    /// The account database could not be read
+    ReadFailed(String, String),
+}
+
+impl std::fmt::Display for PasswdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AccessDenied(source) => write!(f, "Permission denied reading {}", source),
+            Self::ReadFailed(source, msg) => write!(f, "Cannot read {}: {}", source, msg),
+        }
+    }
+}
+
+impl std::error::Error for PasswdError {}
+
+/// Result type for account lookup
+pub type PasswdResult<T> = Result<T, PasswdError>;
+
+/// Parse `/etc/passwd` content
+///
+/// Accounts are returned in file order without group names. Lines with
+/// other than seven fields or a non-numeric uid or gid are skipped.
+pub fn parse_passwd(content: &str) -> Vec<UserAccount> {
+    content.lines().filter_map(parse_passwd_line).collect()
+}
+
+/// Parse one `name:password:uid:gid:gecos:home:shell` line
+fn parse_passwd_line(line: &str) -> Option<UserAccount> {
+    let line = line.trim_end_matches('\r');
+    if line.is_empty() || line.starts_with(['#', '+', '-']) {
+        return None;
+    }
+
+    let fields: Vec<&str> = line.split(':').collect();
+    let [username, _, uid, gid, _, home, shell] = fields.as_slice() else {
+        return None;
+    };
+    if username.is_empty() {
+        return None;
+    }
+
+    Some(UserAccount {
+        username: (*username).to_string(),
+        uid: uid.parse().ok()?,
+        gid: gid.parse().ok()?,
+        home: (*home).to_string(),
+        shell: Some((*shell).to_string()),
+        group: None,
+    })
+}
+
+/// Parse `/etc/group` content into group names by gid
+///
+/// When several groups share a gid, the first keeps it, as with
+/// `getgrgid`. Compat and malformed lines are skipped.
+pub fn parse_group(content: &str) -> HashMap<u32, String> {
+    let mut groups = HashMap::new();
+    for line in content.lines() {
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() || line.starts_with(['#', '+', '-']) {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(':').collect();
+        let [name, _, gid, _] = fields.as_slice() else {
+            continue;
+        };
+        if let (false, Ok(gid)) = (name.is_empty(), gid.parse::<u32>()) {
+            groups.entry(gid).or_insert_with(|| (*name).to_string());
+        }
+    }
+    groups
+}
+
+// ============================================================================
+// Windows Implementation
+// ============================================================================
+
+/// Accounts selected by `selector`, in the order they were listed
+#[cfg(windows)]
+pub fn find_user_accounts(selector: &UserSelector) -> PasswdResult<Vec<UserAccount>> {
+    match selector {
+        UserSelector::Name(name) => Ok(get_user_account(name)?.into_iter().collect()),
+        UserSelector::Uid(_) => Ok(list_user_accounts()?
+            .into_iter()
+            .filter(|account| selector.matches(account))
+            .collect()),
+    }
+}
+
+/// Look up one local account by name
+#[cfg(windows)]
+fn get_user_account(name: &str) -> PasswdResult<Option<UserAccount>> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::NetworkManagement::NetManagement::{
+        NERR_Success, NERR_UserNotFound, NetApiBufferFree, NetUserGetInfo, USER_INFO_3,
+    };
+
+    let name = HSTRING::from(name);
+    let mut buffer: *mut u8 = std::ptr::null_mut();
+
+    // SAFETY: on success the buffer holds one USER_INFO_3, freed below
+    let status = unsafe { NetUserGetInfo(PCWSTR::null(), &name, 3, &mut buffer) };
+    if status == NERR_UserNotFound {
+        return Ok(None);
+    }
+    if status != NERR_Success || buffer.is_null() {
+        return Err(net_api_error("NetUserGetInfo", status));
+    }
+
+    // SAFETY: level 3 fills the buffer with a USER_INFO_3
+    let account = unsafe { user_info_account(&*(buffer as *const USER_INFO_3)) };
+    // SAFETY: the buffer was allocated by NetUserGetInfo
+    unsafe { NetApiBufferFree(Some(buffer as *const core::ffi::c_void)) };
+    Ok(Some(account))
+}
+
+/// List the local user accounts
+#[cfg(windows)]
+pub fn list_user_accounts() -> PasswdResult<Vec<UserAccount>> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::ERROR_MORE_DATA;
+    use windows::Win32::NetworkManagement::NetManagement::{
+        NERR_Success, NetApiBufferFree, NetUserEnum, FILTER_NORMAL_ACCOUNT, MAX_PREFERRED_LENGTH,
+        USER_INFO_3,
+    };
+
+    let mut accounts = Vec::new();
+    let mut resume = 0u32;
+    loop {
+        let mut buffer: *mut u8 = std::ptr::null_mut();
+        let (mut read, mut total) = (0u32, 0u32);
+
+        // SAFETY: the buffer holds `read` USER_INFO_3 entries, freed below
+        let status = unsafe {
+            NetUserEnum(
+                PCWSTR::null(),
+                3,
+                FILTER_NORMAL_ACCOUNT,
+                &mut buffer,
+                MAX_PREFERRED_LENGTH,
+                &mut read,
+                &mut total,
+                Some(&mut resume),
+            )
+        };
+
+        if !buffer.is_null() {
+            if status == NERR_Success || status == ERROR_MORE_DATA.0 {
+                // SAFETY: level 3 fills the buffer with `read` USER_INFO_3
+                let infos = unsafe {
+                    std::slice::from_raw_parts(buffer as *const USER_INFO_3, read as usize)
+                };
+                // SAFETY: the entries' strings live in the same buffer
+                accounts.extend(infos.iter().map(|info| unsafe { user_info_account(info) }));
+            }
+            // SAFETY: the buffer was allocated by NetUserEnum
+            unsafe { NetApiBufferFree(Some(buffer as *const core::ffi::c_void)) };
+        }
+
+        if status == NERR_Success {
+            return Ok(accounts);
+        }
+        if status != ERROR_MORE_DATA.0 {
+            return Err(net_api_error("NetUserEnum", status));
+        }
+    }
+}
+
+/// Account from a level 3 user entry
+#[cfg(windows)]
+unsafe fn user_info_account(
+    info: &windows::Win32::NetworkManagement::NetManagement::USER_INFO_3,
+) -> UserAccount {
+    let text = |value: windows::core::PWSTR| {
+        if value.is_null() {
+            String::new()
+        } else {
+            value.to_string().unwrap_or_default()
+        }
+    };
+    UserAccount {
+        username: text(info.usri3_name),
+        uid: info.usri3_user_id,
+        gid: info.usri3_primary_group_id,
+        home: text(info.usri3_home_dir),
+        shell: None,
+        group: None,
+    }
+}
+
+/// Error for a failed NetAPI call
+#[cfg(windows)]
+fn net_api_error(call: &str, status: u32) -> PasswdError {
+    use windows::Win32::Foundation::ERROR_ACCESS_DENIED;
+
+    if status == ERROR_ACCESS_DENIED.0 {
+        PasswdError::AccessDenied("local accounts".to_string())
+    } else {
+        PasswdError::ReadFailed(
+            "local accounts".to_string(),
+            format!("{} failed with status {}", call, status),
+        )
+    }
+}
+
+// ============================================================================
+// Passwd File Implementation
+// ============================================================================
+
+/// Accounts selected by `selector`, in file order
+#[cfg(not(windows))]
+pub fn find_user_accounts(selector: &UserSelector) -> PasswdResult<Vec<UserAccount>> {
+    Ok(list_user_accounts()?
+        .into_iter()
+        .filter(|account| selector.matches(account))
+        .collect())
+}
+
+/// List the accounts of `/etc/passwd`, with their primary group names
+///
+/// A missing `/etc/group` leaves every `group` unset.
+#[cfg(not(windows))]
+pub fn list_user_accounts() -> PasswdResult<Vec<UserAccount>> {
+    let Some(passwd) = read_account_file(PASSWD_FILE)? else {
+        return Ok(Vec::new());
+    };
+    let groups = read_account_file(GROUP_FILE)?
+        .map(|content| parse_group(&content))
+        .unwrap_or_default();
+
+    Ok(parse_passwd(&passwd)
+        .into_iter()
+        .map(|mut account| {
+            account.group = groups.get(&account.gid).cloned();
+            account
+        })
+        .collect())
+}
+
+/// Content of an account file, or `None` if it does not exist
+#[cfg(not(windows))]
+fn read_account_file(path: &str) -> PasswdResult<Option<String>> {
+    use std::io::ErrorKind;
+
+    match std::fs::read(path) {
+        Ok(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).into_owned())),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+            Err(PasswdError::AccessDenied(path.to_string()))
+        }
+        Err(e) => Err(PasswdError::ReadFailed(path.to_string(), e.to_string())),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSWD: &str = "\
+root:x:0:0:root:/root:/bin/bash
+daemon:x:1:1:daemon:/usr/sbin:/usr/sbin/nologin
+# toor:x:0:0::/root:/bin/sh
+alice:x:1000:1000:Alice,,,:/home/alice:/bin/zsh
++@admins::::::
+-bob
++
+broken:x:abc:1000::/home/broken:/bin/sh
+short:x:1001
+toor:x:0:0::/root:/bin/sh
+alice:x:1002:1002::/home/alice2:/bin/sh
+";
+
+    const GROUP: &str = "\
+root:x:0:
+daemon:x:1:
+alice:x:1000:
+wheel:x:0:alice
++:::
+";
+
+    #[test]
+    fn test_parse_passwd() {
+        let accounts = parse_passwd(PASSWD);
+        let names: Vec<&str> = accounts.iter().map(|a| a.username.as_str()).collect();
+        assert_eq!(names, vec!["root", "daemon", "alice", "toor", "alice"]);
+
+        let daemon = accounts.get(1).unwrap();
+        assert_eq!(daemon.uid, 1);
+        assert_eq!(daemon.home, "/usr/sbin");
+        assert_eq!(daemon.shell.as_deref(), Some("/usr/sbin/nologin"));
+        assert!(daemon.is_system());
+        assert!(!accounts.get(2).unwrap().is_system());
+    }
+
+    #[test]
+    fn test_selector_duplicates_in_file_order() {
+        let accounts = parse_passwd(PASSWD);
+
+        let uid_zero: Vec<&str> = accounts
+            .iter()
+            .filter(|a| UserSelector::Uid(0).matches(a))
+            .map(|a| a.username.as_str())
+            .collect();
+        assert_eq!(uid_zero, vec!["root", "toor"]);
+
+        let alice: Vec<u32> = accounts
+            .iter()
+            .filter(|a| UserSelector::Name("alice".to_string()).matches(a))
+            .map(|a| a.uid)
+            .collect();
+        assert_eq!(alice, vec![1000, 1002]);
+    }
+
+    #[test]
+    fn test_parse_group_first_name_wins() {
+        let groups = parse_group(GROUP);
+        assert_eq!(groups.get(&0).map(String::as_str), Some("root"));
+        assert_eq!(groups.get(&1000).map(String::as_str), Some("alice"));
+        assert_eq!(groups.len(), 3);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_missing_account_file() {
+        assert!(read_account_file("/nonexistent/esp/passwd")
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,273 @@
+//! Process listing
+//!
+//! Lists running processes for the `process` CTN type.
+//!
+//! ## Platform Support
+//!
+//! - **Windows**: CreateToolhelp32Snapshot / Process32FirstW; command lines
+//!   are not available
+//! - **Other platforms**: Reads `/proc/<pid>/stat` and `/proc/<pid>/cmdline`
+
+/// One running process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+
+    /// Process name (`comm` on Linux, at most 15 characters; image name on Windows)
+    pub name: String,
+
+    /// Arguments joined with spaces (empty for kernel threads and on Windows)
+    pub cmdline: String,
+
+    /// Zombie or dead process that has exited but not been reaped
+    pub defunct: bool,
+}
+
+impl ProcessInfo {
+    /// File name of the executable in `argv[0]`
+    fn argv0_name(&self) -> Option<&str> {
+        let argv0 = self.cmdline.split(' ').next()?;
+        argv0
+            .rsplit(['/', '\\'])
+            .next()
+            .filter(|name| !name.is_empty())
+    }
+}
+
+/// Which processes an object selects
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessFilter {
+    /// Exact process name, or the file name of `argv[0]`
+    pub name: Option<String>,
+
+    /// Substring of the command line
+    pub cmdline_contains: Option<String>,
+}
+
+impl ProcessFilter {
+    /// Whether `process` matches every criterion of the filter
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        let name_matches = self.name.as_deref().is_none_or(|name| {
+            process.name == name
+                || process.argv0_name() == Some(name)
+                || (cfg!(windows) && windows_name_matches(&process.name, name))
+        });
+        let cmdline_matches = self
+            .cmdline_contains
+            .as_deref()
+            .is_none_or(|needle| process.cmdline.contains(needle));
+        name_matches && cmdline_matches
+    }
+}
+
+/// Case-insensitive image name match, with or without `.exe`
+fn windows_name_matches(image: &str, name: &str) -> bool {
+    let image = image.to_ascii_lowercase();
+    let name = name.to_ascii_lowercase();
+    image == name || image.strip_suffix(".exe") == Some(name.as_str())
+}
+
+/// Error type for process listing
+#[derive(Debug)]
+pub enum ProcessError {
+    /// Process table could not be read
+    ListFailed(String),
+
+    /// Filter uses a field the platform cannot provide
+    Unsupported(String),
+}
+
+impl std::fmt::Display for ProcessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ListFailed(msg) => write!(f, "Cannot list processes: {}", msg),
+            Self::Unsupported(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ProcessError {}
+
+/// Result type for process listing
+pub type ProcessResult<T> = Result<T, ProcessError>;
+
+/// Live processes matching `filter`, excluding defunct ones and this process
+///
+/// PIDs are sorted.
+pub fn find_processes(filter: &ProcessFilter) -> ProcessResult<Vec<ProcessInfo>> {
+    if cfg!(windows) && filter.cmdline_contains.is_some() {
+        return Err(ProcessError::Unsupported(
+            "cmdline_contains is not supported on Windows".to_string(),
+        ));
+    }
+
+    let own_pid = std::process::id();
+    let mut processes: Vec<ProcessInfo> = list_processes()?
+        .into_iter()
+        .filter(|p| !p.defunct && p.pid != own_pid && filter.matches(p))
+        .collect();
+    processes.sort_by_key(|p| p.pid);
+    Ok(processes)
+}
+
+/// Parse `/proc/<pid>/stat` into the process name and state
+///
+/// The name is in parentheses and may itself contain spaces and
+/// parentheses, so the state follows the last `)`.
+pub fn parse_proc_stat(stat: &str) -> Option<(String, char)> {
+    let open = stat.find('(')?;
+    let close = stat.rfind(')')?;
+    let name = stat.get(open + 1..close)?.to_string();
+    let state = stat.get(close + 1..)?.trim_start().chars().next()?;
+    Some((name, state))
+}
+
+// ============================================================================
+// Windows Implementation
+// ============================================================================
+
+/// List all processes
+#[cfg(windows)]
+pub fn list_processes() -> ProcessResult<Vec<ProcessInfo>> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
+    };
+
+    // SAFETY: the snapshot handle is closed below and entries are plain data
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0)
+            .map_err(|e| ProcessError::ListFailed(e.to_string()))?;
+
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+        let mut processes = Vec::new();
+        let mut next = Process32FirstW(snapshot, &mut entry);
+        while next.is_ok() {
+            let len = entry
+                .szExeFile
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(entry.szExeFile.len());
+            processes.push(ProcessInfo {
+                pid: entry.th32ProcessID,
+                name: String::from_utf16_lossy(entry.szExeFile.get(..len).unwrap_or_default()),
+                cmdline: String::new(),
+                defunct: false,
+            });
+            next = Process32NextW(snapshot, &mut entry);
+        }
+
+        let _ = CloseHandle(snapshot);
+        Ok(processes)
+    }
+}
+
+// ============================================================================
+// /proc Implementation
+// ============================================================================
+
+/// List all processes
+///
+/// Processes that exit while the table is read are skipped.
+#[cfg(not(windows))]
+pub fn list_processes() -> ProcessResult<Vec<ProcessInfo>> {
+    let entries = std::fs::read_dir("/proc")
+        .map_err(|e| ProcessError::ListFailed(format!("/proc: {}", e)))?;
+
+    let mut processes = Vec::new();
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|s| s.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let dir = entry.path();
+        let Some((name, state)) = std::fs::read_to_string(dir.join("stat"))
+            .ok()
+            .as_deref()
+            .and_then(parse_proc_stat)
+        else {
+            continue;
+        };
+        let cmdline = std::fs::read(dir.join("cmdline"))
+            .map(|raw| {
+                raw.split(|&b| b == 0)
+                    .filter(|arg| !arg.is_empty())
+                    .map(String::from_utf8_lossy)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+
+        processes.push(ProcessInfo {
+            pid,
+            name,
+            cmdline,
+            defunct: matches!(state, 'Z' | 'X'),
+        });
+    }
+
+    Ok(processes)
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(name: &str, cmdline: &str) -> ProcessInfo {
+        ProcessInfo {
+            pid: 1,
+            name: name.to_string(),
+            cmdline: cmdline.to_string(),
+            defunct: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_proc_stat() {
+        assert_eq!(
+            parse_proc_stat("812 (sshd) S 1 812 812 0 -1"),
+            Some(("sshd".to_string(), 'S'))
+        );
+        assert_eq!(
+            parse_proc_stat("99 (my (odd) name) Z 1 99"),
+            Some(("my (odd) name".to_string(), 'Z'))
+        );
+        assert_eq!(parse_proc_stat("garbage"), None);
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let sshd = process("sshd", "/usr/sbin/sshd -D");
+        let by_name = ProcessFilter {
+            name: Some("sshd".to_string()),
+            cmdline_contains: None,
+        };
+        assert!(by_name.matches(&sshd));
+        assert!(!by_name.matches(&process("bash", "-bash")));
+
+        // comm is truncated to 15 characters; argv[0] still matches
+        let long = process("systemd-journal", "/usr/lib/systemd/systemd-journald");
+        let filter = ProcessFilter {
+            name: Some("systemd-journald".to_string()),
+            cmdline_contains: None,
+        };
+        assert!(filter.matches(&long));
+
+        let both = ProcessFilter {
+            name: Some("sshd".to_string()),
+            cmdline_contains: Some("-D".to_string()),
+        };
+        assert!(both.matches(&sshd));
+        assert!(!both.matches(&process("sshd", "sshd: alice@pts/0")));
+    }
+}
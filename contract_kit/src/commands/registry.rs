@@ -0,0 +1,375 @@
+//! Windows registry value reads
+//!
+//! Reads a single value with `RegGetValueW` and decodes it into the string
+//! and integer forms the `registry_value` CTN type compares against.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! let hive = RegistryHive::parse("HKLM")?;
+//! match read_registry_value(hive, r"SYSTEM\CurrentControlSet\Control\Lsa", "LmCompatibilityLevel")? {
+//!     Some(value) => println!("{} = {}", value.value_type, value.value_data),
+//!     None => println!("not set"),
+//! }
+//! ```
+//!
+//! ## Platform Support
+//!
+//! - **Windows**: Full support using RegGetValueW
+//! - **Other platforms**: `read_registry_value` returns `RegistryError::Unsupported`
+
+// Value types (winnt.h)
+const REG_NONE: u32 = 0;
+const REG_SZ: u32 = 1;
+const REG_EXPAND_SZ: u32 = 2;
+const REG_BINARY: u32 = 3;
+const REG_DWORD: u32 = 4;
+const REG_DWORD_BIG_ENDIAN: u32 = 5;
+const REG_LINK: u32 = 6;
+const REG_MULTI_SZ: u32 = 7;
+const REG_QWORD: u32 = 11;
+
+/// Root key a registry path is read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistryHive {
+    LocalMachine,
+    CurrentUser,
+    ClassesRoot,
+    Users,
+    CurrentConfig,
+}
+
+impl RegistryHive {
+    /// Parse a hive from its full or abbreviated name (case-insensitive)
+    pub fn parse(name: &str) -> Result<Self, RegistryError> {
+        match name.trim().to_ascii_uppercase().as_str() {
+            "HKLM" | "HKEY_LOCAL_MACHINE" => Ok(Self::LocalMachine),
+            "HKCU" | "HKEY_CURRENT_USER" => Ok(Self::CurrentUser),
+            "HKCR" | "HKEY_CLASSES_ROOT" => Ok(Self::ClassesRoot),
+            "HKU" | "HKEY_USERS" => Ok(Self::Users),
+            "HKCC" | "HKEY_CURRENT_CONFIG" => Ok(Self::CurrentConfig),
+            _ => Err(RegistryError::InvalidHive(name.to_string())),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LocalMachine => "HKEY_LOCAL_MACHINE",
+            Self::CurrentUser => "HKEY_CURRENT_USER",
+            Self::ClassesRoot => "HKEY_CLASSES_ROOT",
+            Self::Users => "HKEY_USERS",
+            Self::CurrentConfig => "HKEY_CURRENT_CONFIG",
+        }
+    }
+}
+
+/// A decoded registry value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryValue {
+    /// Value type name (e.g., "REG_SZ", "REG_DWORD")
+    pub value_type: String,
+
+    /// Value rendered as a string; REG_MULTI_SZ is newline-joined and
+    /// REG_BINARY is lowercase hex
+    pub value_data: String,
+
+    /// Numeric value for REG_DWORD and REG_QWORD
+    pub value_int: Option<i64>,
+}
+
+/// Error type for registry operations
+#[derive(Debug)]
+pub enum RegistryError {
+    /// Hive name not recognized
+    InvalidHive(String),
+
+    /// Caller may not read the key
+    AccessDenied(String),
+
+    /// API call failed
+    ApiError(String, u32),
+
+    /// Registry reads are only available on Windows
+    Unsupported,
+}
+
+impl std::fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidHive(hive) => write!(f, "Invalid registry hive: {}", hive),
+            Self::AccessDenied(key) => write!(f, "Access denied reading {}", key),
+            Self::ApiError(msg, code) => write!(f, "{} (error {})", msg, code),
+            Self::Unsupported => write!(f, "Registry reads are only supported on Windows"),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// Result type for registry operations
+pub type RegistryResult<T> = Result<T, RegistryError>;
+
+/// Name of a registry value type
+pub fn value_type_name(value_type: u32) -> String {
+    match value_type {
+        REG_NONE => "REG_NONE".to_string(),
+        REG_SZ => "REG_SZ".to_string(),
+        REG_EXPAND_SZ => "REG_EXPAND_SZ".to_string(),
+        REG_BINARY => "REG_BINARY".to_string(),
+        REG_DWORD => "REG_DWORD".to_string(),
+        REG_DWORD_BIG_ENDIAN => "REG_DWORD_BIG_ENDIAN".to_string(),
+        REG_LINK => "REG_LINK".to_string(),
+        REG_MULTI_SZ => "REG_MULTI_SZ".to_string(),
+        REG_QWORD => "REG_QWORD".to_string(),
+        other => format!("REG_UNKNOWN({})", other),
+    }
+}
+
+/// Decode raw value bytes as returned by `RegGetValueW`
+pub fn decode_value(value_type: u32, data: &[u8]) -> RegistryValue {
+    let (value_data, value_int) = match value_type {
+        REG_SZ | REG_EXPAND_SZ | REG_LINK => {
+            let text = utf16_from_bytes(data);
+            let end = text.find('\0').unwrap_or(text.len());
+            (text.get(..end).unwrap_or_default().to_string(), None)
+        }
+        REG_MULTI_SZ => {
+            let text = utf16_from_bytes(data);
+            let strings: Vec<&str> = text.split('\0').filter(|s| !s.is_empty()).collect();
+            (strings.join("\n"), None)
+        }
+        REG_DWORD | REG_DWORD_BIG_ENDIAN => {
+            match <[u8; 4]>::try_from(data.get(..4).unwrap_or_default()) {
+                Ok(bytes) => {
+                    let value = if value_type == REG_DWORD {
+                        u32::from_le_bytes(bytes)
+                    } else {
+                        u32::from_be_bytes(bytes)
+                    };
+                    (value.to_string(), Some(i64::from(value)))
+                }
+                Err(_) => (to_hex(data), None),
+            }
+        }
+        REG_QWORD => match <[u8; 8]>::try_from(data.get(..8).unwrap_or_default()) {
+            Ok(bytes) => {
+                let value = u64::from_le_bytes(bytes);
+                (value.to_string(), i64::try_from(value).ok())
+            }
+            Err(_) => (to_hex(data), None),
+        },
+        _ => (to_hex(data), None),
+    };
+
+    RegistryValue {
+        value_type: value_type_name(value_type),
+        value_data,
+        value_int,
+    }
+}
+
+/// Decode little-endian UTF-16, ignoring a trailing odd byte
+fn utf16_from_bytes(data: &[u8]) -> String {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|pair| {
+            u16::from_le_bytes([
+                pair.first().copied().unwrap_or(0),
+                pair.get(1).copied().unwrap_or(0),
+            ])
+        })
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Lowercase hex rendering of binary data
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Normalize a key path for `RegGetValueW` (backslashes, no leading or
+/// trailing separator)
+pub fn normalize_key_path(key_path: &str) -> String {
+    key_path.replace('/', "\\").trim_matches('\\').to_string()
+}
+
+// ============================================================================
+// Windows Implementation
+// ============================================================================
+
+/// Read a registry value
+///
+/// # Returns
+///
+/// `Ok(None)` when the key or value does not exist.
+#[cfg(windows)]
+pub fn read_registry_value(
+    hive: RegistryHive,
+    key_path: &str,
+    value_name: &str,
+) -> RegistryResult<Option<RegistryValue>> {
+    use windows::core::HSTRING;
+    use windows::Win32::Foundation::{
+        ERROR_ACCESS_DENIED, ERROR_FILE_NOT_FOUND, ERROR_MORE_DATA, ERROR_PATH_NOT_FOUND,
+    };
+    use windows::Win32::System::Registry::{
+        RegGetValueW, HKEY, HKEY_CLASSES_ROOT, HKEY_CURRENT_CONFIG, HKEY_CURRENT_USER,
+        HKEY_LOCAL_MACHINE, HKEY_USERS, REG_VALUE_TYPE, RRF_NOEXPAND, RRF_RT_ANY,
+    };
+
+    let root: HKEY = match hive {
+        RegistryHive::LocalMachine => HKEY_LOCAL_MACHINE,
+        RegistryHive::CurrentUser => HKEY_CURRENT_USER,
+        RegistryHive::ClassesRoot => HKEY_CLASSES_ROOT,
+        RegistryHive::Users => HKEY_USERS,
+        RegistryHive::CurrentConfig => HKEY_CURRENT_CONFIG,
+    };
+    let key_path = normalize_key_path(key_path);
+    let subkey = HSTRING::from(key_path.as_str());
+    let name = HSTRING::from(value_name);
+    let flags = RRF_RT_ANY | RRF_NOEXPAND;
+
+    // The value can grow between the size query and the read; retry once
+    for _ in 0..2 {
+        let mut value_type = REG_VALUE_TYPE::default();
+        let mut size: u32 = 0;
+
+        // SAFETY: size query only, no data buffer
+        let status = unsafe {
+            RegGetValueW(
+                root,
+                &subkey,
+                &name,
+                flags,
+                Some(&mut value_type),
+                None,
+                Some(&mut size),
+            )
+        };
+        if status == ERROR_FILE_NOT_FOUND || status == ERROR_PATH_NOT_FOUND {
+            return Ok(None);
+        }
+        if status == ERROR_ACCESS_DENIED {
+            return Err(RegistryError::AccessDenied(format!(
+                "{}\\{}",
+                hive.as_str(),
+                key_path
+            )));
+        }
+        if status.is_err() {
+            return Err(RegistryError::ApiError(
+                "RegGetValueW size query failed".to_string(),
+                status.0,
+            ));
+        }
+
+        let mut buffer: Vec<u8> = vec![0; size as usize];
+
+        // SAFETY: buffer is owned and size is its length in bytes
+        let status = unsafe {
+            RegGetValueW(
+                root,
+                &subkey,
+                &name,
+                flags,
+                Some(&mut value_type),
+                Some(buffer.as_mut_ptr().cast()),
+                Some(&mut size),
+            )
+        };
+        if status == ERROR_MORE_DATA {
+            continue;
+        }
+        if status == ERROR_FILE_NOT_FOUND || status == ERROR_PATH_NOT_FOUND {
+            return Ok(None);
+        }
+        if status.is_err() {
+            return Err(RegistryError::ApiError(
+                "RegGetValueW failed".to_string(),
+                status.0,
+            ));
+        }
+
+        buffer.truncate(size as usize);
+        return Ok(Some(decode_value(value_type.0, &buffer)));
+    }
+
+    Err(RegistryError::ApiError(
+        "RegGetValueW: value kept changing size".to_string(),
+        ERROR_MORE_DATA.0,
+    ))
+}
+
+// ============================================================================
+// Non-Windows Stubs (for cross-compilation)
+// ============================================================================
+
+/// Read a registry value - non-Windows stub
+#[cfg(not(windows))]
+pub fn read_registry_value(
+    _hive: RegistryHive,
+    _key_path: &str,
+    _value_name: &str,
+) -> RegistryResult<Option<RegistryValue>> {
+    Err(RegistryError::Unsupported)
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    #[test]
+    fn test_parse_hive() {
+        assert_eq!(
+            RegistryHive::parse("hklm").unwrap(),
+            RegistryHive::LocalMachine
+        );
+        assert_eq!(
+            RegistryHive::parse("HKEY_CURRENT_USER").unwrap(),
+            RegistryHive::CurrentUser
+        );
+        assert!(RegistryHive::parse("HKXX").is_err());
+    }
+
+    #[test]
+    fn test_decode_strings() {
+        let value = decode_value(REG_SZ, &utf16("Enabled\0"));
+        assert_eq!(value.value_type, "REG_SZ");
+        assert_eq!(value.value_data, "Enabled");
+        assert_eq!(value.value_int, None);
+
+        let value = decode_value(REG_MULTI_SZ, &utf16("first\0second\0\0"));
+        assert_eq!(value.value_type, "REG_MULTI_SZ");
+        assert_eq!(value.value_data, "first\nsecond");
+    }
+
+    #[test]
+    fn test_decode_numbers() {
+        let value = decode_value(REG_DWORD, &5u32.to_le_bytes());
+        assert_eq!(value.value_data, "5");
+        assert_eq!(value.value_int, Some(5));
+
+        let value = decode_value(REG_QWORD, &u64::MAX.to_le_bytes());
+        assert_eq!(value.value_data, u64::MAX.to_string());
+        assert_eq!(value.value_int, None);
+
+        let value = decode_value(REG_BINARY, &[0x0a, 0xff]);
+        assert_eq!(value.value_data, "0aff");
+        assert_eq!(value.value_int, None);
+    }
+
+    #[test]
+    fn test_normalize_key_path() {
+        assert_eq!(
+            normalize_key_path(r"\SOFTWARE/Policies\Microsoft\"),
+            r"SOFTWARE\Policies\Microsoft"
+        );
+    }
+}
@@ -0,0 +1,345 @@
+//! Retry with backoff for transient command failures
+//!
+//! Commands that talk to a remote service (kubectl against the API server)
+//! can fail for a moment during a leader election or a network blip, and
+//! local tools can time out while the system is busy. A [`RetryPolicy`]
+//! re-runs such a command after a fixed or exponentially growing delay,
+//! capped at [`MAX_BACKOFF`]. Only failures classified as transient are
+//! retried; a missing resource, a tool that is not installed or a rejected
+//! command fails at once.
+//!
+//! The default policy makes a single attempt, so retrying is opt-in. Objects
+//! ask for retries with behaviors, read by [`RetryPolicy::from_hints`]:
+//!
+//! - `BEHAVIOR retry count 3 interval_ms 500` retries at a fixed interval;
+//!   adding `BEHAVIOR exponential_backoff` doubles it after each retry
+//! - `BEHAVIOR retries 3` with `BEHAVIOR backoff_ms 500` always doubles
+
+use execution_engine::execution::BehaviorHints;
+use std::thread;
+use std::time::Duration;
+
+use crate::collectors::CollectionPlan;
+
+/// Longest delay between two attempts
+pub const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Most retries a policy allows, whatever is requested
+pub const MAX_RETRIES: u32 = 10;
+
+/// Default delay before the first retry
+pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Lowercased stderr fragments of failures worth retrying
+const TRANSIENT_STDERR: &[&str] = &[
+    "connection refused",
+    "connection reset",
+    "timeout",
+    "timed out",
+    "unable to connect to the server",
+    "temporary failure in name resolution",
+    "no such host",
+    "the server is currently unable to handle the request",
+    "serviceunavailable",
+    "too many requests",
+    "leader changed",
+];
+
+/// How the delay between attempts grows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// Same delay before every retry
+    Fixed,
+    /// Delay doubled after each retry
+    Exponential,
+}
+
+impl Backoff {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fixed => "fixed",
+            Self::Exponential => "exponential",
+        }
+    }
+}
+
+/// How often, and how patiently, to retry a failing command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    retries: u32,
+    backoff: Duration,
+    growth: Backoff,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl RetryPolicy {
+    /// Single attempt, no retries
+    pub fn none() -> Self {
+        Self {
+            retries: 0,
+            backoff: DEFAULT_BACKOFF,
+            growth: Backoff::Exponential,
+        }
+    }
+
+    /// Up to `retries` retries (at most [`MAX_RETRIES`]), waiting `backoff`
+    /// before the first and doubling after each
+    pub fn new(retries: u32, backoff: Duration) -> Self {
+        Self {
+            retries: retries.min(MAX_RETRIES),
+            backoff,
+            growth: Backoff::Exponential,
+        }
+    }
+
+    /// Up to `retries` retries (at most [`MAX_RETRIES`]), waiting
+    /// `interval` (at most [`MAX_BACKOFF`]) before each
+    pub fn fixed(retries: u32, interval: Duration) -> Self {
+        Self {
+            retries: retries.min(MAX_RETRIES),
+            backoff: interval,
+            growth: Backoff::Fixed,
+        }
+    }
+
+    /// Policy for an object, from its behaviors or `default`
+    ///
+    /// `retry count N interval_ms M` retries at a fixed interval (500ms if
+    /// `interval_ms` is left out), or a doubling one with
+    /// `exponential_backoff`. `retries N` and `backoff_ms M` always double
+    /// and fall back to `default` for whichever is not given. The two forms
+    /// cannot be mixed, and no value may be negative.
+    pub fn from_hints(hints: &BehaviorHints, default: RetryPolicy) -> Result<Self, String> {
+        let non_negative = |name: &str| -> Result<Option<u64>, String> {
+            match hints.get_parameter_as_int(name) {
+                None => Ok(None),
+                Some(value) => u64::try_from(value)
+                    .map(Some)
+                    .map_err(|_| format!("'{}' must not be negative, got {}", name, value)),
+            }
+        };
+        let retries = |value: u64| u32::try_from(value).unwrap_or(u32::MAX);
+
+        let legacy_retries = non_negative("retries")?;
+        let legacy_backoff = non_negative("backoff_ms")?;
+
+        if !hints.has_flag("retry") {
+            return Ok(Self::new(
+                legacy_retries.map_or(default.retries(), retries),
+                legacy_backoff.map_or(default.backoff(), Duration::from_millis),
+            ));
+        }
+
+        if legacy_retries.is_some() || legacy_backoff.is_some() {
+            return Err("'retry' cannot be combined with 'retries' or 'backoff_ms'".to_string());
+        }
+        let count = non_negative("count")?.ok_or_else(|| "'retry' requires 'count'".to_string())?;
+        let interval = non_negative("interval_ms")?.map_or(DEFAULT_BACKOFF, Duration::from_millis);
+        Ok(if hints.has_flag("exponential_backoff") {
+            Self::new(retries(count), interval)
+        } else {
+            Self::fixed(retries(count), interval)
+        })
+    }
+
+    /// Maximum number of retries
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Delay before the first retry
+    pub fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    /// How the delay grows between retries
+    pub fn growth(&self) -> Backoff {
+        self.growth
+    }
+
+    /// Delay before retry number `retry` (0-based), capped at [`MAX_BACKOFF`]
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = match self.growth {
+            Backoff::Fixed => 1,
+            Backoff::Exponential => 2u32.saturating_pow(retry),
+        };
+        self.backoff
+            .checked_mul(factor)
+            .unwrap_or(MAX_BACKOFF)
+            .min(MAX_BACKOFF)
+    }
+
+    /// Add the policy to a plan, if it retries at all
+    ///
+    /// Records `max_retries`, `retry_interval_ms` and `retry_backoff`, so
+    /// `--explain` shows how a collection would retry.
+    pub fn describe(&self, plan: CollectionPlan) -> CollectionPlan {
+        if self.retries == 0 {
+            return plan;
+        }
+        plan.with_input("max_retries", self.retries.to_string())
+            .with_input("retry_interval_ms", self.backoff.as_millis().to_string())
+            .with_input("retry_backoff", self.growth.as_str())
+    }
+
+    /// Record on a plan the retries a collection made, if the policy
+    /// retries at all
+    pub fn record(&self, plan: CollectionPlan, retries: u32) -> CollectionPlan {
+        if self.retries == 0 {
+            return plan;
+        }
+        plan.with_input("retries", retries.to_string())
+    }
+
+    /// Run `operation`, retrying while it fails with a transient error
+    ///
+    /// Returns the last result and the number of retries made.
+    pub fn run<T, E>(
+        &self,
+        mut operation: impl FnMut() -> Result<T, E>,
+        is_transient: impl Fn(&E) -> bool,
+    ) -> (Result<T, E>, u32) {
+        let mut retry = 0;
+        loop {
+            let result = operation();
+            match &result {
+                Err(e) if retry < self.retries && is_transient(e) => {
+                    thread::sleep(self.delay(retry));
+                    retry += 1;
+                }
+                _ => return (result, retry),
+            }
+        }
+    }
+}
+
+/// Whether a command's stderr describes a transient failure
+///
+/// Connection, timeout, DNS and server-availability errors are transient.
+/// Anything else, including "not found", is not.
+pub fn is_transient_failure(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    TRANSIENT_STDERR
+        .iter()
+        .any(|fragment| stderr.contains(fragment))
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_fails_twice_then_succeeds() {
+        let calls = Cell::new(0);
+        let policy = RetryPolicy::new(3, Duration::from_millis(1));
+
+        let (result, retries) = policy.run(
+            || {
+                calls.set(calls.get() + 1);
+                if calls.get() <= 2 {
+                    Err("Unable to connect to the server: dial tcp: i/o timeout".to_string())
+                } else {
+                    Ok("items")
+                }
+            },
+            |e| is_transient_failure(e),
+        );
+
+        assert_eq!(result, Ok("items"));
+        assert_eq!(retries, 2);
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_non_transient_failure_is_not_retried() {
+        let calls = Cell::new(0);
+        let policy = RetryPolicy::new(3, Duration::from_millis(1));
+
+        let (result, retries) = policy.run(
+            || -> Result<(), String> {
+                calls.set(calls.get() + 1);
+                Err("Error from server (Forbidden): pods is forbidden".to_string())
+            },
+            |e| is_transient_failure(e),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(retries, 0);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_gives_up_after_retries() {
+        let calls = Cell::new(0);
+        let policy = RetryPolicy::new(2, Duration::from_millis(1));
+
+        let (result, retries) = policy.run(
+            || -> Result<(), String> {
+                calls.set(calls.get() + 1);
+                Err("connection refused".to_string())
+            },
+            |e| is_transient_failure(e),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(retries, 2);
+        assert_eq!(calls.get(), 3);
+
+        // The default policy makes one attempt
+        calls.set(0);
+        let (_, retries) = RetryPolicy::default().run(
+            || -> Result<(), String> {
+                calls.set(calls.get() + 1);
+                Err("connection refused".to_string())
+            },
+            |e| is_transient_failure(e),
+        );
+        assert_eq!((retries, calls.get()), (0, 1));
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let policy = RetryPolicy::new(MAX_RETRIES + 5, Duration::from_millis(100));
+        assert_eq!(policy.retries(), MAX_RETRIES);
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(1), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(800));
+        assert_eq!(policy.delay(9), MAX_BACKOFF);
+        assert_eq!(policy.delay(u32::MAX), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_fixed_interval() {
+        let policy = RetryPolicy::fixed(3, Duration::from_millis(250));
+        assert_eq!(policy.growth(), Backoff::Fixed);
+        assert_eq!(policy.delay(0), Duration::from_millis(250));
+        assert_eq!(policy.delay(5), Duration::from_millis(250));
+        assert_eq!(
+            RetryPolicy::fixed(1, Duration::from_secs(60)).delay(0),
+            MAX_BACKOFF
+        );
+    }
+
+    #[test]
+    fn test_transient_classification() {
+        assert!(is_transient_failure(
+            "Error from server: etcdserver: leader changed"
+        ));
+        assert!(is_transient_failure(
+            "dial tcp: lookup api.example: Temporary failure in name resolution"
+        ));
+        assert!(!is_transient_failure(
+            "Error from server (NotFound): pods \"x\" not found"
+        ));
+        assert!(!is_transient_failure(""));
+    }
+}
@@ -0,0 +1,354 @@
+//! Structured configuration file parsing
+//!
+//! Parses JSON, TOML, INI and YAML text into a `serde_json::Value`, so the
+//! `structured_record` collector can hand every format to the same record
+//! checks as `json_record`.
+//!
+//! - TOML dates and times become their RFC 3339 strings.
+//! - YAML mappings must have string keys; anchors and aliases are resolved.
+//! - INI is parsed by [`parse_ini`] (see its notes on sections and
+//!   duplicate keys). All INI values are strings.
+//!
+//! Parse errors carry the 1-based line where parsing stopped, when the
+//! parser reports one.
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+/// Format of a structured record file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    Json,
+    Toml,
+    Ini,
+    Yaml,
+}
+
+impl RecordFormat {
+    /// Every supported format, in the order they are documented
+    pub const ALL: [RecordFormat; 4] = [Self::Json, Self::Toml, Self::Ini, Self::Yaml];
+
+    /// Parse a format name (`json`, `toml`, `ini`, `yaml`)
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.as_str().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Toml => "toml",
+            Self::Ini => "ini",
+            Self::Yaml => "yaml",
+        }
+    }
+
+    /// Display name, as used in error messages
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Toml => "TOML",
+            Self::Ini => "INI",
+            Self::Yaml => "YAML",
+        }
+    }
+}
+
+/// A structured record file that could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordParseError {
+    pub format: RecordFormat,
+    /// 1-based line where parsing stopped, if known
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl std::fmt::Display for RecordParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(
+                f,
+                "Failed to parse {} at line {}: {}",
+                self.format.label(),
+                line,
+                self.message
+            ),
+            None => write!(
+                f,
+                "Failed to parse {}: {}",
+                self.format.label(),
+                self.message
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RecordParseError {}
+
+/// Parse `content` as `format`
+pub fn parse_record(content: &str, format: RecordFormat) -> Result<Value, RecordParseError> {
+    let error = |line: Option<usize>, message: String| RecordParseError {
+        format,
+        line,
+        message,
+    };
+
+    match format {
+        RecordFormat::Json => serde_json::from_str(content)
+            .map_err(|e| error(Some(e.line()), without_location(&e.to_string()))),
+        RecordFormat::Toml => content
+            .parse::<toml::Table>()
+            .map(|table| toml_to_json(toml::Value::Table(table)))
+            .map_err(|e| {
+                let line = e.span().map(|span| line_of(content, span.start));
+                error(line, e.message().trim().to_string())
+            }),
+        RecordFormat::Yaml => serde_yaml::from_str::<Value>(content).map_err(|e| {
+            let line = e.location().map(|location| location.line());
+            error(line, without_location(&e.to_string()))
+        }),
+        RecordFormat::Ini => parse_ini(content),
+    }
+}
+
+/// Parse INI text
+///
+/// - `[section]` starts a section; its keys become an object under the
+///   section name, taken literally (`[a.b]` is one section named `a.b`, not
+///   nested). Keys before the first section are top-level fields. A section
+///   given twice is merged.
+/// - A line is split at its first `=` into key and value; a line without
+///   `=` is split at its first whitespace, as in `sshd_config`
+///   (`PermitRootLogin no`). A key alone has an empty value.
+/// - Lines starting with `#` or `;` are comments. Values are trimmed, and
+///   one pair of matching surrounding quotes is removed.
+/// - A key given once is a string. A key given more than once in the same
+///   section becomes an array of its values in file order, so no value is
+///   lost (`AllowUsers` on several lines); use `key[*]` in record checks
+///   when a key may repeat.
+pub fn parse_ini(content: &str) -> Result<Value, RecordParseError> {
+    let mut root = Map::new();
+    let mut sections: BTreeMap<String, Map<String, Value>> = BTreeMap::new();
+    let mut section: Option<String> = None;
+
+    for (index, raw) in content.lines().enumerate() {
+        let error = |message: String| RecordParseError {
+            format: RecordFormat::Ini,
+            line: Some(index + 1),
+            message,
+        };
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix('[') {
+            let name = rest
+                .strip_suffix(']')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| error(format!("invalid section header '{}'", line)))?;
+            if root.contains_key(name) {
+                return Err(error(format!(
+                    "section '{}' has the name of a top-level key",
+                    name
+                )));
+            }
+            sections.entry(name.to_string()).or_default();
+            section = Some(name.to_string());
+            continue;
+        }
+
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => match line.split_once(char::is_whitespace) {
+                Some((key, value)) => (key, value.trim()),
+                None => (line, ""),
+            },
+        };
+        if key.is_empty() {
+            return Err(error(format!("missing key in '{}'", line)));
+        }
+
+        let fields = match &section {
+            Some(name) => sections.entry(name.clone()).or_default(),
+            None if sections.contains_key(key) => {
+                return Err(error(format!("key '{}' has the name of a section", key)));
+            }
+            None => &mut root,
+        };
+        insert_ini_value(fields, key, unquote(value));
+    }
+
+    root.extend(
+        sections
+            .into_iter()
+            .map(|(name, fields)| (name, Value::Object(fields))),
+    );
+    Ok(Value::Object(root))
+}
+
+/// Add a value, turning a repeated key into an array
+fn insert_ini_value(fields: &mut Map<String, Value>, key: &str, value: &str) {
+    let value = Value::String(value.to_string());
+    match fields.get_mut(key) {
+        Some(Value::Array(values)) => values.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            fields.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// Remove one pair of matching surrounding quotes
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .into_iter()
+        .find_map(|quote| {
+            value
+                .strip_prefix(quote)
+                .and_then(|rest| rest.strip_suffix(quote))
+        })
+        .unwrap_or(value)
+}
+
+/// Convert a TOML value, writing dates and times as RFC 3339 strings
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+        toml::Value::Array(values) => Value::Array(values.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// 1-based line of a byte offset
+fn line_of(content: &str, offset: usize) -> usize {
+    content
+        .get(..offset.min(content.len()))
+        .map_or(0, |before| before.matches('\n').count())
+        + 1
+}
+
+/// Parser message without its trailing " at line N column M"
+fn without_location(message: &str) -> String {
+    match message.rfind(" at line ") {
+        Some(index) => message.get(..index).unwrap_or(message).to_string(),
+        None => message.to_string(),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_ini_sections_and_duplicates() {
+        let content = "\
+# global
+Port 22
+PermitRootLogin no
+AllowUsers alice
+AllowUsers bob
+
+[cri]
+sandbox_image = \"registry.k8s.io/pause:3.9\"
+; comment
+enabled=true
+";
+        let record = parse_ini(content).unwrap();
+        assert_eq!(
+            record,
+            json!({
+                "Port": "22",
+                "PermitRootLogin": "no",
+                "AllowUsers": ["alice", "bob"],
+                "cri": {
+                    "sandbox_image": "registry.k8s.io/pause:3.9",
+                    "enabled": "true"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_ini_errors_name_the_line() {
+        let err = parse_ini("a = 1\n[broken\nb = 2\n").unwrap_err();
+        assert_eq!(err.line, Some(2));
+        assert!(err
+            .to_string()
+            .starts_with("Failed to parse INI at line 2:"));
+
+        let err = parse_ini("[a]\nx = 1\n[b]\n= 2\n").unwrap_err();
+        assert_eq!(err.line, Some(4));
+    }
+
+    #[test]
+    fn test_toml_parses_with_types() {
+        let content = "\
+version = 2
+
+[plugins.\"io.containerd.grpc.v1.cri\"]
+enable_selinux = true
+max_concurrent_downloads = 3
+updated = 2024-05-01T12:00:00Z
+";
+        let record = parse_record(content, RecordFormat::Toml).unwrap();
+        let cri = &record["plugins"]["io.containerd.grpc.v1.cri"];
+        assert_eq!(record["version"], 2);
+        assert_eq!(cri["enable_selinux"], true);
+        assert_eq!(cri["max_concurrent_downloads"], 3);
+        assert_eq!(cri["updated"], "2024-05-01T12:00:00Z");
+    }
+
+    #[test]
+    fn test_toml_error_names_the_line() {
+        let err = parse_record("a = 1\nb = 2\nc = = 3\n", RecordFormat::Toml).unwrap_err();
+        assert_eq!(err.format, RecordFormat::Toml);
+        assert_eq!(err.line, Some(3));
+        assert!(err
+            .to_string()
+            .starts_with("Failed to parse TOML at line 3:"));
+    }
+
+    #[test]
+    fn test_json_and_yaml_errors_name_the_line() {
+        let err = parse_record("{\n  \"a\": 1,\n  \"b\": }\n", RecordFormat::Json).unwrap_err();
+        assert_eq!(err.line, Some(3));
+
+        let record = parse_record("a: 1\nb:\n  - x\n", RecordFormat::Yaml).unwrap();
+        assert_eq!(record, json!({ "a": 1, "b": ["x"] }));
+        let err = parse_record("a: 1\nb: [x\n", RecordFormat::Yaml).unwrap_err();
+        assert!(err.line.is_some());
+    }
+
+    #[test]
+    fn test_format_names() {
+        assert_eq!(RecordFormat::parse("TOML"), Some(RecordFormat::Toml));
+        assert_eq!(RecordFormat::parse("xml"), None);
+        // The contract advertises exactly the formats parsed here
+        assert_eq!(
+            RecordFormat::ALL.map(|format| format.as_str()),
+            crate::contracts::structured_record_contracts::STRUCTURED_RECORD_FORMATS
+        );
+    }
+}
@@ -1,11 +1,11 @@
-//! Windows native TCP listener operations
+//! Native TCP listener operations
 //!
-//! Uses the IP Helper API (iphlpapi) to query TCP listening ports.
+//! Queries TCP ports in LISTEN state over IPv4 and IPv6.
 //!
 //! ## Usage
 //!
 //! ```ignore
-//! let result = check_port_listening(22, None)?;
+//! let result = check_port_listening(22, None, AddressFamily::Any);
 //! if result.listening {
 //!     println!("Port 22 is listening on {}", result.local_address.unwrap());
 //! }
@@ -13,8 +13,15 @@
 //!
 //! ## Platform Support
 //!
-//! - **Windows**: Full support using GetExtendedTcpTable
-//! - **Linux**: Stub for cross-compilation (use /proc/net/tcp directly)
+//! - **Windows**: GetExtendedTcpTable for `AF_INET` and `AF_INET6`
+//! - **Linux**: Reads `/proc/net/tcp` and `/proc/net/tcp6`
+//!
+//! ## Owning Process
+//!
+//! Windows reports the owning PID with each socket. On Linux the socket's
+//! inode is matched against the `/proc/<pid>/fd` links of every process;
+//! the descriptors of another user's processes can only be read with
+//! privilege, so without it the owner of their sockets is not found.
 
 /// Result of checking a TCP port
 #[derive(Debug, Clone, Default)]
@@ -22,9 +29,15 @@ pub struct TcpListenerResult {
     /// Whether the port is in LISTEN state
     pub listening: bool,
 
-    /// Local address:port if listening (e.g., "0.0.0.0:22")
+    /// Local address:port if listening (e.g., "0.0.0.0:22" or "[::]:22")
     pub local_address: Option<String>,
 
+    /// PID of the process that owns the listening socket, if found
+    pub owner_pid: Option<u32>,
+
+    /// Name of the owning process (`comm` on Linux, image name on Windows)
+    pub owner_process: Option<String>,
+
     /// Error message if collection failed
     pub error: Option<String>,
 }
@@ -53,96 +66,225 @@ impl std::error::Error for TcpListenerError {}
 /// Result type for TCP listener operations
 pub type TcpListenerApiResult<T> = Result<T, TcpListenerError>;
 
-// ============================================================================
-// Windows Implementation
-// ============================================================================
+/// A socket in LISTEN state
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Listener {
+    ip: String,
+    port: u16,
+    /// Owning process, where the socket table reports it (Windows)
+    owner_pid: Option<u32>,
+    /// Socket inode, to find the owning process (Linux)
+    inode: Option<u64>,
+}
 
-#[cfg(windows)]
-use windows::Win32::NetworkManagement::IpHelper::{
-    GetExtendedTcpTable, MIB_TCPROW_OWNER_PID, MIB_TCPTABLE_OWNER_PID, MIB_TCP_STATE_LISTEN,
-    TCP_TABLE_OWNER_PID_LISTENER,
-};
-#[cfg(windows)]
-use windows::Win32::Networking::WinSock::AF_INET;
+/// Address family of the listening sockets to check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AddressFamily {
+    /// IPv4 sockets only
+    V4,
+    /// IPv6 sockets only
+    V6,
+    /// IPv4 and IPv6 sockets
+    #[default]
+    Any,
+}
+
+impl AddressFamily {
+    /// Parse `v4`, `v6` or `any` (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "v4" => Some(Self::V4),
+            "v6" => Some(Self::V6),
+            "any" => Some(Self::Any),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V4 => "v4",
+            Self::V6 => "v6",
+            Self::Any => "any",
+        }
+    }
+
+    fn includes_v4(self) -> bool {
+        self != Self::V6
+    }
+
+    fn includes_v6(self) -> bool {
+        self != Self::V4
+    }
+}
 
 /// Check if a TCP port is listening
 ///
 /// # Arguments
 ///
 /// * `port` - TCP port number (1-65535)
-/// * `host_filter` - Optional bind address filter (e.g., "127.0.0.1")
+/// * `host_filter` - Optional bind address filter (e.g., "127.0.0.1", "::1")
+/// * `family` - Which socket tables to search
 ///
 /// # Returns
 ///
 /// `TcpListenerResult` with listening status and local address if found.
-#[cfg(windows)]
-pub fn check_port_listening(port: u16, host_filter: Option<&str>) -> TcpListenerResult {
+/// IPv4 listeners are reported before IPv6 ones.
+pub fn check_port_listening(
+    port: u16,
+    host_filter: Option<&str>,
+    family: AddressFamily,
+) -> TcpListenerResult {
     if port == 0 {
         return TcpListenerResult {
-            listening: false,
-            local_address: None,
             error: Some("Invalid port: 0".to_string()),
+            ..TcpListenerResult::default()
         };
     }
 
-    // Get the TCP table
-    let table = match get_tcp_table() {
-        Ok(t) => t,
+    let listeners = match list_listeners(family) {
+        Ok(l) => l,
         Err(e) => {
             return TcpListenerResult {
-                listening: false,
-                local_address: None,
                 error: Some(e.to_string()),
+                ..TcpListenerResult::default()
             };
         }
     };
 
-    // Search for matching listener
-    for entry in table {
-        // Check if port matches (convert from network byte order)
-        let entry_port = u16::from_be(entry.dwLocalPort as u16);
-        if entry_port != port {
-            continue;
+    let found = listeners.into_iter().find(|listener| {
+        listener.port == port && host_filter.is_none_or(|filter| host_matches(&listener.ip, filter))
+    });
+
+    match found {
+        Some(listener) => {
+            let owner_pid = listener
+                .owner_pid
+                .or_else(|| listener.inode.and_then(socket_owner_pid));
+            TcpListenerResult {
+                listening: true,
+                local_address: Some(format_local_address(&listener.ip, listener.port)),
+                owner_pid,
+                owner_process: owner_pid.and_then(process_name),
+                error: None,
+            }
         }
+        None => TcpListenerResult::default(),
+    }
+}
 
-        // Check if in LISTEN state
-        if entry.dwState != MIB_TCP_STATE_LISTEN.0 as u32 {
-            continue;
-        }
+/// Get all listening ports
+///
+/// Returns `(address, port)` for every IPv4 and IPv6 TCP socket in LISTEN state.
+pub fn get_all_listening_ports() -> TcpListenerApiResult<Vec<(String, u16)>> {
+    Ok(list_listeners(AddressFamily::Any)?
+        .into_iter()
+        .map(|listener| (listener.ip, listener.port))
+        .collect())
+}
 
-        // Convert IP address from network byte order
-        let ip_bytes = entry.dwLocalAddr.to_ne_bytes();
-        let local_ip = format!(
-            "{}.{}.{}.{}",
-            ip_bytes[0], ip_bytes[1], ip_bytes[2], ip_bytes[3]
-        );
+/// Whether a listener bound to `local_ip` accepts connections for `filter`
+///
+/// Wildcard binds (`0.0.0.0`, `::`) match any filter. Otherwise addresses
+/// are compared parsed, so `::1` matches `0:0:0:0:0:0:0:1` and an
+/// IPv4-mapped listener (`::ffff:127.0.0.1`) matches its IPv4 form.
+fn host_matches(local_ip: &str, filter: &str) -> bool {
+    use std::net::IpAddr;
+
+    let local = match local_ip.parse::<IpAddr>() {
+        Ok(ip) => ip,
+        Err(_) => return local_ip == filter,
+    };
+    if local.is_unspecified() {
+        return true;
+    }
+
+    match filter.parse::<IpAddr>() {
+        Ok(wanted) => local.to_canonical() == wanted.to_canonical(),
+        Err(_) => local_ip == filter,
+    }
+}
 
-        // If host filter specified, check if it matches
-        if let Some(filter) = host_filter {
-            if local_ip != filter && local_ip != "0.0.0.0" {
+/// Format `address:port`, bracketing IPv6 addresses
+fn format_local_address(ip: &str, port: u16) -> String {
+    if ip.contains(':') {
+        format!("[{}]:{}", ip, port)
+    } else {
+        format!("{}:{}", ip, port)
+    }
+}
+
+// ============================================================================
+// Windows Implementation
+// ============================================================================
+
+#[cfg(windows)]
+use windows::Win32::NetworkManagement::IpHelper::{
+    GetExtendedTcpTable, MIB_TCP6ROW_OWNER_PID, MIB_TCP6TABLE_OWNER_PID, MIB_TCPROW_OWNER_PID,
+    MIB_TCPTABLE_OWNER_PID, MIB_TCP_STATE_LISTEN, TCP_TABLE_OWNER_PID_LISTENER,
+};
+#[cfg(windows)]
+use windows::Win32::Networking::WinSock::{AF_INET, AF_INET6};
+
+/// List listening sockets from the Windows TCP tables
+#[cfg(windows)]
+fn list_listeners(family: AddressFamily) -> TcpListenerApiResult<Vec<Listener>> {
+    let mut listeners = Vec::new();
+
+    if family.includes_v4() {
+        for entry in get_tcp_table()? {
+            if entry.dwState != MIB_TCP_STATE_LISTEN.0 as u32 {
                 continue;
             }
+            // Address and port are in network byte order
+            let ip = std::net::Ipv4Addr::from(entry.dwLocalAddr.to_ne_bytes());
+            let port = u16::from_be(entry.dwLocalPort as u16);
+            listeners.push(Listener {
+                ip: ip.to_string(),
+                port,
+                owner_pid: Some(entry.dwOwningPid),
+                inode: None,
+            });
         }
-
-        // Found a matching listener
-        return TcpListenerResult {
-            listening: true,
-            local_address: Some(format!("{}:{}", local_ip, port)),
-            error: None,
-        };
     }
 
-    // Port not found listening
-    TcpListenerResult {
-        listening: false,
-        local_address: None,
-        error: None,
+    if family.includes_v6() {
+        for entry in get_tcp6_table()? {
+            if entry.dwState != MIB_TCP_STATE_LISTEN.0 as u32 {
+                continue;
+            }
+            let ip = std::net::Ipv6Addr::from(entry.ucLocalAddr);
+            let port = u16::from_be(entry.dwLocalPort as u16);
+            listeners.push(Listener {
+                ip: ip.to_string(),
+                port,
+                owner_pid: Some(entry.dwOwningPid),
+                inode: None,
+            });
+        }
     }
+
+    Ok(listeners)
 }
 
-/// Get the TCP table from Windows
+/// The socket table reports the owning PID
 #[cfg(windows)]
-fn get_tcp_table() -> TcpListenerApiResult<Vec<MIB_TCPROW_OWNER_PID>> {
+fn socket_owner_pid(_inode: u64) -> Option<u32> {
+    None
+}
+
+/// Image name of a running process
+#[cfg(windows)]
+fn process_name(pid: u32) -> Option<String> {
+    super::process::list_processes()
+        .ok()?
+        .into_iter()
+        .find(|process| process.pid == pid)
+        .map(|process| process.name)
+}
+
+/// Query GetExtendedTcpTable for one address family into a raw buffer
+#[cfg(windows)]
+fn query_tcp_table(address_family: u32) -> TcpListenerApiResult<Vec<u8>> {
     unsafe {
         // First call to get required buffer size
         let mut size: u32 = 0;
@@ -150,7 +292,7 @@ fn get_tcp_table() -> TcpListenerApiResult<Vec<MIB_TCPROW_OWNER_PID>> {
             None,
             &mut size,
             false,
-            AF_INET.0 as u32,
+            address_family,
             TCP_TABLE_OWNER_PID_LISTENER,
             0,
         );
@@ -176,7 +318,7 @@ fn get_tcp_table() -> TcpListenerApiResult<Vec<MIB_TCPROW_OWNER_PID>> {
             Some(buffer.as_mut_ptr() as *mut _),
             &mut size,
             false,
-            AF_INET.0 as u32,
+            address_family,
             TCP_TABLE_OWNER_PID_LISTENER,
             0,
         );
@@ -188,6 +330,19 @@ fn get_tcp_table() -> TcpListenerApiResult<Vec<MIB_TCPROW_OWNER_PID>> {
             ));
         }
 
+        Ok(buffer)
+    }
+}
+
+/// Get the IPv4 TCP table from Windows
+#[cfg(windows)]
+fn get_tcp_table() -> TcpListenerApiResult<Vec<MIB_TCPROW_OWNER_PID>> {
+    let buffer = query_tcp_table(AF_INET.0 as u32)?;
+    if buffer.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    unsafe {
         // Parse the table
         let table = &*(buffer.as_ptr() as *const MIB_TCPTABLE_OWNER_PID);
         let num_entries = table.dwNumEntries as usize;
@@ -204,65 +359,62 @@ fn get_tcp_table() -> TcpListenerApiResult<Vec<MIB_TCPROW_OWNER_PID>> {
     }
 }
 
-/// Get all listening ports
-///
-/// Returns a list of all TCP ports currently in LISTEN state.
+/// Get the IPv6 TCP table from Windows
 #[cfg(windows)]
-pub fn get_all_listening_ports() -> TcpListenerApiResult<Vec<(String, u16)>> {
-    let table = get_tcp_table()?;
-    let mut listeners = Vec::new();
+fn get_tcp6_table() -> TcpListenerApiResult<Vec<MIB_TCP6ROW_OWNER_PID>> {
+    let buffer = query_tcp_table(AF_INET6.0 as u32)?;
+    if buffer.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    unsafe {
+        let table = &*(buffer.as_ptr() as *const MIB_TCP6TABLE_OWNER_PID);
+        let num_entries = table.dwNumEntries as usize;
 
-    for entry in table {
-        if entry.dwState != MIB_TCP_STATE_LISTEN.0 as u32 {
-            continue;
+        if num_entries == 0 {
+            return Ok(Vec::new());
         }
 
-        let port = u16::from_be(entry.dwLocalPort as u16);
-        let ip_bytes = entry.dwLocalAddr.to_ne_bytes();
-        let local_ip = format!(
-            "{}.{}.{}.{}",
-            ip_bytes[0], ip_bytes[1], ip_bytes[2], ip_bytes[3]
-        );
+        let entries_ptr = table.table.as_ptr();
+        let entries = std::slice::from_raw_parts(entries_ptr, num_entries);
 
-        listeners.push((local_ip, port));
+        Ok(entries.to_vec())
     }
-
-    Ok(listeners)
 }
 
 // ============================================================================
-// Non-Windows Stubs (for cross-compilation)
+// Linux Implementation (procfs)
 // ============================================================================
 
-/// Check if a TCP port is listening - non-Windows stub
-///
-/// On Linux, use /proc/net/tcp directly instead.
+/// List listening sockets from `/proc/net/tcp` and `/proc/net/tcp6`
 #[cfg(not(windows))]
-pub fn check_port_listening(port: u16, host_filter: Option<&str>) -> TcpListenerResult {
-    // Read /proc/net/tcp on Linux
-    use std::fs::File;
-    use std::io::{BufRead, BufReader};
+fn list_listeners(family: AddressFamily) -> TcpListenerApiResult<Vec<Listener>> {
+    let mut listeners = Vec::new();
 
-    if port == 0 {
-        return TcpListenerResult {
-            listening: false,
-            local_address: None,
-            error: Some("Invalid port: 0".to_string()),
-        };
+    if family.includes_v4() {
+        read_proc_net_tcp("/proc/net/tcp", &mut listeners)?;
     }
 
-    let port_hex = format!("{:04X}", port);
-
-    let file = match File::open("/proc/net/tcp") {
-        Ok(f) => f,
-        Err(e) => {
-            return TcpListenerResult {
-                listening: false,
-                local_address: None,
-                error: Some(format!("Cannot open /proc/net/tcp: {}", e)),
-            };
+    if family.includes_v6() {
+        match read_proc_net_tcp("/proc/net/tcp6", &mut listeners) {
+            Ok(()) => {}
+            // IPv6 disabled in the kernel: nothing can listen on it
+            Err(_) if !std::path::Path::new("/proc/net/tcp6").exists() => {}
+            Err(e) => return Err(e),
         }
-    };
+    }
+
+    Ok(listeners)
+}
+
+/// Append the LISTEN sockets of one /proc/net table
+#[cfg(not(windows))]
+fn read_proc_net_tcp(path: &str, listeners: &mut Vec<Listener>) -> TcpListenerApiResult<()> {
+    use std::fs::File;
+    use std::io::{BufRead, BufReader};
+
+    let file = File::open(path)
+        .map_err(|e| TcpListenerError::ApiError(format!("Cannot open {}: {}", path, e), 0))?;
 
     let reader = BufReader::new(file);
 
@@ -272,65 +424,86 @@ pub fn check_port_listening(port: u16, host_filter: Option<&str>) -> TcpListener
             Err(_) => continue,
         };
 
-        if let Some(result) = parse_proc_tcp_line(&line, &port_hex, host_filter) {
-            return result;
+        if let Some(listener) = parse_proc_tcp_line(&line) {
+            listeners.push(listener);
         }
     }
 
-    TcpListenerResult {
-        listening: false,
-        local_address: None,
-        error: None,
-    }
+    Ok(())
 }
 
-/// Parse a line from /proc/net/tcp
+/// Parse a LISTEN line from /proc/net/tcp or /proc/net/tcp6
 #[cfg(not(windows))]
-fn parse_proc_tcp_line(
-    line: &str,
-    port_hex: &str,
-    host_filter: Option<&str>,
-) -> Option<TcpListenerResult> {
+fn parse_proc_tcp_line(line: &str) -> Option<Listener> {
     let parts: Vec<&str> = line.split_whitespace().collect();
     if parts.len() < 4 {
         return None;
     }
 
-    let local_addr = parts.get(1)?;
-    let addr_parts: Vec<&str> = local_addr.split(':').collect();
-    if addr_parts.len() != 2 {
-        return None;
-    }
-
-    let local_ip_hex = addr_parts.first()?;
-    let local_port_hex = addr_parts.get(1)?;
-
-    if *local_port_hex != port_hex {
-        return None;
-    }
-
     // State 0A = LISTEN
     let state = parts.get(3)?;
     if *state != "0A" {
         return None;
     }
 
-    let local_ip = hex_to_ipv4(local_ip_hex);
+    let (local_ip_hex, local_port_hex) = parts.get(1)?.split_once(':')?;
 
-    if let Some(filter) = host_filter {
-        if local_ip != filter && local_ip != "0.0.0.0" {
-            return None;
-        }
-    }
+    // tcp6 addresses are 32 hex chars, tcp addresses 8
+    let local_ip = if local_ip_hex.len() == 32 {
+        hex_to_ipv6(local_ip_hex)
+    } else {
+        hex_to_ipv4(local_ip_hex)
+    };
+
+    let port = u16::from_str_radix(local_port_hex, 16).ok()?;
+
+    // Inode 0 is a socket no process holds
+    let inode = parts
+        .get(9)
+        .and_then(|inode| inode.parse::<u64>().ok())
+        .filter(|inode| *inode != 0);
 
-    let port = u16::from_str_radix(local_port_hex, 16).unwrap_or(0);
-    Some(TcpListenerResult {
-        listening: true,
-        local_address: Some(format!("{}:{}", local_ip, port)),
-        error: None,
+    Some(Listener {
+        ip: local_ip,
+        port,
+        owner_pid: None,
+        inode,
     })
 }
 
+/// Lowest PID holding socket `inode` open, from the `/proc/<pid>/fd` links
+///
+/// Pre-forking servers share the socket with their workers, so the lowest
+/// PID is usually the parent. Processes whose descriptors cannot be read
+/// are skipped.
+#[cfg(not(windows))]
+fn socket_owner_pid(inode: u64) -> Option<u32> {
+    let link = format!("socket:[{}]", inode);
+    std::fs::read_dir("/proc")
+        .ok()?
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+        .filter(|pid| holds_fd_link(*pid, &link))
+        .min()
+}
+
+/// Whether any descriptor of `pid` links to `link`
+#[cfg(not(windows))]
+fn holds_fd_link(pid: u32, link: &str) -> bool {
+    let Ok(fds) = std::fs::read_dir(format!("/proc/{}/fd", pid)) else {
+        return false;
+    };
+    fds.flatten()
+        .any(|fd| std::fs::read_link(fd.path()).is_ok_and(|target| target.as_os_str() == link))
+}
+
+/// `comm` name of a running process
+#[cfg(not(windows))]
+fn process_name(pid: u32) -> Option<String> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    super::process::parse_proc_stat(&stat).map(|(name, _)| name)
+}
+
 /// Convert hex IP (little-endian) to dotted decimal
 #[cfg(not(windows))]
 fn hex_to_ipv4(hex: &str) -> String {
@@ -359,47 +532,29 @@ fn hex_to_ipv4(hex: &str) -> String {
     )
 }
 
-/// Get all listening ports - non-Windows stub
-#[allow(clippy::indexing_slicing)]
+/// Convert a /proc/net/tcp6 hex address to compressed IPv6 text (e.g. "::1")
+///
+/// The kernel prints the address as four 32-bit words in host byte order;
+/// like `hex_to_ipv4`, each word is read as little-endian.
 #[cfg(not(windows))]
-pub fn get_all_listening_ports() -> TcpListenerApiResult<Vec<(String, u16)>> {
-    use std::fs::File;
-    use std::io::{BufRead, BufReader};
-
-    let file = File::open("/proc/net/tcp")
-        .map_err(|e| TcpListenerError::ApiError(format!("Cannot open /proc/net/tcp: {}", e), 0))?;
-
-    let reader = BufReader::new(file);
-    let mut listeners = Vec::new();
+fn hex_to_ipv6(hex: &str) -> String {
+    if hex.len() != 32 {
+        return "invalid".to_string();
+    }
 
-    for line in reader.lines().skip(1) {
-        let line = match line {
-            Ok(l) => l,
-            Err(_) => continue,
+    let mut bytes = [0u8; 16];
+    for (i, chunk) in bytes.chunks_exact_mut(4).enumerate() {
+        let word = match hex
+            .get(i * 8..i * 8 + 8)
+            .and_then(|s| u32::from_str_radix(s, 16).ok())
+        {
+            Some(w) => w,
+            None => return "invalid".to_string(),
         };
-
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() < 4 {
-            continue;
-        }
-
-        // Check state 0A = LISTEN
-        if parts.get(3) != Some(&"0A") {
-            continue;
-        }
-
-        if let Some(local_addr) = parts.get(1) {
-            let addr_parts: Vec<&str> = local_addr.split(':').collect();
-            if addr_parts.len() == 2 {
-                let ip = hex_to_ipv4(addr_parts[0]);
-                if let Ok(port) = u16::from_str_radix(addr_parts[1], 16) {
-                    listeners.push((ip, port));
-                }
-            }
-        }
+        chunk.copy_from_slice(&word.to_le_bytes());
     }
 
-    Ok(listeners)
+    std::net::Ipv6Addr::from(bytes).to_string()
 }
 
 // ============================================================================
@@ -413,7 +568,7 @@ mod tests {
 
     #[test]
     fn test_invalid_port() {
-        let result = check_port_listening(0, None);
+        let result = check_port_listening(0, None, AddressFamily::Any);
         assert!(!result.listening);
         assert!(result.error.is_some());
     }
@@ -421,11 +576,38 @@ mod tests {
     #[test]
     fn test_unlikely_port_not_listening() {
         // Port 65432 is unlikely to be in use
-        let result = check_port_listening(65432, None);
+        let result = check_port_listening(65432, None, AddressFamily::Any);
         assert!(!result.listening);
         assert!(result.error.is_none());
     }
 
+    #[test]
+    fn test_address_family_parse() {
+        assert_eq!(AddressFamily::parse("V6"), Some(AddressFamily::V6));
+        assert_eq!(AddressFamily::parse("any"), Some(AddressFamily::Any));
+        assert_eq!(AddressFamily::parse("ipv4"), None);
+        assert_eq!(AddressFamily::default().as_str(), "any");
+    }
+
+    #[test]
+    fn test_host_matches() {
+        // Wildcard binds accept any filter
+        assert!(host_matches("0.0.0.0", "127.0.0.1"));
+        assert!(host_matches("::", "127.0.0.1"));
+        assert!(host_matches("::", "::1"));
+
+        assert!(host_matches("::1", "0:0:0:0:0:0:0:1"));
+        assert!(host_matches("::ffff:127.0.0.1", "127.0.0.1"));
+        assert!(!host_matches("127.0.0.1", "::1"));
+        assert!(!host_matches("10.0.0.1", "127.0.0.1"));
+    }
+
+    #[test]
+    fn test_format_local_address() {
+        assert_eq!(format_local_address("0.0.0.0", 22), "0.0.0.0:22");
+        assert_eq!(format_local_address("::", 22), "[::]:22");
+    }
+
     #[cfg(windows)]
     mod windows_tests {
         use super::*;
@@ -448,5 +630,63 @@ mod tests {
             assert_eq!(hex_to_ipv4("0100007F"), "127.0.0.1");
             assert_eq!(hex_to_ipv4("0000"), "invalid");
         }
+
+        #[test]
+        fn test_hex_to_ipv6() {
+            assert_eq!(hex_to_ipv6("00000000000000000000000000000000"), "::");
+            assert_eq!(hex_to_ipv6("00000000000000000000000001000000"), "::1");
+            assert_eq!(
+                hex_to_ipv6("0000000000000000FFFF00000100007F"),
+                "::ffff:127.0.0.1"
+            );
+            assert_eq!(hex_to_ipv6("0000"), "invalid");
+        }
+
+        fn listener(ip: &str, port: u16, inode: Option<u64>) -> Listener {
+            Listener {
+                ip: ip.to_string(),
+                port,
+                owner_pid: None,
+                inode,
+            }
+        }
+
+        #[test]
+        fn test_parse_proc_tcp_line() {
+            let v6 = "   0: 00000000000000000000000000000000:0016 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 12345";
+            assert_eq!(
+                parse_proc_tcp_line(v6),
+                Some(listener("::", 22, Some(12345)))
+            );
+
+            let v4 = "   1: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 12346";
+            assert_eq!(
+                parse_proc_tcp_line(v4),
+                Some(listener("127.0.0.1", 8080, Some(12346)))
+            );
+
+            let orphan = "   3: 0100007F:1F91 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 0";
+            assert_eq!(
+                parse_proc_tcp_line(orphan),
+                Some(listener("127.0.0.1", 8081, None))
+            );
+
+            // ESTABLISHED
+            let established = "   2: 0100007F:1F90 0100007F:C350 01 00000000:00000000 00:00000000 00000000     0        0 12347";
+            assert_eq!(parse_proc_tcp_line(established), None);
+        }
+
+        #[cfg(target_os = "linux")]
+        #[test]
+        fn test_owner_of_own_listener() {
+            let socket = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let port = socket.local_addr().unwrap().port();
+
+            let result = check_port_listening(port, Some("127.0.0.1"), AddressFamily::V4);
+            assert!(result.listening);
+            assert_eq!(result.owner_pid, Some(std::process::id()));
+            assert_eq!(result.owner_process, process_name(std::process::id()));
+            assert!(result.owner_process.is_some());
+        }
     }
 }
@@ -0,0 +1,172 @@
+//! Audit Rule CTN contract
+//!
+//! Validates Linux audit (auditd) rules loaded in the kernel or persisted
+//! under `/etc/audit/rules.d/`. Used for STIG/CIS audit configuration checks.
+
+use execution_engine::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    StateFieldSpec,
+};
+use execution_engine::types::common::{DataType, Operation};
+
+use super::k8s_resource_contracts::add_retry_behaviors;
+
+/// Create contract for audit_rule CTN type
+///
+/// Reads rules via `auditctl -l` (fallback: `/etc/audit/rules.d/*.rules`) and
+/// reports whether a rule matching the object's `rule_pattern` and/or `key`
+/// is present.
+pub fn create_audit_rule_contract() -> CtnContract {
+    let mut contract = CtnContract::new("audit_rule".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "rule_pattern".to_string(),
+            data_type: DataType::String,
+            description: "Audit rule (or partial rule) to look for".to_string(),
+            example_values: vec![
+                "-w /etc/passwd -p wa".to_string(),
+                "-a always,exit -F arch=b64 -S execve".to_string(),
+            ],
+            validation_notes: Some(
+                "Flag order is ignored; every flag in the pattern must appear in the rule"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "key".to_string(),
+            data_type: DataType::String,
+            description: "Audit rule key (-k / -F key=)".to_string(),
+            example_values: vec!["identity".to_string(), "exec".to_string()],
+            validation_notes: Some("Combined with rule_pattern when both are given".to_string()),
+        });
+
+    // State requirements
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "present".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether a rule matching the object selectors is loaded".to_string(),
+            example_values: vec!["true".to_string(), "false".to_string()],
+            validation_notes: Some("With no selectors, true if any rule is loaded".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "enabled".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether the kernel audit system is enabled".to_string(),
+            example_values: vec!["true".to_string(), "false".to_string()],
+            validation_notes: Some(
+                "From `auditctl -s`; false if auditctl cannot be run".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "rules".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![Operation::Contains, Operation::NotContains],
+            description: "Set of normalized audit rules".to_string(),
+            example_values: vec!["-w /etc/shadow -p wa -k identity".to_string()],
+            validation_notes: Some(
+                "contains passes if any loaded rule matches the given rule pattern".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "rule_count".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Number of loaded audit rules".to_string(),
+            example_values: vec!["0".to_string(), "25".to_string()],
+            validation_notes: Some("Control lines (-D, -b, -e) are not counted".to_string()),
+        });
+
+    // Field mappings - object to collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("rule_pattern".to_string(), "rule_pattern".to_string());
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("key".to_string(), "key".to_string());
+
+    // Required data fields from collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec![
+        "present".to_string(),
+        "enabled".to_string(),
+        "rules".to_string(),
+    ];
+
+    // Optional data fields
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = vec!["rule_count".to_string()];
+
+    // State to data mappings for validation
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("present".to_string(), "present".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("enabled".to_string(), "enabled".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("rules".to_string(), "rules".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("rule_count".to_string(), "rule_count".to_string());
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "audit_rule".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec!["auditctl_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(50),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: true,
+        },
+    };
+
+    add_retry_behaviors(&mut contract, "auditctl");
+
+    contract
+}
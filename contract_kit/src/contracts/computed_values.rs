@@ -39,6 +39,19 @@ pub fn create_computed_values_contract() -> CtnContract {
             validation_notes: Some("Informational only".to_string()),
         });
 
+    // Any other object field is a computed value to report
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "*".to_string(),
+            data_type: DataType::String,
+            description: "Computed value to report in the result".to_string(),
+            example_values: vec!["VAR max_age".to_string()],
+            validation_notes: Some(
+                "Recorded as evidence and in details.computed; any type".to_string(),
+            ),
+        });
+
     // State requirements - flexible to support any computed value type
     // String values
     contract
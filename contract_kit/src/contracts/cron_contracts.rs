@@ -0,0 +1,141 @@
+//! Cron job CTN contract
+//!
+//! Validates scheduled jobs selected by user or command: whether any
+//! exists, how many, and the schedule of the first.
+
+use execution_engine::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    StateFieldSpec,
+};
+use execution_engine::types::common::{DataType, Operation};
+
+/// Create contract for cron_job CTN type
+///
+/// Parses the system and per-user crontabs on Linux and enumerates Task
+/// Scheduler tasks on Windows. Commented-out jobs are never counted.
+pub fn create_cron_contract() -> CtnContract {
+    let mut contract = CtnContract::new("cron_job".to_string());
+
+    // Object requirements - at least one of user / command_contains
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "user".to_string(),
+            data_type: DataType::String,
+            description: "User the job runs as".to_string(),
+            example_values: vec!["root".to_string(), "www-data".to_string()],
+            validation_notes: Some(
+                "Exact match; the owner of a per-user crontab, or the user column of a system crontab; on Windows the task principal, case-insensitive"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "command_contains".to_string(),
+            data_type: DataType::String,
+            description: "Substring of the job command".to_string(),
+            example_values: vec!["/usr/bin/aide".to_string()],
+            validation_notes: Some(
+                "On Windows the command is the action path and arguments joined with a space"
+                    .to_string(),
+            ),
+        });
+
+    // State requirements
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "exists".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether any matching job is scheduled".to_string(),
+            example_values: vec!["true".to_string(), "false".to_string()],
+            validation_notes: None,
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "count".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Number of matching jobs".to_string(),
+            example_values: vec!["0".to_string(), "1".to_string()],
+            validation_notes: Some("Commented-out jobs are not counted".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "schedule".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::StartsWith,
+                Operation::PatternMatch,
+            ],
+            description: "Schedule of the first matching job".to_string(),
+            example_values: vec!["0 5 * * *".to_string(), "@reboot".to_string()],
+            validation_notes: Some(
+                "Time fields joined with single spaces, or an @ shortcut as written; \
+                 not collected when no job matches"
+                    .to_string(),
+            ),
+        });
+
+    // Field mappings - object to collection
+    for field in ["user", "command_contains"] {
+        contract
+            .field_mappings
+            .collection_mappings
+            .object_to_collection
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Required data fields from collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["exists".to_string(), "count".to_string()];
+
+    // Optional data fields
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = vec!["schedule".to_string()];
+
+    // State to data mappings for validation
+    for field in ["exists", "count", "schedule"] {
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "cron".to_string(),
+        collection_mode: CollectionMode::Metadata,
+        required_capabilities: vec!["cron_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(20),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: true,
+        },
+    };
+
+    contract
+}
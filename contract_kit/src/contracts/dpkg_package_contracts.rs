@@ -0,0 +1,107 @@
+//! Debian package CTN contract
+//!
+//! Validates whether a Debian package is installed, and at which version,
+//! e.g. "ensure telnetd is not installed".
+
+use execution_engine::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    StateFieldSpec,
+};
+use execution_engine::types::common::{DataType, Operation};
+
+/// Create contract for dpkg_package CTN type
+///
+/// Queries the dpkg database with `dpkg-query -W` (Debian and Ubuntu). A
+/// package that is unknown or removed collects as not installed, with an
+/// empty version.
+pub fn create_dpkg_package_contract() -> CtnContract {
+    let mut contract = CtnContract::new("dpkg_package".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "name".to_string(),
+            data_type: DataType::String,
+            description: "Debian package name".to_string(),
+            example_values: vec!["telnetd".to_string(), "libc6:amd64".to_string()],
+            validation_notes: Some(
+                "Without an ':arch' qualifier, the package matches on any architecture".to_string(),
+            ),
+        });
+
+    // State requirements
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "installed".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether the package is installed".to_string(),
+            example_values: vec!["false".to_string()],
+            validation_notes: Some(
+                "true for status 'install ok installed' or 'hold ok installed'; false for \
+                 removed packages whose configuration files remain ('deinstall ok \
+                 config-files') and failed installations"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "version".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::StartsWith,
+                Operation::PatternMatch,
+            ],
+            description: "Installed package version".to_string(),
+            example_values: vec!["1:9.2p1-2+deb12u3".to_string()],
+            validation_notes: Some(
+                "Full Debian version, epoch included; compared as a string, not by version \
+                 order. Empty if not installed"
+                    .to_string(),
+            ),
+        });
+
+    // Field mappings - object to collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("name".to_string(), "name".to_string());
+
+    // Required data fields from collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["installed".to_string(), "version".to_string()];
+
+    // State to data mappings for validation
+    for field in ["installed", "version"] {
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "dpkg_package".to_string(),
+        collection_mode: CollectionMode::Metadata,
+        required_capabilities: vec!["dpkg_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(20),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+    };
+
+    contract
+}
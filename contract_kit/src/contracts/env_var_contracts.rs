@@ -0,0 +1,119 @@
+//! Environment variable CTN contract
+//!
+//! Validates whether an environment variable is set and its value, in the
+//! agent's environment or another process's.
+
+use execution_engine::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    StateFieldSpec,
+};
+use execution_engine::types::common::{DataType, Operation};
+
+/// Create contract for env_var CTN type
+///
+/// Reads the agent's environment, or `/proc/<pid>/environ` on Linux when
+/// `pid` is given. A variable set to the empty string exists with an empty
+/// value.
+pub fn create_env_var_contract() -> CtnContract {
+    let mut contract = CtnContract::new("env_var".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "name".to_string(),
+            data_type: DataType::String,
+            description: "Environment variable name".to_string(),
+            example_values: vec!["LANG".to_string(), "AWS_SECRET_ACCESS_KEY".to_string()],
+            validation_notes: Some("Case-sensitive; must not contain '='".to_string()),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "pid".to_string(),
+            data_type: DataType::Int,
+            description: "Process whose environment to read (default: the agent)".to_string(),
+            example_values: vec!["1".to_string()],
+            validation_notes: Some(
+                "Linux only; reads the environment the process started with".to_string(),
+            ),
+        });
+
+    // State requirements
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "exists".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether the variable is set".to_string(),
+            example_values: vec!["true".to_string(), "false".to_string()],
+            validation_notes: Some("true for a variable set to the empty string".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "value".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::Contains,
+                Operation::NotContains,
+                Operation::StartsWith,
+                Operation::EndsWith,
+                Operation::PatternMatch,
+            ],
+            description: "Value of the variable".to_string(),
+            example_values: vec!["en_US.UTF-8".to_string()],
+            validation_notes: Some("Not collected when the variable is unset".to_string()),
+        });
+
+    // Field mappings - object to collection
+    for field in ["name", "pid"] {
+        contract
+            .field_mappings
+            .collection_mappings
+            .object_to_collection
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Required data fields from collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["exists".to_string()];
+
+    // Optional data fields
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = vec!["value".to_string()];
+
+    // State to data mappings for validation
+    for field in ["exists", "value"] {
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "env_var".to_string(),
+        collection_mode: CollectionMode::Metadata,
+        required_capabilities: vec!["environment_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(1),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+    };
+
+    contract
+}
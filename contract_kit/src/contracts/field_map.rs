@@ -0,0 +1,293 @@
+//! Field mapping overrides
+//!
+//! A contract's `state_to_data` maps each policy state field to the
+//! collected data field it is checked against; a state field without an
+//! entry is checked against the data field of the same name. A
+//! [`FieldOverride`], written `ctn_type:state_field=data_field`, points one
+//! state field of one CTN type at another data field, e.g. when a custom
+//! collector emits `owner` instead of `file_owner`:
+//!
+//! ```ignore
+//! let mut contract = create_file_metadata_contract();
+//! remap_field(&mut contract, "owner_id", "owner")?;
+//! ```
+//!
+//! Overrides change the mapping only; collectors still collect what they
+//! always do.
+
+use execution_engine::strategies::CtnContract;
+
+/// One state field of one CTN type mapped to another data field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldOverride {
+    /// CTN type whose contract is changed, e.g. `file_metadata`
+    pub ctn_type: String,
+    /// State field as written in policies, e.g. `owner_id`
+    pub state_field: String,
+    /// Collected data field to check it against, e.g. `owner`
+    pub data_field: String,
+}
+
+impl FieldOverride {
+    /// Parse `ctn_type:state_field=data_field`
+    ///
+    /// All three parts must be non-empty; the data field may not contain
+    /// whitespace.
+    pub fn parse(spec: &str) -> Result<Self, FieldMapError> {
+        let syntax = || FieldMapError::Syntax(spec.to_string());
+        let (ctn_type, mapping) = spec.split_once(':').ok_or_else(syntax)?;
+        let (state_field, data_field) = mapping.split_once('=').ok_or_else(syntax)?;
+        let (ctn_type, state_field) = (ctn_type.trim(), state_field.trim());
+        if ctn_type.is_empty() || state_field.is_empty() {
+            return Err(syntax());
+        }
+        check_data_field(data_field)?;
+
+        Ok(Self {
+            ctn_type: ctn_type.to_string(),
+            state_field: state_field.to_string(),
+            data_field: data_field.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for FieldOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}={}",
+            self.ctn_type, self.state_field, self.data_field
+        )
+    }
+}
+
+/// Errors parsing or applying a field mapping override
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldMapError {
+    /// Not of the form `ctn_type:state_field=data_field`
+    Syntax(String),
+    /// An empty data field, or one containing whitespace
+    InvalidDataField(String),
+    /// A state field the contract does not define
+    UnknownStateField { field: String, known: Vec<String> },
+}
+
+impl std::fmt::Display for FieldMapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax(spec) => write!(
+                f,
+                "Invalid field mapping '{}' (expected ctn_type:state_field=data_field)",
+                spec
+            ),
+            Self::InvalidDataField(field) => write!(f, "Invalid data field '{}'", field),
+            Self::UnknownStateField { field, known } => write!(
+                f,
+                "Unknown state field '{}' (known: {})",
+                field,
+                known.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FieldMapError {}
+
+/// Check `state_field` against `data_field` instead of its current mapping
+///
+/// Returns the data field it was mapped to. The collection mappings follow:
+/// if no other state field uses the previous data field, it is renamed in
+/// the required and optional data fields; otherwise the new one is added
+/// as optional.
+pub fn remap_field(
+    contract: &mut CtnContract,
+    state_field: &str,
+    data_field: &str,
+) -> Result<String, FieldMapError> {
+    check_data_field(data_field)?;
+    let requirements = &contract.state_requirements;
+    let state_fields: Vec<String> = requirements
+        .required_fields
+        .iter()
+        .chain(&requirements.optional_fields)
+        .map(|spec| spec.name.clone())
+        .collect();
+    if !state_fields.iter().any(|name| name == state_field) {
+        return Err(FieldMapError::UnknownStateField {
+            field: state_field.to_string(),
+            known: state_fields,
+        });
+    }
+
+    let state_to_data = &mut contract.field_mappings.validation_mappings.state_to_data;
+    let previous = state_to_data
+        .insert(state_field.to_string(), data_field.to_string())
+        .unwrap_or_else(|| state_field.to_string());
+    let still_used = state_fields.iter().any(|name| {
+        state_to_data
+            .get(name)
+            .map_or(name.as_str(), String::as_str)
+            == previous
+    });
+
+    let collection = &mut contract.field_mappings.collection_mappings;
+    let listed = collection
+        .required_data_fields
+        .iter()
+        .any(|f| f == data_field)
+        || collection
+            .optional_data_fields
+            .iter()
+            .any(|f| f == data_field);
+    if !still_used {
+        for fields in [
+            &mut collection.required_data_fields,
+            &mut collection.optional_data_fields,
+        ] {
+            if listed {
+                fields.retain(|field| *field != previous);
+            } else if let Some(field) = fields.iter_mut().find(|field| **field == previous) {
+                *field = data_field.to_string();
+            }
+        }
+    }
+    let listed = collection
+        .required_data_fields
+        .iter()
+        .any(|f| f == data_field)
+        || collection
+            .optional_data_fields
+            .iter()
+            .any(|f| f == data_field);
+    if !listed {
+        collection.optional_data_fields.push(data_field.to_string());
+    }
+
+    Ok(previous)
+}
+
+/// Apply the overrides for `ctn_type` to its contract, in order
+pub fn apply_field_overrides(
+    contract: &mut CtnContract,
+    ctn_type: &str,
+    overrides: &[FieldOverride],
+) -> Result<(), FieldMapError> {
+    for o in overrides.iter().filter(|o| o.ctn_type == ctn_type) {
+        remap_field(contract, &o.state_field, &o.data_field)?;
+    }
+    Ok(())
+}
+
+/// Reject a data field no collector could emit
+fn check_data_field(field: &str) -> Result<(), FieldMapError> {
+    if field.is_empty() || field.chars().any(char::is_whitespace) {
+        return Err(FieldMapError::InvalidDataField(field.to_string()));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_file_metadata_contract;
+
+    fn data_fields(contract: &CtnContract) -> (Vec<String>, Vec<String>) {
+        let mappings = &contract.field_mappings.collection_mappings;
+        (
+            mappings.required_data_fields.clone(),
+            mappings.optional_data_fields.clone(),
+        )
+    }
+
+    #[test]
+    fn test_parse_field_override() {
+        let o = FieldOverride::parse("file_metadata:owner_id=owner").unwrap();
+        assert_eq!(o.ctn_type, "file_metadata");
+        assert_eq!(o.state_field, "owner_id");
+        assert_eq!(o.data_field, "owner");
+        assert_eq!(o.to_string(), "file_metadata:owner_id=owner");
+
+        for spec in [
+            "file_metadata",
+            "file_metadata:owner_id",
+            ":owner_id=owner",
+            "x:=owner",
+        ] {
+            assert_eq!(
+                FieldOverride::parse(spec),
+                Err(FieldMapError::Syntax(spec.to_string()))
+            );
+        }
+        assert_eq!(
+            FieldOverride::parse("file_metadata:owner_id="),
+            Err(FieldMapError::InvalidDataField(String::new()))
+        );
+        assert!(FieldOverride::parse("file_metadata:owner_id=file owner").is_err());
+    }
+
+    #[test]
+    fn test_remap_field_renames_unused_data_field() {
+        let mut contract = create_file_metadata_contract();
+        let previous = remap_field(&mut contract, "owner_id", "owner").unwrap();
+        assert_eq!(previous, "file_owner");
+
+        let mappings = &contract.field_mappings.validation_mappings.state_to_data;
+        assert_eq!(mappings.get("owner_id").map(String::as_str), Some("owner"));
+        let (required, _) = data_fields(&contract);
+        assert!(required.contains(&"owner".to_string()));
+        assert!(!required.contains(&"file_owner".to_string()));
+    }
+
+    #[test]
+    fn test_remap_field_keeps_data_field_still_in_use() {
+        let mut contract = create_file_metadata_contract();
+        remap_field(&mut contract, "writable", "readable").unwrap();
+        let (required, _) = data_fields(&contract);
+        assert!(!required.contains(&"writable".to_string()));
+
+        // `readable` still maps to its data field, so it stays required
+        remap_field(&mut contract, "writable", "write_access").unwrap();
+        let (after, optional) = data_fields(&contract);
+        assert_eq!(after, required);
+        assert!(optional.contains(&"write_access".to_string()));
+    }
+
+    #[test]
+    fn test_remap_field_rejects_unknown_state_field() {
+        let mut contract = create_file_metadata_contract();
+        let error = remap_field(&mut contract, "ownr_id", "owner").unwrap_err();
+        assert!(matches!(
+            error,
+            FieldMapError::UnknownStateField { ref field, ref known }
+                if field == "ownr_id" && known.contains(&"owner_id".to_string())
+        ));
+        assert!(!contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .contains_key("ownr_id"));
+    }
+
+    #[test]
+    fn test_apply_field_overrides_is_scoped_to_ctn_type() {
+        let overrides = vec![
+            FieldOverride::parse("file_metadata:owner_id=owner").unwrap(),
+            FieldOverride::parse("process:name=comm").unwrap(),
+        ];
+        let mut contract = create_file_metadata_contract();
+        apply_field_overrides(&mut contract, "file_metadata", &overrides).unwrap();
+        let mappings = &contract.field_mappings.validation_mappings.state_to_data;
+        assert_eq!(mappings.get("owner_id").map(String::as_str), Some("owner"));
+        assert!(!mappings.contains_key("name"));
+    }
+}
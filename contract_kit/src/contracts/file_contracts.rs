@@ -1,6 +1,6 @@
 //! # File System CTN Contracts
 //!
-//! Contracts for file metadata and content validation.
+//! Contracts for file metadata, content and hash validation.
 //!
 //! ## Field Portability
 //!
@@ -8,8 +8,11 @@
 //!
 //! | Category | Fields | Notes |
 //! |----------|--------|-------|
-//! | Portable | `exists`, `readable`, `writable`, `size`, `is_directory`, `owner_id`, `group_id` | Work identically on all platforms |
+//! | Portable | `exists`, `readable`, `writable`, `size`, `is_directory`, `owner_id`, `group_id`, `owner_name`, `group_name`, `is_symlink`, `symlink_target`, `mtime`, `atime`, `age_seconds` | Work identically on all platforms |
+//! | Portable, may be 0 | `ctime` | Creation time; 0 where the file system does not record it |
+//! | Portable, platform-specific values | `acl`, `record` | POSIX ACL on Unix, DACL on Windows |
 //! | Linux/macOS | `permissions` | Octal mode string, empty on Windows |
+//! | Linux | `selinux_context` | SELinux context with `BEHAVIOR collect_acls`, empty without it or without SELinux labels |
 //! | Windows | `is_readonly`, `is_hidden`, `is_system` | Windows attributes, `false` on Unix |
 
 use execution_engine::strategies::{
@@ -26,10 +29,22 @@ use execution_engine::types::common::{DataType, Operation};
 /// - `exists`, `readable`, `writable`, `size`, `is_directory`
 /// - `owner_id` (UID on Unix, SID on Windows)
 /// - `group_id` (GID on Unix, SID on Windows)
+/// - `owner_name`, `group_name` (resolved names; empty if unresolvable)
+/// - `is_symlink`, `symlink_target` (the link itself; other fields follow it)
+/// - `acl` (set of ACL entries) and `record` (record checks over the ACL);
+///   reading an ACL may need elevated privileges and is empty when it fails
+/// - `mtime`, `atime`, `ctime` (epoch seconds; `ctime` is the creation time,
+///   not the Unix inode change time, and 0 where it is not recorded)
+/// - `age_seconds` (seconds since the last modification, at collection time)
 ///
 /// ## Platform-Specific Fields
 /// - `permissions` - Linux/macOS only (octal string)
+/// - `selinux_context` - Linux only, read with `BEHAVIOR collect_acls`;
+///   empty without it or if the file has no SELinux label
 /// - `is_readonly`, `is_hidden`, `is_system` - Windows only
+///
+/// With `BEHAVIOR glob_expand`, a wildcard `path` matches any number of
+/// files, each checked as a separate object.
 pub fn create_file_metadata_contract() -> CtnContract {
     let mut contract = CtnContract::new("file_metadata".to_string());
 
@@ -47,7 +62,9 @@ pub fn create_file_metadata_contract() -> CtnContract {
                 "/etc/sudoers".to_string(),
                 "C:\\Windows\\System32\\config\\SAM".to_string(),
             ],
-            validation_notes: Some("Supports VAR resolution".to_string()),
+            validation_notes: Some(
+                "Supports VAR resolution; wildcards with BEHAVIOR glob_expand".to_string(),
+            ),
         });
 
     contract
@@ -131,11 +148,18 @@ pub fn create_file_metadata_contract() -> CtnContract {
         .add_optional_field(StateFieldSpec {
             name: "owner_id".to_string(),
             data_type: DataType::String,
-            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::PatternMatch,
+            ],
             description: "File owner identifier (UID on Unix, SID on Windows)".to_string(),
             example_values: vec!["0".to_string(), "S-1-5-18".to_string()],
             validation_notes: Some(
-                "Portable field, platform-specific values: UID string on Unix, SID on Windows"
+                "Portable field, platform-specific values: UID string on Unix, SID on Windows. \
+                 `=` also accepts a collection of allowed values and passes if the identifier is \
+                 one of them; `!=` passes if it is none of them. `pattern_match` takes a regex, \
+                 e.g. `^S-1-5-21-.*-500$`"
                     .to_string(),
             ),
         });
@@ -145,11 +169,199 @@ pub fn create_file_metadata_contract() -> CtnContract {
         .add_optional_field(StateFieldSpec {
             name: "group_id".to_string(),
             data_type: DataType::String,
-            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::PatternMatch,
+            ],
             description: "File group identifier (GID on Unix, SID on Windows)".to_string(),
             example_values: vec!["0".to_string(), "S-1-5-32-544".to_string()],
             validation_notes: Some(
-                "Portable field, platform-specific values: GID string on Unix, SID on Windows"
+                "Portable field, platform-specific values: GID string on Unix, SID on Windows. \
+                 `=` also accepts a collection of allowed values and passes if the identifier is \
+                 one of them; `!=` passes if it is none of them. `pattern_match` takes a regex, \
+                 e.g. `^S-1-5-21-.*-500$`"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "owner_name".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "File owner name (user name on Unix, DOMAIN\\User on Windows)"
+                .to_string(),
+            example_values: vec!["root".to_string(), "NT AUTHORITY\\SYSTEM".to_string()],
+            validation_notes: Some(
+                "Portable: resolved via getpwuid on Unix, LookupAccountSid on Windows. Empty if the ID cannot be resolved"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "group_name".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "File group name (group name on Unix, DOMAIN\\Group on Windows)"
+                .to_string(),
+            example_values: vec!["root".to_string(), "BUILTIN\\Administrators".to_string()],
+            validation_notes: Some(
+                "Portable: resolved via getgrgid on Unix, LookupAccountSid on Windows. Empty if the ID cannot be resolved"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "is_symlink".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether the path itself is a symbolic link".to_string(),
+            example_values: vec!["true".to_string(), "false".to_string()],
+            validation_notes: Some(
+                "Portable: not followed. Includes junctions on Windows. On Linux/macOS other fields describe the link target, so a broken link has exists = false"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+        name: "symlink_target".to_string(),
+        data_type: DataType::String,
+        allowed_operations: vec![
+            Operation::Equals,
+            Operation::NotEqual,
+            Operation::Contains,
+            Operation::NotContains,
+            Operation::StartsWith,
+            Operation::EndsWith,
+            Operation::PatternMatch,
+        ],
+        description: "Target of the symbolic link as stored in the link".to_string(),
+        example_values: vec![
+            "/usr/share/zoneinfo/UTC".to_string(),
+            "../usr/share/zoneinfo/UTC".to_string(),
+        ],
+        validation_notes: Some(
+            "Portable: read verbatim (may be relative or missing). Empty if not a symbolic link"
+                .to_string(),
+        ),
+    });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "acl".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![Operation::Contains, Operation::NotContains],
+            description: "Set of access control list entries as tag:principal:permissions"
+                .to_string(),
+            example_values: vec![
+                "user:alice:rw-".to_string(),
+                "allow:BUILTIN\\Users:full".to_string(),
+            ],
+            validation_notes: Some(
+                "contains passes if any entry contains the substring. POSIX ACL on Linux (owner, group and other only on macOS), DACL on Windows. Empty if the ACL cannot be read, which may need elevated privileges"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "record".to_string(),
+            data_type: DataType::RecordData,
+            allowed_operations: vec![Operation::Equals],
+            description: "Record validation over the access control list".to_string(),
+            example_values: vec!["See record_checks".to_string()],
+            validation_notes: Some(
+                "Paths: entries.*.tag, entries.*.principal, entries.*.permissions".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "mtime".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Last modification time in seconds since the Unix epoch".to_string(),
+            example_values: vec!["1700000000".to_string()],
+            validation_notes: Some("Portable: 0 if the file does not exist".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "atime".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Last access time in seconds since the Unix epoch".to_string(),
+            example_values: vec!["1700000000".to_string()],
+            validation_notes: Some(
+                "Portable: may lag behind real access when the file system is mounted noatime or relatime"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "ctime".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Creation time in seconds since the Unix epoch".to_string(),
+            example_values: vec!["1700000000".to_string()],
+            validation_notes: Some(
+                "Portable field, platform-specific support: creation (birth) time, not the Unix inode change time. 0 where the platform or file system does not record it"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "age_seconds".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Seconds since the last modification".to_string(),
+            example_values: vec!["86400".to_string()],
+            validation_notes: Some(
+                "Portable: measured when the file is collected, e.g. age_seconds < 86400 for files modified within a day. 0 if the file does not exist"
                     .to_string(),
             ),
         });
@@ -172,6 +384,32 @@ pub fn create_file_metadata_contract() -> CtnContract {
             ),
         });
 
+    // ========================================================================
+    // State Requirements - Linux Only
+    // ========================================================================
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "selinux_context".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::Contains,
+                Operation::NotContains,
+            ],
+            description: "SELinux context as user:role:type:level (Linux only)".to_string(),
+            example_values: vec![
+                "system_u:object_r:shadow_t:s0".to_string(),
+                ":shadow_t:".to_string(),
+            ],
+            validation_notes: Some(
+                "Linux only: security.selinux extended attribute, read with BEHAVIOR collect_acls. Empty without it, if SELinux is disabled, the file system has no labels, or on macOS/Windows"
+                    .to_string(),
+            ),
+        });
+
     // ========================================================================
     // State Requirements - Windows Only
     // ========================================================================
@@ -231,13 +469,29 @@ pub fn create_file_metadata_contract() -> CtnContract {
         "is_directory".to_string(),
         "file_owner".to_string(),
         "file_group".to_string(),
+        "file_owner_name".to_string(),
+        "file_group_name".to_string(),
+        "is_symlink".to_string(),
+        "symlink_target".to_string(),
+        "acl_entries".to_string(),
+        "acl_record".to_string(),
+        "modified_unix".to_string(),
+        "accessed_unix".to_string(),
+        "created_unix".to_string(),
         // Platform-specific (may be empty/false on some platforms)
         "file_mode".to_string(),
+        "selinux_context".to_string(),
         "is_readonly".to_string(),
         "is_hidden".to_string(),
         "is_system".to_string(),
     ];
 
+    // Derived from the time of collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = vec!["age_seconds".to_string()];
+
     // Portable mappings
     contract
         .field_mappings
@@ -274,6 +528,48 @@ pub fn create_file_metadata_contract() -> CtnContract {
         .validation_mappings
         .state_to_data
         .insert("group_id".to_string(), "file_group".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("owner_name".to_string(), "file_owner_name".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("group_name".to_string(), "file_group_name".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("is_symlink".to_string(), "is_symlink".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("symlink_target".to_string(), "symlink_target".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("acl".to_string(), "acl_entries".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("record".to_string(), "acl_record".to_string());
+    for (state_field, data_field) in [
+        ("mtime", "modified_unix"),
+        ("atime", "accessed_unix"),
+        ("ctime", "created_unix"),
+        ("age_seconds", "age_seconds"),
+    ] {
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(state_field.to_string(), data_field.to_string());
+    }
 
     // Linux/macOS only
     contract
@@ -282,6 +578,13 @@ pub fn create_file_metadata_contract() -> CtnContract {
         .state_to_data
         .insert("permissions".to_string(), "file_mode".to_string());
 
+    // Linux only
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("selinux_context".to_string(), "selinux_context".to_string());
+
     // Windows only
     contract
         .field_mappings
@@ -316,6 +619,23 @@ pub fn create_file_metadata_contract() -> CtnContract {
         },
     };
 
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "glob_expand".to_string(),
+        behavior_type: BehaviorType::Flag,
+        parameters: vec![],
+        description: "Expand wildcards in path and check each matching file as an object"
+            .to_string(),
+        example: "BEHAVIOR glob_expand".to_string(),
+    });
+
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "collect_acls".to_string(),
+        behavior_type: BehaviorType::Flag,
+        parameters: vec![],
+        description: "Also read the file's SELinux context into selinux_context".to_string(),
+        example: "BEHAVIOR collect_acls".to_string(),
+    });
+
     contract
 }
 
@@ -369,6 +689,33 @@ pub fn create_file_content_contract() -> CtnContract {
             validation_notes: Some("Binary files will error or return as binary".to_string()),
         });
 
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "content_capture".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+                Operation::Contains,
+                Operation::NotContains,
+                Operation::StartsWith,
+                Operation::EndsWith,
+                Operation::PatternMatch,
+            ],
+            description: "First capture group of the preceding content pattern_match"
+                .to_string(),
+            example_values: vec!["4".to_string(), "yes".to_string(), "1.2.3".to_string()],
+            validation_notes: Some(
+                "Compared as the field's declared type (string, int, boolean, version or evr_string); fails if the pattern did not match or a version does not parse"
+                    .to_string(),
+            ),
+        });
+
     // Field mappings
     contract
         .field_mappings
@@ -404,17 +751,34 @@ pub fn create_file_content_contract() -> CtnContract {
     contract.add_supported_behavior(SupportedBehavior {
         name: "recursive_scan".to_string(),
         behavior_type: BehaviorType::Flag,
-        parameters: vec![BehaviorParameter {
-            name: "max_depth".to_string(),
-            data_type: DataType::Int,
-            required: false,
-            default_value: Some("3".to_string()),
-            description: "Maximum directory depth for recursive scan".to_string(),
-        }],
+        parameters: vec![
+            BehaviorParameter {
+                name: "max_depth".to_string(),
+                data_type: DataType::Int,
+                required: false,
+                default_value: Some("3".to_string()),
+                description: "Maximum directory depth for recursive scan".to_string(),
+            },
+            BehaviorParameter {
+                name: "walk_threads".to_string(),
+                data_type: DataType::Int,
+                required: false,
+                default_value: Some("4".to_string()),
+                description: "Threads used to walk the directory tree (capped at 16)".to_string(),
+            },
+        ],
         description: "Recursively scan directories for matching files".to_string(),
         example: "BEHAVIOR recursive_scan max_depth 5".to_string(),
     });
 
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "strict_scan".to_string(),
+        behavior_type: BehaviorType::Flag,
+        parameters: vec![],
+        description: "Report paths a recursive scan could not read for lack of permission as a collection error instead of skipping them".to_string(),
+        example: "BEHAVIOR recursive_scan strict_scan".to_string(),
+    });
+
     contract.add_supported_behavior(SupportedBehavior {
         name: "include_hidden".to_string(),
         behavior_type: BehaviorType::Flag,
@@ -439,5 +803,177 @@ pub fn create_file_content_contract() -> CtnContract {
         example: "BEHAVIOR follow_symlinks".to_string(),
     });
 
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "tail_lines".to_string(),
+        behavior_type: BehaviorType::Parameter,
+        parameters: vec![BehaviorParameter {
+            name: "tail_lines".to_string(),
+            data_type: DataType::Int,
+            required: true,
+            default_value: None,
+            description: "Number of lines to read from the end of the file".to_string(),
+        }],
+        description: "Collect only the last lines of the file".to_string(),
+        example: "BEHAVIOR tail_lines 1000".to_string(),
+    });
+
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "head_bytes".to_string(),
+        behavior_type: BehaviorType::Parameter,
+        parameters: vec![BehaviorParameter {
+            name: "head_bytes".to_string(),
+            data_type: DataType::Int,
+            required: true,
+            default_value: None,
+            description: "Number of bytes to read from the start of the file".to_string(),
+        }],
+        description: "Collect only the first bytes of the file".to_string(),
+        example: "BEHAVIOR head_bytes 512".to_string(),
+    });
+
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "byte_range".to_string(),
+        behavior_type: BehaviorType::Flag,
+        parameters: vec![
+            BehaviorParameter {
+                name: "offset".to_string(),
+                data_type: DataType::Int,
+                required: false,
+                default_value: Some("0".to_string()),
+                description: "Byte offset to start reading at".to_string(),
+            },
+            BehaviorParameter {
+                name: "length".to_string(),
+                data_type: DataType::Int,
+                required: true,
+                default_value: None,
+                description: "Number of bytes to read".to_string(),
+            },
+        ],
+        description: "Collect only a byte range of the file".to_string(),
+        example: "BEHAVIOR byte_range offset 4096 length 512".to_string(),
+    });
+
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "max_bytes".to_string(),
+        behavior_type: BehaviorType::Parameter,
+        parameters: vec![BehaviorParameter {
+            name: "max_bytes".to_string(),
+            data_type: DataType::Int,
+            required: false,
+            default_value: Some("10485760".to_string()),
+            description: "Maximum bytes of content to collect per object".to_string(),
+        }],
+        description: "Fail collection instead of reading more than max_bytes (10 MiB by default); recursive scans share one budget across all files".to_string(),
+        example: "BEHAVIOR max_bytes 1048576".to_string(),
+    });
+
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "pattern_extract".to_string(),
+        behavior_type: BehaviorType::Flag,
+        parameters: vec![],
+        description: "Compare the first capture of a content pattern_match as content_capture (always enabled)".to_string(),
+        example: "BEHAVIOR pattern_extract".to_string(),
+    });
+
+    contract
+}
+
+/// Create contract for file_hash CTN type
+///
+/// SHA-256 of a file's content, for comparing against a known-good digest
+/// (a golden config, a vendor binary). The file is hashed in chunks, so
+/// there is no size limit and the content is never held in memory.
+pub fn create_file_hash_contract() -> CtnContract {
+    let mut contract = CtnContract::new("file_hash".to_string());
+
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "path".to_string(),
+            data_type: DataType::String,
+            description: "Path of the file to hash".to_string(),
+            example_values: vec![
+                "/etc/ssh/sshd_config".to_string(),
+                "C:\\Windows\\System32\\drivers\\etc\\hosts".to_string(),
+            ],
+            validation_notes: Some("Supports VAR resolution".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "sha256".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "SHA-256 of the file content, lowercase hex".to_string(),
+            example_values: vec![
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".to_string(),
+            ],
+            validation_notes: Some(
+                "Expected digests are compared case-insensitively, ignoring surrounding whitespace"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "size".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Number of bytes hashed".to_string(),
+            example_values: vec!["3242".to_string()],
+            validation_notes: None,
+        });
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("path".to_string(), "target_path".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["sha256".to_string(), "file_size".to_string()];
+
+    for (state_field, data_field) in [("sha256", "sha256"), ("size", "file_size")] {
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(state_field.to_string(), data_field.to_string());
+    }
+
+    // Collection strategy - reads the whole file, but one chunk at a time
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "filesystem".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec!["file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(20),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: true,
+            requires_elevated_privileges: false,
+        },
+    };
+
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "hash_only".to_string(),
+        behavior_type: BehaviorType::Flag,
+        parameters: vec![],
+        description: "Hash the file without collecting its content (always enabled)".to_string(),
+        example: "BEHAVIOR hash_only".to_string(),
+    });
+
     contract
 }
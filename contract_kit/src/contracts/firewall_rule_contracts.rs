@@ -0,0 +1,313 @@
+//! Firewall Rule CTN contract
+//!
+//! Validates the host firewall ruleset (nftables or legacy iptables).
+//! Used for default-deny and port exposure controls.
+
+use execution_engine::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    StateFieldSpec,
+};
+use execution_engine::types::common::{DataType, Operation};
+
+use super::k8s_resource_contracts::add_retry_behaviors;
+
+/// Create contract for firewall_rule CTN type
+///
+/// Reads `nft -j list ruleset` (fallback: `iptables-save` or `iptables -S`)
+/// and returns the parsed ruleset as RecordData along with derived
+/// policy/exposure fields.
+pub fn create_firewall_rule_contract() -> CtnContract {
+    let mut contract = CtnContract::new("firewall_rule".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "port".to_string(),
+            data_type: DataType::Int,
+            description: "Destination port to evaluate for exposure".to_string(),
+            example_values: vec!["22".to_string(), "3389".to_string()],
+            validation_notes: Some(
+                "Port range 1-65535; required for allows_from_anywhere".to_string(),
+            ),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "protocol".to_string(),
+            data_type: DataType::String,
+            description: "Transport protocol for the port (default: tcp)".to_string(),
+            example_values: vec!["tcp".to_string(), "udp".to_string()],
+            validation_notes: Some("tcp or udp".to_string()),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "chain".to_string(),
+            data_type: DataType::String,
+            description: "Chain to scope rules and default_policy to (default: input)".to_string(),
+            example_values: vec!["input".to_string(), "FORWARD".to_string()],
+            validation_notes: Some("Chain name or base chain hook, case-insensitive".to_string()),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "match_contains".to_string(),
+            data_type: DataType::String,
+            description: "Substring a rule must contain for has_rule".to_string(),
+            example_values: vec!["tcp dport 22 accept".to_string()],
+            validation_notes: Some(
+                "Matched case-insensitively against rule text; required for has_rule".to_string(),
+            ),
+        });
+
+    // State requirements
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "record".to_string(),
+            data_type: DataType::RecordData,
+            allowed_operations: vec![Operation::Equals],
+            description: "Record validation over the parsed ruleset".to_string(),
+            example_values: vec!["See record_checks".to_string()],
+            validation_notes: Some(
+                "Paths: backend, chains.*.policy, rules.*.action, rules.*.dports.*.start"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "backend".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Firewall backend the ruleset was read from".to_string(),
+            example_values: vec!["nftables".to_string(), "iptables".to_string()],
+            validation_notes: Some("Lowercase backend name".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "default_input_policy".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Effective default policy for inbound traffic".to_string(),
+            example_values: vec!["drop".to_string(), "accept".to_string()],
+            validation_notes: Some(
+                "Most restrictive policy across input chains; accept if none".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "default_policy".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Effective default verdict of the object's chain (default: input)"
+                .to_string(),
+            example_values: vec!["drop".to_string(), "reject".to_string()],
+            validation_notes: Some(
+                "accept, drop or reject; a catch-all drop/reject rule overrides an accept \
+                 policy. Not collected for chains that are not base chains"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "default_forward_policy".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Effective default policy for forwarded traffic".to_string(),
+            example_values: vec!["drop".to_string(), "accept".to_string()],
+            validation_notes: Some(
+                "Most restrictive policy across forward chains; accept if none".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "allows_from_anywhere".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether new connections from any address reach the object's port"
+                .to_string(),
+            example_values: vec!["false".to_string()],
+            validation_notes: Some("Only collected when the object sets port".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "has_rule".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether any rule in scope contains the object's match_contains"
+                .to_string(),
+            example_values: vec!["true".to_string()],
+            validation_notes: Some(
+                "Only collected when the object sets match_contains".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "rules".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![Operation::Contains, Operation::NotContains],
+            description: "Set of rules in backend-neutral text form".to_string(),
+            example_values: vec!["input tcp dport 22 accept".to_string()],
+            validation_notes: Some(
+                "contains passes if any rule contains the substring".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "rule_count".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Number of rules in the filter ruleset".to_string(),
+            example_values: vec!["0".to_string(), "12".to_string()],
+            validation_notes: Some(
+                "Counts rules in all chains of the filter ruleset, or in the object's chain"
+                    .to_string(),
+            ),
+        });
+
+    // Field mappings - object to collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("port".to_string(), "port".to_string());
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("protocol".to_string(), "protocol".to_string());
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("chain".to_string(), "chain".to_string());
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("match_contains".to_string(), "match_contains".to_string());
+
+    // Required data fields from collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec![
+        "ruleset".to_string(),
+        "backend".to_string(),
+        "default_input_policy".to_string(),
+        "rules".to_string(),
+    ];
+
+    // Optional data fields
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = vec![
+        "default_forward_policy".to_string(),
+        "default_policy".to_string(),
+        "allows_from_anywhere".to_string(),
+        "has_rule".to_string(),
+        "rule_count".to_string(),
+    ];
+
+    // State to data mappings for validation
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("record".to_string(), "ruleset".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("backend".to_string(), "backend".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert(
+            "default_input_policy".to_string(),
+            "default_input_policy".to_string(),
+        );
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("default_policy".to_string(), "default_policy".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert(
+            "default_forward_policy".to_string(),
+            "default_forward_policy".to_string(),
+        );
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert(
+            "allows_from_anywhere".to_string(),
+            "allows_from_anywhere".to_string(),
+        );
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("rules".to_string(), "rules".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("has_rule".to_string(), "has_rule".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("rule_count".to_string(), "rule_count".to_string());
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "firewall_rule".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec!["firewall_read_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(100),
+            memory_usage_mb: Some(2),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: true,
+        },
+    };
+
+    add_retry_behaviors(&mut contract, "nft and iptables");
+
+    contract
+}
@@ -4,8 +4,8 @@
 //! Returns resource JSON as RecordData for record check validation.
 
 use execution_engine::strategies::{
-    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
-    StateFieldSpec,
+    BehaviorParameter, BehaviorType, CollectionMode, CollectionStrategy, CtnContract,
+    ObjectFieldSpec, PerformanceHints, StateFieldSpec, SupportedBehavior,
 };
 use execution_engine::types::common::{DataType, Operation};
 
@@ -121,7 +121,12 @@ pub fn create_k8s_resource_contract() -> CtnContract {
             ],
             description: "Number of matching resources".to_string(),
             example_values: vec!["0".to_string(), "1".to_string()],
-            validation_notes: Some("Count before name_prefix filtering".to_string()),
+            validation_notes: Some(
+                "Count before name_prefix filtering. `=` also accepts a collection of allowed \
+                 counts and passes if the count is one of them; `!=` passes if it is none of \
+                 them. An empty collection allows no count"
+                    .to_string(),
+            ),
         });
 
     // Field mappings - object to collection
@@ -194,5 +199,79 @@ pub fn create_k8s_resource_contract() -> CtnContract {
         },
     };
 
+    add_retry_behaviors(&mut contract, "kubectl");
+
     contract
 }
+
+/// Add the retry behaviors of command-based collectors to a contract
+///
+/// `command` names the command retried in the descriptions. Shared by the
+/// `k8s_resource`, `audit_rule` and `firewall_rule` contracts.
+pub(crate) fn add_retry_behaviors(contract: &mut CtnContract, command: &str) {
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "retry".to_string(),
+        behavior_type: BehaviorType::Flag,
+        parameters: vec![
+            BehaviorParameter {
+                name: "count".to_string(),
+                data_type: DataType::Int,
+                required: true,
+                default_value: None,
+                description: "Maximum retries after a transient failure (capped at 10)".to_string(),
+            },
+            BehaviorParameter {
+                name: "interval_ms".to_string(),
+                data_type: DataType::Int,
+                required: false,
+                default_value: Some("500".to_string()),
+                description: "Delay before each retry (capped at 30s)".to_string(),
+            },
+        ],
+        description: format!(
+            "Retry {} at a fixed interval on transient failures such as timeouts",
+            command
+        ),
+        example: "BEHAVIOR retry count 3 interval_ms 500".to_string(),
+    });
+
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "exponential_backoff".to_string(),
+        behavior_type: BehaviorType::Flag,
+        parameters: vec![],
+        description: "Double the retry interval after each retry".to_string(),
+        example: "BEHAVIOR exponential_backoff".to_string(),
+    });
+
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "retries".to_string(),
+        behavior_type: BehaviorType::Parameter,
+        parameters: vec![BehaviorParameter {
+            name: "retries".to_string(),
+            data_type: DataType::Int,
+            required: true,
+            default_value: Some("0".to_string()),
+            description: "Maximum retries after a transient failure (capped at 10)".to_string(),
+        }],
+        description: format!(
+            "Retry {} with exponential backoff on transient failures",
+            command
+        ),
+        example: "BEHAVIOR retries 3".to_string(),
+    });
+
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "backoff_ms".to_string(),
+        behavior_type: BehaviorType::Parameter,
+        parameters: vec![BehaviorParameter {
+            name: "backoff_ms".to_string(),
+            data_type: DataType::Int,
+            required: true,
+            default_value: Some("500".to_string()),
+            description: "Delay before the first retry, doubled after each (capped at 30s)"
+                .to_string(),
+        }],
+        description: format!("Initial backoff between {} retries", command),
+        example: "BEHAVIOR backoff_ms 1000".to_string(),
+    });
+}
@@ -0,0 +1,117 @@
+//! Kernel module CTN contract
+//!
+//! Validates whether a Linux kernel module is loaded, blacklisted and
+//! loadable, e.g. "ensure cramfs is disabled".
+
+use execution_engine::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    StateFieldSpec,
+};
+use execution_engine::types::common::{DataType, Operation};
+
+/// Create contract for kernel_module CTN type
+///
+/// Reads `/proc/modules` and the modprobe.d configuration, and dry-runs
+/// `modprobe -n -v` (Linux only). A module that does not exist collects as
+/// not loaded, not blacklisted and not loadable.
+pub fn create_kernel_module_contract() -> CtnContract {
+    let mut contract = CtnContract::new("kernel_module".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "name".to_string(),
+            data_type: DataType::String,
+            description: "Kernel module name".to_string(),
+            example_values: vec!["cramfs".to_string(), "usb-storage".to_string()],
+            validation_notes: Some(
+                "'-' and '_' are interchangeable: usb-storage and usb_storage are the same module"
+                    .to_string(),
+            ),
+        });
+
+    // State requirements
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "loaded".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether the module is currently loaded".to_string(),
+            example_values: vec!["false".to_string()],
+            validation_notes: Some(
+                "Listed in /proc/modules; modules built into the kernel are not listed".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "blacklisted".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether a modprobe.d 'blacklist' line names the module".to_string(),
+            example_values: vec!["true".to_string()],
+            validation_notes: Some(
+                "Blacklisting only stops loading by alias; check loadable as well".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "loadable".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether 'modprobe -n -v' would load the module".to_string(),
+            example_values: vec!["false".to_string()],
+            validation_notes: Some(
+                "false when modprobe cannot find the module or an 'install' line runs \
+                 /bin/true or /bin/false"
+                    .to_string(),
+            ),
+        });
+
+    // Field mappings - object to collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("name".to_string(), "name".to_string());
+
+    // Required data fields from collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec![
+        "loaded".to_string(),
+        "blacklisted".to_string(),
+        "loadable".to_string(),
+    ];
+
+    // State to data mappings for validation
+    for field in ["loaded", "blacklisted", "loadable"] {
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "kernel_module".to_string(),
+        collection_mode: CollectionMode::Metadata,
+        required_capabilities: vec!["modprobe_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(20),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+    };
+
+    contract
+}
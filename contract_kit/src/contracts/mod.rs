@@ -5,15 +5,45 @@
 //! - State requirements: What fields can be validated and with which operations
 //! - Field mappings: How to map between ESP field names and collected data
 //! - Collection strategy: Performance hints and capabilities
+//!
+//! Field mappings can be overridden per CTN type at startup (see `field_map`).
 
+pub mod audit_rule_contracts;
 pub mod computed_values;
+pub mod cron_contracts;
+pub mod dpkg_package_contracts;
+pub mod env_var_contracts;
+pub mod field_map;
 pub mod file_contracts;
+pub mod firewall_rule_contracts;
 pub mod json_contracts;
 pub mod k8s_resource_contracts;
+pub mod kernel_module_contracts;
+pub mod mount_contracts;
+pub mod process_contracts;
+pub mod registry_contracts;
+pub mod structured_record_contracts;
 pub mod tcp_listener_contracts;
+pub mod user_account_contracts;
 
+pub use audit_rule_contracts::create_audit_rule_contract;
 pub use computed_values::create_computed_values_contract;
-pub use file_contracts::{create_file_content_contract, create_file_metadata_contract};
+pub use cron_contracts::create_cron_contract;
+pub use dpkg_package_contracts::create_dpkg_package_contract;
+pub use env_var_contracts::create_env_var_contract;
+pub use field_map::{apply_field_overrides, remap_field, FieldMapError, FieldOverride};
+pub use file_contracts::{
+    create_file_content_contract, create_file_hash_contract, create_file_metadata_contract,
+};
+pub use firewall_rule_contracts::create_firewall_rule_contract;
 pub use json_contracts::create_json_record_contract;
 pub use k8s_resource_contracts::create_k8s_resource_contract;
+pub use kernel_module_contracts::create_kernel_module_contract;
+pub use mount_contracts::create_mount_contract;
+pub use process_contracts::create_process_contract;
+pub use registry_contracts::create_registry_value_contract;
+pub use structured_record_contracts::{
+    create_structured_record_contract, STRUCTURED_RECORD_FORMATS,
+};
 pub use tcp_listener_contracts::create_tcp_listener_contract;
+pub use user_account_contracts::create_user_account_contract;
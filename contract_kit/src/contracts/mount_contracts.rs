@@ -0,0 +1,141 @@
+//! Mount point CTN contract
+//!
+//! Validates whether a path is a mount point and its filesystem type,
+//! device and mount options.
+
+use execution_engine::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    StateFieldSpec,
+};
+use execution_engine::types::common::{DataType, Operation};
+
+/// Create contract for mount_point CTN type
+///
+/// Reads `/proc/mounts` (Linux only). A path inside a mount that is not
+/// itself a mount point has `mounted = false`.
+pub fn create_mount_contract() -> CtnContract {
+    let mut contract = CtnContract::new("mount_point".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "path".to_string(),
+            data_type: DataType::String,
+            description: "Mount point directory".to_string(),
+            example_values: vec!["/tmp".to_string(), "/dev/shm".to_string()],
+            validation_notes: Some(
+                "Absolute path; symlinks are resolved before the lookup".to_string(),
+            ),
+        });
+
+    // State requirements
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "mounted".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether a filesystem is mounted at the path".to_string(),
+            example_values: vec!["true".to_string(), "false".to_string()],
+            validation_notes: Some(
+                "false for a directory inside a mount that is not itself a mount point".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "fstype".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::PatternMatch,
+            ],
+            description: "Filesystem type".to_string(),
+            example_values: vec!["tmpfs".to_string(), "ext4".to_string()],
+            validation_notes: Some("Not collected when nothing is mounted".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "device".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::StartsWith,
+                Operation::PatternMatch,
+            ],
+            description: "Mounted device or source".to_string(),
+            example_values: vec!["/dev/sda2".to_string(), "tmpfs".to_string()],
+            validation_notes: Some(
+                "Bind mounts report the device of the filesystem they expose".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "options".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![Operation::Contains, Operation::NotContains],
+            description: "Mount options".to_string(),
+            example_values: vec!["nosuid".to_string(), "noexec".to_string()],
+            validation_notes: Some(
+                "Matches whole options: 'exec' does not match 'noexec'; \
+                 options with values match as written ('size=1024k')"
+                    .to_string(),
+            ),
+        });
+
+    // Field mappings - object to collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("path".to_string(), "path".to_string());
+
+    // Required data fields from collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["mounted".to_string()];
+
+    // Optional data fields
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = vec![
+        "fstype".to_string(),
+        "device".to_string(),
+        "options".to_string(),
+    ];
+
+    // State to data mappings for validation
+    for field in ["mounted", "fstype", "device", "options"] {
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "mount".to_string(),
+        collection_mode: CollectionMode::Metadata,
+        required_capabilities: vec!["mount_table_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(1),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+    };
+
+    contract
+}
@@ -0,0 +1,135 @@
+//! Process CTN contract
+//!
+//! Validates running processes selected by name or command line: whether
+//! any is running, how many, and their PIDs.
+
+use execution_engine::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    StateFieldSpec,
+};
+use execution_engine::types::common::{DataType, Operation};
+
+/// Create contract for process CTN type
+///
+/// Reads `/proc` on Linux and a Toolhelp snapshot on Windows. Zombie and
+/// defunct processes are never counted.
+pub fn create_process_contract() -> CtnContract {
+    let mut contract = CtnContract::new("process".to_string());
+
+    // Object requirements - at least one of name / cmdline_contains
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "name".to_string(),
+            data_type: DataType::String,
+            description: "Process name".to_string(),
+            example_values: vec!["sshd".to_string(), "auditd".to_string()],
+            validation_notes: Some(
+                "Matches the kernel process name or the file name of argv[0]; on Windows the image name, case-insensitive, with or without .exe"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "cmdline_contains".to_string(),
+            data_type: DataType::String,
+            description: "Substring of the command line".to_string(),
+            example_values: vec!["--config /etc/app.yaml".to_string()],
+            validation_notes: Some(
+                "Arguments are joined with spaces; not supported on Windows".to_string(),
+            ),
+        });
+
+    // State requirements
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "running".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether any matching process is running".to_string(),
+            example_values: vec!["true".to_string(), "false".to_string()],
+            validation_notes: None,
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "count".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Number of matching processes".to_string(),
+            example_values: vec!["0".to_string(), "1".to_string()],
+            validation_notes: Some(
+                "Zombie and defunct processes are not counted. `=` and `!=` also accept a \
+                 collection of allowed counts, as for k8s_resource"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "pids".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![Operation::Contains, Operation::NotContains],
+            description: "PIDs of the matching processes".to_string(),
+            example_values: vec!["1".to_string()],
+            validation_notes: Some(
+                "contains passes if any matching process has the PID".to_string(),
+            ),
+        });
+
+    // Field mappings - object to collection
+    for field in ["name", "cmdline_contains"] {
+        contract
+            .field_mappings
+            .collection_mappings
+            .object_to_collection
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Required data fields from collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec![
+        "running".to_string(),
+        "count".to_string(),
+        "pids".to_string(),
+    ];
+
+    // State to data mappings for validation
+    for field in ["running", "count", "pids"] {
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "process".to_string(),
+        collection_mode: CollectionMode::Metadata,
+        required_capabilities: vec!["process_list_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(50),
+            memory_usage_mb: Some(2),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+    };
+
+    contract
+}
@@ -0,0 +1,177 @@
+//! Registry Value CTN contract
+//!
+//! Validates a single Windows registry value: whether it exists, its type,
+//! and its data.
+
+use execution_engine::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    StateFieldSpec,
+};
+use execution_engine::types::common::{DataType, Operation};
+
+/// Create contract for registry_value CTN type
+///
+/// Reads one value with `RegGetValueW`. Windows only.
+pub fn create_registry_value_contract() -> CtnContract {
+    let mut contract = CtnContract::new("registry_value".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "hive".to_string(),
+            data_type: DataType::String,
+            description: "Root key".to_string(),
+            example_values: vec![
+                "HKLM".to_string(),
+                "HKEY_LOCAL_MACHINE".to_string(),
+                "HKCU".to_string(),
+            ],
+            validation_notes: Some(
+                "HKLM, HKCU, HKCR, HKU, HKCC or their full names (case-insensitive)".to_string(),
+            ),
+        });
+
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "key_path".to_string(),
+            data_type: DataType::String,
+            description: "Key path below the hive".to_string(),
+            example_values: vec![
+                r"SYSTEM\CurrentControlSet\Control\Lsa".to_string(),
+                r"SOFTWARE\Policies\Microsoft\Windows\WindowsUpdate\AU".to_string(),
+            ],
+            validation_notes: Some("Backslash-separated; forward slashes are accepted".to_string()),
+        });
+
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "value_name".to_string(),
+            data_type: DataType::String,
+            description: "Name of the value to read".to_string(),
+            example_values: vec![
+                "LmCompatibilityLevel".to_string(),
+                "NoAutoUpdate".to_string(),
+            ],
+            validation_notes: Some("Empty string reads the key's default value".to_string()),
+        });
+
+    // State requirements
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "exists".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether the value exists".to_string(),
+            example_values: vec!["true".to_string(), "false".to_string()],
+            validation_notes: Some("false when the key or the value is missing".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "value_type".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Registry value type".to_string(),
+            example_values: vec![
+                "REG_SZ".to_string(),
+                "REG_DWORD".to_string(),
+                "REG_MULTI_SZ".to_string(),
+            ],
+            validation_notes: None,
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "value_data".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::Contains,
+                Operation::NotContains,
+                Operation::StartsWith,
+                Operation::EndsWith,
+                Operation::PatternMatch,
+            ],
+            description: "Value data as a string".to_string(),
+            example_values: vec!["5".to_string(), "%SystemRoot%\\System32".to_string()],
+            validation_notes: Some(
+                "REG_MULTI_SZ is newline-joined, REG_BINARY is lowercase hex, REG_EXPAND_SZ is not expanded"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "value_int".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Numeric value data".to_string(),
+            example_values: vec!["0".to_string(), "5".to_string()],
+            validation_notes: Some("Only collected for REG_DWORD and REG_QWORD".to_string()),
+        });
+
+    // Field mappings - object to collection
+    for field in ["hive", "key_path", "value_name"] {
+        contract
+            .field_mappings
+            .collection_mappings
+            .object_to_collection
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Required data fields from collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["exists".to_string()];
+
+    // Optional data fields
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = vec![
+        "value_type".to_string(),
+        "value_data".to_string(),
+        "value_int".to_string(),
+    ];
+
+    // State to data mappings for validation
+    for field in ["exists", "value_type", "value_data", "value_int"] {
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "registry".to_string(),
+        collection_mode: CollectionMode::Metadata,
+        required_capabilities: vec!["registry_read_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(5),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+    };
+
+    contract
+}
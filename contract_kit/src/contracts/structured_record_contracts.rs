@@ -0,0 +1,111 @@
+//! Structured record CTN contract
+//!
+//! Validates configuration files in JSON, TOML, INI or YAML with the same
+//! field path record checks as `json_record`.
+
+use execution_engine::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    StateFieldSpec,
+};
+use execution_engine::types::common::{DataType, Operation};
+
+/// Formats the `format` object field accepts
+pub const STRUCTURED_RECORD_FORMATS: [&str; 4] = ["json", "toml", "ini", "yaml"];
+
+/// Create contract for structured_record CTN type
+///
+/// The collected `record_data` is checked by `JsonRecordExecutor`, so
+/// record checks behave exactly as for `json_record`.
+pub fn create_structured_record_contract() -> CtnContract {
+    let mut contract = CtnContract::new("structured_record".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "path".to_string(),
+            data_type: DataType::String,
+            description: "Path to configuration file".to_string(),
+            example_values: vec![
+                "/etc/containerd/config.toml".to_string(),
+                "/etc/ssh/sshd_config".to_string(),
+            ],
+            validation_notes: Some("Must parse as the given format".to_string()),
+        });
+
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "format".to_string(),
+            data_type: DataType::String,
+            description: "File format".to_string(),
+            example_values: STRUCTURED_RECORD_FORMATS
+                .iter()
+                .map(|f| f.to_string())
+                .collect(),
+            validation_notes: Some(format!(
+                "One of: {}. INI sections become nested objects; all INI values are strings",
+                STRUCTURED_RECORD_FORMATS.join(", ")
+            )),
+        });
+
+    // State requirements - allow record checks
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "record".to_string(),
+            data_type: DataType::RecordData,
+            allowed_operations: vec![Operation::Equals],
+            description: "Record validation with field paths".to_string(),
+            example_values: vec!["See record_checks".to_string()],
+            validation_notes: Some(
+                "A repeated INI key is an array of its values; use key[*]".to_string(),
+            ),
+        });
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("path".to_string(), "file_path".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("format".to_string(), "format".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["record_data".to_string()];
+
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("record".to_string(), "record_data".to_string());
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "structured_record".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec![
+            "file_access".to_string(),
+            "json_parsing".to_string(),
+            "toml_parsing".to_string(),
+            "ini_parsing".to_string(),
+            "yaml_parsing".to_string(),
+        ],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(100),
+            memory_usage_mb: Some(10),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+    };
+
+    contract
+}
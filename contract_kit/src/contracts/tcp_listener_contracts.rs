@@ -11,7 +11,8 @@ use execution_engine::types::common::{DataType, Operation};
 
 /// Create contract for tcp_listener CTN type
 ///
-/// Checks if a TCP port is listening on the local system by reading /proc/net/tcp.
+/// Checks if a TCP port is listening on the local system by reading /proc/net/tcp
+/// and /proc/net/tcp6.
 pub fn create_tcp_listener_contract() -> CtnContract {
     let mut contract = CtnContract::new("tcp_listener".to_string());
 
@@ -35,11 +36,22 @@ pub fn create_tcp_listener_contract() -> CtnContract {
             example_values: vec![
                 "0.0.0.0".to_string(),
                 "127.0.0.1".to_string(),
+                "::1".to_string(),
                 "any".to_string(),
             ],
             validation_notes: Some("Use 'any' or omit to match any bind address".to_string()),
         });
 
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "family".to_string(),
+            data_type: DataType::String,
+            description: "Address family of the listener (default: any)".to_string(),
+            example_values: vec!["v4".to_string(), "v6".to_string(), "any".to_string()],
+            validation_notes: Some("One of v4, v6 or any".to_string()),
+        });
+
     // State requirements
     contract
         .state_requirements
@@ -52,6 +64,37 @@ pub fn create_tcp_listener_contract() -> CtnContract {
             validation_notes: Some("true if any process is listening on the port".to_string()),
         });
 
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "owner_process".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::Contains,
+                Operation::StartsWith,
+                Operation::PatternMatch,
+            ],
+            description: "Name of the process that owns the listening socket".to_string(),
+            example_values: vec!["sshd".to_string(), "nginx".to_string()],
+            validation_notes: Some("Linux: comm name, at most 15 characters; Windows: image name (e.g. sshd.exe). Empty if the owner could not be found, which on Linux needs privilege for other users' processes".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+        name: "owner_pid".to_string(),
+        data_type: DataType::Int,
+        allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+        description: "PID of the process that owns the listening socket".to_string(),
+        example_values: vec!["1".to_string(), "812".to_string()],
+        validation_notes: Some(
+            "Only collected when the owner was found; on Linux, the lowest PID sharing the socket"
+                .to_string(),
+        ),
+    });
+
     // Field mappings - object to collection
     contract
         .field_mappings
@@ -63,6 +106,11 @@ pub fn create_tcp_listener_contract() -> CtnContract {
         .collection_mappings
         .object_to_collection
         .insert("host".to_string(), "host".to_string());
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("family".to_string(), "family".to_string());
 
     // Required data fields from collection
     contract
@@ -74,7 +122,11 @@ pub fn create_tcp_listener_contract() -> CtnContract {
     contract
         .field_mappings
         .collection_mappings
-        .optional_data_fields = vec!["local_address".to_string()];
+        .optional_data_fields = vec![
+        "local_address".to_string(),
+        "owner_process".to_string(),
+        "owner_pid".to_string(),
+    ];
 
     // State to data mappings for validation
     contract
@@ -82,6 +134,16 @@ pub fn create_tcp_listener_contract() -> CtnContract {
         .validation_mappings
         .state_to_data
         .insert("listening".to_string(), "listening".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("owner_process".to_string(), "owner_process".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("owner_pid".to_string(), "owner_pid".to_string());
 
     // Collection strategy
     contract.collection_strategy = CollectionStrategy {
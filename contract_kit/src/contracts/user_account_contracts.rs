@@ -0,0 +1,267 @@
+//! User account CTN contract
+//!
+//! Validates a local account selected by username or uid: whether it
+//! exists, how many accounts match, and the first one's ids, home
+//! directory, shell and primary group.
+
+use execution_engine::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    StateFieldSpec,
+};
+use execution_engine::types::common::{DataType, Operation};
+
+/// Create contract for user_account CTN type
+///
+/// Parses `/etc/passwd` and `/etc/group` on Linux and looks up local
+/// accounts on Windows. When several accounts match, `count` says how
+/// many and the other state fields describe the first.
+pub fn create_user_account_contract() -> CtnContract {
+    let mut contract = CtnContract::new("user_account".to_string());
+
+    // Object requirements - exactly one of username / uid
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "username".to_string(),
+            data_type: DataType::String,
+            description: "Login name of the account".to_string(),
+            example_values: vec!["root".to_string(), "nginx".to_string()],
+            validation_notes: Some("Exact match; case-insensitive on Windows".to_string()),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "uid".to_string(),
+            data_type: DataType::Int,
+            description: "User ID of the account".to_string(),
+            example_values: vec!["0".to_string(), "1000".to_string()],
+            validation_notes: Some("The account RID on Windows".to_string()),
+        });
+
+    // State requirements
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "exists".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether any account matches".to_string(),
+            example_values: vec!["true".to_string(), "false".to_string()],
+            validation_notes: None,
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "count".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Number of matching accounts".to_string(),
+            example_values: vec!["0".to_string(), "1".to_string()],
+            validation_notes: Some(
+                "Duplicate usernames and accounts sharing a uid are each counted".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "username".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::Contains,
+                Operation::StartsWith,
+                Operation::EndsWith,
+                Operation::PatternMatch,
+            ],
+            description: "Login name of the first matching account".to_string(),
+            example_values: vec!["root".to_string(), "nginx".to_string()],
+            validation_notes: Some("Not collected when no account matches".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "uid".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "User ID of the first matching account".to_string(),
+            example_values: vec!["0".to_string(), "1000".to_string()],
+            validation_notes: Some(
+                "The account RID on Windows; not collected when no account matches".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "gid".to_string(),
+            data_type: DataType::Int,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Primary group ID of the first matching account".to_string(),
+            example_values: vec!["0".to_string(), "100".to_string()],
+            validation_notes: Some(
+                "The primary group RID on Windows; not collected when no account matches"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "shell".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::Contains,
+                Operation::StartsWith,
+                Operation::EndsWith,
+                Operation::PatternMatch,
+            ],
+            description: "Login shell of the first matching account, as written".to_string(),
+            example_values: vec!["/usr/sbin/nologin".to_string(), "/bin/bash".to_string()],
+            validation_notes: Some(
+                "Not collected on Windows or when no account matches".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "home".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::Contains,
+                Operation::StartsWith,
+                Operation::EndsWith,
+                Operation::PatternMatch,
+            ],
+            description: "Home directory of the first matching account, as written".to_string(),
+            example_values: vec!["/root".to_string(), "/home/alice".to_string()],
+            validation_notes: Some("Not collected when no account matches".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "group".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::Contains,
+                Operation::StartsWith,
+                Operation::EndsWith,
+                Operation::PatternMatch,
+            ],
+            description: "Name of the first matching account's primary group".to_string(),
+            example_values: vec!["root".to_string(), "wheel".to_string()],
+            validation_notes: Some(
+                "Not collected on Windows or when the gid has no group in /etc/group".to_string(),
+            ),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "is_system".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Whether the first matching account is a system account".to_string(),
+            example_values: vec!["true".to_string(), "false".to_string()],
+            validation_notes: Some(
+                "uid below 1000; not collected when no account matches".to_string(),
+            ),
+        });
+
+    // Field mappings - object to collection
+    for field in ["username", "uid"] {
+        contract
+            .field_mappings
+            .collection_mappings
+            .object_to_collection
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Required data fields from collection
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["exists".to_string(), "count".to_string()];
+
+    // Optional data fields, collected only when an account matches
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = vec![
+        "username".to_string(),
+        "uid".to_string(),
+        "gid".to_string(),
+        "shell".to_string(),
+        "home".to_string(),
+        "group".to_string(),
+        "is_system".to_string(),
+    ];
+
+    // State to data mappings for validation
+    for field in [
+        "exists",
+        "count",
+        "username",
+        "uid",
+        "gid",
+        "shell",
+        "home",
+        "group",
+        "is_system",
+    ] {
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(field.to_string(), field.to_string());
+    }
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "passwd".to_string(),
+        collection_mode: CollectionMode::Metadata,
+        required_capabilities: vec!["account_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(5),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+    };
+
+    contract
+}
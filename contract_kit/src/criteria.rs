@@ -0,0 +1,501 @@
+//! # Criteria Groups
+//!
+//! A policy's `CRI` blocks form a tree: each block combines its members
+//! with `AND` (all must pass) or `OR` (at least one must pass), and may be
+//! negated. The engine evaluates this tree into `tree_passed`; this module
+//! rebuilds the same tree with an outcome for every block, so embedders can
+//! see which member satisfied an any-of group.
+//!
+//! Member outcomes come from an `OutcomeRecorder` shared with
+//! `RecordingExecutor`-wrapped executors. Criteria are evaluated depth-first
+//! in policy order, so recorded outcomes are matched to `CTN` blocks by
+//! position and checked by CTN type.
+//!
+//! ## Combination Rules
+//!
+//! | Logic | Result |
+//! |-------|--------|
+//! | `AND` | Fail if any member fails, else Error if any errored, else Pass |
+//! | `OR`  | Pass if any member passes, else Error if any errored, else Fail |
+//!
+//! Negation swaps Pass and Fail; Error is unchanged.
+
+use common::results::Outcome;
+
+use crate::executors::recording::{copy_outcome, RecordedOutcome};
+
+/// How a `CRI` block combines its members
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupLogic {
+    /// `CRI AND` - every member must pass
+    All,
+    /// `CRI OR` - at least one member must pass
+    Any,
+}
+
+impl GroupLogic {
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "AND" => Some(Self::All),
+            "OR" => Some(Self::Any),
+            _ => None,
+        }
+    }
+
+    /// ESP keyword for this logic
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::All => "AND",
+            Self::Any => "OR",
+        }
+    }
+}
+
+/// Structure of one `CRI` block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CriteriaGroup {
+    pub logic: GroupLogic,
+    pub negate: bool,
+    pub members: Vec<CriteriaMember>,
+}
+
+/// A member of a `CRI` block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CriteriaMember {
+    /// A `CTN` criterion
+    Criterion { ctn_type: String },
+    /// A nested `CRI` block
+    Group(CriteriaGroup),
+}
+
+impl CriteriaGroup {
+    /// Number of criteria in this group, including nested groups
+    pub fn criterion_count(&self) -> usize {
+        self.members
+            .iter()
+            .map(|member| match member {
+                CriteriaMember::Criterion { .. } => 1,
+                CriteriaMember::Group(group) => group.criterion_count(),
+            })
+            .sum()
+    }
+}
+
+/// Evaluated `CRI` block
+#[derive(Debug, PartialEq)]
+pub struct GroupResult {
+    pub logic: GroupLogic,
+    pub negated: bool,
+    pub outcome: Outcome,
+    pub members: Vec<MemberResult>,
+}
+
+/// Evaluated member of a `CRI` block
+#[derive(Debug, PartialEq)]
+pub enum MemberResult {
+    Criterion { ctn_type: String, outcome: Outcome },
+    Group(GroupResult),
+}
+
+impl MemberResult {
+    pub fn outcome(&self) -> &Outcome {
+        match self {
+            Self::Criterion { outcome, .. } => outcome,
+            Self::Group(group) => &group.outcome,
+        }
+    }
+}
+
+impl GroupResult {
+    pub fn passed(&self) -> bool {
+        self.outcome == Outcome::Pass
+    }
+
+    /// Members that passed; for an `OR` group, the ones that satisfied it
+    pub fn passing_members(&self) -> impl Iterator<Item = &MemberResult> {
+        self.members
+            .iter()
+            .filter(|member| *member.outcome() == Outcome::Pass)
+    }
+}
+
+/// Parse the `CRI` block structure of ESP source
+///
+/// Returns the top-level blocks in policy order. Only `CRI`, `CRI_END` and
+/// `CTN` lines are inspected; the compiler validates everything else.
+pub fn parse_criteria_groups(source: &str) -> Result<Vec<CriteriaGroup>, GroupError> {
+    let mut roots = Vec::new();
+    let mut open: Vec<CriteriaGroup> = Vec::new();
+
+    for (index, raw) in source.lines().enumerate() {
+        let line = index + 1;
+        let invalid = |reason: &str| GroupError::InvalidBlock {
+            line,
+            reason: reason.to_string(),
+        };
+        let mut tokens = raw.split('#').next().unwrap_or("").split_whitespace();
+
+        match tokens.next() {
+            Some("CRI") => {
+                let logic = tokens
+                    .next()
+                    .and_then(GroupLogic::from_keyword)
+                    .ok_or_else(|| invalid("CRI must be followed by AND or OR"))?;
+                let negate = match tokens.next() {
+                    None | Some("false") => false,
+                    Some("true") => true,
+                    Some(_) => return Err(invalid("CRI negate flag must be true or false")),
+                };
+                open.push(CriteriaGroup {
+                    logic,
+                    negate,
+                    members: Vec::new(),
+                });
+            }
+            Some("CRI_END") => {
+                let group = open.pop().ok_or_else(|| invalid("CRI_END without CRI"))?;
+                match open.last_mut() {
+                    Some(parent) => parent.members.push(CriteriaMember::Group(group)),
+                    None => roots.push(group),
+                }
+            }
+            Some("CTN") => {
+                let ctn_type = tokens
+                    .next()
+                    .ok_or_else(|| invalid("CTN must name a CTN type"))?;
+                let parent = open
+                    .last_mut()
+                    .ok_or_else(|| invalid("CTN outside a CRI block"))?;
+                parent.members.push(CriteriaMember::Criterion {
+                    ctn_type: ctn_type.to_string(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    if !open.is_empty() {
+        return Err(GroupError::InvalidBlock {
+            line: source.lines().count(),
+            reason: format!("{} CRI block(s) not closed", open.len()),
+        });
+    }
+
+    Ok(roots)
+}
+
+/// Evaluate groups against recorded criterion outcomes
+///
+/// `outcomes` must hold exactly one outcome per criterion, in policy order.
+pub fn evaluate_groups(
+    groups: &[CriteriaGroup],
+    outcomes: &[RecordedOutcome],
+) -> Result<Vec<GroupResult>, GroupError> {
+    let expected: usize = groups.iter().map(CriteriaGroup::criterion_count).sum();
+    if outcomes.len() != expected {
+        return Err(GroupError::OutcomeCount {
+            expected,
+            recorded: outcomes.len(),
+        });
+    }
+
+    let mut remaining = outcomes.iter().enumerate();
+    groups
+        .iter()
+        .map(|group| evaluate_group(group, &mut remaining))
+        .collect()
+}
+
+/// Evaluate one group, consuming its criteria's outcomes
+fn evaluate_group<'a>(
+    group: &CriteriaGroup,
+    outcomes: &mut impl Iterator<Item = (usize, &'a RecordedOutcome)>,
+) -> Result<GroupResult, GroupError> {
+    let mut members = Vec::with_capacity(group.members.len());
+
+    for member in &group.members {
+        let result = match member {
+            CriteriaMember::Criterion { ctn_type } => {
+                let (position, recorded) = outcomes.next().ok_or(GroupError::OutcomeCount {
+                    expected: group.criterion_count(),
+                    recorded: 0,
+                })?;
+                if recorded.ctn_type != *ctn_type {
+                    return Err(GroupError::TypeMismatch {
+                        position,
+                        expected: ctn_type.clone(),
+                        recorded: recorded.ctn_type.clone(),
+                    });
+                }
+                MemberResult::Criterion {
+                    ctn_type: ctn_type.clone(),
+                    outcome: copy_outcome(&recorded.outcome),
+                }
+            }
+            CriteriaMember::Group(nested) => MemberResult::Group(evaluate_group(nested, outcomes)?),
+        };
+        members.push(result);
+    }
+
+    Ok(GroupResult {
+        logic: group.logic,
+        negated: group.negate,
+        outcome: combine_outcomes(
+            group.logic,
+            group.negate,
+            members.iter().map(MemberResult::outcome),
+        ),
+        members,
+    })
+}
+
+/// Combine member outcomes with `logic`, then apply negation
+pub fn combine_outcomes<'a>(
+    logic: GroupLogic,
+    negate: bool,
+    outcomes: impl IntoIterator<Item = &'a Outcome>,
+) -> Outcome {
+    let (mut any_pass, mut any_fail, mut any_error) = (false, false, false);
+    for outcome in outcomes {
+        match outcome {
+            Outcome::Pass => any_pass = true,
+            Outcome::Fail => any_fail = true,
+            _ => any_error = true,
+        }
+    }
+
+    let combined = match logic {
+        GroupLogic::All if any_fail => Outcome::Fail,
+        GroupLogic::All if any_error => Outcome::Error,
+        GroupLogic::All => Outcome::Pass,
+        GroupLogic::Any if any_pass => Outcome::Pass,
+        GroupLogic::Any if any_error => Outcome::Error,
+        GroupLogic::Any => Outcome::Fail,
+    };
+
+    match combined {
+        Outcome::Pass if negate => Outcome::Fail,
+        Outcome::Fail if negate => Outcome::Pass,
+        other => other,
+    }
+}
+
+/// Render group results as an indented outline
+pub fn format_group_outline(groups: &[GroupResult]) -> String {
+    let mut outline = String::new();
+    for group in groups {
+        push_group(&mut outline, group, 0);
+    }
+    outline
+}
+
+fn push_group(outline: &mut String, group: &GroupResult, depth: usize) {
+    let negated = if group.negated { " (negated)" } else { "" };
+    outline.push_str(&format!(
+        "{}CRI {}{}: {:?}\n",
+        "  ".repeat(depth),
+        group.logic.as_str(),
+        negated,
+        group.outcome
+    ));
+    for member in &group.members {
+        match member {
+            MemberResult::Criterion { ctn_type, outcome } => {
+                outline.push_str(&format!(
+                    "{}CTN {}: {:?}\n",
+                    "  ".repeat(depth + 1),
+                    ctn_type,
+                    outcome
+                ));
+            }
+            MemberResult::Group(nested) => push_group(outline, nested, depth + 1),
+        }
+    }
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors rebuilding criteria group results
+#[derive(Debug)]
+pub enum GroupError {
+    /// `CRI`/`CTN` block structure could not be read
+    InvalidBlock { line: usize, reason: String },
+    /// Recorded outcomes do not cover every criterion exactly once
+    OutcomeCount { expected: usize, recorded: usize },
+    /// A recorded outcome belongs to a different CTN type than expected
+    TypeMismatch {
+        position: usize,
+        expected: String,
+        recorded: String,
+    },
+}
+
+impl std::fmt::Display for GroupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidBlock { line, reason } => {
+                write!(f, "Invalid criteria block on line {}: {}", line, reason)
+            }
+            Self::OutcomeCount { expected, recorded } => write!(
+                f,
+                "Expected {} criterion outcomes, recorded {} (are all executors wrapped in RecordingExecutor?)",
+                expected, recorded
+            ),
+            Self::TypeMismatch {
+                position,
+                expected,
+                recorded,
+            } => write!(
+                f,
+                "Criterion {} should be '{}' but recorded '{}'",
+                position + 1,
+                expected,
+                recorded
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GroupError {}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANY_OF_POLICY: &str = r#"
+DEF
+    OBJECT sshd_primary
+        path `/etc/ssh/sshd_config`
+    OBJECT_END
+
+    # At least one of the config locations must be hardened
+    CRI OR
+        CTN file_content
+            TEST all all
+            STATE_REF hardened
+            OBJECT_REF sshd_primary
+        CTN_END
+
+        CTN file_content
+            TEST all all
+            STATE_REF hardened
+            OBJECT_REF sshd_dropin
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+    fn recorded(ctn_type: &str, outcome: Outcome) -> RecordedOutcome {
+        RecordedOutcome::new(ctn_type, outcome)
+    }
+
+    #[test]
+    fn test_any_of_group_passes_with_one_member() {
+        let groups = parse_criteria_groups(ANY_OF_POLICY).unwrap();
+        let results = evaluate_groups(
+            &groups,
+            &[
+                recorded("file_content", Outcome::Fail),
+                recorded("file_content", Outcome::Pass),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].logic, GroupLogic::Any);
+        assert!(results[0].passed());
+        assert_eq!(results[0].passing_members().count(), 1);
+        assert_eq!(*results[0].members[0].outcome(), Outcome::Fail);
+    }
+
+    #[test]
+    fn test_nested_groups_and_negation() {
+        let source = "CRI AND\n  CTN tcp_listener\n  CTN_END\n  CRI OR true # negated\n    CTN file_metadata\n    CTN_END\n  CRI_END\nCRI_END\n";
+        let groups = parse_criteria_groups(source).unwrap();
+        assert_eq!(groups[0].criterion_count(), 2);
+
+        let results = evaluate_groups(
+            &groups,
+            &[
+                recorded("tcp_listener", Outcome::Pass),
+                recorded("file_metadata", Outcome::Fail),
+            ],
+        )
+        .unwrap();
+
+        assert!(matches!(
+            &results[0].members[1],
+            MemberResult::Group(nested) if nested.negated && nested.outcome == Outcome::Pass
+        ));
+        assert!(results[0].passed());
+        assert_eq!(
+            format_group_outline(&results),
+            "CRI AND: Pass\n  CTN tcp_listener: Pass\n  CRI OR (negated): Pass\n    CTN file_metadata: Fail\n"
+        );
+    }
+
+    #[test]
+    fn test_combine_outcomes() {
+        use Outcome::{Error, Fail, Pass};
+
+        assert_eq!(
+            combine_outcomes(GroupLogic::All, false, &[Pass, Fail, Pass]),
+            Fail
+        );
+        assert_eq!(
+            combine_outcomes(GroupLogic::All, false, &[Pass, Error, Pass]),
+            Error
+        );
+        assert_eq!(
+            combine_outcomes(GroupLogic::Any, false, &[Error, Fail, Pass]),
+            Pass
+        );
+        assert_eq!(
+            combine_outcomes(GroupLogic::Any, false, &[Fail, Fail]),
+            Fail
+        );
+        assert_eq!(
+            combine_outcomes(GroupLogic::Any, false, &[Error, Error]),
+            Error
+        );
+        assert_eq!(combine_outcomes(GroupLogic::All, true, &[Pass]), Fail);
+        assert_eq!(combine_outcomes(GroupLogic::All, true, &[Error]), Error);
+    }
+
+    #[test]
+    fn test_mismatched_outcomes_rejected() {
+        let groups = parse_criteria_groups(ANY_OF_POLICY).unwrap();
+
+        assert!(matches!(
+            evaluate_groups(&groups, &[recorded("file_content", Outcome::Pass)]),
+            Err(GroupError::OutcomeCount {
+                expected: 2,
+                recorded: 1
+            })
+        ));
+        assert!(matches!(
+            evaluate_groups(
+                &groups,
+                &[
+                    recorded("file_content", Outcome::Pass),
+                    recorded("tcp_listener", Outcome::Pass),
+                ],
+            ),
+            Err(GroupError::TypeMismatch { position: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_unbalanced_blocks_rejected() {
+        assert!(parse_criteria_groups("CRI AND\n  CTN file_metadata\n").is_err());
+        assert!(parse_criteria_groups("CRI_END\n").is_err());
+        assert!(parse_criteria_groups("CRI XOR\nCRI_END\n").is_err());
+    }
+}
@@ -34,7 +34,10 @@
 //! }
 //! ```
 
+use std::collections::HashMap;
+use std::ops::Deref;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 // ============================================================================
@@ -60,6 +63,53 @@ pub use execution_engine::strategies::{
 // Re-export the full strategies module for registry creation
 pub use execution_engine::strategies;
 
+// Collection plans (for explain)
+pub use crate::collectors::planning::{PlanRecorder, PlannedCollection};
+
+// Criteria group results
+pub use crate::criteria::{
+    combine_outcomes, format_group_outline, CriteriaGroup, CriteriaMember, GroupError, GroupLogic,
+    GroupResult, MemberResult,
+};
+pub use crate::executors::recording::{
+    FieldFailure, OutcomeRecorder, RecordedOutcome, RecordingExecutor,
+};
+
+// Criterion decision traces (for explaining a single check)
+pub use crate::executors::trace::{
+    explain_criterion, format_trace, CriterionExplanation, TraceRecorder, TracingExecutor,
+};
+
+// Finding formatting
+pub use crate::finding_format::{
+    format_findings, ControlRef, DefaultFindingFormatter, FindingContext, FindingFormatter,
+};
+
+// Policy pre-flight checks
+pub use crate::policy_check::{validate_policy, PolicyWarning, PolicyWarningKind};
+
+// Scan-time policy variables
+pub use crate::policy_vars::{bind_vars, declared_vars, VarBindingError};
+
+// Exit codes for multi-policy scans
+pub use crate::exit_code::{ExitCodeMapError, ExitCodePolicy, ScanCounts};
+
+// Compiled AST cache for repeated scans
+#[cfg(feature = "native")]
+pub use crate::scan_cache::ScanCache;
+
+// Record check path resolution (for explain_json_record)
+pub use crate::executors::json_record::{dry_validate_paths, PathResolution};
+pub use execution_engine::types::execution_context::ExecutableCriterion;
+
+// Command whitelist audit (commands a registry's executors may run)
+#[cfg(feature = "native")]
+pub use crate::commands::allowlist::{allowed_commands, CommandAllowlist};
+
+// Per-criterion timings (slowest checks of a scan)
+#[cfg(feature = "native")]
+pub use crate::executors::timing::{timing_breakdown, CriterionTimer, CriterionTiming};
+
 // AST types (for scan_ast)
 pub use common::ast::nodes::EspFile;
 
@@ -73,8 +123,9 @@ pub use execution_engine::execution::engine::PolicyExecutionResult as ScanResult
 pub use execution_engine::types::ExecutionManifest;
 
 // Logging utilities (optional, for users who want logging)
+pub use crate::log_context::FileContext;
+pub use crate::{log_debug, log_error, log_info, log_success};
 pub use common::logging;
-pub use common::{log_debug, log_error, log_info, log_success};
 
 // ============================================================================
 // Error Type
@@ -86,7 +137,9 @@ pub enum ScanError {
     /// File I/O error
     IoError(std::io::Error),
     /// ESP compilation failed
-    CompilationFailed(String),
+    CompilationFailed(Vec<Diagnostic>),
+    /// A serialized AST could not be read
+    InvalidAst(String),
     /// AST conversion failed
     ConversionFailed(String),
     /// Resolution phase failed
@@ -95,17 +148,51 @@ pub enum ScanError {
     ExecutionFailed(String),
     /// Registry error
     RegistryError(String),
+    /// Criteria group results could not be rebuilt
+    GroupEvaluation(GroupError),
+    /// Scan-time variables could not be bound to the policy
+    VarBinding(VarBindingError),
+    /// A record file could not be read or is not JSON
+    RecordFile(String),
+    /// The policy uses a CTN type the agent registers no strategy for
+    CtnTypeDenied { ctn_type: String, line: usize },
 }
 
 impl std::fmt::Display for ScanError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::IoError(e) => write!(f, "I/O error: {}", e),
-            Self::CompilationFailed(msg) => write!(f, "Compilation failed: {}", msg),
+            Self::CompilationFailed(diagnostics) => {
+                write!(f, "Compilation failed: {}", format_diagnostics(diagnostics))
+            }
+            Self::InvalidAst(msg) => write!(f, "Invalid AST: {}", msg),
             Self::ConversionFailed(msg) => write!(f, "AST conversion failed: {}", msg),
             Self::ResolutionFailed(msg) => write!(f, "Resolution failed: {}", msg),
             Self::ExecutionFailed(msg) => write!(f, "Execution failed: {}", msg),
             Self::RegistryError(msg) => write!(f, "Registry error: {}", msg),
+            Self::GroupEvaluation(e) => write!(f, "Criteria group evaluation failed: {}", e),
+            Self::VarBinding(e) => write!(f, "Variable binding failed: {}", e),
+            Self::RecordFile(msg) => write!(f, "Record file error: {}", msg),
+            Self::CtnTypeDenied { ctn_type, line } => write!(
+                f,
+                "CTN type '{}' is denied by agent configuration (line {})",
+                ctn_type, line
+            ),
+        }
+    }
+}
+
+impl ScanError {
+    /// Build a compilation error from compiler output
+    pub fn compilation_failed(message: &str) -> Self {
+        Self::CompilationFailed(parse_diagnostics(message))
+    }
+
+    /// Compiler diagnostics, if this is a compilation failure
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            Self::CompilationFailed(diagnostics) => diagnostics,
+            _ => &[],
         }
     }
 }
@@ -114,6 +201,8 @@ impl std::error::Error for ScanError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::IoError(e) => Some(e),
+            Self::GroupEvaluation(e) => Some(e),
+            Self::VarBinding(e) => Some(e),
             _ => None,
         }
     }
@@ -137,6 +226,143 @@ impl From<execution_engine::conversion::ConversionError> for ScanError {
     }
 }
 
+// ============================================================================
+// Compiler Diagnostics
+// ============================================================================
+
+/// Severity of a compiler diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+impl std::fmt::Display for DiagnosticSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A single compiler diagnostic with its source location (1-based)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Line number, if the compiler reported one
+    pub line: Option<usize>,
+    /// Column number, if the compiler reported one
+    pub column: Option<usize>,
+    /// Diagnostic text as reported by the compiler
+    pub message: String,
+    pub severity: DiagnosticSeverity,
+}
+
+impl Diagnostic {
+    /// Render as `file:line:column: severity: message` for editors and CI
+    pub fn to_annotation(&self, file: &str) -> String {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => format!(
+                "{}:{}:{}: {}: {}",
+                file, line, column, self.severity, self.message
+            ),
+            (Some(line), None) => format!("{}:{}: {}: {}", file, line, self.severity, self.message),
+            _ => format!("{}: {}: {}", file, self.severity, self.message),
+        }
+    }
+}
+
+/// Split compiler output into diagnostics, extracting line/column locations
+///
+/// Recognises `line N, column M` (also `col`) and `N:M` location forms.
+/// Each non-empty line of output becomes one diagnostic.
+pub fn parse_diagnostics(output: &str) -> Vec<Diagnostic> {
+    let diagnostics: Vec<Diagnostic> = output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            let (line, column) = find_location(l);
+            let severity = if l.to_lowercase().starts_with("warning") {
+                DiagnosticSeverity::Warning
+            } else {
+                DiagnosticSeverity::Error
+            };
+            Diagnostic {
+                line,
+                column,
+                message: l.to_string(),
+                severity,
+            }
+        })
+        .collect();
+
+    if diagnostics.is_empty() {
+        return vec![Diagnostic {
+            line: None,
+            column: None,
+            message: output.to_string(),
+            severity: DiagnosticSeverity::Error,
+        }];
+    }
+
+    diagnostics
+}
+
+/// Join diagnostics back into the single-line message form
+fn format_diagnostics(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Find a `line N, column M` or `N:M` location in a message
+fn find_location(text: &str) -> (Option<usize>, Option<usize>) {
+    let lower = text.to_lowercase();
+
+    for (pos, keyword) in lower.match_indices("line") {
+        let rest = lower.get(pos + keyword.len()..).unwrap_or_default();
+        if let Some((line, after)) = leading_number(rest.trim_start()) {
+            let after = after.trim_start_matches([',', ' ', ':']);
+            let column = ["column", "col"]
+                .iter()
+                .find_map(|kw| after.strip_prefix(kw))
+                .and_then(|c| leading_number(c.trim_start()))
+                .map(|(c, _)| c);
+            return (Some(line), column);
+        }
+    }
+
+    // `N:M` form, e.g. `policy.esp:12:5: unexpected token`
+    let parts: Vec<&str> = lower.split(':').collect();
+    for pair in parts.windows(2) {
+        if let [before, after] = pair {
+            let line = trailing_number(before);
+            let column = leading_number(after).map(|(c, _)| c);
+            if let (Some(line), Some(column)) = (line, column) {
+                return (Some(line), Some(column));
+            }
+        }
+    }
+
+    (None, None)
+}
+
+/// Parse a leading decimal number, returning it and the remaining text
+fn leading_number(text: &str) -> Option<(usize, &str)> {
+    let rest = text.trim_start_matches(|c: char| c.is_ascii_digit());
+    let digits = text.strip_suffix(rest)?;
+    Some((digits.parse().ok()?, rest))
+}
+
+/// Parse a trailing decimal number
+fn trailing_number(text: &str) -> Option<usize> {
+    let head = text.trim_end_matches(|c: char| c.is_ascii_digit());
+    text.strip_prefix(head)?.parse().ok()
+}
+
 // ============================================================================
 // Public API Functions
 // ============================================================================
@@ -171,7 +397,7 @@ pub fn scan_file<P: AsRef<Path>>(
 
     // Phase 1: Compile
     let pipeline_result = pipeline::process_file(&path_str)
-        .map_err(|e| ScanError::CompilationFailed(e.to_string()))?;
+        .map_err(|e| ScanError::compilation_failed(&e.to_string()))?;
 
     // Phase 2-4: Execute using the AST
     scan_ast(&pipeline_result.ast, registry)
@@ -198,12 +424,91 @@ pub fn scan_file_manifest<P: AsRef<Path>>(
 
     // Phase 1: Compile
     let pipeline_result = pipeline::process_file(&path_str)
-        .map_err(|e| ScanError::CompilationFailed(e.to_string()))?;
+        .map_err(|e| ScanError::compilation_failed(&e.to_string()))?;
 
     // Phase 2-4: Execute using the AST
     scan_ast_manifest(&pipeline_result.ast, registry)
 }
 
+/// Scan ESP source held in memory and return the result.
+///
+/// For hosts that receive policies over the network or embed them, so the
+/// caller does not have to stage a policy file of its own. `name` is not
+/// read; it stands in for the file path in compiler diagnostics.
+///
+/// # Arguments
+/// * `source` - ESP policy source
+/// * `name` - Name reported in diagnostics, e.g. the policy's original path
+/// * `registry` - Strategy registry with scanner implementations
+///
+/// # Returns
+/// * `Ok(ScanResult)` - The scan completed (check `tree_passed` for compliance status)
+/// * `Err(ScanError)` - The scan could not be completed
+///
+/// # Example
+/// ```ignore
+/// let source = String::from_utf8(response.policy)?;
+/// let result = scan_bytes(&source, "baseline.esp", registry)?;
+/// ```
+pub fn scan_bytes(
+    source: &str,
+    name: &str,
+    registry: Arc<CtnStrategyRegistry>,
+) -> Result<ScanResult, ScanError> {
+    let ast = compile_source(source, name)?;
+    scan_ast(&ast, registry)
+}
+
+/// Scan ESP source held in memory and return the raw execution manifest.
+///
+/// The in-memory counterpart of `scan_file_manifest`; see `scan_bytes`.
+///
+/// # Arguments
+/// * `source` - ESP policy source
+/// * `name` - Name reported in diagnostics
+/// * `registry` - Strategy registry with scanner implementations
+///
+/// # Returns
+/// * `Ok(ExecutionManifest)` - The complete execution data
+/// * `Err(ScanError)` - The scan could not be completed
+pub fn scan_bytes_manifest(
+    source: &str,
+    name: &str,
+    registry: Arc<CtnStrategyRegistry>,
+) -> Result<ExecutionManifest, ScanError> {
+    let ast = compile_source(source, name)?;
+    scan_ast_manifest(&ast, registry)
+}
+
+/// Scan an ESP file, reusing its compiled AST from `cache` when unchanged.
+///
+/// For long-running agents that rescan the same policies: compilation is
+/// skipped unless the file's modification time or size changed since it
+/// was cached. Results are identical to `scan_file`.
+///
+/// # Arguments
+/// * `path` - Path to the ESP file
+/// * `registry` - Strategy registry with scanner implementations
+/// * `cache` - Cache owned by the caller and reused across scans
+///
+/// # Example
+/// ```ignore
+/// let cache = ScanCache::new();
+/// loop {
+///     let result = scan_file_cached("policy.esp", registry.clone(), &cache)?;
+///     std::thread::sleep(interval);
+/// }
+/// ```
+#[cfg(feature = "native")]
+pub fn scan_file_cached<P: AsRef<Path>>(
+    path: P,
+    registry: Arc<CtnStrategyRegistry>,
+    cache: &ScanCache,
+) -> Result<ScanResult, ScanError> {
+    let ast = cache.compile(path.as_ref())?;
+    scan_ast(&ast, registry)
+}
+
 /// Scan a pre-compiled ESP AST and return the result.
 ///
 /// Use this when you already have a compiled AST (e.g., from a gRPC service
@@ -280,7 +585,10 @@ pub fn scan_ast_manifest(
 /// Scan an ESP file with logging enabled.
 ///
 /// Same as `scan_file` but logs progress using the global logging system.
-/// Call `logging::init_global_logging()` before using this.
+/// Call `logging::init_global_logging()` before using this. Every line names
+/// the file of the current thread's `FileContext`, entered here for `path`
+/// if the thread is in none, so lines stay attributable when files are
+/// scanned in parallel (see `log_context`).
 ///
 /// # Arguments
 /// * `path` - Path to the ESP file
@@ -294,6 +602,9 @@ pub fn scan_file_with_logging<P: AsRef<Path>>(
     registry: Arc<CtnStrategyRegistry>,
 ) -> Result<ScanResult, ScanError> {
     let path_str = path.as_ref().display().to_string();
+    let _context = crate::log_context::current_file()
+        .is_none()
+        .then(|| FileContext::enter(path.as_ref(), 1));
 
     log_info!("Scanning ESP file", "path" => &path_str);
 
@@ -305,7 +616,7 @@ pub fn scan_file_with_logging<P: AsRef<Path>>(
             "ESP compilation failed",
             "error" => e.to_string()
         );
-        ScanError::CompilationFailed(e.to_string())
+        ScanError::compilation_failed(&e.to_string())
     })?;
 
     log_success!(
@@ -389,6 +700,122 @@ pub fn scan_file_with_logging<P: AsRef<Path>>(
     Ok(result)
 }
 
+/// Scan result with the outcome of each criteria group
+pub struct GroupedScanResult {
+    pub result: ScanResult,
+    /// Top-level `CRI` blocks, in policy order
+    pub groups: Vec<GroupResult>,
+}
+
+/// Scan an ESP file and report the outcome of every criteria group.
+///
+/// `tree_passed` already reflects `CRI AND`/`CRI OR` logic; this also shows
+/// how each group was decided, e.g. which member satisfied an any-of group.
+/// Every executor in the registry must be wrapped in a `RecordingExecutor`
+/// sharing `recorder`.
+///
+/// # Arguments
+/// * `path` - Path to the ESP file
+/// * `registry` - Strategy registry with recording executors
+/// * `recorder` - Recorder shared with the recording executors
+///
+/// # Returns
+/// * `Ok(GroupedScanResult)` - The scan result and group results
+/// * `Err(ScanError)` - The scan could not be completed or group results
+///   could not be matched to the recorded outcomes
+pub fn scan_file_with_groups<P: AsRef<Path>>(
+    path: P,
+    registry: Arc<CtnStrategyRegistry>,
+    recorder: &OutcomeRecorder,
+) -> Result<GroupedScanResult, ScanError> {
+    let source = std::fs::read_to_string(path.as_ref())?;
+
+    // Discard outcomes left over from an earlier failed run
+    recorder.take();
+    let result = scan_file(path.as_ref(), registry)?;
+    let groups = criteria_group_results(&source, &recorder.take())?;
+
+    Ok(GroupedScanResult { result, groups })
+}
+
+/// Scan an ESP file and render its findings with `formatter`.
+///
+/// Every executor in the registry must be wrapped in a `RecordingExecutor`
+/// sharing `recorder`, so the formatter sees each criterion's failed fields.
+///
+/// # Arguments
+/// * `path` - Path to the ESP file
+/// * `registry` - Strategy registry with recording executors
+/// * `recorder` - Recorder shared with the recording executors
+/// * `formatter` - Renders finding titles and descriptions
+///
+/// # Example
+/// ```ignore
+/// struct Remediation;
+/// impl FindingFormatter for Remediation {
+///     fn description(&self, finding: &FindingContext<'_>) -> String {
+///         finding
+///             .failures
+///             .iter()
+///             .map(|f| format!("Set {} on {} to {}", f.field_name, f.object_id, f.expected))
+///             .collect::<Vec<_>>()
+///             .join("\n")
+///     }
+/// }
+/// let result = scan_file_with_formatter("policy.esp", registry, &recorder, &Remediation)?;
+/// ```
+pub fn scan_file_with_formatter<P: AsRef<Path>>(
+    path: P,
+    registry: Arc<CtnStrategyRegistry>,
+    recorder: &OutcomeRecorder,
+    formatter: &dyn FindingFormatter,
+) -> Result<ScanResult, ScanError> {
+    // Discard outcomes left over from an earlier failed run
+    recorder.take();
+    let mut result = scan_file(path, registry)?;
+    format_findings(&mut result, &recorder.take(), formatter);
+    Ok(result)
+}
+
+/// Rebuild criteria group results from ESP source and recorded outcomes.
+///
+/// For embedders that drive the scan themselves: `outcomes` must come from
+/// one scan of `source` with a `RecordingExecutor`-wrapped registry.
+pub fn criteria_group_results(
+    source: &str,
+    outcomes: &[RecordedOutcome],
+) -> Result<Vec<GroupResult>, ScanError> {
+    crate::criteria::parse_criteria_groups(source)
+        .and_then(|groups| crate::criteria::evaluate_groups(&groups, outcomes))
+        .map_err(ScanError::GroupEvaluation)
+}
+
+/// Describe what a scan of a compiled AST would collect, without collecting.
+///
+/// The registry must be built with `PlanningCollector`-wrapped collectors
+/// sharing `recorder`. Variables and criteria are resolved as in a normal
+/// scan, so the plans show the exact paths and commands each object would
+/// use. No files are read and no commands are executed by the collectors.
+///
+/// # Arguments
+/// * `ast` - The compiled ESP AST
+/// * `registry` - Strategy registry with planning collectors
+/// * `recorder` - Recorder shared with the planning collectors
+///
+/// # Returns
+/// * `Ok(Vec<PlannedCollection>)` - One plan per collected object
+/// * `Err(ScanError)` - The policy could not be resolved
+pub fn explain(
+    ast: &EspFile,
+    registry: Arc<CtnStrategyRegistry>,
+    recorder: &PlanRecorder,
+) -> Result<Vec<PlannedCollection>, ScanError> {
+    // Discard plans left over from an earlier failed run
+    recorder.take();
+    scan_ast_manifest(ast, registry)?;
+    Ok(recorder.take())
+}
+
 /// Extract metadata from a compiled AST.
 ///
 /// Useful for getting policy information without running a full scan.
@@ -423,10 +850,209 @@ pub fn extract_metadata(ast: &EspFile) -> MetaDataBlock {
 pub fn compile_file<P: AsRef<Path>>(path: P) -> Result<EspFile, ScanError> {
     let path_str = path.as_ref().display().to_string();
     let pipeline_result = pipeline::process_file(&path_str)
-        .map_err(|e| ScanError::CompilationFailed(e.to_string()))?;
+        .map_err(|e| ScanError::compilation_failed(&e.to_string()))?;
     Ok(pipeline_result.ast)
 }
 
+/// Compile ESP source held in memory to AST.
+///
+/// The compiler only reads files, so the source is compiled from a private
+/// copy in the system temp directory, which is removed afterwards. Compiler
+/// messages name `name` instead of that copy.
+///
+/// # Arguments
+/// * `source` - ESP policy source
+/// * `name` - Name reported in diagnostics
+///
+/// # Returns
+/// * `Ok(EspFile)` - The compiled AST
+/// * `Err(ScanError)` - The temp directory is not writable, or compilation failed
+pub fn compile_source(source: &str, name: &str) -> Result<EspFile, ScanError> {
+    static SOURCE_COPIES: AtomicUsize = AtomicUsize::new(0);
+    let copy = std::env::temp_dir().join(format!(
+        "esp_source_{}_{}.esp",
+        std::process::id(),
+        SOURCE_COPIES.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::write(&copy, source)?;
+
+    let copy_str = copy.display().to_string();
+    let compiled = pipeline::process_file(&copy_str);
+    let _ = std::fs::remove_file(&copy);
+
+    compiled
+        .map(|result| result.ast)
+        .map_err(|e| ScanError::compilation_failed(&e.to_string().replace(copy_str.as_str(), name)))
+}
+
+/// Compile an ESP file with scan-time values bound to its variables.
+///
+/// Each entry of `vars` replaces the value of the policy's `VAR` of that
+/// name, or fills a `VAR` declared without one (see `policy_vars`). The
+/// bound source is compiled with `compile_source`; compiler diagnostics
+/// keep the policy's path and line numbers.
+///
+/// # Arguments
+/// * `path` - Path to the ESP file
+/// * `vars` - Variable names and values; empty compiles the file as is
+///
+/// # Returns
+/// * `Ok(EspFile)` - The compiled AST
+/// * `Err(ScanError)` - A name is not declared in the policy, a value does
+///   not fit its variable's type, or compilation failed
+pub fn compile_file_with_vars<P: AsRef<Path>>(
+    path: P,
+    vars: &HashMap<String, String>,
+) -> Result<EspFile, ScanError> {
+    if vars.is_empty() {
+        return compile_file(path);
+    }
+
+    let source = std::fs::read_to_string(path.as_ref())?;
+    let bound = bind_vars(&source, vars).map_err(ScanError::VarBinding)?;
+    compile_source(&bound, &path.as_ref().display().to_string())
+}
+
+/// Scan an ESP file with scan-time values bound to its variables.
+///
+/// Lets one policy be reused across environments, e.g. with the expected
+/// NTP server or approved admin group supplied per host. Supplying a name
+/// the policy does not declare is an error rather than being ignored.
+///
+/// # Arguments
+/// * `path` - Path to the ESP file
+/// * `vars` - Variable names and values
+/// * `registry` - Strategy registry with scanner implementations
+///
+/// # Example
+/// ```ignore
+/// let vars = HashMap::from([("config_dir".to_string(), "/opt/app/etc".to_string())]);
+/// let result = scan_file_with_vars("policy.esp", vars, registry)?;
+/// ```
+pub fn scan_file_with_vars<P: AsRef<Path>>(
+    path: P,
+    vars: HashMap<String, String>,
+    registry: Arc<CtnStrategyRegistry>,
+) -> Result<ScanResult, ScanError> {
+    let ast = compile_file_with_vars(path, &vars)?;
+    scan_ast(&ast, registry)
+}
+
+/// Check an ESP file against the registry's contracts without scanning.
+///
+/// The file is compiled first, so syntax errors are reported as for a
+/// scan. The criteria are then checked by `validate_policy`: unknown state
+/// fields, operations a field does not allow, and objects missing required
+/// fields. Nothing is collected or executed.
+///
+/// # Arguments
+/// * `path` - Path to the ESP file
+/// * `registry` - Strategy registry whose contracts the policy must satisfy
+///
+/// # Returns
+/// * `Ok(Vec<PolicyWarning>)` - Problems found, empty if none
+/// * `Err(ScanError)` - The file could not be read or compiled
+pub fn check_file<P: AsRef<Path>>(
+    path: P,
+    registry: &CtnStrategyRegistry,
+) -> Result<Vec<PolicyWarning>, ScanError> {
+    let source = std::fs::read_to_string(path.as_ref())?;
+    compile_file(path.as_ref())?;
+    Ok(validate_policy(&source, registry))
+}
+
+/// Refuse a policy that uses a CTN type with no registered strategy.
+///
+/// The engine fails the scan of such a policy as an execution error. An
+/// agent that registers only some strategies runs this pass first, so a
+/// policy using a type it left out is refused as
+/// `ScanError::CtnTypeDenied`, naming the type and its line, before
+/// anything is collected. A policy that cannot be read or compiled passes,
+/// leaving the scan to report its own error.
+///
+/// # Arguments
+/// * `path` - Path to the ESP file
+/// * `vars` - Scan-time variables, bound as for the scan
+/// * `registry` - Strategy registry the policy will be scanned with
+///
+/// # Returns
+/// * `Ok(())` - Every CTN type has a strategy, or the policy does not compile
+/// * `Err(ScanError::CtnTypeDenied)` - The first CTN type without one
+pub fn check_ctn_types<P: AsRef<Path>>(
+    path: P,
+    vars: &HashMap<String, String>,
+    registry: &CtnStrategyRegistry,
+) -> Result<(), ScanError> {
+    let Ok(source) = std::fs::read_to_string(path.as_ref()) else {
+        return Ok(());
+    };
+    let unregistered = validate_policy(&source, registry)
+        .into_iter()
+        .find(|warning| warning.kind == PolicyWarningKind::UnknownCtnType);
+
+    match unregistered {
+        Some(warning) if compile_file_with_vars(path, vars).is_ok() => {
+            Err(ScanError::CtnTypeDenied {
+                ctn_type: warning.ctn_type,
+                line: warning.line,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Show which record check paths of a `json_record` criterion resolve in a file.
+///
+/// A debugging aid for policy authors whose checks fail because a field
+/// path names nothing. The file is read and parsed as `json_record`
+/// collection reads it, and the field path of each record check is
+/// resolved without evaluating the check (see `dry_validate_paths`). Only
+/// the file is read; nothing is collected or executed.
+///
+/// # Arguments
+/// * `path` - JSON file the criterion's object points at
+/// * `criterion` - The resolved criterion, as passed to its executor
+///
+/// # Returns
+/// * `Ok(Vec<PathResolution>)` - One per record check of each state, in order
+/// * `Err(ScanError)` - The file could not be read or is not JSON
+#[cfg(feature = "native")]
+pub fn explain_json_record<P: AsRef<Path>>(
+    path: P,
+    criterion: &ExecutableCriterion,
+) -> Result<Vec<PathResolution>, ScanError> {
+    use execution_engine::types::common::RecordData;
+
+    let path = path.as_ref().display().to_string();
+    let content = crate::commands::read_file_content(&path)
+        .map_err(|e| ScanError::RecordFile(e.to_string()))?;
+    let json = serde_json::from_str(&content)
+        .map_err(|e| ScanError::RecordFile(format!("Failed to parse JSON in {}: {}", path, e)))?;
+    let record = RecordData::from_json_value(json);
+
+    Ok(criterion
+        .states
+        .iter()
+        .flat_map(|state| dry_validate_paths(&record, &state.record_checks))
+        .collect())
+}
+
+/// Load a compiled ESP AST from its JSON serialization.
+///
+/// The string counterpart of `compile_file` for hosts without a file
+/// system, such as a browser policy editor built without the `native`
+/// feature: the AST is compiled elsewhere and sent as JSON.
+///
+/// # Arguments
+/// * `json` - An `EspFile` serialized as JSON
+///
+/// # Returns
+/// * `Ok(EspFile)` - The AST, ready for `extract_metadata` or `scan_ast`
+/// * `Err(ScanError)` - The JSON is not a valid AST
+pub fn load_ast_json(json: &str) -> Result<EspFile, ScanError> {
+    serde_json::from_str(json).map_err(|e| ScanError::InvalidAst(e.to_string()))
+}
+
 // ============================================================================
 // Helper Functions for Result Handling
 // ============================================================================
@@ -437,6 +1063,98 @@ pub fn is_compliant(result: &ScanResult) -> bool {
     result.tree_passed
 }
 
+/// Exit code for a scan of several policies.
+///
+/// `errors` counts policy files that produced no result. With the default
+/// policy this is 0 (all passed or no policies), 1 (a policy failed),
+/// 3 (no file could be scanned) or 4 (some files could not be scanned);
+/// see `ExitCodePolicy`.
+pub fn exit_code(results: &[ScanResult], errors: usize, policy: &ExitCodePolicy) -> i32 {
+    policy.exit_code(&ScanCounts::from_results(results, errors))
+}
+
+/// Criteria counts of one policy, with criteria marked not applicable
+/// counted on their own.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CriteriaCounts {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub error: usize,
+    /// Criteria an executor marked with `executors::not_applicable`
+    pub not_applicable: usize,
+}
+
+impl CriteriaCounts {
+    /// Count `result`'s criteria, `marked` of which were not applicable.
+    ///
+    /// The engine counts a marked criterion as passed, so `marked` criteria
+    /// move from `passed` to `not_applicable`. Criteria the engine counted
+    /// but never evaluated are left out of `total`. `marked` is the number
+    /// of the policy's `CriterionTiming`s with `not_applicable` set.
+    pub fn new(result: &ScanResult, marked: usize) -> Self {
+        let counts = &result.criteria_counts;
+        let not_applicable = marked.min(counts.passed);
+        let passed = counts.passed - not_applicable;
+        Self {
+            total: passed + counts.failed + counts.error + not_applicable,
+            passed,
+            failed: counts.failed,
+            error: counts.error,
+            not_applicable,
+        }
+    }
+
+    /// Criteria that were evaluated
+    #[inline]
+    pub fn applicable(&self) -> usize {
+        self.total - self.not_applicable
+    }
+
+    /// True when every criterion was marked not applicable
+    #[inline]
+    pub fn none_applicable(&self) -> bool {
+        self.total > 0 && self.applicable() == 0
+    }
+
+    /// Pass rate over applicable criteria as a percentage (0.0 - 100.0).
+    pub fn pass_rate(&self) -> f64 {
+        if self.applicable() == 0 {
+            0.0
+        } else {
+            (self.passed as f64 / self.applicable() as f64) * 100.0
+        }
+    }
+}
+
+/// Scan result with criteria marked not applicable counted separately
+///
+/// Dereferences to the engine's result, except that `criteria_counts` is
+/// the `CriteriaCounts` of the scan.
+pub struct CountedScanResult {
+    pub result: ScanResult,
+    pub criteria_counts: CriteriaCounts,
+}
+
+impl CountedScanResult {
+    /// Count `result`'s criteria, `marked` of which were not applicable
+    pub fn new(result: ScanResult, marked: usize) -> Self {
+        let criteria_counts = CriteriaCounts::new(&result, marked);
+        Self {
+            result,
+            criteria_counts,
+        }
+    }
+}
+
+impl Deref for CountedScanResult {
+    type Target = ScanResult;
+
+    fn deref(&self) -> &ScanResult {
+        &self.result
+    }
+}
+
 /// Get the pass rate as a percentage (0.0 - 100.0).
 #[inline]
 pub fn pass_rate(result: &ScanResult) -> f64 {
@@ -502,3 +1220,302 @@ pub fn format_report(result: &ScanResult) -> String {
 
     report
 }
+
+/// Format a grouped scan result as a detailed report, including the
+/// outcome of each criteria group.
+pub fn format_grouped_report(grouped: &GroupedScanResult) -> String {
+    let mut report = format_report(&grouped.result);
+    if !grouped.groups.is_empty() {
+        report.push_str("\n=== Criteria Groups ===\n");
+        report.push_str(&format_group_outline(&grouped.groups));
+    }
+    report
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line_column_words() {
+        let diagnostics =
+            parse_diagnostics("Syntax error at line 12, column 5: expected STATE_END");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(12));
+        assert_eq!(diagnostics[0].column, Some(5));
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+    }
+
+    #[test]
+    fn test_parse_colon_location() {
+        let diagnostics = parse_diagnostics("policy.esp:3:14: unexpected token 'OBJEC'");
+        assert_eq!(diagnostics[0].line, Some(3));
+        assert_eq!(diagnostics[0].column, Some(14));
+    }
+
+    #[test]
+    fn test_parse_multiple_lines() {
+        let diagnostics =
+            parse_diagnostics("warning: unused variable at line 2\nerror at line 9 col 1: bad");
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Warning);
+        assert_eq!(diagnostics[0].line, Some(2));
+        assert_eq!(diagnostics[0].column, None);
+        assert_eq!(diagnostics[1].line, Some(9));
+        assert_eq!(diagnostics[1].column, Some(1));
+    }
+
+    #[test]
+    fn test_no_location() {
+        let diagnostics = parse_diagnostics("File not found: missing.esp");
+        assert_eq!(diagnostics[0].line, None);
+        assert_eq!(diagnostics[0].column, None);
+    }
+
+    #[test]
+    fn test_display_unchanged() {
+        let message = "Syntax error at line 4, column 2: unexpected end of input";
+        let err = ScanError::compilation_failed(message);
+        assert_eq!(err.to_string(), format!("Compilation failed: {}", message));
+    }
+
+    #[test]
+    fn test_annotation_format() {
+        let diagnostic = Diagnostic {
+            line: Some(7),
+            column: Some(3),
+            message: "unexpected token".to_string(),
+            severity: DiagnosticSeverity::Error,
+        };
+        assert_eq!(
+            diagnostic.to_annotation("policy.esp"),
+            "policy.esp:7:3: error: unexpected token"
+        );
+    }
+
+    #[test]
+    fn test_compile_syntax_error_reports_location() {
+        let path = std::env::temp_dir().join(format!("esp_syntax_{}.esp", std::process::id()));
+        std::fs::write(
+            &path,
+            "META\n    version `1.0.0`\nMETA_END\n\nOBJECT broken\n    path `/etc/passwd`\n\nCTN file_metadata\n",
+        )
+        .unwrap();
+
+        let err = compile_file(&path).expect_err("syntax error should fail compilation");
+        let _ = std::fs::remove_file(&path);
+
+        let diagnostics = err.diagnostics();
+        assert!(!diagnostics.is_empty());
+        assert!(
+            diagnostics.iter().any(|d| d.line.is_some()),
+            "no location in {:?}",
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn test_compile_source_names_policy() {
+        let err = compile_source(
+            "META\n    version `1.0.0`\nMETA_END\n\nOBJECT broken\n    path `/etc/passwd`\n\nCTN file_metadata\n",
+            "inline.esp",
+        )
+        .expect_err("syntax error should fail compilation");
+
+        assert!(!err.diagnostics().is_empty());
+        assert!(
+            !err.to_string().contains("esp_source_"),
+            "temporary copy named in {}",
+            err
+        );
+    }
+
+    const VAR_POLICY: &str = "\
+META
+    esp_id `test-vars-001`
+    version `1.0.0`
+    dsl_schema_version `1.0.0`
+    platform `linux`
+    criticality `low`
+    control_mapping `CIS:1.1`
+    title `Application config exists`
+META_END
+
+DEF
+    VAR config_path string `/etc/app/app.conf`
+
+    OBJECT app_config
+        path VAR config_path
+    OBJECT_END
+
+    STATE present
+        exists boolean = true
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF app_config
+        CTN_END
+    CRI_END
+DEF_END
+";
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_bound_var_changes_scanned_path() {
+        use crate::collectors::{FileSystemCollector, PlanningCollector};
+        use crate::contracts::create_file_metadata_contract;
+        use crate::executors::FileMetadataExecutor;
+
+        let path = std::env::temp_dir().join(format!("esp_var_policy_{}.esp", std::process::id()));
+        std::fs::write(&path, VAR_POLICY).unwrap();
+
+        let recorder = PlanRecorder::new();
+        let mut registry = CtnStrategyRegistry::new();
+        registry
+            .register_ctn_strategy(
+                Box::new(PlanningCollector::new(
+                    FileSystemCollector::new(),
+                    recorder.clone(),
+                )),
+                Box::new(FileMetadataExecutor::new(create_file_metadata_contract())),
+            )
+            .unwrap();
+        let registry = Arc::new(registry);
+
+        let vars = HashMap::from([(
+            "config_path".to_string(),
+            "/opt/app/etc/app.conf".to_string(),
+        )]);
+        let ast = compile_file_with_vars(&path, &vars);
+        let unknown = compile_file_with_vars(
+            &path,
+            &HashMap::from([("config_dir".to_string(), "/opt".to_string())]),
+        );
+        let _ = std::fs::remove_file(&path);
+
+        let plans = explain(&ast.unwrap(), registry, &recorder).unwrap();
+        assert_eq!(plans.len(), 1);
+        assert_eq!(plans[0].plan.target, "/opt/app/etc/app.conf");
+
+        let err = unknown.expect_err("undeclared variable should fail");
+        assert!(matches!(
+            err,
+            ScanError::VarBinding(VarBindingError::UnknownVar { .. })
+        ));
+        assert!(err.to_string().contains("'config_dir'"));
+    }
+
+    #[cfg(all(feature = "native", not(windows)))]
+    const REGISTRY_POLICY: &str = "\
+META
+    esp_id `test-registry-001`
+    version `1.0.0`
+    dsl_schema_version `1.0.0`
+    platform `windows`
+    criticality `medium`
+    control_mapping `CIS:18.3.1`
+    title `LM authentication level is set`
+META_END
+
+DEF
+    OBJECT lsa
+        hive `HKLM`
+        key_path `SYSTEM\\CurrentControlSet\\Control\\Lsa`
+        value_name `LmCompatibilityLevel`
+    OBJECT_END
+
+    STATE present
+        exists boolean = true
+    STATE_END
+
+    CRI AND
+        CTN registry_value
+            TEST all all
+            STATE_REF present
+            OBJECT_REF lsa
+        CTN_END
+    CRI_END
+DEF_END
+";
+
+    #[cfg(all(feature = "native", not(windows)))]
+    #[test]
+    fn test_registry_value_is_not_applicable_off_windows() {
+        use crate::collectors::RegistryCollector;
+        use crate::contracts::create_registry_value_contract;
+        use crate::executors::{RegistryExecutor, TimingExecutor};
+
+        let path =
+            std::env::temp_dir().join(format!("esp_registry_policy_{}.esp", std::process::id()));
+        std::fs::write(&path, REGISTRY_POLICY).unwrap();
+
+        let timer = CriterionTimer::new();
+        let mut registry = CtnStrategyRegistry::new();
+        registry
+            .register_ctn_strategy(
+                Box::new(RegistryCollector::new()),
+                Box::new(TimingExecutor::new(
+                    Box::new(RegistryExecutor::new(create_registry_value_contract())),
+                    timer.clone(),
+                )),
+            )
+            .unwrap();
+
+        let result = scan_file(&path, Arc::new(registry));
+        let _ = std::fs::remove_file(&path);
+        let marked = timer.take().iter().filter(|c| c.not_applicable).count();
+        let result = CountedScanResult::new(result.unwrap(), marked);
+
+        assert!(result.tree_passed);
+        assert_eq!(
+            result.criteria_counts,
+            CriteriaCounts {
+                total: 1,
+                passed: 0,
+                failed: 0,
+                error: 0,
+                not_applicable: 1,
+            }
+        );
+        assert!(result.criteria_counts.none_applicable());
+        assert_eq!(result.criteria_counts.pass_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_unregistered_ctn_type_is_denied() {
+        let path =
+            std::env::temp_dir().join(format!("esp_denied_policy_{}.esp", std::process::id()));
+        std::fs::write(&path, VAR_POLICY).unwrap();
+
+        let registry = CtnStrategyRegistry::new();
+        let denied = check_ctn_types(&path, &HashMap::new(), &registry);
+        let unknown_var = check_ctn_types(
+            &path,
+            &HashMap::from([("config_dir".to_string(), "/opt".to_string())]),
+            &registry,
+        );
+        let _ = std::fs::remove_file(&path);
+
+        let err = denied.expect_err("file_metadata has no strategy");
+        assert!(matches!(
+            &err,
+            ScanError::CtnTypeDenied { ctn_type, line: 24 } if ctn_type == "file_metadata"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "CTN type 'file_metadata' is denied by agent configuration (line 24)"
+        );
+
+        // Policies that do not compile are left to the scan
+        assert!(unknown_var.is_ok());
+        assert!(check_ctn_types(&path, &HashMap::new(), &registry).is_ok());
+    }
+}
@@ -0,0 +1,152 @@
+//! Not Applicable Criteria
+//!
+//! An executor marks a criterion that cannot apply to the host (wrong
+//! platform, missing capability) by returning `not_applicable`. The result
+//! passes, so the criterion never fails its policy, and carries a
+//! `not_applicable` detail that `TimingExecutor` records. The agent counts
+//! the marked criteria on their own (see `execution_api::CriteriaCounts`).
+//!
+//! A collector that finds the host cannot have the object (a registry value
+//! off Windows, a package without dpkg) returns `not_applicable_data`
+//! instead of an error. When every object of a criterion was collected so,
+//! its executor returns `not_applicable` with the collector's reason.
+//!
+//! Only marked criteria are not applicable. A criterion the engine counts
+//! but never evaluates is not.
+
+use common::results::Outcome;
+use execution_engine::strategies::{CollectedData, CtnExecutionResult, TestPhase};
+use execution_engine::types::common::ResolvedValue;
+use std::collections::HashMap;
+
+/// Detail key marking a not applicable result
+pub const NOT_APPLICABLE_DETAIL: &str = "not_applicable";
+
+/// Field of collected data holding why the object cannot apply to the host
+pub const NOT_APPLICABLE_FIELD: &str = "not_applicable_reason";
+
+/// Result for a criterion that does not apply to this host
+pub fn not_applicable(
+    ctn_type: &str,
+    reason: &str,
+    collected_data: HashMap<String, CollectedData>,
+) -> CtnExecutionResult {
+    CtnExecutionResult {
+        ctn_type: ctn_type.to_string(),
+        status: Outcome::Pass,
+        test_phase: TestPhase::Complete,
+        existence_result: None,
+        state_results: Vec::new(),
+        item_check_result: None,
+        message: format!("Not applicable: {}", reason),
+        details: serde_json::json!({
+            NOT_APPLICABLE_DETAIL: true,
+            "reason": reason,
+        }),
+        execution_metadata: Default::default(),
+        collected_data,
+    }
+}
+
+/// Whether an executor marked this result not applicable
+pub fn is_not_applicable(result: &CtnExecutionResult) -> bool {
+    result.status == Outcome::Pass
+        && result
+            .details
+            .get(NOT_APPLICABLE_DETAIL)
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+}
+
+/// Collected data for an object that cannot apply to this host
+pub fn not_applicable_data(
+    object_id: &str,
+    ctn_type: &str,
+    collector_id: &str,
+    reason: &str,
+) -> CollectedData {
+    let mut data = CollectedData::new(
+        object_id.to_string(),
+        ctn_type.to_string(),
+        collector_id.to_string(),
+    );
+    data.add_field(
+        NOT_APPLICABLE_FIELD.to_string(),
+        ResolvedValue::String(reason.to_string()),
+    );
+    data
+}
+
+/// Whether a collector marked this object not applicable
+pub fn is_not_applicable_data(data: &CollectedData) -> bool {
+    data.has_field(NOT_APPLICABLE_FIELD)
+}
+
+/// Why a criterion does not apply, if every one of its objects was
+/// collected with `not_applicable_data`
+pub fn not_applicable_reason(collected_data: &HashMap<String, CollectedData>) -> Option<String> {
+    let mut reason = None;
+    for data in collected_data.values() {
+        match data.get_field(NOT_APPLICABLE_FIELD) {
+            Some(ResolvedValue::String(r)) => reason = Some(r.clone()),
+            _ => return None,
+        }
+    }
+    reason
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_marked_results_are_not_applicable() {
+        let marked = not_applicable("registry_value", "Windows only", HashMap::new());
+        assert!(is_not_applicable(&marked));
+        assert_eq!(marked.status, Outcome::Pass);
+        assert_eq!(marked.message, "Not applicable: Windows only");
+
+        let mut failed = not_applicable("registry_value", "Windows only", HashMap::new());
+        failed.status = Outcome::Fail;
+        assert!(!is_not_applicable(&failed));
+
+        let mut passed = not_applicable("registry_value", "Windows only", HashMap::new());
+        passed.details = serde_json::json!({ "objects_passing": 0 });
+        assert!(!is_not_applicable(&passed));
+    }
+
+    #[test]
+    fn test_reason_needs_every_object_marked() {
+        let marked = |object_id: &str| {
+            let data = not_applicable_data(
+                object_id,
+                "dpkg_package",
+                "dpkg_collector",
+                "dpkg is not installed",
+            );
+            (object_id.to_string(), data)
+        };
+        let mut collected: HashMap<String, CollectedData> =
+            HashMap::from([marked("telnetd"), marked("rsh-server")]);
+        assert!(is_not_applicable_data(&collected["telnetd"]));
+        assert_eq!(
+            not_applicable_reason(&collected).as_deref(),
+            Some("dpkg is not installed")
+        );
+
+        let mut installed = CollectedData::new(
+            "nis".to_string(),
+            "dpkg_package".to_string(),
+            "dpkg_collector".to_string(),
+        );
+        installed.add_field("installed".to_string(), ResolvedValue::Boolean(false));
+        assert!(!is_not_applicable_data(&installed));
+        collected.insert("nis".to_string(), installed);
+        assert_eq!(not_applicable_reason(&collected), None);
+
+        assert_eq!(not_applicable_reason(&HashMap::new()), None);
+    }
+}
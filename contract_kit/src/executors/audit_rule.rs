@@ -0,0 +1,327 @@
+//! Audit Rule Executor
+//!
+//! Validates audit rule presence, audit enablement, and rule set membership.
+
+use common::results::Outcome;
+use execution_engine::execution::{
+    evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
+};
+use execution_engine::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+    FieldValidationResult, StateValidationResult, TestPhase,
+};
+use execution_engine::types::common::{Operation, ResolvedValue};
+use execution_engine::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+use crate::collectors::error_reporting::{
+    count_failing_objects, count_found_objects, not_found_details, CollectionErrorRecord,
+};
+use crate::executors::short_circuit::{state_outcome, EvidenceLevel, ShortCircuit};
+
+use crate::commands::audit_rule::rule_matches_pattern;
+
+/// Executor for audit_rule validation
+pub struct AuditRuleExecutor {
+    contract: CtnContract,
+    evidence_level: EvidenceLevel,
+}
+
+impl AuditRuleExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self {
+            contract,
+            evidence_level: EvidenceLevel::default(),
+        }
+    }
+
+    /// Set how much per-object evidence results must carry
+    pub fn with_evidence_level(mut self, level: EvidenceLevel) -> Self {
+        self.evidence_level = level;
+        self
+    }
+
+    /// Compare values for present/enabled/rule_count and rule set membership
+    fn compare_values(
+        &self,
+        expected: &ResolvedValue,
+        actual: &ResolvedValue,
+        operation: Operation,
+    ) -> bool {
+        match (expected, actual, operation) {
+            // Boolean comparisons (present, enabled)
+            (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::Equals) => {
+                exp == act
+            }
+            (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::NotEqual) => {
+                exp != act
+            }
+            // Integer comparisons (rule_count)
+            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::Equals) => {
+                exp == act
+            }
+            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::NotEqual) => {
+                exp != act
+            }
+            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::GreaterThan) => {
+                act > exp
+            }
+            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::LessThan) => {
+                act < exp
+            }
+            (
+                ResolvedValue::Integer(exp),
+                ResolvedValue::Integer(act),
+                Operation::GreaterThanOrEqual,
+            ) => act >= exp,
+            (
+                ResolvedValue::Integer(exp),
+                ResolvedValue::Integer(act),
+                Operation::LessThanOrEqual,
+            ) => act <= exp,
+            // Set membership (rules)
+            (
+                ResolvedValue::String(pattern),
+                ResolvedValue::Collection(rules),
+                Operation::Contains,
+            ) => self.rules_contain(rules, pattern),
+            (
+                ResolvedValue::String(pattern),
+                ResolvedValue::Collection(rules),
+                Operation::NotContains,
+            ) => !self.rules_contain(rules, pattern),
+            _ => false,
+        }
+    }
+
+    /// Check whether any rule in the collection matches the pattern
+    fn rules_contain(&self, rules: &[ResolvedValue], pattern: &str) -> bool {
+        rules.iter().any(|rule| match rule {
+            ResolvedValue::String(r) => rule_matches_pattern(r, pattern),
+            _ => false,
+        })
+    }
+
+    /// Format a value for display in messages
+    fn format_value(&self, value: &ResolvedValue) -> String {
+        match value {
+            ResolvedValue::String(s) => format!("'{}'", s),
+            ResolvedValue::Collection(items) => format!("<{} rules>", items.len()),
+            other => format!("{:?}", other),
+        }
+    }
+}
+
+impl CtnExecutor for AuditRuleExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let test_spec = &criterion.test;
+
+        // Phase 1: Existence check
+        let objects_expected = criterion.expected_object_count();
+        let collection_errors = CollectionErrorRecord::from_collected(&collected_data);
+        let objects_found = count_found_objects(&collected_data, &collection_errors);
+
+        let existence_passed =
+            evaluate_existence_check(test_spec.existence_check, objects_found, objects_expected);
+
+        if !existence_passed {
+            return Ok(CtnExecutionResult::fail(
+                criterion.criterion_type.clone(),
+                format!(
+                    "Existence check failed: expected {} objects, found {}{}",
+                    objects_expected,
+                    objects_found,
+                    not_found_details(&collection_errors)
+                ),
+            )
+            .with_collected_data(collected_data));
+        }
+
+        // Phase 2: State validation
+        let mut state_results = Vec::new();
+        let mut failure_messages = Vec::new();
+        let short_circuit = ShortCircuit::new(self.evidence_level, |passing, total| {
+            evaluate_item_check(test_spec.item_check, passing, total)
+        });
+        let mut short_circuited = false;
+
+        for (object_id, data) in &collected_data {
+            // Objects that could not be collected are reported, not evaluated
+            if let Some(error) = CollectionErrorRecord::from_collected_data(data) {
+                // Missing objects were already handled by the existence check
+                if error.counts_as_found() {
+                    failure_messages.push(error.message());
+                    state_results.push(StateValidationResult {
+                        object_id: object_id.clone(),
+                        state_results: vec![error.field_result()],
+                        combined_result: false,
+                        state_operator: test_spec.state_operator,
+                        message: error.message(),
+                    });
+                }
+                continue;
+            }
+
+            let mut all_field_results = Vec::new();
+
+            for state in &criterion.states {
+                for field in &state.fields {
+                    let data_field_name = self
+                        .contract
+                        .field_mappings
+                        .validation_mappings
+                        .state_to_data
+                        .get(&field.name)
+                        .cloned()
+                        .unwrap_or_else(|| field.name.clone());
+
+                    let actual_value = match data.get_field(&data_field_name) {
+                        Some(v) => v.clone(),
+                        None => {
+                            let msg = format!("Field '{}' not collected", field.name);
+                            all_field_results.push(FieldValidationResult {
+                                field_name: field.name.clone(),
+                                expected_value: field.value.clone(),
+                                actual_value: ResolvedValue::Boolean(false),
+                                operation: field.operation,
+                                passed: false,
+                                message: msg.clone(),
+                            });
+                            failure_messages.push(format!("Audit rule '{}': {}", object_id, msg));
+                            continue;
+                        }
+                    };
+
+                    let passed = self.compare_values(&field.value, &actual_value, field.operation);
+
+                    let msg = if passed {
+                        format!("Field '{}' check passed", field.name)
+                    } else {
+                        format!(
+                            "Field '{}' check failed: expected {:?} {}, got {}",
+                            field.name,
+                            field.operation,
+                            self.format_value(&field.value),
+                            self.format_value(&actual_value)
+                        )
+                    };
+
+                    if !passed {
+                        failure_messages.push(format!("Audit rule '{}': {}", object_id, msg));
+                    }
+
+                    all_field_results.push(FieldValidationResult {
+                        field_name: field.name.clone(),
+                        expected_value: field.value.clone(),
+                        actual_value,
+                        operation: field.operation,
+                        passed,
+                        message: msg,
+                    });
+                }
+            }
+
+            let state_bools: Vec<bool> = all_field_results.iter().map(|r| r.passed).collect();
+            let combined = evaluate_state_operator(test_spec.state_operator, &state_bools);
+
+            state_results.push(StateValidationResult {
+                object_id: object_id.clone(),
+                state_results: all_field_results,
+                combined_result: combined,
+                state_operator: test_spec.state_operator,
+                message: format!(
+                    "Audit rule '{}': {}",
+                    object_id,
+                    if combined { "passed" } else { "failed" }
+                ),
+            });
+
+            if short_circuit.should_stop(combined, false) {
+                short_circuited = true;
+                break;
+            }
+        }
+
+        // Phase 3: Item check
+        let objects_passing = state_results.iter().filter(|r| r.combined_result).count();
+        let item_passed =
+            evaluate_item_check(test_spec.item_check, objects_passing, state_results.len());
+
+        let (objects_failing, failing_from_errors) = count_failing_objects(&state_results);
+        let final_status = state_outcome(
+            existence_passed && item_passed,
+            objects_failing,
+            failing_from_errors,
+        );
+
+        let message = if final_status == Outcome::Pass {
+            format!(
+                "Audit rule validation passed: {} of {} objects compliant",
+                objects_passing,
+                state_results.len()
+            )
+        } else if final_status == Outcome::Error {
+            format!(
+                "Audit rule validation could not be completed:\n  - {}",
+                failure_messages.join("\n  - ")
+            )
+        } else {
+            format!(
+                "Audit rule validation failed:\n  - {}",
+                failure_messages.join("\n  - ")
+            )
+        };
+
+        Ok(CtnExecutionResult {
+            ctn_type: criterion.criterion_type.clone(),
+            status: final_status,
+            test_phase: TestPhase::Complete,
+            existence_result: None,
+            state_results,
+            item_check_result: None,
+            message,
+            details: serde_json::json!({
+                "failures": failure_messages,
+                "collection_errors": collection_errors
+                    .iter()
+                    .map(CollectionErrorRecord::to_json)
+                    .collect::<Vec<_>>(),
+                "objects_passing": objects_passing,
+                "short_circuited": short_circuited,
+            }),
+            execution_metadata: Default::default(),
+            collected_data,
+        })
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "audit_rule"
+    }
+
+    fn validate_collected_data(
+        &self,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        for data in collected_data.values() {
+            if CollectionErrorRecord::from_collected_data(data).is_some() {
+                continue;
+            }
+            if !data.has_field("present") {
+                return Err(CtnExecutionError::MissingDataField {
+                    field: "present".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+//! Human-Readable Byte Sizes
+//!
+//! Byte-count state fields (`size`) accept a string such as `100MB` in
+//! place of an integer, so a policy can say `size string < \`100MB\``
+//! instead of `size int < 100000000`.
+//!
+//! | Suffix | Multiplier |
+//! |--------|------------|
+//! | none, `B` | 1 |
+//! | `KB`, `MB`, `GB`, `TB` | Decimal: 1000, 1000², 1000³, 1000⁴ |
+//! | `KiB`, `MiB`, `GiB`, `TiB` | Binary: 1024, 1024², 1024³, 1024⁴ |
+//!
+//! Suffixes are case-sensitive apart from `kB`, and the number must be a
+//! whole number; a space between number and suffix is allowed. Bare
+//! prefixes (`M`, `G`) and bit units (`Mb`) are rejected rather than
+//! guessed, since they are read as either unit in the wild.
+
+/// Decimal and binary suffixes with their multipliers
+const UNITS: &[(&str, i64)] = &[
+    ("B", 1),
+    ("KB", 1_000),
+    ("kB", 1_000),
+    ("MB", 1_000_000),
+    ("GB", 1_000_000_000),
+    ("TB", 1_000_000_000_000),
+    ("KiB", 1 << 10),
+    ("MiB", 1 << 20),
+    ("GiB", 1 << 30),
+    ("TiB", 1 << 40),
+];
+
+/// Error parsing a human-readable byte size
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ByteSizeError {
+    /// No whole number before the suffix
+    InvalidNumber(String),
+    /// A suffix that could mean more than one unit (`M`, `Gb`)
+    AmbiguousUnit { unit: String, decimal: String },
+    /// A suffix that is not a size unit
+    UnknownUnit(String),
+    /// The byte count does not fit in a 64-bit integer
+    Overflow(String),
+}
+
+impl std::fmt::Display for ByteSizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidNumber(value) => {
+                write!(f, "'{}' is not a whole number of bytes", value)
+            }
+            Self::AmbiguousUnit { unit, decimal } => write!(
+                f,
+                "ambiguous size unit '{}': use {} (decimal) or {} (binary)",
+                unit,
+                decimal,
+                decimal.replace('B', "iB")
+            ),
+            Self::UnknownUnit(unit) => write!(
+                f,
+                "unknown size unit '{}' (expected one of: {})",
+                unit,
+                UNITS
+                    .iter()
+                    .map(|(suffix, _)| *suffix)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::Overflow(value) => write!(f, "size '{}' is too large", value),
+        }
+    }
+}
+
+impl std::error::Error for ByteSizeError {}
+
+/// Parse a byte count such as `4096`, `100MB` or `1 GiB`
+pub fn parse_byte_size(value: &str) -> Result<i64, ByteSizeError> {
+    let trimmed = value.trim();
+    let digits = trimmed
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(digits);
+    let unit = unit.trim_start();
+
+    if number.is_empty() || unit.starts_with(['.', ',']) {
+        return Err(ByteSizeError::InvalidNumber(value.to_string()));
+    }
+    let number: i64 = number
+        .parse()
+        .map_err(|_| ByteSizeError::Overflow(value.to_string()))?;
+
+    let multiplier = match UNITS.iter().find(|(suffix, _)| *suffix == unit) {
+        Some((_, multiplier)) => *multiplier,
+        None if unit.is_empty() => 1,
+        None => return Err(unit_error(unit)),
+    };
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| ByteSizeError::Overflow(value.to_string()))
+}
+
+/// Why `unit` is not accepted
+fn unit_error(unit: &str) -> ByteSizeError {
+    // A bare prefix, or a prefix with a lowercase (bit) b
+    let prefix = unit.strip_suffix('b').unwrap_or(unit);
+    let decimal = match prefix {
+        "k" | "K" => Some("KB"),
+        "m" | "M" => Some("MB"),
+        "g" | "G" => Some("GB"),
+        "t" | "T" => Some("TB"),
+        _ => None,
+    };
+    match decimal {
+        Some(decimal) => ByteSizeError::AmbiguousUnit {
+            unit: unit.to_string(),
+            decimal: decimal.to_string(),
+        },
+        None => ByteSizeError::UnknownUnit(unit.to_string()),
+    }
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_and_binary_units() {
+        assert_eq!(parse_byte_size("100MB"), Ok(100_000_000));
+        assert_eq!(parse_byte_size("1GiB"), Ok(1_073_741_824));
+        assert_eq!(parse_byte_size("1 GiB"), Ok(1_073_741_824));
+        assert_eq!(parse_byte_size("64kB"), Ok(64_000));
+        assert_eq!(parse_byte_size("512B"), Ok(512));
+        assert_eq!(parse_byte_size("4096"), Ok(4096));
+    }
+
+    #[test]
+    fn test_ambiguous_unit_is_rejected() {
+        let err = parse_byte_size("100M").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ambiguous size unit 'M': use MB (decimal) or MiB (binary)"
+        );
+        assert!(matches!(
+            parse_byte_size("10Gb"),
+            Err(ByteSizeError::AmbiguousUnit { .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_sizes() {
+        assert_eq!(
+            parse_byte_size("100XB"),
+            Err(ByteSizeError::UnknownUnit("XB".to_string()))
+        );
+        assert!(parse_byte_size("100XB")
+            .unwrap_err()
+            .to_string()
+            .starts_with("unknown size unit 'XB' (expected one of: B, KB, kB, MB"));
+        assert!(matches!(
+            parse_byte_size("1.5GB"),
+            Err(ByteSizeError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            parse_byte_size("MB"),
+            Err(ByteSizeError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            parse_byte_size("9000000TiB"),
+            Err(ByteSizeError::Overflow(_))
+        ));
+    }
+}
@@ -3,6 +3,10 @@
 //! Validates STATE fields against resolved variables instead of collected data.
 //! Used for testing RUN operations.
 //!
+//! The values each object reports (see `collectors::computed_values`) are
+//! listed in the result's `details.computed`, keyed by object, with the
+//! object field each came from.
+//!
 //! CURRENT STATUS: STUB - Needs ExecutionContext access to complete
 
 use common::results::Outcome;
@@ -14,8 +18,13 @@ use execution_engine::strategies::{
     FieldValidationResult, StateValidationResult, TestPhase,
 };
 use execution_engine::types::common::ResolvedValue;
-use execution_engine::types::execution_context::ExecutableCriterion;
+use execution_engine::types::execution_context::{ExecutableCriterion, ExecutableObject};
+use serde_json::{json, Value};
 use std::collections::HashMap;
+
+use crate::collectors::computed_values::{computed_fields, object_description};
+use crate::executors::file_metadata::format_value;
+
 pub struct ComputedValuesExecutor {
     contract: CtnContract,
 }
@@ -26,6 +35,44 @@ impl ComputedValuesExecutor {
     }
 }
 
+/// Computed values of each object for `details.computed`: the value, the
+/// object field that carries it and the object's description
+fn computed_details(objects: &[ExecutableObject]) -> Value {
+    let computed = objects
+        .iter()
+        .map(|object| {
+            let values: Vec<Value> = computed_fields(object)
+                .map(|(name, value)| {
+                    json!({
+                        "name": name,
+                        "value": value_json(value),
+                        "source": format!("object {} field {}", object.identifier, name),
+                    })
+                })
+                .collect();
+            let entry = json!({
+                "description": object_description(object),
+                "values": values,
+            });
+            (object.identifier.clone(), entry)
+        })
+        .collect();
+    Value::Object(computed)
+}
+
+/// A computed value as JSON; types without a JSON equivalent are shown as
+/// in result messages
+fn value_json(value: &ResolvedValue) -> Value {
+    match value {
+        ResolvedValue::String(s) => json!(s),
+        ResolvedValue::Integer(i) => json!(i),
+        ResolvedValue::Boolean(b) => json!(b),
+        ResolvedValue::Float(f) => json!(f),
+        ResolvedValue::Collection(items) => items.iter().map(value_json).collect(),
+        other => json!(format_value(other)),
+    }
+}
+
 impl CtnExecutor for ComputedValuesExecutor {
     fn execute_with_contract(
         &self,
@@ -114,10 +161,11 @@ impl CtnExecutor for ComputedValuesExecutor {
             state_results,
             item_check_result: None,
             message,
-            details: serde_json::json!({
+            details: json!({
                 "stub": true,
                 "note": "This executor needs ExecutionContext access to validate variables",
-                "see": "COMPUTED_VALUES_IMPLEMENTATION.md"
+                "see": "COMPUTED_VALUES_IMPLEMENTATION.md",
+                "computed": computed_details(&criterion.objects),
             }),
             execution_metadata: Default::default(),
             collected_data,
@@ -141,3 +189,29 @@ impl CtnExecutor for ComputedValuesExecutor {
         Ok(())
     }
 }
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_json() {
+        assert_eq!(value_json(&ResolvedValue::Integer(90)), json!(90));
+        assert_eq!(
+            value_json(&ResolvedValue::String("90d".to_string())),
+            json!("90d")
+        );
+        assert_eq!(value_json(&ResolvedValue::Boolean(true)), json!(true));
+        assert_eq!(
+            value_json(&ResolvedValue::Collection(vec![
+                ResolvedValue::Integer(1),
+                ResolvedValue::String("a".to_string()),
+            ])),
+            json!([1, "a"])
+        );
+    }
+}
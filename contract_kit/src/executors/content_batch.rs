@@ -0,0 +1,338 @@
+//! Batched Content Checks
+//!
+//! Policies often check several substrings of the same file in separate
+//! criteria (one CTN per control). Evaluated criterion by criterion, each
+//! check is another pass over the file's content. `ContentBatch` groups the
+//! `file_content` checks by object path and evaluates every check planned
+//! for a path in a single pass, the first time any criterion on that path
+//! is executed. Later criteria on the path read their results from the
+//! batch.
+//!
+//! The engine dispatches criteria one at a time, so grouping needs the
+//! criteria up front: call `plan_criterion` for each `file_content`
+//! criterion before dispatch. Checks that were not planned are evaluated
+//! when their criterion runs, together with any other pending checks for
+//! the path.
+//!
+//! Results are reused only for identical content, so a partial read of the
+//! same path (`tail_lines`, `head_bytes`) or a file that changed between
+//! criteria is scanned again.
+
+use execution_engine::execution::comparisons::string;
+use execution_engine::types::common::{Operation, ResolvedValue};
+use execution_engine::types::execution_context::{
+    ExecutableCriterion, ExecutableObject, ExecutableObjectElement,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// One `content` state field: an operation and its expected string
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentCheck {
+    pub operation: Operation,
+    pub expected: String,
+}
+
+impl ContentCheck {
+    pub fn new(operation: Operation, expected: impl Into<String>) -> Self {
+        Self {
+            operation,
+            expected: expected.into(),
+        }
+    }
+
+    /// Evaluate on its own, outside a batched pass
+    fn evaluate(&self, content: &str) -> bool {
+        match string::compare(content, &self.expected, self.operation) {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("String comparison error: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// Content checks of a criterion, in state field order
+///
+/// Only `content` fields with a string value are checks; the executor
+/// reports other values as invalid.
+pub fn content_checks(criterion: &ExecutableCriterion) -> Vec<ContentCheck> {
+    criterion
+        .states
+        .iter()
+        .flat_map(|state| &state.fields)
+        .filter(|field| field.name == "content")
+        .filter_map(|field| match &field.value {
+            ResolvedValue::String(expected) => Some(ContentCheck::new(field.operation, expected)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The `path` of an object, as written in the policy
+pub fn object_path(object: &ExecutableObject) -> Option<&str> {
+    object.elements.iter().find_map(|element| match element {
+        ExecutableObjectElement::Field {
+            name,
+            value: ResolvedValue::String(path),
+            ..
+        } if name == "path" => Some(path.as_str()),
+        _ => None,
+    })
+}
+
+/// Evaluate `checks` on `content` in one pass
+///
+/// `contains` and `not_contains` share a single walk over the content;
+/// `starts` and `ends` only look at the ends of the content. Other
+/// operations are evaluated individually.
+pub fn scan_content(content: &str, checks: &[ContentCheck]) -> Vec<bool> {
+    let mut found: Vec<Option<bool>> = checks
+        .iter()
+        .map(|check| match check.operation {
+            Operation::Contains | Operation::NotContains if !check.expected.is_empty() => None,
+            _ => Some(check.evaluate(content)),
+        })
+        .collect();
+
+    let mut searching: Vec<usize> = (0..checks.len())
+        .filter(|&i| matches!(found.get(i), Some(None)))
+        .collect();
+    let bytes = content.as_bytes();
+    let mut position = 0;
+    while !searching.is_empty() && position < bytes.len() {
+        let rest = bytes.get(position..).unwrap_or_default();
+        searching.retain(|&i| {
+            let Some(check) = checks.get(i) else {
+                return false;
+            };
+            if !rest.starts_with(check.expected.as_bytes()) {
+                return true;
+            }
+            if let Some(slot) = found.get_mut(i) {
+                *slot = Some(true);
+            }
+            false
+        });
+        position += 1;
+    }
+
+    checks
+        .iter()
+        .zip(found)
+        .map(|(check, found)| {
+            let found = found.unwrap_or(false);
+            match check.operation {
+                Operation::NotContains if !check.expected.is_empty() => !found,
+                _ => found,
+            }
+        })
+        .collect()
+}
+
+/// Identity of scanned content, to tell reads of the same path apart
+fn fingerprint(content: &str) -> (usize, u64) {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    (content.len(), hasher.finish())
+}
+
+/// Checks evaluated on one version of a path's content
+struct ScannedContent {
+    fingerprint: (usize, u64),
+    results: Vec<(ContentCheck, bool)>,
+}
+
+#[derive(Default)]
+struct BatchState {
+    planned: HashMap<String, Vec<ContentCheck>>,
+    scanned: HashMap<String, ScannedContent>,
+    scans: usize,
+}
+
+/// Content checks grouped by path, shared by every `file_content` criterion
+/// of a scan
+#[derive(Clone, Default)]
+pub struct ContentBatch {
+    state: Arc<Mutex<BatchState>>,
+}
+
+impl ContentBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Plan `checks` for the content at `path`
+    pub fn plan(&self, path: &str, checks: impl IntoIterator<Item = ContentCheck>) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        let planned = state.planned.entry(path.to_string()).or_default();
+        for check in checks {
+            if !planned.contains(&check) {
+                planned.push(check);
+            }
+        }
+    }
+
+    /// Plan a criterion's content checks for each of its objects' paths
+    pub fn plan_criterion(&self, criterion: &ExecutableCriterion) {
+        let checks = content_checks(criterion);
+        if checks.is_empty() {
+            return;
+        }
+        for path in criterion.objects.iter().filter_map(object_path) {
+            self.plan(path, checks.iter().cloned());
+        }
+    }
+
+    /// Results of `checks` on `content`, read from `path`
+    ///
+    /// Scans the content only if one of `checks` has not been evaluated on
+    /// it yet; the scan also evaluates everything planned for the path.
+    pub fn evaluate(&self, path: &str, content: &str, checks: &[ContentCheck]) -> Vec<bool> {
+        let Ok(mut state) = self.state.lock() else {
+            return scan_content(content, checks);
+        };
+        let state = &mut *state;
+        let fingerprint = fingerprint(content);
+
+        let cached = state
+            .scanned
+            .get(path)
+            .filter(|scanned| scanned.fingerprint == fingerprint);
+        let lookup = |check: &ContentCheck| {
+            cached.and_then(|scanned| {
+                scanned
+                    .results
+                    .iter()
+                    .find(|(evaluated, _)| evaluated == check)
+                    .map(|(_, passed)| *passed)
+            })
+        };
+        if let Some(results) = checks.iter().map(lookup).collect::<Option<Vec<bool>>>() {
+            return results;
+        }
+
+        // Scan everything planned for the path that this content has not
+        // been checked for, along with the requested checks
+        let mut pending: Vec<ContentCheck> = Vec::new();
+        let planned = state.planned.get(path).into_iter().flatten();
+        for check in planned.chain(checks) {
+            if lookup(check).is_none() && !pending.contains(check) {
+                pending.push(check.clone());
+            }
+        }
+        let passed = scan_content(content, &pending);
+        state.scans += 1;
+
+        let scanned = state
+            .scanned
+            .entry(path.to_string())
+            .or_insert_with(|| ScannedContent {
+                fingerprint,
+                results: Vec::new(),
+            });
+        if scanned.fingerprint != fingerprint {
+            scanned.fingerprint = fingerprint;
+            scanned.results.clear();
+        }
+        scanned.results.extend(pending.into_iter().zip(passed));
+
+        checks
+            .iter()
+            .map(|check| {
+                scanned
+                    .results
+                    .iter()
+                    .find(|(evaluated, _)| evaluated == check)
+                    .is_some_and(|(_, passed)| *passed)
+            })
+            .collect()
+    }
+
+    /// Number of passes made over file content
+    pub fn scan_count(&self) -> usize {
+        self.state.lock().map(|state| state.scans).unwrap_or(0)
+    }
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SSHD_CONFIG: &str = "\
+Protocol 2
+PermitRootLogin no
+PasswordAuthentication no
+X11Forwarding no
+";
+
+    fn checks(pairs: &[(Operation, &str)]) -> Vec<ContentCheck> {
+        pairs
+            .iter()
+            .map(|(operation, expected)| ContentCheck::new(*operation, *expected))
+            .collect()
+    }
+
+    #[test]
+    fn test_planned_checks_on_one_file_scan_once() {
+        let batch = ContentBatch::new();
+        let path = "/etc/ssh/sshd_config";
+        let root_login = checks(&[(Operation::Contains, "PermitRootLogin no")]);
+        let passwords = checks(&[(Operation::Contains, "PasswordAuthentication no")]);
+        let protocol = checks(&[(Operation::StartsWith, "Protocol 2")]);
+
+        // Three criteria, planned before dispatch
+        for criterion_checks in [&root_login, &passwords, &protocol] {
+            batch.plan(path, criterion_checks.iter().cloned());
+        }
+
+        assert_eq!(batch.evaluate(path, SSHD_CONFIG, &root_login), [true]);
+        assert_eq!(batch.evaluate(path, SSHD_CONFIG, &passwords), [true]);
+        assert_eq!(batch.evaluate(path, SSHD_CONFIG, &protocol), [true]);
+        assert_eq!(batch.scan_count(), 1);
+    }
+
+    #[test]
+    fn test_unplanned_checks_and_changed_content_rescan() {
+        let batch = ContentBatch::new();
+        let path = "/etc/ssh/sshd_config";
+        let x11 = checks(&[(Operation::Contains, "X11Forwarding no")]);
+        batch.plan(path, x11.iter().cloned());
+
+        assert_eq!(batch.evaluate(path, SSHD_CONFIG, &x11), [true]);
+        let tunnels = checks(&[(Operation::NotContains, "PermitTunnel yes")]);
+        assert_eq!(batch.evaluate(path, SSHD_CONFIG, &tunnels), [true]);
+        assert_eq!(batch.scan_count(), 2);
+
+        // Both are cached for this content, but not for a different read
+        assert_eq!(batch.evaluate(path, SSHD_CONFIG, &tunnels), [true]);
+        assert_eq!(batch.scan_count(), 2);
+        assert_eq!(batch.evaluate(path, "X11Forwarding yes\n", &x11), [false]);
+        assert_eq!(batch.scan_count(), 3);
+    }
+
+    #[test]
+    fn test_single_pass_matches_individual_checks() {
+        let all = checks(&[
+            (Operation::Contains, "PermitRootLogin no"),
+            (Operation::Contains, "PermitRootLogin yes"),
+            (Operation::NotContains, "Protocol 1"),
+            (Operation::NotContains, "X11Forwarding"),
+            (Operation::Contains, ""),
+            (Operation::StartsWith, "Protocol"),
+            (Operation::EndsWith, "no\n"),
+        ]);
+
+        let individually: Vec<bool> = all.iter().map(|c| c.evaluate(SSHD_CONFIG)).collect();
+        assert_eq!(scan_content(SSHD_CONFIG, &all), individually);
+        assert_eq!(individually[..4], [true, false, true, false]);
+    }
+}
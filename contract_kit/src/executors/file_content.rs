@@ -1,10 +1,20 @@
 //! # File Content Executor
 //!
 //! Validates file content with string operations (contains, starts, ends, pattern_match).
+//! A `pattern_match` with a capture group feeds the `content_capture` state
+//! field (see `pattern_extract`).
+//!
+//! An object whose strict recursive scan skipped unreadable paths
+//! (`skipped_paths`, see `collectors::filesystem`) is reported as a
+//! collection error rather than evaluated.
+//!
+//! All content checks of a criterion are evaluated in one pass over each
+//! object's content. With `with_batch`, checks on the same path are grouped
+//! across criteria as well (see `content_batch`).
 
 use common::results::Outcome;
 use execution_engine::execution::{
-    comparisons::string, evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
+    evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
 };
 use execution_engine::strategies::{
     CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
@@ -14,29 +24,90 @@ use execution_engine::types::common::{Operation, ResolvedValue};
 use execution_engine::types::execution_context::ExecutableCriterion;
 use std::collections::HashMap;
 
+use super::content_batch::{content_checks, object_path, scan_content, ContentBatch};
+use super::file_metadata::format_value;
+use super::pattern_extract::{
+    compare_capture, first_capture, typed_capture, PatternExtractError, CAPTURE_FIELD,
+};
+use crate::collectors::error_reporting::{
+    count_failing_objects, count_found_objects, not_found_details, CollectionErrorKind,
+    CollectionErrorRecord,
+};
+
 /// Executor for file_content validation
 pub struct FileContentExecutor {
     contract: CtnContract,
+    batch: Option<ContentBatch>,
 }
 
 impl FileContentExecutor {
     pub fn new(contract: CtnContract) -> Self {
-        Self { contract }
+        Self {
+            contract,
+            batch: None,
+        }
+    }
+
+    /// Share content check results with other criteria on the same path
+    pub fn with_batch(mut self, batch: ContentBatch) -> Self {
+        self.batch = Some(batch);
+        self
     }
 
-    /// Compare string operations using base comparison logic
-    fn compare_string_operation(&self, expected: &str, actual: &str, operation: Operation) -> bool {
-        // FIXED: Use the base string comparison module
-        match string::compare(actual, expected, operation) {
-            Ok(result) => result,
-            Err(e) => {
-                // Log the error and return false
-                eprintln!("String comparison error: {}", e);
-                false
+    /// Results of the criterion's content checks on one object's content
+    fn evaluate_checks(
+        &self,
+        criterion: &ExecutableCriterion,
+        object_id: &str,
+        content: &str,
+    ) -> Vec<bool> {
+        let checks = content_checks(criterion);
+        let path = criterion
+            .objects
+            .iter()
+            .find(|object| object.identifier == object_id)
+            .and_then(object_path);
+
+        match (&self.batch, path) {
+            (Some(batch), Some(path)) => {
+                batch.plan_criterion(criterion);
+                batch.evaluate(path, content, &checks)
             }
+            _ => scan_content(content, &checks),
         }
     }
 
+    /// Collection error for paths a strict recursive scan could not read
+    fn skipped_paths_error(
+        &self,
+        object_id: &str,
+        data: &CollectedData,
+    ) -> Option<CollectionErrorRecord> {
+        let skipped: Vec<&str> = match data.get_field("skipped_paths") {
+            Some(ResolvedValue::Collection(paths)) => paths
+                .iter()
+                .filter_map(|path| match path {
+                    ResolvedValue::String(path) => Some(path.as_str()),
+                    _ => None,
+                })
+                .collect(),
+            _ => return None,
+        };
+        if skipped.is_empty() {
+            return None;
+        }
+        Some(CollectionErrorRecord {
+            kind: CollectionErrorKind::AccessDenied,
+            collector_id: self.contract.collection_strategy.collector_type.clone(),
+            object_id: object_id.to_string(),
+            reason: format!(
+                "strict_scan could not read {} path(s): {}",
+                skipped.len(),
+                skipped.join(", ")
+            ),
+        })
+    }
+
     /// Create a preview of content for error messages (truncated if needed)
     fn preview_content(&self, content: &str, max_len: usize) -> String {
         if content.len() <= max_len {
@@ -47,6 +118,65 @@ impl FileContentExecutor {
     }
 }
 
+/// Validate a `content_capture` field against the latest capture
+///
+/// Fails when no earlier `pattern_match` had a capture group, the pattern
+/// did not match, or the capture does not parse as the field's type
+/// (including a capture that is not a version for a `version` field).
+fn capture_result(
+    capture: Option<&Result<Option<String>, PatternExtractError>>,
+    expected: &ResolvedValue,
+    operation: Operation,
+) -> (ResolvedValue, bool, String) {
+    let missing = ResolvedValue::String(String::new());
+    let captured = match capture {
+        None => {
+            return (
+                missing,
+                false,
+                format!(
+                    "Field '{}' needs an earlier content pattern_match with a capture group",
+                    CAPTURE_FIELD
+                ),
+            )
+        }
+        Some(Err(e)) => return (missing, false, format!("Content capture failed: {}", e)),
+        Some(Ok(None)) => {
+            return (
+                missing,
+                false,
+                "Content capture failed: pattern did not match".to_string(),
+            )
+        }
+        Some(Ok(Some(captured))) => captured,
+    };
+
+    let actual = match typed_capture(captured, expected) {
+        Ok(value) => value,
+        Err(msg) => return (ResolvedValue::String(captured.clone()), false, msg),
+    };
+    let passed = match compare_capture(expected, &actual, operation) {
+        Ok(passed) => passed,
+        Err(msg) => return (actual, false, format!("Content capture failed: {}", msg)),
+    };
+    let msg = if passed {
+        format!(
+            "Content capture passed: {} {:?} {}",
+            format_value(&actual),
+            operation,
+            format_value(expected)
+        )
+    } else {
+        format!(
+            "Content capture failed: expected {:?} {}, got {}",
+            operation,
+            format_value(expected),
+            format_value(&actual)
+        )
+    };
+    (actual, passed, msg)
+}
+
 impl CtnExecutor for FileContentExecutor {
     fn execute_with_contract(
         &self,
@@ -58,7 +188,8 @@ impl CtnExecutor for FileContentExecutor {
 
         // Phase 1: Existence Check
         let objects_expected = criterion.expected_object_count();
-        let objects_found = collected_data.len();
+        let collection_errors = CollectionErrorRecord::from_collected(&collected_data);
+        let objects_found = count_found_objects(&collected_data, &collection_errors);
 
         let existence_passed =
             evaluate_existence_check(test_spec.existence_check, objects_found, objects_expected);
@@ -67,8 +198,10 @@ impl CtnExecutor for FileContentExecutor {
             return Ok(CtnExecutionResult::fail(
                 criterion.criterion_type.clone(),
                 format!(
-                    "Existence check failed: expected {} objects, found {}",
-                    objects_expected, objects_found
+                    "Existence check failed: expected {} objects, found {}{}",
+                    objects_expected,
+                    objects_found,
+                    not_found_details(&collection_errors)
                 ),
             )
             .with_collected_data(collected_data));
@@ -79,6 +212,24 @@ impl CtnExecutor for FileContentExecutor {
         let mut failure_messages = Vec::new();
 
         for (object_id, data) in &collected_data {
+            // Objects that could not be collected are reported, not evaluated
+            if let Some(error) = CollectionErrorRecord::from_collected_data(data)
+                .or_else(|| self.skipped_paths_error(object_id, data))
+            {
+                // Missing objects were already handled by the existence check
+                if error.counts_as_found() {
+                    failure_messages.push(error.message());
+                    state_results.push(StateValidationResult {
+                        object_id: object_id.clone(),
+                        state_results: vec![error.field_result()],
+                        combined_result: false,
+                        state_operator: test_spec.state_operator,
+                        message: error.message(),
+                    });
+                }
+                continue;
+            }
+
             // Get file content
             let content = match data.get_field("file_content") {
                 Some(ResolvedValue::String(c)) => c.clone(),
@@ -95,10 +246,32 @@ impl CtnExecutor for FileContentExecutor {
             };
 
             let mut all_field_results = Vec::new();
+            let mut check_results = self
+                .evaluate_checks(criterion, object_id, &content)
+                .into_iter();
+            // Capture of the latest pattern_match with a capture group
+            let mut capture: Option<Result<Option<String>, PatternExtractError>> = None;
 
             // Validate each state
             for state in &criterion.states {
                 for field in &state.fields {
+                    if field.name == CAPTURE_FIELD {
+                        let (actual_value, passed, msg) =
+                            capture_result(capture.as_ref(), &field.value, field.operation);
+                        if !passed {
+                            failure_messages.push(format!("Object '{}': {}", object_id, msg));
+                        }
+                        all_field_results.push(FieldValidationResult {
+                            field_name: field.name.clone(),
+                            expected_value: field.value.clone(),
+                            actual_value,
+                            operation: field.operation,
+                            passed,
+                            message: msg,
+                        });
+                        continue;
+                    }
+
                     // For content validation, field.name should be "content"
                     if field.name != "content" {
                         continue;
@@ -125,8 +298,15 @@ impl CtnExecutor for FileContentExecutor {
                         }
                     };
 
-                    // Perform string operation
-                    let passed = self.compare_string_operation(expected, &content, field.operation);
+                    // Results come in the same order as the string-valued fields
+                    let passed = check_results.next().unwrap_or(false);
+
+                    if field.operation == Operation::PatternMatch {
+                        match first_capture(&content, expected) {
+                            Err(PatternExtractError::NoCaptureGroup(_)) => {}
+                            result => capture = Some(result),
+                        }
+                    }
 
                     let msg = if passed {
                         format!("Content check passed: {:?} '{}'", field.operation, expected)
@@ -211,8 +391,11 @@ impl CtnExecutor for FileContentExecutor {
             evaluate_item_check(test_spec.item_check, objects_passing, state_results.len());
 
         // Final result
+        let (objects_failing, failing_from_errors) = count_failing_objects(&state_results);
         let final_status = if existence_passed && item_passed {
             Outcome::Pass
+        } else if failing_from_errors > 0 && failing_from_errors == objects_failing {
+            Outcome::Error
         } else {
             Outcome::Fail
         };
@@ -224,6 +407,11 @@ impl CtnExecutor for FileContentExecutor {
                 objects_passing,
                 state_results.len()
             )
+        } else if final_status == Outcome::Error {
+            format!(
+                "File content validation could not be completed:\n  - {}",
+                failure_messages.join("\n  - ")
+            )
         } else if !failure_messages.is_empty() {
             format!(
                 "File content validation failed:\n  - {}",
@@ -247,6 +435,10 @@ impl CtnExecutor for FileContentExecutor {
             message,
             details: serde_json::json!({
                 "failures": failure_messages,
+                "collection_errors": collection_errors
+                    .iter()
+                    .map(CollectionErrorRecord::to_json)
+                    .collect::<Vec<_>>(),
                 "objects_expected": objects_expected,
                 "objects_found": objects_found,
                 "objects_passing": objects_passing,
@@ -276,6 +468,9 @@ impl CtnExecutor for FileContentExecutor {
     ) -> Result<(), CtnExecutionError> {
         // Validate that file_content field is present
         for data in collected_data.values() {
+            if CollectionErrorRecord::from_collected_data(data).is_some() {
+                continue;
+            }
             if !data.has_field("file_content") {
                 return Err(CtnExecutionError::MissingDataField {
                     field: "file_content".to_string(),
@@ -285,3 +480,65 @@ impl CtnExecutor for FileContentExecutor {
         Ok(())
     }
 }
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::file_contracts::create_file_content_contract;
+
+    fn scanned(skipped: Option<&[&str]>) -> CollectedData {
+        let mut data = CollectedData::new(
+            "etc_ssh".to_string(),
+            "file_content".to_string(),
+            "filesystem_collector".to_string(),
+        );
+        data.add_field(
+            "file_content".to_string(),
+            ResolvedValue::String("PermitRootLogin no\n".to_string()),
+        );
+        if let Some(skipped) = skipped {
+            data.add_field(
+                "skipped_paths".to_string(),
+                ResolvedValue::Collection(
+                    skipped
+                        .iter()
+                        .map(|path| ResolvedValue::String(path.to_string()))
+                        .collect(),
+                ),
+            );
+        }
+        data
+    }
+
+    #[test]
+    fn test_skipped_paths_are_a_collection_error() {
+        let executor = FileContentExecutor::new(create_file_content_contract());
+
+        // Lenient scans and strict scans that read everything are evaluated
+        assert!(executor
+            .skipped_paths_error("etc_ssh", &scanned(None))
+            .is_none());
+        assert!(executor
+            .skipped_paths_error("etc_ssh", &scanned(Some(&[])))
+            .is_none());
+
+        let error = executor
+            .skipped_paths_error(
+                "etc_ssh",
+                &scanned(Some(&["/etc/ssh/sshd_config.d", "/etc/ssh/ssh_host_key"])),
+            )
+            .unwrap();
+        assert_eq!(error.kind, CollectionErrorKind::AccessDenied);
+        assert_eq!(error.object_id, "etc_ssh");
+        assert_eq!(
+            error.reason,
+            "strict_scan could not read 2 path(s): /etc/ssh/sshd_config.d, /etc/ssh/ssh_host_key"
+        );
+        assert!(error.counts_as_found());
+    }
+}
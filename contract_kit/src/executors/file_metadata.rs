@@ -1,10 +1,23 @@
 //! # File Metadata Executor
 //!
 //! Validates file metadata (permissions, owner, group, size, existence).
+//! The `acl` field is a set check over ACL entries; record checks run
+//! against the ACL record. `size` also accepts a human-readable size
+//! string such as `100MB` (see `byte_size`). Glob objects are expanded into
+//! one object per matched file before the existence check (see
+//! `glob_objects`). `pattern_match` compiles each pattern once per
+//! criterion; an invalid pattern fails execution rather than the field.
+//!
+//! A criterion whose only state field is `exists = false` asserts absence.
+//! A missing file is collected as an object with `exists = false`, which
+//! the existence check counts as found, so for such a criterion the
+//! existence check is skipped: it passes when the files are absent,
+//! whatever its `TEST` existence check says.
 
 use common::results::Outcome;
 use execution_engine::execution::{
-    evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
+    comparisons::string, evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
+    record_validation::validate_record_checks,
 };
 use execution_engine::strategies::{
     CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
@@ -12,84 +25,231 @@ use execution_engine::strategies::{
 };
 use execution_engine::types::common::{Operation, ResolvedValue};
 use execution_engine::types::execution_context::ExecutableCriterion;
+use regex::Regex;
 use std::collections::HashMap;
 
+use crate::collectors::error_reporting::{
+    count_failing_objects, count_found_objects, not_found_details, CollectionErrorRecord,
+};
+use crate::collectors::glob_objects::{expand_glob_objects, is_glob_object};
+use crate::executors::byte_size::parse_byte_size;
+use crate::executors::short_circuit::{state_outcome, EvidenceLevel, ShortCircuit};
+
+/// State fields holding a byte count, which accept human-readable sizes
+const BYTE_SIZE_FIELDS: &[&str] = &["size"];
+
 /// Executor for file_metadata validation
 pub struct FileMetadataExecutor {
     contract: CtnContract,
+    evidence_level: EvidenceLevel,
 }
 
 impl FileMetadataExecutor {
     pub fn new(contract: CtnContract) -> Self {
-        Self { contract }
+        Self {
+            contract,
+            evidence_level: EvidenceLevel::default(),
+        }
     }
 
-    /// Perform comparison based on operation and data types
-    fn compare_values(
-        &self,
-        expected: &ResolvedValue,
-        actual: &ResolvedValue,
-        operation: Operation,
-    ) -> bool {
-        match (expected, actual, operation) {
-            // String comparisons
-            (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::Equals) => {
-                exp == act
-            }
-            (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::NotEqual) => {
-                exp != act
-            }
+    /// Set how much per-object evidence results must carry
+    pub fn with_evidence_level(mut self, level: EvidenceLevel) -> Self {
+        self.evidence_level = level;
+        self
+    }
 
-            // Boolean comparisons
-            (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::Equals) => {
-                exp == act
-            }
-            (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::NotEqual) => {
-                exp != act
+    /// Expected value to compare, with human-readable sizes converted to bytes
+    fn expected_value(&self, name: &str, value: &ResolvedValue) -> Result<ResolvedValue, String> {
+        match value {
+            ResolvedValue::String(size) if BYTE_SIZE_FIELDS.contains(&name) => {
+                parse_byte_size(size)
+                    .map(ResolvedValue::Integer)
+                    .map_err(|e| format!("Invalid size for field '{}': {}", name, e))
             }
+            _ => Ok(value.clone()),
+        }
+    }
+}
 
-            // Integer comparisons
-            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::Equals) => {
-                exp == act
-            }
-            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::NotEqual) => {
-                exp != act
-            }
-            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::GreaterThan) => {
-                act > exp
-            }
-            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::LessThan) => {
-                act < exp
-            }
-            (
-                ResolvedValue::Integer(exp),
-                ResolvedValue::Integer(act),
-                Operation::GreaterThanOrEqual,
-            ) => act >= exp,
-            (
-                ResolvedValue::Integer(exp),
-                ResolvedValue::Integer(act),
-                Operation::LessThanOrEqual,
-            ) => act <= exp,
-
-            // Type mismatch or unsupported operation
-            _ => false,
+/// Whether every state field of the criterion is `exists = false`
+///
+/// Record checks or any other field make it an ordinary criterion.
+fn asserts_absence(criterion: &ExecutableCriterion) -> bool {
+    let mut fields = criterion
+        .states
+        .iter()
+        .flat_map(|state| &state.fields)
+        .peekable();
+    fields.peek().is_some()
+        && criterion
+            .states
+            .iter()
+            .all(|state| state.record_checks.is_empty())
+        && fields.all(|field| is_absence_field(&field.name, field.operation, &field.value))
+}
+
+/// Whether a state field is `exists = false`
+fn is_absence_field(name: &str, operation: Operation, value: &ResolvedValue) -> bool {
+    name == "exists"
+        && operation == Operation::Equals
+        && matches!(value, ResolvedValue::Boolean(false))
+}
+
+/// Passing `exists = false` result for an object that was not found
+fn absent_field_result() -> FieldValidationResult {
+    FieldValidationResult {
+        field_name: "exists".to_string(),
+        expected_value: ResolvedValue::Boolean(false),
+        actual_value: ResolvedValue::Boolean(false),
+        operation: Operation::Equals,
+        passed: true,
+        message: "Field 'exists' passed: object not found".to_string(),
+    }
+}
+
+/// Compiled `pattern_match` patterns, shared by all objects of a criterion
+#[derive(Debug, Default)]
+struct PatternCache {
+    regexes: HashMap<String, Regex>,
+}
+
+impl PatternCache {
+    /// Whether `text` matches `pattern`, compiling the pattern on first use
+    fn is_match(&mut self, pattern: &str, text: &str) -> Result<bool, regex::Error> {
+        if let Some(regex) = self.regexes.get(pattern) {
+            return Ok(regex.is_match(text));
+        }
+        let regex = Regex::new(pattern)?;
+        let matched = regex.is_match(text);
+        self.regexes.insert(pattern.to_string(), regex);
+        Ok(matched)
+    }
+}
+
+/// Compare a state field, matching string patterns through the cache
+///
+/// An invalid pattern is an error naming the field and the pattern, so a
+/// typo in a policy is not reported as a non-compliant file.
+fn compare_field(
+    patterns: &mut PatternCache,
+    field_name: &str,
+    expected: &ResolvedValue,
+    actual: &ResolvedValue,
+    operation: Operation,
+) -> Result<bool, CtnExecutionError> {
+    match (expected, actual, operation) {
+        (ResolvedValue::String(pattern), ResolvedValue::String(act), Operation::PatternMatch) => {
+            patterns
+                .is_match(pattern, act)
+                .map_err(|e| CtnExecutionError::DataValidationFailed {
+                    reason: format!(
+                        "Invalid pattern '{}' for field '{}': {}",
+                        pattern, field_name, e
+                    ),
+                })
         }
+        _ => Ok(compare_values(expected, actual, operation)),
     }
+}
 
-    /// Format a value for display in error messages
-    fn format_value(&self, value: &ResolvedValue) -> String {
-        match value {
-            ResolvedValue::String(s) => format!("'{}'", s),
-            ResolvedValue::Integer(i) => i.to_string(),
-            ResolvedValue::Boolean(b) => b.to_string(),
-            ResolvedValue::Float(f) => f.to_string(),
-            ResolvedValue::Binary(b) => format!("<binary {} bytes>", b.len()),
-            ResolvedValue::Collection(items) => format!("<collection {} items>", items.len()),
-            ResolvedValue::Version(v) => v.to_string(),
-            ResolvedValue::EvrString(e) => e.to_string(),
-            ResolvedValue::RecordData(_) => "<record>".to_string(),
+/// Compare a collected value against an expected value
+///
+/// Shared by executors whose fields are plain strings, integers and
+/// booleans (`file_metadata`, `registry_value`, `file_content` captures).
+pub(crate) fn compare_values(
+    expected: &ResolvedValue,
+    actual: &ResolvedValue,
+    operation: Operation,
+) -> bool {
+    match (expected, actual, operation) {
+        // String comparisons
+        (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::Equals) => exp == act,
+        (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::NotEqual) => exp != act,
+        // Contains, starts/ends with and pattern match (e.g. symlink_target)
+        (ResolvedValue::String(exp), ResolvedValue::String(act), operation) => {
+            string::compare(act, exp, operation).unwrap_or(false)
+        }
+
+        // Boolean comparisons
+        (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::Equals) => exp == act,
+        (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::NotEqual) => {
+            exp != act
+        }
+
+        // Integer comparisons
+        (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::Equals) => exp == act,
+        (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::NotEqual) => {
+            exp != act
         }
+        (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::GreaterThan) => {
+            act > exp
+        }
+        (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::LessThan) => {
+            act < exp
+        }
+        (
+            ResolvedValue::Integer(exp),
+            ResolvedValue::Integer(act),
+            Operation::GreaterThanOrEqual,
+        ) => act >= exp,
+        (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::LessThanOrEqual) => {
+            act <= exp
+        }
+
+        // Allowed values: `=` passes if the value is one of them (owner_id, group_id)
+        (ResolvedValue::Collection(allowed), actual, Operation::Equals) => {
+            is_member(allowed, actual)
+        }
+        (ResolvedValue::Collection(allowed), actual, Operation::NotEqual) => {
+            !is_member(allowed, actual)
+        }
+
+        // Set membership (acl)
+        (
+            ResolvedValue::String(pattern),
+            ResolvedValue::Collection(entries),
+            Operation::Contains,
+        ) => entries_contain(entries, pattern),
+        (
+            ResolvedValue::String(pattern),
+            ResolvedValue::Collection(entries),
+            Operation::NotContains,
+        ) => !entries_contain(entries, pattern),
+
+        // Type mismatch or unsupported operation
+        _ => false,
+    }
+}
+
+/// Check whether a string or integer is one of the allowed values
+fn is_member(allowed: &[ResolvedValue], actual: &ResolvedValue) -> bool {
+    allowed.iter().any(|value| match (value, actual) {
+        (ResolvedValue::String(v), ResolvedValue::String(a)) => v == a,
+        (ResolvedValue::Integer(v), ResolvedValue::Integer(a)) => v == a,
+        _ => false,
+    })
+}
+
+/// Check whether any entry in the collection contains the pattern
+fn entries_contain(entries: &[ResolvedValue], pattern: &str) -> bool {
+    entries.iter().any(|entry| match entry {
+        ResolvedValue::String(e) => e.contains(pattern),
+        _ => false,
+    })
+}
+
+/// Format a value for display in error messages
+pub(crate) fn format_value(value: &ResolvedValue) -> String {
+    match value {
+        ResolvedValue::String(s) => format!("'{}'", s),
+        ResolvedValue::Integer(i) => i.to_string(),
+        ResolvedValue::Boolean(b) => b.to_string(),
+        ResolvedValue::Float(f) => f.to_string(),
+        ResolvedValue::Binary(b) => format!("<binary {} bytes>", b.len()),
+        ResolvedValue::Collection(items) => format!("<collection {} items>", items.len()),
+        ResolvedValue::Version(v) => v.to_string(),
+        ResolvedValue::EvrString(e) => e.to_string(),
+        ResolvedValue::RecordData(_) => "<record>".to_string(),
     }
 }
 
@@ -102,19 +262,28 @@ impl CtnExecutor for FileMetadataExecutor {
     ) -> Result<CtnExecutionResult, CtnExecutionError> {
         let test_spec = &criterion.test;
 
+        // Glob objects count as one object per matched file
+        let expanded = expand_glob_objects(collected_data, &self.contract);
+        let objects_expected = expanded.expected_objects(criterion.expected_object_count());
+        let collected_data = expanded.collected_data;
+
         // Phase 1: Existence Check
-        let objects_expected = criterion.expected_object_count();
-        let objects_found = collected_data.len();
+        let collection_errors = CollectionErrorRecord::from_collected(&collected_data);
+        let objects_found = count_found_objects(&collected_data, &collection_errors);
 
-        let existence_passed =
-            evaluate_existence_check(test_spec.existence_check, objects_found, objects_expected);
+        // An absence check passes on missing files whatever the existence check
+        let absence_check = asserts_absence(criterion);
+        let existence_passed = absence_check
+            || evaluate_existence_check(test_spec.existence_check, objects_found, objects_expected);
 
         if !existence_passed {
             return Ok(CtnExecutionResult::fail(
                 criterion.criterion_type.clone(),
                 format!(
-                    "Existence check failed: expected {} objects, found {}",
-                    objects_expected, objects_found
+                    "Existence check failed: expected {} objects, found {}{}",
+                    objects_expected,
+                    objects_found,
+                    not_found_details(&collection_errors)
                 ),
             )
             .with_collected_data(collected_data));
@@ -123,13 +292,96 @@ impl CtnExecutor for FileMetadataExecutor {
         // Phase 2: State Validation
         let mut state_results = Vec::new();
         let mut failure_messages = Vec::new();
+        let short_circuit = ShortCircuit::new(self.evidence_level, |passing, total| {
+            evaluate_item_check(test_spec.item_check, passing, total)
+        });
+        let mut short_circuited = false;
+        let mut patterns = PatternCache::default();
 
         for (object_id, data) in &collected_data {
+            // Objects that could not be collected are reported, not evaluated
+            if let Some(error) = CollectionErrorRecord::from_collected_data(data) {
+                // Missing objects were already handled by the existence check,
+                // unless the criterion asserts their absence
+                if absence_check && !error.counts_as_found() {
+                    state_results.push(StateValidationResult {
+                        object_id: object_id.clone(),
+                        state_results: vec![absent_field_result()],
+                        combined_result: true,
+                        state_operator: test_spec.state_operator,
+                        message: format!("Object '{}': passed (not found)", object_id),
+                    });
+                } else if error.counts_as_found() {
+                    failure_messages.push(error.message());
+                    state_results.push(StateValidationResult {
+                        object_id: object_id.clone(),
+                        state_results: vec![error.field_result()],
+                        combined_result: false,
+                        state_operator: test_spec.state_operator,
+                        message: error.message(),
+                    });
+                }
+                continue;
+            }
+
             let mut all_field_results = Vec::new();
 
             // Validate each state
             for state in &criterion.states {
+                // Handle record checks against the ACL record
+                if !state.record_checks.is_empty() {
+                    let record_data = match data.get_field("acl_record") {
+                        Some(ResolvedValue::RecordData(rd)) => rd,
+                        _ => {
+                            let msg = "ACL record field is not RecordData".to_string();
+                            all_field_results.push(FieldValidationResult {
+                                field_name: "record".to_string(),
+                                expected_value: ResolvedValue::String("RecordData".to_string()),
+                                actual_value: ResolvedValue::String("invalid".to_string()),
+                                operation: Operation::Equals,
+                                passed: false,
+                                message: msg.clone(),
+                            });
+                            failure_messages.push(format!("Object '{}': {}", object_id, msg));
+                            continue;
+                        }
+                    };
+
+                    let validation_results =
+                        validate_record_checks(record_data, &state.record_checks).map_err(|e| {
+                            CtnExecutionError::ExecutionFailed {
+                                ctn_type: criterion.criterion_type.clone(),
+                                reason: format!("Record validation failed: {}", e),
+                            }
+                        })?;
+
+                    for result in &validation_results {
+                        all_field_results.push(FieldValidationResult {
+                            field_name: result.field_path.clone(),
+                            expected_value: ResolvedValue::String(
+                                result.expected.clone().unwrap_or_default(),
+                            ),
+                            actual_value: ResolvedValue::String(
+                                result.actual.clone().unwrap_or_default(),
+                            ),
+                            operation: Operation::Equals,
+                            passed: result.passed,
+                            message: result.message.clone(),
+                        });
+
+                        if !result.passed {
+                            failure_messages
+                                .push(format!("Object '{}': {}", object_id, result.message));
+                        }
+                    }
+                }
+
                 for field in &state.fields {
+                    // Record field - handled above
+                    if field.name == "record" {
+                        continue;
+                    }
+
                     let data_field_name = self
                         .contract
                         .field_mappings
@@ -159,25 +411,47 @@ impl CtnExecutor for FileMetadataExecutor {
                         }
                     };
 
+                    let expected_value = match self.expected_value(&field.name, &field.value) {
+                        Ok(value) => value,
+                        Err(msg) => {
+                            all_field_results.push(FieldValidationResult {
+                                field_name: field.name.clone(),
+                                expected_value: field.value.clone(),
+                                actual_value,
+                                operation: field.operation,
+                                passed: false,
+                                message: msg.clone(),
+                            });
+                            failure_messages.push(format!("Object '{}': {}", object_id, msg));
+                            continue;
+                        }
+                    };
+
                     // Perform comparison
-                    let passed = self.compare_values(&field.value, &actual_value, field.operation);
+                    let passed = compare_field(
+                        &mut patterns,
+                        &field.name,
+                        &expected_value,
+                        &actual_value,
+                        field.operation,
+                    )?;
 
                     let msg = if passed {
                         format!(
                             "Field '{}' passed: {} {:?} {}",
                             field.name,
-                            self.format_value(&actual_value),
+                            format_value(&actual_value),
                             field.operation,
-                            self.format_value(&field.value)
+                            format_value(&field.value)
                         )
                     } else {
                         format!(
                             "Field '{}' failed: expected {} {:?} {}, got {}",
                             field.name,
-                            self.format_value(&field.value),
+                            format_value(&field.value),
                             field.operation,
-                            self.format_value(&field.value),
-                            self.format_value(&actual_value)
+                            format_value(&field.value),
+                            format_value(&actual_value)
                         )
                     };
 
@@ -213,19 +487,26 @@ impl CtnExecutor for FileMetadataExecutor {
                     state_bools.len()
                 ),
             });
+
+            if short_circuit.should_stop(combined, false) {
+                short_circuited = true;
+                break;
+            }
         }
 
         // Phase 3: Item Check
         let objects_passing = state_results.iter().filter(|r| r.combined_result).count();
-        let item_passed =
-            evaluate_item_check(test_spec.item_check, objects_passing, state_results.len());
+        // A glob matching no file leaves an absence check nothing to fail
+        let item_passed = (absence_check && state_results.is_empty())
+            || evaluate_item_check(test_spec.item_check, objects_passing, state_results.len());
 
         // Final result
-        let final_status = if existence_passed && item_passed {
-            Outcome::Pass
-        } else {
-            Outcome::Fail
-        };
+        let (objects_failing, failing_from_errors) = count_failing_objects(&state_results);
+        let final_status = state_outcome(
+            existence_passed && item_passed,
+            objects_failing,
+            failing_from_errors,
+        );
 
         // Build detailed message
         let message = if final_status == Outcome::Pass {
@@ -234,6 +515,11 @@ impl CtnExecutor for FileMetadataExecutor {
                 objects_passing,
                 state_results.len()
             )
+        } else if final_status == Outcome::Error {
+            format!(
+                "File metadata validation could not be completed:\n  - {}",
+                failure_messages.join("\n  - ")
+            )
         } else if !failure_messages.is_empty() {
             format!(
                 "File metadata validation failed:\n  - {}",
@@ -257,9 +543,15 @@ impl CtnExecutor for FileMetadataExecutor {
             message,
             details: serde_json::json!({
                 "failures": failure_messages,
+                "collection_errors": collection_errors
+                    .iter()
+                    .map(CollectionErrorRecord::to_json)
+                    .collect::<Vec<_>>(),
                 "objects_expected": objects_expected,
                 "objects_found": objects_found,
                 "objects_passing": objects_passing,
+                "absence_check": absence_check,
+                "short_circuited": short_circuited,
                 "test_specification": {
                     "existence_check": format!("{:?}", test_spec.existence_check),
                     "item_check": format!("{:?}", test_spec.item_check),
@@ -286,6 +578,10 @@ impl CtnExecutor for FileMetadataExecutor {
     ) -> Result<(), CtnExecutionError> {
         // Validate that required fields are present
         for data in collected_data.values() {
+            // Glob objects carry their matches' fields until execution
+            if CollectionErrorRecord::from_collected_data(data).is_some() || is_glob_object(data) {
+                continue;
+            }
             for required_field in &self
                 .contract
                 .field_mappings
@@ -302,3 +598,317 @@ impl CtnExecutor for FileMetadataExecutor {
         Ok(())
     }
 }
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> ResolvedValue {
+        ResolvedValue::Collection(
+            values
+                .iter()
+                .map(|v| ResolvedValue::String(v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_allowed_values() {
+        let allowed = strings(&["0", "root", "bin"]);
+        let owner = |id: &str| ResolvedValue::String(id.to_string());
+
+        // Member
+        assert!(compare_values(&allowed, &owner("0"), Operation::Equals));
+        assert!(compare_values(&allowed, &owner("bin"), Operation::Equals));
+        assert!(!compare_values(
+            &allowed,
+            &owner("bin"),
+            Operation::NotEqual
+        ));
+
+        // Not a member
+        assert!(!compare_values(&allowed, &owner("1000"), Operation::Equals));
+        assert!(compare_values(
+            &allowed,
+            &owner("1000"),
+            Operation::NotEqual
+        ));
+        assert!(!compare_values(
+            &strings(&[]),
+            &owner("0"),
+            Operation::Equals
+        ));
+
+        // Members must have the type of the value
+        let ids = ResolvedValue::Collection(vec![ResolvedValue::Integer(0)]);
+        assert!(!compare_values(&ids, &owner("0"), Operation::Equals));
+        assert!(compare_values(
+            &ids,
+            &ResolvedValue::Integer(0),
+            Operation::Equals
+        ));
+
+        // Other operations are not set checks
+        assert!(!compare_values(&allowed, &owner("0"), Operation::Contains));
+    }
+
+    #[test]
+    fn test_pattern_match_sid() {
+        let mut patterns = PatternCache::default();
+        let pattern = ResolvedValue::String("^S-1-5-21-.*-500$".to_string());
+        let sid = |s: &str| ResolvedValue::String(s.to_string());
+
+        // Built-in Administrator of any domain
+        let admin = sid("S-1-5-21-3623811015-3361044348-30300820-500");
+        assert!(compare_field(
+            &mut patterns,
+            "owner_id",
+            &pattern,
+            &admin,
+            Operation::PatternMatch
+        )
+        .unwrap());
+
+        // An ordinary user account does not match
+        let user = sid("S-1-5-21-3623811015-3361044348-30300820-1001");
+        assert!(!compare_field(
+            &mut patterns,
+            "owner_id",
+            &pattern,
+            &user,
+            Operation::PatternMatch
+        )
+        .unwrap());
+
+        // Compiled once for both objects
+        assert_eq!(patterns.regexes.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_error() {
+        let mut patterns = PatternCache::default();
+        let err = compare_field(
+            &mut patterns,
+            "owner_id",
+            &ResolvedValue::String("S-1-5-(21".to_string()),
+            &ResolvedValue::String("S-1-5-21".to_string()),
+            Operation::PatternMatch,
+        )
+        .unwrap_err();
+
+        let CtnExecutionError::DataValidationFailed { reason } = err else {
+            panic!("expected DataValidationFailed, got {:?}", err);
+        };
+        assert!(reason.contains("'S-1-5-(21'"), "{}", reason);
+        assert!(reason.contains("owner_id"), "{}", reason);
+    }
+
+    #[test]
+    fn test_absence_field() {
+        let bool_value = ResolvedValue::Boolean;
+        assert!(is_absence_field(
+            "exists",
+            Operation::Equals,
+            &bool_value(false)
+        ));
+        assert!(!is_absence_field(
+            "exists",
+            Operation::Equals,
+            &bool_value(true)
+        ));
+        assert!(!is_absence_field(
+            "exists",
+            Operation::NotEqual,
+            &bool_value(true)
+        ));
+        assert!(!is_absence_field(
+            "readable",
+            Operation::Equals,
+            &bool_value(false)
+        ));
+    }
+
+    /// Outcome of one file_metadata criterion over `path`, scanned with
+    /// `TEST <test>` and the state `fields`
+    #[cfg(feature = "native")]
+    fn scan_outcome(path: &str, test: &str, fields: &str) -> Outcome {
+        let object = format!("        path `{}`", path);
+        scan_trace(&object, test, fields, EvidenceLevel::PerObject).outcome
+    }
+
+    /// Trace of one file_metadata criterion over the object with `object`
+    /// lines, scanned with `TEST <test>`, the state `fields` and `level`
+    #[cfg(feature = "native")]
+    fn scan_trace(
+        object: &str,
+        test: &str,
+        fields: &str,
+        level: EvidenceLevel,
+    ) -> crate::executors::CriterionExplanation {
+        use crate::collectors::FileSystemCollector;
+        use crate::contracts::create_file_metadata_contract;
+        use crate::execution_api::{compile_source, scan_ast};
+        use crate::executors::{TraceRecorder, TracingExecutor};
+        use execution_engine::strategies::CtnStrategyRegistry;
+        use std::sync::Arc;
+
+        let source = format!(
+            "\
+META
+    esp_id `test-absence-001`
+    version `1.0.0`
+    dsl_schema_version `1.0.0`
+    platform `linux`
+    criticality `low`
+    control_mapping `CIS:1.1`
+    title `Absence check`
+META_END
+
+DEF
+    OBJECT target
+{}
+    OBJECT_END
+
+    STATE expected
+{}
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST {}
+            STATE_REF expected
+            OBJECT_REF target
+        CTN_END
+    CRI_END
+DEF_END
+",
+            object, fields, test
+        );
+        let ast = compile_source(&source, "absence.esp").unwrap();
+
+        let recorder = TraceRecorder::new();
+        let mut registry = CtnStrategyRegistry::new();
+        registry
+            .register_ctn_strategy(
+                Box::new(FileSystemCollector::new()),
+                Box::new(TracingExecutor::new(
+                    Box::new(
+                        FileMetadataExecutor::new(create_file_metadata_contract())
+                            .with_evidence_level(level),
+                    ),
+                    recorder.clone(),
+                )),
+            )
+            .unwrap();
+        scan_ast(&ast, Arc::new(registry)).unwrap();
+
+        let mut traces = recorder.take();
+        assert_eq!(traces.len(), 1);
+        traces.remove(0)
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_must_exist() {
+        let present = std::env::temp_dir().join(format!("esp_present_{}", std::process::id()));
+        std::fs::write(&present, "present").unwrap();
+        let missing = format!("{}_missing", present.display());
+        let exists = "        exists boolean = true";
+
+        let found = scan_outcome(&present.display().to_string(), "all all", exists);
+        let _ = std::fs::remove_file(&present);
+        assert_eq!(found, Outcome::Pass);
+        assert_eq!(scan_outcome(&missing, "all all", exists), Outcome::Fail);
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_must_not_exist() {
+        let present = std::env::temp_dir().join(format!("esp_absent_{}", std::process::id()));
+        std::fs::write(&present, "present").unwrap();
+        let missing = format!("{}_missing", present.display());
+        let absent = "        exists boolean = false";
+
+        // Passes on absence whatever the existence check
+        for test in ["all all", "none all", "at_least_one all"] {
+            assert_eq!(
+                scan_outcome(&missing, test, absent),
+                Outcome::Pass,
+                "{}",
+                test
+            );
+        }
+        let found = scan_outcome(&present.display().to_string(), "none all", absent);
+        let _ = std::fs::remove_file(&present);
+        assert_eq!(found, Outcome::Fail);
+    }
+
+    #[cfg(all(feature = "native", unix))]
+    #[test]
+    fn test_must_exist_with_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("esp_mode_{}", std::process::id()));
+        std::fs::write(&path, "secret").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        let missing = format!("{}_missing", path.display());
+        let fields = "        exists boolean = true\n        permissions string = `0600`";
+
+        let private = scan_outcome(&path.display().to_string(), "all all", fields);
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let readable = scan_outcome(&path.display().to_string(), "all all", fields);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(private, Outcome::Pass);
+        assert_eq!(readable, Outcome::Fail);
+        // Not an absence check: a missing file fails
+        assert_eq!(scan_outcome(&missing, "all all", fields), Outcome::Fail);
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_short_circuit_keeps_outcome() {
+        let dir = std::env::temp_dir().join(format!("esp_short_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, content) in [
+            ("a", "abc"),
+            ("b", "too long"),
+            ("c", "too long"),
+            ("d", "too long"),
+        ] {
+            std::fs::write(dir.join(name), content).unwrap();
+        }
+        let object = format!(
+            "        path `{}`\n        BEHAVIOR glob_expand",
+            dir.join("*").display()
+        );
+
+        let scan = |fields: &str, level| scan_trace(&object, "all all", fields, level);
+        let evaluated = |trace: &crate::executors::CriterionExplanation| {
+            trace.checks.as_ref().unwrap().objects_evaluated
+        };
+
+        // Three of four files fail: the first failure decides
+        let small = "        size int < 5";
+        let full = scan(small, EvidenceLevel::PerObject);
+        let fast = scan(small, EvidenceLevel::Outcome);
+        // Every file passes: nothing to stop at
+        let any = "        size int > 0";
+        let full_pass = scan(any, EvidenceLevel::PerObject);
+        let fast_pass = scan(any, EvidenceLevel::Outcome);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(full.outcome, Outcome::Fail);
+        assert_eq!(fast.outcome, Outcome::Fail);
+        assert_eq!(evaluated(&full), 4);
+        assert!(evaluated(&fast) <= 2, "{}", evaluated(&fast));
+
+        assert_eq!(full_pass.outcome, Outcome::Pass);
+        assert_eq!(fast_pass.outcome, Outcome::Pass);
+        assert_eq!(evaluated(&fast_pass), 4);
+    }
+}
@@ -0,0 +1,371 @@
+//! Firewall Rule Executor
+//!
+//! Validates firewall rulesets using record checks plus policy, exposure,
+//! and rule set fields.
+
+use common::results::Outcome;
+use execution_engine::execution::{
+    evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
+    record_validation::validate_record_checks,
+};
+use execution_engine::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+    FieldValidationResult, StateValidationResult, TestPhase,
+};
+use execution_engine::types::common::{Operation, ResolvedValue};
+use execution_engine::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+use crate::collectors::error_reporting::{
+    count_failing_objects, count_found_objects, not_found_details, CollectionErrorRecord,
+};
+
+/// Executor for firewall_rule validation
+pub struct FirewallRuleExecutor {
+    contract: CtnContract,
+}
+
+impl FirewallRuleExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+
+    /// Compare values for policy/exposure/count fields and rule set membership
+    fn compare_values(
+        &self,
+        expected: &ResolvedValue,
+        actual: &ResolvedValue,
+        operation: Operation,
+    ) -> bool {
+        match (expected, actual, operation) {
+            // String comparisons (backend, policies)
+            (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::Equals) => {
+                exp.eq_ignore_ascii_case(act)
+            }
+            (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::NotEqual) => {
+                !exp.eq_ignore_ascii_case(act)
+            }
+            // Boolean comparisons (allows_from_anywhere)
+            (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::Equals) => {
+                exp == act
+            }
+            (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::NotEqual) => {
+                exp != act
+            }
+            // Integer comparisons (rule_count)
+            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::Equals) => {
+                exp == act
+            }
+            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::NotEqual) => {
+                exp != act
+            }
+            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::GreaterThan) => {
+                act > exp
+            }
+            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::LessThan) => {
+                act < exp
+            }
+            (
+                ResolvedValue::Integer(exp),
+                ResolvedValue::Integer(act),
+                Operation::GreaterThanOrEqual,
+            ) => act >= exp,
+            (
+                ResolvedValue::Integer(exp),
+                ResolvedValue::Integer(act),
+                Operation::LessThanOrEqual,
+            ) => act <= exp,
+            // Set membership (rules)
+            (ResolvedValue::String(exp), ResolvedValue::Collection(rules), Operation::Contains) => {
+                self.rules_contain(rules, exp)
+            }
+            (
+                ResolvedValue::String(exp),
+                ResolvedValue::Collection(rules),
+                Operation::NotContains,
+            ) => !self.rules_contain(rules, exp),
+            _ => false,
+        }
+    }
+
+    /// Check whether any rule text contains the given substring
+    fn rules_contain(&self, rules: &[ResolvedValue], needle: &str) -> bool {
+        rules.iter().any(|rule| match rule {
+            ResolvedValue::String(r) => r.contains(needle),
+            _ => false,
+        })
+    }
+
+    /// Format a value for display in messages
+    fn format_value(&self, value: &ResolvedValue) -> String {
+        match value {
+            ResolvedValue::String(s) => format!("'{}'", s),
+            ResolvedValue::Collection(items) => format!("<{} rules>", items.len()),
+            ResolvedValue::RecordData(_) => "<ruleset>".to_string(),
+            other => format!("{:?}", other),
+        }
+    }
+}
+
+impl CtnExecutor for FirewallRuleExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let test_spec = &criterion.test;
+
+        // Phase 1: Existence check
+        let objects_expected = criterion.expected_object_count();
+        let collection_errors = CollectionErrorRecord::from_collected(&collected_data);
+        let objects_found = count_found_objects(&collected_data, &collection_errors);
+
+        let existence_passed =
+            evaluate_existence_check(test_spec.existence_check, objects_found, objects_expected);
+
+        if !existence_passed {
+            return Ok(CtnExecutionResult::fail(
+                criterion.criterion_type.clone(),
+                format!(
+                    "Existence check failed: expected {} objects, found {}{}",
+                    objects_expected,
+                    objects_found,
+                    not_found_details(&collection_errors)
+                ),
+            )
+            .with_collected_data(collected_data));
+        }
+
+        // Phase 2: State validation
+        let mut state_results = Vec::new();
+        let mut failure_messages = Vec::new();
+
+        for (object_id, data) in &collected_data {
+            // Objects that could not be collected are reported, not evaluated
+            if let Some(error) = CollectionErrorRecord::from_collected_data(data) {
+                // Missing objects were already handled by the existence check
+                if error.counts_as_found() {
+                    failure_messages.push(error.message());
+                    state_results.push(StateValidationResult {
+                        object_id: object_id.clone(),
+                        state_results: vec![error.field_result()],
+                        combined_result: false,
+                        state_operator: test_spec.state_operator,
+                        message: error.message(),
+                    });
+                }
+                continue;
+            }
+
+            let mut all_field_results = Vec::new();
+
+            // Validate each state
+            for state in &criterion.states {
+                // Handle record checks against the parsed ruleset
+                if !state.record_checks.is_empty() {
+                    let record_data = match data.get_field("ruleset") {
+                        Some(ResolvedValue::RecordData(rd)) => rd,
+                        _ => {
+                            let msg = "Ruleset field is not RecordData".to_string();
+                            all_field_results.push(FieldValidationResult {
+                                field_name: "record".to_string(),
+                                expected_value: ResolvedValue::String("RecordData".to_string()),
+                                actual_value: ResolvedValue::String("invalid".to_string()),
+                                operation: Operation::Equals,
+                                passed: false,
+                                message: msg.clone(),
+                            });
+                            failure_messages.push(format!("Firewall '{}': {}", object_id, msg));
+                            continue;
+                        }
+                    };
+
+                    // Validate record checks
+                    let validation_results =
+                        validate_record_checks(record_data, &state.record_checks).map_err(|e| {
+                            CtnExecutionError::ExecutionFailed {
+                                ctn_type: criterion.criterion_type.clone(),
+                                reason: format!("Record validation failed: {}", e),
+                            }
+                        })?;
+
+                    // Convert to FieldValidationResult format
+                    for result in &validation_results {
+                        all_field_results.push(FieldValidationResult {
+                            field_name: result.field_path.clone(),
+                            expected_value: ResolvedValue::String(
+                                result.expected.clone().unwrap_or_default(),
+                            ),
+                            actual_value: ResolvedValue::String(
+                                result.actual.clone().unwrap_or_default(),
+                            ),
+                            operation: Operation::Equals,
+                            passed: result.passed,
+                            message: result.message.clone(),
+                        });
+
+                        if !result.passed {
+                            failure_messages
+                                .push(format!("Firewall '{}': {}", object_id, result.message));
+                        }
+                    }
+                }
+
+                // Handle regular field checks
+                for field in &state.fields {
+                    let data_field_name = self
+                        .contract
+                        .field_mappings
+                        .validation_mappings
+                        .state_to_data
+                        .get(&field.name)
+                        .cloned()
+                        .unwrap_or_else(|| field.name.clone());
+
+                    // Skip record field - handled above
+                    if field.name == "record" {
+                        continue;
+                    }
+
+                    let actual_value = match data.get_field(&data_field_name) {
+                        Some(v) => v.clone(),
+                        None => {
+                            let msg = format!("Field '{}' not collected", field.name);
+                            all_field_results.push(FieldValidationResult {
+                                field_name: field.name.clone(),
+                                expected_value: field.value.clone(),
+                                actual_value: ResolvedValue::Boolean(false),
+                                operation: field.operation,
+                                passed: false,
+                                message: msg.clone(),
+                            });
+                            failure_messages.push(format!("Firewall '{}': {}", object_id, msg));
+                            continue;
+                        }
+                    };
+
+                    let passed = self.compare_values(&field.value, &actual_value, field.operation);
+
+                    let msg = if passed {
+                        format!("Field '{}' check passed", field.name)
+                    } else {
+                        format!(
+                            "Field '{}' check failed: expected {:?} {}, got {}",
+                            field.name,
+                            field.operation,
+                            self.format_value(&field.value),
+                            self.format_value(&actual_value)
+                        )
+                    };
+
+                    if !passed {
+                        failure_messages.push(format!("Firewall '{}': {}", object_id, msg));
+                    }
+
+                    all_field_results.push(FieldValidationResult {
+                        field_name: field.name.clone(),
+                        expected_value: field.value.clone(),
+                        actual_value,
+                        operation: field.operation,
+                        passed,
+                        message: msg,
+                    });
+                }
+            }
+
+            let state_bools: Vec<bool> = all_field_results.iter().map(|r| r.passed).collect();
+            let combined = evaluate_state_operator(test_spec.state_operator, &state_bools);
+
+            state_results.push(StateValidationResult {
+                object_id: object_id.clone(),
+                state_results: all_field_results,
+                combined_result: combined,
+                state_operator: test_spec.state_operator,
+                message: format!(
+                    "Firewall '{}': {}",
+                    object_id,
+                    if combined { "passed" } else { "failed" }
+                ),
+            });
+        }
+
+        // Phase 3: Item check
+        let objects_passing = state_results.iter().filter(|r| r.combined_result).count();
+        let item_passed =
+            evaluate_item_check(test_spec.item_check, objects_passing, state_results.len());
+
+        let (objects_failing, failing_from_errors) = count_failing_objects(&state_results);
+        let final_status = if existence_passed && item_passed {
+            Outcome::Pass
+        } else if failing_from_errors > 0 && failing_from_errors == objects_failing {
+            Outcome::Error
+        } else {
+            Outcome::Fail
+        };
+
+        let message = if final_status == Outcome::Pass {
+            format!(
+                "Firewall rule validation passed: {} of {} objects compliant",
+                objects_passing,
+                state_results.len()
+            )
+        } else if final_status == Outcome::Error {
+            format!(
+                "Firewall rule validation could not be completed:\n  - {}",
+                failure_messages.join("\n  - ")
+            )
+        } else {
+            format!(
+                "Firewall rule validation failed:\n  - {}",
+                failure_messages.join("\n  - ")
+            )
+        };
+
+        Ok(CtnExecutionResult {
+            ctn_type: criterion.criterion_type.clone(),
+            status: final_status,
+            test_phase: TestPhase::Complete,
+            existence_result: None,
+            state_results,
+            item_check_result: None,
+            message,
+            details: serde_json::json!({
+                "failures": failure_messages,
+                "collection_errors": collection_errors
+                    .iter()
+                    .map(CollectionErrorRecord::to_json)
+                    .collect::<Vec<_>>(),
+                "objects_passing": objects_passing,
+            }),
+            execution_metadata: Default::default(),
+            collected_data,
+        })
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "firewall_rule"
+    }
+
+    fn validate_collected_data(
+        &self,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        for data in collected_data.values() {
+            if CollectionErrorRecord::from_collected_data(data).is_some() {
+                continue;
+            }
+            if !data.has_field("ruleset") {
+                return Err(CtnExecutionError::MissingDataField {
+                    field: "ruleset".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,19 +1,32 @@
 //! JSON record executor
 //!
-//! Validates structured JSON data using record checks.
+//! Validates structured JSON data using record checks. The executor takes
+//! its CTN type and record field from its contract, so it also validates
+//! `structured_record` (TOML, INI and YAML files parsed into a record).
+//!
+//! `dry_validate_paths` resolves the field paths of record checks without
+//! evaluating them, so policy authors can see which paths name a value in
+//! the record and which do not (see `execution_api::explain_json_record`).
 
 use common::results::Outcome;
-use execution_engine::execution::{
-    evaluate_existence_check, evaluate_item_check, record_validation::validate_record_checks,
-};
+use execution_engine::execution::{evaluate_existence_check, evaluate_item_check};
 use execution_engine::strategies::{
     CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
     FieldValidationResult, StateValidationResult, TestPhase,
 };
-use execution_engine::types::common::{Operation, ResolvedValue};
-use execution_engine::types::execution_context::ExecutableCriterion;
+use execution_engine::types::common::{Operation, RecordData, ResolvedValue};
+use execution_engine::types::execution_context::{ExecutableCriterion, ExecutableRecordCheck};
+use serde_json::Value;
 use std::collections::HashMap;
 
+use crate::collectors::error_reporting::{
+    count_failing_objects, count_found_objects, not_found_details, CollectionErrorRecord,
+};
+use crate::executors::record_quantifier::{validate_quantified_record_checks, QuantifiedPath};
+
+/// Path segment matching every member of an object or element of an array
+const WILDCARD_SEGMENT: &str = "*";
+
 pub struct JsonRecordExecutor {
     contract: CtnContract,
 }
@@ -22,6 +35,175 @@ impl JsonRecordExecutor {
     pub fn new(contract: CtnContract) -> Self {
         Self { contract }
     }
+
+    /// Collected field holding the record: the field the `record` state
+    /// maps to (`json_data` for `json_record`)
+    fn record_field(&self) -> &str {
+        self.contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .get("record")
+            .map_or("json_data", String::as_str)
+    }
+}
+
+/// How the field path of one record check resolved
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathResolution {
+    /// Path as written in the check
+    pub field_path: String,
+    /// Whether the path names a value in the record
+    pub resolved: bool,
+    /// Value found; for `*`, `[*]` and filter paths, an array of the values
+    /// found
+    pub value: Option<Value>,
+    /// Where resolution stopped, when the path did not resolve
+    pub missing: Option<String>,
+}
+
+impl PathResolution {
+    fn found(field_path: &str, value: Value) -> Self {
+        Self {
+            field_path: field_path.to_string(),
+            resolved: true,
+            value: Some(value),
+            missing: None,
+        }
+    }
+
+    fn not_found(field_path: &str, missing: String) -> Self {
+        Self {
+            field_path: field_path.to_string(),
+            resolved: false,
+            value: None,
+            missing: Some(missing),
+        }
+    }
+}
+
+/// Resolve the field path of each check against `record`, in check order
+///
+/// Nothing is compared: a check whose path resolves may still fail. A
+/// `[*]` or filter path resolves when its array exists and every selected
+/// element has the rest of the path, as a missing field fails the element
+/// during validation; an array with no selected elements resolves to `[]`.
+pub fn dry_validate_paths(
+    record: &RecordData,
+    checks: &[ExecutableRecordCheck],
+) -> Vec<PathResolution> {
+    checks
+        .iter()
+        .map(|check| resolve_path(record.as_json(), &check.field_path))
+        .collect()
+}
+
+/// Resolve one field path against a JSON record
+fn resolve_path(record: &Value, field_path: &str) -> PathResolution {
+    let path = match QuantifiedPath::parse(field_path) {
+        None => {
+            return match walk(record, field_path) {
+                Ok(mut values) if !field_path.split('.').any(|s| s == WILDCARD_SEGMENT) => {
+                    PathResolution::found(field_path, values.pop().cloned().unwrap_or_default())
+                }
+                Ok(values) => PathResolution::found(
+                    field_path,
+                    Value::Array(values.into_iter().cloned().collect()),
+                ),
+                Err(stop) => PathResolution::not_found(field_path, stop.describe("record")),
+            }
+        }
+        Some(Err(e)) => return PathResolution::not_found(field_path, e.to_string()),
+        Some(Ok(path)) => path,
+    };
+
+    let elements = match walk(record, &path.array_path) {
+        Ok(values) => match values.as_slice() {
+            [Value::Array(elements)] => elements,
+            _ => {
+                return PathResolution::not_found(
+                    field_path,
+                    format!("'{}' is not an array", path.array_path),
+                )
+            }
+        },
+        Err(stop) => return PathResolution::not_found(field_path, stop.describe("record")),
+    };
+
+    let mut found = Vec::new();
+    for (index, element) in elements.iter().enumerate() {
+        if !path.filter.as_ref().is_none_or(|f| f.matches(element)) {
+            continue;
+        }
+        match walk(element, &path.element_path) {
+            Ok(values) => found.extend(values.into_iter().cloned()),
+            Err(stop) => {
+                let element = format!("element {} of '{}'", index, path.array_path);
+                return PathResolution::not_found(field_path, stop.describe(&element));
+            }
+        }
+    }
+    PathResolution::found(field_path, Value::Array(found))
+}
+
+/// Where a path stopped resolving
+struct PathStop {
+    /// Part of the path that resolved
+    walked: String,
+    /// Segment with no value
+    segment: String,
+}
+
+impl PathStop {
+    /// Describe the stop, naming the value the path started from `root`
+    fn describe(&self, root: &str) -> String {
+        if self.walked.is_empty() {
+            format!("{} has no '{}'", root, self.segment)
+        } else {
+            format!("{}: '{}' has no '{}'", root, self.walked, self.segment)
+        }
+    }
+}
+
+/// Values at a dotted path; numeric segments index arrays and `*` matches
+/// every member or element
+fn walk<'a>(record: &'a Value, path: &str) -> Result<Vec<&'a Value>, PathStop> {
+    let mut current = vec![record];
+    let mut walked = String::new();
+    for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+        let next: Vec<&Value> = current
+            .iter()
+            .flat_map(|value| step(value, segment))
+            .collect();
+        if next.is_empty() {
+            return Err(PathStop {
+                walked,
+                segment: segment.to_string(),
+            });
+        }
+        if !walked.is_empty() {
+            walked.push('.');
+        }
+        walked.push_str(segment);
+        current = next;
+    }
+    Ok(current)
+}
+
+/// Values one segment below `value`
+fn step<'a>(value: &'a Value, segment: &str) -> Vec<&'a Value> {
+    match value {
+        Value::Object(map) if segment == WILDCARD_SEGMENT => map.values().collect(),
+        Value::Array(items) if segment == WILDCARD_SEGMENT => items.iter().collect(),
+        Value::Object(map) => map.get(segment).into_iter().collect(),
+        Value::Array(items) => segment
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| items.get(i))
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    }
 }
 
 impl CtnExecutor for JsonRecordExecutor {
@@ -35,7 +217,8 @@ impl CtnExecutor for JsonRecordExecutor {
 
         // Phase 1: Existence check
         let objects_expected = criterion.expected_object_count();
-        let objects_found = collected_data.len();
+        let collection_errors = CollectionErrorRecord::from_collected(&collected_data);
+        let objects_found = count_found_objects(&collected_data, &collection_errors);
 
         let existence_passed =
             evaluate_existence_check(test_spec.existence_check, objects_found, objects_expected);
@@ -44,8 +227,10 @@ impl CtnExecutor for JsonRecordExecutor {
             return Ok(CtnExecutionResult::fail(
                 criterion.criterion_type.clone(),
                 format!(
-                    "Existence check failed: expected {} objects, found {}",
-                    objects_expected, objects_found
+                    "Existence check failed: expected {} objects, found {}{}",
+                    objects_expected,
+                    objects_found,
+                    not_found_details(&collection_errors)
                 ),
             )
             .with_collected_data(collected_data));
@@ -56,17 +241,33 @@ impl CtnExecutor for JsonRecordExecutor {
         let mut failure_messages = Vec::new();
 
         for (object_id, data) in &collected_data {
+            // Objects that could not be collected are reported, not evaluated
+            if let Some(error) = CollectionErrorRecord::from_collected_data(data) {
+                // Missing objects were already handled by the existence check
+                if error.counts_as_found() {
+                    failure_messages.push(error.message());
+                    state_results.push(StateValidationResult {
+                        object_id: object_id.clone(),
+                        state_results: vec![error.field_result()],
+                        combined_result: false,
+                        state_operator: test_spec.state_operator,
+                        message: error.message(),
+                    });
+                }
+                continue;
+            }
+
             // Extract RecordData from collected data
-            let record_data = match data.get_field("json_data") {
+            let record_data = match data.get_field(self.record_field()) {
                 Some(ResolvedValue::RecordData(rd)) => rd,
                 Some(_) => {
                     return Err(CtnExecutionError::DataValidationFailed {
-                        reason: "json_data field is not RecordData".to_string(),
+                        reason: format!("{} field is not RecordData", self.record_field()),
                     });
                 }
                 None => {
                     return Err(CtnExecutionError::MissingDataField {
-                        field: "json_data".to_string(),
+                        field: self.record_field().to_string(),
                     });
                 }
             };
@@ -75,14 +276,13 @@ impl CtnExecutor for JsonRecordExecutor {
             for state in &criterion.states {
                 // Validate record checks if present
                 if !state.record_checks.is_empty() {
-                    // FIXED: Use validate_record_checks from base library
+                    // Engine record validation, plus `[*]` array quantifiers
                     let validation_results =
-                        validate_record_checks(record_data, &state.record_checks).map_err(|e| {
-                            CtnExecutionError::ExecutionFailed {
+                        validate_quantified_record_checks(record_data, &state.record_checks)
+                            .map_err(|e| CtnExecutionError::ExecutionFailed {
                                 ctn_type: criterion.criterion_type.clone(),
                                 reason: format!("Record validation failed: {}", e),
-                            }
-                        })?;
+                            })?;
 
                     // Convert to FieldValidationResult format
                     let field_results: Vec<FieldValidationResult> = validation_results
@@ -135,8 +335,11 @@ impl CtnExecutor for JsonRecordExecutor {
         let item_passed =
             evaluate_item_check(test_spec.item_check, objects_passing, state_results.len());
 
+        let (objects_failing, failing_from_errors) = count_failing_objects(&state_results);
         let final_status = if existence_passed && item_passed {
             Outcome::Pass
+        } else if failing_from_errors > 0 && failing_from_errors == objects_failing {
+            Outcome::Error
         } else {
             Outcome::Fail
         };
@@ -147,6 +350,11 @@ impl CtnExecutor for JsonRecordExecutor {
                 objects_passing,
                 state_results.len()
             )
+        } else if final_status == Outcome::Error {
+            format!(
+                "JSON record validation could not be completed:\n  - {}",
+                failure_messages.join("\n  - ")
+            )
         } else {
             format!(
                 "JSON record validation failed:\n  - {}",
@@ -164,6 +372,10 @@ impl CtnExecutor for JsonRecordExecutor {
             message,
             details: serde_json::json!({
                 "failures": failure_messages,
+                "collection_errors": collection_errors
+                    .iter()
+                    .map(CollectionErrorRecord::to_json)
+                    .collect::<Vec<_>>(),
                 "objects_passing": objects_passing,
             }),
             execution_metadata: Default::default(),
@@ -176,7 +388,7 @@ impl CtnExecutor for JsonRecordExecutor {
     }
 
     fn ctn_type(&self) -> &str {
-        "json_record"
+        &self.contract.ctn_type
     }
 
     fn validate_collected_data(
@@ -185,12 +397,91 @@ impl CtnExecutor for JsonRecordExecutor {
         _contract: &CtnContract,
     ) -> Result<(), CtnExecutionError> {
         for data in collected_data.values() {
-            if !data.has_field("json_data") {
+            if CollectionErrorRecord::from_collected_data(data).is_some() {
+                continue;
+            }
+            if !data.has_field(self.record_field()) {
                 return Err(CtnExecutionError::MissingDataField {
-                    field: "json_data".to_string(),
+                    field: self.record_field().to_string(),
                 });
             }
         }
         Ok(())
     }
 }
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pod() -> Value {
+        json!({
+            "metadata": { "name": "web", "labels": { "app": "web", "tier": "front" } },
+            "spec": {
+                "containers": [
+                    { "name": "app", "image": "nginx:1.27", "ports": [80] },
+                    { "name": "sidecar", "image": "envoy:1.31" }
+                ]
+            }
+        })
+    }
+
+    #[test]
+    fn test_plain_paths() {
+        let resolved = resolve_path(&pod(), "spec.containers.1.image");
+        assert!(resolved.resolved);
+        assert_eq!(resolved.value, Some(json!("envoy:1.31")));
+
+        let missing = resolve_path(&pod(), "spec.containers.0.securityContext.runAsUser");
+        assert!(!missing.resolved);
+        assert_eq!(missing.value, None);
+        assert_eq!(
+            missing.missing.as_deref(),
+            Some("record: 'spec.containers.0' has no 'securityContext'")
+        );
+        assert_eq!(
+            resolve_path(&pod(), "status").missing.as_deref(),
+            Some("record has no 'status'")
+        );
+    }
+
+    #[test]
+    fn test_wildcard_segment_collects_values() {
+        let resolved = resolve_path(&pod(), "metadata.labels.*");
+        assert!(resolved.resolved);
+        assert_eq!(resolved.value, Some(json!(["web", "front"])));
+    }
+
+    #[test]
+    fn test_quantified_paths() {
+        let images = resolve_path(&pod(), "spec.containers[*].image");
+        assert!(images.resolved);
+        assert_eq!(images.value, Some(json!(["nginx:1.27", "envoy:1.31"])));
+
+        // The sidecar has no ports, so the check would fail it
+        let ports = resolve_path(&pod(), "spec.containers[*].ports");
+        assert!(!ports.resolved);
+        assert_eq!(
+            ports.missing.as_deref(),
+            Some("element 1 of 'spec.containers' has no 'ports'")
+        );
+
+        let filtered = resolve_path(&pod(), "spec.containers[?(@.name==app)].ports");
+        assert!(filtered.resolved);
+        assert_eq!(filtered.value, Some(json!([[80]])));
+
+        let none_selected = resolve_path(&pod(), "spec.containers[?(@.name==db)].image");
+        assert!(none_selected.resolved);
+        assert_eq!(none_selected.value, Some(json!([])));
+
+        assert_eq!(
+            resolve_path(&pod(), "metadata[*].name").missing.as_deref(),
+            Some("'metadata' is not an array")
+        );
+        assert!(!resolve_path(&pod(), "spec.containers[?(@.name)].image").resolved);
+    }
+}
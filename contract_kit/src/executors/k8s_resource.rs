@@ -5,7 +5,6 @@
 use common::results::Outcome;
 use execution_engine::execution::{
     evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
-    record_validation::validate_record_checks,
 };
 use execution_engine::strategies::{
     CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
@@ -15,6 +14,11 @@ use execution_engine::types::common::{Operation, ResolvedValue};
 use execution_engine::types::execution_context::ExecutableCriterion;
 use std::collections::HashMap;
 
+use crate::collectors::error_reporting::{
+    count_failing_objects, count_found_objects, not_found_details, CollectionErrorRecord,
+};
+use crate::executors::record_quantifier::validate_quantified_record_checks;
+
 /// Executor for k8s_resource validation
 pub struct K8sResourceExecutor {
     contract: CtnContract,
@@ -24,50 +28,67 @@ impl K8sResourceExecutor {
     pub fn new(contract: CtnContract) -> Self {
         Self { contract }
     }
+}
 
-    /// Compare values for found/count fields
-    fn compare_values(
-        &self,
-        expected: &ResolvedValue,
-        actual: &ResolvedValue,
-        operation: Operation,
-    ) -> bool {
-        match (expected, actual, operation) {
-            // Boolean comparisons (found)
-            (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::Equals) => {
-                exp == act
-            }
-            (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::NotEqual) => {
-                exp != act
-            }
-            // Integer comparisons (count)
-            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::Equals) => {
-                exp == act
-            }
-            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::NotEqual) => {
-                exp != act
-            }
-            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::GreaterThan) => {
-                act > exp
-            }
-            (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::LessThan) => {
-                act < exp
-            }
-            (
-                ResolvedValue::Integer(exp),
-                ResolvedValue::Integer(act),
-                Operation::GreaterThanOrEqual,
-            ) => act >= exp,
-            (
-                ResolvedValue::Integer(exp),
-                ResolvedValue::Integer(act),
-                Operation::LessThanOrEqual,
-            ) => act <= exp,
-            _ => false,
+/// Compare boolean (found) and integer (count) fields
+///
+/// Shared with `process`, whose `running` and `count` fields have the same
+/// semantics. A collection of expected values is a set of allowed counts:
+/// `=` passes if the count is one of them and `!=` if it is none of them,
+/// so an empty set fails `=` and passes `!=`.
+pub(crate) fn compare_found_count(
+    expected: &ResolvedValue,
+    actual: &ResolvedValue,
+    operation: Operation,
+) -> bool {
+    match (expected, actual, operation) {
+        // Boolean comparisons (found)
+        (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::Equals) => exp == act,
+        (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::NotEqual) => {
+            exp != act
+        }
+        // Integer comparisons (count)
+        (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::Equals) => exp == act,
+        (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::NotEqual) => {
+            exp != act
+        }
+        (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::GreaterThan) => {
+            act > exp
+        }
+        (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::LessThan) => {
+            act < exp
+        }
+        (
+            ResolvedValue::Integer(exp),
+            ResolvedValue::Integer(act),
+            Operation::GreaterThanOrEqual,
+        ) => act >= exp,
+        (ResolvedValue::Integer(exp), ResolvedValue::Integer(act), Operation::LessThanOrEqual) => {
+            act <= exp
         }
+        // Allowed counts
+        (ResolvedValue::Collection(allowed), ResolvedValue::Integer(act), Operation::Equals) => {
+            is_allowed_count(allowed, *act)
+        }
+        (ResolvedValue::Collection(allowed), ResolvedValue::Integer(act), Operation::NotEqual) => {
+            !is_allowed_count(allowed, *act)
+        }
+        _ => false,
     }
 }
 
+/// Whether `count` is one of the allowed values
+///
+/// Members may be integers or integer strings, as a `SPLIT` RUN operation
+/// produces; other members never match.
+fn is_allowed_count(allowed: &[ResolvedValue], count: i64) -> bool {
+    allowed.iter().any(|value| match value {
+        ResolvedValue::Integer(v) => *v == count,
+        ResolvedValue::String(v) => v.trim().parse::<i64>() == Ok(count),
+        _ => false,
+    })
+}
+
 impl CtnExecutor for K8sResourceExecutor {
     fn execute_with_contract(
         &self,
@@ -79,7 +100,8 @@ impl CtnExecutor for K8sResourceExecutor {
 
         // Phase 1: Existence check
         let objects_expected = criterion.expected_object_count();
-        let objects_found = collected_data.len();
+        let collection_errors = CollectionErrorRecord::from_collected(&collected_data);
+        let objects_found = count_found_objects(&collected_data, &collection_errors);
 
         let existence_passed =
             evaluate_existence_check(test_spec.existence_check, objects_found, objects_expected);
@@ -88,8 +110,10 @@ impl CtnExecutor for K8sResourceExecutor {
             return Ok(CtnExecutionResult::fail(
                 criterion.criterion_type.clone(),
                 format!(
-                    "Existence check failed: expected {} resources, found {}",
-                    objects_expected, objects_found
+                    "Existence check failed: expected {} resources, found {}{}",
+                    objects_expected,
+                    objects_found,
+                    not_found_details(&collection_errors)
                 ),
             )
             .with_collected_data(collected_data));
@@ -100,6 +124,22 @@ impl CtnExecutor for K8sResourceExecutor {
         let mut failure_messages = Vec::new();
 
         for (object_id, data) in &collected_data {
+            // Objects that could not be collected are reported, not evaluated
+            if let Some(error) = CollectionErrorRecord::from_collected_data(data) {
+                // Missing objects were already handled by the existence check
+                if error.counts_as_found() {
+                    failure_messages.push(error.message());
+                    state_results.push(StateValidationResult {
+                        object_id: object_id.clone(),
+                        state_results: vec![error.field_result()],
+                        combined_result: false,
+                        state_operator: test_spec.state_operator,
+                        message: error.message(),
+                    });
+                }
+                continue;
+            }
+
             let mut all_field_results = Vec::new();
 
             // Check if resource was found
@@ -147,14 +187,13 @@ impl CtnExecutor for K8sResourceExecutor {
                         }
                     };
 
-                    // Validate record checks
+                    // Validate record checks, including `[*]` array quantifiers
                     let validation_results =
-                        validate_record_checks(record_data, &state.record_checks).map_err(|e| {
-                            CtnExecutionError::ExecutionFailed {
+                        validate_quantified_record_checks(record_data, &state.record_checks)
+                            .map_err(|e| CtnExecutionError::ExecutionFailed {
                                 ctn_type: criterion.criterion_type.clone(),
                                 reason: format!("Record validation failed: {}", e),
-                            }
-                        })?;
+                            })?;
 
                     // Convert to FieldValidationResult format
                     for result in &validation_results {
@@ -211,7 +250,7 @@ impl CtnExecutor for K8sResourceExecutor {
                         }
                     };
 
-                    let passed = self.compare_values(&field.value, &actual_value, field.operation);
+                    let passed = compare_found_count(&field.value, &actual_value, field.operation);
 
                     let msg = if passed {
                         format!("Field '{}' check passed", field.name)
@@ -258,8 +297,11 @@ impl CtnExecutor for K8sResourceExecutor {
         let item_passed =
             evaluate_item_check(test_spec.item_check, objects_passing, state_results.len());
 
+        let (objects_failing, failing_from_errors) = count_failing_objects(&state_results);
         let final_status = if existence_passed && item_passed {
             Outcome::Pass
+        } else if failing_from_errors > 0 && failing_from_errors == objects_failing {
+            Outcome::Error
         } else {
             Outcome::Fail
         };
@@ -270,6 +312,11 @@ impl CtnExecutor for K8sResourceExecutor {
                 objects_passing,
                 state_results.len()
             )
+        } else if final_status == Outcome::Error {
+            format!(
+                "Kubernetes resource validation could not be completed:\n  - {}",
+                failure_messages.join("\n  - ")
+            )
         } else {
             format!(
                 "Kubernetes resource validation failed:\n  - {}",
@@ -287,6 +334,10 @@ impl CtnExecutor for K8sResourceExecutor {
             message,
             details: serde_json::json!({
                 "failures": failure_messages,
+                "collection_errors": collection_errors
+                    .iter()
+                    .map(CollectionErrorRecord::to_json)
+                    .collect::<Vec<_>>(),
                 "objects_passing": objects_passing,
             }),
             execution_metadata: Default::default(),
@@ -308,6 +359,9 @@ impl CtnExecutor for K8sResourceExecutor {
         _contract: &CtnContract,
     ) -> Result<(), CtnExecutionError> {
         for data in collected_data.values() {
+            if CollectionErrorRecord::from_collected_data(data).is_some() {
+                continue;
+            }
             if !data.has_field("found") {
                 return Err(CtnExecutionError::MissingDataField {
                     field: "found".to_string(),
@@ -317,3 +371,78 @@ impl CtnExecutor for K8sResourceExecutor {
         Ok(())
     }
 }
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(values: &[i64]) -> ResolvedValue {
+        ResolvedValue::Collection(values.iter().map(|v| ResolvedValue::Integer(*v)).collect())
+    }
+
+    #[test]
+    fn test_count_in_allowed_set() {
+        let replicas = ResolvedValue::Integer;
+        let allowed = counts(&[2, 3, 5]);
+
+        assert!(compare_found_count(
+            &allowed,
+            &replicas(3),
+            Operation::Equals
+        ));
+        assert!(!compare_found_count(
+            &allowed,
+            &replicas(4),
+            Operation::Equals
+        ));
+        assert!(compare_found_count(
+            &allowed,
+            &replicas(4),
+            Operation::NotEqual
+        ));
+        assert!(!compare_found_count(
+            &allowed,
+            &replicas(5),
+            Operation::NotEqual
+        ));
+
+        // SPLIT produces strings
+        let split = ResolvedValue::Collection(vec![
+            ResolvedValue::String("1".to_string()),
+            ResolvedValue::String(" 3".to_string()),
+            ResolvedValue::String("many".to_string()),
+        ]);
+        assert!(compare_found_count(&split, &replicas(3), Operation::Equals));
+        assert!(!compare_found_count(
+            &split,
+            &replicas(2),
+            Operation::Equals
+        ));
+
+        // Sets are not ordered, so only = and != apply
+        assert!(!compare_found_count(
+            &allowed,
+            &replicas(9),
+            Operation::GreaterThan
+        ));
+        assert!(!compare_found_count(
+            &allowed,
+            &ResolvedValue::Boolean(true),
+            Operation::Equals
+        ));
+    }
+
+    #[test]
+    fn test_empty_allowed_set() {
+        let empty = counts(&[]);
+        let zero = ResolvedValue::Integer(0);
+
+        // Nothing is in an empty set
+        assert!(!compare_found_count(&empty, &zero, Operation::Equals));
+        assert!(compare_found_count(&empty, &zero, Operation::NotEqual));
+    }
+}
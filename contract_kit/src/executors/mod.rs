@@ -1,24 +1,100 @@
 //! # Executors Module
 //!
 //! Executors validate collected data against state requirements:
+//! - CronExecutor: Scheduled job validation (exists, count, schedule)
+//! - DpkgPackageExecutor: Debian package installation and version checks
+//! - EnvVarExecutor: Environment variable validation (exists, value)
 //! - FileMetadataExecutor: File permissions, ownership, size validation
 //! - FileContentExecutor: Content string operations (contains, starts, ends, pattern)
-//! - JsonRecordExecutor: Structured JSON field validation
+//! - FileHashExecutor: File SHA-256 and size validation
+//! - JsonRecordExecutor: Structured JSON field validation (also for
+//!   `structured_record` TOML, INI and YAML files)
+//! - KernelModuleExecutor: Kernel module validation (loaded, blacklisted, loadable)
+//! - MountExecutor: Mount point validation (mounted, fstype, device, options)
+//! - ProcessExecutor: Running process validation (running, count, pids)
+//! - RegistryExecutor: Windows registry value validation
 //! - RpmPackageExecutor: Package installation and version checks
 //! - SelinuxStatusExecutor: SELinux enforcement mode validation
 //! - SysctlParameterExecutor: Kernel parameter validation
 //! - SystemdServiceExecutor: Service status validation
+//! - UserAccountExecutor: Local account validation (exists, uid, shell, home)
+//!
+//! `not_applicable` marks a criterion that cannot apply to the host (see
+//! `applicability`).
+//! `RecordingExecutor` wraps any executor to record criterion outcomes for
+//! criteria group results. `EvidenceLevel::Outcome` lets executors with
+//! `all` item checks stop at the first failing object (see `short_circuit`).
+//! `ContentBatch` groups `file_content` checks on the same path across
+//! criteria (see `content_batch`). `TimingExecutor` records how long each
+//! criterion took to collect and evaluate (native only). `TracingExecutor`
+//! records each criterion's decision trace, for explaining one failing
+//! check.
+//! `record_quantifier` adds `[*]` and `[?(...)]` filter quantifiers to
+//! record check paths for `json_record` and `k8s_resource`.
+//! `pattern_extract` lets `file_content` compare a regex capture through
+//! `content_capture`. `dry_validate_paths` resolves record check paths
+//! without evaluating them, for debugging `json_record` policies.
 
+pub mod applicability;
+pub mod audit_rule;
+pub mod byte_size;
 pub mod computed_values;
+pub mod content_batch;
+pub mod cron;
+pub mod dpkg_package;
+pub mod env_var;
 pub mod file_content;
+pub mod file_hash;
 pub mod file_metadata;
+pub mod firewall_rule;
 pub mod json_record;
 pub mod k8s_resource;
+pub mod kernel_module;
+pub mod mount;
+pub mod pattern_extract;
+pub mod process;
+pub mod record_quantifier;
+pub mod recording;
+pub mod registry_value;
+pub mod short_circuit;
 pub mod tcp_listener;
+#[cfg(feature = "native")]
+pub mod timing;
+pub mod trace;
+pub mod user_account;
 
+pub use applicability::{
+    is_not_applicable, is_not_applicable_data, not_applicable, not_applicable_data,
+    not_applicable_reason,
+};
+pub use audit_rule::AuditRuleExecutor;
+pub use byte_size::{parse_byte_size, ByteSizeError};
 pub use computed_values::ComputedValuesExecutor;
+pub use content_batch::{ContentBatch, ContentCheck};
+pub use cron::CronExecutor;
+pub use dpkg_package::DpkgPackageExecutor;
+pub use env_var::EnvVarExecutor;
 pub use file_content::FileContentExecutor;
+pub use file_hash::FileHashExecutor;
 pub use file_metadata::FileMetadataExecutor;
-pub use json_record::JsonRecordExecutor;
+pub use firewall_rule::FirewallRuleExecutor;
+pub use json_record::{dry_validate_paths, JsonRecordExecutor, PathResolution};
 pub use k8s_resource::K8sResourceExecutor;
+pub use kernel_module::KernelModuleExecutor;
+pub use mount::MountExecutor;
+pub use pattern_extract::{first_capture, PatternExtractError, CAPTURE_FIELD};
+pub use process::ProcessExecutor;
+pub use record_quantifier::{
+    validate_quantified_record_checks, Quantifier, RecordCheckResult, RecordQuantifierError,
+};
+pub use recording::{FieldFailure, OutcomeRecorder, RecordedOutcome, RecordingExecutor};
+pub use registry_value::RegistryExecutor;
+pub use short_circuit::{EvidenceLevel, ShortCircuit};
 pub use tcp_listener::TcpListenerExecutor;
+#[cfg(feature = "native")]
+pub use timing::{timing_breakdown, CriterionTimer, CriterionTiming, TimingExecutor};
+pub use trace::{
+    explain_criterion, format_trace, CheckTrace, CriterionExplanation, FieldTrace, ObjectTrace,
+    StateTrace, TraceRecorder, TracingExecutor,
+};
+pub use user_account::UserAccountExecutor;
@@ -0,0 +1,209 @@
+//! Pattern Capture Extraction
+//!
+//! A `content` `pattern_match` whose pattern has a capture group also
+//! extracts the group's text from the file content. A later
+//! `content_capture` state field compares that text, converted to the
+//! field's declared type, so a policy can match a config line and then
+//! check the value in it:
+//!
+//! ```text
+//! content string pattern_match `^MaxAuthTries\s+(?P<tries>\d+)`
+//! content_capture int <= `4`
+//! ```
+//!
+//! The first named group is used if the pattern has one, otherwise group 1.
+//! Patterns are compiled in multi-line mode, so `^` and `$` match at line
+//! boundaries.
+//!
+//! A `version` or `evr_string` field compares the capture with the engine's
+//! version rules, so `1.10.0 > 1.9.2` and `2:1.8.0-1.el9 > 1:2.0.0-1.el9`:
+//!
+//! ```text
+//! content string pattern_match `^Version=(\S+)`
+//! content_capture version >= `1.2.3`
+//! ```
+
+use execution_engine::execution::comparisons::{evr, version};
+use execution_engine::types::common::{Operation, ResolvedValue};
+use regex::RegexBuilder;
+
+use super::file_metadata::compare_values;
+
+/// Synthetic state field holding the extracted capture
+pub const CAPTURE_FIELD: &str = "content_capture";
+
+/// Error extracting a capture from content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternExtractError {
+    /// Pattern is not a valid regular expression
+    InvalidPattern(String),
+
+    /// Pattern has no capture group to extract
+    NoCaptureGroup(String),
+}
+
+impl std::fmt::Display for PatternExtractError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPattern(e) => write!(f, "Invalid pattern: {}", e),
+            Self::NoCaptureGroup(pattern) => {
+                write!(f, "Pattern '{}' has no capture group", pattern)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatternExtractError {}
+
+/// Text of the first capture of `pattern` in `content`
+///
+/// Returns `Ok(None)` when the pattern does not match or the group did not
+/// take part in the match.
+pub fn first_capture(content: &str, pattern: &str) -> Result<Option<String>, PatternExtractError> {
+    let regex = RegexBuilder::new(pattern)
+        .multi_line(true)
+        .build()
+        .map_err(|e| PatternExtractError::InvalidPattern(e.to_string()))?;
+
+    if regex.captures_len() < 2 {
+        return Err(PatternExtractError::NoCaptureGroup(pattern.to_string()));
+    }
+    let group = regex
+        .capture_names()
+        .position(|name| name.is_some())
+        .unwrap_or(1);
+
+    Ok(regex
+        .captures(content)
+        .and_then(|captures| captures.get(group))
+        .map(|m| m.as_str().to_string()))
+}
+
+/// Convert a capture to the type of the expected value
+///
+/// Integers and booleans are parsed (surrounding whitespace ignored); any
+/// other string comparison uses the capture as-is. Versions stay strings,
+/// trimmed, and must start with a digit.
+pub fn typed_capture(capture: &str, expected: &ResolvedValue) -> Result<ResolvedValue, String> {
+    match expected {
+        ResolvedValue::Version(_) | ResolvedValue::EvrString(_) => {
+            let trimmed = capture.trim();
+            if trimmed.starts_with(|c: char| c.is_ascii_digit())
+                && !trimmed.contains(char::is_whitespace)
+            {
+                Ok(ResolvedValue::String(trimmed.to_string()))
+            } else {
+                Err(format!("Captured '{}' is not a version", capture))
+            }
+        }
+        ResolvedValue::String(_) => Ok(ResolvedValue::String(capture.to_string())),
+        ResolvedValue::Integer(_) => capture
+            .trim()
+            .parse()
+            .map(ResolvedValue::Integer)
+            .map_err(|_| format!("Captured '{}' is not an integer", capture)),
+        ResolvedValue::Boolean(_) => capture
+            .trim()
+            .to_ascii_lowercase()
+            .parse()
+            .map(ResolvedValue::Boolean)
+            .map_err(|_| format!("Captured '{}' is not a boolean", capture)),
+        other => Err(format!(
+            "Cannot compare a capture with {:?}; use a string, int, boolean, version or evr_string field",
+            other
+        )),
+    }
+}
+
+/// Compare a typed capture against the expected value
+///
+/// Versions use the engine's version and EVR comparators; a version either
+/// side cannot parse is an error, so the check fails with its message.
+pub fn compare_capture(
+    expected: &ResolvedValue,
+    actual: &ResolvedValue,
+    operation: Operation,
+) -> Result<bool, String> {
+    match (expected, actual) {
+        (ResolvedValue::Version(exp), ResolvedValue::String(act)) => {
+            version::compare(act, &exp.to_string(), operation)
+                .map_err(|_| format!("Cannot compare version '{}' {:?} '{}'", act, operation, exp))
+        }
+        (ResolvedValue::EvrString(exp), ResolvedValue::String(act)) => {
+            evr::compare(act, &exp.to_string(), operation)
+                .map_err(|_| format!("Cannot compare EVR '{}' {:?} '{}'", act, operation, exp))
+        }
+        _ => Ok(compare_values(expected, actual, operation)),
+    }
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "# sshd\nPort 22\nMaxAuthTries 6\nPermitRootLogin no\n";
+
+    #[test]
+    fn test_first_capture_multiline() {
+        assert_eq!(
+            first_capture(CONFIG, r"^MaxAuthTries\s+(\d+)$").unwrap(),
+            Some("6".to_string())
+        );
+        // The named group wins over an earlier unnamed one
+        assert_eq!(
+            first_capture(CONFIG, r"^(Port) (?P<port>\d+)").unwrap(),
+            Some("22".to_string())
+        );
+    }
+
+    #[test]
+    fn test_first_capture_no_match_or_group() {
+        assert_eq!(first_capture(CONFIG, r"^Banner (\S+)").unwrap(), None);
+        assert!(matches!(
+            first_capture(CONFIG, r"^Port \d+"),
+            Err(PatternExtractError::NoCaptureGroup(_))
+        ));
+        assert!(matches!(
+            first_capture(CONFIG, r"(unclosed"),
+            Err(PatternExtractError::InvalidPattern(_))
+        ));
+    }
+
+    #[test]
+    fn test_typed_capture() {
+        assert_eq!(
+            typed_capture(" 6 ", &ResolvedValue::Integer(4)).unwrap(),
+            ResolvedValue::Integer(6)
+        );
+        assert_eq!(
+            typed_capture("Yes", &ResolvedValue::String(String::new())).unwrap(),
+            ResolvedValue::String("Yes".to_string())
+        );
+        assert!(typed_capture("six", &ResolvedValue::Integer(4)).is_err());
+    }
+
+    #[test]
+    fn test_version_capture() {
+        let expected = ResolvedValue::Version("1.2.3".to_string());
+        let actual = typed_capture(" 1.10.0\n", &expected).unwrap();
+        assert_eq!(actual, ResolvedValue::String("1.10.0".to_string()));
+
+        assert!(compare_capture(&expected, &actual, Operation::GreaterThanOrEqual).unwrap());
+        assert!(!compare_capture(&expected, &actual, Operation::LessThan).unwrap());
+
+        let older = typed_capture("1.2.0", &expected).unwrap();
+        assert!(!compare_capture(&expected, &older, Operation::GreaterThanOrEqual).unwrap());
+    }
+
+    #[test]
+    fn test_unparseable_version_capture() {
+        let expected = ResolvedValue::Version("1.2.3".to_string());
+        let err = typed_capture("unknown", &expected).unwrap_err();
+        assert!(err.contains("'unknown' is not a version"), "{}", err);
+        assert!(typed_capture("1.2 beta", &expected).is_err());
+        assert!(typed_capture("", &ResolvedValue::EvrString("0:1.0-1".to_string())).is_err());
+    }
+}
@@ -0,0 +1,663 @@
+//! Array Quantifiers in Record Checks
+//!
+//! A record check path may quantify over the elements of an array with
+//! `[*]`, e.g. `spec.containers[*].resources.limits.memory`. The part
+//! after `[*]` is checked on each element of `spec.containers`, and the
+//! check's entity check decides how element results combine:
+//!
+//! | Entity check | Passes when |
+//! |--------------|-------------|
+//! | `all` (default) | Every element passes; an empty array passes |
+//! | `at_least_one` | At least one element passes |
+//! | `none` | No element passes |
+//! | `only_one` | Exactly one element passes |
+//!
+//! Elements are compared by the engine's record validation, so operations
+//! and types behave as in any other record check; a missing field fails
+//! the element. Failures name the offending indices (and the element's
+//! `name`, if it has one), e.g. `failed at index 1 (sidecar)`.
+//!
+//! A filter segment `[?(@.field==value)]` in place of `[*]` quantifies
+//! over only the elements whose `field` equals `value` (or differs from it,
+//! with `!=`), e.g. `spec.containers[?(@.name==app)].image`. Indices in
+//! messages are those of the whole array. An array with no matching
+//! elements behaves like an empty one.
+//!
+//! Paths without `[*]` or a filter, including the dotted `a.*.b` wildcard,
+//! are passed to the engine unchanged. One quantifier per path is supported.
+
+use execution_engine::execution::record_validation::validate_record_checks;
+use execution_engine::types::common::{EntityCheck, RecordData};
+use execution_engine::types::execution_context::ExecutableRecordCheck;
+use serde_json::{json, Value};
+
+/// Array marker in a record check path
+pub const ARRAY_QUANTIFIER: &str = "[*]";
+
+/// Start of a filter segment in a record check path
+pub const FILTER_START: &str = "[?(";
+
+/// End of a filter segment
+const FILTER_END: &str = ")]";
+
+/// Key an element is wrapped under for element-level validation
+const ELEMENT_KEY: &str = "element";
+
+/// Most failing indices listed in a message
+const MAX_LISTED_INDICES: usize = 10;
+
+/// Result of one record check
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordCheckResult {
+    pub field_path: String,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub passed: bool,
+    pub message: String,
+}
+
+/// Error validating record checks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordQuantifierError {
+    /// A path with more than one `[*]` or filter
+    NestedQuantifier(String),
+    /// A filter segment that is not `[?(@.field==value)]` or `!=`
+    InvalidFilter(String),
+    /// The engine could not validate a check
+    Validation(String),
+}
+
+impl std::fmt::Display for RecordQuantifierError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NestedQuantifier(path) => write!(
+                f,
+                "'{}' has more than one '{}' or filter quantifier",
+                path, ARRAY_QUANTIFIER
+            ),
+            Self::InvalidFilter(path) => write!(
+                f,
+                "'{}' has an invalid filter; expected [?(@.field==value)] or [?(@.field!=value)]",
+                path
+            ),
+            Self::Validation(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for RecordQuantifierError {}
+
+/// How element results combine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantifier {
+    All,
+    Any,
+    None,
+    OnlyOne,
+}
+
+impl Quantifier {
+    pub fn from_entity_check(entity_check: Option<EntityCheck>) -> Self {
+        match entity_check {
+            Some(EntityCheck::AtLeastOne) => Self::Any,
+            Some(EntityCheck::None) => Self::None,
+            Some(EntityCheck::OnlyOne) => Self::OnlyOne,
+            Some(EntityCheck::All) | None => Self::All,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Any => "at_least_one",
+            Self::None => "none",
+            Self::OnlyOne => "only_one",
+        }
+    }
+}
+
+/// Element filter of a `[?(@.field==value)]` segment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementFilter {
+    /// Dotted path within the element; empty for the element itself
+    pub field_path: String,
+    /// Value the field is compared with
+    pub value: Value,
+    /// `!=` rather than `==`
+    pub negated: bool,
+}
+
+impl ElementFilter {
+    /// Parse the expression between `[?(` and `)]`
+    ///
+    /// The value is a JSON literal (`80`, `true`, `"app"`), a single-quoted
+    /// string (`'app'`) or, failing both, a bare string (`app`).
+    fn parse(expression: &str) -> Option<Self> {
+        let equals = expression.find("==");
+        let not_equals = expression.find("!=");
+        let (at, negated) = match (equals, not_equals) {
+            (Some(eq), Some(ne)) if ne < eq => (ne, true),
+            (Some(eq), _) => (eq, false),
+            (None, Some(ne)) => (ne, true),
+            (None, None) => return None,
+        };
+        let (field, value) = expression.split_at(at);
+        let field = field.trim().strip_prefix('@')?;
+        if !(field.is_empty() || field.starts_with('.')) {
+            return None;
+        }
+        let value = value.get(2..)?.trim();
+        if value.is_empty() {
+            return None;
+        }
+
+        let value = match value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+            Some(quoted) => Value::String(quoted.to_string()),
+            None => {
+                serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()))
+            }
+        };
+        Some(Self {
+            field_path: field.trim_start_matches('.').to_string(),
+            value,
+            negated,
+        })
+    }
+
+    /// Whether `element` is selected; an element without the field differs
+    /// from every value
+    pub fn matches(&self, element: &Value) -> bool {
+        let equal = value_at_path(element, &self.field_path) == Some(&self.value);
+        equal != self.negated
+    }
+}
+
+/// A record check path split at its `[*]` or filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantifiedPath {
+    /// Dotted path to the array
+    pub array_path: String,
+    /// Filter selecting elements; `None` for `[*]`
+    pub filter: Option<ElementFilter>,
+    /// Dotted path within each element; empty for the element itself
+    pub element_path: String,
+}
+
+impl QuantifiedPath {
+    /// Split `path`, or `None` if it has no `[*]` or filter
+    pub fn parse(path: &str) -> Option<Result<Self, RecordQuantifierError>> {
+        let start = [path.find(ARRAY_QUANTIFIER), path.find(FILTER_START)]
+            .into_iter()
+            .flatten()
+            .min()?;
+        let (array_path, quantified) = path.split_at(start);
+
+        let (filter, rest) = match quantified.strip_prefix(ARRAY_QUANTIFIER) {
+            Some(rest) => (None, rest),
+            None => {
+                let filter = quantified
+                    .strip_prefix(FILTER_START)
+                    .and_then(|segment| segment.split_once(FILTER_END))
+                    .and_then(|(expression, rest)| Some((ElementFilter::parse(expression)?, rest)));
+                match filter {
+                    Some((filter, rest)) => (Some(filter), rest),
+                    None => {
+                        return Some(Err(RecordQuantifierError::InvalidFilter(path.to_string())))
+                    }
+                }
+            }
+        };
+        if rest.contains(ARRAY_QUANTIFIER) || rest.contains(FILTER_START) {
+            return Some(Err(RecordQuantifierError::NestedQuantifier(
+                path.to_string(),
+            )));
+        }
+        Some(Ok(Self {
+            array_path: array_path.to_string(),
+            filter,
+            element_path: rest.trim_start_matches('.').to_string(),
+        }))
+    }
+
+    /// Path of a check on an element wrapped under `ELEMENT_KEY`
+    fn wrapped_element_path(&self) -> String {
+        if self.element_path.is_empty() {
+            ELEMENT_KEY.to_string()
+        } else {
+            format!("{}.{}", ELEMENT_KEY, self.element_path)
+        }
+    }
+}
+
+/// Outcome of one array element
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementResult {
+    pub passed: bool,
+    /// Actual value, if the element has the field
+    pub actual: Option<String>,
+    pub message: String,
+}
+
+/// Value at a dotted path; numeric segments index arrays
+pub fn value_at_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |current, segment| match current {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+/// Combine element results of a quantified check
+///
+/// `evaluate` is called on each element of the array at
+/// `path.array_path` in `record` that the path's filter selects.
+pub fn evaluate_quantified(
+    record: &Value,
+    field_path: &str,
+    path: &QuantifiedPath,
+    quantifier: Quantifier,
+    mut evaluate: impl FnMut(&Value) -> Result<ElementResult, RecordQuantifierError>,
+) -> Result<RecordCheckResult, RecordQuantifierError> {
+    let elements = match value_at_path(record, &path.array_path) {
+        Some(Value::Array(elements)) => elements,
+        found => {
+            let problem = if found.is_some() {
+                "not an array"
+            } else {
+                "not present"
+            };
+            return Ok(RecordCheckResult {
+                field_path: field_path.to_string(),
+                expected: None,
+                actual: None,
+                passed: false,
+                message: format!("{}: '{}' is {}", field_path, path.array_path, problem),
+            });
+        }
+    };
+
+    let selected: Vec<(usize, &Value)> = elements
+        .iter()
+        .enumerate()
+        .filter(|(_, element)| path.filter.as_ref().is_none_or(|f| f.matches(element)))
+        .collect();
+
+    let mut passing = Vec::new();
+    let mut failing = Vec::new();
+    for &(index, element) in &selected {
+        let result = evaluate(element)?;
+        let label = element_label(index, element);
+        if result.passed {
+            passing.push((label, result));
+        } else {
+            failing.push((label, result));
+        }
+    }
+
+    let passed = match quantifier {
+        Quantifier::All => failing.is_empty(),
+        Quantifier::Any => !passing.is_empty(),
+        Quantifier::None => passing.is_empty(),
+        Quantifier::OnlyOne => passing.len() == 1,
+    };
+
+    let summary = format!(
+        "{}: {} of {} elements passed ({})",
+        field_path,
+        passing.len(),
+        selected.len(),
+        quantifier.as_str()
+    );
+    let message = match quantifier {
+        _ if elements.is_empty() => format!("{}; '{}' is empty", summary, path.array_path),
+        _ if selected.is_empty() => format!(
+            "{}; no element of '{}' matches the filter",
+            summary, path.array_path
+        ),
+        Quantifier::All | Quantifier::Any if !passed => {
+            format!("{}; failed at {}", summary, describe(&failing))
+        }
+        Quantifier::None | Quantifier::OnlyOne if !passed && !passing.is_empty() => {
+            format!("{}; passed at {}", summary, describe(&passing))
+        }
+        _ => summary,
+    };
+
+    // Actual values of the elements that decided the result
+    let deciding = match quantifier {
+        Quantifier::All | Quantifier::Any if !passed => &failing,
+        _ => &passing,
+    };
+    let actual = deciding
+        .iter()
+        .map(|(label, result)| {
+            format!(
+                "{}={}",
+                label,
+                result.actual.as_deref().unwrap_or("<missing>")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Ok(RecordCheckResult {
+        field_path: field_path.to_string(),
+        expected: None,
+        actual: Some(actual),
+        passed,
+        message,
+    })
+}
+
+/// `index N` or `index N (name)` for an element with a `name`
+fn element_label(index: usize, element: &Value) -> String {
+    match element.get("name").and_then(Value::as_str) {
+        Some(name) => format!("index {} ({})", index, name),
+        None => format!("index {}", index),
+    }
+}
+
+/// Labels and messages of elements, up to `MAX_LISTED_INDICES`
+fn describe(elements: &[(String, ElementResult)]) -> String {
+    let mut listed: Vec<String> = elements
+        .iter()
+        .take(MAX_LISTED_INDICES)
+        .map(|(label, result)| format!("{}: {}", label, result.message))
+        .collect();
+    if elements.len() > MAX_LISTED_INDICES {
+        listed.push(format!("{} more", elements.len() - MAX_LISTED_INDICES));
+    }
+    listed.join("; ")
+}
+
+/// Validate record checks, evaluating `[*]` paths element by element
+///
+/// Drop-in for the engine's `validate_record_checks`: results are returned
+/// in check order, one per check.
+pub fn validate_quantified_record_checks(
+    record_data: &RecordData,
+    checks: &[ExecutableRecordCheck],
+) -> Result<Vec<RecordCheckResult>, RecordQuantifierError> {
+    let mut results = Vec::with_capacity(checks.len());
+    for check in checks {
+        let Some(path) = QuantifiedPath::parse(&check.field_path) else {
+            results.extend(validate_plain(record_data, std::slice::from_ref(check))?);
+            continue;
+        };
+        let path = path?;
+
+        let mut element_check = check.clone();
+        element_check.field_path = path.wrapped_element_path();
+        element_check.entity_check = None;
+        let mut expected = None;
+
+        let result = evaluate_quantified(
+            record_data.as_json(),
+            &check.field_path,
+            &path,
+            Quantifier::from_entity_check(check.entity_check),
+            |element| {
+                let wrapped = RecordData::from_json_value(json!({ ELEMENT_KEY: element }));
+                let element_results =
+                    validate_plain(&wrapped, std::slice::from_ref(&element_check))?;
+                let Some(result) = element_results.into_iter().next() else {
+                    return Err(RecordQuantifierError::Validation(format!(
+                        "no result for '{}'",
+                        check.field_path
+                    )));
+                };
+                expected = expected.take().or(result.expected);
+                Ok(ElementResult {
+                    passed: result.passed,
+                    actual: result.actual,
+                    message: result.message.replace(&format!("{}.", ELEMENT_KEY), ""),
+                })
+            },
+        )?;
+        results.push(RecordCheckResult { expected, ..result });
+    }
+    Ok(results)
+}
+
+/// Engine validation of checks without a quantifier
+fn validate_plain(
+    record_data: &RecordData,
+    checks: &[ExecutableRecordCheck],
+) -> Result<Vec<RecordCheckResult>, RecordQuantifierError> {
+    let results = validate_record_checks(record_data, checks)
+        .map_err(|e| RecordQuantifierError::Validation(e.to_string()))?;
+    Ok(results
+        .into_iter()
+        .map(|r| RecordCheckResult {
+            field_path: r.field_path,
+            expected: r.expected,
+            actual: r.actual,
+            passed: r.passed,
+            message: r.message,
+        })
+        .collect())
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMORY_LIMIT: &str = "spec.containers[*].resources.limits.memory";
+
+    fn pod() -> Value {
+        json!({
+            "kind": "Pod",
+            "metadata": { "name": "web" },
+            "spec": {
+                "containers": [
+                    {
+                        "name": "app",
+                        "image": "nginx:1.27",
+                        "resources": { "limits": { "memory": "256Mi", "cpu": "500m" } }
+                    },
+                    {
+                        "name": "sidecar",
+                        "image": "envoy:1.31",
+                        "resources": {}
+                    }
+                ]
+            }
+        })
+    }
+
+    /// `resources.limits.memory` is set
+    fn memory_limit_set(element: &Value) -> Result<ElementResult, RecordQuantifierError> {
+        let actual = value_at_path(element, "resources.limits.memory")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        Ok(ElementResult {
+            passed: actual.is_some(),
+            message: match actual {
+                Some(_) => "memory limit set".to_string(),
+                None => "resources.limits.memory not present".to_string(),
+            },
+            actual,
+        })
+    }
+
+    fn check(quantifier: Quantifier) -> RecordCheckResult {
+        let path = QuantifiedPath::parse(MEMORY_LIMIT).unwrap().unwrap();
+        evaluate_quantified(&pod(), MEMORY_LIMIT, &path, quantifier, memory_limit_set).unwrap()
+    }
+
+    #[test]
+    fn test_parse_quantified_path() {
+        assert_eq!(
+            QuantifiedPath::parse(MEMORY_LIMIT).unwrap().unwrap(),
+            QuantifiedPath {
+                array_path: "spec.containers".to_string(),
+                filter: None,
+                element_path: "resources.limits.memory".to_string(),
+            }
+        );
+        let ports = QuantifiedPath::parse("spec.ports[*]").unwrap().unwrap();
+        assert_eq!(ports.element_path, "");
+        assert_eq!(ports.wrapped_element_path(), ELEMENT_KEY);
+        assert!(QuantifiedPath::parse("spec.containers.*.image").is_none());
+        assert!(matches!(
+            QuantifiedPath::parse("spec.containers[*].ports[*].hostPort"),
+            Some(Err(RecordQuantifierError::NestedQuantifier(_)))
+        ));
+    }
+
+    #[test]
+    fn test_all_reports_failing_index() {
+        let result = check(Quantifier::All);
+
+        assert!(!result.passed);
+        assert_eq!(
+            result.message,
+            "spec.containers[*].resources.limits.memory: 1 of 2 elements passed (all); \
+             failed at index 1 (sidecar): resources.limits.memory not present"
+        );
+        assert_eq!(
+            result.actual.as_deref(),
+            Some("index 1 (sidecar)=<missing>")
+        );
+    }
+
+    #[test]
+    fn test_any_and_none_over_mixed_containers() {
+        let any = check(Quantifier::Any);
+        assert!(any.passed);
+        assert_eq!(
+            any.message,
+            "spec.containers[*].resources.limits.memory: 1 of 2 elements passed (at_least_one)"
+        );
+        assert_eq!(any.actual.as_deref(), Some("index 0 (app)=256Mi"));
+
+        let none = check(Quantifier::None);
+        assert!(!none.passed);
+        assert!(none
+            .message
+            .ends_with("passed at index 0 (app): memory limit set"));
+        assert!(check(Quantifier::OnlyOne).passed);
+    }
+
+    #[test]
+    fn test_missing_and_empty_arrays() {
+        let path = QuantifiedPath::parse("spec.initContainers[*].image")
+            .unwrap()
+            .unwrap();
+        let missing = evaluate_quantified(
+            &pod(),
+            "spec.initContainers[*].image",
+            &path,
+            Quantifier::All,
+            memory_limit_set,
+        )
+        .unwrap();
+        assert!(!missing.passed);
+        assert!(missing
+            .message
+            .ends_with("'spec.initContainers' is not present"));
+
+        let empty = json!({ "spec": { "initContainers": [] } });
+        let evaluate = |quantifier| {
+            evaluate_quantified(
+                &empty,
+                "spec.initContainers[*].image",
+                &path,
+                quantifier,
+                memory_limit_set,
+            )
+            .unwrap()
+            .passed
+        };
+        assert!(evaluate(Quantifier::All));
+        assert!(!evaluate(Quantifier::Any));
+        assert!(evaluate(Quantifier::None));
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        let path = QuantifiedPath::parse("spec.containers[?(@.name==app)].image")
+            .unwrap()
+            .unwrap();
+        assert_eq!(path.array_path, "spec.containers");
+        assert_eq!(path.element_path, "image");
+        assert_eq!(
+            path.filter,
+            Some(ElementFilter {
+                field_path: "name".to_string(),
+                value: json!("app"),
+                negated: false,
+            })
+        );
+
+        let filter = |expression: &str| {
+            QuantifiedPath::parse(&format!("items[?({})].x", expression))
+                .unwrap()
+                .map(|path| path.filter.unwrap())
+        };
+        assert_eq!(filter("@.port == 80").unwrap().value, json!(80));
+        assert_eq!(filter("@.tls.enabled!=true").unwrap().value, json!(true));
+        assert!(filter("@.tls.enabled!=true").unwrap().negated);
+        assert_eq!(filter("@.name=='a b'").unwrap().value, json!("a b"));
+        assert_eq!(filter("@==\"x\"").unwrap().field_path, "");
+
+        for invalid in ["@.name", "name==app", "@name==app", "@.name=="] {
+            assert!(
+                matches!(
+                    filter(invalid),
+                    Err(RecordQuantifierError::InvalidFilter(_))
+                ),
+                "{}",
+                invalid
+            );
+        }
+        assert!(matches!(
+            QuantifiedPath::parse("items[?(@.a==1)].b[*]"),
+            Some(Err(RecordQuantifierError::NestedQuantifier(_)))
+        ));
+    }
+
+    #[test]
+    fn test_filter_selects_elements() {
+        let with_limits = "spec.containers[?(@.name!=sidecar)].resources.limits.memory";
+        let path = QuantifiedPath::parse(with_limits).unwrap().unwrap();
+        let result = evaluate_quantified(
+            &pod(),
+            with_limits,
+            &path,
+            Quantifier::All,
+            memory_limit_set,
+        )
+        .unwrap();
+        assert!(result.passed);
+        assert_eq!(
+            result.message,
+            format!("{}: 1 of 1 elements passed (all)", with_limits)
+        );
+
+        // Indices are those of the whole array
+        let sidecar = "spec.containers[?(@.image=='envoy:1.31')].resources.limits.memory";
+        let path = QuantifiedPath::parse(sidecar).unwrap().unwrap();
+        let result =
+            evaluate_quantified(&pod(), sidecar, &path, Quantifier::All, memory_limit_set).unwrap();
+        assert!(!result.passed);
+        assert!(result.message.contains("failed at index 1 (sidecar)"));
+
+        // No match behaves like an empty array
+        let none = "spec.containers[?(@.name==db)].resources.limits.memory";
+        let path = QuantifiedPath::parse(none).unwrap().unwrap();
+        let evaluate = |quantifier| {
+            evaluate_quantified(&pod(), none, &path, quantifier, memory_limit_set).unwrap()
+        };
+        assert!(evaluate(Quantifier::All).passed);
+        assert!(!evaluate(Quantifier::Any).passed);
+        assert!(evaluate(Quantifier::All)
+            .message
+            .ends_with("no element of 'spec.containers' matches the filter"));
+    }
+}
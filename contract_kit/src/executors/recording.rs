@@ -0,0 +1,215 @@
+//! Outcome Recording
+//!
+//! `RecordingExecutor` wraps an executor and records the outcome of each
+//! criterion it evaluates, in evaluation order. Criteria group results are
+//! rebuilt from these outcomes (see `crate::criteria`), and findings are
+//! re-rendered from their messages and failed fields (see
+//! `crate::finding_format`).
+
+use common::results::Outcome;
+use execution_engine::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use execution_engine::types::common::ResolvedValue;
+use execution_engine::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Outcome of one evaluated criterion
+#[derive(Debug, PartialEq)]
+pub struct RecordedOutcome {
+    pub ctn_type: String,
+    pub outcome: Outcome,
+    /// Executor message, which the engine uses as the finding description
+    pub message: String,
+    /// State fields that did not hold, in evaluation order
+    pub failures: Vec<FieldFailure>,
+}
+
+impl RecordedOutcome {
+    /// Outcome with no message or field failures
+    pub fn new(ctn_type: impl Into<String>, outcome: Outcome) -> Self {
+        Self {
+            ctn_type: ctn_type.into(),
+            outcome,
+            message: String::new(),
+            failures: Vec::new(),
+        }
+    }
+
+    fn from_result(result: &CtnExecutionResult) -> Self {
+        let failures = result
+            .state_results
+            .iter()
+            .flat_map(|state| {
+                state
+                    .state_results
+                    .iter()
+                    .filter(|field| !field.passed)
+                    .map(|field| FieldFailure {
+                        object_id: state.object_id.clone(),
+                        field_name: field.field_name.clone(),
+                        operation: format!("{:?}", field.operation),
+                        expected: display_value(&field.expected_value),
+                        actual: display_value(&field.actual_value),
+                        message: field.message.clone(),
+                    })
+            })
+            .collect();
+
+        Self {
+            ctn_type: result.ctn_type.clone(),
+            outcome: copy_outcome(&result.status),
+            message: result.message.clone(),
+            failures,
+        }
+    }
+}
+
+/// A state field that did not hold for one object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldFailure {
+    pub object_id: String,
+    pub field_name: String,
+    /// Comparison, e.g. `Equals`
+    pub operation: String,
+    pub expected: String,
+    pub actual: String,
+    /// Executor message for this field
+    pub message: String,
+}
+
+/// Shared sink for criterion outcomes
+#[derive(Clone, Default)]
+pub struct OutcomeRecorder {
+    outcomes: Arc<Mutex<Vec<RecordedOutcome>>>,
+}
+
+impl OutcomeRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&self, outcome: RecordedOutcome) {
+        if let Ok(mut outcomes) = self.outcomes.lock() {
+            outcomes.push(outcome);
+        }
+    }
+
+    /// Take all recorded outcomes, leaving the recorder empty
+    pub fn take(&self) -> Vec<RecordedOutcome> {
+        self.outcomes
+            .lock()
+            .map(|mut outcomes| std::mem::take(&mut *outcomes))
+            .unwrap_or_default()
+    }
+}
+
+/// Executor wrapper that records each criterion's outcome
+///
+/// Criteria whose collected data fails validation, or whose execution
+/// returns an error, are recorded as `Error`.
+pub struct RecordingExecutor {
+    inner: Box<dyn CtnExecutor>,
+    recorder: OutcomeRecorder,
+}
+
+impl RecordingExecutor {
+    pub fn new(inner: Box<dyn CtnExecutor>, recorder: OutcomeRecorder) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+impl CtnExecutor for RecordingExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: HashMap<String, CollectedData>,
+        contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let result = self
+            .inner
+            .execute_with_contract(criterion, collected_data, contract);
+
+        let outcome = match &result {
+            Ok(executed) => RecordedOutcome::from_result(executed),
+            Err(e) => RecordedOutcome {
+                message: e.to_string(),
+                ..RecordedOutcome::new(&criterion.criterion_type, Outcome::Error)
+            },
+        };
+        self.recorder.record(outcome);
+
+        result
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.inner.get_ctn_contract()
+    }
+
+    fn ctn_type(&self) -> &str {
+        self.inner.ctn_type()
+    }
+
+    fn validate_collected_data(
+        &self,
+        collected_data: &HashMap<String, CollectedData>,
+        contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        let validated = self.inner.validate_collected_data(collected_data, contract);
+        // Invalid data means the criterion is never executed
+        if let Err(e) = &validated {
+            self.recorder.record(RecordedOutcome {
+                message: e.to_string(),
+                ..RecordedOutcome::new(self.inner.ctn_type(), Outcome::Error)
+            });
+        }
+        validated
+    }
+}
+
+/// Copy an outcome, treating anything but Pass and Fail as Error
+pub(crate) fn copy_outcome(outcome: &Outcome) -> Outcome {
+    match outcome {
+        Outcome::Pass => Outcome::Pass,
+        Outcome::Fail => Outcome::Fail,
+        _ => Outcome::Error,
+    }
+}
+
+/// Render a value the way executors do in their messages
+pub(crate) fn display_value(value: &ResolvedValue) -> String {
+    match value {
+        ResolvedValue::String(s) => format!("'{}'", s),
+        ResolvedValue::Integer(i) => i.to_string(),
+        ResolvedValue::Boolean(b) => b.to_string(),
+        ResolvedValue::Float(f) => f.to_string(),
+        ResolvedValue::Binary(b) => format!("<binary {} bytes>", b.len()),
+        ResolvedValue::Collection(items) => format!("<collection {} items>", items.len()),
+        ResolvedValue::Version(v) => v.to_string(),
+        ResolvedValue::EvrString(e) => e.to_string(),
+        ResolvedValue::RecordData(_) => "<record>".to_string(),
+    }
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder_keeps_order_and_take_empties() {
+        let recorder = OutcomeRecorder::new();
+        let clone = recorder.clone();
+        recorder.record(RecordedOutcome::new("file_metadata", Outcome::Fail));
+        clone.record(RecordedOutcome::new("tcp_listener", Outcome::Pass));
+
+        let outcomes = recorder.take();
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].ctn_type, "file_metadata");
+        assert_eq!(outcomes[1].outcome, Outcome::Pass);
+        assert!(clone.take().is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+//! Short-Circuit Evaluation
+//!
+//! For `TEST ... all` criteria, one object that fails its states decides the
+//! criterion. When only the outcome is needed (`EvidenceLevel::Outcome`),
+//! executors stop state validation at that object instead of comparing the
+//! remaining objects.
+//!
+//! The gate never changes outcomes. It stops only on a genuine state
+//! failure, never on a collection error, because a criterion whose failures
+//! all come from collection errors is reported as Error rather than Fail.
+//! Executors that can return an error for a later object (e.g. malformed
+//! collected data) do not short-circuit.
+
+use common::results::Outcome;
+
+/// How much per-object evidence a criterion result must carry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvidenceLevel {
+    /// Validate and report every object
+    #[default]
+    PerObject,
+    /// Only the outcome is needed; state results may stop at the deciding object
+    Outcome,
+}
+
+/// Decides when state validation may stop early
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShortCircuit {
+    enabled: bool,
+}
+
+impl ShortCircuit {
+    /// Gate for a criterion
+    ///
+    /// `item_check` evaluates the criterion's item check for
+    /// `(objects_passing, objects_total)`.
+    pub fn new(level: EvidenceLevel, item_check: impl Fn(usize, usize) -> bool) -> Self {
+        // Passing when both objects pass but failing when one of two fails
+        // identifies `all`: any single failure then decides the item check
+        let decided_by_one_failure = item_check(2, 2) && !item_check(1, 2);
+        Self {
+            enabled: level == EvidenceLevel::Outcome && decided_by_one_failure,
+        }
+    }
+
+    /// Whether validation can stop after an object with this result
+    pub fn should_stop(&self, passed: bool, from_collection_error: bool) -> bool {
+        self.enabled && !passed && !from_collection_error
+    }
+}
+
+/// Outcome of a criterion after its item check
+///
+/// Failures that all come from collection errors make the criterion an
+/// Error; any genuine state failure makes it a Fail.
+pub fn state_outcome(
+    checks_passed: bool,
+    objects_failing: usize,
+    failing_from_errors: usize,
+) -> Outcome {
+    if checks_passed {
+        Outcome::Pass
+    } else if failing_from_errors > 0 && failing_from_errors == objects_failing {
+        Outcome::Error
+    } else {
+        Outcome::Fail
+    }
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Object result: (states passed, failed from a collection error)
+    type ObjectResult = (bool, bool);
+
+    fn item_all(passing: usize, total: usize) -> bool {
+        passing == total
+    }
+
+    fn item_at_least_one(passing: usize, _total: usize) -> bool {
+        passing >= 1
+    }
+
+    fn item_none(passing: usize, _total: usize) -> bool {
+        passing == 0
+    }
+
+    /// Evaluate objects the way the executors do, optionally short-circuiting
+    fn evaluate(
+        objects: &[ObjectResult],
+        item_check: fn(usize, usize) -> bool,
+        level: EvidenceLevel,
+    ) -> (Outcome, usize) {
+        let short_circuit = ShortCircuit::new(level, item_check);
+        let mut evaluated = Vec::new();
+        for &(passed, from_error) in objects {
+            evaluated.push((passed, from_error));
+            if short_circuit.should_stop(passed, from_error) {
+                break;
+            }
+        }
+
+        let passing = evaluated.iter().filter(|(passed, _)| *passed).count();
+        let failing = evaluated.len() - passing;
+        let from_errors = evaluated
+            .iter()
+            .filter(|(passed, from_error)| !passed && *from_error)
+            .count();
+        let outcome = state_outcome(item_check(passing, evaluated.len()), failing, from_errors);
+        (outcome, evaluated.len())
+    }
+
+    /// Every sequence of up to four objects, each passing, failing, or errored
+    fn all_sequences() -> Vec<Vec<ObjectResult>> {
+        let kinds = [(true, false), (false, false), (false, true)];
+        let mut sequences = vec![Vec::new()];
+        for len in 1..=4u32 {
+            for n in 0..3usize.pow(len) {
+                let mut code = n;
+                let sequence = (0..len)
+                    .map(|_| {
+                        let kind = kinds[code % 3];
+                        code /= 3;
+                        kind
+                    })
+                    .collect();
+                sequences.push(sequence);
+            }
+        }
+        sequences
+    }
+
+    #[test]
+    fn test_short_circuit_never_changes_outcome() {
+        for item_check in [item_all, item_at_least_one, item_none] {
+            for objects in all_sequences() {
+                let (full, _) = evaluate(&objects, item_check, EvidenceLevel::PerObject);
+                let (fast, _) = evaluate(&objects, item_check, EvidenceLevel::Outcome);
+                assert_eq!(full, fast, "outcome changed for {:?}", objects);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stops_at_first_state_failure() {
+        let objects = [(true, false), (false, false), (false, true), (true, false)];
+
+        let (outcome, evaluated) = evaluate(&objects, item_all, EvidenceLevel::Outcome);
+        assert_eq!(outcome, Outcome::Fail);
+        assert_eq!(evaluated, 2);
+
+        let (_, evaluated) = evaluate(&objects, item_all, EvidenceLevel::PerObject);
+        assert_eq!(evaluated, 4);
+    }
+
+    #[test]
+    fn test_only_all_item_check_is_gated() {
+        assert!(ShortCircuit::new(EvidenceLevel::Outcome, item_all).should_stop(false, false));
+        assert!(!ShortCircuit::new(EvidenceLevel::Outcome, item_all).should_stop(false, true));
+        assert!(
+            !ShortCircuit::new(EvidenceLevel::Outcome, item_at_least_one).should_stop(false, false)
+        );
+        assert!(!ShortCircuit::new(EvidenceLevel::Outcome, item_none).should_stop(false, false));
+        assert!(!ShortCircuit::new(EvidenceLevel::PerObject, item_all).should_stop(false, false));
+    }
+}
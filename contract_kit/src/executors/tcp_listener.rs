@@ -10,36 +10,34 @@ use execution_engine::strategies::{
     CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
     FieldValidationResult, StateValidationResult, TestPhase,
 };
-use execution_engine::types::common::{Operation, ResolvedValue};
+use execution_engine::types::common::ResolvedValue;
 use execution_engine::types::execution_context::ExecutableCriterion;
 use std::collections::HashMap;
 
+use crate::collectors::error_reporting::{
+    count_failing_objects, count_found_objects, not_found_details, CollectionErrorRecord,
+};
+use crate::executors::file_metadata::{compare_values, format_value};
+use crate::executors::short_circuit::{state_outcome, EvidenceLevel, ShortCircuit};
+
 /// Executor for tcp_listener validation
 pub struct TcpListenerExecutor {
     contract: CtnContract,
+    evidence_level: EvidenceLevel,
 }
 
 impl TcpListenerExecutor {
     pub fn new(contract: CtnContract) -> Self {
-        Self { contract }
+        Self {
+            contract,
+            evidence_level: EvidenceLevel::default(),
+        }
     }
 
-    /// Compare boolean values
-    fn compare_values(
-        &self,
-        expected: &ResolvedValue,
-        actual: &ResolvedValue,
-        operation: Operation,
-    ) -> bool {
-        match (expected, actual, operation) {
-            (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::Equals) => {
-                exp == act
-            }
-            (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::NotEqual) => {
-                exp != act
-            }
-            _ => false,
-        }
+    /// Set how much per-object evidence results must carry
+    pub fn with_evidence_level(mut self, level: EvidenceLevel) -> Self {
+        self.evidence_level = level;
+        self
     }
 }
 
@@ -54,7 +52,8 @@ impl CtnExecutor for TcpListenerExecutor {
 
         // Phase 1: Existence check
         let objects_expected = criterion.expected_object_count();
-        let objects_found = collected_data.len();
+        let collection_errors = CollectionErrorRecord::from_collected(&collected_data);
+        let objects_found = count_found_objects(&collected_data, &collection_errors);
 
         let existence_passed =
             evaluate_existence_check(test_spec.existence_check, objects_found, objects_expected);
@@ -63,8 +62,10 @@ impl CtnExecutor for TcpListenerExecutor {
             return Ok(CtnExecutionResult::fail(
                 criterion.criterion_type.clone(),
                 format!(
-                    "Existence check failed: expected {} ports, found {}",
-                    objects_expected, objects_found
+                    "Existence check failed: expected {} ports, found {}{}",
+                    objects_expected,
+                    objects_found,
+                    not_found_details(&collection_errors)
                 ),
             )
             .with_collected_data(collected_data));
@@ -73,8 +74,28 @@ impl CtnExecutor for TcpListenerExecutor {
         // Phase 2: State validation
         let mut state_results = Vec::new();
         let mut failure_messages = Vec::new();
+        let short_circuit = ShortCircuit::new(self.evidence_level, |passing, total| {
+            evaluate_item_check(test_spec.item_check, passing, total)
+        });
+        let mut short_circuited = false;
 
         for (object_id, data) in &collected_data {
+            // Objects that could not be collected are reported, not evaluated
+            if let Some(error) = CollectionErrorRecord::from_collected_data(data) {
+                // Missing objects were already handled by the existence check
+                if error.counts_as_found() {
+                    failure_messages.push(error.message());
+                    state_results.push(StateValidationResult {
+                        object_id: object_id.clone(),
+                        state_results: vec![error.field_result()],
+                        combined_result: false,
+                        state_operator: test_spec.state_operator,
+                        message: error.message(),
+                    });
+                }
+                continue;
+            }
+
             let mut all_field_results = Vec::new();
 
             for state in &criterion.states {
@@ -105,17 +126,23 @@ impl CtnExecutor for TcpListenerExecutor {
                         }
                     };
 
-                    let passed = self.compare_values(&field.value, &actual_value, field.operation);
+                    let passed = compare_values(&field.value, &actual_value, field.operation);
 
                     let msg = if passed {
                         format!(
-                            "Port '{}' check passed: listening = {:?}",
-                            object_id, actual_value
+                            "Port '{}' check passed: {} = {}",
+                            object_id,
+                            field.name,
+                            format_value(&actual_value)
                         )
                     } else {
                         format!(
-                            "Port '{}' check failed: expected listening {:?} {:?}, got {:?}",
-                            object_id, field.operation, field.value, actual_value
+                            "Port '{}' check failed: expected {} {:?} {}, got {}",
+                            object_id,
+                            field.name,
+                            field.operation,
+                            format_value(&field.value),
+                            format_value(&actual_value)
                         )
                     };
 
@@ -148,6 +175,11 @@ impl CtnExecutor for TcpListenerExecutor {
                     if combined { "passed" } else { "failed" }
                 ),
             });
+
+            if short_circuit.should_stop(combined, false) {
+                short_circuited = true;
+                break;
+            }
         }
 
         // Phase 3: Item check
@@ -155,11 +187,12 @@ impl CtnExecutor for TcpListenerExecutor {
         let item_passed =
             evaluate_item_check(test_spec.item_check, objects_passing, state_results.len());
 
-        let final_status = if existence_passed && item_passed {
-            Outcome::Pass
-        } else {
-            Outcome::Fail
-        };
+        let (objects_failing, failing_from_errors) = count_failing_objects(&state_results);
+        let final_status = state_outcome(
+            existence_passed && item_passed,
+            objects_failing,
+            failing_from_errors,
+        );
 
         let message = if final_status == Outcome::Pass {
             format!(
@@ -167,6 +200,11 @@ impl CtnExecutor for TcpListenerExecutor {
                 objects_passing,
                 state_results.len()
             )
+        } else if final_status == Outcome::Error {
+            format!(
+                "TCP listener validation could not be completed:\n  - {}",
+                failure_messages.join("\n  - ")
+            )
         } else {
             format!(
                 "TCP listener validation failed:\n  - {}",
@@ -184,7 +222,12 @@ impl CtnExecutor for TcpListenerExecutor {
             message,
             details: serde_json::json!({
                 "failures": failure_messages,
+                "collection_errors": collection_errors
+                    .iter()
+                    .map(CollectionErrorRecord::to_json)
+                    .collect::<Vec<_>>(),
                 "objects_passing": objects_passing,
+                "short_circuited": short_circuited,
             }),
             execution_metadata: Default::default(),
             collected_data,
@@ -205,6 +248,9 @@ impl CtnExecutor for TcpListenerExecutor {
         _contract: &CtnContract,
     ) -> Result<(), CtnExecutionError> {
         for data in collected_data.values() {
+            if CollectionErrorRecord::from_collected_data(data).is_some() {
+                continue;
+            }
             if !data.has_field("listening") {
                 return Err(CtnExecutionError::MissingDataField {
                     field: "listening".to_string(),
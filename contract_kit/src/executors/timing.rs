@@ -0,0 +1,256 @@
+//! Criterion Timing
+//!
+//! `TimingExecutor` wraps an executor and records how long each criterion
+//! took, so a slow scan can be attributed to specific checks. A criterion's
+//! time is the collection time of its objects, recorded by `TimedCollector`
+//! wrappers sharing the same `CriterionTimer`, plus the time spent
+//! evaluating it. Objects served from a cache cost no collection time.
+//!
+//! Timings are diagnostics: they are not part of scan results or hashes.
+//! Each record also notes whether the executor marked the criterion not
+//! applicable, which the scanner counts (see `applicability`).
+//! `timing_breakdown` ranks criteria by collection time to find the checks
+//! that dominate a slow scan.
+
+use execution_engine::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use execution_engine::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::executors::applicability::is_not_applicable;
+
+/// Time spent on one criterion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CriterionTiming {
+    pub ctn_type: String,
+    /// Objects the criterion evaluated, sorted
+    pub objects: Vec<String>,
+    /// Collection time of the objects
+    pub collection: Duration,
+    /// Time the executor spent evaluating
+    pub evaluation: Duration,
+    /// Whether the executor marked the criterion not applicable
+    pub not_applicable: bool,
+}
+
+impl CriterionTiming {
+    /// Collection and evaluation time
+    pub fn total(&self) -> Duration {
+        self.collection + self.evaluation
+    }
+
+    /// Identifies the criterion in reports, as `ctn_type[object, ...]`
+    pub fn id(&self) -> String {
+        format!("{}[{}]", self.ctn_type, self.objects.join(", "))
+    }
+}
+
+/// Criterion IDs with their collection time in milliseconds, slowest first
+///
+/// Criteria with equal times keep their evaluation order.
+///
+/// ```ignore
+/// for (criterion, ms) in timing_breakdown(&timer.take()).iter().take(10) {
+///     println!("{:>8} ms  {}", ms, criterion);
+/// }
+/// ```
+pub fn timing_breakdown(timings: &[CriterionTiming]) -> Vec<(String, u64)> {
+    let mut breakdown: Vec<(String, u64)> = timings
+        .iter()
+        .map(|timing| {
+            let millis = u64::try_from(timing.collection.as_millis()).unwrap_or(u64::MAX);
+            (timing.id(), millis)
+        })
+        .collect();
+    breakdown.sort_by(|a, b| b.1.cmp(&a.1));
+    breakdown
+}
+
+#[derive(Default)]
+struct TimerState {
+    /// Collection time of objects not yet claimed by a criterion
+    collections: HashMap<String, Duration>,
+    criteria: Vec<CriterionTiming>,
+}
+
+/// Shared sink for collection and criterion timings
+#[derive(Clone, Default)]
+pub struct CriterionTimer {
+    state: Arc<Mutex<TimerState>>,
+}
+
+impl CriterionTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the time spent collecting `object_id`
+    pub fn record_collection(&self, object_id: &str, elapsed: Duration) {
+        if let Ok(mut state) = self.state.lock() {
+            *state.collections.entry(object_id.to_string()).or_default() += elapsed;
+        }
+    }
+
+    /// Record a criterion, claiming the collection time of its objects
+    fn record_criterion(
+        &self,
+        ctn_type: &str,
+        mut objects: Vec<String>,
+        evaluation: Duration,
+        not_applicable: bool,
+    ) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        objects.sort();
+        let collection = objects
+            .iter()
+            .filter_map(|object_id| state.collections.remove(object_id))
+            .sum();
+        state.criteria.push(CriterionTiming {
+            ctn_type: ctn_type.to_string(),
+            objects,
+            collection,
+            evaluation,
+            not_applicable,
+        });
+    }
+
+    /// Take all criterion timings in evaluation order, leaving the timer empty
+    pub fn take(&self) -> Vec<CriterionTiming> {
+        self.state
+            .lock()
+            .map(|mut state| {
+                state.collections.clear();
+                std::mem::take(&mut state.criteria)
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Executor wrapper that records each criterion's time
+pub struct TimingExecutor {
+    inner: Box<dyn CtnExecutor>,
+    timer: CriterionTimer,
+}
+
+impl TimingExecutor {
+    pub fn new(inner: Box<dyn CtnExecutor>, timer: CriterionTimer) -> Self {
+        Self { inner, timer }
+    }
+}
+
+impl CtnExecutor for TimingExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: HashMap<String, CollectedData>,
+        contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let objects = collected_data.keys().cloned().collect();
+        let start = Instant::now();
+        let result = self
+            .inner
+            .execute_with_contract(criterion, collected_data, contract);
+        let not_applicable = result.as_ref().is_ok_and(is_not_applicable);
+        self.timer.record_criterion(
+            &criterion.criterion_type,
+            objects,
+            start.elapsed(),
+            not_applicable,
+        );
+        result
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.inner.get_ctn_contract()
+    }
+
+    fn ctn_type(&self) -> &str {
+        self.inner.ctn_type()
+    }
+
+    fn validate_collected_data(
+        &self,
+        collected_data: &HashMap<String, CollectedData>,
+        contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        self.inner.validate_collected_data(collected_data, contract)
+    }
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_criterion_claims_collection_time_of_its_objects() {
+        let timer = CriterionTimer::new();
+        timer.record_collection("sshd_config", Duration::from_millis(30));
+        timer.record_collection("sshd_config", Duration::from_millis(10));
+        timer.record_collection("auditd_conf", Duration::from_millis(5));
+
+        timer.record_criterion(
+            "file_content",
+            vec!["sshd_config".to_string()],
+            Duration::from_millis(2),
+            false,
+        );
+        // Already claimed, so a second criterion on the object costs nothing
+        timer.record_criterion(
+            "file_metadata",
+            vec!["sshd_config".to_string()],
+            Duration::from_millis(1),
+            false,
+        );
+
+        let timings = timer.take();
+        assert_eq!(timings.len(), 2);
+        assert_eq!(timings[0].collection, Duration::from_millis(40));
+        assert_eq!(timings[0].total(), Duration::from_millis(42));
+        assert_eq!(timings[1].collection, Duration::ZERO);
+
+        // Taking also drops unclaimed collection time
+        assert!(timer.take().is_empty());
+        timer.record_criterion(
+            "file_content",
+            vec!["auditd_conf".into()],
+            Duration::ZERO,
+            true,
+        );
+        let timings = timer.take();
+        assert_eq!(timings[0].collection, Duration::ZERO);
+        assert!(timings[0].not_applicable);
+    }
+
+    #[test]
+    fn test_timing_breakdown_slowest_first() {
+        let timing = |ctn_type: &str, objects: &[&str], ms: u64| CriterionTiming {
+            ctn_type: ctn_type.to_string(),
+            objects: objects.iter().map(|o| o.to_string()).collect(),
+            collection: Duration::from_millis(ms),
+            evaluation: Duration::from_millis(100),
+            not_applicable: false,
+        };
+        let timings = vec![
+            timing("file_metadata", &["sshd_config"], 3),
+            timing("k8s_resource", &["cluster_roles", "pods"], 4790),
+            timing("file_content", &["sshd_config"], 3),
+        ];
+
+        assert_eq!(
+            timing_breakdown(&timings),
+            vec![
+                ("k8s_resource[cluster_roles, pods]".to_string(), 4790),
+                ("file_metadata[sshd_config]".to_string(), 3),
+                ("file_content[sshd_config]".to_string(), 3),
+            ]
+        );
+    }
+}
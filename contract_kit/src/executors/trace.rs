@@ -0,0 +1,510 @@
+//! Criterion Traces
+//!
+//! `TracingExecutor` wraps an executor and records the decision trace of
+//! each criterion it evaluates: the objects collected with their raw
+//! fields, the expected and actual value of every state field checked, and
+//! how the existence check, state operator and item check combined into
+//! the outcome. `explain_criterion` picks the traces of one criterion and
+//! `format_trace` renders a trace as an indented tree.
+//!
+//! Criteria are identified as in timings (`CriterionTiming::id`), by CTN
+//! type and sorted object IDs, e.g. `file_metadata[passwd_file]`.
+
+use common::results::Outcome;
+use execution_engine::execution::{evaluate_existence_check, evaluate_item_check};
+use execution_engine::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+    StateValidationResult,
+};
+use execution_engine::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::collectors::error_reporting::{count_found_objects, CollectionErrorRecord};
+use crate::executors::recording::{copy_outcome, display_value};
+
+/// Decision trace of one evaluated criterion
+#[derive(Debug, PartialEq)]
+pub struct CriterionExplanation {
+    /// `ctn_type[object, ...]`, as in timings
+    pub id: String,
+    pub ctn_type: String,
+    pub outcome: Outcome,
+    /// Executor message, which the engine uses as the finding description
+    pub message: String,
+    /// How the checks combined; `None` when the collected data was rejected
+    /// before evaluation
+    pub checks: Option<CheckTrace>,
+    /// Collected objects, sorted by object ID
+    pub objects: Vec<ObjectTrace>,
+}
+
+/// Existence check, state operator and item check of a criterion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckTrace {
+    /// Existence check, e.g. `AtLeastOne`
+    pub existence_check: String,
+    pub objects_expected: usize,
+    pub objects_found: usize,
+    pub existence_passed: bool,
+    /// How the states of one object combine, e.g. `And`
+    pub state_operator: String,
+    /// Item check, e.g. `All`
+    pub item_check: String,
+    /// Objects whose states held, of those evaluated
+    pub objects_passing: usize,
+    pub objects_evaluated: usize,
+    /// `None` when a failed existence check stopped evaluation first
+    pub item_passed: Option<bool>,
+}
+
+/// One collected object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectTrace {
+    pub object_id: String,
+    /// The contract's data fields as collected, in contract order; fields
+    /// not collected are left out
+    pub collected: Vec<(String, String)>,
+    /// Why the object could not be collected, if it could not
+    pub collection_error: Option<String>,
+    /// States checked against the object; `None` if it was not evaluated
+    pub states: Option<StateTrace>,
+}
+
+/// States of one object, combined by the state operator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateTrace {
+    pub passed: bool,
+    pub message: String,
+    pub fields: Vec<FieldTrace>,
+}
+
+/// One state field compared with the collected value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldTrace {
+    pub field_name: String,
+    /// Comparison, e.g. `Equals`
+    pub operation: String,
+    pub expected: String,
+    pub actual: String,
+    pub passed: bool,
+    pub message: String,
+}
+
+impl CriterionExplanation {
+    /// Whether this trace is of `criterion_id`
+    ///
+    /// A bare CTN type matches every criterion of that type.
+    pub fn matches(&self, criterion_id: &str) -> bool {
+        self.id == criterion_id || self.ctn_type == criterion_id
+    }
+}
+
+/// Traces of the criteria matching `criterion_id`, in evaluation order
+pub fn explain_criterion<'a>(
+    traces: &'a [CriterionExplanation],
+    criterion_id: &str,
+) -> Vec<&'a CriterionExplanation> {
+    traces
+        .iter()
+        .filter(|trace| trace.matches(criterion_id))
+        .collect()
+}
+
+/// Render a trace as an indented tree
+///
+/// ```text
+/// file_metadata[passwd_file]: Fail
+///   existence: AtLeastOne, 1 of 1 expected object(s) found: passed
+///   object passwd_file
+///     collected:
+///       mode = '0644'
+///     states (And): failed
+///       [FAIL] mode Equals: expected '0600', actual '0644'
+///   item check: All, 0 of 1 object(s) passed: failed
+///   message: File metadata validation failed: ...
+/// ```
+pub fn format_trace(trace: &CriterionExplanation) -> String {
+    let mut lines = vec![format!("{}: {:?}", trace.id, trace.outcome)];
+
+    if let Some(checks) = &trace.checks {
+        lines.push(format!(
+            "  existence: {}, {} of {} expected object(s) found: {}",
+            checks.existence_check,
+            checks.objects_found,
+            checks.objects_expected,
+            verdict(checks.existence_passed)
+        ));
+    }
+
+    for object in &trace.objects {
+        lines.push(format!("  object {}", object.object_id));
+        if let Some(error) = &object.collection_error {
+            lines.push(format!("    not collected: {}", error));
+        } else if !object.collected.is_empty() {
+            lines.push("    collected:".to_string());
+            for (name, value) in &object.collected {
+                lines.push(format!("      {} = {}", name, value));
+            }
+        }
+        match (&object.states, &trace.checks) {
+            (Some(states), Some(checks)) => {
+                lines.push(format!(
+                    "    states ({}): {}",
+                    checks.state_operator,
+                    verdict(states.passed)
+                ));
+                for field in &states.fields {
+                    lines.push(format!(
+                        "      [{}] {} {}: expected {}, actual {}",
+                        if field.passed { "PASS" } else { "FAIL" },
+                        field.field_name,
+                        field.operation,
+                        field.expected,
+                        field.actual
+                    ));
+                }
+            }
+            _ => lines.push("    states: not evaluated".to_string()),
+        }
+    }
+
+    if let Some(checks) = &trace.checks {
+        lines.push(match checks.item_passed {
+            Some(passed) => format!(
+                "  item check: {}, {} of {} object(s) passed: {}",
+                checks.item_check,
+                checks.objects_passing,
+                checks.objects_evaluated,
+                verdict(passed)
+            ),
+            None => format!("  item check: {}, not evaluated", checks.item_check),
+        });
+    }
+    for (index, line) in trace.message.lines().enumerate() {
+        let label = if index == 0 { "message:" } else { "        " };
+        lines.push(format!("  {} {}", label, line.trim_start()));
+    }
+
+    lines.join("\n")
+}
+
+fn verdict(passed: bool) -> &'static str {
+    if passed {
+        "passed"
+    } else {
+        "failed"
+    }
+}
+
+/// Shared sink for criterion traces
+#[derive(Clone, Default)]
+pub struct TraceRecorder {
+    traces: Arc<Mutex<Vec<CriterionExplanation>>>,
+}
+
+impl TraceRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&self, trace: CriterionExplanation) {
+        if let Ok(mut traces) = self.traces.lock() {
+            traces.push(trace);
+        }
+    }
+
+    /// Take all recorded traces, leaving the recorder empty
+    pub fn take(&self) -> Vec<CriterionExplanation> {
+        self.traces
+            .lock()
+            .map(|mut traces| std::mem::take(&mut *traces))
+            .unwrap_or_default()
+    }
+}
+
+/// Executor wrapper that records each criterion's decision trace
+///
+/// Criteria whose collected data fails validation, or whose execution
+/// returns an error, are recorded as `Error` with the objects collected.
+pub struct TracingExecutor {
+    inner: Box<dyn CtnExecutor>,
+    recorder: TraceRecorder,
+}
+
+impl TracingExecutor {
+    pub fn new(inner: Box<dyn CtnExecutor>, recorder: TraceRecorder) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+impl CtnExecutor for TracingExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: HashMap<String, CollectedData>,
+        contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let test_spec = &criterion.test;
+        let objects_expected = criterion.expected_object_count();
+        let collection_errors = CollectionErrorRecord::from_collected(&collected_data);
+        let objects_found = count_found_objects(&collected_data, &collection_errors);
+        let existence_passed =
+            evaluate_existence_check(test_spec.existence_check, objects_found, objects_expected);
+        let objects = collected_objects(&collected_data, contract);
+        let id = criterion_id(&criterion.criterion_type, &collected_data);
+
+        let result = self
+            .inner
+            .execute_with_contract(criterion, collected_data, contract);
+
+        let trace = match &result {
+            Ok(executed) => {
+                let objects_passing = executed
+                    .state_results
+                    .iter()
+                    .filter(|state| state.combined_result)
+                    .count();
+                let objects_evaluated = executed.state_results.len();
+                CriterionExplanation {
+                    id,
+                    ctn_type: executed.ctn_type.clone(),
+                    outcome: copy_outcome(&executed.status),
+                    message: executed.message.clone(),
+                    checks: Some(CheckTrace {
+                        existence_check: format!("{:?}", test_spec.existence_check),
+                        objects_expected,
+                        objects_found,
+                        existence_passed,
+                        state_operator: format!("{:?}", test_spec.state_operator),
+                        item_check: format!("{:?}", test_spec.item_check),
+                        objects_passing,
+                        objects_evaluated,
+                        item_passed: existence_passed.then(|| {
+                            evaluate_item_check(
+                                test_spec.item_check,
+                                objects_passing,
+                                objects_evaluated,
+                            )
+                        }),
+                    }),
+                    objects: with_states(objects, &executed.state_results),
+                }
+            }
+            Err(e) => CriterionExplanation {
+                id,
+                ctn_type: criterion.criterion_type.clone(),
+                outcome: Outcome::Error,
+                message: e.to_string(),
+                checks: None,
+                objects,
+            },
+        };
+        self.recorder.record(trace);
+
+        result
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.inner.get_ctn_contract()
+    }
+
+    fn ctn_type(&self) -> &str {
+        self.inner.ctn_type()
+    }
+
+    fn validate_collected_data(
+        &self,
+        collected_data: &HashMap<String, CollectedData>,
+        contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        let validated = self.inner.validate_collected_data(collected_data, contract);
+        // Invalid data means the criterion is never executed
+        if let Err(e) = &validated {
+            let ctn_type = self.inner.ctn_type();
+            self.recorder.record(CriterionExplanation {
+                id: criterion_id(ctn_type, collected_data),
+                ctn_type: ctn_type.to_string(),
+                outcome: Outcome::Error,
+                message: e.to_string(),
+                checks: None,
+                objects: collected_objects(collected_data, contract),
+            });
+        }
+        validated
+    }
+}
+
+/// `ctn_type[object, ...]` with the object IDs sorted, as in timings
+fn criterion_id(ctn_type: &str, collected_data: &HashMap<String, CollectedData>) -> String {
+    let mut objects: Vec<&str> = collected_data.keys().map(String::as_str).collect();
+    objects.sort_unstable();
+    format!("{}[{}]", ctn_type, objects.join(", "))
+}
+
+/// Collected objects with the contract's data fields, sorted by object ID
+fn collected_objects(
+    collected_data: &HashMap<String, CollectedData>,
+    contract: &CtnContract,
+) -> Vec<ObjectTrace> {
+    let mappings = &contract.field_mappings.collection_mappings;
+    let mut objects: Vec<ObjectTrace> = collected_data
+        .iter()
+        .map(
+            |(object_id, data)| match CollectionErrorRecord::from_collected_data(data) {
+                Some(error) => ObjectTrace {
+                    object_id: object_id.clone(),
+                    collected: Vec::new(),
+                    collection_error: Some(error.message()),
+                    states: None,
+                },
+                None => ObjectTrace {
+                    object_id: object_id.clone(),
+                    collected: mappings
+                        .required_data_fields
+                        .iter()
+                        .chain(&mappings.optional_data_fields)
+                        .filter_map(|name| {
+                            data.get_field(name)
+                                .map(|value| (name.clone(), display_value(value)))
+                        })
+                        .collect(),
+                    collection_error: None,
+                    states: None,
+                },
+            },
+        )
+        .collect();
+    objects.sort_by(|a, b| a.object_id.cmp(&b.object_id));
+    objects
+}
+
+/// Attach each object's state results
+fn with_states(
+    mut objects: Vec<ObjectTrace>,
+    state_results: &[StateValidationResult],
+) -> Vec<ObjectTrace> {
+    for state in state_results {
+        if let Some(object) = objects.iter_mut().find(|o| o.object_id == state.object_id) {
+            object.states = Some(StateTrace {
+                passed: state.combined_result,
+                message: state.message.clone(),
+                fields: state
+                    .state_results
+                    .iter()
+                    .map(|field| FieldTrace {
+                        field_name: field.field_name.clone(),
+                        operation: format!("{:?}", field.operation),
+                        expected: display_value(&field.expected_value),
+                        actual: display_value(&field.actual_value),
+                        passed: field.passed,
+                        message: field.message.clone(),
+                    })
+                    .collect(),
+            });
+        }
+    }
+    objects
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failing_trace() -> CriterionExplanation {
+        CriterionExplanation {
+            id: "file_metadata[passwd_file, shadow_file]".to_string(),
+            ctn_type: "file_metadata".to_string(),
+            outcome: Outcome::Fail,
+            message: "File metadata validation failed:\n  - mode of shadow_file".to_string(),
+            checks: Some(CheckTrace {
+                existence_check: "AtLeastOne".to_string(),
+                objects_expected: 2,
+                objects_found: 2,
+                existence_passed: true,
+                state_operator: "And".to_string(),
+                item_check: "All".to_string(),
+                objects_passing: 1,
+                objects_evaluated: 2,
+                item_passed: Some(false),
+            }),
+            objects: vec![
+                ObjectTrace {
+                    object_id: "passwd_file".to_string(),
+                    collected: vec![("mode".to_string(), "'0644'".to_string())],
+                    collection_error: None,
+                    states: Some(StateTrace {
+                        passed: true,
+                        message: String::new(),
+                        fields: vec![FieldTrace {
+                            field_name: "mode".to_string(),
+                            operation: "Equals".to_string(),
+                            expected: "'0644'".to_string(),
+                            actual: "'0644'".to_string(),
+                            passed: true,
+                            message: String::new(),
+                        }],
+                    }),
+                },
+                ObjectTrace {
+                    object_id: "shadow_file".to_string(),
+                    collected: Vec::new(),
+                    collection_error: Some("permission denied".to_string()),
+                    states: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_explain_criterion_by_id_or_type() {
+        let traces = vec![
+            failing_trace(),
+            CriterionExplanation {
+                id: "tcp_listener[ssh_port]".to_string(),
+                ctn_type: "tcp_listener".to_string(),
+                ..failing_trace()
+            },
+        ];
+
+        let by_id = explain_criterion(&traces, "file_metadata[passwd_file, shadow_file]");
+        assert_eq!(by_id.len(), 1);
+        assert_eq!(by_id[0].ctn_type, "file_metadata");
+        assert_eq!(explain_criterion(&traces, "tcp_listener").len(), 1);
+        assert!(explain_criterion(&traces, "file_metadata[passwd_file]").is_empty());
+    }
+
+    #[test]
+    fn test_format_trace_tree() {
+        let text = format_trace(&failing_trace());
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "file_metadata[passwd_file, shadow_file]: Fail");
+        assert_eq!(
+            lines[1],
+            "  existence: AtLeastOne, 2 of 2 expected object(s) found: passed"
+        );
+        assert!(lines.contains(&"      mode = '0644'"));
+        assert!(lines.contains(&"    states (And): passed"));
+        assert!(lines.contains(&"      [PASS] mode Equals: expected '0644', actual '0644'"));
+        assert!(lines.contains(&"    not collected: permission denied"));
+        assert!(lines.contains(&"    states: not evaluated"));
+        assert!(lines.contains(&"  item check: All, 1 of 2 object(s) passed: failed"));
+        assert_eq!(
+            lines[lines.len() - 2],
+            "  message: File metadata validation failed:"
+        );
+        assert_eq!(lines[lines.len() - 1], "           - mode of shadow_file");
+    }
+
+    #[test]
+    fn test_recorder_take_empties() {
+        let recorder = TraceRecorder::new();
+        recorder.record(failing_trace());
+        assert_eq!(recorder.take().len(), 1);
+        assert!(recorder.take().is_empty());
+    }
+}
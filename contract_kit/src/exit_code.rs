@@ -0,0 +1,265 @@
+//! # Exit Codes
+//!
+//! Maps the outcome of a multi-policy scan to a process exit code, so the
+//! agent and embedders report the same result to CI systems.
+//!
+//! ## Default Mapping
+//!
+//! | Scan | Code |
+//! |------|------|
+//! | Every policy passed | 0 |
+//! | A policy failed, none errored | 1 |
+//! | No policy file could be scanned | 3 |
+//! | Some policy files could not be scanned, others were | 4 |
+//! | No policies | 0 |
+//!
+//! Errors take precedence over failures. A scan with no policies counts as
+//! passing unless `empty` is mapped. Code 2 is left to callers for usage
+//! errors, before any policy is scanned.
+//!
+//! ## Map Syntax
+//!
+//! `ExitCodePolicy` parses from comma-separated `key=code` pairs, applied
+//! left to right over the default mapping:
+//!
+//! | Key | Sets |
+//! |-----|------|
+//! | `fail` | Code when a policy failed |
+//! | `error` | Code when no policy file could be scanned |
+//! | `partial` | Code when some policy files could not be scanned |
+//! | `non-pass` | `fail`, `error` and `partial` |
+//! | `empty` | Code when there were no policies |
+//!
+//! Codes are 0 to 255. `non-pass=1` makes every non-passing scan exit 1;
+//! `fail=0` reports failures without failing the job.
+
+use std::str::FromStr;
+
+use crate::execution_api::ScanResult;
+
+/// Policy counts of a scan
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScanCounts {
+    /// Policies that passed
+    pub passed: usize,
+    /// Policies that failed
+    pub failed: usize,
+    /// Policy files that produced no result
+    pub errors: usize,
+}
+
+impl ScanCounts {
+    /// Count results; `errors` is the number of files that failed to scan
+    pub fn from_results(results: &[ScanResult], errors: usize) -> Self {
+        let passed = results.iter().filter(|r| r.tree_passed).count();
+        Self {
+            passed,
+            failed: results.len() - passed,
+            errors,
+        }
+    }
+
+    /// Whether no policy was scanned or attempted
+    pub fn is_empty(&self) -> bool {
+        self.passed == 0 && self.failed == 0 && self.errors == 0
+    }
+
+    /// Whether some policy files errored while others produced a result
+    pub fn is_partial(&self) -> bool {
+        self.errors > 0 && self.passed + self.failed > 0
+    }
+}
+
+/// Exit code for each scan outcome
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExitCodePolicy {
+    /// Code when a policy failed and none errored
+    pub fail: i32,
+    /// Code when no policy file could be scanned
+    pub error: i32,
+    /// Code when some policy files could not be scanned and others were
+    pub partial: i32,
+    /// Code when there were no policies
+    pub empty: i32,
+}
+
+impl Default for ExitCodePolicy {
+    fn default() -> Self {
+        Self {
+            fail: 1,
+            error: 3,
+            partial: 4,
+            empty: 0,
+        }
+    }
+}
+
+impl ExitCodePolicy {
+    /// Every non-passing scan exits with `code`
+    pub fn any_non_pass(code: i32) -> Self {
+        Self {
+            fail: code,
+            error: code,
+            partial: code,
+            ..Self::default()
+        }
+    }
+
+    /// Exit code for a scan with these counts
+    pub fn exit_code(&self, counts: &ScanCounts) -> i32 {
+        if counts.is_partial() {
+            self.partial
+        } else if counts.errors > 0 {
+            self.error
+        } else if counts.failed > 0 {
+            self.fail
+        } else if counts.is_empty() {
+            self.empty
+        } else {
+            0
+        }
+    }
+}
+
+impl FromStr for ExitCodePolicy {
+    type Err = ExitCodeMapError;
+
+    fn from_str(map: &str) -> Result<Self, Self::Err> {
+        let mut policy = Self::default();
+
+        for entry in map.split(',').map(str::trim) {
+            let (key, value) = entry
+                .split_once('=')
+                .ok_or_else(|| ExitCodeMapError::MissingCode(entry.to_string()))?;
+            let value = value.trim();
+            let code = value
+                .parse::<u8>()
+                .map_err(|_| ExitCodeMapError::InvalidCode(value.to_string()))?;
+            let code = i32::from(code);
+
+            match key.trim() {
+                "fail" => policy.fail = code,
+                "error" => policy.error = code,
+                "partial" => policy.partial = code,
+                "non-pass" => {
+                    policy.fail = code;
+                    policy.error = code;
+                    policy.partial = code;
+                }
+                "empty" => policy.empty = code,
+                other => return Err(ExitCodeMapError::UnknownKey(other.to_string())),
+            }
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Errors parsing an exit code map
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExitCodeMapError {
+    /// Entry is not `key=code`
+    MissingCode(String),
+    /// Code is not a number from 0 to 255
+    InvalidCode(String),
+    /// Key is not `fail`, `error`, `partial`, `non-pass` or `empty`
+    UnknownKey(String),
+}
+
+impl std::fmt::Display for ExitCodeMapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingCode(entry) => write!(f, "expected key=code, got '{}'", entry),
+            Self::InvalidCode(code) => {
+                write!(f, "invalid exit code '{}' (expected 0-255)", code)
+            }
+            Self::UnknownKey(key) => write!(
+                f,
+                "unknown key '{}' (expected fail, error, partial, non-pass or empty)",
+                key
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExitCodeMapError {}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(passed: usize, failed: usize, errors: usize) -> ScanCounts {
+        ScanCounts {
+            passed,
+            failed,
+            errors,
+        }
+    }
+
+    /// Exit codes for: empty, all pass, some fail, some error, fail and
+    /// error, all error
+    fn codes(policy: &ExitCodePolicy) -> [i32; 6] {
+        [
+            counts(0, 0, 0),
+            counts(3, 0, 0),
+            counts(2, 1, 0),
+            counts(3, 0, 1),
+            counts(1, 1, 1),
+            counts(0, 0, 2),
+        ]
+        .map(|c| policy.exit_code(&c))
+    }
+
+    #[test]
+    fn test_default_mapping() {
+        let policy = ExitCodePolicy::default();
+        assert_eq!(codes(&policy), [0, 0, 1, 4, 4, 3]);
+    }
+
+    #[test]
+    fn test_any_non_pass_mapping() {
+        let policy: ExitCodePolicy = "non-pass=1".parse().unwrap();
+        assert_eq!(policy, ExitCodePolicy::any_non_pass(1));
+        assert_eq!(codes(&policy), [0, 0, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_custom_mappings() {
+        let policy: ExitCodePolicy = "fail=1, error=3, partial=4".parse().unwrap();
+        assert_eq!(policy, ExitCodePolicy::default());
+
+        let policy: ExitCodePolicy = "fail=0,error=70,partial=71,empty=4".parse().unwrap();
+        assert_eq!(codes(&policy), [4, 0, 0, 71, 71, 70]);
+
+        // Later entries override earlier ones
+        let policy: ExitCodePolicy = "non-pass=5,error=6".parse().unwrap();
+        assert_eq!(codes(&policy), [0, 0, 5, 5, 5, 6]);
+    }
+
+    #[test]
+    fn test_invalid_maps_are_rejected() {
+        assert_eq!(
+            "fail".parse::<ExitCodePolicy>(),
+            Err(ExitCodeMapError::MissingCode("fail".to_string()))
+        );
+        assert_eq!(
+            "".parse::<ExitCodePolicy>(),
+            Err(ExitCodeMapError::MissingCode(String::new()))
+        );
+        assert_eq!(
+            "fail=256".parse::<ExitCodePolicy>(),
+            Err(ExitCodeMapError::InvalidCode("256".to_string()))
+        );
+        assert_eq!(
+            "error=-1".parse::<ExitCodePolicy>(),
+            Err(ExitCodeMapError::InvalidCode("-1".to_string()))
+        );
+        assert_eq!(
+            "warn=3".parse::<ExitCodePolicy>(),
+            Err(ExitCodeMapError::UnknownKey("warn".to_string()))
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! # Finding Formatting
+//!
+//! The engine writes each finding from its criterion's executor message. A
+//! `FindingFormatter` re-renders findings after the scan, so a team can
+//! phrase them for its audience (remediation steps for engineers, control
+//! language for auditors) without changing executors. Console, full and
+//! assessor output all read the rewritten findings.
+//!
+//! The formatter sees the finding, the CTN type and failed fields of the
+//! criterion that produced it, and the policy's control mappings. Criterion
+//! details come from an `OutcomeRecorder` shared with
+//! `RecordingExecutor`-wrapped executors, as for criteria groups. A finding
+//! is matched to the first unused failing or errored outcome whose message
+//! is its description; a finding without a match is still formatted, with
+//! no CTN type and no failed fields.
+
+use common::results::Outcome;
+
+use crate::execution_api::ScanResult;
+use crate::executors::recording::{FieldFailure, RecordedOutcome};
+
+/// A control mapping of the scanned policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlRef {
+    pub framework: String,
+    pub control_id: String,
+}
+
+/// Everything a formatter can use to render one finding
+#[derive(Debug, Clone, Copy)]
+pub struct FindingContext<'a> {
+    pub policy_id: &'a str,
+    pub finding_id: &'a str,
+    /// Severity as the engine displays it
+    pub severity: &'a str,
+    /// Title as written by the engine
+    pub title: &'a str,
+    /// Description as written by the engine
+    pub description: &'a str,
+    /// CTN type of the criterion, when it was matched
+    pub ctn_type: Option<&'a str>,
+    /// Fields that failed, with expected and actual values
+    pub failures: &'a [FieldFailure],
+    pub controls: &'a [ControlRef],
+}
+
+/// Renders finding text
+pub trait FindingFormatter: Send + Sync {
+    /// Finding title; keeps the engine's title by default
+    fn title(&self, finding: &FindingContext<'_>) -> String {
+        finding.title.to_string()
+    }
+
+    /// Finding description
+    fn description(&self, finding: &FindingContext<'_>) -> String;
+}
+
+/// Formatter that keeps the engine's wording
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultFindingFormatter;
+
+impl FindingFormatter for DefaultFindingFormatter {
+    fn description(&self, finding: &FindingContext<'_>) -> String {
+        finding.description.to_string()
+    }
+}
+
+/// Re-render the findings of a scan result with `formatter`
+///
+/// `outcomes` must come from the scan that produced `result`.
+pub fn format_findings(
+    result: &mut ScanResult,
+    outcomes: &[RecordedOutcome],
+    formatter: &dyn FindingFormatter,
+) {
+    let controls: Vec<ControlRef> = result
+        .outcome
+        .control_mappings
+        .iter()
+        .map(|m| ControlRef {
+            framework: m.framework.to_string(),
+            control_id: m.control_id.to_string(),
+        })
+        .collect();
+    let mut remaining: Vec<&RecordedOutcome> = outcomes
+        .iter()
+        .filter(|o| o.outcome != Outcome::Pass)
+        .collect();
+
+    for finding in &mut result.findings {
+        let outcome = take_outcome(&mut remaining, &finding.description);
+        let severity = finding.severity.to_string();
+        let context = FindingContext {
+            policy_id: &result.outcome.policy_id,
+            finding_id: &finding.finding_id,
+            severity: &severity,
+            title: &finding.title,
+            description: &finding.description,
+            ctn_type: outcome.map(|o| o.ctn_type.as_str()),
+            failures: outcome.map(|o| o.failures.as_slice()).unwrap_or_default(),
+            controls: &controls,
+        };
+        let title = formatter.title(&context);
+        let description = formatter.description(&context);
+
+        finding.title = title;
+        finding.description = description;
+    }
+}
+
+/// Remove and return the first outcome whose message produced `description`
+fn take_outcome<'a>(
+    remaining: &mut Vec<&'a RecordedOutcome>,
+    description: &str,
+) -> Option<&'a RecordedOutcome> {
+    let position = remaining.iter().position(|o| {
+        !o.message.is_empty() && (o.message == description || description.contains(&o.message))
+    })?;
+    Some(remaining.remove(position))
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESCRIPTION: &str =
+        "File metadata validation failed:\n  - Object 'shadow': Field 'mode' failed";
+
+    /// Remediation-flavored template
+    struct RemediationFormatter;
+
+    impl FindingFormatter for RemediationFormatter {
+        fn title(&self, finding: &FindingContext<'_>) -> String {
+            format!("[{}] {}", finding.severity, finding.title)
+        }
+
+        fn description(&self, finding: &FindingContext<'_>) -> String {
+            let mut text = String::new();
+            for failure in finding.failures {
+                text.push_str(&format!(
+                    "Set {} on {} to {} (found {}).\n",
+                    failure.field_name, failure.object_id, failure.expected, failure.actual
+                ));
+            }
+            let controls: Vec<String> = finding
+                .controls
+                .iter()
+                .map(|c| format!("{} {}", c.framework, c.control_id))
+                .collect();
+            text.push_str(&format!("Controls: {}", controls.join(", ")));
+            text
+        }
+    }
+
+    fn failed_outcome() -> RecordedOutcome {
+        RecordedOutcome {
+            message: DESCRIPTION.to_string(),
+            failures: vec![FieldFailure {
+                object_id: "shadow".to_string(),
+                field_name: "mode".to_string(),
+                operation: "Equals".to_string(),
+                expected: "'0000'".to_string(),
+                actual: "'0644'".to_string(),
+                message: "Field 'mode' failed".to_string(),
+            }],
+            ..RecordedOutcome::new("file_metadata", Outcome::Fail)
+        }
+    }
+
+    fn context<'a>(
+        outcome: Option<&'a RecordedOutcome>,
+        controls: &'a [ControlRef],
+    ) -> FindingContext<'a> {
+        FindingContext {
+            policy_id: "shadow-perms",
+            finding_id: "F-1",
+            severity: "high",
+            title: "Shadow file permissions",
+            description: DESCRIPTION,
+            ctn_type: outcome.map(|o| o.ctn_type.as_str()),
+            failures: outcome.map(|o| o.failures.as_slice()).unwrap_or_default(),
+            controls,
+        }
+    }
+
+    #[test]
+    fn test_custom_formatter_renders_template() {
+        let outcomes = [
+            RecordedOutcome::new("tcp_listener", Outcome::Pass),
+            failed_outcome(),
+        ];
+        let mut remaining: Vec<&RecordedOutcome> = outcomes
+            .iter()
+            .filter(|o| o.outcome != Outcome::Pass)
+            .collect();
+        let outcome = take_outcome(&mut remaining, DESCRIPTION);
+        assert_eq!(outcome.map(|o| o.ctn_type.as_str()), Some("file_metadata"));
+
+        let controls = [ControlRef {
+            framework: "NIST-800-53".to_string(),
+            control_id: "AC-6".to_string(),
+        }];
+        let finding = context(outcome, &controls);
+
+        assert_eq!(
+            RemediationFormatter.title(&finding),
+            "[high] Shadow file permissions"
+        );
+        assert_eq!(
+            RemediationFormatter.description(&finding),
+            "Set mode on shadow to '0000' (found '0644').\nControls: NIST-800-53 AC-6"
+        );
+    }
+
+    #[test]
+    fn test_default_formatter_keeps_wording() {
+        let outcome = failed_outcome();
+        let finding = context(Some(&outcome), &[]);
+
+        assert_eq!(DefaultFindingFormatter.title(&finding), finding.title);
+        assert_eq!(DefaultFindingFormatter.description(&finding), DESCRIPTION);
+    }
+
+    #[test]
+    fn test_outcomes_are_matched_once() {
+        let outcomes = [failed_outcome(), failed_outcome()];
+        let mut remaining: Vec<&RecordedOutcome> = outcomes.iter().collect();
+
+        assert!(take_outcome(&mut remaining, DESCRIPTION).is_some());
+        assert!(take_outcome(&mut remaining, DESCRIPTION).is_some());
+        assert!(take_outcome(&mut remaining, DESCRIPTION).is_none());
+        assert!(take_outcome(&mut vec![&outcomes[0]], "Port 22 not listening").is_none());
+    }
+}
@@ -9,7 +9,22 @@
 //! - `executors` - Validation logic for each CTN type
 //! - `contracts` - CTN type definitions and field mappings
 //! - `commands` - Platform-specific command whitelists
+//! - `criteria` - Criteria group (`CRI AND`/`CRI OR`) results
 //! - `execution_api` - High-level scan execution API
+//! - `exit_code` - Exit code mapping for scan outcomes
+//! - `finding_format` - Custom finding wording
+//! - `log_context` - Per-thread policy context for log lines
+//! - `policy_check` - Pre-flight checks of policies against contracts
+//! - `policy_vars` - Policy `VAR` values supplied at scan time
+//! - `scan_cache` - Compiled AST cache for repeated scans
+//!
+//! ## Features
+//!
+//! - `native` (default) - Collectors and commands that read the host
+//!   (files, commands, sockets, kubectl), and `scan_cache`. Build with
+//!   `--no-default-features` for `wasm32-unknown-unknown`, keeping policy
+//!   metadata, AST loading, every executor, and the `computed_values`
+//!   collector.
 //!
 //! ## Usage
 //!
@@ -42,5 +57,13 @@
 pub mod collectors;
 pub mod commands;
 pub mod contracts;
+pub mod criteria;
 pub mod execution_api;
 pub mod executors;
+pub mod exit_code;
+pub mod finding_format;
+pub mod log_context;
+pub mod policy_check;
+pub mod policy_vars;
+#[cfg(feature = "native")]
+pub mod scan_cache;
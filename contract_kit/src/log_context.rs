@@ -0,0 +1,296 @@
+//! # Log Context
+//!
+//! Attributes log lines to the policy being scanned, also when policies are
+//! scanned on several threads at once.
+//!
+//! Each thread enters a [`FileContext`] for its policy, kept in thread-local
+//! storage. The logging macros of `execution_api` (`log_info!`,
+//! `log_error!`, ...) read it and add a `file` field naming the current
+//! thread's policy, so lines from parallel workers are labelled with their
+//! own policy. Lines logged outside a context carry no `file` field.
+//!
+//! `common::logging` also labels lines with one process-wide file context,
+//! which is all that lines logged with `common`'s macros directly have, the
+//! engine's own lines included. While exactly one policy is in
+//! flight it names that policy, so a single-worker scan is labelled as
+//! before; while several are, it is cleared rather than naming another
+//! worker's policy.
+//!
+//! The counters behind `logging::print_cargo_style_summary` are process-wide
+//! and not touched here, so the summary still covers every worker.
+//!
+//! ```ignore
+//! let _context = FileContext::enter(&esp_file, index + 1);
+//! let result = scan_file_with_logging(&esp_file, registry);
+//! // The context ends when `_context` is dropped
+//! ```
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread::{self, ThreadId};
+
+use common::logging;
+
+#[doc(hidden)]
+pub use common::{
+    log_debug as common_log_debug, log_error as common_log_error, log_info as common_log_info,
+    log_success as common_log_success,
+};
+
+thread_local! {
+    /// Policy the current thread is scanning
+    static CURRENT: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+/// Policies in flight, at most one per thread, for the process-wide label
+static ACTIVE: Mutex<Vec<ActiveFile>> = Mutex::new(Vec::new());
+
+/// A policy being scanned on one thread
+struct ActiveFile {
+    thread: ThreadId,
+    path: PathBuf,
+    number: usize,
+}
+
+/// Guard attributing the current thread's log lines to one policy
+///
+/// The context ends when the guard is dropped, and the thread returns to the
+/// context it was in before, if any.
+#[must_use = "the context ends when the guard is dropped"]
+pub struct FileContext {
+    thread: ThreadId,
+    previous: Option<ActiveFile>,
+    // The context belongs to the thread that entered it
+    _not_send: PhantomData<*const ()>,
+}
+
+impl FileContext {
+    /// Enter the context of `path`, the `number`th policy of the scan
+    ///
+    /// Nests inside any context the current thread is already in.
+    pub fn enter(path: &Path, number: usize) -> Self {
+        let thread = thread::current().id();
+        CURRENT.with(|current| *current.borrow_mut() = Some(path.to_path_buf()));
+        let mut previous = None;
+        update(|active| {
+            previous = take(active, thread);
+            active.push(ActiveFile {
+                thread,
+                path: path.to_path_buf(),
+                number,
+            });
+        });
+        Self {
+            thread,
+            previous,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Drop for FileContext {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|current| {
+            *current.borrow_mut() = previous.as_ref().map(|file| file.path.clone());
+        });
+        update(|active| {
+            take(active, self.thread);
+            active.extend(previous);
+        });
+    }
+}
+
+/// Policy the current thread is scanning, if it entered a context
+pub fn current_file() -> Option<PathBuf> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// `file` field of the current thread's log lines
+#[doc(hidden)]
+pub fn file_label() -> Option<String> {
+    current_file().map(|path| path.display().to_string())
+}
+
+/// Change the policies in flight and relabel the process-wide context
+///
+/// The lock is held while relabelling, so the label cannot fall behind the
+/// policies in flight.
+fn update(change: impl FnOnce(&mut Vec<ActiveFile>)) {
+    let mut active = lock();
+    change(&mut active);
+    match active.as_slice() {
+        [file] => logging::set_file_context(file.path.clone(), file.number),
+        _ => logging::clear_file_context(),
+    }
+}
+
+/// Remove the policy `thread` is scanning from the policies in flight
+fn take(active: &mut Vec<ActiveFile>, thread: ThreadId) -> Option<ActiveFile> {
+    let index = active.iter().position(|file| file.thread == thread)?;
+    Some(active.swap_remove(index))
+}
+
+fn lock() -> MutexGuard<'static, Vec<ActiveFile>> {
+    ACTIVE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Expand a `common` logging macro, adding the current thread's `file` field
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_with_file {
+    ($log:ident, ($($head:expr),+) $(, $key:expr => $value:expr)*) => {
+        match $crate::log_context::file_label() {
+            Some(file) => {
+                $crate::log_context::$log!($($head),+ $(, $key => $value)*, "file" => &file);
+            }
+            None => {
+                $crate::log_context::$log!($($head),+ $(, $key => $value)*);
+            }
+        }
+    };
+}
+
+/// `common::log_debug!`, labelled with the current thread's policy
+///
+/// Format-style calls are passed to `common` unchanged, without the label.
+#[macro_export]
+macro_rules! log_debug {
+    ($msg:expr $(, $key:expr => $value:expr)* $(,)?) => {
+        $crate::__log_with_file!(common_log_debug, ($msg) $(, $key => $value)*)
+    };
+    ($($arg:tt)*) => {
+        $crate::log_context::common_log_debug!($($arg)*)
+    };
+}
+
+/// `common::log_info!`, labelled with the current thread's policy
+///
+/// Format-style calls are passed to `common` unchanged, without the label.
+#[macro_export]
+macro_rules! log_info {
+    ($msg:expr $(, $key:expr => $value:expr)* $(,)?) => {
+        $crate::__log_with_file!(common_log_info, ($msg) $(, $key => $value)*)
+    };
+    ($($arg:tt)*) => {
+        $crate::log_context::common_log_info!($($arg)*)
+    };
+}
+
+/// `common::log_success!`, labelled with the current thread's policy
+///
+/// Format-style calls are passed to `common` unchanged, without the label.
+#[macro_export]
+macro_rules! log_success {
+    ($code:expr, $msg:expr $(, $key:expr => $value:expr)* $(,)?) => {
+        $crate::__log_with_file!(common_log_success, ($code, $msg) $(, $key => $value)*)
+    };
+    ($($arg:tt)*) => {
+        $crate::log_context::common_log_success!($($arg)*)
+    };
+}
+
+/// `common::log_error!`, labelled with the current thread's policy
+///
+/// Format-style calls are passed to `common` unchanged, without the label.
+#[macro_export]
+macro_rules! log_error {
+    ($code:expr, $msg:expr $(, $key:expr => $value:expr)* $(,)?) => {
+        $crate::__log_with_file!(common_log_error, ($code, $msg) $(, $key => $value)*)
+    };
+    ($($arg:tt)*) => {
+        $crate::log_context::common_log_error!($($arg)*)
+    };
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic
+)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Barrier;
+
+    #[test]
+    fn test_context_is_per_thread() {
+        let barrier = Barrier::new(2);
+        thread::scope(|scope| {
+            for name in ["first.esp", "second.esp"] {
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    let _context = FileContext::enter(Path::new(name), 1);
+                    // Both contexts are entered before either is checked
+                    barrier.wait();
+                    assert_eq!(current_file(), Some(PathBuf::from(name)));
+                    barrier.wait();
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_parallel_lines_name_their_own_file() {
+        let barrier = Barrier::new(2);
+        let labels = Mutex::new(Vec::new());
+        thread::scope(|scope| {
+            for (number, name) in [(1, "first.esp"), (2, "second.esp")] {
+                let (barrier, labels) = (&barrier, &labels);
+                scope.spawn(move || {
+                    let _context = FileContext::enter(Path::new(name), number);
+                    // Each thread labels a line of every phase while both
+                    // policies are in flight
+                    for _ in 0..3 {
+                        barrier.wait();
+                        labels.lock().unwrap().push((name, file_label()));
+                    }
+                    barrier.wait();
+                });
+            }
+        });
+
+        let labels = labels.into_inner().unwrap();
+        assert_eq!(labels.len(), 6);
+        for (name, label) in labels {
+            assert_eq!(label.as_deref(), Some(name));
+        }
+    }
+
+    #[test]
+    fn test_nested_context_restores_outer() {
+        thread::spawn(|| {
+            let outer = FileContext::enter(Path::new("outer.esp"), 1);
+            let inner = FileContext::enter(Path::new("inner.esp"), 1);
+            assert_eq!(file_label().as_deref(), Some("inner.esp"));
+            drop(inner);
+            assert_eq!(file_label().as_deref(), Some("outer.esp"));
+            drop(outer);
+            assert_eq!(file_label(), None);
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_context_ends_on_drop() {
+        thread::spawn(|| {
+            assert_eq!(current_file(), None);
+            let context = FileContext::enter(Path::new("a.esp"), 1);
+            assert_eq!(current_file(), Some(PathBuf::from("a.esp")));
+            assert_eq!(file_label().as_deref(), Some("a.esp"));
+            drop(context);
+            assert_eq!(current_file(), None);
+            assert_eq!(file_label(), None);
+        })
+        .join()
+        .unwrap();
+    }
+}
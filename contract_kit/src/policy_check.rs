@@ -0,0 +1,568 @@
+//! # Policy Pre-flight Checks
+//!
+//! Cross-checks a policy against the contracts of its CTN types without
+//! collecting or executing anything. A misspelled state field or an
+//! operation a field does not support otherwise surfaces only at execution
+//! time, as a per-criterion error on a real host; checking in CI catches it
+//! while the policy is being written.
+//!
+//! For every `CTN` block, the states and objects it uses (by `STATE_REF`,
+//! `OBJECT_REF` or inline) are checked against the registered contract:
+//!
+//! - Each state field must be a state field of the contract
+//! - Each field's operator must be one of the field's allowed operations
+//! - Each object must set the contract's required object fields
+//!
+//! Only block structure and the leading tokens of field lines are
+//! inspected; the compiler validates everything else. Operators without a
+//! contract equivalent (`ieq`, `not_starts`, ...) and objects built from
+//! sets are not checked.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+use execution_engine::strategies::{CtnContract, CtnStrategyRegistry};
+use execution_engine::types::common::Operation;
+
+/// ESP operators with a contract `Operation`, in display order
+const OPERATORS: &[&str] = &[
+    "=",
+    "!=",
+    ">",
+    "<",
+    ">=",
+    "<=",
+    "contains",
+    "not_contains",
+    "starts",
+    "ends",
+    "pattern_match",
+];
+
+/// Largest edit distance for a "did you mean" suggestion
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// What is wrong with a policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyWarningKind {
+    /// No contract is registered for the CTN type
+    UnknownCtnType,
+    /// A state field the contract does not define
+    UnknownField {
+        state: String,
+        field: String,
+        /// Closest field name the contract does define
+        suggestion: Option<String>,
+    },
+    /// An operator the contract does not allow for the field
+    DisallowedOperation {
+        state: String,
+        field: String,
+        operation: String,
+        allowed: Vec<String>,
+    },
+    /// An object does not set a field the contract requires
+    MissingObjectField { object: String, field: String },
+}
+
+/// A problem found by `validate_policy`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyWarning {
+    /// 1-based line of the offending field, object or `CTN`
+    pub line: usize,
+    pub ctn_type: String,
+    pub kind: PolicyWarningKind,
+}
+
+impl std::fmt::Display for PolicyWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            PolicyWarningKind::UnknownCtnType => {
+                write!(f, "CTN type '{}' has no registered contract", self.ctn_type)
+            }
+            PolicyWarningKind::UnknownField {
+                state,
+                field,
+                suggestion,
+            } => {
+                write!(
+                    f,
+                    "state '{}': '{}' is not a {} field",
+                    state, field, self.ctn_type
+                )?;
+                match suggestion {
+                    Some(suggestion) => write!(f, " (did you mean '{}'?)", suggestion),
+                    None => Ok(()),
+                }
+            }
+            PolicyWarningKind::DisallowedOperation {
+                state,
+                field,
+                operation,
+                allowed,
+            } => write!(
+                f,
+                "state '{}': {} field '{}' does not allow '{}' (allowed: {})",
+                state,
+                self.ctn_type,
+                field,
+                operation,
+                allowed.join(", ")
+            ),
+            PolicyWarningKind::MissingObjectField { object, field } => write!(
+                f,
+                "object '{}' does not set required {} field '{}'",
+                object, self.ctn_type, field
+            ),
+        }
+    }
+}
+
+/// A field line of a `STATE` or `OBJECT` block
+struct FieldLine {
+    line: usize,
+    name: String,
+    /// Operator, for state fields
+    operation: Option<String>,
+}
+
+/// A `STATE` or `OBJECT` block
+struct Block {
+    line: usize,
+    name: String,
+    fields: Vec<FieldLine>,
+    /// Fields come from a set, so they cannot be checked
+    opaque: bool,
+}
+
+/// A `CTN` block with the blocks it uses
+#[derive(Default)]
+struct Criterion {
+    line: usize,
+    ctn_type: String,
+    state_refs: Vec<String>,
+    object_refs: Vec<String>,
+    states: Vec<Block>,
+    objects: Vec<Block>,
+}
+
+/// Block structure of a policy
+#[derive(Default)]
+struct PolicyBlocks {
+    states: HashMap<String, Block>,
+    objects: HashMap<String, Block>,
+    criteria: Vec<Criterion>,
+}
+
+/// Check a policy's criteria against the registry's contracts
+///
+/// Returns warnings in policy order; an empty list means no problems were
+/// found. Nothing is collected or executed.
+pub fn validate_policy(source: &str, registry: &CtnStrategyRegistry) -> Vec<PolicyWarning> {
+    check_policy(source, |ctn_type| registry.get_ctn_contract(ctn_type).ok())
+}
+
+/// Check a policy's criteria against the contracts `contract_for` returns
+fn check_policy<C: Borrow<CtnContract>>(
+    source: &str,
+    contract_for: impl Fn(&str) -> Option<C>,
+) -> Vec<PolicyWarning> {
+    let blocks = parse_blocks(source);
+    let mut warnings: Vec<PolicyWarning> = Vec::new();
+
+    for criterion in &blocks.criteria {
+        let Some(contract) = contract_for(&criterion.ctn_type) else {
+            warnings.push(PolicyWarning {
+                line: criterion.line,
+                ctn_type: criterion.ctn_type.clone(),
+                kind: PolicyWarningKind::UnknownCtnType,
+            });
+            continue;
+        };
+        let contract = contract.borrow();
+
+        let states = criterion
+            .state_refs
+            .iter()
+            .filter_map(|name| blocks.states.get(name))
+            .chain(&criterion.states);
+        for state in states {
+            for field in &state.fields {
+                if let Some(kind) = check_state_field(contract, state, field) {
+                    push_unique(&mut warnings, field.line, &criterion.ctn_type, kind);
+                }
+            }
+        }
+
+        let objects = criterion
+            .object_refs
+            .iter()
+            .filter_map(|name| blocks.objects.get(name))
+            .chain(&criterion.objects);
+        for object in objects.filter(|o| !o.opaque) {
+            for required in &contract.object_requirements.required_fields {
+                if !object.fields.iter().any(|f| f.name == required.name) {
+                    let kind = PolicyWarningKind::MissingObjectField {
+                        object: object.name.clone(),
+                        field: required.name.clone(),
+                    };
+                    push_unique(&mut warnings, object.line, &criterion.ctn_type, kind);
+                }
+            }
+        }
+    }
+
+    warnings.sort_by_key(|w| w.line);
+    warnings
+}
+
+/// Add a warning unless a shared block already produced it
+fn push_unique(
+    warnings: &mut Vec<PolicyWarning>,
+    line: usize,
+    ctn_type: &str,
+    kind: PolicyWarningKind,
+) {
+    let warning = PolicyWarning {
+        line,
+        ctn_type: ctn_type.to_string(),
+        kind,
+    };
+    if !warnings.contains(&warning) {
+        warnings.push(warning);
+    }
+}
+
+/// Check one state field against the contract
+fn check_state_field(
+    contract: &CtnContract,
+    state: &Block,
+    field: &FieldLine,
+) -> Option<PolicyWarningKind> {
+    let requirements = &contract.state_requirements;
+    let mut specs = requirements
+        .required_fields
+        .iter()
+        .chain(&requirements.optional_fields);
+
+    let Some(spec) = specs.find(|s| s.name == field.name) else {
+        let known = requirements
+            .required_fields
+            .iter()
+            .chain(&requirements.optional_fields)
+            .map(|s| s.name.as_str());
+        return Some(PolicyWarningKind::UnknownField {
+            state: state.name.clone(),
+            field: field.name.clone(),
+            suggestion: closest(&field.name, known),
+        });
+    };
+
+    let symbol = field.operation.as_deref()?;
+    let operation = parse_operation(symbol)?;
+    if spec.allowed_operations.is_empty() || spec.allowed_operations.contains(&operation) {
+        return None;
+    }
+    Some(PolicyWarningKind::DisallowedOperation {
+        state: state.name.clone(),
+        field: field.name.clone(),
+        operation: symbol.to_string(),
+        allowed: OPERATORS
+            .iter()
+            .filter(|s| parse_operation(s).is_some_and(|op| spec.allowed_operations.contains(&op)))
+            .map(|s| s.to_string())
+            .collect(),
+    })
+}
+
+/// Contract operation of an ESP operator, if it has one
+fn parse_operation(symbol: &str) -> Option<Operation> {
+    match symbol {
+        "=" => Some(Operation::Equals),
+        "!=" => Some(Operation::NotEqual),
+        ">" => Some(Operation::GreaterThan),
+        "<" => Some(Operation::LessThan),
+        ">=" => Some(Operation::GreaterThanOrEqual),
+        "<=" => Some(Operation::LessThanOrEqual),
+        "contains" => Some(Operation::Contains),
+        "not_contains" => Some(Operation::NotContains),
+        "starts" => Some(Operation::StartsWith),
+        "ends" => Some(Operation::EndsWith),
+        "pattern_match" | "matches" => Some(Operation::PatternMatch),
+        _ => None,
+    }
+}
+
+/// Known name closest to `name`, if it is a likely misspelling
+fn closest<'a>(name: &str, known: impl Iterator<Item = &'a str>) -> Option<String> {
+    known
+        .map(|k| (edit_distance(name, k), k))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, k)| k.to_string())
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = previous
+                .get(j)
+                .map_or(usize::MAX, |d| d + usize::from(ca != *cb));
+            let delete = previous.get(j + 1).map_or(usize::MAX, |d| d + 1);
+            let insert = current.last().map_or(usize::MAX, |d| d + 1);
+            current.push(substitute.min(delete).min(insert));
+        }
+        previous = current;
+    }
+
+    previous.last().copied().unwrap_or_default()
+}
+
+/// Collect the `STATE`, `OBJECT` and `CTN` blocks of a policy
+fn parse_blocks(source: &str) -> PolicyBlocks {
+    let mut blocks = PolicyBlocks::default();
+    let mut criterion: Option<Criterion> = None;
+    let mut state: Option<Block> = None;
+    let mut object: Option<Block> = None;
+    let mut in_record = false;
+
+    for (index, raw) in source.lines().enumerate() {
+        let line = index + 1;
+        let mut tokens = raw.split_whitespace();
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+        if keyword.starts_with('#') {
+            continue;
+        }
+        let name = tokens.next().unwrap_or_default().to_string();
+
+        if let Some(current) = state.as_mut() {
+            match keyword {
+                "STATE_END" => {
+                    let Some(done) = state.take() else { continue };
+                    match criterion.as_mut() {
+                        Some(c) => c.states.push(done),
+                        None => {
+                            blocks.states.insert(done.name.clone(), done);
+                        }
+                    }
+                }
+                "record" => {
+                    in_record = true;
+                    current.fields.push(FieldLine {
+                        line,
+                        name: keyword.to_string(),
+                        operation: None,
+                    });
+                }
+                "record_end" => in_record = false,
+                _ if in_record => {}
+                "SET_REF" => current.opaque = true,
+                _ if is_field_name(keyword) => current.fields.push(FieldLine {
+                    line,
+                    name: keyword.to_string(),
+                    operation: tokens.next().map(str::to_string),
+                }),
+                _ => {}
+            }
+            continue;
+        }
+
+        if let Some(current) = object.as_mut() {
+            match keyword {
+                "OBJECT_END" => {
+                    let Some(done) = object.take() else { continue };
+                    match criterion.as_mut() {
+                        Some(c) => c.objects.push(done),
+                        None => {
+                            blocks.objects.insert(done.name.clone(), done);
+                        }
+                    }
+                }
+                "SET_REF" => current.opaque = true,
+                _ if is_field_name(keyword) => current.fields.push(FieldLine {
+                    line,
+                    name: keyword.to_string(),
+                    operation: None,
+                }),
+                _ => {}
+            }
+            continue;
+        }
+
+        match keyword {
+            "STATE" => state = Some(Block::new(line, name)),
+            "OBJECT" => object = Some(Block::new(line, name)),
+            "CTN" => {
+                criterion = Some(Criterion {
+                    line,
+                    ctn_type: name,
+                    ..Criterion::default()
+                });
+            }
+            "STATE_REF" => {
+                if let Some(c) = criterion.as_mut() {
+                    c.state_refs.push(name);
+                }
+            }
+            "OBJECT_REF" => {
+                if let Some(c) = criterion.as_mut() {
+                    c.object_refs.push(name);
+                }
+            }
+            "CTN_END" => {
+                if let Some(c) = criterion.take() {
+                    blocks.criteria.push(c);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+impl Block {
+    fn new(line: usize, name: String) -> Self {
+        Self {
+            line,
+            name,
+            fields: Vec::new(),
+            opaque: false,
+        }
+    }
+}
+
+/// Field names are lowercase; keywords (`BEHAVIOR`, `FILTER`) are not
+fn is_field_name(token: &str) -> bool {
+    token.chars().next().is_some_and(|c| c.is_ascii_lowercase()) && token != "behavior"
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::{create_file_metadata_contract, create_tcp_listener_contract};
+
+    fn check(source: &str) -> Vec<PolicyWarning> {
+        check_policy(source, |ctn_type| match ctn_type {
+            "file_metadata" => Some(create_file_metadata_contract()),
+            "tcp_listener" => Some(create_tcp_listener_contract()),
+            _ => None,
+        })
+    }
+
+    const VALID: &str = "\
+DEF
+    OBJECT shadow
+        path `/etc/shadow`
+    OBJECT_END
+
+    STATE locked_down
+        exists boolean = true
+        permissions string = `0640`
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF locked_down
+            OBJECT_REF shadow
+        CTN_END
+    CRI_END
+DEF_END
+";
+
+    #[test]
+    fn test_valid_policy_has_no_warnings() {
+        assert!(check(VALID).is_empty());
+    }
+
+    #[test]
+    fn test_disallowed_operation() {
+        let source = VALID.replace("exists boolean = true", "exists boolean contains true");
+        let warnings = check(&source);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, 7);
+        match &warnings[0].kind {
+            PolicyWarningKind::DisallowedOperation {
+                field,
+                operation,
+                allowed,
+                ..
+            } => {
+                assert_eq!(field, "exists");
+                assert_eq!(operation, "contains");
+                assert!(allowed.contains(&"=".to_string()));
+                assert!(!allowed.contains(&"contains".to_string()));
+            }
+            other => panic!("unexpected warning: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_misspelled_field_suggests_known_field() {
+        let source = VALID.replace("permissions string", "permisions string");
+        let warnings = check(&source);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].to_string(),
+            "line 8: state 'locked_down': 'permisions' is not a file_metadata field \
+             (did you mean 'permissions'?)"
+        );
+    }
+
+    #[test]
+    fn test_missing_object_field_and_unknown_type() {
+        let source = "\
+DEF
+    CRI AND
+        CTN tcp_listener
+            TEST all all
+            STATE open
+                listening boolean = true
+            STATE_END
+            OBJECT ssh
+                host `0.0.0.0`
+            OBJECT_END
+        CTN_END
+        CTN sysctl
+            TEST all all
+        CTN_END
+    CRI_END
+DEF_END
+";
+        let warnings = check(source);
+
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(
+            warnings[0].kind,
+            PolicyWarningKind::MissingObjectField {
+                object: "ssh".to_string(),
+                field: "port".to_string(),
+            }
+        );
+        assert_eq!(warnings[0].line, 8);
+        assert_eq!(warnings[1].kind, PolicyWarningKind::UnknownCtnType);
+        assert_eq!(warnings[1].ctn_type, "sysctl");
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("permisions", "permissions"), 1);
+        assert_eq!(edit_distance("owner", "owner"), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(closest("zzz", ["owner", "group"].into_iter()), None);
+    }
+}
@@ -0,0 +1,308 @@
+//! # Scan-Time Policy Variables
+//!
+//! Binds values supplied at scan time to a policy's `VAR` declarations, so
+//! one policy can be reused across environments (the expected NTP server,
+//! the approved admin group, a config directory).
+//!
+//! Binding rewrites the declarations in the policy source before it is
+//! compiled: a supplied value replaces the declared one, or fills a `VAR`
+//! declared without a value. Everything that references the variable then
+//! resolves to the supplied value as usual. Lines are never added or
+//! removed, so compiler diagnostics keep their line numbers.
+//!
+//! ## Values
+//!
+//! | Declared type | Supplied value |
+//! |---------------|----------------|
+//! | `int` | Integer (`15`) |
+//! | `float` | Number (`0.5`) |
+//! | `boolean` | `true` or `false` |
+//! | anything else | Used as a string literal; backticks are escaped |
+//!
+//! A supplied name that the policy does not declare is an error, so a typo
+//! cannot silently leave the policy's own value in place.
+
+use std::collections::HashMap;
+
+/// Error binding scan-time values to policy variables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VarBindingError {
+    /// A supplied variable is not declared in the policy
+    UnknownVar { name: String, declared: Vec<String> },
+    /// A supplied value does not fit the variable's declared type
+    InvalidValue {
+        name: String,
+        var_type: String,
+        value: String,
+    },
+}
+
+impl std::fmt::Display for VarBindingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownVar { name, declared } if declared.is_empty() => write!(
+                f,
+                "Variable '{}' is not declared in the policy (it declares no variables)",
+                name
+            ),
+            Self::UnknownVar { name, declared } => write!(
+                f,
+                "Variable '{}' is not declared in the policy (declared: {})",
+                name,
+                declared.join(", ")
+            ),
+            Self::InvalidValue {
+                name,
+                var_type,
+                value,
+            } => write!(
+                f,
+                "Value '{}' for variable '{}' is not a valid {}",
+                value, name, var_type
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VarBindingError {}
+
+/// A `VAR name type [value]` declaration
+struct Declaration<'a> {
+    /// Line up to and including the type
+    head: &'a str,
+    name: &'a str,
+    var_type: &'a str,
+}
+
+/// Split a declaration line, or `None` for any other line
+///
+/// References inside `RUN` blocks (`VAR base_dir`) have no type and are
+/// not declarations.
+fn parse_declaration(line: &str) -> Option<Declaration<'_>> {
+    let rest = line.trim_start().strip_prefix("VAR")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let after_var = rest.trim_start();
+    let name = after_var.split_whitespace().next()?;
+    let type_text = after_var.get(name.len()..)?.trim_start();
+    let var_type = type_text.split_whitespace().next()?;
+
+    // Head ends where the type ends
+    let head = line.get(..line.len() - type_text.len() + var_type.len())?;
+    Some(Declaration {
+        head,
+        name,
+        var_type,
+    })
+}
+
+/// Names of the variables declared in `source`, in declaration order
+pub fn declared_vars(source: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for_each_declaration(source, |_, declaration| {
+        if !names.iter().any(|n| n == declaration.name) {
+            names.push(declaration.name.to_string());
+        }
+    });
+    names
+}
+
+/// Call `visit` with the index and declaration of every declaration line
+fn for_each_declaration<'a>(source: &'a str, mut visit: impl FnMut(usize, Declaration<'a>)) {
+    let mut in_run = false;
+    for (index, line) in source.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') {
+            continue;
+        }
+        if trimmed == "RUN_END" {
+            in_run = false;
+        } else if trimmed.starts_with("RUN ") {
+            in_run = true;
+        } else if !in_run {
+            if let Some(declaration) = parse_declaration(line) {
+                visit(index, declaration);
+            }
+        }
+    }
+}
+
+/// ESP literal for `value` as a variable of `var_type`
+fn literal(name: &str, var_type: &str, value: &str) -> Result<String, VarBindingError> {
+    let invalid = || VarBindingError::InvalidValue {
+        name: name.to_string(),
+        var_type: var_type.to_string(),
+        value: value.to_string(),
+    };
+    if value.contains(['\n', '\r']) {
+        return Err(invalid());
+    }
+
+    let trimmed = value.trim();
+    match var_type {
+        "int" => trimmed
+            .parse::<i64>()
+            .map(|_| trimmed.to_string())
+            .map_err(|_| invalid()),
+        "float" => trimmed
+            .parse::<f64>()
+            .ok()
+            .filter(|f| f.is_finite())
+            .map(|_| trimmed.to_string())
+            .ok_or_else(invalid),
+        "boolean" => match trimmed {
+            "true" | "false" => Ok(trimmed.to_string()),
+            _ => Err(invalid()),
+        },
+        _ => Ok(format!("`{}`", value.replace('`', "``"))),
+    }
+}
+
+/// Rewrite the `VAR` declarations in `source` with the supplied values
+///
+/// # Returns
+/// * `Ok(String)` - The policy source with the values bound
+/// * `Err(VarBindingError)` - A name is not declared, or a value does not
+///   fit its variable's type
+pub fn bind_vars(source: &str, vars: &HashMap<String, String>) -> Result<String, VarBindingError> {
+    let declared = declared_vars(source);
+    let mut unknown: Vec<&String> = vars
+        .keys()
+        .filter(|name| !declared.contains(name))
+        .collect();
+    unknown.sort();
+    if let Some(name) = unknown.first() {
+        return Err(VarBindingError::UnknownVar {
+            name: name.to_string(),
+            declared,
+        });
+    }
+
+    let mut bound: HashMap<usize, String> = HashMap::new();
+    let mut error = None;
+    for_each_declaration(source, |index, declaration| {
+        let Some(value) = vars.get(declaration.name) else {
+            return;
+        };
+        match literal(declaration.name, declaration.var_type, value) {
+            Ok(literal) => {
+                bound.insert(index, format!("{} {}", declaration.head, literal));
+            }
+            Err(e) => {
+                error.get_or_insert(e);
+            }
+        }
+    });
+    if let Some(e) = error {
+        return Err(e);
+    }
+
+    let mut output = String::with_capacity(source.len());
+    for (index, line) in source.split_inclusive('\n').enumerate() {
+        match bound.get(&index) {
+            Some(replacement) => {
+                output.push_str(replacement);
+                // Keep the original line ending
+                let ending = line.trim_end_matches(['\n', '\r']).len();
+                output.push_str(line.get(ending..).unwrap_or_default());
+            }
+            None => output.push_str(line),
+        }
+    }
+    Ok(output)
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = "\
+DEF
+    # VAR commented string `ignored`
+    VAR config_dir string `/etc/app`
+    VAR min_length int 15
+    VAR base_dir string
+    VAR filename string `app.conf`
+
+    RUN full_path CONCAT
+        VAR base_dir
+        literal `/`
+        VAR filename
+    RUN_END
+
+    OBJECT app_config
+        path VAR config_dir
+    OBJECT_END
+DEF_END
+";
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_declared_vars_skip_references_and_comments() {
+        assert_eq!(
+            declared_vars(POLICY),
+            ["config_dir", "min_length", "base_dir", "filename"]
+        );
+    }
+
+    #[test]
+    fn test_bind_replaces_and_fills_values() {
+        let bound = bind_vars(
+            POLICY,
+            &vars(&[
+                ("config_dir", "/opt/app/etc"),
+                ("min_length", "20"),
+                ("base_dir", "/srv"),
+            ]),
+        )
+        .unwrap();
+
+        assert!(bound.contains("    VAR config_dir string `/opt/app/etc`\n"));
+        assert!(bound.contains("    VAR min_length int 20\n"));
+        assert!(bound.contains("    VAR base_dir string `/srv`\n"));
+        // Untouched lines, including references, stay as written
+        assert!(bound.contains("    VAR filename string `app.conf`\n"));
+        assert!(bound.contains("        VAR base_dir\n"));
+        assert_eq!(bound.lines().count(), POLICY.lines().count());
+    }
+
+    #[test]
+    fn test_string_values_escape_backticks() {
+        let bound = bind_vars(POLICY, &vars(&[("filename", "a`b.conf")])).unwrap();
+        assert!(bound.contains("VAR filename string `a``b.conf`"));
+    }
+
+    #[test]
+    fn test_unknown_var_is_an_error() {
+        let err = bind_vars(POLICY, &vars(&[("config_dri", "/opt")])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Variable 'config_dri' is not declared in the policy \
+             (declared: config_dir, min_length, base_dir, filename)"
+        );
+    }
+
+    #[test]
+    fn test_value_must_fit_type() {
+        let err = bind_vars(POLICY, &vars(&[("min_length", "fifteen")])).unwrap_err();
+        assert!(matches!(err, VarBindingError::InvalidValue { .. }));
+        assert!(bind_vars(POLICY, &vars(&[("config_dir", "/a\n/b")])).is_err());
+    }
+
+    #[test]
+    fn test_crlf_line_endings_are_kept() {
+        let source = "DEF\r\n    VAR port int 22\r\nDEF_END\r\n";
+        let bound = bind_vars(source, &vars(&[("port", "2222")])).unwrap();
+        assert_eq!(bound, "DEF\r\n    VAR port int 2222\r\nDEF_END\r\n");
+    }
+}
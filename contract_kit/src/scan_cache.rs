@@ -0,0 +1,184 @@
+//! # Compiled AST Cache
+//!
+//! `ScanCache` keeps the compiled AST of each scanned file so that repeated
+//! scans of an unchanged policy skip compilation. Entries are keyed by path
+//! and validated against the file's modification time and size on every
+//! lookup; any change to either recompiles the file.
+//!
+//! The cache is opt-in: callers own a `ScanCache` and pass it to
+//! `execution_api::scan_file_cached`. Failed compilations are not cached.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use common::ast::nodes::EspFile;
+
+use crate::execution_api::{compile_file, ScanError};
+
+/// File state a cached AST was compiled from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    fn read(path: &Path) -> Result<Self, ScanError> {
+        let metadata = std::fs::metadata(path)?;
+        Ok(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+struct CachedAst {
+    stamp: FileStamp,
+    ast: Arc<EspFile>,
+}
+
+/// Cache of compiled ASTs, shared across scans
+///
+/// Safe to share between threads. Entries for deleted files stay until
+/// `clear` is called.
+#[derive(Default)]
+pub struct ScanCache {
+    entries: Mutex<HashMap<PathBuf, CachedAst>>,
+    compiles: AtomicUsize,
+}
+
+impl ScanCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compiled AST for `path`, compiling only if the file changed
+    pub fn compile(&self, path: &Path) -> Result<Arc<EspFile>, ScanError> {
+        self.get_or_compile(path, |path| compile_file(path))
+    }
+
+    /// Number of compilations performed through this cache
+    pub fn compile_count(&self) -> usize {
+        self.compiles.load(Ordering::Relaxed)
+    }
+
+    /// Number of cached files
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|e| e.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every cached AST
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+
+    fn get_or_compile(
+        &self,
+        path: &Path,
+        compile: impl FnOnce(&Path) -> Result<EspFile, ScanError>,
+    ) -> Result<Arc<EspFile>, ScanError> {
+        let stamp = FileStamp::read(path)?;
+
+        if let Ok(entries) = self.entries.lock() {
+            if let Some(cached) = entries.get(path).filter(|c| c.stamp == stamp) {
+                return Ok(Arc::clone(&cached.ast));
+            }
+        }
+
+        // Compile outside the lock so other files are not blocked
+        self.compiles.fetch_add(1, Ordering::Relaxed);
+        let ast = Arc::new(compile(path)?);
+
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(
+                path.to_path_buf(),
+                CachedAst {
+                    stamp,
+                    ast: Arc::clone(&ast),
+                },
+            );
+        }
+        Ok(ast)
+    }
+}
+
+// ===== Tests =====
+
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Copy of a known-good policy that the test may modify
+    fn policy_copy(name: &str) -> PathBuf {
+        let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("../esp/test_file_metadata.esp");
+        let path =
+            std::env::temp_dir().join(format!("esp_cache_{}_{}.esp", std::process::id(), name));
+        std::fs::copy(source, &path).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_unchanged_file_is_not_recompiled() {
+        let path = policy_copy("unchanged");
+        let cache = ScanCache::new();
+
+        let first = cache.compile(&path).unwrap();
+        let second = cache.compile(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(cache.compile_count(), 1);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_touched_file_is_recompiled() {
+        let path = policy_copy("touched");
+        let cache = ScanCache::new();
+        cache.compile(&path).unwrap();
+
+        // Same content, newer modification time
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(modified + Duration::from_secs(60))
+            .unwrap();
+        drop(file);
+        cache.compile(&path).unwrap();
+        assert_eq!(cache.compile_count(), 2);
+
+        // Changed size
+        let mut source = std::fs::read_to_string(&path).unwrap();
+        source.push_str("\n# edited\n");
+        std::fs::write(&path, source).unwrap();
+        cache.compile(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(cache.compile_count(), 3);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_failed_compile_is_not_cached() {
+        let path = policy_copy("failed");
+        let cache = ScanCache::new();
+
+        let result = cache.get_or_compile(&path, |_| Err(ScanError::compilation_failed("bad")));
+        assert!(result.is_err());
+        assert!(cache.is_empty());
+
+        cache.compile(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(cache.compile_count(), 2);
+    }
+}